@@ -1,51 +1,96 @@
 use {
-    crate::audio_thread::{AudioBufferMut, AudioBufferOwned, AudioBufferRef, OneShot},
-    std::{path::PathBuf, sync::Arc},
+    crate::audio_thread::{
+        AudioBufferMut, AudioBufferOwned, AudioBufferRef, Interpolation, MIN_SPEED, NativeFormat,
+        OneShot, PlayHandle, channel_mix_gain, stereo_gains,
+    },
+    std::{
+        path::{Path, PathBuf},
+        sync::Arc,
+    },
     symphonia::core::{
         audio::Audio,
-        codecs::audio::AudioDecoderOptions,
-        formats::{FormatOptions, TrackType, probe::Hint},
+        codecs::audio::{AudioDecoder, AudioDecoderOptions},
+        formats::{FormatOptions, FormatReader, SeekMode, SeekTo, TrackType, probe::Hint},
         io::{MediaSource, MediaSourceStream},
         meta::MetadataOptions,
     },
 };
 
+mod streaming;
+pub use self::streaming::*;
+
 /// An error that might occur when loading a file as an [`AudioFile`].
 #[derive(Debug)]
 pub enum AudioFileError {
-    /// The file could not be opened in the first place.
+    /// The requested file does not exist.
+    NotFound(std::io::Error),
+    /// The file could not be read.
     Io(std::io::Error),
 
-    /// A loading error occured.
-    Loading(symphonia::core::errors::Error),
-
+    /// The file is not in a format that can be read, or it uses a codec that is not supported.
+    UnsupportedFormat(symphonia::core::errors::Error),
     /// No audio track was found in the file.
     NoAudioTrack,
     /// A track was found, but it cannot be played because no codec could be found to decode it.
     CodecNotFound,
+
+    /// The file is in a supported format, but its content could not be decoded. This usually
+    /// means that the file is corrupted.
+    Decode(symphonia::core::errors::Error),
 }
 
-impl From<symphonia::core::errors::Error> for AudioFileError {
-    #[inline]
-    fn from(err: symphonia::core::errors::Error) -> Self {
-        Self::Loading(err)
+impl AudioFileError {
+    /// Creates a new [`AudioFileError`] from an error that occured while decoding the content
+    /// of a file.
+    fn decode(err: symphonia::core::errors::Error) -> Self {
+        match err {
+            symphonia::core::errors::Error::IoError(err) => Self::from(err),
+            err => Self::Decode(err),
+        }
+    }
+
+    /// Creates a new [`AudioFileError`] from an error that occured while determining the format
+    /// of a file or creating a decoder for it.
+    fn unsupported(err: symphonia::core::errors::Error) -> Self {
+        match err {
+            symphonia::core::errors::Error::IoError(err) => Self::from(err),
+            err => Self::UnsupportedFormat(err),
+        }
+    }
+
+    /// Returns whether the error indicates that the file cannot be read because its format is
+    /// not supported.
+    ///
+    /// This is the case for [`UnsupportedFormat`](Self::UnsupportedFormat),
+    /// [`NoAudioTrack`](Self::NoAudioTrack) and [`CodecNotFound`](Self::CodecNotFound).
+    pub fn is_unsupported(&self) -> bool {
+        matches!(
+            self,
+            Self::UnsupportedFormat(_) | Self::NoAudioTrack | Self::CodecNotFound,
+        )
     }
 }
 
 impl From<std::io::Error> for AudioFileError {
     #[inline]
     fn from(err: std::io::Error) -> Self {
-        Self::Io(err)
+        if err.kind() == std::io::ErrorKind::NotFound {
+            Self::NotFound(err)
+        } else {
+            Self::Io(err)
+        }
     }
 }
 
 impl std::fmt::Display for AudioFileError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            Self::NotFound(_) => write!(f, "The file does not exist"),
             Self::Io(err) => std::fmt::Display::fmt(err, f),
-            Self::Loading(err) => std::fmt::Debug::fmt(err, f),
+            Self::UnsupportedFormat(err) => write!(f, "Unsupported audio format: {err}"),
             Self::NoAudioTrack => write!(f, "No audio track found in the file"),
             Self::CodecNotFound => write!(f, "No codec found to decode the audio track"),
+            Self::Decode(err) => write!(f, "Failed to decode the file: {err}"),
         }
     }
 }
@@ -53,10 +98,12 @@ impl std::fmt::Display for AudioFileError {
 impl std::error::Error for AudioFileError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
+            Self::NotFound(err) => Some(err),
             Self::Io(err) => Some(err),
-            Self::Loading(err) => Some(err),
+            Self::UnsupportedFormat(err) => Some(err),
             Self::NoAudioTrack => None,
             Self::CodecNotFound => None,
+            Self::Decode(err) => Some(err),
         }
     }
 }
@@ -67,30 +114,204 @@ pub struct AudioFile {
     data: AudioBufferOwned,
     /// The frame rate of the audio file.
     frame_rate: f64,
+    /// The format that the samples were encoded in, if it could be represented as an
+    /// [`advice::Format`].
+    ///
+    /// The samples are always converted to `f32` when loaded.
+    sample_format: Option<advice::Format>,
+
+    /// The peak amplitude of the samples, computed when the file is loaded.
+    ///
+    /// This is the peak of the original samples, before any gain has been applied.
+    peak: f32,
+    /// The gain that has been applied to the samples of the file.
+    gain: f32,
 }
 
 impl AudioFile {
-    /// Creates a new audio file with
+    /// Loads the audio file at the provided path.
+    ///
+    /// The format of the file is determined from its content (WAV, FLAC, OGG/Vorbis and MP3 are
+    /// supported). The extension of the file is only used as a hint.
     pub fn load(file: PathBuf) -> Result<Self, AudioFileError> {
-        Self::load_from_source(Box::new(std::fs::File::open(&file)?))
+        let extension = file.extension().and_then(|ext| ext.to_str());
+        Self::load_from_source(Box::new(std::fs::File::open(&file)?), extension)
+    }
+
+    /// Opens the audio file at the provided path for streaming.
+    ///
+    /// Unlike [`AudioFile::load`], the file is not loaded in memory entirely. It is decoded
+    /// progressively by a background thread while it plays, which is preferable for long files.
+    pub fn open_streaming(file: &Path) -> Result<StreamingAudioFile, AudioFileError> {
+        StreamingAudioFile::open(file)
     }
 
     /// Loads an [`AudioFile`] from an arbitrary media source.
-    pub fn load_from_source(source: Box<dyn MediaSource>) -> Result<Self, AudioFileError> {
+    ///
+    /// # Parameters
+    ///
+    /// - `source`: the media source to read the file from.
+    ///
+    /// - `extension`: the extension of the file, if known. It's only used as a hint, the format
+    ///   of the file is always determined from its content.
+    pub fn load_from_source(
+        source: Box<dyn MediaSource>,
+        extension: Option<&str>,
+    ) -> Result<Self, AudioFileError> {
+        let mut track = TrackDecoder::open(source, extension)?;
+
+        let mut data = AudioBufferOwned::new(track.channel_count);
+        while track.decode_next(&mut data)? {}
+
+        let peak = data.as_audio_buffer_ref().peak();
+
+        Ok(Self {
+            frame_rate: track.frame_rate,
+            sample_format: track.sample_format.flatten(),
+            data,
+            peak,
+            gain: 1.0,
+        })
+    }
+
+    /// Normalizes the audio file so that its peak amplitude reaches `target_peak`.
+    ///
+    /// The gain is computed from the peak that was measured when the file was loaded and is
+    /// applied directly to the samples. Calling this function multiple times does not accumulate
+    /// the gain.
+    ///
+    /// Silent files are left untouched.
+    pub fn normalize(&mut self, target_peak: f32) {
+        if self.peak <= 0.0 {
+            return;
+        }
+
+        let gain = target_peak / self.peak;
+        self.data.as_audio_buffer_mut().apply_gain(gain / self.gain);
+        self.gain = gain;
+    }
+
+    /// Normalizes the audio file so that its peak amplitude reaches `target_peak`.
+    ///
+    /// See [`normalize`](Self::normalize) for more information.
+    #[inline]
+    pub fn normalized(mut self, target_peak: f32) -> Self {
+        self.normalize(target_peak);
+        self
+    }
+
+    /// Returns the peak amplitude of the audio file, as it was when the file was loaded.
+    #[inline]
+    pub fn peak(&self) -> f32 {
+        self.peak
+    }
+
+    /// Returns the gain that has been applied to the samples of the audio file.
+    ///
+    /// This is `1.0` unless the file has been normalized.
+    #[inline]
+    pub fn gain(&self) -> f32 {
+        self.gain
+    }
+
+    /// Returns the data of the audio file.
+    #[inline]
+    pub fn data(&self) -> AudioBufferRef {
+        self.data.as_audio_buffer_ref()
+    }
+
+    /// Returns the frame rate of the audio file.
+    #[inline]
+    pub fn frame_rate(&self) -> f64 {
+        self.frame_rate
+    }
+
+    /// Returns the format that the samples of the file were encoded in.
+    ///
+    /// This is `None` if the file contains no samples.
+    #[inline]
+    pub fn sample_format(&self) -> Option<advice::Format> {
+        self.sample_format
+    }
+
+    /// Creates a new [`AudioFilePlayer`] instance that plays this audio file.
+    ///
+    /// See [`AudioFilePlayer::new`] for the meaning of the parameters.
+    pub fn player(self: &Arc<Self>, volume: f32, pan: f32) -> AudioFilePlayer {
+        AudioFilePlayer::new(self.clone(), volume, pan)
+    }
+
+    /// Plays the audio file at the center of the stereo field.
+    ///
+    /// This is equivalent to `play_with(volume, 0.0)`.
+    #[inline]
+    pub fn play(self: &Arc<Self>, volume: f32) -> PlayHandle {
+        self.play_with(volume, 0.0)
+    }
+
+    /// Plays the audio file at the provided position in the stereo field.
+    ///
+    /// The returned [`PlayHandle`] can be used to stop the file early. Dropping it lets the file
+    /// play until the end.
+    ///
+    /// See [`AudioFilePlayer::new`] for the meaning of the parameters.
+    pub fn play_with(self: &Arc<Self>, volume: f32, pan: f32) -> PlayHandle {
+        crate::audio_thread::one_shot_controls().play(self.player(volume, pan))
+    }
+}
+
+impl std::fmt::Debug for AudioFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AudioFile")
+            .field("frame_rate", &self.frame_rate)
+            .field("peak", &self.peak)
+            .field("gain", &self.gain)
+            .finish_non_exhaustive()
+    }
+}
+
+/// An audio track that is being decoded.
+struct TrackDecoder {
+    /// The format reader responsible for reading packets from the media source.
+    format: Box<dyn FormatReader>,
+    /// The decoder responsible for decoding the packets of the track.
+    decoder: Box<dyn AudioDecoder>,
+    /// The ID of the track being decoded.
+    track_id: u32,
+
+    /// The number of channels of the track.
+    channel_count: usize,
+    /// The frame rate of the track.
+    frame_rate: f64,
+    /// The format that the samples of the track are encoded in.
+    ///
+    /// This is `None` until the first packet has been decoded, and `Some(None)` if the format
+    /// cannot be represented as an [`advice::Format`].
+    sample_format: Option<Option<advice::Format>>,
+}
+
+impl TrackDecoder {
+    /// Probes the provided media source and creates a decoder for its audio track.
+    ///
+    /// See [`AudioFile::load_from_source`] for the meaning of the parameters.
+    fn open(source: Box<dyn MediaSource>, extension: Option<&str>) -> Result<Self, AudioFileError> {
         //
         // Probe the input media source for the file format that we're dealing with.
         //
 
-        let mut format = symphonia::default::get_probe().probe(
-            Hint::new()
-                .with_extension("wav")
-                .with_extension("flac")
-                .with_extension("ogg")
-                .with_extension("mp3"),
-            MediaSourceStream::new(source, Default::default()),
-            FormatOptions::default(),
-            MetadataOptions::default(),
-        )?;
+        let mut hint = Hint::new();
+        if let Some(extension) = extension {
+            hint.with_extension(extension);
+        }
+
+        let format = symphonia::default::get_probe()
+            .probe(
+                &hint,
+                MediaSourceStream::new(source, Default::default()),
+                FormatOptions::default(),
+                MetadataOptions::default(),
+            )
+            .map_err(AudioFileError::unsupported)?;
 
         // TODO: Remove this. It just want to be notified when a file has multiple tracks to decide
         // how to handle that case.
@@ -113,70 +334,103 @@ impl AudioFile {
             .audio()
             .ok_or(AudioFileError::CodecNotFound)?;
 
-        // TODO: Determine in which case those informations are not available.
-        let channel_count = audio_codec_params.channels.as_ref().unwrap().count();
-        let frame_rate = audio_codec_params.sample_rate.unwrap() as f64;
-
-        let mut decoder = symphonia::default::get_codecs()
-            .make_audio_decoder(audio_codec_params, &AudioDecoderOptions::default())?;
-
-        let mut data = AudioBufferOwned::new(channel_count);
+        // Without those, the samples cannot be interpreted correctly.
+        let channel_count = audio_codec_params
+            .channels
+            .as_ref()
+            .ok_or(AudioFileError::CodecNotFound)?
+            .count();
+        let frame_rate = audio_codec_params
+            .sample_rate
+            .ok_or(AudioFileError::CodecNotFound)? as f64;
+
+        let decoder = symphonia::default::get_codecs()
+            .make_audio_decoder(audio_codec_params, &AudioDecoderOptions::default())
+            .map_err(AudioFileError::unsupported)?;
+
+        Ok(Self {
+            format,
+            decoder,
+            track_id,
+            channel_count,
+            frame_rate,
+            sample_format: None,
+        })
+    }
 
-        while let Some(packet) = format.next_packet()? {
-            // If the packet does not belong to the audio track we're interested in, skip it.
-            if packet.track_id() != track_id {
-                continue;
+    /// Decodes the next packet of the track and appends its samples to `data`.
+    ///
+    /// # Returns
+    ///
+    /// `false` if the end of the track has been reached.
+    fn decode_next(&mut self, data: &mut AudioBufferOwned) -> Result<bool, AudioFileError> {
+        let packet = loop {
+            match self.format.next_packet().map_err(AudioFileError::decode)? {
+                // If the packet does not belong to the audio track we're interested in, skip it.
+                Some(packet) if packet.track_id() != self.track_id => continue,
+                Some(packet) => break packet,
+                None => return Ok(false),
             }
+        };
+
+        // Decode the packet into audio samples.
+        let buf = self
+            .decoder
+            .decode(&packet)
+            .map_err(AudioFileError::decode)?;
+
+        use symphonia::core::audio::GenericAudioBufferRef;
+        self.sample_format.get_or_insert(match buf {
+            GenericAudioBufferRef::F32(_) => Some(advice::Format::F32),
+            GenericAudioBufferRef::F64(_) => Some(advice::Format::F64),
+            GenericAudioBufferRef::U8(_) => Some(advice::Format::U8),
+            GenericAudioBufferRef::U16(_) => Some(advice::Format::U16),
+            GenericAudioBufferRef::U24(_) => Some(advice::Format::U24),
+            GenericAudioBufferRef::U32(_) => Some(advice::Format::U32),
+            GenericAudioBufferRef::S8(_) => Some(advice::Format::I8),
+            GenericAudioBufferRef::S16(_) => Some(advice::Format::I16),
+            GenericAudioBufferRef::S24(_) => Some(advice::Format::I24),
+            GenericAudioBufferRef::S32(_) => Some(advice::Format::I32),
+        });
 
-            // Decode the packet into audio samples.
-            let buf = decoder.decode(&packet)?;
-
-            use symphonia::core::audio::GenericAudioBufferRef;
-            match buf {
-                GenericAudioBufferRef::F32(buf) => extend_copy(buf, &mut data),
-                GenericAudioBufferRef::F64(buf) => extend_convert(buf, &mut data),
-                GenericAudioBufferRef::U8(buf) => extend_convert(buf, &mut data),
-                GenericAudioBufferRef::U16(buf) => extend_convert(buf, &mut data),
-                GenericAudioBufferRef::U24(buf) => extend_convert(buf, &mut data),
-                GenericAudioBufferRef::U32(buf) => extend_convert(buf, &mut data),
-                GenericAudioBufferRef::S8(buf) => extend_convert(buf, &mut data),
-                GenericAudioBufferRef::S16(buf) => extend_convert(buf, &mut data),
-                GenericAudioBufferRef::S24(buf) => extend_convert(buf, &mut data),
-                GenericAudioBufferRef::S32(buf) => extend_convert(buf, &mut data),
-            }
+        match buf {
+            GenericAudioBufferRef::F32(buf) => extend_copy(buf, data),
+            GenericAudioBufferRef::F64(buf) => extend_convert(buf, data),
+            GenericAudioBufferRef::U8(buf) => extend_convert(buf, data),
+            GenericAudioBufferRef::U16(buf) => extend_convert(buf, data),
+            GenericAudioBufferRef::U24(buf) => extend_convert(buf, data),
+            GenericAudioBufferRef::U32(buf) => extend_convert(buf, data),
+            GenericAudioBufferRef::S8(buf) => extend_convert(buf, data),
+            GenericAudioBufferRef::S16(buf) => extend_convert(buf, data),
+            GenericAudioBufferRef::S24(buf) => extend_convert(buf, data),
+            GenericAudioBufferRef::S32(buf) => extend_convert(buf, data),
         }
 
-        Ok(Self { frame_rate, data })
-    }
-
-    /// Returns the data of the audio file.
-    #[inline]
-    pub fn data(&self) -> AudioBufferRef {
-        self.data.as_audio_buffer_ref()
-    }
-
-    /// Returns the frame rate of the audio file.
-    #[inline]
-    pub fn frame_rate(&self) -> f64 {
-        self.frame_rate
-    }
-
-    /// Creates a new [`AudioFilePlayer`] instance that plays this audio file.
-    pub fn player(self: &Arc<Self>, volume: f32) -> AudioFilePlayer {
-        AudioFilePlayer::new(self.clone(), volume)
-    }
-
-    /// Plays the audio file.
-    pub fn play(self: &Arc<Self>, volume: f32) {
-        crate::audio_thread::one_shot_controls().play(self.player(volume));
+        Ok(true)
     }
-}
 
-impl std::fmt::Debug for AudioFile {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("AudioFile")
-            .field("frame_rate", &self.frame_rate)
-            .finish_non_exhaustive()
+    /// Seeks the track to the provided frame.
+    ///
+    /// # Returns
+    ///
+    /// The number of frames that must be skipped after the next decoded packets to actually
+    /// reach the requested frame. Packets usually start before the requested position.
+    fn seek(&mut self, frame: u64) -> Result<u64, AudioFileError> {
+        // The timestamps of audio tracks are expressed in frames.
+        let seeked = self
+            .format
+            .seek(
+                SeekMode::Accurate,
+                SeekTo::TimeStamp {
+                    ts: frame,
+                    track_id: self.track_id,
+                },
+            )
+            .map_err(AudioFileError::decode)?;
+
+        self.decoder.reset();
+
+        Ok(seeked.required_ts.saturating_sub(seeked.actual_ts))
     }
 }
 
@@ -217,35 +471,207 @@ where
 pub struct AudioFilePlayer {
     /// The file to play.
     file: Arc<AudioFile>,
-    /// The current frame index.
-    next_index: usize,
+    /// The position of the next frame to read, in frames of the file.
+    ///
+    /// This is fractional when the file is resampled to the frame rate of the stream.
+    position: f64,
+    /// The interpolation used when the file is resampled.
+    interpolation: Interpolation,
+    /// The speed at which the file is played back.
+    ///
+    /// This is applied on top of the ratio between the frame rate of the file and the one of
+    /// the stream.
+    speed: f64,
     /// The volume at which to play the file.
     volume: f32,
+    /// The gains applied to the channels of the file when the output is stereo.
+    ///
+    /// Those are computed once when the player is created so that the audio thread does not
+    /// have to evaluate the pan law.
+    stereo_gains: [f32; 2],
 }
 
 impl AudioFilePlayer {
     /// Creates a new [`PlayAudioFile`] instance.
+    ///
+    /// # Parameters
+    ///
+    /// - `volume`: The gain applied to the samples of the file.
+    ///
+    /// - `pan`: The position of the file in the stereo field, from `-1.0` (hard left) to `1.0`
+    ///   (hard right). See [`stereo_gains`] for how it is applied. This has no effect when the
+    ///   output is not stereo.
     #[inline]
-    pub fn new(file: Arc<AudioFile>, volume: f32) -> Self {
+    pub fn new(file: Arc<AudioFile>, volume: f32, pan: f32) -> Self {
+        let stereo_gains = stereo_gains(file.data().channel_count(), volume, pan);
+
         Self {
             file,
-            next_index: 0,
+            position: 0.0,
+            interpolation: Interpolation::default(),
+            speed: 1.0,
             volume,
+            stereo_gains,
+        }
+    }
+
+    /// Sets the interpolation used when the frame rate of the file differs from the frame rate of
+    /// the stream.
+    pub fn with_interpolation(mut self, interpolation: Interpolation) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+
+    /// Sets the speed at which the file is played back.
+    ///
+    /// A speed of `2.0` plays the file twice as fast, an octave up. The speed is clamped to
+    /// [`MIN_SPEED`].
+    pub fn with_speed(mut self, speed: f64) -> Self {
+        self.set_speed(speed);
+        self
+    }
+
+    /// Returns the gain applied to the samples added to the provided output channel, before the
+    /// channels of the file are mixed into it.
+    fn output_gain(&self, output_channel_count: usize, channel: usize) -> f32 {
+        if output_channel_count == 2 {
+            self.stereo_gains[channel]
+        } else {
+            self.volume
         }
     }
 }
 
 impl OneShot for AudioFilePlayer {
-    fn fill_buffer(&mut self, _frame_rate: f64, mut buf: AudioBufferMut) -> bool {
-        assert_eq!(buf.channel_count(), self.file.data().channel_count());
-
-        for (dst_channel, src) in buf.channels_mut().zip(self.file.data().channels()) {
-            for (dst, sample) in dst_channel.iter_mut().zip(src.iter().skip(self.next_index)) {
-                *dst += *sample * self.volume;
+    fn fill_buffer(&mut self, frame_rate: f64, mut buf: AudioBufferMut) -> bool {
+        let data = self.file.data();
+        let output_channel_count = buf.channel_count();
+        let src_channel_count = data.channel_count();
+
+        // The number of frames of the file that are consumed for every frame of the stream.
+        let rate_ratio = self.file.frame_rate() / frame_rate;
+        let step = rate_ratio * self.speed;
+        let resampling = step != 1.0 || self.position.fract() != 0.0;
+
+        for (channel, dst_channel) in buf.channels_mut().enumerate() {
+            let output_gain = self.output_gain(output_channel_count, channel);
+
+            for (src_channel, src) in data.channels().enumerate() {
+                let gain = channel_mix_gain(
+                    src_channel_count,
+                    output_channel_count,
+                    src_channel,
+                    channel,
+                ) * output_gain;
+                if gain == 0.0 {
+                    continue;
+                }
+
+                if resampling {
+                    for (i, dst) in dst_channel.iter_mut().enumerate() {
+                        let pos = self.position + i as f64 * step;
+                        *dst += self.interpolation.sample(src, pos) * gain;
+                    }
+                } else {
+                    let skip = self.position as usize;
+                    for (dst, sample) in dst_channel.iter_mut().zip(src.iter().skip(skip)) {
+                        *dst += *sample * gain;
+                    }
+                }
             }
         }
 
-        self.next_index += buf.frame_count();
-        self.next_index < self.file.data().frame_count()
+        // The voice is removed once the read index has moved past the last frame of the file.
+        self.position += buf.frame_count() as f64 * step;
+        self.position < data.frame_count() as f64
+    }
+
+    fn set_speed(&mut self, speed: f64) -> bool {
+        self.speed = speed.max(MIN_SPEED);
+        true
+    }
+
+    fn native_format(&self) -> Option<NativeFormat> {
+        // Any gain would modify the original samples.
+        if self.volume != 1.0 || self.file.gain() != 1.0 {
+            return None;
+        }
+
+        // Changing the speed resamples the file.
+        if self.speed != 1.0 {
+            return None;
+        }
+
+        // Balancing a stereo file away from the center attenuates one of its channels.
+        if self.file.data().channel_count() == 2 && self.stereo_gains != [1.0; 2] {
+            return None;
+        }
+
+        Some(NativeFormat {
+            frame_rate: self.file.frame_rate(),
+            channel_count: self.file.data().channel_count(),
+            sample_format: self.file.sample_format()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::{AudioFile, AudioFilePlayer},
+        crate::audio_thread::{AudioBufferOwned, OneShot},
+        std::sync::Arc,
+    };
+
+    /// Creates a 48 kHz [`AudioFile`] with the provided channels.
+    fn file(channels: &[&[f32]]) -> Arc<AudioFile> {
+        let data = AudioBufferOwned::from_channels(channels);
+        let peak = data.as_audio_buffer_ref().peak();
+
+        Arc::new(AudioFile {
+            data,
+            frame_rate: 48000.0,
+            sample_format: None,
+            peak,
+            gain: 1.0,
+        })
+    }
+
+    /// Plays the whole file into a 48 kHz output with `channel_count` channels.
+    fn render(file: &Arc<AudioFile>, channel_count: usize) -> AudioBufferOwned {
+        let mut out = AudioBufferOwned::new(channel_count);
+        out.resize(file.data().frame_count(), 0.0);
+
+        let playing = AudioFilePlayer::new(file.clone(), 1.0, 0.0)
+            .fill_buffer(48000.0, out.as_audio_buffer_mut());
+        assert!(!playing);
+
+        out
+    }
+
+    #[test]
+    fn surround_file_plays_on_stereo_output() {
+        // L, R, C, LFE, Ls, Rs
+        let file = file(&[
+            &[1.0; 4], &[0.0; 4], &[0.0; 4], &[1.0; 4], &[0.0; 4], &[0.5; 4],
+        ]);
+        let out = render(&file, 2);
+
+        // The LFE channel is discarded, and the surround channels are folded in at -3 dB.
+        assert_eq!(out.channel(0).unwrap(), [1.0; 4]);
+        assert!(
+            out.channel(1)
+                .unwrap()
+                .iter()
+                .all(|s| (s - 0.5 * std::f32::consts::FRAC_1_SQRT_2).abs() < 1e-6)
+        );
+    }
+
+    #[test]
+    fn stereo_file_plays_on_mono_output() {
+        let file = file(&[&[1.0; 4], &[0.5; 4]]);
+        let out = render(&file, 1);
+
+        assert_eq!(out.channel(0).unwrap(), [0.75; 4]);
     }
 }
@@ -0,0 +1,608 @@
+use {
+    super::{AudioFileError, TrackDecoder},
+    crate::audio_thread::{
+        AudioBufferMut, AudioBufferOwned, Interpolation, MIN_SPEED, OneShot, PlayHandle,
+        RingConsumer, RingProducer, channel_mix_gain, ring_buffer, stereo_gains,
+    },
+    std::{
+        path::Path,
+        sync::{
+            Arc,
+            atomic::{AtomicBool, AtomicU64, Ordering},
+        },
+        thread::JoinHandle,
+        time::Duration,
+    },
+};
+
+/// The duration of audio that the ring buffer of a [`StreamingAudioFile`] can hold, in seconds.
+const RING_BUFFER_DURATION: f64 = 2.0;
+
+/// The number of frames that a [`StreamingAudioFilePlayer`] reads ahead of the ring buffer.
+///
+/// This bounds the number of frames resampled at once, not the number of frames that can be
+/// produced by a single call to [`OneShot::fill_buffer`].
+const WINDOW_CAPACITY: usize = 4096;
+
+/// The number of frames before the read position that [`Interpolation`] reads.
+const HISTORY_FRAMES: usize = 1;
+
+/// The number of frames after the read position that [`Interpolation`] reads.
+const LOOKAHEAD_FRAMES: usize = 2;
+
+/// The interval at which the I/O thread checks for new work when it has nothing to do.
+const IO_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// The state shared between the [`StreamingAudioFile`], its I/O thread and its player.
+#[derive(Default)]
+struct SharedState {
+    /// When set, the I/O thread should stop as soon as possible.
+    closing: AtomicBool,
+
+    /// Set by [`StreamingAudioFile::seek`] when a seek has been requested.
+    ///
+    /// The I/O thread automatically clears this flag to acknowledge the request.
+    seek_pending: AtomicBool,
+    /// The frame that the stream should seek to when `seek_pending` is set.
+    seek_target: AtomicU64,
+    /// Set by the I/O thread when the frames currently in the ring buffer are stale (because of
+    /// a seek) and must be discarded.
+    ///
+    /// The player automatically clears this flag once the frames have been discarded. The I/O
+    /// thread does not write new frames until then.
+    flush: AtomicBool,
+
+    /// Set by the I/O thread once the whole file has been decoded.
+    finished: AtomicBool,
+    /// The number of times the player ran out of frames before the end of the file.
+    underrun_count: AtomicU64,
+}
+
+/// An audio file that is decoded progressively while it plays, instead of being loaded in
+/// memory entirely.
+///
+/// A background I/O thread decodes the file ahead of the playback position and writes the
+/// frames to a ring buffer, which is read by the audio thread through a
+/// [`StreamingAudioFilePlayer`].
+pub struct StreamingAudioFile {
+    /// The state shared with the I/O thread and the player.
+    shared: Arc<SharedState>,
+    /// The reading half of the ring buffer, until a player is created.
+    consumer: Option<RingConsumer>,
+    /// The I/O thread decoding the file.
+    thread: Option<JoinHandle<()>>,
+
+    /// The number of channels of the file.
+    channel_count: usize,
+    /// The frame rate of the file.
+    frame_rate: f64,
+}
+
+impl StreamingAudioFile {
+    /// Opens the audio file at the provided path for streaming.
+    ///
+    /// The file is probed and its decoder created before this function returns, meaning that
+    /// unsupported files are reported immediately.
+    pub fn open(path: &Path) -> Result<Self, AudioFileError> {
+        let extension = path.extension().and_then(|ext| ext.to_str());
+        let track = TrackDecoder::open(Box::new(std::fs::File::open(path)?), extension)?;
+
+        let channel_count = track.channel_count;
+        let frame_rate = track.frame_rate;
+
+        let capacity = (frame_rate * RING_BUFFER_DURATION).ceil() as usize;
+        let (producer, consumer) = ring_buffer(channel_count, capacity.max(1));
+
+        let shared = Arc::new(SharedState::default());
+
+        let mut io_thread = IoThread {
+            shared: shared.clone(),
+            track,
+            producer,
+            pending: AudioBufferOwned::new(channel_count),
+            pending_offset: 0,
+            skip: 0,
+        };
+
+        let thread = std::thread::Builder::new()
+            .name("yadaw-audio-file-streaming-thread".into())
+            .spawn(move || io_thread.run())?;
+
+        Ok(Self {
+            shared,
+            consumer: Some(consumer),
+            thread: Some(thread),
+            channel_count,
+            frame_rate,
+        })
+    }
+
+    /// Returns the number of channels of the file.
+    #[inline]
+    pub fn channel_count(&self) -> usize {
+        self.channel_count
+    }
+
+    /// Returns the frame rate of the file.
+    #[inline]
+    pub fn frame_rate(&self) -> f64 {
+        self.frame_rate
+    }
+
+    /// Requests the stream to continue from the provided frame.
+    ///
+    /// This function returns immediately. The player outputs silence until the I/O thread has
+    /// decoded enough frames at the new position.
+    pub fn seek(&self, frame: u64) {
+        self.shared.seek_target.store(frame, Ordering::Relaxed);
+        self.shared.seek_pending.store(true, Ordering::Release);
+        self.wake_io_thread();
+    }
+
+    /// Returns the number of times the player ran out of decoded frames before the end of the
+    /// file.
+    ///
+    /// Like [`advice::Stream::underrun_count`], the counter is never reset.
+    #[inline]
+    pub fn underrun_count(&self) -> u64 {
+        self.shared.underrun_count.load(Ordering::Relaxed)
+    }
+
+    /// Creates the [`StreamingAudioFilePlayer`] that plays this file.
+    ///
+    /// # Returns
+    ///
+    /// A streaming file can only be played by a single player. `None` is returned if the player
+    /// has already been created.
+    ///
+    /// See [`AudioFilePlayer::new`] for the meaning of the parameters.
+    ///
+    /// [`AudioFilePlayer::new`]: super::AudioFilePlayer::new
+    pub fn player(&mut self, volume: f32, pan: f32) -> Option<StreamingAudioFilePlayer> {
+        let consumer = self.consumer.take()?;
+        let stereo_gains = stereo_gains(consumer.channel_count(), volume, pan);
+
+        let mut window = AudioBufferOwned::new(consumer.channel_count());
+        window.reserve(WINDOW_CAPACITY);
+
+        Some(StreamingAudioFilePlayer {
+            shared: self.shared.clone(),
+            consumer,
+            frame_rate: self.frame_rate,
+            window,
+            position: 0.0,
+            interpolation: Interpolation::default(),
+            speed: 1.0,
+            volume,
+            stereo_gains,
+        })
+    }
+
+    /// Plays the audio file at the center of the stereo field.
+    ///
+    /// This is equivalent to `play_with(volume, 0.0)`.
+    #[inline]
+    pub fn play(&mut self, volume: f32) -> Option<PlayHandle> {
+        self.play_with(volume, 0.0)
+    }
+
+    /// Plays the audio file at the provided position in the stereo field.
+    ///
+    /// This does nothing and returns `None` if the file is already being played.
+    ///
+    /// See [`AudioFilePlayer::new`] for the meaning of the parameters.
+    ///
+    /// [`AudioFilePlayer::new`]: super::AudioFilePlayer::new
+    pub fn play_with(&mut self, volume: f32, pan: f32) -> Option<PlayHandle> {
+        self.player(volume, pan)
+            .map(|player| crate::audio_thread::one_shot_controls().play(player))
+    }
+
+    /// Wakes the I/O thread up so that it notices new requests immediately.
+    fn wake_io_thread(&self) {
+        if let Some(thread) = self.thread.as_ref() {
+            thread.thread().unpark();
+        }
+    }
+}
+
+impl Drop for StreamingAudioFile {
+    fn drop(&mut self) {
+        self.shared.closing.store(true, Ordering::Relaxed);
+        self.wake_io_thread();
+
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl std::fmt::Debug for StreamingAudioFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StreamingAudioFile")
+            .field("channel_count", &self.channel_count)
+            .field("frame_rate", &self.frame_rate)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The state of the I/O thread of a [`StreamingAudioFile`].
+struct IoThread {
+    /// The state shared with the [`StreamingAudioFile`] and its player.
+    shared: Arc<SharedState>,
+    /// The decoder of the file.
+    track: TrackDecoder,
+    /// The writing half of the ring buffer.
+    producer: RingProducer,
+
+    /// The frames that have been decoded but not yet written to the ring buffer.
+    pending: AudioBufferOwned,
+    /// The index of the first frame of `pending` that has not been written to the ring buffer.
+    pending_offset: usize,
+    /// The number of decoded frames that must be dropped to reach the position requested by the
+    /// last seek.
+    skip: u64,
+}
+
+impl IoThread {
+    /// Runs the I/O thread until the [`StreamingAudioFile`] is dropped.
+    fn run(&mut self) {
+        while !self.shared.closing.load(Ordering::Relaxed) {
+            if self.shared.seek_pending.swap(false, Ordering::Acquire) {
+                self.seek();
+                continue;
+            }
+
+            if self.pending_offset < self.pending.frame_count() {
+                let written = self
+                    .producer
+                    .push(self.pending.as_audio_buffer_ref(), self.pending_offset);
+                self.pending_offset += written;
+
+                if written == 0 {
+                    // The ring buffer is full, wait for the player to consume some frames.
+                    std::thread::park_timeout(IO_POLL_INTERVAL);
+                }
+
+                continue;
+            }
+
+            if self.shared.finished.load(Ordering::Relaxed) {
+                std::thread::park_timeout(IO_POLL_INTERVAL);
+                continue;
+            }
+
+            self.decode_next();
+        }
+    }
+
+    /// Decodes the next packet of the file into the pending frames.
+    fn decode_next(&mut self) {
+        self.pending.clear();
+        self.pending_offset = 0;
+
+        match self.track.decode_next(&mut self.pending) {
+            Ok(true) => {
+                let skipped = self.skip.min(self.pending.frame_count() as u64);
+                self.skip -= skipped;
+                self.pending_offset = skipped as usize;
+            }
+            Ok(false) => self.shared.finished.store(true, Ordering::Release),
+            Err(err) => {
+                log::error!("Failed to decode streaming audio file: {err}");
+                self.shared.finished.store(true, Ordering::Release);
+            }
+        }
+    }
+
+    /// Seeks the file to the requested position, and waits for the player to discard the
+    /// frames that were decoded before the seek.
+    fn seek(&mut self) {
+        self.shared.flush.store(true, Ordering::Release);
+
+        self.pending.clear();
+        self.pending_offset = 0;
+        self.shared.finished.store(false, Ordering::Relaxed);
+
+        let target = self.shared.seek_target.load(Ordering::Relaxed);
+        match self.track.seek(target) {
+            Ok(skip) => self.skip = skip,
+            Err(err) => {
+                log::error!("Failed to seek streaming audio file: {err}");
+                self.skip = 0;
+            }
+        }
+
+        // Writing to the ring buffer before the player has discarded the stale frames would
+        // mix the old position with the new one.
+        while self.shared.flush.load(Ordering::Acquire) {
+            if self.shared.closing.load(Ordering::Relaxed)
+                || self.shared.seek_pending.load(Ordering::Relaxed)
+            {
+                return;
+            }
+
+            std::thread::park_timeout(IO_POLL_INTERVAL);
+        }
+    }
+}
+
+/// Plays a [`StreamingAudioFile`] on the audio thread.
+pub struct StreamingAudioFilePlayer {
+    /// The state shared with the [`StreamingAudioFile`] and its I/O thread.
+    shared: Arc<SharedState>,
+    /// The reading half of the ring buffer.
+    consumer: RingConsumer,
+    /// The frame rate of the file.
+    frame_rate: f64,
+
+    /// The frames that have been read from the ring buffer but not yet fully consumed.
+    ///
+    /// A few frames before the read position are kept so that the interpolation has some
+    /// history to work with. The capacity of this buffer is allocated when the player is
+    /// created and never grows.
+    window: AudioBufferOwned,
+    /// The position of the next frame to read, in frames of the file, relative to the first
+    /// frame of `window`.
+    position: f64,
+    /// The interpolation used when the file is resampled.
+    interpolation: Interpolation,
+    /// The speed at which the file is played back.
+    ///
+    /// See [`AudioFilePlayer::with_speed`](super::AudioFilePlayer::with_speed).
+    speed: f64,
+
+    /// The volume at which to play the file.
+    volume: f32,
+    /// The gains applied to the channels of the file when the output is stereo.
+    ///
+    /// See [`AudioFilePlayer::new`](super::AudioFilePlayer::new).
+    stereo_gains: [f32; 2],
+}
+
+impl StreamingAudioFilePlayer {
+    /// Sets the interpolation used when the frame rate of the file differs from the frame rate of
+    /// the stream.
+    pub fn with_interpolation(mut self, interpolation: Interpolation) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+
+    /// Sets the speed at which the file is played back.
+    ///
+    /// A speed of `2.0` plays the file twice as fast, an octave up. The speed is clamped to
+    /// [`MIN_SPEED`].
+    pub fn with_speed(mut self, speed: f64) -> Self {
+        self.set_speed(speed);
+        self
+    }
+
+    /// Returns the gain applied to the samples added to the provided output channel, before the
+    /// channels of the file are mixed into it.
+    fn output_gain(&self, output_channel_count: usize, channel: usize) -> f32 {
+        if output_channel_count == 2 {
+            self.stereo_gains[channel]
+        } else {
+            self.volume
+        }
+    }
+
+    /// Drops the frames of the window that the interpolation will not need anymore.
+    fn discard_consumed_frames(&mut self) {
+        let len = self.window.frame_count();
+        let consumed = (self.position.floor() as usize)
+            .saturating_sub(HISTORY_FRAMES)
+            .min(len);
+
+        if consumed == 0 {
+            return;
+        }
+
+        for channel in self.window.channels_mut() {
+            channel.copy_within(consumed.., 0);
+        }
+        self.window.truncate(len - consumed);
+        self.position -= consumed as f64;
+    }
+
+    /// Moves as many frames as possible from the ring buffer to the window.
+    fn refill_window(&mut self) {
+        let len = self.window.frame_count();
+        let free = self.window.capacity() - len;
+        if free == 0 {
+            return;
+        }
+
+        let count = self.consumer.available_frames().min(free);
+        // The capacity has been reserved up front, this does not allocate.
+        self.window.resize(len + count, 0.0);
+
+        let window = &mut self.window;
+        self.consumer.pop(count, |channel, frame, sample| {
+            if let Some(dst) = window.channel_mut(channel) {
+                dst[len + frame] = sample;
+            }
+        });
+    }
+
+    /// Adds the resampled frames of the window to `count` frames of `buf`, starting at the
+    /// frame `offset`.
+    fn render(&self, buf: &mut AudioBufferMut, offset: usize, count: usize, step: f64) {
+        let output_channel_count = buf.channel_count();
+        let src_channel_count = self.window.channel_count();
+
+        for (channel, dst_channel) in buf.channels_mut().enumerate() {
+            let output_gain = self.output_gain(output_channel_count, channel);
+            let dst_channel = &mut dst_channel[offset..offset + count];
+
+            for (src_channel, src) in self.window.channels().enumerate() {
+                let gain = channel_mix_gain(
+                    src_channel_count,
+                    output_channel_count,
+                    src_channel,
+                    channel,
+                ) * output_gain;
+                if gain == 0.0 {
+                    continue;
+                }
+
+                for (i, dst) in dst_channel.iter_mut().enumerate() {
+                    let pos = self.position + i as f64 * step;
+                    *dst += self.interpolation.sample(src, pos) * gain;
+                }
+            }
+        }
+    }
+}
+
+impl OneShot for StreamingAudioFilePlayer {
+    fn fill_buffer(&mut self, frame_rate: f64, mut buf: AudioBufferMut) -> bool {
+        if self.shared.flush.load(Ordering::Acquire) {
+            // The stream is seeking, the frames in the ring buffer are stale.
+            self.consumer.clear();
+            self.window.clear();
+            self.position = 0.0;
+            self.shared.flush.store(false, Ordering::Release);
+            return true;
+        }
+
+        // The number of frames of the file that are consumed for every frame of the stream.
+        let step = self.frame_rate / frame_rate * self.speed;
+        let requested = buf.frame_count();
+        let mut written = 0;
+
+        loop {
+            self.discard_consumed_frames();
+
+            // Once the I/O thread is done, the frames that remain in the ring buffer are the
+            // last ones of the file.
+            let finished = self.shared.finished.load(Ordering::Acquire);
+            self.refill_window();
+            let drained = finished && self.consumer.available_frames() == 0;
+
+            // Until the end of the file, the interpolation needs a few frames past the read
+            // position.
+            let len = self.window.frame_count() as f64;
+            let limit = if drained {
+                len
+            } else {
+                len - LOOKAHEAD_FRAMES as f64
+            };
+
+            let count = if self.position < limit {
+                (((limit - self.position) / step).ceil() as usize).min(requested - written)
+            } else {
+                0
+            };
+
+            if count == 0 {
+                if drained {
+                    return false;
+                }
+
+                if self.window.frame_count() == self.window.capacity() {
+                    // The read position is past the whole window (very high speeds), keep
+                    // skipping frames.
+                    continue;
+                }
+
+                self.shared.underrun_count.fetch_add(1, Ordering::Relaxed);
+                return true;
+            }
+
+            self.render(&mut buf, written, count, step);
+            self.position += count as f64 * step;
+            written += count;
+
+            if written == requested {
+                return !drained || self.position < len;
+            }
+        }
+    }
+
+    fn set_speed(&mut self, speed: f64) -> bool {
+        self.speed = speed.max(MIN_SPEED);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::{SharedState, StreamingAudioFile, StreamingAudioFilePlayer},
+        crate::audio_thread::{AudioBufferOwned, OneShot, ring_buffer},
+        std::sync::{Arc, atomic::Ordering},
+    };
+
+    /// Creates the player of a fully decoded 48 kHz mono file containing `frames`.
+    fn player(frames: &[f32]) -> StreamingAudioFilePlayer {
+        let (mut producer, consumer) = ring_buffer(1, frames.len());
+        let src = AudioBufferOwned::from_channels(&[frames]);
+        assert_eq!(producer.push(src.as_audio_buffer_ref(), 0), frames.len());
+
+        let shared = Arc::new(SharedState::default());
+        shared.finished.store(true, Ordering::Relaxed);
+
+        let mut file = StreamingAudioFile {
+            shared,
+            consumer: Some(consumer),
+            thread: None,
+            channel_count: 1,
+            frame_rate: 48000.0,
+        };
+        file.player(1.0, 0.0).unwrap()
+    }
+
+    /// Returns a mono file whose frames are equal to their index.
+    fn ramp(frame_count: usize) -> Vec<f32> {
+        (0..frame_count).map(|i| i as f32).collect()
+    }
+
+    /// Plays `frame_count` frames of the player into a mono buffer at `frame_rate`.
+    fn render(
+        player: &mut StreamingAudioFilePlayer,
+        frame_rate: f64,
+        frame_count: usize,
+    ) -> (AudioBufferOwned, bool) {
+        let mut out = AudioBufferOwned::new(1);
+        out.resize(frame_count, 0.0);
+        let playing = player.fill_buffer(frame_rate, out.as_audio_buffer_mut());
+        (out, playing)
+    }
+
+    #[test]
+    fn resamples_to_the_stream_frame_rate() {
+        let mut player = player(&ramp(10000));
+
+        // This reads more frames than the window of the player can hold at once.
+        let (out, playing) = render(&mut player, 24000.0, 4000);
+        assert!(playing);
+        assert!(
+            out.channel(0)
+                .unwrap()
+                .iter()
+                .enumerate()
+                .all(|(i, &s)| s == 2.0 * i as f32)
+        );
+
+        // Only 1000 frames remain.
+        let (out, playing) = render(&mut player, 24000.0, 2000);
+        assert!(!playing);
+        assert_eq!(out.channel(0).unwrap()[999], 9998.0);
+        assert_eq!(out.channel(0).unwrap()[1000], 0.0);
+    }
+
+    #[test]
+    fn set_speed_changes_the_read_rate() {
+        let mut player = player(&ramp(1000));
+        assert!(player.set_speed(0.5));
+
+        let (out, playing) = render(&mut player, 48000.0, 200);
+        assert!(playing);
+        // The first frames are skipped because the frames before the start of the file are
+        // considered silent.
+        for (i, &s) in out.channel(0).unwrap().iter().enumerate().skip(2) {
+            assert!((s - 0.5 * i as f32).abs() < 1e-4, "frame {i} is {s}");
+        }
+    }
+}
@@ -1,4 +1,9 @@
-use std::{mem::forget, ptr::NonNull};
+use std::{
+    marker::PhantomData,
+    mem::forget,
+    ops::{Add, Mul},
+    ptr::NonNull,
+};
 
 /// A trait for types that can be converted to another type while keeping their original meaning
 /// (or as close as possible) in the context of an audio sample.
@@ -75,6 +80,157 @@ impl_IntoSample_unsigned_int_to_float!(
     (u32, i32) = f64,
 );
 
+/// A signed 24-bit integer sample, packed in three little-endian bytes.
+///
+/// This is the layout used by packed 24-bit audio streams (such as ALSA's `S24_3LE` format), and
+/// it can be written directly to the buffers of such streams.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct I24(pub [u8; 3]);
+
+/// A signed 24-bit integer sample, packed in three big-endian bytes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct I24Be(pub [u8; 3]);
+
+/// An unsigned 24-bit integer sample, packed in three little-endian bytes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct U24(pub [u8; 3]);
+
+/// An unsigned 24-bit integer sample, packed in three big-endian bytes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct U24Be(pub [u8; 3]);
+
+impl I24 {
+    /// Creates a new [`I24`] from the provided value.
+    ///
+    /// Values outside of the range `I24_MIN..=I24_MAX` are clamped.
+    #[inline]
+    pub fn new(value: i32) -> Self {
+        let [a, b, c, _] = value.clamp(I24_MIN, I24_MAX).to_le_bytes();
+        Self([a, b, c])
+    }
+
+    /// Returns the value of the sample.
+    #[inline]
+    pub fn get(self) -> i32 {
+        let [a, b, c] = self.0;
+        // The arithmetic shift takes care of the sign extension.
+        i32::from_le_bytes([0, a, b, c]) >> 8
+    }
+}
+
+impl I24Be {
+    /// Creates a new [`I24Be`] from the provided value.
+    ///
+    /// Values outside of the range `I24_MIN..=I24_MAX` are clamped.
+    #[inline]
+    pub fn new(value: i32) -> Self {
+        let [_, a, b, c] = value.clamp(I24_MIN, I24_MAX).to_be_bytes();
+        Self([a, b, c])
+    }
+
+    /// Returns the value of the sample.
+    #[inline]
+    pub fn get(self) -> i32 {
+        let [a, b, c] = self.0;
+        // The arithmetic shift takes care of the sign extension.
+        i32::from_be_bytes([a, b, c, 0]) >> 8
+    }
+}
+
+impl U24 {
+    /// Creates a new [`U24`] from the provided value.
+    ///
+    /// Values larger than `U24_MAX` are clamped.
+    #[inline]
+    pub fn new(value: u32) -> Self {
+        let [a, b, c, _] = value.min(U24_MAX).to_le_bytes();
+        Self([a, b, c])
+    }
+
+    /// Returns the value of the sample.
+    #[inline]
+    pub fn get(self) -> u32 {
+        let [a, b, c] = self.0;
+        u32::from_le_bytes([a, b, c, 0])
+    }
+}
+
+impl U24Be {
+    /// Creates a new [`U24Be`] from the provided value.
+    ///
+    /// Values larger than `U24_MAX` are clamped.
+    #[inline]
+    pub fn new(value: u32) -> Self {
+        let [_, a, b, c] = value.min(U24_MAX).to_be_bytes();
+        Self([a, b, c])
+    }
+
+    /// Returns the value of the sample.
+    #[inline]
+    pub fn get(self) -> u32 {
+        let [a, b, c] = self.0;
+        u32::from_be_bytes([0, a, b, c])
+    }
+}
+
+/// The smallest value that can be stored in a signed 24-bit sample.
+pub const I24_MIN: i32 = -0x80_0000;
+/// The largest value that can be stored in a signed 24-bit sample.
+pub const I24_MAX: i32 = 0x7F_FFFF;
+/// The largest value that can be stored in an unsigned 24-bit sample.
+pub const U24_MAX: u32 = 0xFF_FFFF;
+
+macro_rules! impl_IntoSample_signed_24bit_and_float {
+    ($($src:ty = $dst:ty),* $(,)?) => {
+        $(
+            impl IntoSample<$dst> for $src {
+                #[inline]
+                fn into_sample(self) -> $dst {
+                    const AMPLITUDE: $dst = -(I24_MIN as $dst);
+                    self.get() as $dst / AMPLITUDE
+                }
+            }
+
+            impl IntoSample<$src> for $dst {
+                #[inline]
+                fn into_sample(self) -> $src {
+                    const AMPLITUDE: $dst = -(I24_MIN as $dst);
+                    <$src>::new((self * AMPLITUDE) as i32)
+                }
+            }
+        )*
+    }
+}
+
+impl_IntoSample_signed_24bit_and_float!(I24 = f32, I24Be = f32, I24 = f64, I24Be = f64);
+
+macro_rules! impl_IntoSample_unsigned_24bit_and_float {
+    ($($src:ty = $dst:ty),* $(,)?) => {
+        $(
+            impl IntoSample<$dst> for $src {
+                #[inline]
+                fn into_sample(self) -> $dst {
+                    I24::new(self.get() as i32 + I24_MIN).into_sample()
+                }
+            }
+
+            impl IntoSample<$src> for $dst {
+                #[inline]
+                fn into_sample(self) -> $src {
+                    let signed: I24 = self.into_sample();
+                    <$src>::new((signed.get() - I24_MIN) as u32)
+                }
+            }
+        )*
+    }
+}
+
+impl_IntoSample_unsigned_24bit_and_float!(U24 = f32, U24Be = f32, U24 = f64, U24Be = f64);
+
 /// An exclusive reference to a collection of buffers that contain audio data.
 ///
 /// # Data layout
@@ -210,6 +366,23 @@ impl<T> AudioBufferMut<'_, T> {
             .map(move |&p| unsafe { std::slice::from_raw_parts_mut(p, self.frame_count) })
     }
 
+    /// Returns an iterator over the frames of the audio buffer.
+    ///
+    /// See [`AudioBufferOwned::frames`] for more information.
+    #[inline]
+    pub fn frames(&self) -> impl ExactSizeIterator<Item = Frame<'_, T>> + '_ {
+        (0..self.frame_count).map(move |index| unsafe { Frame::new(self.data, index) })
+    }
+
+    /// Returns an iterator over the frames of the audio buffer, allowing them to be modified.
+    ///
+    /// See [`AudioBufferOwned::frames`] for more information.
+    #[inline]
+    pub fn frames_mut(&mut self) -> impl ExactSizeIterator<Item = FrameMut<'_, T>> + '_ {
+        let data = self.data;
+        (0..self.frame_count).map(move |index| unsafe { FrameMut::new(data, index) })
+    }
+
     /// Re-borrows the buffer with a shorter lifetime without consuming the original reference.
     pub fn reborrow(&mut self) -> AudioBufferMut<T> {
         AudioBufferMut {
@@ -217,6 +390,174 @@ impl<T> AudioBufferMut<'_, T> {
             frame_count: self.frame_count,
         }
     }
+
+    /// De-interleaves, converts & copies the provided interleaved audio data into this buffer.
+    ///
+    /// Exactly `frame_count * channel_count` samples are read from `src`.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `channel_count` is equal to `.channel_count()`, and that the
+    /// provided pointer is valid for reading `frame_count * channel_count` elements.
+    pub unsafe fn fill_from_interleaved_unchecked<U>(&mut self, src: *const U, channel_count: usize)
+    where
+        U: Copy + IntoSample<T>,
+    {
+        let frame_count = self.frame_count();
+
+        for c in 0..channel_count {
+            unsafe {
+                let src = src.add(c);
+                let dst = self.channel_mut_ptr(c);
+                for i in 0..frame_count {
+                    *dst.add(i) = src.add(i * channel_count).read().into_sample();
+                }
+            }
+        }
+    }
+
+    /// De-interleaves, converts & copies the provided interleaved audio data into this buffer.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `channel_count` is not equal to `.channel_count()`, or if `src`
+    /// contains less than `frame_count * channel_count` samples.
+    pub fn fill_from_interleaved<U>(&mut self, src: &[U], channel_count: usize)
+    where
+        U: Copy + IntoSample<T>,
+    {
+        assert_eq!(
+            self.channel_count(),
+            channel_count,
+            "The number of channels must match",
+        );
+        assert!(
+            src.len() >= self.frame_count() * channel_count,
+            "The source buffer is too small",
+        );
+
+        unsafe { self.fill_from_interleaved_unchecked(src.as_ptr(), channel_count) }
+    }
+
+    /// Adds the samples of the provided audio buffer to the samples of this buffer.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the provided audio buffer does not have the same number of
+    /// channels and frames as this buffer.
+    pub fn add_from(&mut self, other: AudioBufferRef<T>)
+    where
+        T: Copy + Add<Output = T>,
+    {
+        self.zip_with(other, |dst, src| *dst = *dst + src);
+    }
+
+    /// Adds the samples of the provided audio buffer, multiplied by `gain`, to the samples of this
+    /// buffer.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the provided audio buffer does not have the same number of
+    /// channels and frames as this buffer.
+    pub fn add_scaled_from(&mut self, other: AudioBufferRef<T>, gain: T)
+    where
+        T: Copy + Add<Output = T> + Mul<Output = T>,
+    {
+        self.zip_with(other, |dst, src| *dst = *dst + src * gain);
+    }
+
+    /// Calls the provided function for every pair of samples of this buffer and the provided
+    /// buffer.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the provided audio buffer does not have the same number of
+    /// channels and frames as this buffer.
+    fn zip_with(&mut self, other: AudioBufferRef<T>, mut f: impl FnMut(&mut T, T))
+    where
+        T: Copy,
+    {
+        assert_eq!(
+            self.channel_count(),
+            other.channel_count(),
+            "The number of channels must match",
+        );
+        assert_eq!(
+            self.frame_count(),
+            other.frame_count(),
+            "The number of frames must match",
+        );
+
+        for (dst, src) in self.channels_mut().zip(other.channels()) {
+            dst.iter_mut().zip(src).for_each(|(d, &s)| f(d, s));
+        }
+    }
+}
+
+impl AudioBufferMut<'_, f32> {
+    /// Multiplies every sample of the audio buffer by the provided gain.
+    pub fn apply_gain(&mut self, gain: f32) {
+        self.channels_mut()
+            .for_each(|c| c.iter_mut().for_each(|s| *s *= gain));
+    }
+
+    /// Returns the left and right channels of a stereo buffer.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the buffer does not have exactly two channels.
+    #[track_caller]
+    fn stereo_channels_mut(&mut self) -> (&mut [f32], &mut [f32]) {
+        assert_eq!(
+            self.channel_count(),
+            2,
+            "Mid/side conversion requires exactly two channels",
+        );
+
+        // SAFETY: We just checked that the buffer has two channels, and they never overlap.
+        unsafe {
+            (
+                std::slice::from_raw_parts_mut(self.channel_mut_ptr(0), self.frame_count),
+                std::slice::from_raw_parts_mut(self.channel_mut_ptr(1), self.frame_count),
+            )
+        }
+    }
+
+    /// Converts a stereo (left/right) buffer to mid/side in place.
+    ///
+    /// After the call, the first channel contains the mid signal `(L + R) / 2` and the second
+    /// channel contains the side signal `(L - R) / 2`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the buffer does not have exactly two channels.
+    #[track_caller]
+    pub fn stereo_to_mid_side(&mut self) {
+        let (left, right) = self.stereo_channels_mut();
+        for (l, r) in left.iter_mut().zip(right) {
+            let (mid, side) = ((*l + *r) * 0.5, (*l - *r) * 0.5);
+            *l = mid;
+            *r = side;
+        }
+    }
+
+    /// Converts a mid/side buffer back to stereo (left/right) in place.
+    ///
+    /// This is the inverse of [`stereo_to_mid_side`](Self::stereo_to_mid_side): `L = M + S` and
+    /// `R = M - S`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the buffer does not have exactly two channels.
+    #[track_caller]
+    pub fn mid_side_to_stereo(&mut self) {
+        let (mid, side) = self.stereo_channels_mut();
+        for (m, s) in mid.iter_mut().zip(side) {
+            let (left, right) = (*m + *s, *m - *s);
+            *m = left;
+            *s = right;
+        }
+    }
 }
 
 /// An exclusive reference to a collection of buffers that contain audio data.
@@ -363,6 +704,178 @@ impl<T> AudioBufferRef<'_, T> {
     }
 }
 
+impl AudioBufferRef<'_, f32> {
+    /// Returns the peak amplitude of the audio buffer.
+    ///
+    /// This is the largest absolute value of any sample in any channel.
+    pub fn peak(&self) -> f32 {
+        self.channels()
+            .flat_map(|c| c.iter())
+            .fold(0.0, |peak, s| peak.max(s.abs()))
+    }
+
+    /// Mixes the audio data of this buffer into a destination buffer with fewer (or as many)
+    /// channels.
+    ///
+    /// The mixing matrix is chosen from the channel counts of the two buffers. Channels are
+    /// expected in the usual WAV/WASAPI order (`L, R, C, LFE, Ls, Rs, ...`).
+    ///
+    /// # Matrices
+    ///
+    /// - **Same channel count**: the channels are copied as-is.
+    ///
+    /// - **Stereo to mono**: `M = 0.5 L + 0.5 R`.
+    ///
+    /// - **Quad (4 channels) to stereo**: `L' = L + 0.707 Ls` and `R' = R + 0.707 Rs`.
+    ///
+    /// - **5.1 (6 channels) to stereo**: `L' = L + 0.707 C + 0.707 Ls` and
+    ///   `R' = R + 0.707 C + 0.707 Rs`. The LFE channel is discarded.
+    ///
+    /// - **7.1 (8 channels) to stereo**: same as 5.1, with the side channels folded in the same
+    ///   way as the surround channels (`0.707`).
+    ///
+    /// - **Quad, 5.1 or 7.1 to mono**: the buffer is folded down to stereo as described above,
+    ///   and the two resulting channels are averaged.
+    ///
+    /// Any other combination keeps the first channels of this buffer and discards the others.
+    ///
+    /// No normalization is applied after the fold-down, which means that a loud surround signal
+    /// can exceed the `[-1, 1]` range.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the destination buffer has more channels than this buffer, or if
+    /// the two buffers do not have the same number of frames.
+    #[track_caller]
+    pub fn downmix_into(&self, dest: AudioBufferMut) {
+        assert!(
+            dest.channel_count() <= self.channel_count(),
+            "The destination buffer must not have more channels than the source buffer",
+        );
+        assert_eq!(
+            self.frame_count(),
+            dest.frame_count(),
+            "The number of frames must match",
+        );
+
+        self.mix_into(dest);
+    }
+
+    /// Mixes the audio data of this buffer into a destination buffer with more (or as many)
+    /// channels.
+    ///
+    /// The mixing matrix is chosen from the channel counts of the two buffers. Channels are
+    /// expected in the usual WAV/WASAPI order (`L, R, C, LFE, Ls, Rs, ...`).
+    ///
+    /// # Matrices
+    ///
+    /// - **Mono to stereo or more**: the mono channel is duplicated at unity gain into the left
+    ///   and right channels (`L = M` and `R = M`). The other channels are silent.
+    ///
+    /// - **Anything else**: the channels of this buffer are copied to the first channels of the
+    ///   destination buffer, and the remaining channels are silent.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the destination buffer has fewer channels than this buffer, or if
+    /// the two buffers do not have the same number of frames.
+    #[track_caller]
+    pub fn upmix_into(&self, dest: AudioBufferMut) {
+        assert!(
+            dest.channel_count() >= self.channel_count(),
+            "The destination buffer must not have fewer channels than the source buffer",
+        );
+        assert_eq!(
+            self.frame_count(),
+            dest.frame_count(),
+            "The number of frames must match",
+        );
+
+        self.mix_into(dest);
+    }
+
+    /// Overwrites the channels of the destination buffer with the channels of this buffer,
+    /// weighted by [`channel_mix_gain`].
+    fn mix_into(&self, mut dest: AudioBufferMut) {
+        let src_count = self.channel_count();
+        let dst_count = dest.channel_count();
+
+        for (d, dst) in dest.channels_mut().enumerate() {
+            dst.fill(0.0);
+            for (s, src) in self.channels().enumerate() {
+                let gain = channel_mix_gain(src_count, dst_count, s, d);
+                if gain != 0.0 {
+                    dst.iter_mut()
+                        .zip(src)
+                        .for_each(|(out, &sample)| *out += sample * gain);
+                }
+            }
+        }
+    }
+}
+
+/// The gain applied to the channels that are folded into the front channels (-3 dB).
+const FOLD_DOWN_GAIN: f32 = std::f32::consts::FRAC_1_SQRT_2;
+
+/// Returns the matrix used to fold a buffer with `channel_count` channels down to stereo.
+///
+/// Each row of the matrix corresponds to an output channel (left, then right), and lists the
+/// input channels that are mixed into it along with their coefficient.
+fn stereo_fold_down_matrix(channel_count: usize) -> Option<[&'static [(usize, f32)]; 2]> {
+    const G: f32 = FOLD_DOWN_GAIN;
+
+    match channel_count {
+        // L, R
+        2 => Some([&[(0, 1.0)], &[(1, 1.0)]]),
+        // L, R, Ls, Rs
+        4 => Some([&[(0, 1.0), (2, G)], &[(1, 1.0), (3, G)]]),
+        // L, R, C, LFE, Ls, Rs
+        6 => Some([&[(0, 1.0), (2, G), (4, G)], &[(1, 1.0), (2, G), (5, G)]]),
+        // L, R, C, LFE, Ls, Rs, Lside, Rside
+        8 => Some([
+            &[(0, 1.0), (2, G), (4, G), (6, G)],
+            &[(1, 1.0), (2, G), (5, G), (7, G)],
+        ]),
+        _ => None,
+    }
+}
+
+/// Returns the gain with which the channel `src` of a signal with `src_count` channels is mixed
+/// into the channel `dst` of a signal with `dst_count` channels.
+///
+/// This is the mixing matrix of [`AudioBufferRef::downmix_into`] and
+/// [`AudioBufferRef::upmix_into`], one coefficient at a time. Players that read their source at
+/// fractional positions use it to mix their channels directly into the output buffer.
+pub fn channel_mix_gain(src_count: usize, dst_count: usize, src: usize, dst: usize) -> f32 {
+    /// Returns the coefficient of the channel `src` in the provided row of a fold-down matrix.
+    fn row_gain(row: &[(usize, f32)], src: usize) -> f32 {
+        row.iter()
+            .filter(|&&(c, _)| c == src)
+            .map(|&(_, coefficient)| coefficient)
+            .sum()
+    }
+
+    let identity = if src == dst { 1.0 } else { 0.0 };
+
+    match (src_count, dst_count) {
+        (1, 2..) => {
+            if dst < 2 {
+                1.0
+            } else {
+                0.0
+            }
+        }
+        (src_count, 1 | 2) if src_count > dst_count => match stereo_fold_down_matrix(src_count) {
+            Some(matrix) if dst_count == 1 => {
+                0.5 * (row_gain(matrix[0], src) + row_gain(matrix[1], src))
+            }
+            Some(matrix) => row_gain(matrix[dst], src),
+            None => identity,
+        },
+        _ => identity,
+    }
+}
+
 /// An owned audio buffer.
 ///
 /// # Data layout
@@ -418,6 +931,26 @@ impl<T> AudioBufferOwned<T> {
         }
     }
 
+    /// Creates a new audio buffer containing a copy of the provided channels.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the channels do not all have the same length.
+    #[cfg(test)]
+    pub fn from_channels(channels: &[&[T]]) -> Self
+    where
+        T: Copy + Default,
+    {
+        let frame_count = channels.first().map_or(0, |c| c.len());
+
+        let mut buf = Self::new(channels.len());
+        buf.resize(frame_count, T::default());
+        for (dst, src) in buf.channels_mut().zip(channels) {
+            dst.copy_from_slice(src);
+        }
+        buf
+    }
+
     /// Returns the current capacity of the audio buffer.
     #[inline(always)]
     pub fn capacity(&self) -> usize {
@@ -693,6 +1226,38 @@ impl<T> AudioBufferOwned<T> {
         })
     }
 
+    /// Returns the pointers to the channels of the audio buffer.
+    #[inline]
+    fn channel_ptrs(&self) -> &[*mut T] {
+        unsafe { std::slice::from_raw_parts(self.data.as_ptr(), self.channel_count) }
+    }
+
+    /// Returns an iterator over the frames of the audio buffer.
+    ///
+    /// Each item is a view over one sample per channel, at the same frame index. This is
+    /// useful for algorithms that need to look at all channels at once (interleaved metering,
+    /// mid/side processing, etc.).
+    ///
+    /// # Performance
+    ///
+    /// No allocation is performed, but because the data is stored in a planar layout, accessing
+    /// the samples of a frame is a gather across non-contiguous memory. When an algorithm can
+    /// process channels independently, [`channels`](Self::channels) is significantly faster.
+    #[inline]
+    pub fn frames(&self) -> impl ExactSizeIterator<Item = Frame<'_, T>> + '_ {
+        let data = self.channel_ptrs();
+        (0..self.frame_count).map(move |index| unsafe { Frame::new(data, index) })
+    }
+
+    /// Returns an iterator over the frames of the audio buffer, allowing them to be modified.
+    ///
+    /// See [`frames`](Self::frames) for more information.
+    #[inline]
+    pub fn frames_mut(&mut self) -> impl ExactSizeIterator<Item = FrameMut<'_, T>> + '_ {
+        let data = self.channel_ptrs();
+        (0..self.frame_count).map(move |index| unsafe { FrameMut::new(data, index) })
+    }
+
     /// Returns an [`AudioBufferRef`] that references the same audio data.
     #[inline]
     pub fn as_audio_buffer_mut(&mut self) -> AudioBufferMut<T> {
@@ -794,8 +1359,373 @@ impl<T> AudioBufferOwned<T> {
     }
 }
 
+/// A view over a single frame of a planar audio buffer.
+///
+/// A frame contains exactly one sample per channel.
+#[derive(Clone, Copy)]
+pub struct Frame<'a, T> {
+    /// The pointers to the channels of the buffer.
+    channels: &'a [*mut T],
+    /// The index of the frame within each channel.
+    index: usize,
+}
+
+impl<'a, T> Frame<'a, T> {
+    /// Creates a new [`Frame`].
+    ///
+    /// # Safety
+    ///
+    /// Every pointer in `channels` must reference at least `index + 1` initialized samples
+    /// that remain valid and are not mutated for the lifetime `'a`.
+    #[inline]
+    unsafe fn new(channels: &'a [*mut T], index: usize) -> Self {
+        Self { channels, index }
+    }
+
+    /// Returns the number of samples in the frame (the number of channels).
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.channels.len()
+    }
+
+    /// Returns whether the frame is empty (the buffer has no channels).
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.channels.is_empty()
+    }
+
+    /// Returns the sample of the provided channel.
+    ///
+    /// # Returns
+    ///
+    /// Returns `None` if the provided `channel` index is out of bounds.
+    #[inline]
+    pub fn get(&self, channel: usize) -> Option<&'a T> {
+        let p = *self.channels.get(channel)?;
+        Some(unsafe { &*p.add(self.index) })
+    }
+
+    /// Returns an iterator over the samples of the frame, one per channel.
+    #[inline]
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &'a T> + 'a {
+        let index = self.index;
+        self.channels
+            .iter()
+            .map(move |&p| unsafe { &*p.add(index) })
+    }
+}
+
+/// A mutable view over a single frame of a planar audio buffer.
+///
+/// A frame contains exactly one sample per channel.
+pub struct FrameMut<'a, T> {
+    /// The pointers to the channels of the buffer.
+    channels: &'a [*mut T],
+    /// The index of the frame within each channel.
+    index: usize,
+    /// Tells the borrow checker that this type mutably borrows `T`s.
+    _marker: PhantomData<&'a mut T>,
+}
+
+impl<'a, T> FrameMut<'a, T> {
+    /// Creates a new [`FrameMut`].
+    ///
+    /// # Safety
+    ///
+    /// Every pointer in `channels` must reference at least `index + 1` initialized samples
+    /// that remain valid for the lifetime `'a`. No other reference to the samples at `index`
+    /// may exist during that lifetime.
+    #[inline]
+    unsafe fn new(channels: &'a [*mut T], index: usize) -> Self {
+        Self {
+            channels,
+            index,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the number of samples in the frame (the number of channels).
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.channels.len()
+    }
+
+    /// Returns whether the frame is empty (the buffer has no channels).
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.channels.is_empty()
+    }
+
+    /// Returns the sample of the provided channel.
+    ///
+    /// # Returns
+    ///
+    /// Returns `None` if the provided `channel` index is out of bounds.
+    #[inline]
+    pub fn get(&self, channel: usize) -> Option<&T> {
+        let p = *self.channels.get(channel)?;
+        Some(unsafe { &*p.add(self.index) })
+    }
+
+    /// Returns the sample of the provided channel.
+    ///
+    /// # Returns
+    ///
+    /// Returns `None` if the provided `channel` index is out of bounds.
+    #[inline]
+    pub fn get_mut(&mut self, channel: usize) -> Option<&mut T> {
+        let p = *self.channels.get(channel)?;
+        Some(unsafe { &mut *p.add(self.index) })
+    }
+
+    /// Returns an iterator over the samples of the frame, one per channel.
+    #[inline]
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &T> + '_ {
+        let index = self.index;
+        self.channels
+            .iter()
+            .map(move |&p| unsafe { &*p.add(index) })
+    }
+
+    /// Returns an iterator over the samples of the frame, one per channel.
+    #[inline]
+    pub fn iter_mut(&mut self) -> impl ExactSizeIterator<Item = &mut T> + '_ {
+        let index = self.index;
+        self.channels
+            .iter()
+            .map(move |&p| unsafe { &mut *p.add(index) })
+    }
+}
+
 #[inline(never)]
 #[cold]
 fn capacity_overflow() -> ! {
     panic!("capacity overflow")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        AudioBufferOwned, I24, I24_MAX, I24_MIN, I24Be, IntoSample, U24, U24_MAX, U24Be,
+        channel_mix_gain,
+    };
+
+    /// Samples that are exactly representable by every 24-bit format.
+    const FLOAT_SAMPLES: [f64; 6] = [-1.0, -0.5, 0.0, 0.25, 0.5, I24_MAX as f64 / 8388608.0];
+
+    /// Converts every sample of [`FLOAT_SAMPLES`] to `S` and back, both through `f32` and `f64`,
+    /// and checks that the samples are unchanged.
+    fn assert_float_round_trip<S>()
+    where
+        S: Copy + IntoSample<f32> + IntoSample<f64> + std::fmt::Debug,
+        f32: IntoSample<S>,
+        f64: IntoSample<S>,
+    {
+        for expected in FLOAT_SAMPLES {
+            let sample: S = expected.into_sample();
+            let actual: f64 = sample.into_sample();
+            assert_eq!(actual, expected, "f64 round trip through {sample:?}");
+
+            let sample: S = (expected as f32).into_sample();
+            let actual: f32 = sample.into_sample();
+            assert_eq!(actual, expected as f32, "f32 round trip through {sample:?}");
+        }
+    }
+
+    #[test]
+    fn i24_round_trip() {
+        assert_eq!(I24::new(0x12_3456).0, [0x56, 0x34, 0x12]);
+        for value in [I24_MIN, -0x12_3456, -1, 0, 1, 0x12_3456, I24_MAX] {
+            assert_eq!(I24::new(value).get(), value);
+        }
+        assert_eq!(I24::new(i32::MAX).get(), I24_MAX);
+        assert_float_round_trip::<I24>();
+    }
+
+    #[test]
+    fn i24_be_round_trip() {
+        assert_eq!(I24Be::new(0x12_3456).0, [0x12, 0x34, 0x56]);
+        for value in [I24_MIN, -0x12_3456, -1, 0, 1, 0x12_3456, I24_MAX] {
+            assert_eq!(I24Be::new(value).get(), value);
+        }
+        assert_eq!(I24Be::new(i32::MIN).get(), I24_MIN);
+        assert_float_round_trip::<I24Be>();
+    }
+
+    #[test]
+    fn u24_round_trip() {
+        assert_eq!(U24::new(0x12_3456).0, [0x56, 0x34, 0x12]);
+        for value in [0, 1, 0x80_0000, 0x12_3456, U24_MAX] {
+            assert_eq!(U24::new(value).get(), value);
+        }
+        assert_eq!(IntoSample::<f32>::into_sample(U24::new(0x80_0000)), 0.0);
+        assert_float_round_trip::<U24>();
+    }
+
+    #[test]
+    fn u24_be_round_trip() {
+        assert_eq!(U24Be::new(0x12_3456).0, [0x12, 0x34, 0x56]);
+        for value in [0, 1, 0x80_0000, 0x12_3456, U24_MAX] {
+            assert_eq!(U24Be::new(value).get(), value);
+        }
+        assert_eq!(U24Be::new(u32::MAX).get(), U24_MAX);
+        assert_float_round_trip::<U24Be>();
+    }
+
+    #[test]
+    fn add_scaled_from_mixes_stereo_buffers() {
+        let mut dst = AudioBufferOwned::from_channels(&[&[0.5, 0.25, 0.0], &[-0.5, 0.0, 1.0]]);
+        let src = AudioBufferOwned::from_channels(&[&[1.0, 1.0, 1.0], &[0.5, -0.5, 0.0]]);
+
+        dst.as_audio_buffer_mut()
+            .add_scaled_from(src.as_audio_buffer_ref(), 0.5);
+
+        assert_eq!(dst.channel(0).unwrap(), [1.0, 0.75, 0.5]);
+        assert_eq!(dst.channel(1).unwrap(), [-0.25, -0.25, 1.0]);
+
+        dst.as_audio_buffer_mut()
+            .add_from(src.as_audio_buffer_ref());
+
+        assert_eq!(dst.channel(0).unwrap(), [2.0, 1.75, 1.5]);
+        assert_eq!(dst.channel(1).unwrap(), [0.25, -0.75, 1.0]);
+    }
+
+    #[test]
+    #[should_panic = "The number of frames must match"]
+    fn add_from_panics_on_mismatched_frame_counts() {
+        let mut dst = AudioBufferOwned::from_channels(&[&[0.0; 4], &[0.0; 4]]);
+        let src = AudioBufferOwned::from_channels(&[&[0.0; 3], &[0.0; 3]]);
+
+        dst.as_audio_buffer_mut()
+            .add_from(src.as_audio_buffer_ref());
+    }
+
+    #[test]
+    #[should_panic = "The number of channels must match"]
+    fn add_from_panics_on_mismatched_channel_counts() {
+        let mut dst = AudioBufferOwned::from_channels(&[&[0.0; 4], &[0.0; 4]]);
+        let src = AudioBufferOwned::from_channels(&[&[0.0; 4]]);
+
+        dst.as_audio_buffer_mut()
+            .add_from(src.as_audio_buffer_ref());
+    }
+
+    #[test]
+    fn mid_side_round_trip() {
+        let left = [0.0, 0.5, -0.25, 1.0, 0.123];
+        let right = [0.0, -0.5, 0.75, 1.0, -0.456];
+        let mut buf = AudioBufferOwned::from_channels(&[&left, &right]);
+
+        buf.as_audio_buffer_mut().stereo_to_mid_side();
+        assert_eq!(buf.channel(0).unwrap()[1..4], [0.0, 0.25, 1.0]);
+        assert_eq!(buf.channel(1).unwrap()[1..4], [0.5, -0.5, 0.0]);
+
+        buf.as_audio_buffer_mut().mid_side_to_stereo();
+        for (channel, expected) in buf.channels().zip([left, right]) {
+            assert!(
+                channel
+                    .iter()
+                    .zip(expected)
+                    .all(|(a, e)| (a - e).abs() < 1e-6),
+                "expected {expected:?}, got {channel:?}",
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn mid_side_requires_two_channels() {
+        let mut buf = AudioBufferOwned::from_channels(&[&[0.0; 4]]);
+        buf.as_audio_buffer_mut().stereo_to_mid_side();
+    }
+
+    #[test]
+    fn frames_of_stereo_buffer() {
+        let mut buf = AudioBufferOwned::from_channels(&[&[1.0, 2.0, 3.0], &[-1.0, -2.0, -3.0]]);
+
+        let frames: Vec<Vec<f32>> = buf.frames().map(|f| f.iter().copied().collect()).collect();
+        assert_eq!(frames, [[1.0, -1.0], [2.0, -2.0], [3.0, -3.0]]);
+
+        for mut frame in buf.frames_mut() {
+            assert_eq!(frame.len(), 2);
+            frame.iter_mut().for_each(|s| *s *= 2.0);
+            *frame.get_mut(1).unwrap() += 1.0;
+        }
+
+        assert_eq!(buf.channel(0).unwrap(), [2.0, 4.0, 6.0]);
+        assert_eq!(buf.channel(1).unwrap(), [-1.0, -3.0, -5.0]);
+
+        let view = buf.as_audio_buffer_mut();
+        assert_eq!(view.frames().len(), 3);
+        assert_eq!(view.frames().nth(1).unwrap().get(0), Some(&4.0));
+        assert_eq!(view.frames().nth(1).unwrap().get(2), None);
+    }
+
+    #[test]
+    fn downmix_stereo_to_mono() {
+        let src = AudioBufferOwned::from_channels(&[&[1.0, 0.5, -1.0], &[0.0, 0.5, 1.0]]);
+        let mut dest = AudioBufferOwned::from_channels(&[&[9.0; 3]]);
+
+        src.as_audio_buffer_ref()
+            .downmix_into(dest.as_audio_buffer_mut());
+
+        assert_eq!(dest.channel(0).unwrap(), [0.5, 0.5, 0.0]);
+    }
+
+    #[test]
+    fn upmix_mono_to_stereo() {
+        let src = AudioBufferOwned::from_channels(&[&[1.0, -0.5, 0.25]]);
+        let mut dest = AudioBufferOwned::from_channels(&[&[9.0; 3], &[9.0; 3]]);
+
+        src.as_audio_buffer_ref()
+            .upmix_into(dest.as_audio_buffer_mut());
+
+        assert_eq!(dest.channel(0).unwrap(), [1.0, -0.5, 0.25]);
+        assert_eq!(dest.channel(1).unwrap(), [1.0, -0.5, 0.25]);
+    }
+
+    #[test]
+    fn downmix_surround_to_stereo() {
+        const G: f32 = std::f32::consts::FRAC_1_SQRT_2;
+
+        // L, R, C, LFE, Ls, Rs
+        let src =
+            AudioBufferOwned::from_channels(&[&[1.0], &[0.0], &[1.0], &[1.0], &[0.0], &[1.0]]);
+        let mut dest = AudioBufferOwned::from_channels(&[&[9.0], &[9.0]]);
+
+        src.as_audio_buffer_ref()
+            .downmix_into(dest.as_audio_buffer_mut());
+
+        assert!((dest.channel(0).unwrap()[0] - (1.0 + G)).abs() < 1e-6);
+        assert!((dest.channel(1).unwrap()[0] - 2.0 * G).abs() < 1e-6);
+    }
+
+    #[test]
+    fn channel_mix_gain_matrices() {
+        const G: f32 = std::f32::consts::FRAC_1_SQRT_2;
+
+        // Mono is duplicated to the front channels only.
+        assert_eq!(channel_mix_gain(1, 6, 0, 0), 1.0);
+        assert_eq!(channel_mix_gain(1, 6, 0, 1), 1.0);
+        assert_eq!(channel_mix_gain(1, 6, 0, 2), 0.0);
+
+        // Quad folds its surround channels into the front channel of the same side.
+        assert_eq!(channel_mix_gain(4, 2, 2, 0), G);
+        assert_eq!(channel_mix_gain(4, 2, 2, 1), 0.0);
+
+        // The center of 5.1 reaches both sides, and the LFE is discarded.
+        assert_eq!(channel_mix_gain(6, 2, 2, 0), G);
+        assert_eq!(channel_mix_gain(6, 2, 2, 1), G);
+        assert_eq!(channel_mix_gain(6, 2, 3, 0), 0.0);
+        assert_eq!(channel_mix_gain(6, 1, 2, 0), G);
+
+        // Stereo to mono averages the two channels.
+        assert_eq!(channel_mix_gain(2, 1, 1, 0), 0.5);
+
+        // Unknown layouts keep the channels with the same index.
+        assert_eq!(channel_mix_gain(3, 2, 1, 1), 1.0);
+        assert_eq!(channel_mix_gain(3, 2, 2, 0), 0.0);
+        assert_eq!(channel_mix_gain(2, 4, 1, 1), 1.0);
+        assert_eq!(channel_mix_gain(2, 4, 1, 3), 0.0);
+    }
+}
@@ -0,0 +1,95 @@
+use crate::audio_thread::{AudioBufferMut, Biquad, MasterStage, NativeFormat};
+
+/// A node of the audio graph run by the audio thread.
+///
+/// The nodes of the graph are processed in sequence, each one receiving the buffer produced by
+/// the previous ones. Sources (such as [`OneShotPlayer`](super::OneShotPlayer)) add their
+/// samples to the buffer, while effects (such as [`Biquad`] filters) transform it in place.
+///
+/// # Real-time safety
+///
+/// [`process`](Self::process) is called on the high-priority audio thread and must *never*
+/// block. That includes memory allocations, I/O, and locks that might be contended.
+pub trait AudioNode: Send {
+    /// Processes the provided buffer in place.
+    ///
+    /// The buffer contains the output of the previous nodes of the graph (or silence for the
+    /// first node).
+    fn process(&mut self, frame_rate: f64, buf: AudioBufferMut);
+
+    /// Returns the format of the samples that the last call to [`process`](Self::process) left
+    /// in the buffer, if those samples were not modified in any way since they were decoded.
+    ///
+    /// This is used to decide whether bit-perfect playback is possible. The default
+    /// implementation returns `None`, which is what nodes that alter the signal should do.
+    fn native_format(&self) -> Option<NativeFormat> {
+        None
+    }
+}
+
+/// A node that multiplies the signal by a constant gain.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GainNode {
+    /// The linear gain applied to every sample.
+    pub gain: f32,
+}
+
+impl GainNode {
+    /// Creates a new [`GainNode`] with the provided linear gain.
+    #[inline]
+    pub fn new(gain: f32) -> Self {
+        Self { gain }
+    }
+}
+
+impl Default for GainNode {
+    #[inline]
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
+
+impl AudioNode for GainNode {
+    #[inline]
+    fn process(&mut self, _frame_rate: f64, mut buf: AudioBufferMut) {
+        buf.apply_gain(self.gain);
+    }
+}
+
+impl AudioNode for Biquad {
+    #[inline]
+    fn process(&mut self, _frame_rate: f64, buf: AudioBufferMut) {
+        self.process_buffer(buf);
+    }
+}
+
+impl AudioNode for MasterStage {
+    #[inline]
+    fn process(&mut self, _frame_rate: f64, buf: AudioBufferMut) {
+        MasterStage::process(self, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::{AudioNode, GainNode},
+        crate::audio_thread::{AudioBufferOwned, MasterSaturation, MasterStage},
+    };
+
+    #[test]
+    fn gain_then_clamp() {
+        let mut nodes: Vec<Box<dyn AudioNode>> = vec![
+            Box::new(GainNode::new(4.0)),
+            Box::new(MasterStage::new(48000.0, MasterSaturation::HardClip)),
+        ];
+        let mut buf = AudioBufferOwned::from_channels(&[&[0.125, 0.5, -0.5], &[0.0, -0.25, 1.0]]);
+
+        for node in &mut nodes {
+            node.process(48000.0, buf.as_audio_buffer_mut());
+        }
+
+        assert_eq!(buf.channel(0).unwrap(), [0.5, 1.0, -1.0]);
+        assert_eq!(buf.channel(1).unwrap(), [0.0, -1.0, 1.0]);
+    }
+}
@@ -0,0 +1,272 @@
+use {crate::audio_thread::AudioBufferMut, std::f64::consts::TAU};
+
+/// The maximum number of channels a [`Biquad`] filter can process.
+///
+/// Channels past this limit are left untouched.
+pub const BIQUAD_MAX_CHANNELS: usize = 8;
+
+/// The normalized coefficients of a biquad filter.
+///
+/// The `a0` coefficient is always assumed to be `1.0`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BiquadCoefficients {
+    pub b0: f32,
+    pub b1: f32,
+    pub b2: f32,
+    pub a1: f32,
+    pub a2: f32,
+}
+
+impl BiquadCoefficients {
+    /// The coefficients of a filter that lets the signal through unchanged.
+    pub const IDENTITY: Self = Self {
+        b0: 1.0,
+        b1: 0.0,
+        b2: 0.0,
+        a1: 0.0,
+        a2: 0.0,
+    };
+
+    /// Creates a new [`BiquadCoefficients`] instance from un-normalized coefficients.
+    fn normalize(b0: f64, b1: f64, b2: f64, a0: f64, a1: f64, a2: f64) -> Self {
+        Self {
+            b0: (b0 / a0) as f32,
+            b1: (b1 / a0) as f32,
+            b2: (b2 / a0) as f32,
+            a1: (a1 / a0) as f32,
+            a2: (a2 / a0) as f32,
+        }
+    }
+
+    /// Returns the `(cos(w0), alpha)` pair used by most of the filter designs.
+    fn cos_and_alpha(frame_rate: f64, frequency: f64, q: f64) -> (f64, f64) {
+        let w0 = TAU * frequency / frame_rate;
+        let (sin, cos) = w0.sin_cos();
+        (cos, sin / (2.0 * q))
+    }
+
+    /// Creates the coefficients of a low-pass filter.
+    ///
+    /// # Parameters
+    ///
+    /// - `frame_rate`: The frame rate of the processed signal.
+    ///
+    /// - `frequency`: The cutoff frequency of the filter, in Hertz.
+    ///
+    /// - `q`: The quality factor of the filter. `1/sqrt(2)` gives a flat pass band.
+    pub fn lowpass(frame_rate: f64, frequency: f64, q: f64) -> Self {
+        let (cos, alpha) = Self::cos_and_alpha(frame_rate, frequency, q);
+        Self::normalize(
+            (1.0 - cos) / 2.0,
+            1.0 - cos,
+            (1.0 - cos) / 2.0,
+            1.0 + alpha,
+            -2.0 * cos,
+            1.0 - alpha,
+        )
+    }
+
+    /// Creates the coefficients of a high-pass filter.
+    ///
+    /// See [`lowpass`](Self::lowpass) for a description of the parameters.
+    pub fn highpass(frame_rate: f64, frequency: f64, q: f64) -> Self {
+        let (cos, alpha) = Self::cos_and_alpha(frame_rate, frequency, q);
+        Self::normalize(
+            (1.0 + cos) / 2.0,
+            -(1.0 + cos),
+            (1.0 + cos) / 2.0,
+            1.0 + alpha,
+            -2.0 * cos,
+            1.0 - alpha,
+        )
+    }
+
+    /// Creates the coefficients of a peaking (bell) filter.
+    ///
+    /// # Parameters
+    ///
+    /// - `frame_rate`: The frame rate of the processed signal.
+    ///
+    /// - `frequency`: The center frequency of the filter, in Hertz.
+    ///
+    /// - `q`: The quality factor of the filter. Higher values make the bell narrower.
+    ///
+    /// - `gain`: The gain applied at the center frequency, in decibels.
+    pub fn peaking(frame_rate: f64, frequency: f64, q: f64, gain: f64) -> Self {
+        let (cos, alpha) = Self::cos_and_alpha(frame_rate, frequency, q);
+        let a = 10f64.powf(gain / 40.0);
+        Self::normalize(
+            1.0 + alpha * a,
+            -2.0 * cos,
+            1.0 - alpha * a,
+            1.0 + alpha / a,
+            -2.0 * cos,
+            1.0 - alpha / a,
+        )
+    }
+
+    /// Creates the coefficients of a low-shelf filter.
+    ///
+    /// # Parameters
+    ///
+    /// - `frame_rate`: The frame rate of the processed signal.
+    ///
+    /// - `frequency`: The corner frequency of the shelf, in Hertz.
+    ///
+    /// - `q`: The quality factor of the filter.
+    ///
+    /// - `gain`: The gain applied below the corner frequency, in decibels.
+    pub fn low_shelf(frame_rate: f64, frequency: f64, q: f64, gain: f64) -> Self {
+        let (cos, alpha) = Self::cos_and_alpha(frame_rate, frequency, q);
+        let a = 10f64.powf(gain / 40.0);
+        let k = 2.0 * a.sqrt() * alpha;
+        Self::normalize(
+            a * ((a + 1.0) - (a - 1.0) * cos + k),
+            2.0 * a * ((a - 1.0) - (a + 1.0) * cos),
+            a * ((a + 1.0) - (a - 1.0) * cos - k),
+            (a + 1.0) + (a - 1.0) * cos + k,
+            -2.0 * ((a - 1.0) + (a + 1.0) * cos),
+            (a + 1.0) + (a - 1.0) * cos - k,
+        )
+    }
+
+    /// Creates the coefficients of a high-shelf filter.
+    ///
+    /// # Parameters
+    ///
+    /// - `frame_rate`: The frame rate of the processed signal.
+    ///
+    /// - `frequency`: The corner frequency of the shelf, in Hertz.
+    ///
+    /// - `q`: The quality factor of the filter.
+    ///
+    /// - `gain`: The gain applied above the corner frequency, in decibels.
+    pub fn high_shelf(frame_rate: f64, frequency: f64, q: f64, gain: f64) -> Self {
+        let (cos, alpha) = Self::cos_and_alpha(frame_rate, frequency, q);
+        let a = 10f64.powf(gain / 40.0);
+        let k = 2.0 * a.sqrt() * alpha;
+        Self::normalize(
+            a * ((a + 1.0) + (a - 1.0) * cos + k),
+            -2.0 * a * ((a - 1.0) + (a + 1.0) * cos),
+            a * ((a + 1.0) + (a - 1.0) * cos - k),
+            (a + 1.0) - (a - 1.0) * cos + k,
+            2.0 * ((a - 1.0) - (a + 1.0) * cos),
+            (a + 1.0) - (a - 1.0) * cos - k,
+        )
+    }
+}
+
+impl Default for BiquadCoefficients {
+    #[inline]
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+/// The state of a biquad filter for a single channel.
+#[derive(Debug, Default, Clone, Copy)]
+struct ChannelState {
+    z1: f32,
+    z2: f32,
+}
+
+/// A biquad filter.
+///
+/// The filter is implemented using the transposed direct form II, and keeps track of its state
+/// independently for each channel.
+///
+/// # Remarks
+///
+/// Processing audio through the filter never allocates, which means that it can safely be used
+/// on the audio thread.
+#[derive(Debug, Clone)]
+pub struct Biquad {
+    /// The coefficients of the filter.
+    coefficients: BiquadCoefficients,
+    /// The state of the filter for each channel.
+    state: [ChannelState; BIQUAD_MAX_CHANNELS],
+}
+
+impl Biquad {
+    /// Creates a new [`Biquad`] filter with the provided coefficients.
+    pub fn new(coefficients: BiquadCoefficients) -> Self {
+        Self {
+            coefficients,
+            state: [ChannelState::default(); BIQUAD_MAX_CHANNELS],
+        }
+    }
+
+    /// Returns the coefficients of the filter.
+    #[inline]
+    pub fn coefficients(&self) -> &BiquadCoefficients {
+        &self.coefficients
+    }
+
+    /// Sets the coefficients of the filter.
+    ///
+    /// The state of the filter is preserved in order to avoid clicks when the coefficients are
+    /// updated while audio is playing.
+    #[inline]
+    pub fn set_coefficients(&mut self, coefficients: BiquadCoefficients) {
+        self.coefficients = coefficients;
+    }
+
+    /// Resets the state of the filter.
+    pub fn reset(&mut self) {
+        self.state = [ChannelState::default(); BIQUAD_MAX_CHANNELS];
+    }
+
+    /// Processes the provided buffer in place.
+    ///
+    /// Channels past [`BIQUAD_MAX_CHANNELS`] are left untouched.
+    pub fn process_buffer(&mut self, mut buf: AudioBufferMut) {
+        let BiquadCoefficients { b0, b1, b2, a1, a2 } = self.coefficients;
+
+        for (channel, state) in buf.channels_mut().zip(self.state.iter_mut()) {
+            let ChannelState { mut z1, mut z2 } = *state;
+
+            for sample in channel {
+                let input = *sample;
+                let output = b0 * input + z1;
+                z1 = b1 * input - a1 * output + z2;
+                z2 = b2 * input - a2 * output;
+                *sample = output;
+            }
+
+            *state = ChannelState { z1, z2 };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::{Biquad, BiquadCoefficients},
+        crate::audio_thread::AudioBufferOwned,
+        std::f64::consts::FRAC_1_SQRT_2,
+    };
+
+    /// Runs the provided signal through a 1 kHz low-pass filter and returns the amplitude of the
+    /// last output frame, once the filter has settled.
+    fn lowpass_output(signal: impl Fn(usize) -> f32) -> f32 {
+        let input: Vec<f32> = (0..4096).map(signal).collect();
+        let mut buf = AudioBufferOwned::from_channels(&[&input]);
+
+        let mut filter = Biquad::new(BiquadCoefficients::lowpass(48000.0, 1000.0, FRAC_1_SQRT_2));
+        filter.process_buffer(buf.as_audio_buffer_mut());
+
+        buf.channel(0).unwrap().last().unwrap().abs()
+    }
+
+    #[test]
+    fn lowpass_has_unity_gain_at_dc() {
+        let output = lowpass_output(|_| 1.0);
+        assert!((output - 1.0).abs() < 1e-3, "gain at DC is {output}");
+    }
+
+    #[test]
+    fn lowpass_rejects_nyquist() {
+        let output = lowpass_output(|i| [1.0, -1.0][i % 2]);
+        assert!(output < 1e-3, "gain at Nyquist is {output}");
+    }
+}
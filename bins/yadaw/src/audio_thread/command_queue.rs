@@ -0,0 +1,149 @@
+use std::{
+    cell::UnsafeCell,
+    mem::MaybeUninit,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// A slot of a [`CommandQueue`].
+struct Slot<T> {
+    /// The sequence number of the slot, minus the index of the slot in the queue.
+    ///
+    /// Storing the sequence relative to the index allows every slot to start at zero, which
+    /// makes it possible to create the queue in a `const` context.
+    ///
+    /// A slot at index `i` can be written to by the producer that reserved the position `pos`
+    /// when its sequence is `pos`, and read by the consumer at position `pos` when its sequence
+    /// is `pos + 1`.
+    sequence: AtomicUsize,
+    /// The value stored in the slot.
+    value: UnsafeCell<MaybeUninit<T>>,
+}
+
+impl<T> Slot<T> {
+    /// Creates a new empty [`Slot`].
+    const fn new() -> Self {
+        Self {
+            sequence: AtomicUsize::new(0),
+            value: UnsafeCell::new(MaybeUninit::uninit()),
+        }
+    }
+}
+
+/// A bounded multi-producer multi-consumer queue of small commands.
+///
+/// Neither side ever blocks nor allocates, which makes the queue suitable for sending commands
+/// to the audio thread.
+pub struct CommandQueue<T, const N: usize> {
+    /// The slots of the queue.
+    slots: [Slot<T>; N],
+    /// The position of the next value to push.
+    tail: AtomicUsize,
+    /// The position of the next value to pop.
+    head: AtomicUsize,
+}
+
+// SAFETY: Access to the values is synchronized through the sequence numbers of the slots.
+unsafe impl<T: Send, const N: usize> Sync for CommandQueue<T, N> {}
+
+impl<T, const N: usize> CommandQueue<T, N> {
+    /// Creates a new empty [`CommandQueue`].
+    pub const fn new() -> Self {
+        assert!(N > 0, "The capacity of a command queue must not be zero");
+
+        Self {
+            slots: [const { Slot::new() }; N],
+            tail: AtomicUsize::new(0),
+            head: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the slot at the provided position, along with its current sequence number.
+    #[inline]
+    fn slot(&self, pos: usize) -> (&Slot<T>, usize) {
+        let index = pos % N;
+        let slot = &self.slots[index];
+        let sequence = slot.sequence.load(Ordering::Acquire).wrapping_add(index);
+        (slot, sequence)
+    }
+
+    /// Pushes a value to the queue.
+    ///
+    /// # Returns
+    ///
+    /// If the queue is full, the value is given back.
+    pub fn push(&self, value: T) -> Result<(), T> {
+        let mut pos = self.tail.load(Ordering::Relaxed);
+
+        loop {
+            let (slot, sequence) = self.slot(pos);
+
+            match (sequence.wrapping_sub(pos) as isize).signum() {
+                0 => match self.tail.compare_exchange_weak(
+                    pos,
+                    pos.wrapping_add(1),
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => {
+                        // SAFETY: The position was reserved by this producer, no other thread
+                        // accesses the slot until its sequence number is updated.
+                        unsafe { (*slot.value.get()).write(value) };
+                        slot.sequence
+                            .store(pos.wrapping_add(1).wrapping_sub(pos % N), Ordering::Release);
+                        return Ok(());
+                    }
+                    Err(actual) => pos = actual,
+                },
+                -1 => return Err(value),
+                _ => pos = self.tail.load(Ordering::Relaxed),
+            }
+        }
+    }
+
+    /// Pops a value from the queue.
+    ///
+    /// # Returns
+    ///
+    /// Returns `None` if the queue is empty.
+    pub fn pop(&self) -> Option<T> {
+        let mut pos = self.head.load(Ordering::Relaxed);
+
+        loop {
+            let (slot, sequence) = self.slot(pos);
+
+            match (sequence.wrapping_sub(pos.wrapping_add(1)) as isize).signum() {
+                0 => match self.head.compare_exchange_weak(
+                    pos,
+                    pos.wrapping_add(1),
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => {
+                        // SAFETY: The producer that wrote the slot has released it, and the
+                        // position was reserved by this consumer.
+                        let value = unsafe { (*slot.value.get()).assume_init_read() };
+                        slot.sequence
+                            .store(pos.wrapping_add(N).wrapping_sub(pos % N), Ordering::Release);
+                        return Some(value);
+                    }
+                    Err(actual) => pos = actual,
+                },
+                -1 => return None,
+                _ => pos = self.head.load(Ordering::Relaxed),
+            }
+        }
+    }
+}
+
+impl<T, const N: usize> Default for CommandQueue<T, N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Drop for CommandQueue<T, N> {
+    fn drop(&mut self) {
+        while self.pop().is_some() {}
+    }
+}
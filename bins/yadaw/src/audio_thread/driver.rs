@@ -1,10 +1,62 @@
 use {
-    crate::audio_thread::{AudioBufferMut, AudioBufferOwned, AudioThread, IntoSample},
-    advice::{StreamCallback, StreamConfig},
+    crate::audio_thread::{
+        AudioBufferMut, AudioBufferOwned, AudioThread, AudioThreadControls, AudioThreadEvent,
+        DEVICE_FADE_DURATION, IntoSample, MasterSaturation,
+    },
+    advice::{Device, Stream, StreamCallback, StreamConfig},
+    std::{
+        cell::{Cell, RefCell},
+        sync::Arc,
+        time::{Duration, Instant},
+    },
 };
 
+/// An output stream that is currently driving an [`AudioThread`].
+struct ActiveOutput {
+    /// The stream itself.
+    stream: Box<dyn Stream>,
+    /// The controls of the audio thread rendering to the stream.
+    controls: Arc<AudioThreadControls>,
+    /// The sequence number of the last levels returned by [`poll_levels`].
+    levels_seen: Cell<usize>,
+}
+
+/// An output stream that is fading out after the output device was switched.
+struct FadingOutput {
+    /// The output itself.
+    output: ActiveOutput,
+    /// The instant after which the stream is closed even if its audio thread did not report
+    /// that it finished fading out.
+    deadline: Instant,
+}
+
+thread_local! {
+    /// The output stream that is currently in use.
+    ///
+    /// Streams are not thread-safe, so this is owned by the thread that initialized the audio
+    /// thread (the UI thread).
+    static ACTIVE_OUTPUT: RefCell<Option<ActiveOutput>> = const { RefCell::new(None) };
+
+    /// The previous output streams that are still fading out.
+    ///
+    /// They are closed by [`close_faded_outputs`] once their audio thread is silent.
+    static FADING_OUTPUTS: RefCell<Vec<FadingOutput>> = const { RefCell::new(Vec::new()) };
+
+    /// Whether bit-perfect playback is enabled.
+    ///
+    /// This is remembered so that it can be applied to the audio threads created when switching
+    /// output devices.
+    static BIT_PERFECT: Cell<bool> = const { Cell::new(false) };
+
+    /// The saturation mode used by the master stage of the audio threads.
+    ///
+    /// This is applied to the audio threads created when switching output devices.
+    static MASTER_SATURATION: Cell<MasterSaturation> =
+        const { Cell::new(MasterSaturation::HardClip) };
+}
+
 /// Initializes the audio thread for the application.
-pub fn initialize_audio_thread() {
+pub fn initialize_audio_thread(ctx: &kui::Ctx) {
     let host = advice::default_host()
         .unwrap_or_else(|err| panic!("Failed to initialize the audio host: {err}"))
         .unwrap_or_else(|| panic!("No audio backend available"));
@@ -14,6 +66,29 @@ pub fn initialize_audio_thread() {
         .unwrap_or_else(|err| panic!("Failed to get the default output device: {err}"))
         .unwrap_or_else(|| panic!("No default output device available"));
 
+    switch_output_device(ctx, output_device.as_ref());
+}
+
+/// Switches the output device used by the audio thread.
+///
+/// The previous stream (if any) is faded out over [`DEVICE_FADE_DURATION`] while the new stream
+/// is faded in over the same duration. The audio thread is re-created for the new stream,
+/// meaning that a device with a different channel count or frame rate is properly handled.
+///
+/// The previous stream is closed later, from a callback scheduled on `ctx`, once its audio
+/// thread reports that it is silent.
+///
+/// # Remarks
+///
+/// This function does not wait for the previous stream to fade out. However, the two fades are
+/// not aligned: each stream runs on the clock of its own device, and the new stream only starts
+/// fading in once its device requests the first buffer. Depending on the latency of the
+/// devices, the fades may overlap partially, or be separated by a short gap of silence.
+pub fn switch_output_device(ctx: &kui::Ctx, output_device: &dyn Device) {
+    if let Some(prev) = ACTIVE_OUTPUT.with_borrow_mut(Option::take) {
+        fade_out_later(ctx, prev);
+    }
+
     let config = output_device
         .output_formats(advice::ShareMode::Share)
         .unwrap_or_else(|err| panic!("Failed to get the available output formats: {err}"))
@@ -27,7 +102,10 @@ pub fn initialize_audio_thread() {
             44100.0,
         );
 
-    let handler = unsafe { make_stream_handler(&config) };
+    let controls = Arc::new(AudioThreadControls::default());
+    controls.set_bit_perfect(BIT_PERFECT.get());
+    let handler =
+        unsafe { make_stream_handler(&config, controls.clone(), MASTER_SATURATION.get()) };
 
     let stream = output_device
         .open_output_stream(config, handler)
@@ -36,8 +114,109 @@ pub fn initialize_audio_thread() {
         .start()
         .unwrap_or_else(|err| panic!("Failed to start the output stream: {err}"));
 
-    // Leak the stream so that it is not closed when the function returns.
-    std::mem::forget(stream);
+    ACTIVE_OUTPUT.set(Some(ActiveOutput {
+        stream,
+        controls,
+        levels_seen: Cell::new(0),
+    }));
+}
+
+/// Enables or disables bit-perfect playback.
+///
+/// When enabled, samples that already match the format of the output stream are sent to the
+/// device without any processing. See [`AudioThreadControls::set_bit_perfect`].
+pub fn set_bit_perfect(yes: bool) {
+    BIT_PERFECT.set(yes);
+    ACTIVE_OUTPUT.with_borrow(|output| {
+        if let Some(output) = output {
+            output.controls.set_bit_perfect(yes);
+        }
+    });
+}
+
+/// Sets the saturation mode used by the master stage of the audio thread.
+///
+/// The audio thread is only configured when it is created, so the new mode takes effect the
+/// next time the output device is switched with [`switch_output_device`].
+pub fn set_master_saturation(saturation: MasterSaturation) {
+    MASTER_SATURATION.set(saturation);
+}
+
+/// Returns the levels of the last buffer produced by the audio thread, if they changed since the
+/// last call to this function.
+///
+/// This never blocks the audio thread, and is meant to be polled by the UI thread (for example
+/// once per frame).
+///
+/// # Returns
+///
+/// Returns an [`AudioThreadEvent::Levels`] event, or `None` if no new levels were produced.
+pub fn poll_levels() -> Option<AudioThreadEvent> {
+    ACTIVE_OUTPUT.with_borrow(|output| {
+        let output = output.as_ref()?;
+        let (seq, levels) = output.controls.levels();
+        if seq == 0 || output.levels_seen.replace(seq) == seq {
+            return None;
+        }
+        Some(AudioThreadEvent::Levels(levels))
+    })
+}
+
+/// The interval at which the outputs that are fading out are checked for completion.
+const FADE_POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// Fades out the provided output, and schedules its stream to be closed once the fade is
+/// complete.
+fn fade_out_later(ctx: &kui::Ctx, output: ActiveOutput) {
+    // If the stream stopped producing audio for some reason, we don't want to wait forever.
+    const TIMEOUT: Duration = DEVICE_FADE_DURATION.saturating_mul(4);
+
+    output.controls.request_fade_out();
+
+    let was_empty = FADING_OUTPUTS.with_borrow_mut(|outputs| {
+        outputs.push(FadingOutput {
+            output,
+            deadline: Instant::now() + TIMEOUT,
+        });
+        outputs.len() == 1
+    });
+
+    // A callback is already scheduled otherwise.
+    if was_empty {
+        schedule_close_faded_outputs(ctx);
+    }
+}
+
+/// Closes the outputs that finished fading out (or that timed out), and schedules another
+/// check if some are still fading.
+fn close_faded_outputs(ctx: &kui::Ctx) {
+    let now = Instant::now();
+
+    let still_fading = FADING_OUTPUTS.with_borrow_mut(|outputs| {
+        outputs.retain(|fading| {
+            if !fading.output.controls.is_silent() && now < fading.deadline {
+                return true;
+            }
+
+            if let Err(err) = fading.output.stream.stop() {
+                log::warn!("Failed to stop the previous output stream: {err}");
+            }
+            false
+        });
+        !outputs.is_empty()
+    });
+
+    if still_fading {
+        schedule_close_faded_outputs(ctx);
+    }
+}
+
+/// Schedules a call to [`close_faded_outputs`] after [`FADE_POLL_INTERVAL`].
+fn schedule_close_faded_outputs(ctx: &kui::Ctx) {
+    ctx.call_after(FADE_POLL_INTERVAL, {
+        let ctx = ctx.clone();
+        move || close_faded_outputs(&ctx)
+    });
 }
 
 /// Makes the output stream handler for the provided parameters.
@@ -46,14 +225,20 @@ pub fn initialize_audio_thread() {
 ///
 /// The caller must make sure that the returned handler is only used with a stream created with the
 /// same configuration.
-unsafe fn make_stream_handler(config: &StreamConfig) -> Box<dyn Send + FnMut(StreamCallback)> {
+unsafe fn make_stream_handler(
+    config: &StreamConfig,
+    controls: Arc<AudioThreadControls>,
+    saturation: MasterSaturation,
+) -> Box<dyn Send + FnMut(StreamCallback)> {
     unsafe fn make_stream_handler_interleaved<T>(
         config: &StreamConfig,
+        controls: Arc<AudioThreadControls>,
+        saturation: MasterSaturation,
     ) -> Box<dyn Send + FnMut(StreamCallback)>
     where
         f32: IntoSample<T>,
     {
-        let mut audio_thread = AudioThread::new(config.frame_rate);
+        let mut audio_thread = AudioThread::new(config, controls, saturation);
         let mut buffer = AudioBufferOwned::new(config.channel_count as usize);
         Box::new(move |callback| unsafe {
             buffer.resize(callback.frame_count(), 0.0); // FIXME: Remove this allocation
@@ -66,13 +251,15 @@ unsafe fn make_stream_handler(config: &StreamConfig) -> Box<dyn Send + FnMut(Str
 
     unsafe fn make_stream_handler_planar<T>(
         config: &StreamConfig,
+        controls: Arc<AudioThreadControls>,
+        saturation: MasterSaturation,
     ) -> Box<dyn Send + FnMut(StreamCallback)>
     where
         f32: IntoSample<T>,
     {
         // let mut converter = StreamConverter::new(config.channel_count as usize);
         let mut buffer = AudioBufferOwned::new(config.channel_count as usize);
-        let mut audio_thread = AudioThread::new(config.frame_rate);
+        let mut audio_thread = AudioThread::new(config, controls, saturation);
         Box::new(move |callback| unsafe {
             buffer.resize(callback.frame_count(), 0.0); // FIXME: Remove this allocation
             audio_thread.fill_buffer(buffer.as_audio_buffer_mut());
@@ -88,9 +275,11 @@ unsafe fn make_stream_handler(config: &StreamConfig) -> Box<dyn Send + FnMut(Str
 
     unsafe fn make_stream_handler_planar_f32(
         config: &StreamConfig,
+        controls: Arc<AudioThreadControls>,
+        saturation: MasterSaturation,
     ) -> Box<dyn Send + FnMut(StreamCallback)> {
         let channel_count = config.channel_count;
-        let mut audio_thread = AudioThread::new(config.frame_rate);
+        let mut audio_thread = AudioThread::new(config, controls, saturation);
         Box::new(move |callback| unsafe {
             audio_thread.fill_buffer(AudioBufferMut::from_raw_parts(
                 callback.data().planar as *const *mut f32,
@@ -101,12 +290,27 @@ unsafe fn make_stream_handler(config: &StreamConfig) -> Box<dyn Send + FnMut(Str
     }
 
     unsafe {
-        use advice::{ChannelLayout::*, Format::*};
+        use {
+            crate::audio_thread::{I24 as PackedI24, U24 as PackedU24},
+            advice::{ChannelLayout::*, Format::*},
+        };
         match (config.channel_layout, config.format) {
-            (Interleaved, F32) => make_stream_handler_interleaved::<f32>(config),
-            (Interleaved, I16) => make_stream_handler_interleaved::<i16>(config),
-            (Planar, F32) => make_stream_handler_planar_f32(config),
-            (Planar, I16) => make_stream_handler_planar::<i16>(config),
+            (Interleaved, F32) => {
+                make_stream_handler_interleaved::<f32>(config, controls, saturation)
+            }
+            (Interleaved, I16) => {
+                make_stream_handler_interleaved::<i16>(config, controls, saturation)
+            }
+            (Interleaved, I24) => {
+                make_stream_handler_interleaved::<PackedI24>(config, controls, saturation)
+            }
+            (Interleaved, U24) => {
+                make_stream_handler_interleaved::<PackedU24>(config, controls, saturation)
+            }
+            (Planar, F32) => make_stream_handler_planar_f32(config, controls, saturation),
+            (Planar, I16) => make_stream_handler_planar::<i16>(config, controls, saturation),
+            (Planar, I24) => make_stream_handler_planar::<PackedI24>(config, controls, saturation),
+            (Planar, U24) => make_stream_handler_planar::<PackedU24>(config, controls, saturation),
             (channel_layout, sample_format) => panic!(
                 "Unsupported channel layout and format combination: {channel_layout:?}, {sample_format:?}"
             ),
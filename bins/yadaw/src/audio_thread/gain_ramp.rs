@@ -0,0 +1,103 @@
+use crate::audio_thread::AudioBufferMut;
+
+/// A gain that linearly moves towards a target value over a fixed number of frames.
+///
+/// This is used to avoid clicks when the gain applied to a signal changes abruptly.
+#[derive(Debug, Clone, Copy)]
+pub struct GainRamp {
+    /// The current gain.
+    current: f32,
+    /// The gain that the ramp is moving towards.
+    target: f32,
+    /// The amount added to `current` for every frame.
+    step: f32,
+    /// The number of frames remaining until `current` reaches `target`.
+    remaining: usize,
+}
+
+impl GainRamp {
+    /// Creates a new [`GainRamp`] that is not moving and stays at `gain`.
+    pub const fn new(gain: f32) -> Self {
+        Self {
+            current: gain,
+            target: gain,
+            step: 0.0,
+            remaining: 0,
+        }
+    }
+
+    /// Starts moving the gain towards `target` over `frames` frames.
+    ///
+    /// If `frames` is zero, the gain is set to the target immediately.
+    pub fn ramp_to(&mut self, target: f32, frames: usize) {
+        self.target = target;
+
+        if frames == 0 {
+            self.current = target;
+            self.step = 0.0;
+            self.remaining = 0;
+        } else {
+            self.step = (target - self.current) / frames as f32;
+            self.remaining = frames;
+        }
+    }
+
+    /// Returns the current gain.
+    #[inline]
+    pub fn current(&self) -> f32 {
+        self.current
+    }
+
+    /// Returns the gain that the ramp is moving towards.
+    #[inline]
+    pub fn target(&self) -> f32 {
+        self.target
+    }
+
+    /// Returns whether the ramp is still moving.
+    #[inline]
+    pub fn is_ramping(&self) -> bool {
+        self.remaining != 0
+    }
+
+    /// Multiplies the samples of the provided buffer by the gain, advancing the ramp by the
+    /// number of frames in the buffer.
+    pub fn process(&mut self, mut buf: AudioBufferMut) {
+        let frame_count = buf.frame_count();
+
+        if !self.is_ramping() {
+            if self.current != 1.0 {
+                buf.apply_gain(self.current);
+            }
+            return;
+        }
+
+        let ramping_frames = self.remaining.min(frame_count);
+
+        for channel in buf.channels_mut() {
+            let (ramping, steady) = channel.split_at_mut(ramping_frames);
+
+            let mut gain = self.current;
+            for sample in ramping {
+                gain += self.step;
+                *sample *= gain;
+            }
+
+            steady.iter_mut().for_each(|s| *s *= self.target);
+        }
+
+        self.remaining -= ramping_frames;
+        if self.remaining == 0 {
+            self.current = self.target;
+        } else {
+            self.current += self.step * ramping_frames as f32;
+        }
+    }
+}
+
+impl Default for GainRamp {
+    #[inline]
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
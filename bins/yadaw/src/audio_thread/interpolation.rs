@@ -0,0 +1,78 @@
+/// The way a signal is reconstructed between two of its frames.
+///
+/// This is used to resample signals on the fly, by reading them at fractional positions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Interpolation {
+    /// Linear interpolation between the two closest frames.
+    ///
+    /// This is the cheapest option, but it slightly attenuates high frequencies and lets some
+    /// aliasing through.
+    Linear,
+    /// Cubic (Catmull-Rom) interpolation between the four closest frames.
+    #[default]
+    Cubic,
+}
+
+impl Interpolation {
+    /// Returns the value of the provided signal at the fractional frame `pos`.
+    ///
+    /// Frames outside of `src` are considered silent.
+    pub fn sample(self, src: &[f32], pos: f64) -> f32 {
+        let index = pos.floor();
+        let t = (pos - index) as f32;
+        let index = index as isize;
+
+        let at = |offset: isize| -> f32 {
+            usize::try_from(index + offset)
+                .ok()
+                .and_then(|i| src.get(i))
+                .copied()
+                .unwrap_or(0.0)
+        };
+
+        match self {
+            Self::Linear => {
+                let (a, b) = (at(0), at(1));
+                a + (b - a) * t
+            }
+            Self::Cubic => {
+                let (p0, p1, p2, p3) = (at(-1), at(0), at(1), at(2));
+                let a = -0.5 * p0 + 1.5 * p1 - 1.5 * p2 + 0.5 * p3;
+                let b = p0 - 2.5 * p1 + 2.0 * p2 - 0.5 * p3;
+                let c = -0.5 * p0 + 0.5 * p2;
+                ((a * t + b) * t + c) * t + p1
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::Interpolation, std::f64::consts::TAU};
+
+    /// Returns `frame_count` frames of a 1 kHz sine wave sampled at `frame_rate`.
+    fn tone(frame_rate: f64, frame_count: usize) -> Vec<f32> {
+        (0..frame_count)
+            .map(|i| (TAU * 1000.0 * i as f64 / frame_rate).sin() as f32)
+            .collect()
+    }
+
+    #[test]
+    fn resampled_tone_keeps_its_frequency() {
+        let src = tone(48000.0, 4800);
+        let expected = tone(44100.0, 4400);
+        let step = 48000.0 / 44100.0;
+
+        for interpolation in [Interpolation::Linear, Interpolation::Cubic] {
+            // The first frames are skipped because the frames before the start of the source
+            // are considered silent.
+            for (i, &expected) in expected.iter().enumerate().skip(4) {
+                let actual = interpolation.sample(&src, i as f64 * step);
+                assert!(
+                    (actual - expected).abs() < 5e-3,
+                    "{interpolation:?}: frame {i} is {actual}, expected {expected}",
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use {
+    crate::audio_thread::AudioBufferMut,
+    std::sync::atomic::{self, AtomicU32, AtomicUsize, Ordering},
+};
+
+/// The maximum number of channels that [`Levels`] can measure.
+///
+/// Channels past this limit are ignored.
+pub const LEVELS_MAX_CHANNELS: usize = 8;
+
+/// The peak and RMS levels of a buffer produced by the audio thread.
+///
+/// The values are linear amplitudes. Converting them to decibels is up to the caller.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Levels {
+    /// The number of channels that were measured.
+    channel_count: usize,
+    /// The peak amplitude of each channel.
+    peak: [f32; LEVELS_MAX_CHANNELS],
+    /// The root mean square of each channel.
+    rms: [f32; LEVELS_MAX_CHANNELS],
+}
+
+impl Levels {
+    /// Measures the levels of the provided buffer.
+    pub fn measure(buf: &AudioBufferMut) -> Self {
+        let mut levels = Self {
+            channel_count: buf.channel_count().min(LEVELS_MAX_CHANNELS),
+            ..Self::default()
+        };
+
+        let frame_count = buf.frame_count().max(1) as f32;
+        for (i, channel) in buf.channels().take(LEVELS_MAX_CHANNELS).enumerate() {
+            let (peak, sum_sq) = channel.iter().fold((0.0f32, 0.0f32), |(peak, sum_sq), &s| {
+                (peak.max(s.abs()), sum_sq + s * s)
+            });
+            levels.peak[i] = peak;
+            levels.rms[i] = (sum_sq / frame_count).sqrt();
+        }
+
+        levels
+    }
+
+    /// Returns the number of channels that were measured.
+    #[inline]
+    pub fn channel_count(&self) -> usize {
+        self.channel_count
+    }
+
+    /// Returns the peak amplitude of each channel.
+    #[inline]
+    pub fn peak(&self) -> &[f32] {
+        &self.peak[..self.channel_count]
+    }
+
+    /// Returns the root mean square of each channel.
+    #[inline]
+    pub fn rms(&self) -> &[f32] {
+        &self.rms[..self.channel_count]
+    }
+}
+
+/// A single-slot channel holding the latest [`Levels`] published by the audio thread.
+///
+/// The slot is a sequence lock: the writer never blocks nor allocates, and readers retry when
+/// they race with a write. Only one thread may publish to the slot.
+#[derive(Default)]
+pub struct LevelsSlot {
+    /// Incremented before and after each write. An odd value means that a write is in
+    /// progress.
+    sequence: AtomicUsize,
+    /// The number of channels of the latest levels.
+    channel_count: AtomicUsize,
+    /// The bits of the peak amplitude of each channel.
+    peak: [AtomicU32; LEVELS_MAX_CHANNELS],
+    /// The bits of the root mean square of each channel.
+    rms: [AtomicU32; LEVELS_MAX_CHANNELS],
+}
+
+impl LevelsSlot {
+    /// Publishes new levels, replacing the previous ones.
+    ///
+    /// # Remarks
+    ///
+    /// This function must only be called from a single thread (the audio thread).
+    pub fn publish(&self, levels: &Levels) {
+        let seq = self.sequence.load(Ordering::Relaxed);
+        self.sequence.store(seq.wrapping_add(1), Ordering::Relaxed);
+        atomic::fence(Ordering::Release);
+
+        self.channel_count
+            .store(levels.channel_count, Ordering::Relaxed);
+        for i in 0..LEVELS_MAX_CHANNELS {
+            self.peak[i].store(levels.peak[i].to_bits(), Ordering::Relaxed);
+            self.rms[i].store(levels.rms[i].to_bits(), Ordering::Relaxed);
+        }
+
+        self.sequence.store(seq.wrapping_add(2), Ordering::Release);
+    }
+
+    /// Reads the latest levels.
+    ///
+    /// # Returns
+    ///
+    /// Returns the levels along with their sequence number. The sequence number changes every
+    /// time new levels are published, and is `0` when no levels have been published yet.
+    pub fn read(&self) -> (usize, Levels) {
+        loop {
+            let seq = self.sequence.load(Ordering::Acquire);
+            if seq % 2 != 0 {
+                std::hint::spin_loop();
+                continue;
+            }
+
+            let mut levels = Levels {
+                channel_count: self
+                    .channel_count
+                    .load(Ordering::Relaxed)
+                    .min(LEVELS_MAX_CHANNELS),
+                ..Levels::default()
+            };
+            for i in 0..LEVELS_MAX_CHANNELS {
+                levels.peak[i] = f32::from_bits(self.peak[i].load(Ordering::Relaxed));
+                levels.rms[i] = f32::from_bits(self.rms[i].load(Ordering::Relaxed));
+            }
+
+            atomic::fence(Ordering::Acquire);
+            if self.sequence.load(Ordering::Relaxed) == seq {
+                return (seq, levels);
+            }
+        }
+    }
+}
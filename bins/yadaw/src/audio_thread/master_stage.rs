@@ -0,0 +1,96 @@
+use {crate::audio_thread::AudioBufferMut, std::time::Duration};
+
+/// The time it takes for the gain reduction of the [`MasterSaturation::Limiter`] to recover
+/// by about 63% once the signal falls back below the ceiling.
+const LIMITER_RELEASE: Duration = Duration::from_millis(80);
+
+/// The way the master stage of the audio thread keeps the output within `[-1.0, 1.0]`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MasterSaturation {
+    /// Samples outside of the range are clamped to it.
+    ///
+    /// Samples that are already within the range are left untouched, making this the only
+    /// mode that is bit-exact for signals that do not clip.
+    #[default]
+    HardClip,
+    /// Samples are passed through a `tanh` curve.
+    ///
+    /// This rounds off peaks smoothly, at the cost of slightly coloring the whole signal.
+    SoftClip,
+    /// A peak limiter without look-ahead.
+    ///
+    /// The gain is reduced instantly when a frame would exceed the range, and recovers
+    /// exponentially over [`LIMITER_RELEASE`]. The same gain is applied to all channels to
+    /// preserve the stereo image.
+    Limiter,
+}
+
+/// The last processing stage of the audio thread, responsible for preventing the output from
+/// going outside of the `[-1.0, 1.0]` range.
+#[derive(Debug, Clone, Copy)]
+pub struct MasterStage {
+    /// The selected saturation mode.
+    saturation: MasterSaturation,
+    /// The gain currently applied by the limiter.
+    limiter_gain: f32,
+    /// The factor used to move the limiter gain back towards `1.0` for every frame.
+    limiter_release: f32,
+}
+
+impl MasterStage {
+    /// Creates a new [`MasterStage`].
+    ///
+    /// # Parameters
+    ///
+    /// - `frame_rate`: The frame rate of the processed signal.
+    ///
+    /// - `saturation`: The way the output is kept within `[-1.0, 1.0]`.
+    pub fn new(frame_rate: f64, saturation: MasterSaturation) -> Self {
+        let release_frames = LIMITER_RELEASE.as_secs_f64() * frame_rate;
+
+        Self {
+            saturation,
+            limiter_gain: 1.0,
+            limiter_release: (-1.0 / release_frames).exp() as f32,
+        }
+    }
+
+    /// Processes the provided buffer in place.
+    pub fn process(&mut self, mut buf: AudioBufferMut) {
+        match self.saturation {
+            MasterSaturation::HardClip => {
+                buf.channels_mut()
+                    .for_each(|c| c.iter_mut().for_each(|s| *s = s.clamp(-1.0, 1.0)));
+            }
+            MasterSaturation::SoftClip => {
+                buf.channels_mut()
+                    .for_each(|c| c.iter_mut().for_each(|s| *s = s.tanh()));
+            }
+            MasterSaturation::Limiter => self.limit(buf),
+        }
+    }
+
+    /// Applies the peak limiter to the provided buffer.
+    fn limit(&mut self, mut buf: AudioBufferMut) {
+        let mut gain = self.limiter_gain;
+
+        for mut frame in buf.frames_mut() {
+            let peak = frame.iter().fold(0.0f32, |acc, s| acc.max(s.abs()));
+            let target = if peak > 1.0 { 1.0 / peak } else { 1.0 };
+
+            if target < gain {
+                gain = target;
+            } else {
+                gain = target + (gain - target) * self.limiter_release;
+            }
+
+            // The clamp only catches rounding errors, the gain already keeps the frame within
+            // the range.
+            frame
+                .iter_mut()
+                .for_each(|s| *s = (*s * gain).clamp(-1.0, 1.0));
+        }
+
+        self.limiter_gain = gain;
+    }
+}
@@ -1,4 +1,13 @@
-use crate::audio_thread::one_shot_player::OneShotPlayer;
+use {
+    crate::audio_thread::one_shot_player::OneShotPlayer,
+    std::{
+        sync::{
+            Arc,
+            atomic::{AtomicBool, Ordering},
+        },
+        time::Duration,
+    },
+};
 
 mod driver;
 pub use self::driver::*;
@@ -6,31 +15,165 @@ pub use self::driver::*;
 mod audio_buffer;
 pub use self::audio_buffer::*;
 
+mod audio_node;
+pub use self::audio_node::*;
+
+mod biquad;
+pub use self::biquad::*;
+
+mod command_queue;
+pub use self::command_queue::*;
+
+mod gain_ramp;
+pub use self::gain_ramp::*;
+
+mod interpolation;
+pub use self::interpolation::*;
+
+mod levels;
+pub use self::levels::*;
+
+mod master_stage;
+pub use self::master_stage::*;
+
 mod one_shot_player;
 pub use self::one_shot_player::*;
 
+mod pan;
+pub use self::pan::*;
+
+mod ring_buffer;
+pub use self::ring_buffer::*;
+
+mod test_tone;
+pub use self::test_tone::*;
+
+mod transport;
+pub use self::transport::*;
+
+/// The duration of the fades applied when the audio thread starts or stops producing audio
+/// (for example when the output device changes).
+pub const DEVICE_FADE_DURATION: Duration = Duration::from_millis(20);
+
 /// An event that might occur from the audio thread.
 #[derive(Debug, Clone, Copy)]
 pub enum AudioThreadEvent {
     /// The number of one-shot objects that are currently playing has changed.
     OneShotCountChanged(usize),
+    /// The levels of the last buffer produced by the audio thread.
+    ///
+    /// Those are not sent to the window because its event queue is not lock-free. They are
+    /// retrieved with [`poll_levels`] instead.
+    Levels(Levels),
+    /// The position of the transport, in frames.
+    ///
+    /// Like [`Levels`](Self::Levels), this is not sent to the window. It's retrieved with
+    /// [`TransportControls::poll_moved`] instead.
+    TransportMoved(u64),
+}
+
+/// The shared state used to control an [`AudioThread`] from other threads.
+#[derive(Default)]
+pub struct AudioThreadControls {
+    /// When set, the audio thread should start fading out its output.
+    ///
+    /// The audio thread will automatically clear this flag to acknowledge the operation.
+    fade_out: AtomicBool,
+    /// Set by the audio thread once it has completely faded out and only produces silence.
+    silent: AtomicBool,
+    /// Whether bit-perfect playback is allowed.
+    bit_perfect: AtomicBool,
+    /// Set by the audio thread when the last buffer it produced was passed through without any
+    /// processing.
+    passing_through: AtomicBool,
+    /// The levels of the last buffer produced by the audio thread.
+    levels: LevelsSlot,
+}
+
+impl AudioThreadControls {
+    /// Requests the audio thread to fade out its output over [`DEVICE_FADE_DURATION`].
+    #[inline]
+    pub fn request_fade_out(&self) {
+        self.fade_out.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns whether the audio thread has finished fading out.
+    #[inline]
+    pub fn is_silent(&self) -> bool {
+        self.silent.load(Ordering::Acquire)
+    }
+
+    /// Sets whether the audio thread is allowed to pass samples through without processing them.
+    ///
+    /// See [`AudioThread::fill_buffer`] for the exact conditions under which this happens.
+    #[inline]
+    pub fn set_bit_perfect(&self, yes: bool) {
+        self.bit_perfect.store(yes, Ordering::Relaxed);
+    }
+
+    /// Returns whether the last buffer produced by the audio thread was bit-perfect.
+    #[inline]
+    pub fn is_passing_through(&self) -> bool {
+        self.passing_through.load(Ordering::Relaxed)
+    }
+
+    /// Returns the levels of the last buffer produced by the audio thread, along with their
+    /// sequence number.
+    ///
+    /// See [`LevelsSlot::read`].
+    #[inline]
+    pub fn levels(&self) -> (usize, Levels) {
+        self.levels.read()
+    }
 }
 
 /// The state of the audio thread.
 struct AudioThread {
     /// The number of frames the audio thread is processing per second.
     frame_rate: f64,
+    /// The sample format of the stream that the audio thread is rendering to.
+    sample_format: advice::Format,
+
+    /// The controls shared with the UI thread.
+    controls: Arc<AudioThreadControls>,
+    /// The master gain used to fade the output in and out.
+    fade: GainRamp,
+    /// The stage that keeps the output within `[-1.0, 1.0]`.
+    master: MasterStage,
 
-    /// The player responsible for playing one-shot samples.
-    one_shot_player: OneShotPlayer,
+    /// The nodes of the audio graph, processed in order.
+    ///
+    /// The first node is the player responsible for playing one-shot samples.
+    nodes: Vec<Box<dyn AudioNode>>,
+    /// The clock tracking the position of the playhead.
+    transport: Transport,
 }
 
 impl AudioThread {
     /// Creates a new audio thread.
-    pub fn new(frame_rate: f64) -> Self {
+    ///
+    /// The output of the audio thread is faded in over [`DEVICE_FADE_DURATION`], and kept within
+    /// `[-1.0, 1.0]` according to `saturation`.
+    pub fn new(
+        config: &advice::StreamConfig,
+        controls: Arc<AudioThreadControls>,
+        saturation: MasterSaturation,
+    ) -> Self {
+        let frame_rate = config.frame_rate;
+        let mut fade = GainRamp::new(0.0);
+        fade.ramp_to(1.0, fade_frame_count(frame_rate));
+
         Self {
             frame_rate,
-            one_shot_player: OneShotPlayer::default(),
+            sample_format: config.format,
+            controls,
+            fade,
+            master: MasterStage::new(frame_rate, saturation),
+            nodes: vec![Box::new(OneShotPlayer::new(
+                config.channel_count as usize,
+                config.buffer_size.map_or(0, |size| size.get() as usize),
+            ))],
+            transport: Transport::new(),
         }
     }
 
@@ -43,13 +186,97 @@ impl AudioThread {
     ///
     /// This means that any operation that involves the kernel (unless it's specifically a real-time
     /// safe operation) should be avoided at all cost. That includes memory allocations, I/O, etc.
+    ///
+    /// # Bit-perfect playback
+    ///
+    /// When bit-perfect playback is enabled through [`AudioThreadControls::set_bit_perfect`],
+    /// the samples are passed through without any processing (no fade, no saturation) if all of
+    /// the following conditions are met:
+    ///
+    /// - The audio graph is made of a single node, which reports a
+    ///   [`native_format`](AudioNode::native_format) (meaning that it does not modify its
+    ///   samples). For the [`OneShotPlayer`], this requires exactly one one-shot object to be
+    ///   playing.
+    ///
+    /// - The frame rate of that object is exactly the frame rate of the stream (no resampling).
+    ///
+    /// - Its channel count is exactly the channel count of the stream (no up/down-mixing).
+    ///
+    /// - Its sample format is exactly the sample format of the stream. Conversions between the
+    ///   supported stream formats and the internal `f32` format are lossless.
+    ///
+    /// - The output is not fading in or out.
+    ///
+    /// Otherwise, the regular processing path is used.
+    ///
+    /// # Levels
+    ///
+    /// The levels of every buffer are measured once it has been completely processed, and are
+    /// made available to the UI thread through [`poll_levels`].
+    ///
+    /// # Transport
+    ///
+    /// The commands sent to the [`Transport`] are processed before the buffer is produced, and
+    /// its position is advanced by the number of frames of the buffer once it has been produced.
     fn fill_buffer(&mut self, mut buf: AudioBufferMut) {
         buf.channels_mut().for_each(|c| c.fill(0.0));
 
-        self.one_shot_player
-            .fill_buffer(self.frame_rate, buf.reborrow());
+        self.transport.process_commands();
+
+        for node in &mut self.nodes {
+            node.process(self.frame_rate, buf.reborrow());
+        }
+
+        if self.controls.fade_out.swap(false, Ordering::Relaxed) {
+            self.fade.ramp_to(0.0, fade_frame_count(self.frame_rate));
+        }
+
+        let passing_through = self.can_pass_through(buf.channel_count());
+        self.controls
+            .passing_through
+            .store(passing_through, Ordering::Relaxed);
+        if !passing_through {
+            self.fade.process(buf.reborrow());
+
+            if self.fade.target() == 0.0 && !self.fade.is_ramping() {
+                self.controls.silent.store(true, Ordering::Release);
+            }
+
+            self.master.process(buf.reborrow());
+        }
+
+        self.controls.levels.publish(&Levels::measure(&buf));
+
+        self.transport.advance(self.frame_rate, buf.frame_count());
+    }
 
-        buf.channels_mut()
-            .for_each(|c| c.iter_mut().for_each(|s| *s = s.clamp(-1.0, 1.0)));
+    /// Returns whether the content of the buffer that was just filled can be sent to the device
+    /// as-is.
+    ///
+    /// See [`fill_buffer`](Self::fill_buffer) for the exact conditions.
+    fn can_pass_through(&self, channel_count: usize) -> bool {
+        if !self.controls.bit_perfect.load(Ordering::Relaxed) {
+            return false;
+        }
+
+        if self.fade.is_ramping() || self.fade.current() != 1.0 {
+            return false;
+        }
+
+        let native_format = match self.nodes.as_slice() {
+            [node] => node.native_format(),
+            _ => None,
+        };
+
+        native_format.is_some_and(|format| {
+            format.frame_rate == self.frame_rate
+                && format.channel_count == channel_count
+                && format.sample_format == self.sample_format
+        })
     }
 }
+
+/// Returns the number of frames that [`DEVICE_FADE_DURATION`] spans at the provided frame rate.
+fn fade_frame_count(frame_rate: f64) -> usize {
+    (DEVICE_FADE_DURATION.as_secs_f64() * frame_rate) as usize
+}
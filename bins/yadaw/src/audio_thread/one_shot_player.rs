@@ -1,9 +1,21 @@
 use {
-    crate::audio_thread::{AudioBufferMut, AudioThreadEvent},
+    crate::audio_thread::{
+        AudioBufferMut, AudioBufferOwned, AudioNode, AudioThreadEvent, CommandQueue,
+    },
     parking_lot::Mutex,
-    std::sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    std::{
+        sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        time::Duration,
+    },
 };
 
+/// The maximum number of commands that can be waiting to be processed by the audio thread.
+const MAX_PENDING_COMMANDS: usize = 64;
+
+/// The number of frames allocated up front for the scratch buffer of the [`OneShotPlayer`]
+/// when the buffer size of the stream is not known in advance.
+const DEFAULT_SCRATCH_FRAMES: usize = 4096;
+
 /// Describes a one-shot object that can be played once.
 pub trait OneShot: Send {
     /// Fills the provided buffer with audio data.
@@ -11,6 +23,128 @@ pub trait OneShot: Send {
     /// The provided [`AudioBufferMut`] should not be overwritten, instead data should be added to
     /// it, ignoring eventual clipping.
     fn fill_buffer(&mut self, frame_rate: f64, buf: AudioBufferMut) -> bool;
+
+    /// Returns the format of the samples produced by this object, if it is eligible for
+    /// bit-perfect playback.
+    ///
+    /// By returning `Some(_)`, the object promises that [`fill_buffer`](Self::fill_buffer) adds
+    /// its original samples to the buffer without modifying them in any way (no gain, no
+    /// resampling, no effects).
+    fn native_format(&self) -> Option<NativeFormat> {
+        None
+    }
+
+    /// Changes the speed at which the object is played back.
+    ///
+    /// A speed of `2.0` plays the object twice as fast, an octave higher. This is independent
+    /// from the frame rate passed to [`fill_buffer`](Self::fill_buffer), which the object must
+    /// still account for.
+    ///
+    /// # Returns
+    ///
+    /// Whether the object supports variable-speed playback. The default implementation ignores
+    /// the request and returns `false`.
+    fn set_speed(&mut self, speed: f64) -> bool {
+        let _ = speed;
+        false
+    }
+}
+
+/// The slowest speed at which a [`OneShot`] object can be played back.
+///
+/// Requests for slower (or negative) speeds are clamped to this value.
+pub const MIN_SPEED: f64 = 1.0 / 64.0;
+
+/// The format of the samples produced by a [`OneShot`] object, before they are converted to
+/// the internal format of the audio thread.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NativeFormat {
+    /// The number of frames per second.
+    pub frame_rate: f64,
+    /// The number of channels.
+    pub channel_count: usize,
+    /// The format that the samples were originally encoded in.
+    pub sample_format: advice::Format,
+}
+
+/// A command sent to the [`OneShotPlayer`] through a [`PlayHandle`].
+#[derive(Debug, Clone, Copy)]
+enum VoiceCommand {
+    /// Fades the voice out, then stops it.
+    FadeOut {
+        /// The identifier of the voice to fade out.
+        voice: u64,
+        /// The duration of the fade.
+        duration: Duration,
+    },
+    /// Changes the playback speed of the voice.
+    SetSpeed {
+        /// The identifier of the voice.
+        voice: u64,
+        /// The new speed of the voice.
+        speed: f64,
+    },
+}
+
+impl VoiceCommand {
+    /// Returns the identifier of the voice targeted by the command.
+    #[inline]
+    fn voice(&self) -> u64 {
+        match *self {
+            Self::FadeOut { voice, .. } | Self::SetSpeed { voice, .. } => voice,
+        }
+    }
+}
+
+/// A handle to a one-shot object scheduled with [`OneShotPlayerControls::play`].
+///
+/// The handle can be used to stop the object early. Dropping it does not stop the object,
+/// which keeps playing until it finishes naturally.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PlayHandle {
+    /// The identifier of the voice playing the object.
+    voice: u64,
+}
+
+impl PlayHandle {
+    /// Stops the object immediately.
+    #[inline]
+    pub fn stop(&self) {
+        self.fade_out(Duration::ZERO);
+    }
+
+    /// Fades the object out linearly over the provided duration, then stops it.
+    ///
+    /// This does nothing if the object has already finished playing. If the object is already
+    /// fading out, the fade continues from its current gain.
+    pub fn fade_out(&self, duration: Duration) {
+        let command = VoiceCommand::FadeOut {
+            voice: self.voice,
+            duration,
+        };
+
+        if CONTROLS.commands.push(command).is_err() {
+            log::warn!("Too many pending one-shot commands, ignoring a fade-out request");
+        }
+    }
+
+    /// Changes the speed at which the object is played back.
+    ///
+    /// A speed of `2.0` plays the object twice as fast (an octave up), and `0.5` twice as slow
+    /// (an octave down). The speed is clamped to [`MIN_SPEED`].
+    ///
+    /// This does nothing if the object has already finished playing, or if it does not support
+    /// variable-speed playback (see [`OneShot::set_speed`]).
+    pub fn set_speed(&self, speed: f64) {
+        let command = VoiceCommand::SetSpeed {
+            voice: self.voice,
+            speed: speed.max(MIN_SPEED),
+        };
+
+        if CONTROLS.commands.push(command).is_err() {
+            log::warn!("Too many pending one-shot commands, ignoring a speed change");
+        }
+    }
 }
 
 /// The shared state used to control the one shot player.
@@ -22,7 +156,11 @@ pub struct OneShotPlayerControls {
     clear: AtomicBool,
 
     /// A list of new one-shot objects to play.
-    to_play: Mutex<Vec<Box<dyn OneShot>>>,
+    to_play: Mutex<Vec<Voice>>,
+    /// The identifier of the next voice to be created.
+    next_voice: AtomicU64,
+    /// The commands sent through [`PlayHandle`]s.
+    commands: CommandQueue<VoiceCommand, MAX_PENDING_COMMANDS>,
 
     /// The number of objects that are currently playing.
     ///
@@ -36,18 +174,34 @@ impl OneShotPlayerControls {
         Self {
             clear: AtomicBool::new(false),
             to_play: Mutex::new(Vec::new()),
+            next_voice: AtomicU64::new(0),
+            commands: CommandQueue::new(),
             now_playing: AtomicUsize::new(0),
         }
     }
 
     /// Schedules an one-shot object to be played.
-    pub fn play(&self, obj: impl 'static + OneShot) {
-        self.play_boxed(Box::new(obj));
+    ///
+    /// The returned [`PlayHandle`] can be used to stop the object early.
+    pub fn play(&self, obj: impl 'static + OneShot) -> PlayHandle {
+        self.play_boxed(Box::new(obj))
     }
 
     /// Schedules an one-shot object to be played.
-    pub fn play_boxed(&self, obj: Box<dyn OneShot>) {
-        self.to_play.lock().push(obj);
+    ///
+    /// The returned [`PlayHandle`] can be used to stop the object early.
+    pub fn play_boxed(&self, obj: Box<dyn OneShot>) -> PlayHandle {
+        let mut to_play = self.to_play.lock();
+        // The identifier is allocated while holding the lock so that pending voices are always
+        // sorted by identifier. The audio thread relies on that to tell apart voices that have
+        // not been handed to it yet from voices that already finished.
+        let id = self.next_voice.fetch_add(1, Ordering::Relaxed);
+        to_play.push(Voice {
+            id,
+            obj,
+            fade: None,
+        });
+        PlayHandle { voice: id }
     }
 
     /// Requests the one shot player to clear its playing list.
@@ -71,23 +225,181 @@ pub fn one_shot_controls() -> &'static OneShotPlayerControls {
     &CONTROLS
 }
 
+/// A linear fade applied to a [`Voice`] before it stops.
+#[derive(Debug, Clone, Copy)]
+struct VoiceFade {
+    /// The gain applied to the next frame.
+    gain: f32,
+    /// The amount removed from `gain` for every frame.
+    step: f32,
+}
+
+/// A one-shot object that is playing.
+struct Voice {
+    /// The identifier of the voice, referenced by its [`PlayHandle`].
+    id: u64,
+    /// The object producing the samples.
+    obj: Box<dyn OneShot>,
+    /// The fade applied to the voice, if it was requested to stop.
+    fade: Option<VoiceFade>,
+}
+
+impl Voice {
+    /// Starts fading the voice out over the provided duration.
+    fn fade_out(&mut self, frame_rate: f64, duration: Duration) {
+        let gain = self.fade.map_or(1.0, |fade| fade.gain);
+        let frames = (duration.as_secs_f64() * frame_rate) as usize;
+
+        self.fade = Some(VoiceFade {
+            gain: if frames == 0 { 0.0 } else { gain },
+            step: gain / frames.max(1) as f32,
+        });
+    }
+
+    /// Adds the samples of the voice to the provided buffer.
+    ///
+    /// `scratch` is used to render the object before its fade is applied.
+    ///
+    /// # Returns
+    ///
+    /// Whether the voice is still playing.
+    fn fill_buffer(
+        &mut self,
+        frame_rate: f64,
+        mut buf: AudioBufferMut,
+        scratch: &mut AudioBufferOwned,
+    ) -> bool {
+        let Some(fade) = self.fade.as_mut() else {
+            return self.obj.fill_buffer(frame_rate, buf);
+        };
+
+        if fade.gain <= 0.0 {
+            return false;
+        }
+
+        let frame_count = buf.frame_count();
+        scratch.clear();
+        // This only allocates if the stream requests more frames than it announced.
+        scratch.resize(frame_count, 0.0);
+        let playing = self
+            .obj
+            .fill_buffer(frame_rate, scratch.as_audio_buffer_mut());
+
+        for (dst, src) in buf.channels_mut().zip(scratch.channels()) {
+            let mut gain = fade.gain;
+            for (dst, src) in dst.iter_mut().zip(src) {
+                *dst += *src * gain;
+                gain = (gain - fade.step).max(0.0);
+            }
+        }
+
+        fade.gain = (fade.gain - fade.step * frame_count as f32).max(0.0);
+        playing && fade.gain > 0.0
+    }
+}
+
+/// Applies a command to the voice it targets.
+///
+/// # Parameters
+///
+/// - `next_voice`: The identifier following the last voice that was moved to `playing`.
+///
+/// # Returns
+///
+/// `false` if the voice is still waiting to be handed to the audio thread, in which case the
+/// command must be applied later. Commands targeting voices that already finished are simply
+/// ignored.
+fn apply_command(
+    playing: &mut [Voice],
+    next_voice: u64,
+    frame_rate: f64,
+    command: VoiceCommand,
+) -> bool {
+    if command.voice() >= next_voice {
+        return false;
+    }
+
+    let Some(voice) = playing.iter_mut().find(|v| v.id == command.voice()) else {
+        return true;
+    };
+
+    match command {
+        VoiceCommand::FadeOut { duration, .. } => voice.fade_out(frame_rate, duration),
+        VoiceCommand::SetSpeed { speed, .. } => {
+            voice.obj.set_speed(speed);
+        }
+    }
+
+    true
+}
+
 /// A simple one-shot player (e.g. sample player).
 ///
 /// Makes sure to release resources once they are no longer needed.
-#[derive(Default)]
 pub struct OneShotPlayer {
     /// The list of objects that are currently playing.
-    playing: Vec<Box<dyn OneShot>>,
+    playing: Vec<Voice>,
+    /// The identifier following the last voice that was moved to `playing`.
+    next_voice: u64,
+    /// The commands targeting voices that have not been handed to the audio thread yet.
+    ///
+    /// This happens when the list of voices to play was locked by another thread. The capacity
+    /// of this list is allocated up front.
+    deferred: Vec<VoiceCommand>,
+    /// The buffer in which fading voices are rendered before their fade is applied.
+    scratch: AudioBufferOwned,
+    /// The native format of the only object that was playing during the last call to
+    /// [`process`](AudioNode::process).
+    ///
+    /// This is `None` if zero or multiple objects were playing, or if the object is not
+    /// eligible for bit-perfect playback.
+    sole_native_format: Option<NativeFormat>,
 }
 
 impl OneShotPlayer {
+    /// Creates a new [`OneShotPlayer`].
+    ///
+    /// # Parameters
+    ///
+    /// - `channel_count`: The number of channels of the buffers passed to
+    ///   [`process`](AudioNode::process).
+    ///
+    /// - `frame_count`: The number of frames that the player is expected to render at once,
+    ///   or `0` if unknown.
+    ///
+    /// # Remarks
+    ///
+    /// Everything the player needs during playback is allocated here, on the thread creating
+    /// the player.
+    pub fn new(channel_count: usize, frame_count: usize) -> Self {
+        let mut scratch = AudioBufferOwned::new(channel_count);
+        scratch.reserve(if frame_count == 0 {
+            DEFAULT_SCRATCH_FRAMES
+        } else {
+            frame_count
+        });
+
+        Self {
+            playing: Vec::new(),
+            next_voice: 0,
+            deferred: Vec::with_capacity(MAX_PENDING_COMMANDS),
+            scratch,
+            sole_native_format: None,
+        }
+    }
+}
+
+impl AudioNode for OneShotPlayer {
     /// Fills the provided buffer with audio data.
     ///
     /// Data is *added* to the buffer.
-    pub fn fill_buffer(&mut self, frame_rate: f64, mut buf: AudioBufferMut) {
+    fn process(&mut self, frame_rate: f64, mut buf: AudioBufferMut) {
         let prev_playing = self.playing.len();
 
         if let Some(mut new) = CONTROLS.to_play.try_lock() {
+            if let Some(last) = new.last() {
+                self.next_voice = last.id + 1;
+            }
             // FIXME: This allocates on the audio thread. BAD!
             self.playing.append(new.as_mut());
         }
@@ -96,8 +408,30 @@ impl OneShotPlayer {
             self.playing.clear();
         }
 
+        let (playing, next_voice) = (&mut self.playing, self.next_voice);
+        self.deferred
+            .retain(|&command| !apply_command(playing, next_voice, frame_rate, command));
+
+        while let Some(command) = CONTROLS.commands.pop() {
+            if apply_command(&mut self.playing, self.next_voice, frame_rate, command) {
+                continue;
+            }
+
+            // When too many commands are waiting, the newest ones are dropped, just like when
+            // the command queue itself is full.
+            if self.deferred.len() < self.deferred.capacity() {
+                self.deferred.push(command);
+            }
+        }
+
+        self.sole_native_format = match self.playing.as_slice() {
+            [voice] if voice.fade.is_none() => voice.obj.native_format(),
+            _ => None,
+        };
+
+        let scratch = &mut self.scratch;
         self.playing
-            .retain_mut(|obj| obj.fill_buffer(frame_rate, buf.reborrow()));
+            .retain_mut(|voice| voice.fill_buffer(frame_rate, buf.reborrow(), scratch));
 
         CONTROLS
             .now_playing
@@ -108,4 +442,54 @@ impl OneShotPlayer {
                 .send_event(AudioThreadEvent::OneShotCountChanged(self.playing.len()));
         }
     }
+
+    /// Returns the native format of the only object that was playing during the last call to
+    /// [`process`](AudioNode::process).
+    ///
+    /// See [`OneShot::native_format`].
+    #[inline]
+    fn native_format(&self) -> Option<NativeFormat> {
+        self.sole_native_format
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::{OneShot, Voice, VoiceCommand, apply_command},
+        crate::audio_thread::AudioBufferMut,
+        std::time::Duration,
+    };
+
+    /// A silent object that never finishes.
+    struct Silence;
+
+    impl OneShot for Silence {
+        fn fill_buffer(&mut self, _frame_rate: f64, _buf: AudioBufferMut) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn commands_for_pending_voices_are_kept() {
+        let mut playing = vec![Voice {
+            id: 0,
+            obj: Box::new(Silence),
+            fade: None,
+        }];
+        let fade_out = |voice| VoiceCommand::FadeOut {
+            voice,
+            duration: Duration::from_millis(10),
+        };
+
+        // Voice 1 was not handed to the player yet.
+        assert!(!apply_command(&mut playing, 1, 48000.0, fade_out(1)));
+        assert!(playing[0].fade.is_none());
+
+        assert!(apply_command(&mut playing, 1, 48000.0, fade_out(0)));
+        assert!(playing[0].fade.is_some());
+
+        // Voice 1 finished playing before the command was applied.
+        assert!(apply_command(&mut playing, 2, 48000.0, fade_out(1)));
+    }
 }
@@ -0,0 +1,92 @@
+use std::f32::consts::{FRAC_PI_4, SQRT_2};
+
+/// Returns the gains to apply to the left and right channels when placing a mono signal in the
+/// stereo field.
+///
+/// `pan` ranges from `-1.0` (hard left) to `1.0` (hard right). Values outside of this range are
+/// clamped.
+///
+/// This uses a constant-power pan law: the perceived loudness of the signal does not change as
+/// it moves across the stereo field. At the center, both channels are attenuated by 3dB.
+pub fn constant_power_pan(pan: f32) -> [f32; 2] {
+    let angle = (pan.clamp(-1.0, 1.0) + 1.0) * FRAC_PI_4;
+    [angle.cos(), angle.sin()]
+}
+
+/// Returns the gains to apply to the left and right channels of a stereo signal to balance it.
+///
+/// `pan` ranges from `-1.0` (hard left) to `1.0` (hard right). Values outside of this range are
+/// clamped.
+///
+/// Unlike [`constant_power_pan`], the signal is left untouched at the center. Moving towards one
+/// side progressively attenuates the opposite channel following the same constant-power curve.
+pub fn stereo_balance(pan: f32) -> [f32; 2] {
+    let [left, right] = constant_power_pan(pan);
+    [(left * SQRT_2).min(1.0), (right * SQRT_2).min(1.0)]
+}
+
+/// Returns the gains to apply to the left and right channels of a signal with `channel_count`
+/// channels when it is played on a stereo output.
+///
+/// Mono signals are panned using [`constant_power_pan`], and stereo signals are balanced using
+/// [`stereo_balance`]. Other signals are not affected by `pan`. In every case, the gains are
+/// multiplied by `volume`.
+pub fn stereo_gains(channel_count: usize, volume: f32, pan: f32) -> [f32; 2] {
+    match channel_count {
+        1 => constant_power_pan(pan).map(|g| g * volume),
+        2 => stereo_balance(pan).map(|g| g * volume),
+        _ => [volume; 2],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::{constant_power_pan, stereo_balance, stereo_gains},
+        std::f32::consts::FRAC_1_SQRT_2,
+    };
+
+    /// Asserts that the provided gains are equal to the expected ones, within float tolerance.
+    fn assert_gains(actual: [f32; 2], expected: [f32; 2]) {
+        assert!(
+            actual
+                .iter()
+                .zip(&expected)
+                .all(|(a, e)| (a - e).abs() < 1e-6),
+            "expected {expected:?}, got {actual:?}",
+        );
+    }
+
+    #[test]
+    fn constant_power_pan_at_center() {
+        assert_gains(constant_power_pan(0.0), [FRAC_1_SQRT_2, FRAC_1_SQRT_2]);
+    }
+
+    #[test]
+    fn constant_power_pan_hard_left_and_right() {
+        assert_gains(constant_power_pan(-1.0), [1.0, 0.0]);
+        assert_gains(constant_power_pan(1.0), [0.0, 1.0]);
+    }
+
+    #[test]
+    fn constant_power_pan_keeps_power() {
+        for pan in [-0.75, -0.3, 0.1, 0.6] {
+            let [left, right] = constant_power_pan(pan);
+            assert!((left * left + right * right - 1.0).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn stereo_balance_at_center_and_sides() {
+        assert_gains(stereo_balance(0.0), [1.0, 1.0]);
+        assert_gains(stereo_balance(-1.0), [1.0, 0.0]);
+        assert_gains(stereo_balance(1.0), [0.0, 1.0]);
+    }
+
+    #[test]
+    fn stereo_gains_depend_on_channel_count() {
+        assert_gains(stereo_gains(1, 0.5, -1.0), [0.5, 0.0]);
+        assert_gains(stereo_gains(2, 0.5, 0.0), [0.5, 0.5]);
+        assert_gains(stereo_gains(6, 0.5, 1.0), [0.5, 0.5]);
+    }
+}
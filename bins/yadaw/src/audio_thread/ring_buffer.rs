@@ -0,0 +1,197 @@
+use {
+    crate::audio_thread::AudioBufferRef,
+    std::{
+        cell::UnsafeCell,
+        sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        },
+    },
+};
+
+/// The state shared between the [`RingProducer`] and the [`RingConsumer`].
+struct RingBuffer {
+    /// The samples of the ring buffer.
+    ///
+    /// The samples are stored in a planar layout: the frames of channel `c` are stored starting
+    /// at index `c * capacity`.
+    data: Box<[UnsafeCell<f32>]>,
+    /// The number of frames that the ring buffer can hold.
+    capacity: usize,
+    /// The number of channels in the ring buffer.
+    channel_count: usize,
+
+    /// The total number of frames that have been read by the consumer.
+    ///
+    /// Only the consumer writes to this value.
+    read: AtomicUsize,
+    /// The total number of frames that have been written by the producer.
+    ///
+    /// Only the producer writes to this value.
+    write: AtomicUsize,
+}
+
+// SAFETY: The producer and the consumer never access the same samples at the same time. The
+// indices are synchronized through the `read` and `write` atomics.
+unsafe impl Sync for RingBuffer {}
+
+impl RingBuffer {
+    /// Returns a pointer to the sample of the provided channel at the provided (unwrapped) frame
+    /// index.
+    ///
+    /// # Safety
+    ///
+    /// `channel` must be less than `channel_count`.
+    #[inline]
+    unsafe fn sample_ptr(&self, channel: usize, frame: usize) -> *mut f32 {
+        let index = channel * self.capacity + frame % self.capacity;
+        unsafe { self.data.get_unchecked(index).get() }
+    }
+}
+
+/// Creates a new single-producer single-consumer ring buffer of audio frames.
+///
+/// Neither side ever blocks nor allocates, which makes the [`RingConsumer`] suitable for use on
+/// the audio thread.
+///
+/// # Parameters
+///
+/// - `channel_count`: the number of channels of the frames stored in the ring buffer.
+///
+/// - `capacity`: the number of frames that the ring buffer can hold.
+pub fn ring_buffer(channel_count: usize, capacity: usize) -> (RingProducer, RingConsumer) {
+    assert!(
+        capacity > 0,
+        "The capacity of a ring buffer must not be zero"
+    );
+
+    let shared = Arc::new(RingBuffer {
+        data: (0..channel_count * capacity)
+            .map(|_| UnsafeCell::new(0.0))
+            .collect(),
+        capacity,
+        channel_count,
+        read: AtomicUsize::new(0),
+        write: AtomicUsize::new(0),
+    });
+
+    (
+        RingProducer {
+            shared: shared.clone(),
+        },
+        RingConsumer { shared },
+    )
+}
+
+/// The writing half of a ring buffer created with [`ring_buffer`].
+pub struct RingProducer {
+    /// The shared ring buffer.
+    shared: Arc<RingBuffer>,
+}
+
+impl RingProducer {
+    /// Returns the number of frames that can currently be written to the ring buffer.
+    #[inline]
+    pub fn free_frames(&self) -> usize {
+        let read = self.shared.read.load(Ordering::Acquire);
+        let write = self.shared.write.load(Ordering::Relaxed);
+        self.shared.capacity - write.wrapping_sub(read)
+    }
+
+    /// Writes as many frames of `src` as possible to the ring buffer, starting at the frame
+    /// `offset` of `src`.
+    ///
+    /// # Returns
+    ///
+    /// The number of frames that were written.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `src` does not have the same number of channels as the ring
+    /// buffer.
+    pub fn push(&mut self, src: AudioBufferRef, offset: usize) -> usize {
+        assert_eq!(
+            src.channel_count(),
+            self.shared.channel_count,
+            "The number of channels must match",
+        );
+
+        let count = self
+            .free_frames()
+            .min(src.frame_count().saturating_sub(offset));
+        let write = self.shared.write.load(Ordering::Relaxed);
+
+        for (c, channel) in src.channels().enumerate() {
+            for (i, &sample) in channel[offset..offset + count].iter().enumerate() {
+                // SAFETY: The consumer never reads the frames past `write`.
+                unsafe { *self.shared.sample_ptr(c, write.wrapping_add(i)) = sample };
+            }
+        }
+
+        self.shared
+            .write
+            .store(write.wrapping_add(count), Ordering::Release);
+        count
+    }
+}
+
+/// The reading half of a ring buffer created with [`ring_buffer`].
+pub struct RingConsumer {
+    /// The shared ring buffer.
+    shared: Arc<RingBuffer>,
+}
+
+impl RingConsumer {
+    /// Returns the number of frames that can currently be read from the ring buffer.
+    #[inline]
+    pub fn available_frames(&self) -> usize {
+        let write = self.shared.write.load(Ordering::Acquire);
+        let read = self.shared.read.load(Ordering::Relaxed);
+        write.wrapping_sub(read)
+    }
+
+    /// Returns the number of channels in the ring buffer.
+    #[inline]
+    pub fn channel_count(&self) -> usize {
+        self.shared.channel_count
+    }
+
+    /// Reads up to `count` frames from the ring buffer.
+    ///
+    /// # Callback
+    ///
+    /// The provided callback is called once per sample read.
+    ///
+    /// ```rust,ignore
+    /// fn callback(channel: usize, frame: usize, sample: f32);
+    /// ```
+    ///
+    /// # Returns
+    ///
+    /// The number of frames that were read.
+    pub fn pop(&mut self, count: usize, mut f: impl FnMut(usize, usize, f32)) -> usize {
+        let count = self.available_frames().min(count);
+        let read = self.shared.read.load(Ordering::Relaxed);
+
+        for c in 0..self.shared.channel_count {
+            for i in 0..count {
+                // SAFETY: The producer never writes to the frames between `read` and `write`.
+                f(c, i, unsafe {
+                    *self.shared.sample_ptr(c, read.wrapping_add(i))
+                });
+            }
+        }
+
+        self.shared
+            .read
+            .store(read.wrapping_add(count), Ordering::Release);
+        count
+    }
+
+    /// Discards all the frames that are currently available.
+    #[inline]
+    pub fn clear(&mut self) {
+        let write = self.shared.write.load(Ordering::Acquire);
+        self.shared.read.store(write, Ordering::Release);
+    }
+}
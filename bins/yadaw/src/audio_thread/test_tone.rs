@@ -0,0 +1,157 @@
+use {
+    crate::audio_thread::{AudioBufferMut, OneShot},
+    std::time::Duration,
+};
+
+/// The signal generated by a [`TestTone`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TestToneKind {
+    /// A sine wave at the provided frequency, in hertz.
+    Sine(f64),
+    /// White noise.
+    WhiteNoise,
+}
+
+/// A [`OneShot`] object that generates a test signal.
+///
+/// This is useful to check that audio is correctly flowing to the output device without having
+/// to load any file. The same signal is written to every channel of the output.
+///
+/// Like any other one-shot object, it is played through the one-shot player:
+///
+/// ```ignore
+/// one_shot_controls().play(TestTone::sine(440.0, 0.25).with_duration(Duration::from_secs(1)));
+/// ```
+///
+/// Test tones that have no duration play until the one-shot player is cleared.
+#[derive(Debug, Clone)]
+pub struct TestTone {
+    /// The kind of signal to generate.
+    kind: TestToneKind,
+    /// The peak amplitude of the signal.
+    amplitude: f32,
+    /// How long the tone should play for.
+    duration: Option<Duration>,
+
+    /// The number of frames that have been generated so far.
+    elapsed_frames: u64,
+    /// The phase of the sine wave, in the range `[0, 1)`.
+    ///
+    /// This is kept across buffers so that the generated signal is continuous.
+    phase: f64,
+    /// The state of the random number generator used for white noise.
+    rng_state: u32,
+}
+
+impl TestTone {
+    /// Creates a new [`TestTone`] generating the provided signal.
+    ///
+    /// `amplitude` is the peak amplitude of the signal. For a sine wave, the RMS level of the
+    /// signal is `amplitude / sqrt(2)`.
+    pub fn new(kind: TestToneKind, amplitude: f32) -> Self {
+        Self {
+            kind,
+            amplitude,
+            duration: None,
+            elapsed_frames: 0,
+            phase: 0.0,
+            rng_state: 0x9e37_79b9,
+        }
+    }
+
+    /// Creates a new [`TestTone`] generating a sine wave at the provided frequency (in hertz).
+    #[inline]
+    pub fn sine(frequency: f64, amplitude: f32) -> Self {
+        Self::new(TestToneKind::Sine(frequency), amplitude)
+    }
+
+    /// Creates a new [`TestTone`] generating white noise.
+    #[inline]
+    pub fn white_noise(amplitude: f32) -> Self {
+        Self::new(TestToneKind::WhiteNoise, amplitude)
+    }
+
+    /// Stops the tone after the provided duration.
+    pub fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+
+    /// Returns the kind of signal generated by the tone.
+    #[inline]
+    pub fn kind(&self) -> TestToneKind {
+        self.kind
+    }
+
+    /// Returns the peak amplitude of the generated signal.
+    #[inline]
+    pub fn amplitude(&self) -> f32 {
+        self.amplitude
+    }
+
+    /// Generates the next sample of the signal, without the amplitude applied.
+    fn next_sample(&mut self, frame_rate: f64) -> f32 {
+        match self.kind {
+            TestToneKind::Sine(frequency) => {
+                let sample = (self.phase * std::f64::consts::TAU).sin() as f32;
+                self.phase = (self.phase + frequency / frame_rate).fract();
+                sample
+            }
+            TestToneKind::WhiteNoise => {
+                // xorshift32
+                self.rng_state ^= self.rng_state << 13;
+                self.rng_state ^= self.rng_state >> 17;
+                self.rng_state ^= self.rng_state << 5;
+                (self.rng_state as f32 / u32::MAX as f32) * 2.0 - 1.0
+            }
+        }
+    }
+}
+
+impl OneShot for TestTone {
+    fn fill_buffer(&mut self, frame_rate: f64, mut buf: AudioBufferMut) -> bool {
+        let total_frames = self
+            .duration
+            .map_or(u64::MAX, |d| (d.as_secs_f64() * frame_rate) as u64);
+        let remaining = total_frames.saturating_sub(self.elapsed_frames);
+        let frame_count = (buf.frame_count() as u64).min(remaining) as usize;
+
+        for frame_index in 0..frame_count {
+            let sample = self.next_sample(frame_rate) * self.amplitude;
+            for channel in buf.channels_mut() {
+                // SAFETY: `frame_count` is at most the number of frames in the buffer.
+                unsafe { *channel.get_unchecked_mut(frame_index) += sample };
+            }
+        }
+
+        self.elapsed_frames += frame_count as u64;
+        self.elapsed_frames < total_frames
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::TestTone,
+        crate::audio_thread::{AudioBufferOwned, OneShot},
+        std::f32::consts::SQRT_2,
+    };
+
+    #[test]
+    fn sine_rms_matches_amplitude() {
+        let mut tone = TestTone::sine(1000.0, 0.5);
+
+        // 100 full periods, generated over multiple buffers.
+        let mut samples = Vec::new();
+        let mut buf = AudioBufferOwned::new(1);
+        for _ in 0..10 {
+            buf.clear();
+            buf.resize(480, 0.0);
+            assert!(tone.fill_buffer(48000.0, buf.as_audio_buffer_mut()));
+            samples.extend_from_slice(buf.channel(0).unwrap());
+        }
+
+        let rms = (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt();
+        assert!((rms - 0.5 / SQRT_2).abs() < 1e-4, "RMS is {rms}");
+    }
+}
@@ -0,0 +1,340 @@
+use {
+    crate::audio_thread::{AudioThreadEvent, CommandQueue},
+    std::sync::atomic::{AtomicBool, AtomicU64, Ordering},
+};
+
+/// The maximum number of commands that can be waiting to be processed by the audio thread.
+const MAX_PENDING_COMMANDS: usize = 32;
+
+/// The number of times per second the position of a playing transport is published to the
+/// other threads.
+///
+/// Changes caused by commands (play, stop, seek...) are always published immediately.
+const PUBLISH_RATE: f64 = 60.0;
+
+/// The tempo used before any tempo is set.
+const DEFAULT_BEATS_PER_MINUTE: f64 = 120.0;
+
+/// A region of the timeline that is played in a loop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LoopRegion {
+    /// The first frame of the region.
+    pub start: u64,
+    /// The frame right after the last frame of the region.
+    pub end: u64,
+}
+
+impl LoopRegion {
+    /// Returns the number of frames in the region.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.end.saturating_sub(self.start)
+    }
+
+    /// Returns whether the region contains no frames.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// A command sent to the [`Transport`] through [`TransportControls`].
+#[derive(Debug, Clone, Copy)]
+enum TransportCommand {
+    /// Starts advancing the position.
+    Play,
+    /// Stops advancing the position.
+    Stop,
+    /// Moves the position to the provided frame.
+    Seek(u64),
+    /// Changes the tempo, in beats per minute.
+    SetTempo(f64),
+    /// Changes the loop region.
+    SetLoopRegion(Option<LoopRegion>),
+}
+
+/// The shared state used to control the transport of the audio thread.
+pub struct TransportControls {
+    /// The commands waiting to be processed by the audio thread.
+    commands: CommandQueue<TransportCommand, MAX_PENDING_COMMANDS>,
+
+    /// The position of the transport, in frames.
+    ///
+    /// This is written to regularly by the audio thread.
+    position: AtomicU64,
+    /// Whether the transport is playing.
+    playing: AtomicBool,
+    /// The bits of the tempo of the transport, in beats per minute.
+    beats_per_minute: AtomicU64,
+    /// The first frame of the loop region.
+    loop_start: AtomicU64,
+    /// The end of the loop region, or `0` if looping is disabled.
+    loop_end: AtomicU64,
+
+    /// The position returned by the last call to [`poll_moved`](Self::poll_moved).
+    ///
+    /// `u64::MAX` if it was never called.
+    position_seen: AtomicU64,
+}
+
+impl TransportControls {
+    /// Creates a new [`TransportControls`] instance.
+    pub const fn new() -> Self {
+        Self {
+            commands: CommandQueue::new(),
+            position: AtomicU64::new(0),
+            playing: AtomicBool::new(false),
+            beats_per_minute: AtomicU64::new(DEFAULT_BEATS_PER_MINUTE.to_bits()),
+            loop_start: AtomicU64::new(0),
+            loop_end: AtomicU64::new(0),
+            position_seen: AtomicU64::new(u64::MAX),
+        }
+    }
+
+    /// Sends a command to the audio thread.
+    fn send(&self, command: TransportCommand) {
+        if self.commands.push(command).is_err() {
+            log::warn!("Too many pending transport commands, ignoring {command:?}");
+        }
+    }
+
+    /// Starts playing from the current position.
+    #[inline]
+    pub fn play(&self) {
+        self.send(TransportCommand::Play);
+    }
+
+    /// Stops playing, keeping the current position.
+    #[inline]
+    pub fn stop(&self) {
+        self.send(TransportCommand::Stop);
+    }
+
+    /// Moves the position of the transport to the provided frame.
+    ///
+    /// This does not change whether the transport is playing.
+    #[inline]
+    pub fn seek(&self, frame: u64) {
+        self.send(TransportCommand::Seek(frame));
+    }
+
+    /// Sets the tempo of the transport, in beats per minute.
+    ///
+    /// Non-positive tempos are ignored.
+    pub fn set_tempo(&self, beats_per_minute: f64) {
+        if beats_per_minute > 0.0 {
+            self.send(TransportCommand::SetTempo(beats_per_minute));
+        }
+    }
+
+    /// Sets the region of the timeline that is played in a loop.
+    ///
+    /// Passing `None`, or an empty region, disables looping.
+    #[inline]
+    pub fn set_loop_region(&self, region: Option<LoopRegion>) {
+        self.send(TransportCommand::SetLoopRegion(
+            region.filter(|r| !r.is_empty()),
+        ));
+    }
+
+    /// Returns the position of the transport, in frames.
+    ///
+    /// While the transport is playing, this lags behind the actual position by up to
+    /// `1 / PUBLISH_RATE` seconds (plus the duration of a buffer).
+    #[inline]
+    pub fn position(&self) -> u64 {
+        self.position.load(Ordering::Relaxed)
+    }
+
+    /// Returns whether the transport is playing.
+    #[inline]
+    pub fn is_playing(&self) -> bool {
+        self.playing.load(Ordering::Relaxed)
+    }
+
+    /// Returns the tempo of the transport, in beats per minute.
+    #[inline]
+    pub fn tempo(&self) -> f64 {
+        f64::from_bits(self.beats_per_minute.load(Ordering::Relaxed))
+    }
+
+    /// Returns the region of the timeline that is played in a loop, if any.
+    pub fn loop_region(&self) -> Option<LoopRegion> {
+        let region = LoopRegion {
+            start: self.loop_start.load(Ordering::Relaxed),
+            end: self.loop_end.load(Ordering::Relaxed),
+        };
+        (!region.is_empty()).then_some(region)
+    }
+
+    /// Returns an [`AudioThreadEvent::TransportMoved`] event if the position of the transport
+    /// changed since the last call to this function.
+    ///
+    /// This never blocks the audio thread, and is meant to be polled by the UI thread (for
+    /// example once per frame). The audio thread only publishes the position of a playing
+    /// transport `PUBLISH_RATE` times per second, which throttles the events.
+    pub fn poll_moved(&self) -> Option<AudioThreadEvent> {
+        let position = self.position();
+        if self.position_seen.swap(position, Ordering::Relaxed) == position {
+            return None;
+        }
+        Some(AudioThreadEvent::TransportMoved(position))
+    }
+}
+
+impl Default for TransportControls {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+static CONTROLS: TransportControls = TransportControls::new();
+
+/// Returns the controls for the transport of the audio thread.
+#[inline]
+pub fn transport_controls() -> &'static TransportControls {
+    &CONTROLS
+}
+
+/// The sample-accurate clock of the audio thread.
+///
+/// The transport tracks the position of the playhead on the timeline, in frames. While it is
+/// playing, the position is advanced by the number of frames of every buffer produced by the
+/// audio thread.
+///
+/// The state of the transport is restored from [`TransportControls`] when it is created,
+/// meaning that it survives a change of output device.
+pub struct Transport {
+    /// The position of the transport, in frames.
+    position: u64,
+    /// Whether the transport is playing.
+    playing: bool,
+    /// The tempo of the transport, in beats per minute.
+    beats_per_minute: f64,
+    /// The region of the timeline that is played in a loop.
+    loop_region: Option<LoopRegion>,
+
+    /// The number of frames to process before the position is published again.
+    frames_until_publish: u64,
+}
+
+impl Transport {
+    /// Creates a new [`Transport`], restoring the last state published to the
+    /// [`TransportControls`].
+    pub fn new() -> Self {
+        Self {
+            position: CONTROLS.position(),
+            playing: CONTROLS.is_playing(),
+            beats_per_minute: CONTROLS.tempo(),
+            loop_region: CONTROLS.loop_region(),
+            frames_until_publish: 0,
+        }
+    }
+
+    /// Returns the position of the transport, in frames.
+    ///
+    /// While the audio thread is producing a buffer, this is the position of the first frame of
+    /// that buffer.
+    #[inline]
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Returns whether the transport is playing.
+    #[inline]
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    /// Returns the tempo of the transport, in beats per minute.
+    #[inline]
+    pub fn tempo(&self) -> f64 {
+        self.beats_per_minute
+    }
+
+    /// Returns the number of frames in a beat at the provided frame rate.
+    #[inline]
+    pub fn frames_per_beat(&self, frame_rate: f64) -> f64 {
+        frame_rate * 60.0 / self.beats_per_minute
+    }
+
+    /// Returns the region of the timeline that is played in a loop, if any.
+    #[inline]
+    pub fn loop_region(&self) -> Option<LoopRegion> {
+        self.loop_region
+    }
+
+    /// Processes the commands sent through the [`TransportControls`].
+    ///
+    /// This must be called at the beginning of every buffer.
+    pub fn process_commands(&mut self) {
+        let mut changed = false;
+
+        while let Some(command) = CONTROLS.commands.pop() {
+            match command {
+                TransportCommand::Play => self.playing = true,
+                TransportCommand::Stop => self.playing = false,
+                TransportCommand::Seek(frame) => self.position = frame,
+                TransportCommand::SetTempo(bpm) => self.beats_per_minute = bpm,
+                TransportCommand::SetLoopRegion(region) => self.loop_region = region,
+            }
+            changed = true;
+        }
+
+        if changed {
+            self.publish();
+        }
+    }
+
+    /// Advances the position of the transport by the provided number of frames.
+    ///
+    /// This must be called once the buffer has been produced. Nothing happens if the transport
+    /// is stopped.
+    ///
+    /// When the position crosses the end of the loop region, it wraps around to its start,
+    /// keeping the frames that went past the end. Positions that were already past the end of
+    /// the loop region (for example after seeking there) are not affected.
+    pub fn advance(&mut self, frame_rate: f64, frame_count: usize) {
+        if !self.playing {
+            return;
+        }
+
+        let prev = self.position;
+        self.position += frame_count as u64;
+
+        let position = self.position;
+        if let Some(region) = self
+            .loop_region
+            .filter(|r| prev < r.end && position >= r.end)
+        {
+            self.position = region.start + (position - region.end) % region.len();
+        }
+
+        self.frames_until_publish = self.frames_until_publish.saturating_sub(frame_count as u64);
+        if self.frames_until_publish == 0 {
+            self.publish();
+            self.frames_until_publish = (frame_rate / PUBLISH_RATE).max(1.0) as u64;
+        }
+    }
+
+    /// Publishes the state of the transport to the [`TransportControls`].
+    fn publish(&self) {
+        let (loop_start, loop_end) = self.loop_region.map_or((0, 0), |r| (r.start, r.end));
+
+        CONTROLS.position.store(self.position, Ordering::Relaxed);
+        CONTROLS.playing.store(self.playing, Ordering::Relaxed);
+        CONTROLS
+            .beats_per_minute
+            .store(self.beats_per_minute.to_bits(), Ordering::Relaxed);
+        CONTROLS.loop_start.store(loop_start, Ordering::Relaxed);
+        CONTROLS.loop_end.store(loop_end, Ordering::Relaxed);
+    }
+}
+
+impl Default for Transport {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
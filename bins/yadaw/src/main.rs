@@ -47,11 +47,22 @@ fn main() {
         debug_assert!(MAIN_WINDOW.get().is_none());
         let _ = MAIN_WINDOW.set(window.make_proxy());
 
+        self::ui::restore_window_geometry(&window);
+        window.set_close_handler({
+            let window = window.clone();
+            move || self::ui::save_window_geometry(&window)
+        });
+
+        #[cfg(feature = "hot-reload")]
+        if let Err(err) = self::settings::watch() {
+            log::warn!("Failed to watch the settings file: {err}");
+        }
+
         //
         // Setup the audio thread.
         //
 
-        self::audio_thread::initialize_audio_thread();
+        self::audio_thread::initialize_audio_thread(&ctx);
 
         //
         // Play the welcome sound.
@@ -65,12 +76,8 @@ fn main() {
         // Show the window.
         //
 
-        window.set_root_element(kui::elem! {
-            kui::elements::anchor {
-                align_center;
-                child: self::ui::magic_menu::magic_menu();
-            }
-        });
+        window.set_clear_color(self::ui::palette(&ctx).background);
+        window.set_root_element(self::ui::root_element());
 
         window.show();
     });
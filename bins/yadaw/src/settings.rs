@@ -1,7 +1,8 @@
 use {
+    parking_lot::RwLock,
     serde::{Deserialize, Serialize, de::DeserializeOwned},
     serde_inline_default::serde_inline_default,
-    std::{path::Path, sync::OnceLock},
+    std::{path::Path, sync::Arc},
 };
 
 /// Yadaw settings.
@@ -50,10 +51,13 @@ impl Settings {
 
     /// Loads the settings from the default path.
     pub fn load() -> Result<Self, SettingsError> {
-        Self::load_from_path("settings.toml".as_ref())
+        Self::load_from_path(DEFAULT_PATH.as_ref())
     }
 }
 
+/// The path of the settings file.
+const DEFAULT_PATH: &str = "settings.toml";
+
 /// An error that might occur when attempting to load the settings from a file.
 #[derive(Debug, thiserror::Error)]
 pub enum SettingsError {
@@ -84,11 +88,13 @@ fn serde_default<T: DeserializeOwned>() -> T {
 }
 
 /// The global settings instance.
-static SETTINGS: OnceLock<Settings> = OnceLock::new();
+///
+/// This is `None` until [`initialize`] is called.
+static SETTINGS: RwLock<Option<Arc<Settings>>> = RwLock::new(None);
 
 /// Initializes the global settings.
 pub fn initialize() {
-    debug_assert!(SETTINGS.get().is_none());
+    debug_assert!(SETTINGS.read().is_none());
 
     let s = match Settings::load() {
         Ok(s) => s,
@@ -98,13 +104,108 @@ pub fn initialize() {
         }
     };
 
-    let _ = SETTINGS.set(s);
+    *SETTINGS.write() = Some(Arc::new(s));
 }
 
-/// Returns a reference to the global settings instance.
+/// Returns the current global settings instance.
+///
+/// # Remarks
+///
+/// The settings may be reloaded while the application is running (see [`watch`]). The returned
+/// value is a snapshot that won't be updated in that case.
 #[inline]
-pub fn get() -> &'static Settings {
+pub fn get() -> Arc<Settings> {
     SETTINGS
-        .get()
+        .read()
+        .clone()
         .expect("Attempted to use `SETTINGS` before it was initialized")
 }
+
+/// The event sent to the main window when the settings have been reloaded from the disk.
+#[derive(Debug, Clone)]
+pub struct SettingsChanged {
+    /// The settings that were in use before the reload.
+    pub previous: Arc<Settings>,
+    /// The new settings.
+    pub current: Arc<Settings>,
+}
+
+/// Reloads the global settings from the disk.
+///
+/// If the settings file cannot be loaded, the previous settings are kept. Otherwise, if they
+/// changed, a [`SettingsChanged`] event is sent to the main window.
+pub fn reload() {
+    let new = match Settings::load() {
+        Ok(s) => s,
+        Err(e) => {
+            log::warn!("Failed to reload the settings file, keeping the previous settings: {e}");
+            return;
+        }
+    };
+
+    let previous = {
+        let mut lock = SETTINGS.write();
+        let Some(previous) = lock.as_ref() else {
+            return;
+        };
+
+        if **previous == new {
+            return;
+        }
+
+        let previous = previous.clone();
+        *lock = Some(Arc::new(new));
+        previous
+    };
+
+    log::info!("Reloaded the settings file");
+
+    crate::main_window().send_event(SettingsChanged {
+        previous,
+        current: get(),
+    });
+}
+
+/// Starts watching the settings file for changes, calling [`reload`] whenever it is modified.
+///
+/// The watcher runs until the application exits.
+///
+/// # Remarks
+///
+/// The directory containing the settings file is watched rather than the file itself because
+/// many editors replace the file instead of writing to it, which would break a watch on the
+/// file.
+#[cfg(feature = "hot-reload")]
+pub fn watch() -> notify::Result<()> {
+    use {
+        notify::{EventKind, RecursiveMode, Watcher},
+        parking_lot::Mutex,
+    };
+
+    static WATCHER: Mutex<Option<notify::RecommendedWatcher>> = Mutex::new(None);
+
+    let path = std::path::absolute(DEFAULT_PATH)?;
+    let dir = path.parent().unwrap_or(&path).to_path_buf();
+
+    let mut watcher = notify::recommended_watcher({
+        let path = path.clone();
+        move |res: notify::Result<notify::Event>| match res {
+            Ok(event) => {
+                let relevant = matches!(
+                    event.kind,
+                    EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_),
+                ) && event.paths.iter().any(|p| *p == path);
+
+                if relevant {
+                    reload();
+                }
+            }
+            Err(e) => log::warn!("Error while watching the settings file: {e}"),
+        }
+    })?;
+
+    watcher.watch(&dir, RecursiveMode::NonRecursive)?;
+    *WATCHER.lock() = Some(watcher);
+
+    Ok(())
+}
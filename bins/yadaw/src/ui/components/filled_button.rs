@@ -1,8 +1,10 @@
-use kui::{
-    elem,
-    elements::{Length, button, div, interactive::make_appearance, label},
-    peniko::Color,
-    winit::window::CursorIcon,
+use {
+    crate::ui::palette,
+    kui::{
+        elem,
+        elements::{Length, button, div, interactive::make_appearance, label},
+        winit::window::CursorIcon,
+    },
 };
 
 /// A button element that can be clicked.
@@ -67,24 +69,25 @@ where
                             padding_bottom: 8px;
                             padding_left: 16px;
                             padding_right: 16px;
-                            brush: "#fff";
                             width: self.width;
 
                             label {
                                 text: self.text;
                                 font_stack: "Funnel Sans";
-                                brush: "#000";
                                 align_middle;
                                 inline: !has_width;
                             }
                         }
                     },
                     move |el, cx, state, _| {
-                        if state.hover() {
-                            el.style.brush = Some(Color::from_rgb8(222, 222, 222).into());
+                        let palette = palette(&cx.ctx);
+                        let background = if state.hover() {
+                            palette.control_hover
                         } else {
-                            el.style.brush = Some(Color::from_rgb8(255, 255, 255).into());
-                        }
+                            palette.control
+                        };
+                        el.style.brush = Some(background.into());
+                        el.child.style_mut().brush = palette.text_on(background).into();
                         if state.just_entered() {
                             cx.window.set_cursor(CursorIcon::Pointer);
                         }
@@ -1,5 +1,6 @@
 mod filled_button;
 mod text_input;
+mod waveform;
 
 /// A button that has a filled background.
 pub fn filled_button() -> self::filled_button::Builder<()> {
@@ -10,3 +11,8 @@ pub fn filled_button() -> self::filled_button::Builder<()> {
 pub fn text_input() -> self::text_input::Builder<()> {
     self::text_input::Builder::default()
 }
+
+/// An element that draws the waveform of an audio file.
+pub fn waveform(file: std::sync::Arc<crate::audio_file::AudioFile>) -> self::waveform::Waveform {
+    self::waveform::Waveform::new(file)
+}
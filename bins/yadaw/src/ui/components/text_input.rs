@@ -1,8 +1,12 @@
-use kui::{
-    IntoElement, elem,
-    elements::{Length, div, interactive::make_appearance, label, text_input},
-    peniko::Color,
-    winit::window::CursorIcon,
+use {
+    crate::ui::palette,
+    kui::{
+        IntoElement, elem,
+        elements::{
+            Length, div, interactive::make_appearance, label, text_input, text_input::EditableText,
+        },
+        winit::window::CursorIcon,
+    },
 };
 
 /// A text input element.
@@ -51,7 +55,6 @@ where
                 appearance: make_appearance(
                     elem!{
                         div {
-                            border_brush: "#555";
                             border_thickness: 2upx;
                             padding_top: 8px;
                             padding_bottom: 8px;
@@ -63,24 +66,38 @@ where
                             label {
                                 text: self.placeholder.as_str();
                                 font_stack: "Funnel Sans";
-                                brush: "#555";
                             }
                         }
                     },
-                    move |elem, cx, state, text: &str| {
+                    move |elem, cx, state, text: &EditableText| {
+                        let palette = palette(&cx.ctx);
+                        elem.child.style_mut().brush = if text.is_empty() {
+                            palette.text_muted.into()
+                        } else {
+                            palette.text.into()
+                        };
+                        elem.style.border_brush = Some(if state.focused() {
+                            palette.border_focused.into()
+                        } else {
+                            palette.border.into()
+                        });
+
                         if state.value_changed() {
                             if text.is_empty() {
                                 elem.child.set_text(self.placeholder.clone());
-                                elem.child.style_mut().brush = Color::from_rgb8(0x55, 0x55, 0x55).into();
-                                cx.window.request_redraw();
                             } else {
-                                elem.child.set_text(text);
-                                elem.child.style_mut().brush = Color::from_rgb8(0xff, 0xff, 0xff).into();
-                                cx.window.request_redraw();
+                                elem.child.set_text(text.text());
                             }
 
-                            (self.on_change)(text);
+                            (self.on_change)(text.text());
                         }
+
+                        // The text scrolls to keep the caret visible while the input is focused.
+                        // The placeholder is never selected, and the caret stays before it.
+                        elem.child.set_caret_brush(Some(palette.text.into()));
+                        elem.child.set_caret(state.focused().then_some(text.caret()));
+                        elem.child.set_selection(text.selection());
+                        cx.window.request_redraw();
                         if state.just_entered() {
                             cx.window.set_cursor(CursorIcon::Text);
                             cx.window.request_redraw();
@@ -89,12 +106,7 @@ where
                             cx.window.set_cursor(CursorIcon::Default);
                             cx.window.request_redraw();
                         }
-                        if state.just_focused() {
-                            elem.style.border_brush = Some(Color::from_rgb8(0xff, 0xff, 0xff).into());
-                            cx.window.request_redraw();
-                        }
-                        if state.just_unfocused() {
-                            elem.style.border_brush = Some(Color::from_rgb8(0x55, 0x55, 0x55).into());
+                        if state.just_focused() || state.just_unfocused() {
                             cx.window.request_redraw();
                         }
                     }
@@ -0,0 +1,219 @@
+use {
+    crate::{audio_file::AudioFile, ui::palette},
+    kui::{
+        ElemContext, Element, LayoutContext, SizeHint,
+        elements::Length,
+        kurbo::{Affine, BezPath, Point, Rect, Size},
+        peniko::{Brush, Fill},
+    },
+    std::sync::Arc,
+};
+
+/// The minimum and maximum sample values of a range of frames.
+type Peak = (f32, f32);
+
+/// The peaks of every channel of an audio file, for a given number of pixel columns.
+struct Peaks {
+    /// The number of columns the peaks were computed for.
+    columns: usize,
+    /// The number of frames covered by a single column.
+    frames_per_column: f64,
+    /// The peaks of each channel, one per column.
+    lanes: Vec<Vec<Peak>>,
+}
+
+impl Peaks {
+    /// Computes the peaks of the provided audio file.
+    fn compute(file: &AudioFile, columns: usize, frames_per_column: f64) -> Self {
+        let data = file.data();
+        let frame_count = data.frame_count();
+
+        let lanes = data
+            .channels()
+            .map(|samples| {
+                (0..columns)
+                    .map(|column| {
+                        let start = (column as f64 * frames_per_column) as usize;
+                        let end = ((column + 1) as f64 * frames_per_column) as usize;
+                        let end = end.max(start + 1).min(frame_count);
+
+                        samples
+                            .get(start..end)
+                            .unwrap_or_default()
+                            .iter()
+                            .fold((0.0f32, 0.0f32), |(min, max), &s| (min.min(s), max.max(s)))
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Self {
+            columns,
+            frames_per_column,
+            lanes,
+        }
+    }
+}
+
+/// An element that draws the waveform of an audio file.
+///
+/// Each channel of the file is drawn in its own lane, stacked from top to bottom. The peaks
+/// displayed for each pixel column are cached and only recomputed when the width of the element
+/// or its zoom level changes.
+pub struct Waveform {
+    /// The audio file to draw.
+    file: Arc<AudioFile>,
+    /// The number of frames displayed per logical pixel.
+    ///
+    /// When `None`, the whole file is stretched to the width of the element.
+    frames_per_pixel: Option<f64>,
+    /// The preferred height of the element.
+    height: Length,
+    /// The brush used to fill the waveform.
+    ///
+    /// When `None`, the accent color of the palette is used.
+    brush: Option<Brush>,
+
+    /// The bounds of the element.
+    rect: Rect,
+    /// The number of frames covered by a single physical pixel.
+    frames_per_column: f64,
+    /// The cached peaks of the file.
+    peaks: Option<Peaks>,
+}
+
+impl Waveform {
+    /// Creates a new [`Waveform`] element drawing the provided file.
+    pub fn new(file: Arc<AudioFile>) -> Self {
+        Self {
+            file,
+            frames_per_pixel: None,
+            height: Length::Pixels(64.0),
+            brush: None,
+            rect: Rect::ZERO,
+            frames_per_column: 0.0,
+            peaks: None,
+        }
+    }
+
+    /// Sets the horizontal zoom level of the waveform, in frames per logical pixel.
+    ///
+    /// When set, the preferred width of the element is the length of the file at this zoom
+    /// level. Otherwise, the whole file is stretched to the width of the element.
+    pub fn zoom(mut self, frames_per_pixel: impl Into<Option<f64>>) -> Self {
+        self.frames_per_pixel = frames_per_pixel.into().filter(|&f| f > 0.0);
+        self
+    }
+
+    /// Sets the preferred height of the element.
+    pub fn height(mut self, height: Length) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// Sets the brush used to fill the waveform.
+    pub fn brush(mut self, brush: impl Into<Brush>) -> Self {
+        self.brush = Some(brush.into());
+        self
+    }
+
+    /// Returns the peaks for the current size of the element, recomputing them if needed.
+    fn peaks(&mut self) -> &Peaks {
+        let columns = self.rect.width().ceil().max(0.0) as usize;
+        let frames_per_column = self.frames_per_column;
+
+        let outdated = self.peaks.as_ref().is_none_or(|peaks| {
+            peaks.columns != columns || peaks.frames_per_column != frames_per_column
+        });
+        if outdated {
+            self.peaks = Some(Peaks::compute(&self.file, columns, frames_per_column));
+        }
+
+        self.peaks.as_ref().unwrap()
+    }
+}
+
+impl Element for Waveform {
+    fn size_hint(
+        &mut self,
+        _elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        space: Size,
+    ) -> SizeHint {
+        let width = match self.frames_per_pixel {
+            Some(frames_per_pixel) => {
+                self.file.data().frame_count() as f64 / frames_per_pixel
+                    * layout_context.scale_factor
+            }
+            None if space.width.is_finite() => space.width,
+            None => 0.0,
+        };
+        let height = self.height.resolve(&layout_context);
+
+        SizeHint {
+            preferred: Size::new(width, height),
+            min: Size::ZERO,
+            max: Size::new(f64::INFINITY, f64::INFINITY),
+        }
+    }
+
+    fn place(
+        &mut self,
+        _elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        pos: Point,
+        size: Size,
+    ) {
+        self.rect = Rect::from_origin_size(pos, size);
+        self.frames_per_column = match self.frames_per_pixel {
+            Some(frames_per_pixel) => frames_per_pixel / layout_context.scale_factor,
+            None if size.width > 0.0 => self.file.data().frame_count() as f64 / size.width,
+            None => 0.0,
+        };
+    }
+
+    #[inline]
+    fn hit_test(&self, point: Point) -> bool {
+        self.rect.contains(point)
+    }
+
+    fn draw(&mut self, elem_context: &ElemContext, scene: &mut kui::vello::Scene) {
+        let rect = self.rect;
+        let brush = self
+            .brush
+            .clone()
+            .unwrap_or_else(|| palette(&elem_context.ctx).accent.into());
+
+        let peaks = self.peaks();
+        if peaks.columns == 0 || peaks.lanes.is_empty() {
+            return;
+        }
+
+        let lane_height = rect.height() / peaks.lanes.len() as f64;
+
+        for (lane, columns) in peaks.lanes.iter().enumerate() {
+            let center = rect.y0 + lane_height * (lane as f64 + 0.5);
+            let half = lane_height / 2.0;
+            let y = |s: f32| center - s.clamp(-1.0, 1.0) as f64 * half;
+            let x = |column: usize| rect.x0 + column as f64 + 0.5;
+
+            // Trace the maximums from left to right, then the minimums back from right to left.
+            let mut path = BezPath::new();
+            for (column, &(_, max)) in columns.iter().enumerate() {
+                let point = Point::new(x(column), y(max));
+                if column == 0 {
+                    path.move_to(point);
+                } else {
+                    path.line_to(point);
+                }
+            }
+            for (column, &(min, max)) in columns.iter().enumerate().rev() {
+                // Make sure that silent columns are still visible as a thin line.
+                path.line_to(Point::new(x(column), y(min).max(y(max) + 1.0)));
+            }
+            path.close_path();
+
+            scene.fill(Fill::NonZero, Affine::IDENTITY, &brush, None, &path);
+        }
+    }
+}
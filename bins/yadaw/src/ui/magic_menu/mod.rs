@@ -44,6 +44,7 @@ impl MagicMenu {
 /// Builds the magic menu element.
 pub fn magic_menu() -> impl kui::Element {
     let mut state = MagicMenu::default();
+    let has_results = !state.results.is_empty();
 
     kui::elem! {
         div {
@@ -62,9 +63,11 @@ pub fn magic_menu() -> impl kui::Element {
                     on_change: move |s| state.search(s);
                 }
 
-                flex {
-                    gap: 8px;
-                    vertical;
+                if has_results {
+                    flex {
+                        gap: 8px;
+                        vertical;
+                    }
                 }
             }
         }
@@ -1,7 +1,69 @@
-use kui::elements::text::TextResource;
+use {
+    crate::audio_file::AudioFile,
+    kui::elements::text::TextResource,
+    std::{path::PathBuf, sync::Arc},
+};
 
 pub mod components;
 pub mod magic_menu;
+pub mod sequencer;
+
+mod theme;
+pub use self::theme::*;
+
+mod window_geometry;
+pub use self::window_geometry::*;
+
+/// Builds the root element of the main window.
+pub fn root_element() -> impl kui::Element {
+    kui::elem! {
+        kui::elements::drop_target {
+            on_drop: |_, ev| preview_dropped_files(&ev.paths);
+            child: kui::elem! {
+                kui::elements::anchor {
+                    align_center;
+                    child: self::magic_menu::magic_menu();
+                }
+            };
+        }
+    }
+}
+
+/// The peak amplitude that previewed files are normalized to (about -6 dBFS).
+const PREVIEW_PEAK: f32 = 0.5;
+
+/// Plays the audio files that have been dropped on the main window.
+///
+/// The files are loaded in the background and normalized to [`PREVIEW_PEAK`], so that samples
+/// recorded at wildly different levels are auditioned at a consistent volume. Files that cannot
+/// be played are skipped.
+fn preview_dropped_files(paths: &[PathBuf]) {
+    for path in paths {
+        let path = path.clone();
+        rayon::spawn(move || match AudioFile::load(path.clone()) {
+            Ok(file) => {
+                Arc::new(file.normalized(PREVIEW_PEAK)).play(1.0);
+            }
+            Err(err) if err.is_unsupported() => {
+                log::info!("Ignoring dropped file `{}`: {err}", path.display());
+            }
+            Err(err) => log::error!("Failed to load dropped file `{}`: {err}", path.display()),
+        });
+    }
+}
+
+/// Switches the theme of the application to the provided mode.
+///
+/// The UI of the window is rebuilt so that every component picks up the new colors.
+pub fn set_theme_mode(ctx: &kui::Ctx, window: &kui::Window, mode: ThemeMode) {
+    let palette = ctx.with_resource_or_default(|theme: &mut Theme| {
+        theme.set_mode(mode);
+        theme.palette
+    });
+
+    window.set_clear_color(palette.background);
+    window.set_root_element(root_element());
+}
 
 /// Initializes the fonts for the application.
 pub fn initialize_fonts(ctx: &kui::Ctx) -> std::io::Result<()> {
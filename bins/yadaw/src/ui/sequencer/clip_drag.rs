@@ -0,0 +1,80 @@
+use {
+    super::{SequencerEvent, SequencerUiState},
+    kui::ElemContext,
+};
+
+/// The state of a clip being dragged along the timeline of the sequencer.
+///
+/// The start of the clip snaps to the grid of the sequencer, unless the Alt key is held.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClipDrag {
+    /// The start frame of the clip when the drag began.
+    origin_frame: f64,
+    /// The horizontal position of the pointer when the drag began, in physical pixels.
+    origin_x: f64,
+    /// The offset last reported through [`SequencerEvent::SetDragOffset`].
+    offset: f64,
+}
+
+impl ClipDrag {
+    /// Begins dragging a clip.
+    ///
+    /// # Parameters
+    ///
+    /// - `origin_frame`: The start frame of the clip.
+    ///
+    /// - `origin_x`: The horizontal position of the pointer, in physical pixels.
+    pub fn new(origin_frame: f64, origin_x: f64) -> Self {
+        Self {
+            origin_frame,
+            origin_x,
+            offset: 0.0,
+        }
+    }
+
+    /// Returns the offset of the clip since the beginning of the drag, in frames.
+    #[inline]
+    pub fn offset(&self) -> f64 {
+        self.offset
+    }
+
+    /// Returns the current start frame of the clip.
+    #[inline]
+    pub fn start_frame(&self) -> f64 {
+        self.origin_frame + self.offset
+    }
+
+    /// Updates the drag for the provided pointer position, in physical pixels.
+    ///
+    /// When the snapped position of the clip changes, a [`SequencerEvent::SetDragOffset`] event
+    /// is sent to the window.
+    ///
+    /// # Returns
+    ///
+    /// The new start frame of the clip.
+    pub fn update(
+        &mut self,
+        elem_context: &ElemContext,
+        state: &SequencerUiState,
+        x: f64,
+        scale_factor: f64,
+    ) -> f64 {
+        let pixels = (x - self.origin_x) / scale_factor;
+        let mut start = (self.origin_frame + pixels * state.timeline.frames_per_pixel).max(0.0);
+
+        if !elem_context.window.keyboard_modifiers().alt_key() {
+            start = state.snap(start);
+        }
+
+        let offset = start - self.origin_frame;
+        if offset != self.offset {
+            self.offset = offset;
+            elem_context
+                .window
+                .make_proxy()
+                .send_event(SequencerEvent::SetDragOffset(offset));
+        }
+
+        start
+    }
+}
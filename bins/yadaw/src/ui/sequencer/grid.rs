@@ -0,0 +1,105 @@
+/// The smallest distance between two grid lines, in logical pixels.
+///
+/// When zoomed out, the grid is made coarser until its lines are at least this far apart.
+const MIN_GRID_SPACING: f64 = 12.0;
+
+/// The largest distance between two grid lines, in logical pixels.
+///
+/// When zoomed in, the grid is subdivided until its lines are at most this far apart, so that
+/// snapping does not prevent precise placement.
+const MAX_GRID_SPACING: f64 = 96.0;
+
+/// The tempo and meter of the project, used to place the lines of the grid.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tempo {
+    /// The number of frames per second of the project.
+    pub frame_rate: f64,
+    /// The number of beats per minute.
+    pub beats_per_minute: f64,
+    /// The number of beats in a bar.
+    pub beats_per_bar: u32,
+}
+
+impl Default for Tempo {
+    fn default() -> Self {
+        Self {
+            frame_rate: 48000.0,
+            beats_per_minute: 120.0,
+            beats_per_bar: 4,
+        }
+    }
+}
+
+impl Tempo {
+    /// Returns the number of frames in a beat.
+    #[inline]
+    pub fn frames_per_beat(&self) -> f64 {
+        self.frame_rate * 60.0 / self.beats_per_minute
+    }
+
+    /// Returns the number of frames in a bar.
+    #[inline]
+    pub fn frames_per_bar(&self) -> f64 {
+        self.frames_per_beat() * self.beats_per_bar as f64
+    }
+}
+
+/// The division of the grid that clips snap to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GridDivision {
+    /// Grid lines are placed on every bar.
+    Bars,
+    /// Grid lines are placed on every beat.
+    #[default]
+    Beats,
+    /// Grid lines are placed on every fraction of a beat.
+    ///
+    /// For instance, `Subdivision(4)` places lines on sixteenth notes in 4/4.
+    Subdivision(u32),
+    /// Grid lines are placed every provided number of frames, regardless of the tempo.
+    Frames(u64),
+    /// There is no grid.
+    Off,
+}
+
+impl GridDivision {
+    /// Returns the distance between two grid lines, in frames.
+    ///
+    /// Returns `None` when there is no grid.
+    pub fn frames(self, tempo: &Tempo) -> Option<f64> {
+        let frames = match self {
+            Self::Bars => tempo.frames_per_bar(),
+            Self::Beats => tempo.frames_per_beat(),
+            Self::Subdivision(n) => tempo.frames_per_beat() / n.max(1) as f64,
+            Self::Frames(n) => n as f64,
+            Self::Off => return None,
+        };
+
+        (frames.is_finite() && frames > 0.0).then_some(frames)
+    }
+}
+
+/// Adapts the spacing of the grid to the current zoom level.
+///
+/// The spacing is doubled while the lines are closer than [`MIN_GRID_SPACING`], and halved
+/// while they are further apart than [`MAX_GRID_SPACING`] (but never below one frame).
+///
+/// # Parameters
+///
+/// - `frames`: The spacing of the grid, in frames.
+///
+/// - `frames_per_pixel`: The number of frames per logical pixel.
+pub fn effective_grid_spacing(mut frames: f64, frames_per_pixel: f64) -> f64 {
+    if frames_per_pixel <= 0.0 {
+        return frames;
+    }
+
+    while frames / frames_per_pixel < MIN_GRID_SPACING {
+        frames *= 2.0;
+    }
+    while frames / frames_per_pixel > MAX_GRID_SPACING && frames >= 2.0 {
+        frames /= 2.0;
+    }
+
+    frames
+}
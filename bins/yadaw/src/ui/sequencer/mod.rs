@@ -0,0 +1,142 @@
+//! The sequencer, where the clips of the project are arranged on a timeline.
+
+use {kui::elements::selection::Selection, std::sync::Arc};
+
+mod clip_drag;
+pub use self::clip_drag::*;
+
+mod grid;
+pub use self::grid::*;
+
+mod playhead;
+pub use self::playhead::*;
+
+mod selection;
+pub use self::selection::*;
+
+mod time_ruler;
+pub use self::time_ruler::*;
+
+/// An event that updates the state of the elements of the sequencer.
+///
+/// Those events are sent to the whole UI tree of the window (usually through a
+/// [`kui::WindowProxy`]), and every element of the sequencer picks the ones it cares about.
+/// Elements must not mark them as handled.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SequencerEvent {
+    /// The transport has moved to the provided frame.
+    SetPlayheadFrame(f64),
+    /// The user asked to move the transport to the provided frame.
+    Seek(f64),
+    /// The horizontal zoom level has changed, in frames per logical pixel.
+    SetZoom(f64),
+    /// The frame displayed at the left edge of the sequencer has changed.
+    SetScroll(f64),
+    /// The clip being dragged has moved by the provided number of frames since the beginning of
+    /// the drag.
+    ///
+    /// The offset is already snapped to the grid, so that elements displaying the position of
+    /// the clip (such as the header column) stay aligned with it.
+    SetDragOffset(f64),
+    /// The set of selected clips has changed.
+    ///
+    /// The event carries the clips that are now selected, in the order they were selected.
+    SelectionChanged(Arc<[ClipId]>),
+}
+
+/// Maps the frames of the project to horizontal positions in the sequencer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Timeline {
+    /// The number of frames displayed per logical pixel.
+    pub frames_per_pixel: f64,
+    /// The frame displayed at the left edge of the sequencer.
+    pub scroll: f64,
+}
+
+impl Default for Timeline {
+    fn default() -> Self {
+        Self {
+            frames_per_pixel: 256.0,
+            scroll: 0.0,
+        }
+    }
+}
+
+impl Timeline {
+    /// Converts a frame to a horizontal position, in physical pixels.
+    ///
+    /// # Parameters
+    ///
+    /// - `frame`: The frame to convert.
+    ///
+    /// - `origin`: The position of the left edge of the sequencer.
+    ///
+    /// - `scale_factor`: The scale factor of the window.
+    pub fn frame_to_x(&self, frame: f64, origin: f64, scale_factor: f64) -> f64 {
+        origin + (frame - self.scroll) / self.frames_per_pixel * scale_factor
+    }
+
+    /// Converts a horizontal position, in physical pixels, to a frame.
+    ///
+    /// This is the inverse of [`frame_to_x`](Self::frame_to_x).
+    pub fn x_to_frame(&self, x: f64, origin: f64, scale_factor: f64) -> f64 {
+        self.scroll + (x - origin) / scale_factor * self.frames_per_pixel
+    }
+
+    /// Returns the number of frames visible in a sequencer of the provided width, in physical
+    /// pixels.
+    pub fn visible_frames(&self, width: f64, scale_factor: f64) -> f64 {
+        width / scale_factor * self.frames_per_pixel
+    }
+
+    /// Applies the provided event to the timeline.
+    ///
+    /// Returns whether the timeline has changed.
+    pub fn handle_event(&mut self, event: &SequencerEvent) -> bool {
+        let old = *self;
+        match *event {
+            SequencerEvent::SetZoom(frames_per_pixel) if frames_per_pixel > 0.0 => {
+                self.frames_per_pixel = frames_per_pixel;
+            }
+            SequencerEvent::SetScroll(scroll) => self.scroll = scroll.max(0.0),
+            _ => (),
+        }
+        old != *self
+    }
+}
+
+/// The state of the sequencer that is shared by its elements.
+#[derive(Debug, Clone, Default)]
+pub struct SequencerUiState {
+    /// The mapping between frames and positions.
+    pub timeline: Timeline,
+    /// The tempo and meter of the project.
+    pub tempo: Tempo,
+    /// The division of the grid that clips snap to.
+    pub grid: GridDivision,
+    /// The clips that are currently selected.
+    pub selection: Selection<ClipId>,
+}
+
+impl SequencerUiState {
+    /// Returns the distance between two lines of the grid at the current zoom level, in frames.
+    ///
+    /// Returns `None` when there is no grid.
+    pub fn grid_spacing(&self) -> Option<f64> {
+        let frames = self.grid.frames(&self.tempo)?;
+        Some(effective_grid_spacing(
+            frames,
+            self.timeline.frames_per_pixel,
+        ))
+    }
+
+    /// Snaps the provided frame to the nearest line of the grid.
+    ///
+    /// The frame is returned unchanged when there is no grid.
+    pub fn snap(&self, frame: f64) -> f64 {
+        match self.grid_spacing() {
+            Some(spacing) => (frame / spacing).round() * spacing,
+            None => frame,
+        }
+    }
+}
@@ -0,0 +1,228 @@
+use {
+    super::{SequencerEvent, Timeline},
+    crate::ui::palette,
+    kui::{
+        ElemContext, Element, LayoutContext, SizeHint,
+        elements::Length,
+        event::{Event, EventResult},
+        kurbo::{Affine, BezPath, Point, Rect, Size},
+        peniko::{Brush, Fill},
+    },
+    std::time::Instant,
+};
+
+/// The rate at which the displayed position of the playhead catches up with the transport, in
+/// units per second.
+///
+/// Transport updates arrive at a lower rate than the display refreshes, so the playhead glides
+/// between them rather than jumping.
+const PLAYHEAD_SMOOTHING_RATE: f64 = 25.0;
+
+/// The size of the triangle drawn at the top of the playhead, relative to its line width.
+const HEAD_SIZE: f64 = 5.0;
+
+/// A vertical line showing the current position of the transport in the sequencer.
+///
+/// The playhead is driven by [`SequencerEvent`]s. When following is enabled and the playhead
+/// leaves the visible part of the sequencer, it sends a [`SequencerEvent::SetScroll`] event so
+/// that the content scrolls along with it.
+pub struct Playhead {
+    /// The mapping between frames and positions.
+    timeline: Timeline,
+    /// The frame reported by the transport.
+    frame: f64,
+    /// The frame at which the playhead is currently drawn.
+    displayed_frame: f64,
+    /// The last time the playhead was drawn while gliding.
+    last_frame: Option<Instant>,
+    /// Whether the sequencer scrolls to keep the playhead visible.
+    follow: bool,
+
+    /// The width of the line.
+    width: Length,
+    /// The brush used to draw the playhead.
+    ///
+    /// When `None`, the accent color of the palette is used.
+    brush: Option<Brush>,
+
+    /// The bounds of the sequencer area covered by the playhead.
+    rect: Rect,
+    /// The scale factor of the window.
+    scale_factor: f64,
+    /// The resolved width of the line.
+    computed_width: f64,
+}
+
+impl Default for Playhead {
+    fn default() -> Self {
+        Self {
+            timeline: Timeline::default(),
+            frame: 0.0,
+            displayed_frame: 0.0,
+            last_frame: None,
+            follow: true,
+            width: Length::Pixels(1.5),
+            brush: None,
+            rect: Rect::ZERO,
+            scale_factor: 1.0,
+            computed_width: 0.0,
+        }
+    }
+}
+
+impl Playhead {
+    /// Sets the initial timeline of the playhead.
+    pub fn timeline(mut self, timeline: Timeline) -> Self {
+        self.timeline = timeline;
+        self
+    }
+
+    /// Sets whether the sequencer scrolls to keep the playhead visible.
+    pub fn follow(mut self, yes: bool) -> Self {
+        self.follow = yes;
+        self
+    }
+
+    /// Sets the width of the line.
+    pub fn width(mut self, width: Length) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Sets the brush used to draw the playhead.
+    pub fn brush(mut self, brush: impl Into<Brush>) -> Self {
+        self.brush = Some(brush.into());
+        self
+    }
+
+    /// Moves the playhead to the provided frame.
+    fn set_frame(&mut self, elem_context: &ElemContext, frame: f64) {
+        self.frame = frame;
+
+        // Jumps (seeking, looping) are not animated: only small forward steps are.
+        let step = self.frame - self.displayed_frame;
+        let visible = self
+            .timeline
+            .visible_frames(self.rect.width(), self.scale_factor);
+        if step < 0.0 || step > visible / 4.0 {
+            self.displayed_frame = frame;
+            self.last_frame = None;
+        }
+
+        let outside = frame < self.timeline.scroll || frame >= self.timeline.scroll + visible;
+        if self.follow && visible > 0.0 && outside {
+            // Scroll by pages, leaving some room before the playhead.
+            let scroll = (frame - visible / 10.0).max(0.0);
+            elem_context
+                .window
+                .make_proxy()
+                .send_event(SequencerEvent::SetScroll(scroll));
+        }
+
+        elem_context.window.request_redraw();
+    }
+
+    /// Advances the glide of the playhead, returning whether it is still moving.
+    fn animate(&mut self) -> bool {
+        if self.displayed_frame == self.frame {
+            self.last_frame = None;
+            return false;
+        }
+
+        let now = Instant::now();
+        let dt = self
+            .last_frame
+            .map_or(0.0, |last| (now - last).as_secs_f64());
+        self.last_frame = Some(now);
+
+        let t = 1.0 - (-PLAYHEAD_SMOOTHING_RATE * dt).exp();
+        self.displayed_frame += (self.frame - self.displayed_frame) * t;
+
+        // Stop once the remaining distance is smaller than a tenth of a pixel.
+        if (self.frame - self.displayed_frame).abs() < self.timeline.frames_per_pixel / 10.0 {
+            self.displayed_frame = self.frame;
+        }
+
+        true
+    }
+}
+
+impl Element for Playhead {
+    fn size_hint(
+        &mut self,
+        _elem_context: &ElemContext,
+        _layout_context: LayoutContext,
+        space: Size,
+    ) -> SizeHint {
+        SizeHint {
+            preferred: space,
+            min: Size::ZERO,
+            max: Size::new(f64::INFINITY, f64::INFINITY),
+        }
+    }
+
+    fn place(
+        &mut self,
+        _elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        pos: Point,
+        size: Size,
+    ) {
+        self.rect = Rect::from_origin_size(pos, size);
+        self.scale_factor = layout_context.scale_factor;
+        self.computed_width = self.width.resolve(&layout_context);
+    }
+
+    #[inline]
+    fn hit_test(&self, _point: Point) -> bool {
+        // The playhead is drawn on top of the sequencer and must not capture the pointer.
+        false
+    }
+
+    fn draw(&mut self, elem_context: &ElemContext, scene: &mut kui::vello::Scene) {
+        if self.animate() {
+            elem_context.window.request_redraw();
+        }
+
+        let x = self
+            .timeline
+            .frame_to_x(self.displayed_frame, self.rect.x0, self.scale_factor);
+        if x < self.rect.x0 || x > self.rect.x1 {
+            return;
+        }
+
+        let brush = self
+            .brush
+            .clone()
+            .unwrap_or_else(|| palette(&elem_context.ctx).accent.into());
+
+        let half = self.computed_width / 2.0;
+        scene.fill(
+            Fill::NonZero,
+            Affine::IDENTITY,
+            &brush,
+            None,
+            &Rect::new(x - half, self.rect.y0, x + half, self.rect.y1),
+        );
+
+        let head = self.computed_width * HEAD_SIZE;
+        let mut path = BezPath::new();
+        path.move_to((x - head, self.rect.y0));
+        path.line_to((x + head, self.rect.y0));
+        path.line_to((x, self.rect.y0 + head));
+        path.close_path();
+        scene.fill(Fill::NonZero, Affine::IDENTITY, &brush, None, &path);
+    }
+
+    fn event(&mut self, elem_context: &ElemContext, event: &dyn Event) -> EventResult {
+        if let Some(ev) = event.downcast_ref::<SequencerEvent>() {
+            if let SequencerEvent::SetPlayheadFrame(frame) | SequencerEvent::Seek(frame) = *ev {
+                self.set_frame(elem_context, frame);
+            } else if self.timeline.handle_event(ev) {
+                elem_context.window.request_redraw();
+            }
+        }
+
+        EventResult::Continue
+    }
+}
@@ -0,0 +1,114 @@
+use {
+    super::{SequencerEvent, SequencerUiState},
+    crate::ui::palette,
+    kui::{
+        ElemContext,
+        elements::selection::{Marquee, MarqueePhase, SelectionMode},
+        kurbo::{Affine, Rect, Stroke},
+    },
+    std::sync::Arc,
+};
+
+/// The width of the border drawn around selected clips, in logical pixels.
+const HIGHLIGHT_WIDTH: f64 = 2.0;
+
+/// Identifies a clip of the sequencer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ClipId(pub u64);
+
+impl SequencerUiState {
+    /// Returns whether the provided clip is selected.
+    #[inline]
+    pub fn is_selected(&self, id: ClipId) -> bool {
+        self.selection.is_selected(&id)
+    }
+
+    /// Handles a click on a clip.
+    ///
+    /// Holding Shift (or the platform's toggle modifier) adds the clip to the selection or
+    /// removes it. Otherwise, the clip replaces the selection.
+    ///
+    /// A [`SequencerEvent::SelectionChanged`] event is sent when the selection changes.
+    pub fn click_clip(&mut self, elem_context: &ElemContext, id: ClipId) {
+        let changed = match SelectionMode::from_modifiers(elem_context.window.keyboard_modifiers())
+        {
+            SelectionMode::Replace => self.selection.select(id),
+            SelectionMode::Toggle | SelectionMode::Extend => self.selection.toggle(id),
+        };
+
+        if changed {
+            self.notify_selection_changed(elem_context);
+        }
+    }
+
+    /// Clears the selection, sending a [`SequencerEvent::SelectionChanged`] event if it was not
+    /// already empty.
+    pub fn clear_selection(&mut self, elem_context: &ElemContext) {
+        if self.selection.clear() {
+            self.notify_selection_changed(elem_context);
+        }
+    }
+
+    /// Updates the selection from a marquee drag, usually reported by a
+    /// [`SelectionArea`](kui::elements::selection::SelectionArea) wrapping the clips.
+    ///
+    /// # Parameters
+    ///
+    /// - `clips`: The clips that may be selected, along with their bounds in window coordinates.
+    ///
+    /// - `marquee`: The marquee reported by the selection area.
+    ///
+    /// A [`SequencerEvent::SelectionChanged`] event is sent when the selection changes.
+    pub fn update_marquee(
+        &mut self,
+        elem_context: &ElemContext,
+        clips: impl IntoIterator<Item = (ClipId, Rect)>,
+        marquee: &Marquee,
+    ) {
+        if marquee.phase == MarqueePhase::Started {
+            self.selection.begin_marquee();
+        }
+
+        let changed = self
+            .selection
+            .update_marquee(clips, marquee.rect, marquee.mode);
+
+        if marquee.phase == MarqueePhase::Ended {
+            self.selection.end_marquee();
+        }
+
+        if changed {
+            self.notify_selection_changed(elem_context);
+        }
+    }
+
+    /// Sends a [`SequencerEvent::SelectionChanged`] event with the current selection.
+    fn notify_selection_changed(&self, elem_context: &ElemContext) {
+        let selected: Arc<[ClipId]> = self.selection.selected().into();
+        elem_context
+            .window
+            .make_proxy()
+            .send_event(SequencerEvent::SelectionChanged(selected));
+    }
+}
+
+/// Draws the border that highlights a selected clip.
+///
+/// # Parameters
+///
+/// - `rect`: The bounds of the clip, in physical pixels.
+///
+/// - `scale_factor`: The scale factor of the window.
+pub fn draw_selection_highlight(
+    elem_context: &ElemContext,
+    scene: &mut kui::vello::Scene,
+    rect: Rect,
+    scale_factor: f64,
+) {
+    let width = HIGHLIGHT_WIDTH * scale_factor;
+    let brush = palette(&elem_context.ctx).accent;
+
+    // Keep the border inside of the clip so that it does not overlap its neighbors.
+    let rect = rect.inset(-width / 2.0);
+    scene.stroke(&Stroke::new(width), Affine::IDENTITY, brush, None, &rect);
+}
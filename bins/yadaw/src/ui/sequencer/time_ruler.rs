@@ -0,0 +1,311 @@
+use {
+    super::{SequencerEvent, SequencerUiState},
+    crate::ui::palette,
+    kui::{
+        ElemContext, Element, LayoutContext, SizeHint,
+        elements::{
+            Length,
+            text::{Text, UniformStyle},
+        },
+        event::{Event, EventResult, PointerButton},
+        kurbo::{Affine, Line, Point, Rect, Size, Stroke},
+        peniko::Mix,
+        winit::event::{ButtonSource, MouseButton},
+    },
+};
+
+/// The smallest distance between two labels of the ruler, in logical pixels.
+const MIN_LABEL_SPACING: f64 = 56.0;
+
+/// The smallest distance between two unlabeled lines of the ruler, in logical pixels.
+const MIN_LINE_SPACING: f64 = 6.0;
+
+/// The largest number of subdivisions of a beat displayed by the ruler.
+const MAX_SUBDIVISIONS: u32 = 16;
+
+/// The distance between a label and the line it belongs to, in logical pixels.
+const LABEL_OFFSET: f64 = 3.0;
+
+/// The unit of the labeled lines of a [`TimeRuler`], chosen from the zoom level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RulerLevel {
+    /// A line every provided number of bars, labeled `bar`.
+    Bars(u32),
+    /// A line every beat, labeled `bar.beat`.
+    Beats,
+    /// A line every provided fraction of a beat, labeled `bar.beat.subdivision`.
+    Subdivisions(u32),
+}
+
+impl RulerLevel {
+    /// Picks the finest level whose labels are at least [`MIN_LABEL_SPACING`] apart.
+    fn pick(state: &SequencerUiState) -> Self {
+        let frames_per_pixel = state.timeline.frames_per_pixel;
+        let beat_pixels = state.tempo.frames_per_beat() / frames_per_pixel;
+        let bar_pixels = state.tempo.frames_per_bar() / frames_per_pixel;
+
+        if beat_pixels >= MIN_LABEL_SPACING {
+            let mut subdivisions = 1;
+            while subdivisions < MAX_SUBDIVISIONS
+                && beat_pixels / (subdivisions * 2) as f64 >= MIN_LABEL_SPACING
+            {
+                subdivisions *= 2;
+            }
+
+            if subdivisions == 1 {
+                Self::Beats
+            } else {
+                Self::Subdivisions(subdivisions)
+            }
+        } else {
+            let mut bars = 1;
+            while (bars as f64) * bar_pixels < MIN_LABEL_SPACING && bars < 1 << 16 {
+                bars *= 2;
+            }
+            Self::Bars(bars)
+        }
+    }
+
+    /// Returns the distance between two labeled lines, in frames.
+    fn step(self, state: &SequencerUiState) -> f64 {
+        match self {
+            Self::Bars(n) => state.tempo.frames_per_bar() * n as f64,
+            Self::Beats => state.tempo.frames_per_beat(),
+            Self::Subdivisions(n) => state.tempo.frames_per_beat() / n as f64,
+        }
+    }
+
+    /// Returns the distance between two unlabeled lines, in frames.
+    fn minor_step(self, state: &SequencerUiState) -> f64 {
+        match self {
+            Self::Bars(1) => state.tempo.frames_per_beat(),
+            _ => self.step(state) / 2.0,
+        }
+    }
+
+    /// Returns the label of the `index`-th labeled line, starting from zero.
+    fn label(self, state: &SequencerUiState, index: u64) -> String {
+        let beats_per_bar = state.tempo.beats_per_bar.max(1) as u64;
+        match self {
+            Self::Bars(n) => format!("{}", index * n as u64 + 1),
+            Self::Beats => format!(
+                "{}.{}",
+                index / beats_per_bar + 1,
+                index % beats_per_bar + 1
+            ),
+            Self::Subdivisions(n) => {
+                let beat = index / n as u64;
+                format!(
+                    "{}.{}.{}",
+                    beat / beats_per_bar + 1,
+                    beat % beats_per_bar + 1,
+                    index % n as u64 + 1
+                )
+            }
+        }
+    }
+}
+
+/// A labeled line of a [`TimeRuler`].
+struct RulerLabel {
+    /// The position of the line, in physical pixels.
+    x: f64,
+    /// The label drawn next to the line.
+    text: Text<UniformStyle>,
+}
+
+/// An element that displays the bars and beats of the project along the top of the sequencer.
+///
+/// The ruler switches between bars, beats and subdivisions of beats as the zoom level
+/// increases, and follows the horizontal scroll of the sequencer through [`SequencerEvent`]s.
+/// Clicking the ruler sends a [`SequencerEvent::Seek`] event.
+pub struct TimeRuler {
+    /// The state of the sequencer.
+    state: SequencerUiState,
+    /// The preferred height of the ruler.
+    height: Length,
+    /// The font size of the labels.
+    font_size: Length,
+
+    /// The labeled lines of the ruler, computed when it is placed.
+    labels: Vec<RulerLabel>,
+    /// The positions of the unlabeled lines of the ruler, in physical pixels.
+    minor_lines: Vec<f64>,
+    /// The bounds of the element.
+    rect: Rect,
+    /// The scale factor of the window.
+    scale_factor: f64,
+}
+
+impl TimeRuler {
+    /// Creates a new [`TimeRuler`] element.
+    pub fn new(state: SequencerUiState) -> Self {
+        Self {
+            state,
+            height: Length::Pixels(24.0),
+            font_size: Length::Pixels(11.0),
+            labels: Vec::new(),
+            minor_lines: Vec::new(),
+            rect: Rect::ZERO,
+            scale_factor: 1.0,
+        }
+    }
+
+    /// Sets the preferred height of the ruler.
+    pub fn height(mut self, height: Length) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// Sets the font size of the labels.
+    pub fn font_size(mut self, size: Length) -> Self {
+        self.font_size = size;
+        self
+    }
+}
+
+impl Element for TimeRuler {
+    fn size_hint(
+        &mut self,
+        _elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        space: Size,
+    ) -> SizeHint {
+        let width = if space.width.is_finite() {
+            space.width
+        } else {
+            0.0
+        };
+        let height = self.height.resolve(&layout_context);
+
+        SizeHint {
+            preferred: Size::new(width, height),
+            min: Size::new(0.0, height),
+            max: Size::new(f64::INFINITY, height),
+        }
+    }
+
+    fn place(
+        &mut self,
+        elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        pos: Point,
+        size: Size,
+    ) {
+        self.rect = Rect::from_origin_size(pos, size);
+        self.scale_factor = layout_context.scale_factor;
+        self.labels.clear();
+        self.minor_lines.clear();
+
+        let state = &self.state;
+        let timeline = state.timeline;
+        let level = RulerLevel::pick(state);
+        let step = level.step(state);
+        if !step.is_finite() || step <= 0.0 {
+            return;
+        }
+
+        let first_frame = timeline.scroll;
+        let last_frame = first_frame + timeline.visible_frames(size.width, self.scale_factor);
+        let label_brush = palette(&elem_context.ctx).text_muted;
+
+        // Start one line before the left edge, so that its label remains visible while it is
+        // partially scrolled out.
+        let first = (first_frame / step).floor().max(1.0) as u64 - 1;
+        let last = (last_frame / step).ceil() as u64;
+        for index in first..=last {
+            let x = timeline.frame_to_x(index as f64 * step, self.rect.x0, self.scale_factor);
+
+            let mut text = Text::default()
+                .text(level.label(state, index))
+                .inline(true)
+                .brush(label_brush)
+                .font_size(self.font_size.clone());
+            let text_size = text
+                .size_hint(
+                    elem_context,
+                    layout_context,
+                    Size::new(f64::INFINITY, f64::INFINITY),
+                )
+                .preferred;
+            let text_pos = Point::new(x + LABEL_OFFSET * self.scale_factor, self.rect.y0);
+            text.place(elem_context, layout_context, text_pos, text_size);
+
+            self.labels.push(RulerLabel { x, text });
+        }
+
+        let minor_step = level.minor_step(state);
+        if minor_step / timeline.frames_per_pixel >= MIN_LINE_SPACING {
+            let first = (first_frame / minor_step).floor() as u64;
+            let last = (last_frame / minor_step).ceil() as u64;
+            let ratio = (step / minor_step).round() as u64;
+            self.minor_lines.extend(
+                (first..=last)
+                    .filter(|index| ratio == 0 || index % ratio != 0)
+                    .map(|index| {
+                        timeline.frame_to_x(
+                            index as f64 * minor_step,
+                            self.rect.x0,
+                            self.scale_factor,
+                        )
+                    }),
+            );
+        }
+    }
+
+    #[inline]
+    fn hit_test(&self, point: Point) -> bool {
+        self.rect.contains(point)
+    }
+
+    fn draw(&mut self, elem_context: &ElemContext, scene: &mut kui::vello::Scene) {
+        let line_brush = palette(&elem_context.ctx).border;
+        let stroke = Stroke::new(self.scale_factor);
+
+        scene.push_layer(Mix::Clip, 1.0, Affine::IDENTITY, &self.rect);
+
+        for label in &mut self.labels {
+            let line = Line::new((label.x, self.rect.y0), (label.x, self.rect.y1));
+            scene.stroke(&stroke, Affine::IDENTITY, line_brush, None, &line);
+            label.text.draw(elem_context, scene);
+        }
+
+        let minor_top = self.rect.y1 - self.rect.height() / 3.0;
+        for &x in &self.minor_lines {
+            let line = Line::new((x, minor_top), (x, self.rect.y1));
+            scene.stroke(&stroke, Affine::IDENTITY, line_brush, None, &line);
+        }
+
+        scene.pop_layer();
+    }
+
+    fn event(&mut self, elem_context: &ElemContext, event: &dyn Event) -> EventResult {
+        if let Some(ev) = event.downcast_ref::<SequencerEvent>() {
+            if self.state.timeline.handle_event(ev) {
+                elem_context.window.request_relayout();
+            }
+            return EventResult::Continue;
+        }
+
+        if let Some(ev) = event.downcast_ref::<PointerButton>() {
+            if ev.primary
+                && ev.state.is_pressed()
+                && matches!(ev.button, ButtonSource::Mouse(MouseButton::Left))
+                && self.rect.contains(ev.position)
+            {
+                let frame = self
+                    .state
+                    .timeline
+                    .x_to_frame(ev.position.x, self.rect.x0, self.scale_factor)
+                    .max(0.0);
+                elem_context
+                    .window
+                    .make_proxy()
+                    .send_event(SequencerEvent::Seek(frame));
+                return EventResult::Handled;
+            }
+        }
+
+        EventResult::Continue
+    }
+}
@@ -0,0 +1,247 @@
+use kui::{
+    color::{ColorSpace, Oklab, Srgb},
+    peniko::{Brush, Color},
+};
+
+/// Whether the application uses light or dark colors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ThemeMode {
+    /// Dark text on light backgrounds.
+    Light,
+    /// Light text on dark backgrounds.
+    #[default]
+    Dark,
+}
+
+/// The colors used to draw the user interface.
+///
+/// Each field is a *role* rather than a specific color, so that components do not have to know
+/// which theme is currently in use.
+#[derive(Debug, Clone, Copy)]
+pub struct Palette {
+    /// The color behind everything else (the clear color of windows).
+    pub background: Color,
+    /// The color of panels and menus displayed on top of the background.
+    pub surface: Color,
+    /// The color used to highlight important or selected things.
+    pub accent: Color,
+    /// The color of regular text.
+    pub text: Color,
+    /// The color of secondary text, such as placeholders.
+    pub text_muted: Color,
+    /// The color of the borders of inactive controls.
+    pub border: Color,
+    /// The color of the borders of focused controls.
+    pub border_focused: Color,
+    /// The background color of filled controls, such as buttons.
+    pub control: Color,
+    /// The background color of filled controls when hovered.
+    pub control_hover: Color,
+}
+
+impl Palette {
+    /// The default palette for [`ThemeMode::Dark`].
+    pub const DARK: Self = Self {
+        background: Color::from_rgb8(0x00, 0x00, 0x00),
+        surface: Color::from_rgb8(0x11, 0x11, 0x11),
+        accent: Color::from_rgb8(0x4a, 0x90, 0xe2),
+        text: Color::from_rgb8(0xff, 0xff, 0xff),
+        text_muted: Color::from_rgb8(0x55, 0x55, 0x55),
+        border: Color::from_rgb8(0x55, 0x55, 0x55),
+        border_focused: Color::from_rgb8(0xff, 0xff, 0xff),
+        control: Color::from_rgb8(0xff, 0xff, 0xff),
+        control_hover: Color::from_rgb8(0xde, 0xde, 0xde),
+    };
+
+    /// The default palette for [`ThemeMode::Light`].
+    pub const LIGHT: Self = Self {
+        background: Color::from_rgb8(0xf4, 0xf4, 0xf4),
+        surface: Color::from_rgb8(0xff, 0xff, 0xff),
+        accent: Color::from_rgb8(0x2f, 0x6f, 0xc4),
+        text: Color::from_rgb8(0x11, 0x11, 0x11),
+        text_muted: Color::from_rgb8(0x88, 0x88, 0x88),
+        border: Color::from_rgb8(0xbb, 0xbb, 0xbb),
+        border_focused: Color::from_rgb8(0x11, 0x11, 0x11),
+        control: Color::from_rgb8(0x22, 0x22, 0x22),
+        control_hover: Color::from_rgb8(0x44, 0x44, 0x44),
+    };
+
+    /// Returns the default palette for the provided mode.
+    pub const fn for_mode(mode: ThemeMode) -> Self {
+        match mode {
+            ThemeMode::Light => Self::LIGHT,
+            ThemeMode::Dark => Self::DARK,
+        }
+    }
+
+    /// Returns a color that is readable on top of the provided background color.
+    ///
+    /// This picks either the regular text color of the palette or the text color of the opposite
+    /// palette, whichever contrasts the most with the background.
+    pub fn text_on(&self, background: Color) -> Color {
+        let dark_text = text_color_for_background(background) == Color::BLACK;
+        let palette_text_is_dark = relative_luminance(self.text) < 0.5;
+
+        if dark_text == palette_text_is_dark {
+            self.text
+        } else if dark_text {
+            Palette::LIGHT.text
+        } else {
+            Palette::DARK.text
+        }
+    }
+}
+
+/// A **resource** that stores the theme of the application.
+///
+/// Components read their colors from this resource (usually when they are built or when their
+/// state changes) rather than from hardcoded constants. Use [`set_theme_mode`] to switch between
+/// the light and dark themes.
+///
+/// [`set_theme_mode`]: crate::ui::set_theme_mode
+#[derive(Debug, Clone, Copy)]
+pub struct Theme {
+    /// The current mode.
+    mode: ThemeMode,
+    /// The colors of the theme.
+    pub palette: Palette,
+}
+
+impl Theme {
+    /// Creates a new [`Theme`] using the default palette of the provided mode.
+    pub const fn new(mode: ThemeMode) -> Self {
+        Self {
+            mode,
+            palette: Palette::for_mode(mode),
+        }
+    }
+
+    /// Returns the current mode of the theme.
+    #[inline]
+    pub fn mode(&self) -> ThemeMode {
+        self.mode
+    }
+
+    /// Sets the mode of the theme, replacing the palette with the default palette of that mode.
+    pub fn set_mode(&mut self, mode: ThemeMode) {
+        *self = Self::new(mode);
+    }
+}
+
+impl Default for Theme {
+    #[inline]
+    fn default() -> Self {
+        Self::new(ThemeMode::default())
+    }
+}
+
+/// Returns the palette of the current theme.
+///
+/// If no theme has been registered yet, the default theme is inserted in the context.
+pub fn palette(ctx: &kui::Ctx) -> Palette {
+    ctx.with_resource_or_default(|theme: &mut Theme| theme.palette)
+}
+
+/// Returns the relative luminance of the provided color, as defined by WCAG.
+fn relative_luminance(color: Color) -> f32 {
+    /// Converts a gamma-encoded sRGB component to a linear component.
+    fn linearize(c: f32) -> f32 {
+        if c <= 0.04045 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    }
+
+    let [r, g, b, _] = color.components;
+    0.2126 * linearize(r) + 0.7152 * linearize(g) + 0.0722 * linearize(b)
+}
+
+/// Returns either black or white, whichever is the most readable on top of the provided
+/// background color.
+pub fn text_color_for_background(background: Color) -> Color {
+    // The luminance for which the contrast ratio with black and white is the same.
+    const THRESHOLD: f32 = 0.179;
+
+    if relative_luminance(background) > THRESHOLD {
+        Color::BLACK
+    } else {
+        Color::WHITE
+    }
+}
+
+/// Linearly interpolates between two colors.
+///
+/// The interpolation is done in the Oklab color space, which makes the intermediate colors look
+/// evenly spaced. For example, the midpoint between black and white is a gray with a perceived
+/// lightness of 50%, while interpolating sRGB components would produce a lighter gray.
+///
+/// # Parameters
+///
+/// - `t`: the interpolation factor. `0.0` returns `a` and `1.0` returns `b`. Values outside of
+///   that range are clamped.
+pub fn lerp_color(a: Color, b: Color, t: f32) -> Color {
+    let t = t.clamp(0.0, 1.0);
+
+    let [la, aa, ba, alpha_a] = a.convert::<Oklab>().components;
+    let [lb, ab, bb, alpha_b] = b.convert::<Oklab>().components;
+
+    // The components are premultiplied by their alpha so that fully transparent colors do not
+    // tint the result.
+    let alpha = alpha_a + (alpha_b - alpha_a) * t;
+    if alpha <= 0.0 {
+        return Color::TRANSPARENT;
+    }
+
+    let mix = |x: f32, y: f32| (x * alpha_a + (y * alpha_b - x * alpha_a) * t) / alpha;
+    let [r, g, b] = Oklab::convert::<Srgb>([mix(la, lb), mix(aa, ab), mix(ba, bb)]);
+
+    Color::new([
+        r.clamp(0.0, 1.0),
+        g.clamp(0.0, 1.0),
+        b.clamp(0.0, 1.0),
+        alpha,
+    ])
+}
+
+/// Interpolates between two brushes.
+///
+/// Solid colors are interpolated with [`lerp_color`]. Other kinds of brushes cannot be
+/// interpolated, and `a` or `b` is returned depending on which one is the closest to `t`.
+pub fn lerp_brush(a: &Brush, b: &Brush, t: f32) -> Brush {
+    match (a, b) {
+        (Brush::Solid(a), Brush::Solid(b)) => Brush::Solid(lerp_color(*a, *b, t)),
+        _ if t < 0.5 => a.clone(),
+        _ => b.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::lerp_color, kui::peniko::Color};
+
+    #[test]
+    fn black_to_white_midpoint_is_perceptual_gray() {
+        let [r, g, b, a] = lerp_color(Color::BLACK, Color::WHITE, 0.5).components;
+
+        // An Oklab lightness of 0.5 is a linear luminance of 0.125, which is encoded as ~0.389
+        // in sRGB.
+        for c in [r, g, b] {
+            assert!((c - 0.389).abs() < 1e-3, "{c}");
+        }
+        assert_eq!(a, 1.0);
+    }
+
+    #[test]
+    fn endpoints_and_clamping() {
+        let red = Color::new([1.0, 0.0, 0.0, 1.0]);
+        let blue = Color::new([0.0, 0.0, 1.0, 1.0]);
+
+        for (t, expected) in [(0.0, red), (-1.0, red), (1.0, blue), (2.0, blue)] {
+            let actual = lerp_color(red, blue, t).components;
+            for (x, y) in actual.into_iter().zip(expected.components) {
+                assert!((x - y).abs() < 1e-3, "{t}: {actual:?}");
+            }
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use {
+    kui::winit::{
+        dpi::{PhysicalPosition, PhysicalSize},
+        window::{Fullscreen, Window},
+    },
+    serde::{Deserialize, Serialize},
+};
+
+/// The path of the file in which the geometry of the main window is saved.
+///
+/// Unlike the settings file, this file is owned by the application. It is overwritten every
+/// time the application exits, so it is kept separate to avoid touching the user's settings
+/// (and the comments they contain).
+const STATE_PATH: &str = "window-state.toml";
+
+/// The minimum width and height of a restored window.
+///
+/// This protects against a state file that would make the window too small to be found.
+const MIN_SIZE: u32 = 200;
+
+/// The distance, in physical pixels, between the top of the window and the point that must be
+/// visible on a monitor for a saved position to be used.
+///
+/// That point is usually located on the title bar, which means that the user can always grab
+/// the window to move it.
+const TITLE_BAR_OFFSET: i32 = 16;
+
+/// The position and size of the main window.
+///
+/// This is saved when the application exits, and restored when it starts again.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+struct WindowGeometry {
+    /// The position of the top-left corner of the window (including its decorations), in
+    /// physical pixels.
+    ///
+    /// This is `None` on platforms that do not allow applications to know where their windows
+    /// are.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    position: Option<[i32; 2]>,
+    /// The size of the content of the window, in physical pixels.
+    size: [u32; 2],
+    /// Whether the window was maximized.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    maximized: bool,
+    /// Whether the window was fullscreen.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    fullscreen: bool,
+}
+
+impl WindowGeometry {
+    /// Loads the geometry saved when the application last exited.
+    ///
+    /// # Returns
+    ///
+    /// `None` if no geometry was saved, or if the state file cannot be read.
+    fn load() -> Option<Self> {
+        let s = match std::fs::read_to_string(STATE_PATH) {
+            Ok(s) => s,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return None,
+            Err(err) => {
+                log::warn!("Failed to read the saved window geometry: {err}");
+                return None;
+            }
+        };
+
+        toml::from_str(&s)
+            .inspect_err(|err| log::warn!("Ignoring the saved window geometry: {err}"))
+            .ok()
+    }
+
+    /// Saves the geometry to the state file, replacing its previous content.
+    fn save(&self) {
+        let s = match toml::to_string(self) {
+            Ok(s) => s,
+            Err(err) => {
+                log::error!("Failed to serialize the window geometry: {err}");
+                return;
+            }
+        };
+
+        if let Err(err) = std::fs::write(STATE_PATH, s) {
+            log::error!("Failed to save the window geometry: {err}");
+        }
+    }
+}
+
+/// Restores the position and size of the provided window from the state file.
+///
+/// The saved geometry is only used if the window would still be reachable: the top-center of
+/// the window must be located on one of the available monitors. This is not the case when the
+/// window was last on a monitor that has since been disconnected, or when the resolution of
+/// the monitors changed. In that case, the default geometry of the window is kept.
+pub fn restore_window_geometry(window: &kui::Window) {
+    let Some(geometry) = WindowGeometry::load() else {
+        return;
+    };
+
+    window.with_winit_window(|window| {
+        let size = PhysicalSize::new(
+            geometry.size[0].max(MIN_SIZE),
+            geometry.size[1].max(MIN_SIZE),
+        );
+
+        if let Some([x, y]) = geometry.position {
+            let Some(monitor_size) = monitor_size_at(window, x + size.width as i32 / 2, y) else {
+                log::info!("The saved window position is off-screen, using the default one");
+                return;
+            };
+
+            window.set_outer_position(PhysicalPosition::new(x, y).into());
+            let _ = window.request_surface_size(
+                PhysicalSize::new(
+                    size.width.min(monitor_size.width),
+                    size.height.min(monitor_size.height),
+                )
+                .into(),
+            );
+        } else {
+            let _ = window.request_surface_size(size.into());
+        }
+
+        if geometry.maximized {
+            window.set_maximized(true);
+        }
+
+        if geometry.fullscreen {
+            window.set_fullscreen(Some(Fullscreen::Borderless(None)));
+        }
+    });
+}
+
+/// Saves the position and size of the provided window to the state file.
+pub fn save_window_geometry(window: &kui::Window) {
+    let geometry = window.with_winit_window(|window| {
+        let size = window.surface_size();
+
+        // NOTE: When the window is maximized, this saves the maximized geometry rather than
+        // the one the window would get back when un-maximized. winit provides no way to query
+        // the latter.
+        WindowGeometry {
+            position: window.outer_position().ok().map(|pos| [pos.x, pos.y]),
+            size: [size.width, size.height],
+            maximized: window.is_maximized(),
+            fullscreen: window.fullscreen().is_some(),
+        }
+    });
+
+    geometry.save();
+}
+
+/// Returns the size of the monitor that contains the provided point, if any.
+fn monitor_size_at(window: &dyn Window, x: i32, y: i32) -> Option<PhysicalSize<u32>> {
+    let y = y + TITLE_BAR_OFFSET;
+
+    window.available_monitors().find_map(|monitor| {
+        let pos = monitor.position()?;
+        let size = monitor.current_video_mode()?.size();
+
+        let contains = x >= pos.x
+            && y >= pos.y
+            && x < pos.x + size.width as i32
+            && y < pos.y + size.height as i32;
+
+        contains.then_some(size)
+    })
+}
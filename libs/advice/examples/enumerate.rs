@@ -7,11 +7,20 @@ fn main() {
 
 /// Print host data.
 fn print_host_info() -> Result<(), advice::Error> {
+    let available = advice::available_hosts();
+    if !available.is_empty() {
+        let names: Vec<&str> = available.iter().map(|id| id.display_name()).collect();
+        println!("Available hosts: {}", names.join(", "));
+    }
+
     let Some(host) = advice::default_host()? else {
         println!("No host found.");
         return Ok(());
     };
 
+    println!("Using host: {}", host.id());
+    println!();
+
     let devices = host.devices()?;
 
     for device in devices {
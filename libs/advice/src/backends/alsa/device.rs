@@ -0,0 +1,163 @@
+use {
+    super::{
+        pcm::Pcm,
+        stream::{AlsaStream, StreamDirection},
+        utility::KNOWN_FORMATS,
+    },
+    crate::{
+        ChannelLayout, Device, DeviceFormats, Error, ShareMode, Stream, StreamCallback,
+        StreamConfig,
+    },
+    alsa_sys::{SND_PCM_STREAM_CAPTURE, SND_PCM_STREAM_PLAYBACK, snd_pcm_stream_t},
+    std::ffi::CString,
+};
+
+/// The frame rates that are checked when the device supports a range of frame rates.
+const COMMON_FRAME_RATES: [u32; 13] = [
+    8000, 11025, 16000, 22050, 32000, 44100, 48000, 88200, 96000, 176400, 192000, 352800, 384000,
+];
+
+/// A device object for the ALSA backend.
+pub struct AlsaDevice {
+    /// The ALSA name of the PCM device (e.g. `default` or `hw:CARD=PCH,DEV=0`).
+    pcm_name: CString,
+    /// The human-readable description of the device, if one is available.
+    description: Option<String>,
+    /// Whether the device can be used for playback.
+    playback: bool,
+    /// Whether the device can be used for capture.
+    capture: bool,
+}
+
+impl AlsaDevice {
+    /// Creates a new [`AlsaDevice`].
+    pub fn new(
+        pcm_name: CString,
+        description: Option<String>,
+        playback: bool,
+        capture: bool,
+    ) -> Self {
+        Self {
+            pcm_name,
+            description,
+            playback,
+            capture,
+        }
+    }
+
+    /// Queries the formats supported by the device in the provided direction.
+    fn query_supported_formats(
+        &self,
+        stream: snd_pcm_stream_t,
+    ) -> Result<Option<DeviceFormats>, Error> {
+        let pcm = match Pcm::open(&self.pcm_name, stream, true) {
+            Ok(pcm) => pcm,
+            // The device cannot be used in that direction.
+            Err(
+                Error::UnsupportedConfiguration
+                | Error::DeviceNotAvailable
+                | Error::DeviceDisconnected,
+            ) => return Ok(None),
+            Err(err) => return Err(err),
+        };
+
+        let params = pcm.hw_params_any()?;
+        let mut formats = DeviceFormats::DUMMY;
+
+        for format in KNOWN_FORMATS {
+            if params.test_format(&pcm, format) {
+                formats.formats.insert(format.into());
+            }
+        }
+
+        for layout in [ChannelLayout::Interleaved, ChannelLayout::Planar] {
+            if params.test_channel_layout(&pcm, layout) {
+                formats.channel_layouts.insert(layout.into());
+            }
+        }
+
+        formats.max_channel_count = params.channels_max()?.min(u16::MAX as u32) as u16;
+
+        // Devices usually report either a handful of discrete frame rates, or a continuous range
+        // (when a plugin resamples the audio). Only the common frame rates within the range are
+        // reported.
+        let (min_rate, max_rate) = params.rate_range()?;
+        formats.frame_rates = COMMON_FRAME_RATES
+            .into_iter()
+            .filter(|&rate| (min_rate..=max_rate).contains(&rate))
+            .filter(|&rate| params.test_rate(&pcm, rate))
+            .map(|rate| rate as f64)
+            .collect();
+        if formats.frame_rates.is_empty() && min_rate == max_rate {
+            formats.frame_rates.push(min_rate as f64);
+        }
+
+        // The buffer size of a stream is the size of an ALSA period: the number of frames that
+        // are processed in a single call to the callback.
+        let (min_period, max_period) = params.period_size_range()?;
+        formats.min_buffer_size = min_period.min(u32::MAX as _) as u32;
+        formats.max_buffer_size = max_period.min(u32::MAX as _) as u32;
+
+        if formats.validate() {
+            Ok(Some(formats))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl Device for AlsaDevice {
+    fn name(&self) -> Result<Option<String>, Error> {
+        // ALSA descriptions usually span two lines: the name of the card, and a description of
+        // the PCM device itself.
+        let name = match self.description.as_deref() {
+            Some(desc) => desc.lines().collect::<Vec<_>>().join(", "),
+            None => self.pcm_name.to_string_lossy().into_owned(),
+        };
+        Ok(Some(name))
+    }
+
+    fn output_formats(&self, _share: ShareMode) -> Result<Option<DeviceFormats>, Error> {
+        if self.playback {
+            self.query_supported_formats(SND_PCM_STREAM_PLAYBACK)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn input_formats(&self, _share: ShareMode) -> Result<Option<DeviceFormats>, Error> {
+        if self.capture {
+            self.query_supported_formats(SND_PCM_STREAM_CAPTURE)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn open_output_stream(
+        &self,
+        config: StreamConfig,
+        callback: Box<dyn Send + FnMut(StreamCallback)>,
+    ) -> Result<Box<dyn Stream>, Error> {
+        let pcm = Pcm::open(&self.pcm_name, SND_PCM_STREAM_PLAYBACK, false)?;
+        Ok(Box::new(AlsaStream::new(
+            pcm,
+            StreamDirection::Playback,
+            &config,
+            callback,
+        )?))
+    }
+
+    fn open_input_stream(
+        &self,
+        config: StreamConfig,
+        callback: Box<dyn Send + FnMut(StreamCallback)>,
+    ) -> Result<Box<dyn Stream>, Error> {
+        let pcm = Pcm::open(&self.pcm_name, SND_PCM_STREAM_CAPTURE, false)?;
+        Ok(Box::new(AlsaStream::new(
+            pcm,
+            StreamDirection::Capture,
+            &config,
+            callback,
+        )?))
+    }
+}
@@ -0,0 +1,149 @@
+use {
+    super::{device::AlsaDevice, utility::backend_error, watcher::PollingThread},
+    crate::{BackendError, Device, DeviceEvent, DeviceWatcher, Host, HostId, RoleHint},
+    alsa_sys::{snd_device_name_free_hint, snd_device_name_get_hint, snd_device_name_hint},
+    std::{
+        ffi::{CStr, CString},
+        os::raw::{c_char, c_void},
+    },
+};
+
+/// The [`Host`] implementation for ALSA.
+pub struct AlsaHost;
+
+impl AlsaHost {
+    /// Returns the `default` device, which follows the user's ALSA configuration.
+    fn default_device() -> AlsaDevice {
+        AlsaDevice::new(c"default".into(), None, true, true)
+    }
+
+    /// Enumerates the PCM devices known to ALSA.
+    ///
+    /// # Parameters
+    ///
+    /// - `filter`: a function that receives whether a device supports playback and capture, and
+    ///   returns whether the device should be included in the result.
+    fn enumerate_devices(
+        filter: impl Fn(bool, bool) -> bool,
+    ) -> Result<Vec<Box<dyn Device>>, BackendError> {
+        let mut devices: Vec<Box<dyn Device>> = Vec::new();
+
+        for_each_hint(|name, description, playback, capture| {
+            if filter(playback, capture) {
+                devices.push(Box::new(AlsaDevice::new(
+                    name,
+                    description,
+                    playback,
+                    capture,
+                )));
+            }
+        })?;
+
+        Ok(devices)
+    }
+}
+
+/// Calls the provided function for every PCM device hint known to ALSA.
+///
+/// The function receives the name of the device, its description, and whether it supports
+/// playback and capture.
+pub fn for_each_hint(
+    mut f: impl FnMut(CString, Option<String>, bool, bool),
+) -> Result<(), BackendError> {
+    let mut hints: *mut *mut c_void = std::ptr::null_mut();
+
+    unsafe {
+        let ret = snd_device_name_hint(-1, c"pcm".as_ptr(), &mut hints);
+        if ret < 0 {
+            return Err(backend_error("Failed to enumerate audio devices", ret));
+        }
+    }
+
+    unsafe {
+        let mut cursor = hints;
+        while !(*cursor).is_null() {
+            let hint = *cursor;
+            cursor = cursor.add(1);
+
+            let Some(name) = get_hint(hint, c"NAME") else {
+                continue;
+            };
+
+            // The `null` device discards everything, it's not useful to anyone.
+            if name.as_bytes() == b"null" {
+                continue;
+            }
+
+            let description = get_hint(hint, c"DESC").map(|d| d.to_string_lossy().into());
+
+            // When missing, the device supports both directions.
+            let (playback, capture) = match get_hint(hint, c"IOID").as_deref() {
+                Some(io) if io == c"Output" => (true, false),
+                Some(io) if io == c"Input" => (false, true),
+                _ => (true, true),
+            };
+
+            f(name, description, playback, capture);
+        }
+
+        snd_device_name_free_hint(hints);
+    }
+
+    Ok(())
+}
+
+/// Reads a hint of a device name hint, as returned by `snd_device_name_hint`.
+///
+/// # Safety
+///
+/// The provided hint must be valid.
+unsafe fn get_hint(hint: *const c_void, id: &CStr) -> Option<CString> {
+    unsafe {
+        let value: *mut c_char = snd_device_name_get_hint(hint, id.as_ptr());
+        if value.is_null() {
+            return None;
+        }
+
+        let result = CStr::from_ptr(value).to_owned();
+        libc::free(value as *mut c_void);
+        Some(result)
+    }
+}
+
+impl Host for AlsaHost {
+    #[inline]
+    fn id(&self) -> HostId {
+        HostId::Alsa
+    }
+
+    #[inline]
+    fn devices(&self) -> Result<Vec<Box<dyn Device>>, BackendError> {
+        Self::enumerate_devices(|_, _| true)
+    }
+
+    #[inline]
+    fn output_devices(&self) -> Result<Vec<Box<dyn Device>>, BackendError> {
+        Self::enumerate_devices(|playback, _| playback)
+    }
+
+    #[inline]
+    fn input_devices(&self) -> Result<Vec<Box<dyn Device>>, BackendError> {
+        Self::enumerate_devices(|_, capture| capture)
+    }
+
+    fn default_input_device(&self, _: RoleHint) -> Result<Option<Box<dyn Device>>, BackendError> {
+        Ok(Some(Box::new(Self::default_device())))
+    }
+
+    fn default_output_device(&self, _: RoleHint) -> Result<Option<Box<dyn Device>>, BackendError> {
+        Ok(Some(Box::new(Self::default_device())))
+    }
+
+    fn watch_devices(
+        &self,
+        callback: Box<dyn Send + FnMut(DeviceEvent)>,
+    ) -> Result<DeviceWatcher, BackendError> {
+        // The `default` device follows the ALSA configuration, it never changes while running.
+        DeviceWatcher::new(callback, PollingThread::new)
+    }
+}
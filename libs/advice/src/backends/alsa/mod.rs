@@ -0,0 +1,16 @@
+use {
+    self::host::AlsaHost,
+    crate::{BackendError, Host},
+};
+
+mod device;
+mod host;
+mod pcm;
+mod stream;
+mod utility;
+mod watcher;
+
+/// Returns the host implementation for ALSA.
+pub fn get_host() -> Result<Box<dyn Host>, BackendError> {
+    Ok(Box::new(AlsaHost))
+}
@@ -0,0 +1,322 @@
+use {
+    super::utility::{channel_layout_to_alsa, check, format_to_alsa},
+    crate::{ChannelLayout, Error, Format},
+    alsa_sys::{
+        SND_PCM_NONBLOCK, snd_pcm_close, snd_pcm_hw_params, snd_pcm_hw_params_any,
+        snd_pcm_hw_params_free, snd_pcm_hw_params_get_buffer_size,
+        snd_pcm_hw_params_get_channels_max, snd_pcm_hw_params_get_period_size,
+        snd_pcm_hw_params_get_period_size_max, snd_pcm_hw_params_get_period_size_min,
+        snd_pcm_hw_params_get_rate_max, snd_pcm_hw_params_get_rate_min, snd_pcm_hw_params_malloc,
+        snd_pcm_hw_params_set_access, snd_pcm_hw_params_set_buffer_size_near,
+        snd_pcm_hw_params_set_channels, snd_pcm_hw_params_set_format,
+        snd_pcm_hw_params_set_period_size_near, snd_pcm_hw_params_set_rate_near,
+        snd_pcm_hw_params_t, snd_pcm_hw_params_test_access, snd_pcm_hw_params_test_format,
+        snd_pcm_hw_params_test_rate, snd_pcm_open, snd_pcm_stream_t, snd_pcm_t, snd_pcm_uframes_t,
+    },
+    std::{ffi::CStr, ptr::NonNull},
+};
+
+/// An open ALSA PCM handle.
+///
+/// The handle is closed when this value is dropped.
+pub struct Pcm(NonNull<snd_pcm_t>);
+
+// SAFETY: A PCM handle may be used from any thread, as long as it is not used concurrently.
+unsafe impl Send for Pcm {}
+
+impl Pcm {
+    /// Opens the PCM device with the provided name.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: the ALSA name of the device (e.g. `default` or `hw:0,0`).
+    ///
+    /// - `stream`: whether the device should be opened for playback or capture.
+    ///
+    /// - `nonblock`: whether opening the device should fail immediately when it is busy.
+    pub fn open(name: &CStr, stream: snd_pcm_stream_t, nonblock: bool) -> Result<Self, Error> {
+        let mode = if nonblock { SND_PCM_NONBLOCK as _ } else { 0 };
+        let mut pcm = std::ptr::null_mut();
+
+        unsafe {
+            check(
+                "snd_pcm_open",
+                snd_pcm_open(&mut pcm, name.as_ptr(), stream, mode),
+            )?;
+            Ok(Self(NonNull::new_unchecked(pcm)))
+        }
+    }
+
+    /// Returns the raw PCM handle.
+    #[inline]
+    pub fn as_ptr(&self) -> *mut snd_pcm_t {
+        self.0.as_ptr()
+    }
+
+    /// Returns the full configuration space of the device.
+    pub fn hw_params_any(&self) -> Result<HwParams, Error> {
+        let params = HwParams::new()?;
+        unsafe {
+            check(
+                "snd_pcm_hw_params_any",
+                snd_pcm_hw_params_any(self.as_ptr(), params.as_ptr()),
+            )?;
+        }
+        Ok(params)
+    }
+
+    /// Installs the provided hardware configuration.
+    ///
+    /// The configuration space must have been restricted to a single configuration beforehand,
+    /// or ALSA will pick one.
+    pub fn install_hw_params(&self, params: &HwParams) -> Result<(), Error> {
+        unsafe {
+            check(
+                "snd_pcm_hw_params",
+                snd_pcm_hw_params(self.as_ptr(), params.as_ptr()),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for Pcm {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe { snd_pcm_close(self.as_ptr()) };
+    }
+}
+
+/// A hardware configuration space for a [`Pcm`].
+///
+/// The configuration space is freed when this value is dropped.
+pub struct HwParams(NonNull<snd_pcm_hw_params_t>);
+
+impl HwParams {
+    /// Allocates a new (uninitialized) configuration space.
+    fn new() -> Result<Self, Error> {
+        let mut params = std::ptr::null_mut();
+        unsafe {
+            check(
+                "snd_pcm_hw_params_malloc",
+                snd_pcm_hw_params_malloc(&mut params),
+            )?;
+            Ok(Self(NonNull::new_unchecked(params)))
+        }
+    }
+
+    /// Returns the raw configuration space.
+    #[inline]
+    pub fn as_ptr(&self) -> *mut snd_pcm_hw_params_t {
+        self.0.as_ptr()
+    }
+
+    /// Returns whether the provided sample format is part of the configuration space.
+    pub fn test_format(&self, pcm: &Pcm, format: Format) -> bool {
+        unsafe {
+            snd_pcm_hw_params_test_format(pcm.as_ptr(), self.as_ptr(), format_to_alsa(format)) == 0
+        }
+    }
+
+    /// Returns whether the provided channel layout is part of the configuration space.
+    pub fn test_channel_layout(&self, pcm: &Pcm, layout: ChannelLayout) -> bool {
+        unsafe {
+            snd_pcm_hw_params_test_access(
+                pcm.as_ptr(),
+                self.as_ptr(),
+                channel_layout_to_alsa(layout),
+            ) == 0
+        }
+    }
+
+    /// Returns whether the provided frame rate is part of the configuration space.
+    pub fn test_rate(&self, pcm: &Pcm, rate: u32) -> bool {
+        unsafe { snd_pcm_hw_params_test_rate(pcm.as_ptr(), self.as_ptr(), rate, 0) == 0 }
+    }
+
+    /// Returns the maximum number of channels of the configuration space.
+    pub fn channels_max(&self) -> Result<u32, Error> {
+        let mut val = 0;
+        unsafe {
+            check(
+                "snd_pcm_hw_params_get_channels_max",
+                snd_pcm_hw_params_get_channels_max(self.as_ptr(), &mut val),
+            )?;
+        }
+        Ok(val)
+    }
+
+    /// Returns the range of frame rates of the configuration space.
+    pub fn rate_range(&self) -> Result<(u32, u32), Error> {
+        let mut min = 0;
+        let mut max = 0;
+        unsafe {
+            check(
+                "snd_pcm_hw_params_get_rate_min",
+                snd_pcm_hw_params_get_rate_min(self.as_ptr(), &mut min, std::ptr::null_mut()),
+            )?;
+            check(
+                "snd_pcm_hw_params_get_rate_max",
+                snd_pcm_hw_params_get_rate_max(self.as_ptr(), &mut max, std::ptr::null_mut()),
+            )?;
+        }
+        Ok((min, max))
+    }
+
+    /// Returns the range of period sizes of the configuration space, in frames.
+    pub fn period_size_range(&self) -> Result<(snd_pcm_uframes_t, snd_pcm_uframes_t), Error> {
+        let mut min = 0;
+        let mut max = 0;
+        unsafe {
+            check(
+                "snd_pcm_hw_params_get_period_size_min",
+                snd_pcm_hw_params_get_period_size_min(
+                    self.as_ptr(),
+                    &mut min,
+                    std::ptr::null_mut(),
+                ),
+            )?;
+            check(
+                "snd_pcm_hw_params_get_period_size_max",
+                snd_pcm_hw_params_get_period_size_max(
+                    self.as_ptr(),
+                    &mut max,
+                    std::ptr::null_mut(),
+                ),
+            )?;
+        }
+        Ok((min, max))
+    }
+
+    /// Returns the period size of the configuration, in frames.
+    ///
+    /// This is only meaningful once the configuration space has been restricted to a single
+    /// period size.
+    pub fn period_size(&self) -> Result<snd_pcm_uframes_t, Error> {
+        let mut val = 0;
+        unsafe {
+            check(
+                "snd_pcm_hw_params_get_period_size",
+                snd_pcm_hw_params_get_period_size(self.as_ptr(), &mut val, std::ptr::null_mut()),
+            )?;
+        }
+        Ok(val)
+    }
+
+    /// Returns the buffer size of the configuration, in frames.
+    ///
+    /// This is only meaningful once the configuration space has been restricted to a single
+    /// buffer size.
+    pub fn buffer_size(&self) -> Result<snd_pcm_uframes_t, Error> {
+        let mut val = 0;
+        unsafe {
+            check(
+                "snd_pcm_hw_params_get_buffer_size",
+                snd_pcm_hw_params_get_buffer_size(self.as_ptr(), &mut val),
+            )?;
+        }
+        Ok(val)
+    }
+
+    /// Restricts the configuration space to the provided channel layout.
+    pub fn set_channel_layout(&self, pcm: &Pcm, layout: ChannelLayout) -> Result<(), Error> {
+        unsafe {
+            check(
+                "snd_pcm_hw_params_set_access",
+                snd_pcm_hw_params_set_access(
+                    pcm.as_ptr(),
+                    self.as_ptr(),
+                    channel_layout_to_alsa(layout),
+                ),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Restricts the configuration space to the provided sample format.
+    pub fn set_format(&self, pcm: &Pcm, format: Format) -> Result<(), Error> {
+        unsafe {
+            check(
+                "snd_pcm_hw_params_set_format",
+                snd_pcm_hw_params_set_format(pcm.as_ptr(), self.as_ptr(), format_to_alsa(format)),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Restricts the configuration space to the provided number of channels.
+    pub fn set_channels(&self, pcm: &Pcm, channels: u32) -> Result<(), Error> {
+        unsafe {
+            check(
+                "snd_pcm_hw_params_set_channels",
+                snd_pcm_hw_params_set_channels(pcm.as_ptr(), self.as_ptr(), channels),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Restricts the configuration space to the frame rate closest to the provided one.
+    ///
+    /// # Returns
+    ///
+    /// The frame rate that was actually selected.
+    pub fn set_rate_near(&self, pcm: &Pcm, rate: u32) -> Result<u32, Error> {
+        let mut val = rate;
+        unsafe {
+            check(
+                "snd_pcm_hw_params_set_rate_near",
+                snd_pcm_hw_params_set_rate_near(
+                    pcm.as_ptr(),
+                    self.as_ptr(),
+                    &mut val,
+                    std::ptr::null_mut(),
+                ),
+            )?;
+        }
+        Ok(val)
+    }
+
+    /// Restricts the configuration space to the period size closest to the provided one.
+    ///
+    /// # Returns
+    ///
+    /// The period size that was actually selected, in frames.
+    pub fn set_period_size_near(
+        &self,
+        pcm: &Pcm,
+        size: snd_pcm_uframes_t,
+    ) -> Result<snd_pcm_uframes_t, Error> {
+        let mut val = size;
+        unsafe {
+            check(
+                "snd_pcm_hw_params_set_period_size_near",
+                snd_pcm_hw_params_set_period_size_near(
+                    pcm.as_ptr(),
+                    self.as_ptr(),
+                    &mut val,
+                    std::ptr::null_mut(),
+                ),
+            )?;
+        }
+        Ok(val)
+    }
+
+    /// Restricts the configuration space to the buffer size closest to the provided one.
+    pub fn set_buffer_size_near(&self, pcm: &Pcm, size: snd_pcm_uframes_t) -> Result<(), Error> {
+        let mut val = size;
+        unsafe {
+            check(
+                "snd_pcm_hw_params_set_buffer_size_near",
+                snd_pcm_hw_params_set_buffer_size_near(pcm.as_ptr(), self.as_ptr(), &mut val),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for HwParams {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe { snd_pcm_hw_params_free(self.as_ptr()) };
+    }
+}
@@ -0,0 +1,463 @@
+use {
+    super::{pcm::Pcm, utility::check},
+    crate::{BackendError, ChannelLayout, Error, Stream, StreamCallback, StreamConfig, StreamData},
+    alsa_sys::{
+        snd_pcm_delay, snd_pcm_drop, snd_pcm_prepare, snd_pcm_readi, snd_pcm_readn,
+        snd_pcm_recover, snd_pcm_sframes_t, snd_pcm_writei, snd_pcm_writen,
+    },
+    std::{
+        num::NonZero,
+        os::raw::{c_int, c_void},
+        sync::{
+            Arc, Mutex, PoisonError,
+            atomic::{AtomicU8, AtomicU64, Ordering},
+        },
+        thread::JoinHandle,
+        time::Duration,
+    },
+};
+
+/// Whether the stream should be playing or not.
+const COMMAND_PLAYING: u8 = 1 << 0;
+/// Whether the stream should be closing or not.
+const COMMAND_CLOSING: u8 = 1 << 1;
+
+/// The direction of an [`AlsaStream`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamDirection {
+    /// The stream plays audio data on the device.
+    Playback,
+    /// The stream captures audio data from the device.
+    Capture,
+}
+
+/// The state that is shared between the [`AlsaStream`] and the high-priority thread.
+struct SharedState {
+    /// A set of flags that represent the commands requested by the [`AlsaStream`] to the
+    /// high-priority thread.
+    command: AtomicU8,
+    /// The number of underruns (or overruns, for input streams) detected by the high-priority
+    /// thread.
+    underrun_count: AtomicU64,
+    /// The delay of the stream measured by the high-priority thread after the last period, in
+    /// frames.
+    ///
+    /// Before the first period is transferred, this is the size of the device buffer.
+    delay: AtomicU64,
+    /// The error that caused the high-priority thread to stop, if any.
+    error: Mutex<Option<Error>>,
+}
+
+/// Represents a running stream on the ALSA host.
+pub struct AlsaStream {
+    /// The state shared between the high-priority thread and the [`AlsaStream`].
+    shared_state: Arc<SharedState>,
+    /// The high-priority thread driving the stream.
+    ///
+    /// The thread is parked while the stream is stopped, and must be unparked whenever the
+    /// commands are updated.
+    thread: Option<JoinHandle<()>>,
+    /// The frame rate that was selected by the device.
+    frame_rate: u32,
+    /// The configuration actually in use by the stream.
+    config: StreamConfig,
+}
+
+impl AlsaStream {
+    /// Creates a new [`AlsaStream`] from the provided PCM device.
+    pub fn new(
+        pcm: Pcm,
+        direction: StreamDirection,
+        config: &StreamConfig,
+        callback: Box<dyn Send + FnMut(StreamCallback)>,
+    ) -> Result<Self, Error> {
+        //
+        // Configure the device with the format supplied by the user.
+        //
+
+        let params = pcm.hw_params_any()?;
+        params.set_channel_layout(&pcm, config.channel_layout)?;
+        params.set_format(&pcm, config.format)?;
+        params.set_channels(&pcm, config.channel_count as u32)?;
+        let frame_rate = params.set_rate_near(&pcm, config.frame_rate.round() as u32)?;
+
+        if let Some(buffer_size) = config.buffer_size {
+            // The callback is called once per period. The device buffer holds two periods: one
+            // being processed by the device while the other one is handled by the callback.
+            let period_size = params.set_period_size_near(&pcm, buffer_size.get() as _)?;
+            params.set_buffer_size_near(&pcm, period_size * 2)?;
+        }
+
+        pcm.install_hw_params(&params)?;
+
+        let period_size = params.period_size()? as usize;
+        let buffer_size = params.buffer_size()?;
+        drop(params);
+
+        //
+        // Create and run the high-priority thread.
+        //
+
+        let shared_state = Arc::new(SharedState {
+            command: AtomicU8::new(0),
+            underrun_count: AtomicU64::new(0),
+            delay: AtomicU64::new(buffer_size),
+            error: Mutex::new(None),
+        });
+
+        let channel_count = config.channel_count as usize;
+        let sample_size = config.format.size_in_bytes() as usize;
+
+        let mut thread_state = HighPriorityThread {
+            pcm,
+            direction,
+            channel_layout: config.channel_layout,
+            shared_state: shared_state.clone(),
+            playing: false,
+            buffer: vec![0; period_size * channel_count * sample_size],
+            planes: Vec::with_capacity(channel_count),
+            plane_cursors: Vec::with_capacity(channel_count),
+            channel_count,
+            sample_size,
+            period_size,
+            callback,
+        };
+
+        let thread_name = match direction {
+            StreamDirection::Playback => "advice-alsa-audio-rendering-thread",
+            StreamDirection::Capture => "advice-alsa-audio-capturing-thread",
+        };
+
+        let thread = std::thread::Builder::new()
+            .name(thread_name.into())
+            .spawn(move || thread_state.run())
+            .map_err(|err| {
+                BackendError::new(format!("Failed to spawn high-priority thread: {err}"))
+            })?;
+
+        // The callback is called once per period, which is what the user thinks of as the
+        // buffer size.
+        let config = StreamConfig {
+            frame_rate: frame_rate as f64,
+            buffer_size: NonZero::new(period_size.min(u32::MAX as usize) as u32),
+            channel_positions: None,
+            ..config.clone()
+        };
+
+        Ok(Self {
+            shared_state,
+            thread: Some(thread),
+            frame_rate,
+            config,
+        })
+    }
+
+    /// Notifies the high-priority thread that the commands have been updated.
+    fn notify_thread(&self) {
+        if let Some(thread) = self.thread.as_ref() {
+            thread.thread().unpark();
+        }
+    }
+}
+
+impl Stream for AlsaStream {
+    fn start(&self) -> Result<(), Error> {
+        // The high-priority thread is gone, starting the stream would silently do nothing.
+        self.check_error()?;
+
+        self.shared_state
+            .command
+            .fetch_or(COMMAND_PLAYING, Ordering::SeqCst);
+        self.notify_thread();
+        Ok(())
+    }
+
+    fn stop(&self) -> Result<(), Error> {
+        self.check_error()?;
+
+        self.shared_state
+            .command
+            .fetch_and(!COMMAND_PLAYING, Ordering::SeqCst);
+        self.notify_thread();
+        Ok(())
+    }
+
+    fn check_error(&self) -> Result<(), Error> {
+        let error = self
+            .shared_state
+            .error
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        error.clone().map_or(Ok(()), Err)
+    }
+
+    #[inline]
+    fn config(&self) -> &StreamConfig {
+        &self.config
+    }
+
+    fn latency(&self) -> Result<Duration, Error> {
+        let delay = self.shared_state.delay.load(Ordering::Relaxed);
+        Ok(Duration::from_secs_f64(
+            delay as f64 / self.frame_rate as f64,
+        ))
+    }
+
+    #[inline]
+    fn underrun_count(&self) -> u64 {
+        self.shared_state.underrun_count.load(Ordering::Relaxed)
+    }
+}
+
+impl Drop for AlsaStream {
+    fn drop(&mut self) {
+        self.shared_state
+            .command
+            .fetch_or(COMMAND_CLOSING, Ordering::SeqCst);
+        self.notify_thread();
+
+        // The thread returns after at most one period. Waiting for it ensures that the device
+        // is closed by the time the stream is dropped.
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Requests the current thread to become a high-priority real-time thread.
+///
+/// This usually requires specific privileges (such as an `rtprio` limit). When the request
+/// fails, the thread keeps running with its default priority.
+fn become_high_priority_thread() {
+    unsafe {
+        let param = libc::sched_param {
+            sched_priority: libc::sched_get_priority_max(libc::SCHED_FIFO),
+        };
+        let _ = libc::pthread_setschedparam(libc::pthread_self(), libc::SCHED_FIFO, &param);
+    }
+}
+
+/// The state of the high-priority thread working with the stream.
+struct HighPriorityThread {
+    /// The PCM device driven by the thread.
+    pcm: Pcm,
+    /// Whether the thread renders or captures audio data.
+    direction: StreamDirection,
+    /// The layout of the audio data exchanged with the device.
+    channel_layout: ChannelLayout,
+
+    /// The shared state between the high-priority thread and the [`AlsaStream`].
+    shared_state: Arc<SharedState>,
+    /// Whether the device is currently running or not.
+    playing: bool,
+
+    /// The buffer exchanged with the user-defined callback, holding a single period.
+    ///
+    /// For planar streams, the channels are stored one after the other.
+    buffer: Vec<u8>,
+    /// For planar streams, a pointer to the start of each channel within `buffer`.
+    planes: Vec<*mut u8>,
+    /// For planar streams, a pointer to the first frame of each channel that has not been
+    /// transferred to (or from) the device yet.
+    plane_cursors: Vec<*mut c_void>,
+
+    /// The number of channels of the stream.
+    channel_count: usize,
+    /// The size of a single sample, in bytes.
+    sample_size: usize,
+    /// The size of a period, in frames.
+    period_size: usize,
+
+    /// The user-defined callback responsible for actually rendering or capturing the audio data.
+    callback: Box<dyn Send + FnMut(StreamCallback)>,
+}
+
+// SAFETY: The raw pointers stored in the thread state all point into `buffer`, which is owned
+// by the state itself.
+unsafe impl Send for HighPriorityThread {}
+
+impl HighPriorityThread {
+    /// Runs the high priority thread.
+    pub fn run(&mut self) {
+        become_high_priority_thread();
+
+        let plane_size = self.period_size * self.sample_size;
+        let base = self.buffer.as_mut_ptr();
+        self.planes = (0..self.channel_count)
+            .map(|i| unsafe { base.add(i * plane_size) })
+            .collect();
+
+        if let Err(err) = self.run_fallible() {
+            *self
+                .shared_state
+                .error
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner) = Some(err);
+        }
+
+        if self.playing {
+            unsafe { snd_pcm_drop(self.pcm.as_ptr()) };
+        }
+    }
+
+    /// Runs the high-priority thread to completion, returns an error if something goes wrong.
+    fn run_fallible(&mut self) -> Result<(), Error> {
+        while self.process_commands()? {
+            if !self.playing {
+                // Spurious wake-ups are fine, the commands are checked again anyway.
+                std::thread::park();
+                continue;
+            }
+
+            match self.direction {
+                StreamDirection::Playback => self.render()?,
+                StreamDirection::Capture => self.capture()?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Process the commands that have been requested by the [`AlsaStream`].
+    ///
+    /// # Returns
+    ///
+    /// This function returns whether the stream should continue running or not.
+    fn process_commands(&mut self) -> Result<bool, Error> {
+        let new_commands = self.shared_state.command.load(Ordering::SeqCst);
+
+        if new_commands & COMMAND_CLOSING != 0 {
+            return Ok(false);
+        }
+
+        let should_play = new_commands & COMMAND_PLAYING != 0;
+
+        if should_play != self.playing {
+            self.playing = should_play;
+
+            // Playback streams start automatically once data has been written to them, and
+            // capture streams once data is read from them.
+            if self.playing {
+                unsafe { check("snd_pcm_prepare", snd_pcm_prepare(self.pcm.as_ptr()))? };
+            } else {
+                unsafe { check("snd_pcm_drop", snd_pcm_drop(self.pcm.as_ptr()))? };
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Calls the user-defined callback with the content of the buffer.
+    fn call_callback(&mut self) {
+        let data = match self.channel_layout {
+            ChannelLayout::Interleaved => StreamData {
+                interleaved: self.buffer.as_mut_ptr(),
+            },
+            ChannelLayout::Planar => StreamData {
+                planar: self.planes.as_ptr(),
+            },
+        };
+
+        (self.callback)(StreamCallback {
+            data,
+            frame_count: self.period_size,
+        });
+    }
+
+    /// Recovers from an error returned by `snd_pcm_writei` or `snd_pcm_readi`.
+    fn recover(&mut self, err: snd_pcm_sframes_t) -> Result<(), Error> {
+        let err = err as c_int;
+
+        if err == -libc::EPIPE {
+            // The device ran out of data to play (or of space to store captured data).
+            self.shared_state
+                .underrun_count
+                .fetch_add(1, Ordering::Relaxed);
+        }
+
+        unsafe {
+            check(
+                "snd_pcm_recover",
+                snd_pcm_recover(self.pcm.as_ptr(), err, 1),
+            )?
+        };
+        Ok(())
+    }
+
+    /// Transfers the period starting at the provided frame between the buffer and the device.
+    ///
+    /// # Returns
+    ///
+    /// The number of frames that were transferred, or a negative error code.
+    fn transfer(&mut self, offset: usize) -> snd_pcm_sframes_t {
+        let remaining = (self.period_size - offset) as _;
+        let pcm = self.pcm.as_ptr();
+
+        unsafe {
+            match self.channel_layout {
+                ChannelLayout::Interleaved => {
+                    let frame_size = self.channel_count * self.sample_size;
+                    let ptr = self.buffer.as_mut_ptr().add(offset * frame_size) as *mut c_void;
+                    match self.direction {
+                        StreamDirection::Playback => snd_pcm_writei(pcm, ptr, remaining),
+                        StreamDirection::Capture => snd_pcm_readi(pcm, ptr, remaining),
+                    }
+                }
+                ChannelLayout::Planar => {
+                    self.plane_cursors.clear();
+                    self.plane_cursors.extend(
+                        self.planes
+                            .iter()
+                            .map(|&p| p.add(offset * self.sample_size) as *mut c_void),
+                    );
+                    let ptrs = self.plane_cursors.as_mut_ptr();
+                    match self.direction {
+                        StreamDirection::Playback => snd_pcm_writen(pcm, ptrs, remaining),
+                        StreamDirection::Capture => snd_pcm_readn(pcm, ptrs, remaining),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Transfers a whole period between the buffer and the device, blocking until done.
+    fn transfer_period(&mut self) -> Result<(), Error> {
+        let mut offset = 0;
+        while offset < self.period_size {
+            let ret = self.transfer(offset);
+            if ret < 0 {
+                self.recover(ret)?;
+            } else {
+                offset += ret as usize;
+            }
+        }
+
+        self.update_delay();
+        Ok(())
+    }
+
+    /// Measures the delay of the stream and publishes it to the [`AlsaStream`].
+    ///
+    /// Failing to measure the delay is not fatal, the previous value is kept.
+    fn update_delay(&self) {
+        let mut delay: snd_pcm_sframes_t = 0;
+        let ret = unsafe { snd_pcm_delay(self.pcm.as_ptr(), &mut delay) };
+        if ret >= 0 {
+            self.shared_state
+                .delay
+                .store(delay.max(0) as u64, Ordering::Relaxed);
+        }
+    }
+
+    /// Executes the output callback once and plays the rendered period.
+    fn render(&mut self) -> Result<(), Error> {
+        self.call_callback();
+        self.transfer_period()
+    }
+
+    /// Captures a period and executes the input callback once.
+    fn capture(&mut self) -> Result<(), Error> {
+        self.transfer_period()?;
+        self.call_callback();
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+use {
+    crate::{BackendError, ChannelLayout, Error, Format},
+    alsa_sys::{
+        SND_PCM_ACCESS_RW_INTERLEAVED, SND_PCM_ACCESS_RW_NONINTERLEAVED, SND_PCM_FORMAT_FLOAT_LE,
+        SND_PCM_FORMAT_FLOAT64_LE, SND_PCM_FORMAT_S8, SND_PCM_FORMAT_S16_LE,
+        SND_PCM_FORMAT_S24_3LE, SND_PCM_FORMAT_S32_LE, SND_PCM_FORMAT_U8, SND_PCM_FORMAT_U16_LE,
+        SND_PCM_FORMAT_U24_3LE, SND_PCM_FORMAT_U32_LE, snd_pcm_access_t, snd_pcm_format_t,
+        snd_strerror,
+    },
+    std::{ffi::CStr, os::raw::c_int},
+};
+
+/// Turns the provided ALSA error code into a [`BackendError`].
+pub fn backend_error(context: &str, err: c_int) -> BackendError {
+    let message = unsafe { CStr::from_ptr(snd_strerror(err)) };
+    BackendError::with_code(
+        format!("ALSA: {}: {} ({})", context, message.to_string_lossy(), err),
+        err,
+    )
+}
+
+/// Turns the provided ALSA error code into an [`Error`].
+///
+/// This function will automatically catch errors indicating that the device is not available
+/// (or was disconnected) and return an [`Error::DeviceNotAvailable`] (or
+/// [`Error::DeviceDisconnected`]) instead.
+pub fn device_error(context: &str, err: c_int) -> Error {
+    match -err {
+        libc::ENODEV => Error::DeviceDisconnected,
+        libc::ENOENT | libc::ENXIO => Error::DeviceNotAvailable,
+        libc::EBUSY => Error::DeviceInUse,
+        libc::EINVAL => Error::UnsupportedConfiguration,
+        _ => Error::Backend(backend_error(context, err)),
+    }
+}
+
+/// Checks the return value of an ALSA function, turning negative values into an [`Error`].
+#[inline]
+pub fn check(context: &str, ret: c_int) -> Result<c_int, Error> {
+    if ret < 0 {
+        Err(device_error(context, ret))
+    } else {
+        Ok(ret)
+    }
+}
+
+/// All the formats that the ALSA backend knows how to use.
+pub const KNOWN_FORMATS: [Format; 10] = [
+    Format::I8,
+    Format::U8,
+    Format::I16,
+    Format::U16,
+    Format::I24,
+    Format::U24,
+    Format::I32,
+    Format::U32,
+    Format::F32,
+    Format::F64,
+];
+
+/// Converts the provided [`Format`] to an ALSA sample format.
+///
+/// 24-bit formats are mapped to their packed 3-byte variant, which matches
+/// [`Format::size_in_bytes`].
+pub fn format_to_alsa(format: Format) -> snd_pcm_format_t {
+    match format {
+        Format::I8 => SND_PCM_FORMAT_S8,
+        Format::U8 => SND_PCM_FORMAT_U8,
+        Format::I16 => SND_PCM_FORMAT_S16_LE,
+        Format::U16 => SND_PCM_FORMAT_U16_LE,
+        Format::I24 => SND_PCM_FORMAT_S24_3LE,
+        Format::U24 => SND_PCM_FORMAT_U24_3LE,
+        Format::I32 => SND_PCM_FORMAT_S32_LE,
+        Format::U32 => SND_PCM_FORMAT_U32_LE,
+        Format::F32 => SND_PCM_FORMAT_FLOAT_LE,
+        Format::F64 => SND_PCM_FORMAT_FLOAT64_LE,
+    }
+}
+
+/// Converts the provided [`ChannelLayout`] to an ALSA access mode.
+pub fn channel_layout_to_alsa(layout: ChannelLayout) -> snd_pcm_access_t {
+    match layout {
+        ChannelLayout::Interleaved => SND_PCM_ACCESS_RW_INTERLEAVED,
+        ChannelLayout::Planar => SND_PCM_ACCESS_RW_NONINTERLEAVED,
+    }
+}
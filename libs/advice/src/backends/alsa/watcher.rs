@@ -0,0 +1,92 @@
+use {
+    super::host::for_each_hint,
+    crate::{BackendError, DeviceEvent},
+    std::{
+        collections::HashSet,
+        ffi::CString,
+        sync::{
+            Arc,
+            atomic::{AtomicBool, Ordering},
+            mpsc,
+        },
+        thread::JoinHandle,
+        time::Duration,
+    },
+};
+
+/// The interval at which the device list is polled.
+///
+/// ALSA itself has no way to notify applications that the available devices changed.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A thread that periodically polls the device list to watch for changes.
+///
+/// The thread is stopped when this value is dropped.
+pub struct PollingThread {
+    /// Whether the thread should stop.
+    closing: Arc<AtomicBool>,
+    /// The polling thread.
+    thread: Option<JoinHandle<()>>,
+}
+
+impl PollingThread {
+    /// Spawns the polling thread, emitting events to the provided sender.
+    pub fn new(sender: mpsc::Sender<DeviceEvent>) -> Result<Self, BackendError> {
+        let mut known = device_names()?;
+        let closing = Arc::new(AtomicBool::new(false));
+
+        let thread = std::thread::Builder::new()
+            .name("advice-alsa-device-polling-thread".into())
+            .spawn({
+                let closing = closing.clone();
+                move || {
+                    loop {
+                        // Spurious wake-ups only make the thread poll a bit earlier.
+                        std::thread::park_timeout(POLL_INTERVAL);
+                        if closing.load(Ordering::Relaxed) {
+                            break;
+                        }
+
+                        let Ok(current) = device_names() else {
+                            continue;
+                        };
+
+                        if current.difference(&known).next().is_some() {
+                            let _ = sender.send(DeviceEvent::Added);
+                        }
+                        if known.difference(&current).next().is_some() {
+                            let _ = sender.send(DeviceEvent::Removed);
+                        }
+
+                        known = current;
+                    }
+                }
+            })
+            .map_err(|err| BackendError::new(format!("Failed to spawn polling thread: {err}")))?;
+
+        Ok(Self {
+            closing,
+            thread: Some(thread),
+        })
+    }
+}
+
+impl Drop for PollingThread {
+    fn drop(&mut self) {
+        self.closing.store(true, Ordering::Relaxed);
+
+        if let Some(thread) = self.thread.take() {
+            thread.thread().unpark();
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Returns the names of the PCM devices currently known to ALSA.
+fn device_names() -> Result<HashSet<CString>, BackendError> {
+    let mut names = HashSet::new();
+    for_each_hint(|name, _, _, _| {
+        names.insert(name);
+    })?;
+    Ok(names)
+}
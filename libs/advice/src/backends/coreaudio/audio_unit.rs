@@ -5,7 +5,7 @@ use {
         AURenderCallbackStruct, AudioBufferList, AudioComponentDescription, AudioComponentFindNext,
         AudioComponentInstanceDispose, AudioComponentInstanceNew, AudioDeviceID,
         AudioOutputUnitStart, AudioOutputUnitStop, AudioStreamBasicDescription, AudioTimeStamp,
-        AudioUnit as AudioUnitSys, AudioUnitElement, AudioUnitInitialize,
+        AudioUnit as AudioUnitSys, AudioUnitElement, AudioUnitGetProperty, AudioUnitInitialize,
         AudioUnitRenderActionFlags, AudioUnitScope, AudioUnitSetProperty, OSStatus, OSType, UInt32,
         kAudioDevicePropertyBufferFrameSize, kAudioOutputUnitProperty_CurrentDevice,
         kAudioUnitManufacturer_Apple, kAudioUnitProperty_SetRenderCallback,
@@ -231,6 +231,29 @@ impl AudioUnit {
         Ok(())
     }
 
+    /// Returns the device currently used by the audio unit.
+    pub fn current_device(&self) -> Result<AudioDeviceID, Error> {
+        let mut device_id: AudioDeviceID = 0;
+        let mut size = std::mem::size_of::<AudioDeviceID>() as u32;
+
+        unsafe {
+            let ret = AudioUnitGetProperty(
+                self.inner,
+                kAudioOutputUnitProperty_CurrentDevice,
+                kAudioUnitScope_Global,
+                0,
+                &mut device_id as *mut _ as *mut _,
+                &mut size,
+            );
+
+            if ret != noErr as i32 {
+                return Err(device_error("Failed to get current device", ret));
+            }
+        }
+
+        Ok(device_id)
+    }
+
     /// Sets the buffer size of the audio unit.
     pub fn set_buffer_size(
         &self,
@@ -1,12 +1,17 @@
 use {
-    super::device::CoreAudioDevice,
-    crate::{BackendError, Device, Host, RoleHint, backends::coreaudio::utility::backend_error},
+    super::{device::CoreAudioDevice, watcher::DeviceListeners},
+    crate::{
+        BackendError, Device, DeviceEvent, DeviceWatcher, Host, HostId, RoleHint,
+        backends::coreaudio::utility::backend_error,
+    },
     coreaudio_sys::{
         AudioDeviceID, AudioObjectGetPropertyData, AudioObjectGetPropertyDataSize,
-        AudioObjectPropertyAddress, AudioObjectPropertySelector,
-        kAudioHardwarePropertyDefaultInputDevice, kAudioHardwarePropertyDefaultOutputDevice,
-        kAudioHardwarePropertyDevices, kAudioObjectPropertyElementMain,
-        kAudioObjectPropertyScopeGlobal, kAudioObjectSystemObject, noErr,
+        AudioObjectPropertyAddress, AudioObjectPropertyScope, AudioObjectPropertySelector,
+        kAudioDevicePropertyStreams, kAudioHardwarePropertyDefaultInputDevice,
+        kAudioHardwarePropertyDefaultOutputDevice, kAudioHardwarePropertyDevices,
+        kAudioObjectPropertyElementMain, kAudioObjectPropertyScopeGlobal,
+        kAudioObjectPropertyScopeInput, kAudioObjectPropertyScopeOutput, kAudioObjectSystemObject,
+        noErr,
     },
 };
 
@@ -15,7 +20,7 @@ pub struct CoreAudioHost;
 
 impl CoreAudioHost {
     /// Returns the list of available device IDs.
-    fn enumerate_device_ids(&self) -> Result<Vec<AudioDeviceID>, BackendError> {
+    pub fn enumerate_device_ids(&self) -> Result<Vec<AudioDeviceID>, BackendError> {
         let property_address = AudioObjectPropertyAddress {
             mSelector: kAudioHardwarePropertyDevices,
             mScope: kAudioObjectPropertyScopeGlobal,
@@ -68,6 +73,51 @@ impl CoreAudioHost {
         Ok(device_ids)
     }
 
+    /// Returns whether the provided device has at least one stream in the provided scope.
+    fn has_streams(
+        &self,
+        device_id: AudioDeviceID,
+        scope: AudioObjectPropertyScope,
+    ) -> Result<bool, BackendError> {
+        let property_address = AudioObjectPropertyAddress {
+            mSelector: kAudioDevicePropertyStreams,
+            mScope: scope,
+            mElement: kAudioObjectPropertyElementMain,
+        };
+
+        let mut size = 0;
+
+        unsafe {
+            let ret = AudioObjectGetPropertyDataSize(
+                device_id,
+                &property_address,
+                0,
+                std::ptr::null(),
+                &mut size,
+            );
+
+            if ret != noErr as i32 {
+                return Err(backend_error("Failed to get the streams of a device", ret));
+            }
+        }
+
+        Ok(size != 0)
+    }
+
+    /// Returns the devices that have at least one stream in the provided scope.
+    fn enumerate_devices_with_scope(
+        &self,
+        scope: AudioObjectPropertyScope,
+    ) -> Result<Vec<Box<dyn Device>>, BackendError> {
+        let mut devices: Vec<Box<dyn Device>> = Vec::new();
+        for id in self.enumerate_device_ids()? {
+            if self.has_streams(id, scope)? {
+                devices.push(Box::new(CoreAudioDevice::new(id, false)));
+            }
+        }
+        Ok(devices)
+    }
+
     /// Returns the default device for the provided role hint.
     pub fn get_default_device(
         &self,
@@ -102,6 +152,11 @@ impl CoreAudioHost {
 }
 
 impl Host for CoreAudioHost {
+    #[inline]
+    fn id(&self) -> HostId {
+        HostId::CoreAudio
+    }
+
     fn devices(&self) -> Result<Vec<Box<dyn Device>>, BackendError> {
         Ok(self
             .enumerate_device_ids()?
@@ -110,6 +165,16 @@ impl Host for CoreAudioHost {
             .collect())
     }
 
+    #[inline]
+    fn output_devices(&self) -> Result<Vec<Box<dyn Device>>, BackendError> {
+        self.enumerate_devices_with_scope(kAudioObjectPropertyScopeOutput)
+    }
+
+    #[inline]
+    fn input_devices(&self) -> Result<Vec<Box<dyn Device>>, BackendError> {
+        self.enumerate_devices_with_scope(kAudioObjectPropertyScopeInput)
+    }
+
     fn default_input_device(&self, _: RoleHint) -> Result<Option<Box<dyn Device>>, BackendError> {
         self.get_default_device(kAudioHardwarePropertyDefaultInputDevice)
             .map(|id| Some(Box::new(CoreAudioDevice::new(id, false)) as Box<dyn Device>))
@@ -119,4 +184,11 @@ impl Host for CoreAudioHost {
         self.get_default_device(kAudioHardwarePropertyDefaultOutputDevice)
             .map(|id| Some(Box::new(CoreAudioDevice::new(id, true)) as Box<dyn Device>))
     }
+
+    fn watch_devices(
+        &self,
+        callback: Box<dyn Send + FnMut(DeviceEvent)>,
+    ) -> Result<DeviceWatcher, BackendError> {
+        DeviceWatcher::new(callback, DeviceListeners::new)
+    }
 }
@@ -8,6 +8,7 @@ mod device;
 mod host;
 mod stream;
 mod utility;
+mod watcher;
 
 /// Returns the host implementation for CoreAudio.
 pub fn get_host() -> Result<Box<dyn Host>, BackendError> {
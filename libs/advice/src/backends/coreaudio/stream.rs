@@ -1,11 +1,28 @@
 use {
-    super::{audio_unit::AudioUnit, utility::make_basic_desc},
+    super::{
+        audio_unit::AudioUnit,
+        utility::{device_error, make_basic_desc},
+    },
     crate::{Error, ShareMode, Stream, StreamCallback, StreamConfig, StreamData},
-    coreaudio_sys::{AudioDeviceID, kAudioUnitScope_Input},
+    coreaudio_sys::{
+        AudioDeviceID, AudioObjectGetPropertyData, AudioObjectPropertyAddress,
+        AudioObjectPropertySelector, kAudioDevicePropertyBufferFrameSize,
+        kAudioDevicePropertyLatency, kAudioDevicePropertySafetyOffset,
+        kAudioObjectPropertyElementMain, kAudioObjectPropertyScopeOutput, kAudioUnitScope_Input,
+        noErr,
+    },
+    std::{num::NonZero, time::Duration},
 };
 
 /// The output stream for CoreAudio.
-pub struct CoreAudioOutputStream(AudioUnit);
+pub struct CoreAudioOutputStream {
+    /// The audio unit driving the stream.
+    audio_unit: AudioUnit,
+    /// The frame rate of the stream.
+    frame_rate: f64,
+    /// The configuration actually in use by the stream.
+    config: StreamConfig,
+}
 
 impl CoreAudioOutputStream {
     /// Creates a new [`CoreAudioOutputStream`].
@@ -48,22 +65,87 @@ impl CoreAudioOutputStream {
         })?;
         audio_unit.initialize()?;
 
-        Ok(Self(audio_unit))
+        // The HAL may have rounded the requested buffer size to something the device supports.
+        let buffer_size = audio_unit
+            .current_device()
+            .and_then(|id| get_output_property_u32(id, kAudioDevicePropertyBufferFrameSize))
+            .ok()
+            .and_then(NonZero::new)
+            .or(config.buffer_size);
+
+        Ok(Self {
+            audio_unit,
+            frame_rate: config.frame_rate,
+            config: StreamConfig {
+                buffer_size,
+                channel_positions: None,
+                ..config.clone()
+            },
+        })
     }
 }
 
+/// Reads a property of the provided device as a `u32`, in the output scope.
+fn get_output_property_u32(
+    device_id: AudioDeviceID,
+    selector: AudioObjectPropertySelector,
+) -> Result<u32, Error> {
+    let property_address = AudioObjectPropertyAddress {
+        mSelector: selector,
+        mScope: kAudioObjectPropertyScopeOutput,
+        mElement: kAudioObjectPropertyElementMain,
+    };
+
+    let mut value: u32 = 0;
+    let mut size = std::mem::size_of::<u32>() as u32;
+
+    unsafe {
+        let ret = AudioObjectGetPropertyData(
+            device_id,
+            &property_address,
+            0,
+            std::ptr::null(),
+            &mut size,
+            &mut value as *mut _ as _,
+        );
+
+        if ret != noErr as i32 {
+            return Err(device_error("Failed to read device property", ret));
+        }
+    }
+
+    Ok(value)
+}
+
 impl Stream for CoreAudioOutputStream {
     #[inline]
     fn start(&self) -> Result<(), Error> {
-        self.0.output_stop()
+        self.audio_unit.output_stop()
     }
 
     #[inline]
     fn stop(&self) -> Result<(), Error> {
-        self.0.output_start()
+        self.audio_unit.output_start()
     }
 
     fn check_error(&self) -> Result<(), Error> {
         Ok(())
     }
+
+    #[inline]
+    fn config(&self) -> &StreamConfig {
+        &self.config
+    }
+
+    fn latency(&self) -> Result<Duration, Error> {
+        let device_id = self.audio_unit.current_device()?;
+
+        // The device latency, the safety offset of the HAL, and the buffer that is currently
+        // being rendered all delay the output, in frames.
+        let frames = get_output_property_u32(device_id, kAudioDevicePropertyLatency)? as u64
+            + get_output_property_u32(device_id, kAudioDevicePropertySafetyOffset)? as u64
+            + get_output_property_u32(device_id, kAudioDevicePropertyBufferFrameSize)? as u64;
+
+        Ok(Duration::from_secs_f64(frames as f64 / self.frame_rate))
+    }
 }
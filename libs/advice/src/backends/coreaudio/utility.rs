@@ -10,7 +10,7 @@ use {
 
 /// Converts the provided `OSStatus` to a crate-specific error.
 pub fn backend_error(ctx: &str, err: OSStatus) -> BackendError {
-    BackendError::new(format!("{}: 0x{:x}", ctx, err))
+    BackendError::with_code(format!("{}: 0x{:x}", ctx, err), err)
 }
 
 /// Converts the provided `OSStatus` to a crate-specific error.
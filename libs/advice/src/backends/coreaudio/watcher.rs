@@ -0,0 +1,151 @@
+use {
+    super::{host::CoreAudioHost, utility::backend_error},
+    crate::{BackendError, DeviceEvent},
+    coreaudio_sys::{
+        AudioObjectAddPropertyListener, AudioObjectID, AudioObjectPropertyAddress,
+        AudioObjectRemovePropertyListener, OSStatus, UInt32,
+        kAudioHardwarePropertyDefaultInputDevice, kAudioHardwarePropertyDefaultOutputDevice,
+        kAudioHardwarePropertyDevices, kAudioObjectPropertyElementMain,
+        kAudioObjectPropertyScopeGlobal, kAudioObjectSystemObject, noErr,
+    },
+    std::{
+        ffi::c_void,
+        sync::{
+            Mutex, PoisonError,
+            atomic::{AtomicUsize, Ordering},
+            mpsc,
+        },
+    },
+};
+
+/// The properties of the system object that the watcher listens to.
+const WATCHED_SELECTORS: [u32; 3] = [
+    kAudioHardwarePropertyDevices,
+    kAudioHardwarePropertyDefaultInputDevice,
+    kAudioHardwarePropertyDefaultOutputDevice,
+];
+
+/// The state that is passed to the property listener.
+struct ListenerState {
+    /// The sender used to emit events.
+    sender: Mutex<mpsc::Sender<DeviceEvent>>,
+    /// The number of devices the last time the device list was read.
+    ///
+    /// CoreAudio only reports that the list changed, this is used to tell additions and
+    /// removals apart.
+    device_count: AtomicUsize,
+}
+
+/// Property listeners registered on the system object to watch the available devices.
+///
+/// The listeners are removed when this value is dropped.
+pub struct DeviceListeners {
+    /// The state passed to the listener.
+    ///
+    /// It must remain at a stable address for as long as the listeners are registered.
+    state: Box<ListenerState>,
+    /// The number of listeners that have been successfully registered.
+    registered: usize,
+}
+
+impl DeviceListeners {
+    /// Registers the property listeners, emitting events to the provided sender.
+    pub fn new(sender: mpsc::Sender<DeviceEvent>) -> Result<Self, BackendError> {
+        let device_count = CoreAudioHost.enumerate_device_ids()?.len();
+
+        let mut this = Self {
+            state: Box::new(ListenerState {
+                sender: Mutex::new(sender),
+                device_count: AtomicUsize::new(device_count),
+            }),
+            registered: 0,
+        };
+
+        for selector in WATCHED_SELECTORS {
+            let ret = unsafe {
+                AudioObjectAddPropertyListener(
+                    kAudioObjectSystemObject,
+                    &make_address(selector),
+                    Some(listener),
+                    this.state_ptr(),
+                )
+            };
+
+            if ret != noErr as i32 {
+                // The listeners that were already registered are removed when `this` is dropped.
+                return Err(backend_error("Failed to register the device watcher", ret));
+            }
+
+            this.registered += 1;
+        }
+
+        Ok(this)
+    }
+
+    /// Returns the pointer passed to the listener as its client data.
+    fn state_ptr(&self) -> *mut c_void {
+        &*self.state as *const ListenerState as *mut c_void
+    }
+}
+
+impl Drop for DeviceListeners {
+    fn drop(&mut self) {
+        for &selector in &WATCHED_SELECTORS[..self.registered] {
+            unsafe {
+                AudioObjectRemovePropertyListener(
+                    kAudioObjectSystemObject,
+                    &make_address(selector),
+                    Some(listener),
+                    self.state_ptr(),
+                );
+            }
+        }
+    }
+}
+
+/// Creates the address of a global property of the system object.
+fn make_address(selector: u32) -> AudioObjectPropertyAddress {
+    AudioObjectPropertyAddress {
+        mSelector: selector,
+        mScope: kAudioObjectPropertyScopeGlobal,
+        mElement: kAudioObjectPropertyElementMain,
+    }
+}
+
+/// The property listener registered by [`DeviceListeners`].
+///
+/// It's called by the HAL on one of its own threads.
+extern "C" fn listener(
+    _object_id: AudioObjectID,
+    address_count: UInt32,
+    addresses: *const AudioObjectPropertyAddress,
+    client_data: *mut c_void,
+) -> OSStatus {
+    let state = unsafe { &*(client_data as *const ListenerState) };
+    let addresses = unsafe { std::slice::from_raw_parts(addresses, address_count as usize) };
+
+    let sender = state.sender.lock().unwrap_or_else(PoisonError::into_inner);
+
+    for address in addresses {
+        let event = match address.mSelector {
+            kAudioHardwarePropertyDevices => {
+                let Ok(ids) = CoreAudioHost.enumerate_device_ids() else {
+                    continue;
+                };
+                let previous = state.device_count.swap(ids.len(), Ordering::Relaxed);
+                if ids.len() < previous {
+                    DeviceEvent::Removed
+                } else {
+                    DeviceEvent::Added
+                }
+            }
+            kAudioHardwarePropertyDefaultInputDevice
+            | kAudioHardwarePropertyDefaultOutputDevice => DeviceEvent::DefaultChanged,
+            _ => continue,
+        };
+
+        let _ = sender.send(event);
+    }
+
+    noErr as OSStatus
+}
@@ -1,3 +1,5 @@
+#[cfg(all(feature = "alsa", target_os = "linux"))]
+pub mod alsa;
 #[cfg(all(feature = "coreaudio", target_os = "macos"))]
 pub mod coreaudio;
 #[cfg(all(feature = "wasapi", target_os = "windows"))]
@@ -1,10 +1,10 @@
 use {
     crate::{
-        ChannelLayouts, Device, DeviceFormats, Error, Format, ShareMode, Stream, StreamCallback,
-        StreamConfig,
+        ChannelLayouts, ChannelPositions, Device, DeviceFormats, Error, Format, ShareMode, Stream,
+        StreamCallback, StreamConfig,
         backends::wasapi::{
             host_config::WasapiHostConfig,
-            stream::WasapiStream,
+            stream::{StreamDirection, WasapiStream},
             utility::{
                 break_waveformat, device_error, duration_to_frames, guard, make_waveformatex,
                 make_waveformatextensible, share_mode_to_wasapi,
@@ -141,6 +141,12 @@ impl WasapiDevice {
         }
     }
 
+    /// Returns whether a device with the provided data-flow can be used to capture the audio it
+    /// is playing, in the provided share mode.
+    fn supports_loopback(&self, data_flow: EDataFlow, share: ShareMode) -> bool {
+        self.config.loopback_capture && data_flow == eRender && share == ShareMode::Share
+    }
+
     /// Creates a new [`IAudioClient`] for the device.
     fn create_new_audio_client(&self) -> Result<IAudioClient, Error> {
         unsafe {
@@ -178,6 +184,23 @@ impl WasapiDevice {
         }
     }
 
+    /// Opens a [`WasapiStream`] on the device.
+    fn open_stream(
+        &self,
+        direction: StreamDirection,
+        config: StreamConfig,
+        callback: Box<dyn Send + FnMut(StreamCallback)>,
+    ) -> Result<WasapiStream, Error> {
+        WasapiStream::new(
+            self.take_audio_client()?,
+            &|| self.create_new_audio_client(),
+            direction,
+            config,
+            self.config.polling,
+            callback,
+        )
+    }
+
     /// Gets the mix format of the device when used in shared mode.
     fn get_shared_mix_format(&self) -> Result<WaveformatObject, Error> {
         unsafe {
@@ -354,6 +377,7 @@ impl WasapiDevice {
                         channel_count,
                         format,
                         frame_rate,
+                        ChannelPositions::default_for_channel_count(channel_count),
                         &mut waveformat,
                     ) {
                         continue;
@@ -396,7 +420,8 @@ impl Device for WasapiDevice {
     }
 
     fn input_formats(&self, share: ShareMode) -> Result<Option<DeviceFormats>, Error> {
-        if self.data_flow()? == eCapture {
+        let data_flow = self.data_flow()?;
+        if data_flow == eCapture || self.supports_loopback(data_flow, share) {
             let share = share_mode_to_wasapi(share);
             self.query_supported_formats(share)
         } else {
@@ -409,15 +434,22 @@ impl Device for WasapiDevice {
         config: StreamConfig,
         callback: Box<dyn Send + FnMut(StreamCallback)>,
     ) -> Result<Box<dyn Stream>, Error> {
-        let stream = WasapiStream::new(self.take_audio_client()?, config, callback)?;
+        let stream = self.open_stream(StreamDirection::Render, config, callback)?;
         Ok(Box::new(stream))
     }
 
     fn open_input_stream(
         &self,
-        _config: StreamConfig,
-        _callback: Box<dyn Send + FnMut(StreamCallback)>,
+        config: StreamConfig,
+        callback: Box<dyn Send + FnMut(StreamCallback)>,
     ) -> Result<Box<dyn Stream>, Error> {
-        unimplemented!()
+        let direction = match self.data_flow()? {
+            flow if flow == eCapture => StreamDirection::Capture,
+            flow if self.supports_loopback(flow, config.share_mode) => StreamDirection::Loopback,
+            _ => return Err(Error::UnsupportedConfiguration),
+        };
+
+        let stream = self.open_stream(direction, config, callback)?;
+        Ok(Box::new(stream))
     }
 }
@@ -1,17 +1,19 @@
 use {
     crate::{
-        BackendError, Device, Host, RoleHint,
+        BackendError, Device, DeviceEvent, DeviceWatcher, Host, HostId, RoleHint,
         backends::wasapi::{
             WasapiHostConfig,
             device::WasapiDevice,
-            utility::{backend_error, role_hint_to_wasapi},
+            notification_client::NotificationClient,
+            utility::{backend_error, guard, role_hint_to_wasapi},
         },
     },
     std::rc::Rc,
     windows::Win32::{
+        Foundation::E_NOTFOUND,
         Media::Audio::{
-            DEVICE_STATE_ACTIVE, EDataFlow, ERole, IMMDeviceEnumerator, MMDeviceEnumerator, eAll,
-            eCapture, eRender,
+            DEVICE_STATE_ACTIVE, EDataFlow, ERole, IMMDeviceEnumerator, IMMNotificationClient,
+            MMDeviceEnumerator, eAll, eCapture, eRender,
         },
         System::Com::{CLSCTX_ALL, CoCreateInstance},
     },
@@ -43,30 +45,37 @@ impl WasapiHost {
     }
 
     /// Returns the default endpoint for the provided flow and role values.
+    ///
+    /// # Returns
+    ///
+    /// `None` if no device is available for the provided flow.
     pub fn get_default_endpoint(
         &self,
         flow: EDataFlow,
         role: ERole,
     ) -> Result<Option<Box<dyn Device>>, BackendError> {
         unsafe {
-            let device = self
-                .device_enumerator
-                .GetDefaultAudioEndpoint(flow, role)
-                .map_err(|err| backend_error("Failed to get default device", err))?;
+            let device = match self.device_enumerator.GetDefaultAudioEndpoint(flow, role) {
+                Ok(device) => device,
+                Err(err) if err.code() == E_NOTFOUND => return Ok(None),
+                Err(err) => return Err(backend_error("Failed to get default device", err)),
+            };
             Ok(Some(Box::new(WasapiDevice::from_wasapi_device(
                 self.config.clone(),
                 device,
             ))))
         }
     }
-}
 
-impl Host for WasapiHost {
-    fn devices(&self) -> Result<Vec<Box<dyn Device>>, BackendError> {
+    /// Returns the active endpoints for the provided flow.
+    pub fn enumerate_endpoints(
+        &self,
+        flow: EDataFlow,
+    ) -> Result<Vec<Box<dyn Device>>, BackendError> {
         unsafe {
             let collection = self
                 .device_enumerator
-                .EnumAudioEndpoints(eAll, DEVICE_STATE_ACTIVE)
+                .EnumAudioEndpoints(flow, DEVICE_STATE_ACTIVE)
                 .map_err(|err| backend_error("Failed to enumerate audio devices", err))?;
 
             let count = collection
@@ -87,6 +96,32 @@ impl Host for WasapiHost {
             Ok(devices)
         }
     }
+}
+
+impl Host for WasapiHost {
+    #[inline]
+    fn id(&self) -> HostId {
+        HostId::Wasapi
+    }
+
+    #[inline]
+    fn devices(&self) -> Result<Vec<Box<dyn Device>>, BackendError> {
+        self.enumerate_endpoints(eAll)
+    }
+
+    #[inline]
+    fn output_devices(&self) -> Result<Vec<Box<dyn Device>>, BackendError> {
+        self.enumerate_endpoints(eRender)
+    }
+
+    #[inline]
+    fn input_devices(&self) -> Result<Vec<Box<dyn Device>>, BackendError> {
+        if self.config.loopback_capture {
+            self.enumerate_endpoints(eAll)
+        } else {
+            self.enumerate_endpoints(eCapture)
+        }
+    }
 
     fn default_input_device(
         &self,
@@ -101,4 +136,26 @@ impl Host for WasapiHost {
     ) -> Result<Option<Box<dyn Device>>, BackendError> {
         self.get_default_endpoint(eRender, role_hint_to_wasapi(role))
     }
+
+    fn watch_devices(
+        &self,
+        callback: Box<dyn Send + FnMut(DeviceEvent)>,
+    ) -> Result<DeviceWatcher, BackendError> {
+        DeviceWatcher::new(callback, |sender| {
+            let client: IMMNotificationClient = NotificationClient::new(sender).into();
+            let device_enumerator = self.device_enumerator.clone();
+
+            unsafe {
+                device_enumerator
+                    .RegisterEndpointNotificationCallback(&client)
+                    .map_err(|err| backend_error("Failed to register the device watcher", err))?;
+            }
+
+            // Unregistering releases the enumerator's reference to the client, which in turn
+            // drops the sender.
+            Ok(guard(move || unsafe {
+                let _ = device_enumerator.UnregisterEndpointNotificationCallback(&client);
+            }))
+        })
+    }
 }
@@ -13,6 +13,34 @@ pub struct WasapiHostConfig {
     pub tried_formats: Cow<'static, [Format]>,
     /// The list of sample rates to try when trying to determine the formats available on a device.
     pub tried_frame_rates: Cow<'static, [u32]>,
+    /// Whether output devices can be used to capture the audio they are playing.
+    ///
+    /// When enabled, output devices are also listed as input devices, and opening an input
+    /// stream on them records whatever the system is currently playing on that device (often
+    /// called "loopback" or "what you hear" recording).
+    ///
+    /// # Remarks
+    ///
+    /// Loopback capture is only available in shared mode. Output devices report no input
+    /// formats in exclusive mode, and opening an exclusive input stream on them fails with
+    /// [`Error::UnsupportedConfiguration`](crate::Error::UnsupportedConfiguration).
+    ///
+    /// The audio engine does not deliver anything to loopback streams while nothing is being
+    /// played on the device. The stream makes up for it by passing silent buffers to the
+    /// callback, so captured audio stays in sync with the wall clock.
+    ///
+    /// This requires Windows 10 version 1703 or later.
+    pub loopback_capture: bool,
+    /// Whether streams should poll the device instead of waiting for it to signal that it needs
+    /// attention.
+    ///
+    /// Event-driven streams have a lower latency and use less CPU, but some (usually virtual)
+    /// drivers never signal anything, which makes the stream stall. Polled streams wake up
+    /// twice per buffer and process as many frames as the device is able to accept.
+    ///
+    /// Streams automatically fall back to polling when the driver reports that it does not
+    /// support event-driven streams, regardless of this setting.
+    pub polling: bool,
 }
 
 impl Default for WasapiHostConfig {
@@ -28,6 +56,8 @@ impl Default for WasapiHostConfig {
             tried_channel_counts: Cow::Borrowed(&TRIED_CHANNEL_COUNTS),
             tried_formats: Cow::Borrowed(&TRIED_FORMATS),
             tried_frame_rates: Cow::Borrowed(&TRIED_FRAME_RATES),
+            loopback_capture: false,
+            polling: false,
         }
     }
 }
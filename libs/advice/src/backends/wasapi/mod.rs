@@ -6,6 +6,7 @@ use {
 mod com;
 mod device;
 mod host;
+mod notification_client;
 mod stream;
 mod utility;
 
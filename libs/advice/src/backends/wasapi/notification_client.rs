@@ -0,0 +1,83 @@
+use {
+    crate::DeviceEvent,
+    std::sync::mpsc,
+    windows::Win32::{
+        Foundation::PROPERTYKEY,
+        Media::Audio::{
+            DEVICE_STATE, DEVICE_STATE_ACTIVE, EDataFlow, ERole, IMMNotificationClient,
+            IMMNotificationClient_Impl,
+        },
+    },
+    windows_core::{PCWSTR, implement},
+};
+
+/// An [`IMMNotificationClient`] implementation that forwards device changes to a channel.
+///
+/// # Remarks
+///
+/// The notification methods are called by the system on one of its own threads. They must not
+/// block, which is why the events are only forwarded to the dispatch thread of the watcher.
+#[implement(IMMNotificationClient)]
+pub struct NotificationClient {
+    /// The sender used to emit events.
+    sender: mpsc::Sender<DeviceEvent>,
+}
+
+impl NotificationClient {
+    /// Creates a new [`NotificationClient`] emitting events to the provided sender.
+    pub fn new(sender: mpsc::Sender<DeviceEvent>) -> Self {
+        Self { sender }
+    }
+
+    /// Emits the provided event.
+    ///
+    /// If the watcher is being dropped, the event is simply ignored.
+    fn emit(&self, event: DeviceEvent) {
+        let _ = self.sender.send(event);
+    }
+}
+
+impl IMMNotificationClient_Impl for NotificationClient_Impl {
+    fn OnDeviceStateChanged(
+        &self,
+        _device_id: &PCWSTR,
+        new_state: DEVICE_STATE,
+    ) -> windows_core::Result<()> {
+        // Unplugging a device usually only changes its state, the device itself remains known
+        // to the system.
+        if new_state == DEVICE_STATE_ACTIVE {
+            self.emit(DeviceEvent::Added);
+        } else {
+            self.emit(DeviceEvent::Removed);
+        }
+        Ok(())
+    }
+
+    fn OnDeviceAdded(&self, _device_id: &PCWSTR) -> windows_core::Result<()> {
+        self.emit(DeviceEvent::Added);
+        Ok(())
+    }
+
+    fn OnDeviceRemoved(&self, _device_id: &PCWSTR) -> windows_core::Result<()> {
+        self.emit(DeviceEvent::Removed);
+        Ok(())
+    }
+
+    fn OnDefaultDeviceChanged(
+        &self,
+        _flow: EDataFlow,
+        _role: ERole,
+        _default_device_id: &PCWSTR,
+    ) -> windows_core::Result<()> {
+        self.emit(DeviceEvent::DefaultChanged);
+        Ok(())
+    }
+
+    fn OnPropertyValueChanged(
+        &self,
+        _device_id: &PCWSTR,
+        _key: &PROPERTYKEY,
+    ) -> windows_core::Result<()> {
+        Ok(())
+    }
+}
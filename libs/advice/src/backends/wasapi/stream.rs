@@ -1,20 +1,28 @@
 use {
     crate::{
-        BackendError, ChannelLayout, Error, Stream, StreamCallback, StreamConfig, StreamData,
+        BackendError, ChannelLayout, ChannelPositions, Error, ShareMode, Stream, StreamCallback,
+        StreamConfig, StreamData,
         backends::wasapi::utility::{
             backend_error, device_error, frames_to_duration, guard, make_waveformatex,
-            share_mode_to_wasapi,
+            make_waveformatextensible, share_mode_to_wasapi,
         },
     },
-    std::sync::{
-        Arc,
-        atomic::{AtomicU8, Ordering},
+    std::{
+        num::NonZero,
+        sync::{
+            Arc, Mutex, PoisonError,
+            atomic::{AtomicU8, AtomicU64, Ordering},
+        },
+        thread::JoinHandle,
+        time::{Duration, Instant},
     },
     windows::Win32::{
         Foundation::{GetLastError, HANDLE, WAIT_FAILED},
         Media::Audio::{
-            AUDCLNT_STREAMFLAGS_EVENTCALLBACK, IAudioCaptureClient, IAudioClient,
-            IAudioRenderClient, WAVEFORMATEXTENSIBLE,
+            AUDCLNT_BUFFERFLAGS_DATA_DISCONTINUITY, AUDCLNT_BUFFERFLAGS_SILENT,
+            AUDCLNT_E_EVENTHANDLE_NOT_EXPECTED, AUDCLNT_E_EVENTHANDLE_NOT_SET, AUDCLNT_SHAREMODE,
+            AUDCLNT_STREAMFLAGS_EVENTCALLBACK, AUDCLNT_STREAMFLAGS_LOOPBACK, IAudioCaptureClient,
+            IAudioClient, IAudioRenderClient, WAVEFORMATEXTENSIBLE,
         },
         System::Threading::{
             CreateEventA, GetCurrentThread, INFINITE, SetEvent, SetThreadPriority,
@@ -28,11 +36,31 @@ const COMMAND_PLAYING: u8 = 1 << 0;
 /// Whether the stream should be closing or not.
 const COMMAND_CLOSING: u8 = 1 << 1;
 
+/// The direction of a [`WasapiStream`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamDirection {
+    /// The stream renders audio data to the device.
+    Render,
+    /// The stream captures audio data from the device.
+    Capture,
+    /// The stream captures the audio data being rendered to an output device.
+    ///
+    /// This is only supported in shared mode.
+    Loopback,
+}
+
 /// The state that is shared between the [`WasapiStream`] and the high-priority thread.
 struct SharedState {
     /// A set of flags that represent the commands requested by the [`WasapiStream`] to the
     /// high-priority thread.
     command: AtomicU8,
+    /// The number of underruns detected by the high-priority thread.
+    ///
+    /// For input streams, this counts the overruns (captured data that was lost because the
+    /// callback did not read it fast enough).
+    underrun_count: AtomicU64,
+    /// The error that caused the high-priority thread to stop, if any.
+    error: Mutex<Option<Error>>,
 }
 
 /// Represents a running stream on the WASAPI host.
@@ -42,19 +70,48 @@ pub struct WasapiStream {
     /// The handle of an event that must be signaled when the `command` field of the shared state
     /// is updated.
     command_changed_event: HANDLE,
+    /// The high-priority thread driving the stream.
+    thread: Option<JoinHandle<()>>,
+
+    /// The audio client that was used to create the stream.
+    ///
+    /// It's used to query the state of the stream from outside of the high-priority thread.
+    audio_client: IAudioClient,
+    /// The frame rate of the stream.
+    frame_rate: u32,
+    /// The configuration actually in use by the stream.
+    config: StreamConfig,
 }
 
 impl WasapiStream {
-    /// Creates a new [`WasapiStream`] for rendering audio.
+    /// Creates a new [`WasapiStream`] for rendering or capturing audio.
+    ///
+    /// # Parameters
+    ///
+    /// - `audio_client`: The audio client to initialize for the stream.
+    ///
+    /// - `activate`: A function that activates a new audio client for the same device. It's
+    ///   used when `audio_client` can't be used in event-driven mode and the stream has to fall
+    ///   back to polling.
+    ///
+    /// - `polling`: Whether to poll the audio client instead of relying on the events it
+    ///   signals. See [`WasapiHostConfig::polling`](crate::WasapiHostConfig::polling).
     pub fn new(
         audio_client: IAudioClient,
+        activate: &dyn Fn() -> Result<IAudioClient, Error>,
+        direction: StreamDirection,
         config: StreamConfig,
+        polling: bool,
         callback: Box<dyn Send + FnMut(StreamCallback)>,
     ) -> Result<Self, Error> {
         if config.channel_layout != ChannelLayout::Interleaved {
             return Err(Error::UnsupportedConfiguration);
         }
 
+        if direction == StreamDirection::Loopback && config.share_mode != ShareMode::Share {
+            return Err(Error::UnsupportedConfiguration);
+        }
+
         //
         // Initialize the audio client with the format supplied by the user.
         //
@@ -65,45 +122,69 @@ impl WasapiStream {
             .buffer_size
             .map_or(0, |sz| frames_to_duration(sz.get(), frame_rate));
 
+        // The plain `WAVEFORMATEX` structure has no way to describe speaker positions. It's
+        // only used for mono and stereo streams when no specific positions were requested.
         let mut waveformat = WAVEFORMATEXTENSIBLE::default();
-        if !make_waveformatex(
-            config.channel_count,
-            config.format,
-            config.frame_rate as u32,
-            &mut waveformat.Format,
-        ) {
-            return Err(Error::UnsupportedConfiguration);
-        }
-
-        unsafe {
-            audio_client
-                .Initialize(
-                    share_mode_to_wasapi(config.share_mode),
-                    AUDCLNT_STREAMFLAGS_EVENTCALLBACK,
-                    buffer_duration as i64,
-                    0,
-                    &waveformat.Format,
-                    None,
-                )
-                .map_err(|err| device_error("IAudioClient::Initialize", err))?;
-        }
+        let mut channel_positions = None;
+        let ok = if config.channel_positions.is_some() || config.channel_count > 2 {
+            let channel_mask = config.channel_positions.unwrap_or_else(|| {
+                ChannelPositions::default_for_channel_count(config.channel_count)
+            });
 
-        //
-        // Create an event that will be signaled when the audio client is ready to receive more
-        // data.
-        //
+            // WASAPI either rejects masks that don't match the channel count with an opaque
+            // error, or silently maps the channels to the wrong speakers.
+            if !channel_mask.is_empty()
+                && channel_mask.bits().count_ones() != config.channel_count as u32
+            {
+                return Err(Error::UnsupportedConfiguration);
+            }
 
-        let buffer_available_event = unsafe {
-            CreateEventA(None, false, false, None)
-                .map_err(|err| device_error("CreateEvent", err))?
+            channel_positions = Some(channel_mask);
+
+            make_waveformatextensible(
+                config.channel_count,
+                config.format,
+                frame_rate,
+                channel_mask,
+                &mut waveformat,
+            )
+        } else {
+            make_waveformatex(
+                config.channel_count,
+                config.format,
+                frame_rate,
+                &mut waveformat.Format,
+            )
         };
 
-        unsafe {
-            audio_client
-                .SetEventHandle(buffer_available_event)
-                .map_err(|err| device_error("IAudioClient::SetEventHandle", err))?;
+        if !ok {
+            return Err(Error::UnsupportedConfiguration);
         }
 
+        let init = AudioClientInit {
+            share_mode: share_mode_to_wasapi(config.share_mode),
+            loopback: direction == StreamDirection::Loopback,
+            buffer_duration,
+            waveformat: &waveformat,
+        };
+
+        // Some (usually virtual) drivers do not support event-driven streams. An audio client
+        // can only be initialized once, so falling back to polling requires a new one.
+        let (audio_client, buffer_available_event) = if polling {
+            init.initialize(&audio_client, false)?;
+            (audio_client, None)
+        } else {
+            match init.initialize(&audio_client, true) {
+                Ok(event) => (audio_client, event),
+                Err(err) if is_event_mode_unsupported(&err) => {
+                    let audio_client = activate()?;
+                    init.initialize(&audio_client, false)?;
+                    (audio_client, None)
+                }
+                Err(err) => return Err(err),
+            }
+        };
+
         //
         // Create the event that will be used to signal the high-priority thread that the
         // commands have been updated.
@@ -115,13 +196,24 @@ impl WasapiStream {
         };
 
         //
-        // Create the render client.
+        // Create the render or capture client.
         //
 
-        let render_client = unsafe {
-            audio_client
-                .GetService::<IAudioRenderClient>()
-                .map_err(|err| device_error("IAudioClient::GetSerice<IAudioRenderClient>", err))?
+        let stream_client = unsafe {
+            match direction {
+                StreamDirection::Render => audio_client
+                    .GetService::<IAudioRenderClient>()
+                    .map(StreamClient::Render)
+                    .map_err(|err| {
+                        device_error("IAudioClient::GetService<IAudioRenderClient>", err)
+                    })?,
+                StreamDirection::Capture | StreamDirection::Loopback => audio_client
+                    .GetService::<IAudioCaptureClient>()
+                    .map(StreamClient::Capture)
+                    .map_err(|err| {
+                        device_error("IAudioClient::GetService<IAudioCaptureClient>", err)
+                    })?,
+            }
         };
 
         //
@@ -136,34 +228,87 @@ impl WasapiStream {
 
         let shared_state = Arc::new(SharedState {
             command: AtomicU8::new(0),
+            underrun_count: AtomicU64::new(0),
+            error: Mutex::new(None),
         });
 
+        let frame_size = config.channel_count as usize * config.format.size_in_bytes() as usize;
+
+        let buffer_duration = Duration::from_secs_f64(buffer_size as f64 / frame_rate as f64);
+        let buffer_millis = (buffer_duration.as_millis() as u32).max(1);
+
+        // Without the buffer-available event, the audio client must be polled. Waking up twice
+        // per buffer leaves enough time to process the buffer before the device runs out of it.
+        // Loopback streams must also wake up regularly (see `capture_silence`).
+        let wait_timeout = match (buffer_available_event, direction) {
+            (None, _) => (buffer_millis / 2).max(1),
+            (Some(_), StreamDirection::Loopback) => buffer_millis,
+            (Some(_), _) => INFINITE,
+        };
+
+        let silence = match direction {
+            StreamDirection::Loopback => vec![0; buffer_size as usize * frame_size],
+            _ => Vec::new(),
+        };
+
+        let mut events = vec![command_changed_event];
+        events.extend(buffer_available_event);
+
         let mut thread_state = HighPriorityThread {
-            audio_client,
-            stream_client: StreamClient::Render(render_client),
+            audio_client: audio_client.clone(),
+            stream_client,
             shared_state: shared_state.clone(),
             playing: false,
-            events: [command_changed_event, buffer_available_event],
+            primed: false,
+            events,
+            wait_timeout,
             buffer_size,
+            frame_size,
+            buffer_duration,
+            last_packet: Instant::now(),
+            silence,
             callback,
         };
 
-        std::thread::Builder::new()
-            .name("advice-waspi-audio-rendering-thread".into())
+        let thread_name = match direction {
+            StreamDirection::Render => "advice-wasapi-audio-rendering-thread",
+            StreamDirection::Capture | StreamDirection::Loopback => {
+                "advice-wasapi-audio-capturing-thread"
+            }
+        };
+
+        let thread = std::thread::Builder::new()
+            .name(thread_name.into())
             .spawn(move || thread_state.run())
             .map_err(|err| {
                 BackendError::new(format!("Failed to spawn high-priority thread: {err}"))
             })?;
 
+        // The audio client accepts the provided format as-is or fails to initialize, only the
+        // buffer size may have been changed.
+        let config = StreamConfig {
+            frame_rate: frame_rate as f64,
+            buffer_size: NonZero::new(buffer_size),
+            channel_positions,
+            ..config
+        };
+
         Ok(Self {
             shared_state,
             command_changed_event,
+            thread: Some(thread),
+            audio_client,
+            frame_rate,
+            config,
         })
     }
 }
 
 impl Stream for WasapiStream {
     fn start(&self) -> Result<(), Error> {
+        // The high-priority thread is gone, starting the stream would silently do nothing.
+        self.check_error()?;
+
         self.shared_state
             .command
             .fetch_or(COMMAND_PLAYING, Ordering::SeqCst);
@@ -174,6 +319,8 @@ impl Stream for WasapiStream {
     }
 
     fn stop(&self) -> Result<(), Error> {
+        self.check_error()?;
+
         self.shared_state
             .command
             .fetch_and(!COMMAND_PLAYING, Ordering::SeqCst);
@@ -184,7 +331,42 @@ impl Stream for WasapiStream {
     }
 
     fn check_error(&self) -> Result<(), Error> {
-        unimplemented!()
+        let error = self
+            .shared_state
+            .error
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        error.clone().map_or(Ok(()), Err)
+    }
+
+    #[inline]
+    fn config(&self) -> &StreamConfig {
+        &self.config
+    }
+
+    fn latency(&self) -> Result<Duration, Error> {
+        // The stream latency is the latency of the device itself (and of the audio engine in
+        // shared mode). The frames that are currently waiting in the buffer must be added to it.
+        let (stream_latency, padding) = unsafe {
+            let stream_latency = self
+                .audio_client
+                .GetStreamLatency()
+                .map_err(|err| device_error("IAudioClient::GetStreamLatency", err))?;
+            let padding = self
+                .audio_client
+                .GetCurrentPadding()
+                .map_err(|err| device_error("IAudioClient::GetCurrentPadding", err))?;
+            (stream_latency, padding)
+        };
+
+        // `GetStreamLatency` returns a value in units of 100 nanoseconds.
+        Ok(Duration::from_nanos(stream_latency as u64 * 100)
+            + Duration::from_secs_f64(padding as f64 / self.frame_rate as f64))
+    }
+
+    #[inline]
+    fn underrun_count(&self) -> u64 {
+        self.shared_state.underrun_count.load(Ordering::Relaxed)
     }
 }
 
@@ -193,9 +375,100 @@ impl Drop for WasapiStream {
         self.shared_state
             .command
             .fetch_or(COMMAND_CLOSING, Ordering::SeqCst);
+
+        // The high-priority thread might be waiting for the commands to change (when the stream
+        // is stopped, the device never signals it). If signaling fails, there is nothing much we
+        // can do about it.
+        unsafe {
+            let _ = SetEvent(self.command_changed_event);
+        }
+
+        // Waiting for the thread ensures that the callback is no longer running (and that the
+        // audio client is stopped) by the time the stream is dropped.
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
     }
 }
 
+/// The parameters used to initialize the audio client of a [`WasapiStream`].
+struct AudioClientInit<'a> {
+    /// The share mode of the stream.
+    share_mode: AUDCLNT_SHAREMODE,
+    /// Whether the stream captures the audio data rendered to the device.
+    loopback: bool,
+    /// The requested duration of the buffer, in 100-nanosecond units.
+    buffer_duration: u64,
+    /// The format of the stream.
+    waveformat: &'a WAVEFORMATEXTENSIBLE,
+}
+
+impl AudioClientInit<'_> {
+    /// Initializes the provided audio client.
+    ///
+    /// # Returns
+    ///
+    /// When `event_driven` is set, the event that the audio client signals when it is ready to
+    /// receive more data (or has captured more data).
+    fn initialize(
+        &self,
+        audio_client: &IAudioClient,
+        event_driven: bool,
+    ) -> Result<Option<HANDLE>, Error> {
+        let mut stream_flags = 0;
+        if event_driven {
+            stream_flags |= AUDCLNT_STREAMFLAGS_EVENTCALLBACK;
+        }
+        if self.loopback {
+            stream_flags |= AUDCLNT_STREAMFLAGS_LOOPBACK;
+        }
+
+        unsafe {
+            audio_client
+                .Initialize(
+                    self.share_mode,
+                    stream_flags,
+                    self.buffer_duration as i64,
+                    0,
+                    &self.waveformat.Format,
+                    None,
+                )
+                .map_err(|err| device_error("IAudioClient::Initialize", err))?;
+        }
+
+        if !event_driven {
+            return Ok(None);
+        }
+
+        let buffer_available_event = unsafe {
+            CreateEventA(None, false, false, None)
+                .map_err(|err| device_error("CreateEvent", err))?
+        };
+
+        unsafe {
+            audio_client
+                .SetEventHandle(buffer_available_event)
+                .map_err(|err| device_error("IAudioClient::SetEventHandle", err))?;
+        }
+
+        Ok(Some(buffer_available_event))
+    }
+}
+
+/// Returns whether the provided error indicates that an audio client can't be used in
+/// event-driven mode.
+///
+/// Generic errors (such as `E_INVALIDARG`) are not taken into account, because they are also
+/// returned for formats that the device does not support. Falling back to polling would only
+/// hide the actual error.
+fn is_event_mode_unsupported(err: &Error) -> bool {
+    err.as_backend_error()
+        .and_then(BackendError::code)
+        .is_some_and(|code| {
+            code == AUDCLNT_E_EVENTHANDLE_NOT_EXPECTED.0 || code == AUDCLNT_E_EVENTHANDLE_NOT_SET.0
+        })
+}
+
 /// Requests the current thread to become a high-priority time-critical thread.
 fn become_high_priority_thread() {
     unsafe {
@@ -209,10 +482,6 @@ enum StreamClient {
     /// For output streams, the render client.
     Render(IAudioRenderClient),
     /// For input streams, the capture client.
-    #[allow(
-        dead_code,
-        reason = "TODO: remove this when implementing input streams"
-    )]
     Capture(IAudioCaptureClient),
 }
 
@@ -231,14 +500,41 @@ struct HighPriorityThread {
     ///
     /// - `0`: The event that signals that the commands have been updated.
     ///
-    /// - `1`: The event that signals that the audio client is ready to receive more data.
-    events: [HANDLE; 2],
+    /// - `1`: The event that signals that the audio client is ready to receive more data. This
+    ///   one is missing when the audio client is polled.
+    events: Vec<HANDLE>,
+    /// The number of milliseconds to wait for the events before waking up anyway while the
+    /// stream is playing.
+    ///
+    /// This is `INFINITE` for event-driven streams that are not loopback streams.
+    wait_timeout: u32,
 
     /// Whether the audio client is currently running or not.
     playing: bool,
+    /// Whether the buffer has been filled at least once since the audio client was started.
+    ///
+    /// The buffer is expected to be empty right after the audio client starts, this must not be
+    /// counted as an underrun.
+    ///
+    /// For input streams, this is whether a packet has been captured since the audio client was
+    /// started. The first packet is usually flagged as a discontinuity, which is not an overrun.
+    primed: bool,
 
     /// The size of the buffer, in frames.
     buffer_size: u32,
+    /// The size of a single frame, in bytes.
+    frame_size: usize,
+    /// The time it takes for the device to play (or capture) `buffer_size` frames.
+    buffer_duration: Duration,
+
+    /// The last time a packet (or a silent buffer) was passed to the callback of an input
+    /// stream.
+    last_packet: Instant,
+    /// A buffer of `buffer_size` frames passed to the callback in place of the packets that a
+    /// loopback stream does not receive while nothing is playing.
+    ///
+    /// This is empty for streams that are not loopback streams.
+    silence: Vec<u8>,
 
     /// The user-defined callback responsible for actually rendering or capturing the audio data.
     callback: Box<dyn Send + FnMut(StreamCallback)>,
@@ -256,16 +552,30 @@ impl HighPriorityThread {
 
         let result = match self.stream_client {
             StreamClient::Render(_) => unsafe { self.run_output_fallible() },
-            StreamClient::Capture(_) => unimplemented!(),
+            StreamClient::Capture(_) => unsafe { self.run_input_fallible() },
         };
 
+        if self.playing {
+            // Either the stream is closing or an error is already being reported.
+            let _ = unsafe { self.audio_client.Stop() };
+        }
+
         if let Err(err) = result {
-            // TODO: Send the error to the main thread.
-            panic!("Error in high-priority thread: {err}");
+            *self
+                .shared_state
+                .error
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner) = Some(err);
+
+            // The stream is no longer playing, make sure the commands reflect that.
+            self.shared_state
+                .command
+                .fetch_and(!COMMAND_PLAYING, Ordering::SeqCst);
         }
     }
 
-    /// Rusn the high-priority thread to completion, returns an error if something goes wrong.
+    /// Runs the high-priority thread to completion for an output stream, returns an error if
+    /// something goes wrong.
     ///
     /// # Safety
     ///
@@ -278,6 +588,24 @@ impl HighPriorityThread {
         Ok(())
     }
 
+    /// Runs the high-priority thread to completion for an input stream, returns an error if
+    /// something goes wrong.
+    ///
+    /// # Safety
+    ///
+    /// Must be called with `stream_client` set to `StreamClient::Capture`.
+    unsafe fn run_input_fallible(&mut self) -> Result<(), Error> {
+        while self.process_commands()? {
+            self.wait_for_stuff_to_happen()?;
+            unsafe { self.capture()? };
+
+            if self.playing && self.last_packet.elapsed() >= self.buffer_duration {
+                self.capture_silence();
+            }
+        }
+        Ok(())
+    }
+
     /// Process the commands that have been requested by the [`WasapiStream`].
     ///
     /// # Returns
@@ -296,6 +624,8 @@ impl HighPriorityThread {
             self.playing = should_play;
 
             if self.playing {
+                self.primed = false;
+                self.last_packet = Instant::now();
                 unsafe {
                     self.audio_client
                         .Start()
@@ -314,8 +644,16 @@ impl HighPriorityThread {
     }
 
     /// Whether the audio client should wait for something to happen (new commands, buffer, etc).
+    ///
+    /// While the stream is playing, this also returns after `wait_timeout` milliseconds.
     fn wait_for_stuff_to_happen(&self) -> Result<(), Error> {
-        let result = unsafe { WaitForMultipleObjectsEx(&self.events, false, INFINITE, false) };
+        let timeout = if self.playing {
+            self.wait_timeout
+        } else {
+            INFINITE
+        };
+
+        let result = unsafe { WaitForMultipleObjectsEx(&self.events, false, timeout, false) };
 
         if result == WAIT_FAILED {
             let err = unsafe { GetLastError() };
@@ -347,6 +685,18 @@ impl HighPriorityThread {
                 return Ok(());
             }
 
+            if self.playing {
+                // If the whole buffer is available, the device has played everything we gave it
+                // and had to play silence while waiting for us.
+                if self.primed && padding == 0 {
+                    self.shared_state
+                        .underrun_count
+                        .fetch_add(1, Ordering::Relaxed);
+                }
+
+                self.primed = true;
+            }
+
             let buf = render_client
                 .GetBuffer(available_frames)
                 .map_err(|err| device_error("IAudioRenderClient::GetBuffer", err))?;
@@ -360,4 +710,87 @@ impl HighPriorityThread {
             Ok(())
         }
     }
+
+    /// Executes the input callback for every packet that the device has captured.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `stream_client` is set to `StreamClient::Capture`.
+    unsafe fn capture(&mut self) -> Result<(), Error> {
+        unsafe {
+            let capture_client = match self.stream_client {
+                StreamClient::Capture(ref capture) => capture,
+                _ => std::hint::unreachable_unchecked(),
+            };
+
+            // Unlike render streams, the padding of a capture stream is the amount of data
+            // waiting to be read. That data is split into packets that must be read one at a
+            // time.
+            loop {
+                let packet_size = capture_client
+                    .GetNextPacketSize()
+                    .map_err(|err| device_error("IAudioCaptureClient::GetNextPacketSize", err))?;
+                if packet_size == 0 {
+                    return Ok(());
+                }
+
+                let mut data: *mut u8 = std::ptr::null_mut();
+                let mut frame_count: u32 = 0;
+                let mut flags: u32 = 0;
+                capture_client
+                    .GetBuffer(&mut data, &mut frame_count, &mut flags, None, None)
+                    .map_err(|err| device_error("IAudioCaptureClient::GetBuffer", err))?;
+                let _guard = guard(|| drop(capture_client.ReleaseBuffer(frame_count)));
+
+                if frame_count == 0 {
+                    continue;
+                }
+
+                if flags & AUDCLNT_BUFFERFLAGS_DATA_DISCONTINUITY.0 as u32 != 0 && self.primed {
+                    self.shared_state
+                        .underrun_count
+                        .fetch_add(1, Ordering::Relaxed);
+                }
+                self.primed = true;
+
+                // The content of the buffer must be treated as silence, regardless of what it
+                // actually contains.
+                if flags & AUDCLNT_BUFFERFLAGS_SILENT.0 as u32 != 0 {
+                    std::ptr::write_bytes(data, 0, frame_count as usize * self.frame_size);
+                }
+
+                (self.callback)(StreamCallback {
+                    data: StreamData { interleaved: data },
+                    frame_count: frame_count as usize,
+                });
+                self.last_packet = Instant::now();
+            }
+        }
+    }
+
+    /// Executes the input callback with a buffer of silence.
+    ///
+    /// This is used by loopback streams, which receive no packets at all while nothing is being
+    /// played on the device. It's called when no packet was received for the duration of a
+    /// buffer.
+    fn capture_silence(&mut self) {
+        if self.silence.is_empty() {
+            return;
+        }
+
+        // The callback may have written to the buffer the last time it was called.
+        self.silence.fill(0);
+
+        (self.callback)(StreamCallback {
+            data: StreamData {
+                interleaved: self.silence.as_mut_ptr(),
+            },
+            frame_count: self.buffer_size as usize,
+        });
+
+        // The first packet received when the device starts playing again is flagged as a
+        // discontinuity, which is not an overrun.
+        self.primed = false;
+        self.last_packet = Instant::now();
+    }
 }
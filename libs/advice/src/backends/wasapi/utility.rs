@@ -1,13 +1,13 @@
 use {
-    crate::{BackendError, Error, Format, RoleHint, ShareMode},
+    crate::{BackendError, ChannelPositions, Error, Format, RoleHint, ShareMode},
     std::mem::ManuallyDrop,
     windows::Win32::Media::{
         Audio::{
             AUDCLNT_E_BUFFER_SIZE_ERROR, AUDCLNT_E_DEVICE_IN_USE, AUDCLNT_E_DEVICE_INVALIDATED,
             AUDCLNT_E_EXCLUSIVE_MODE_NOT_ALLOWED, AUDCLNT_E_EXCLUSIVE_MODE_ONLY,
-            AUDCLNT_E_UNSUPPORTED_FORMAT, AUDCLNT_SHAREMODE, AUDCLNT_SHAREMODE_EXCLUSIVE,
-            AUDCLNT_SHAREMODE_SHARED, ERole, WAVE_FORMAT_PCM, WAVEFORMATEX, WAVEFORMATEXTENSIBLE,
-            eCommunications, eConsole, eMultimedia,
+            AUDCLNT_E_RESOURCES_INVALIDATED, AUDCLNT_E_UNSUPPORTED_FORMAT, AUDCLNT_SHAREMODE,
+            AUDCLNT_SHAREMODE_EXCLUSIVE, AUDCLNT_SHAREMODE_SHARED, ERole, WAVE_FORMAT_PCM,
+            WAVEFORMATEX, WAVEFORMATEXTENSIBLE, eCommunications, eConsole, eMultimedia,
         },
         KernelStreaming::{KSDATAFORMAT_SUBTYPE_PCM, WAVE_FORMAT_EXTENSIBLE},
         Multimedia::{KSDATAFORMAT_SUBTYPE_IEEE_FLOAT, WAVE_FORMAT_IEEE_FLOAT},
@@ -18,19 +18,22 @@ use {
 pub fn backend_error(context: &str, err: windows::core::Error) -> BackendError {
     let err_message = err.message();
     if err_message.is_empty() {
-        BackendError::new(format!("WASAPI: {}: {}", context, err))
+        BackendError::with_code(format!("WASAPI: {}: {}", context, err), err.code().0)
     } else {
-        BackendError::new(format!("WASAPI: {}: {} ({})", context, err_message, err))
+        BackendError::with_code(
+            format!("WASAPI: {}: {} ({})", context, err_message, err),
+            err.code().0,
+        )
     }
 }
 
 /// Turns the provided `HRESULT` into a [`Error`].
 ///
-/// This function will automatically catch errors indicating that the device is not longer
-/// available and return an [`Error::DeviceNotAvailable`] instead.
+/// This function will automatically catch errors indicating that the device was disconnected
+/// and return an [`Error::DeviceDisconnected`] instead.
 pub fn device_error(context: &str, err: windows::core::Error) -> Error {
     match err.code() {
-        AUDCLNT_E_DEVICE_INVALIDATED => Error::DeviceNotAvailable,
+        AUDCLNT_E_DEVICE_INVALIDATED | AUDCLNT_E_RESOURCES_INVALIDATED => Error::DeviceDisconnected,
         AUDCLNT_E_DEVICE_IN_USE => Error::DeviceInUse,
         AUDCLNT_E_EXCLUSIVE_MODE_NOT_ALLOWED
         | AUDCLNT_E_EXCLUSIVE_MODE_ONLY
@@ -105,10 +108,14 @@ pub fn make_waveformatex(
 
 /// Like [`make_waveformatex`], but fills the "extensible" part instead of the
 /// `WAVEFORMATEX` part.
+///
+/// `channel_mask` is the set of speakers that the channels are mapped to. An empty mask lets the
+/// device choose how channels are mapped.
 pub fn make_waveformatextensible(
     channel_count: u16,
     format: Format,
     frame_rate: u32,
+    channel_mask: ChannelPositions,
     waveformat: &mut WAVEFORMATEXTENSIBLE,
 ) -> bool {
     waveformat.Format.wFormatTag = WAVE_FORMAT_EXTENSIBLE as u16;
@@ -127,6 +134,8 @@ pub fn make_waveformatextensible(
     waveformat.Format.nBlockAlign = channel_count * sample_size as u16;
     waveformat.Format.wBitsPerSample = sample_size as u16 * 8;
     waveformat.Format.cbSize = EXPECTED_EXTENSIBLE_SIZE;
+    waveformat.Samples.wValidBitsPerSample = sample_size as u16 * 8;
+    waveformat.dwChannelMask = channel_mask.bits();
 
     true
 }
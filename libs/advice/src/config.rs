@@ -134,6 +134,96 @@ pub enum ChannelLayout {
     Planar,
 }
 
+bitflags! {
+    /// A set of speaker positions that the channels of a stream are mapped to.
+    ///
+    /// The channels of a stream are assigned to the positions present in the set, in the order
+    /// in which they are declared here. For example, a stream with the positions
+    /// `FRONT_LEFT | FRONT_RIGHT | LOW_FREQUENCY` sends its first channel to the front left
+    /// speaker, its second channel to the front right speaker, and its third channel to the
+    /// subwoofer.
+    ///
+    /// The values of the flags match the speaker mask used by Windows (`dwChannelMask`).
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct ChannelPositions: u32 {
+        /// The front left speaker.
+        const FRONT_LEFT = 0x1;
+        /// The front right speaker.
+        const FRONT_RIGHT = 0x2;
+        /// The front center speaker.
+        const FRONT_CENTER = 0x4;
+        /// The low-frequency effects speaker (subwoofer).
+        const LOW_FREQUENCY = 0x8;
+        /// The back left speaker.
+        const BACK_LEFT = 0x10;
+        /// The back right speaker.
+        const BACK_RIGHT = 0x20;
+        /// The speaker between the front left and the front center speakers.
+        const FRONT_LEFT_OF_CENTER = 0x40;
+        /// The speaker between the front right and the front center speakers.
+        const FRONT_RIGHT_OF_CENTER = 0x80;
+        /// The back center speaker.
+        const BACK_CENTER = 0x100;
+        /// The side left speaker.
+        const SIDE_LEFT = 0x200;
+        /// The side right speaker.
+        const SIDE_RIGHT = 0x400;
+        /// The top center speaker.
+        const TOP_CENTER = 0x800;
+        /// The top front left speaker.
+        const TOP_FRONT_LEFT = 0x1000;
+        /// The top front center speaker.
+        const TOP_FRONT_CENTER = 0x2000;
+        /// The top front right speaker.
+        const TOP_FRONT_RIGHT = 0x4000;
+        /// The top back left speaker.
+        const TOP_BACK_LEFT = 0x8000;
+        /// The top back center speaker.
+        const TOP_BACK_CENTER = 0x10000;
+        /// The top back right speaker.
+        const TOP_BACK_RIGHT = 0x20000;
+
+        /// A single channel played on the front center speaker.
+        const MONO = Self::FRONT_CENTER.bits();
+        /// The regular left and right speakers.
+        const STEREO = Self::FRONT_LEFT.bits() | Self::FRONT_RIGHT.bits();
+        /// Stereo with a subwoofer.
+        const SURROUND_2_1 = Self::STEREO.bits() | Self::LOW_FREQUENCY.bits();
+        /// Two front and two back speakers.
+        const QUAD = Self::STEREO.bits() | Self::BACK_LEFT.bits() | Self::BACK_RIGHT.bits();
+        /// Quadraphonic with a front center speaker.
+        const SURROUND_5_0 = Self::QUAD.bits() | Self::FRONT_CENTER.bits();
+        /// The usual 5.1 surround layout.
+        const SURROUND_5_1 = Self::SURROUND_5_0.bits() | Self::LOW_FREQUENCY.bits();
+        /// 5.1 surround with an additional back center speaker.
+        const SURROUND_6_1 = Self::SURROUND_5_1.bits() | Self::BACK_CENTER.bits();
+        /// The usual 7.1 surround layout.
+        const SURROUND_7_1 = Self::SURROUND_5_1.bits()
+            | Self::SIDE_LEFT.bits()
+            | Self::SIDE_RIGHT.bits();
+    }
+}
+
+impl ChannelPositions {
+    /// Returns the standard speaker positions for the provided number of channels.
+    ///
+    /// If there is no standard layout for that number of channels, an empty set is returned.
+    /// The backend is then free to map the channels however it wants.
+    pub fn default_for_channel_count(channel_count: u16) -> Self {
+        match channel_count {
+            1 => Self::MONO,
+            2 => Self::STEREO,
+            3 => Self::SURROUND_2_1,
+            4 => Self::QUAD,
+            5 => Self::SURROUND_5_0,
+            6 => Self::SURROUND_5_1,
+            7 => Self::SURROUND_6_1,
+            8 => Self::SURROUND_7_1,
+            _ => Self::empty(),
+        }
+    }
+}
+
 /// The formats that are supported by a device.
 ///
 /// # Remarks
@@ -262,6 +352,8 @@ impl DeviceFormats {
                     )
                 })
                 .unwrap(),
+
+            channel_positions: None,
         }
     }
 
@@ -331,4 +423,43 @@ pub struct StreamConfig {
     pub buffer_size: Option<NonZero<u32>>,
     /// The layout used by the stream to encode individual channels of audio data.
     pub channel_layout: ChannelLayout,
+    /// The speakers that the channels of the stream should be mapped to.
+    ///
+    /// When set to a non-empty set, the number of positions in the set must equal
+    /// [`channel_count`](Self::channel_count). Backends that honor this hint fail with
+    /// [`Error::UnsupportedConfiguration`](crate::Error::UnsupportedConfiguration) otherwise.
+    ///
+    /// # Default
+    ///
+    /// If the provided value is `None`, the standard positions for the requested number of
+    /// channels are used (see [`ChannelPositions::default_for_channel_count`]).
+    ///
+    /// # Hint
+    ///
+    /// This field is a *hint* for the backend. Backends that have no way to specify speaker
+    /// positions will ignore this value and use the layout chosen by the device.
+    pub channel_positions: Option<ChannelPositions>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ChannelPositions;
+
+    #[test]
+    fn default_positions_match_channel_count() {
+        for channel_count in 1..=8 {
+            let positions = ChannelPositions::default_for_channel_count(channel_count);
+            assert_eq!(
+                positions.bits().count_ones(),
+                channel_count as u32,
+                "wrong default positions for {channel_count} channels",
+            );
+        }
+    }
+
+    #[test]
+    fn no_default_positions_for_unusual_channel_counts() {
+        assert!(ChannelPositions::default_for_channel_count(0).is_empty());
+        assert!(ChannelPositions::default_for_channel_count(9).is_empty());
+    }
 }
@@ -1,18 +1,44 @@
 /// Represents an error that might occur when interacting when the raw audio backend.
 #[derive(Debug, Clone)]
-pub struct BackendError(String);
+pub struct BackendError {
+    /// A human-readable description of the error.
+    message: String,
+    /// The error code returned by the platform, if any.
+    code: Option<i32>,
+}
 
 impl BackendError {
     /// Creates a new [`BackendError`] with the given message.
     pub(crate) fn new(message: impl Into<String>) -> Self {
-        Self(message.into())
+        Self {
+            message: message.into(),
+            code: None,
+        }
+    }
+
+    /// Creates a new [`BackendError`] with the given message and platform error code.
+    pub(crate) fn with_code(message: impl Into<String>, code: i32) -> Self {
+        Self {
+            message: message.into(),
+            code: Some(code),
+        }
+    }
+
+    /// Returns the error code returned by the platform, if the error originates from a call to
+    /// the platform's audio API.
+    ///
+    /// The meaning of the code depends on the backend: it's an `HRESULT` for WASAPI, an
+    /// `OSStatus` for CoreAudio, and a (negative) `errno` value for ALSA.
+    #[inline]
+    pub fn code(&self) -> Option<i32> {
+        self.code
     }
 }
 
 impl std::fmt::Display for BackendError {
     #[inline]
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.pad(&self.0)
+        f.pad(&self.message)
     }
 }
 
@@ -25,21 +51,69 @@ pub enum Error {
     Backend(BackendError),
     /// The provided stream configuration is not supported by the device.
     UnsupportedConfiguration,
-    /// Indicates that the device is not (or no longer) available.
+    /// Indicates that the device is not available.
     ///
-    /// This usually occurs when the device is disconnected while used.
+    /// This usually occurs when the device does not exist (anymore) when it's opened.
     DeviceNotAvailable,
+    /// Indicates that the device was disconnected while it was being used.
+    ///
+    /// Streams opened on the device must be closed, and the device must be queried again once
+    /// it is reconnected.
+    DeviceDisconnected,
     /// The device is in use and cannot be accessed.
     DeviceInUse,
 }
 
+/// The kind of an [`Error`].
+///
+/// This is useful to react to an error without having to inspect its content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// See [`Error::Backend`].
+    ///
+    /// The platform error code can be retrieved with [`BackendError::code`].
+    Backend,
+    /// See [`Error::UnsupportedConfiguration`].
+    UnsupportedConfiguration,
+    /// See [`Error::DeviceNotAvailable`].
+    DeviceNotAvailable,
+    /// See [`Error::DeviceDisconnected`].
+    DeviceDisconnected,
+    /// See [`Error::DeviceInUse`].
+    DeviceInUse,
+}
+
+impl Error {
+    /// Returns the kind of this error.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Error::Backend(_) => ErrorKind::Backend,
+            Error::UnsupportedConfiguration => ErrorKind::UnsupportedConfiguration,
+            Error::DeviceNotAvailable => ErrorKind::DeviceNotAvailable,
+            Error::DeviceDisconnected => ErrorKind::DeviceDisconnected,
+            Error::DeviceInUse => ErrorKind::DeviceInUse,
+        }
+    }
+
+    /// Returns the underlying [`BackendError`], if this is an [`Error::Backend`].
+    #[inline]
+    pub fn as_backend_error(&self) -> Option<&BackendError> {
+        match self {
+            Error::Backend(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
 impl std::fmt::Display for Error {
     #[rustfmt::skip]
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Error::Backend(e) => std::fmt::Display::fmt(e, f),
             Error::UnsupportedConfiguration => f.pad("The provided stream configuration is not supported by the device"),
-            Error::DeviceNotAvailable => f.pad("Device not (or no longer) available"),
+            Error::DeviceNotAvailable => f.pad("Device not available"),
+            Error::DeviceDisconnected => f.pad("The device was disconnected"),
             Error::DeviceInUse => f.pad("The device is in use and cannot be accessed"),
         }
     }
@@ -51,6 +125,7 @@ impl std::error::Error for Error {
             Error::Backend(e) => Some(e),
             Error::UnsupportedConfiguration => None,
             Error::DeviceNotAvailable => None,
+            Error::DeviceDisconnected => None,
             Error::DeviceInUse => None,
         }
     }
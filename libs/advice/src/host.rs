@@ -1,4 +1,4 @@
-use crate::{BackendError, Device};
+use crate::{BackendError, Device, DeviceEvent, DeviceWatcher};
 
 /// A hint for the role of a device.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -13,11 +13,84 @@ pub enum RoleHint {
     Communications,
 }
 
+/// Identifies a [`Host`] implementation.
+///
+/// All variants are always available, regardless of the backends that are compiled in. This
+/// makes it possible to store a host identifier (for example in a settings file) and read it
+/// back on any platform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HostId {
+    /// The Windows Audio Session API.
+    Wasapi,
+    /// Apple's CoreAudio.
+    CoreAudio,
+    /// The Advanced Linux Sound Architecture.
+    Alsa,
+}
+
+impl HostId {
+    /// All the host identifiers.
+    pub const ALL: [HostId; 3] = [HostId::Wasapi, HostId::CoreAudio, HostId::Alsa];
+
+    /// Returns a stable name for the host.
+    ///
+    /// This name is suitable for persistence and can be turned back into a [`HostId`] using
+    /// [`HostId::from_name`].
+    pub fn name(self) -> &'static str {
+        match self {
+            HostId::Wasapi => "wasapi",
+            HostId::CoreAudio => "coreaudio",
+            HostId::Alsa => "alsa",
+        }
+    }
+
+    /// Returns a human-readable name for the host.
+    pub fn display_name(self) -> &'static str {
+        match self {
+            HostId::Wasapi => "WASAPI",
+            HostId::CoreAudio => "CoreAudio",
+            HostId::Alsa => "ALSA",
+        }
+    }
+
+    /// Returns the [`HostId`] with the provided name (as returned by [`HostId::name`]).
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|id| id.name() == name)
+    }
+
+    /// Returns whether the backend for this host has been compiled in.
+    ///
+    /// This does not mean that the host can actually be used on the current system.
+    pub fn is_compiled(self) -> bool {
+        match self {
+            HostId::Wasapi => cfg!(all(feature = "wasapi", target_os = "windows")),
+            HostId::CoreAudio => cfg!(all(feature = "coreaudio", target_os = "macos")),
+            HostId::Alsa => cfg!(all(feature = "alsa", target_os = "linux")),
+        }
+    }
+}
+
+impl std::fmt::Display for HostId {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.display_name())
+    }
+}
+
 /// Represents an host responsible for managing a collection of audio devices.
 pub trait Host {
+    /// Returns the identifier of this [`Host`].
+    fn id(&self) -> HostId;
+
     /// Returns the devices that are managed by this [`Host`].
     fn devices(&self) -> Result<Vec<Box<dyn Device>>, BackendError>;
 
+    /// Returns the devices that can be used to output audio.
+    fn output_devices(&self) -> Result<Vec<Box<dyn Device>>, BackendError>;
+
+    /// Returns the devices that can be used to capture audio.
+    fn input_devices(&self) -> Result<Vec<Box<dyn Device>>, BackendError>;
+
     /// Returns the default input device, if one is available.
     fn default_input_device(&self, role: RoleHint)
     -> Result<Option<Box<dyn Device>>, BackendError>;
@@ -27,4 +100,14 @@ pub trait Host {
         &self,
         role: RoleHint,
     ) -> Result<Option<Box<dyn Device>>, BackendError>;
+
+    /// Starts watching the devices managed by this [`Host`].
+    ///
+    /// The provided callback is called whenever a device is added or removed, or when the
+    /// default device changes. It keeps being called until the returned [`DeviceWatcher`] is
+    /// dropped.
+    fn watch_devices(
+        &self,
+        callback: Box<dyn Send + FnMut(DeviceEvent)>,
+    ) -> Result<DeviceWatcher, BackendError>;
 }
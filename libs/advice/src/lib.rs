@@ -18,6 +18,9 @@ pub use self::stream::*;
 mod config;
 pub use self::config::*;
 
+mod watch;
+pub use self::watch::*;
+
 mod backends;
 
 #[cfg(all(feature = "wasapi", target_os = "windows"))]
@@ -31,6 +34,38 @@ pub enum HostConfig {
     /// Use the CoreAudio host.
     #[cfg(all(feature = "coreaudio", target_os = "macos"))]
     CoreAudio,
+    /// Use the ALSA host.
+    #[cfg(all(feature = "alsa", target_os = "linux"))]
+    Alsa,
+}
+
+impl HostConfig {
+    /// Returns the default configuration for the provided host.
+    ///
+    /// If the backend for that host has not been compiled in, `None` is returned.
+    pub fn default_for(id: HostId) -> Option<Self> {
+        match id {
+            #[cfg(all(feature = "wasapi", target_os = "windows"))]
+            HostId::Wasapi => Some(HostConfig::Wasapi(WasapiHostConfig::default())),
+            #[cfg(all(feature = "coreaudio", target_os = "macos"))]
+            HostId::CoreAudio => Some(HostConfig::CoreAudio),
+            #[cfg(all(feature = "alsa", target_os = "linux"))]
+            HostId::Alsa => Some(HostConfig::Alsa),
+            _ => None,
+        }
+    }
+
+    /// Returns the identifier of the host that this configuration is for.
+    pub fn id(&self) -> HostId {
+        match *self {
+            #[cfg(all(feature = "wasapi", target_os = "windows"))]
+            HostConfig::Wasapi(_) => HostId::Wasapi,
+            #[cfg(all(feature = "coreaudio", target_os = "macos"))]
+            HostConfig::CoreAudio => HostId::CoreAudio,
+            #[cfg(all(feature = "alsa", target_os = "linux"))]
+            HostConfig::Alsa => HostId::Alsa,
+        }
+    }
 }
 
 /// Gets a specific host implementation with the provided configuration.
@@ -45,12 +80,46 @@ pub fn get_host(config: HostConfig) -> Result<Option<Box<dyn Host>>, BackendErro
         HostConfig::Wasapi(config) => backends::wasapi::get_host(config).map(Some),
         #[cfg(all(feature = "coreaudio", target_os = "macos"))]
         HostConfig::CoreAudio => backends::coreaudio::get_host().map(Some),
+        #[cfg(all(feature = "alsa", target_os = "linux"))]
+        HostConfig::Alsa => backends::alsa::get_host().map(Some),
     }
 }
 
+/// Gets the host with the provided identifier, using its default configuration.
+///
+/// This is useful to restore a host that was previously persisted using [`HostId::name`].
+///
+/// If the backend for that host has not been compiled in, or if the host is not available,
+/// `None` is returned.
+pub fn get_host_by_id(id: HostId) -> Result<Option<Box<dyn Host>>, BackendError> {
+    match HostConfig::default_for(id) {
+        Some(config) => get_host(config),
+        None => Ok(None),
+    }
+}
+
+/// Returns the hosts that can be used on the current system.
+///
+/// A host is part of the returned list if its backend has been compiled in *and* it could be
+/// successfully initialized. Hosts that fail to initialize are silently skipped.
+///
+/// # Remarks
+///
+/// This function initializes every compiled host to check whether it works, which might be
+/// somewhat expensive. Its result should be cached rather than queried repeatedly.
+pub fn available_hosts() -> Vec<HostId> {
+    HostId::ALL
+        .into_iter()
+        .filter(|&id| id.is_compiled())
+        .filter(|&id| matches!(get_host_by_id(id), Ok(Some(_))))
+        .collect()
+}
+
 /// Gets the default host for the current platform.
 ///
 /// If you need to configure the host, use [`get_host`] instead.
+///
+/// If no backend has been compiled in, `None` is returned.
 #[allow(unreachable_code)]
 pub fn default_host() -> Result<Option<Box<dyn Host>>, BackendError> {
     #[cfg(all(feature = "wasapi", target_os = "windows"))]
@@ -59,5 +128,53 @@ pub fn default_host() -> Result<Option<Box<dyn Host>>, BackendError> {
     #[cfg(all(feature = "coreaudio", target_os = "macos"))]
     return self::backends::coreaudio::get_host().map(Some);
 
-    panic!("No `advice` audio backend available - check the enabled feature flags");
+    #[cfg(all(feature = "alsa", target_os = "linux"))]
+    return self::backends::alsa::get_host().map(Some);
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HostId, available_hosts, get_host_by_id};
+
+    /// Checks that the host with the provided identifier can be retrieved by its identifier, and
+    /// that it is listed by [`available_hosts`].
+    #[allow(dead_code)]
+    fn check_host_by_id(id: HostId) {
+        assert!(id.is_compiled());
+
+        let host = get_host_by_id(id)
+            .unwrap_or_else(|err| panic!("Failed to initialize the {id} host: {err}"))
+            .unwrap_or_else(|| panic!("The {id} host is compiled in but was not returned"));
+        assert_eq!(host.id(), id);
+
+        assert!(available_hosts().contains(&id));
+    }
+
+    #[test]
+    #[cfg(all(feature = "wasapi", target_os = "windows"))]
+    fn wasapi_host_by_id() {
+        check_host_by_id(HostId::Wasapi);
+    }
+
+    #[test]
+    #[cfg(all(feature = "coreaudio", target_os = "macos"))]
+    fn coreaudio_host_by_id() {
+        check_host_by_id(HostId::CoreAudio);
+    }
+
+    #[test]
+    #[cfg(all(feature = "alsa", target_os = "linux"))]
+    fn alsa_host_by_id() {
+        check_host_by_id(HostId::Alsa);
+    }
+
+    #[test]
+    fn missing_hosts_are_not_returned() {
+        for id in HostId::ALL.into_iter().filter(|id| !id.is_compiled()) {
+            assert!(matches!(get_host_by_id(id), Ok(None)));
+            assert!(!available_hosts().contains(&id));
+        }
+    }
 }
@@ -1,4 +1,7 @@
-use crate::Error;
+use {
+    crate::{Error, StreamConfig},
+    std::time::Duration,
+};
 
 /// Stores the actual data that the stream is rendering or capturing.
 #[derive(Clone, Copy)]
@@ -155,5 +158,62 @@ pub trait Stream {
     /// If the stream has encountered an error, this function returns the error. In that case, the
     /// high-priority thread driving the audio stream has already returned internally and the
     /// stream is likely unusable.
+    ///
+    /// When the device was unplugged while the stream was running, the error is
+    /// [`Error::DeviceDisconnected`].
     fn check_error(&self) -> Result<(), Error>;
+
+    /// Returns the configuration that is actually in use by the stream.
+    ///
+    /// The configuration passed when opening the stream is only a request, and the backend may
+    /// have adjusted some of its fields. The returned configuration contains the resolved
+    /// values:
+    ///
+    /// - [`frame_rate`](StreamConfig::frame_rate) is the frame rate selected by the device.
+    ///
+    /// - [`buffer_size`](StreamConfig::buffer_size) is the size of the buffer allocated by the
+    ///   backend, which is the largest number of frames passed to a single call of the callback.
+    ///   It's only `None` when the backend has no way to determine it.
+    ///
+    /// - [`channel_positions`](StreamConfig::channel_positions) is `None` when the positions
+    ///   used by the device are not known.
+    ///
+    /// # Remarks
+    ///
+    /// Some backends may still call the callback with fewer frames than the returned buffer
+    /// size.
+    fn config(&self) -> &StreamConfig;
+
+    /// Returns the latency of the stream.
+    ///
+    /// For output streams, this is the time it takes for a frame written by the callback to
+    /// actually be played by the device. For input streams, this is the time elapsed between
+    /// the moment a frame is captured by the device and the moment it is handed to the callback.
+    ///
+    /// # Remarks
+    ///
+    /// The value changes over time as the stream runs, and includes whatever buffering the
+    /// backend adds on top of the device itself (for example, the audio engine of shared-mode
+    /// streams).
+    ///
+    /// When the latency cannot be determined, an error is returned instead of a zero duration.
+    fn latency(&self) -> Result<Duration, Error>;
+
+    /// Returns the number of underruns that the stream has experienced since it was created.
+    ///
+    /// An underrun happens when the callback does not provide data fast enough and the device
+    /// runs out of samples to play, which is usually heard as a click or a short gap. Frequent
+    /// underruns generally mean that the buffer size is too small.
+    ///
+    /// # Remarks
+    ///
+    /// The counter is updated by the high-priority thread each time it wakes up, and reading it
+    /// is cheap. It is never reset: to count the underruns that happened during a period of
+    /// time, sample the counter at the beginning and at the end of that period and compute the
+    /// difference.
+    ///
+    /// Backends that cannot detect underruns always return `0`.
+    fn underrun_count(&self) -> u64 {
+        0
+    }
 }
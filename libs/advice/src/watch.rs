@@ -0,0 +1,87 @@
+use {
+    crate::BackendError,
+    std::{any::Any, sync::mpsc, thread::JoinHandle},
+};
+
+/// An event describing a change in the set of devices managed by a [`Host`].
+///
+/// Events do not reference the device they are about. When receiving one, the device list (or
+/// the default device) should be queried again from the [`Host`].
+///
+/// [`Host`]: crate::Host
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeviceEvent {
+    /// A device has been added, or has become available.
+    Added,
+    /// A device has been removed, or is no longer available.
+    Removed,
+    /// The default input or output device has changed.
+    DefaultChanged,
+}
+
+/// Keeps a device watch registered with a [`Host`].
+///
+/// The callback is unregistered when this value is dropped.
+///
+/// # Remarks
+///
+/// Events are delivered on a dedicated thread owned by the [`DeviceWatcher`]. This is neither
+/// the thread that created the watcher, nor one of the high-priority threads driving streams.
+///
+/// [`Host`]: crate::Host
+pub struct DeviceWatcher {
+    /// The backend-specific registration.
+    ///
+    /// Dropping it must stop the backend from emitting new events.
+    registration: Option<Box<dyn Any>>,
+    /// The thread that delivers the events to the user-defined callback.
+    thread: Option<JoinHandle<()>>,
+}
+
+impl DeviceWatcher {
+    /// Creates a new [`DeviceWatcher`].
+    ///
+    /// # Parameters
+    ///
+    /// - `callback`: the user-defined callback, called on the dispatch thread.
+    ///
+    /// - `register`: a function that registers the backend-specific watch. It receives the
+    ///   sender that must be used to emit the events, and returns a value that unregisters the
+    ///   watch when dropped.
+    pub(crate) fn new<R: Any>(
+        mut callback: Box<dyn Send + FnMut(DeviceEvent)>,
+        register: impl FnOnce(mpsc::Sender<DeviceEvent>) -> Result<R, BackendError>,
+    ) -> Result<Self, BackendError> {
+        let (sender, receiver) = mpsc::channel();
+
+        let thread = std::thread::Builder::new()
+            .name("advice-device-watcher-thread".into())
+            .spawn(move || {
+                // The loop ends once every sender has been dropped, which happens when the
+                // registration is dropped.
+                for event in receiver {
+                    callback(event);
+                }
+            })
+            .map_err(|err| BackendError::new(format!("Failed to spawn watcher thread: {err}")))?;
+
+        // If the registration fails, the sender is dropped and the thread exits on its own.
+        let registration = register(sender)?;
+
+        Ok(Self {
+            registration: Some(Box::new(registration)),
+            thread: Some(thread),
+        })
+    }
+}
+
+impl Drop for DeviceWatcher {
+    fn drop(&mut self) {
+        // Unregister first so that the dispatch thread is guaranteed to stop.
+        drop(self.registration.take());
+
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
@@ -2,7 +2,7 @@
 
 use {
     crate::utility::is_string_literal,
-    proc_macro2::{Span, TokenStream, TokenTree},
+    proc_macro2::{Literal, Span, TokenStream, TokenTree},
     quote::quote_spanned,
 };
 
@@ -58,17 +58,44 @@ pub fn parse_color_literal(tokens: TokenStream) -> TokenStream {
         return quote_transparent(tt.span());
     };
 
-    if !lit.starts_with("#") {
-        tt.span()
-            .unwrap()
-            .error("Expected a color literal")
-            .help("Color literals start with '#'")
-            .emit();
+    if let Some(hex) = lit.strip_prefix('#') {
+        return parse_hex_color(hex, &tt);
+    }
+
+    if let Some(args) = function_args(lit, "rgb") {
+        return parse_rgb_color(&args, false, &tt);
+    }
+
+    if let Some(args) = function_args(lit, "rgba") {
+        return parse_rgb_color(&args, true, &tt);
+    }
+
+    if let Some(args) = function_args(lit, "hsl") {
+        return parse_hsl_color(&args, &tt);
+    }
+
+    if lit == "transparent" {
         return quote_transparent(tt.span());
     }
 
-    let lit = &lit[1..];
+    if let Some(rgb) = named_color(lit) {
+        let [_, r, g, b] = rgb.to_be_bytes();
+        return quote_rgb(r, g, b, tt.span());
+    }
 
+    tt.span()
+        .unwrap()
+        .error("Expected a color literal")
+        .help(
+            "Color literals are hexadecimal codes (`#rrggbb`), `rgb(..)`, `rgba(..)`, `hsl(..)` \
+             or CSS color names",
+        )
+        .emit();
+    quote_transparent(tt.span())
+}
+
+/// Parses the digits of an hexadecimal color literal (without the leading `#`).
+fn parse_hex_color(lit: &str, tt: &Literal) -> TokenStream {
     fn hex_digit(a: u8, span: Span) -> u8 {
         match a {
             b'0'..=b'9' => a - b'0',
@@ -130,3 +157,307 @@ pub fn parse_color_literal(tokens: TokenStream) -> TokenStream {
         }
     }
 }
+
+/// Returns whether the provided string literal (without its quotes) looks like a color that
+/// [`parse_color_literal`] understands.
+pub fn is_color_literal(lit: &str) -> bool {
+    lit.starts_with('#')
+        || function_args(lit, "rgb").is_some()
+        || function_args(lit, "rgba").is_some()
+        || function_args(lit, "hsl").is_some()
+        || lit == "transparent"
+        || named_color(lit).is_some()
+}
+
+/// If `lit` is a call to the function `name` (e.g. `rgb(1, 2, 3)`), returns its arguments.
+fn function_args<'a>(lit: &'a str, name: &str) -> Option<Vec<&'a str>> {
+    let args = lit
+        .strip_prefix(name)?
+        .trim_start()
+        .strip_prefix('(')?
+        .strip_suffix(')')?;
+    Some(args.split(',').map(str::trim).collect())
+}
+
+/// Parses a component of a functional color literal.
+///
+/// The component must be a number between `0` and `max`.
+///
+/// # Returns
+///
+/// The message describing the problem when the component is invalid.
+fn parse_component(arg: &str, name: &str, max: f64) -> Result<f64, String> {
+    let Ok(value) = arg.parse::<f64>() else {
+        return Err(format!(
+            "Expected a number for the {name} component, got `{arg}`"
+        ));
+    };
+
+    if !(0.0..=max).contains(&value) {
+        return Err(format!(
+            "The {name} component must be between 0 and {max}, got {value}"
+        ));
+    }
+
+    Ok(value)
+}
+
+/// Like [`parse_component`], but emits an error on the literal when the component is invalid.
+fn parse_component_or_emit(arg: &str, name: &str, max: f64, tt: &Literal) -> Option<f64> {
+    parse_component(arg, name, max)
+        .map_err(|message| tt.span().unwrap().error(message).emit())
+        .ok()
+}
+
+/// Reports that a functional color literal has the wrong number of arguments.
+fn wrong_arg_count(expected: &str, tt: &Literal) -> TokenStream {
+    tt.span()
+        .unwrap()
+        .error("Wrong number of color components")
+        .help(format!("Expected `{expected}`"))
+        .emit();
+    quote_transparent(tt.span())
+}
+
+/// Parses the arguments of an `rgb(r, g, b)` or `rgba(r, g, b, a)` color literal.
+///
+/// The channels range from `0` to `255`, and the alpha component from `0.0` to `1.0`.
+fn parse_rgb_color(args: &[&str], alpha: bool, tt: &Literal) -> TokenStream {
+    let (channels, a) = match (args, alpha) {
+        ([r, g, b], false) => ([r, g, b], None),
+        ([r, g, b, a], true) => ([r, g, b], Some(a)),
+        (_, false) => return wrong_arg_count("rgb(r, g, b)", tt),
+        (_, true) => return wrong_arg_count("rgba(r, g, b, a)", tt),
+    };
+
+    let [r, g, b] = [
+        ("red", channels[0]),
+        ("green", channels[1]),
+        ("blue", channels[2]),
+    ]
+    .map(|(name, arg)| parse_component_or_emit(arg, name, 255.0, tt).map(|c| c.round() as u8));
+    let a =
+        a.map(|a| parse_component_or_emit(a, "alpha", 1.0, tt).map(|a| (a * 255.0).round() as u8));
+
+    match (r, g, b, a) {
+        (Some(r), Some(g), Some(b), None) => quote_rgb(r, g, b, tt.span()),
+        (Some(r), Some(g), Some(b), Some(Some(a))) => quote_rgba(r, g, b, a, tt.span()),
+        _ => quote_transparent(tt.span()),
+    }
+}
+
+/// Parses the arguments of an `hsl(h, s%, l%)` color literal.
+///
+/// The hue is in degrees and wraps around. The saturation and lightness range from `0%` to
+/// `100%`.
+fn parse_hsl_color(args: &[&str], tt: &Literal) -> TokenStream {
+    let [h, s, l] = *args else {
+        return wrong_arg_count("hsl(h, s%, l%)", tt);
+    };
+
+    let h = h.strip_suffix("deg").unwrap_or(h);
+    let Ok(h) = h.parse::<f64>() else {
+        tt.span()
+            .unwrap()
+            .error(format!(
+                "Expected a number for the hue component, got `{h}`"
+            ))
+            .emit();
+        return quote_transparent(tt.span());
+    };
+
+    let percent = |arg: &str, name: &str| {
+        let arg = arg.strip_suffix('%').unwrap_or(arg);
+        parse_component_or_emit(arg, name, 100.0, tt).map(|v| v / 100.0)
+    };
+
+    match (percent(s, "saturation"), percent(l, "lightness")) {
+        (Some(s), Some(l)) => {
+            let [r, g, b] = hsl_to_rgb(h, s, l);
+            quote_rgb(r, g, b, tt.span())
+        }
+        _ => quote_transparent(tt.span()),
+    }
+}
+
+/// Converts an HSL color to 8-bit RGB channels.
+///
+/// `h` is in degrees, `s` and `l` range from `0.0` to `1.0`.
+fn hsl_to_rgb(h: f64, s: f64, l: f64) -> [u8; 3] {
+    let h = h.rem_euclid(360.0) / 60.0;
+    let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+    let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+    let m = l - c / 2.0;
+
+    let (r, g, b) = match h as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+
+    [r, g, b].map(|v| ((v + m) * 255.0).round().clamp(0.0, 255.0) as u8)
+}
+
+/// Returns the `0xRRGGBB` value of the provided CSS named color.
+fn named_color(name: &str) -> Option<u32> {
+    NAMED_COLORS
+        .binary_search_by_key(&name, |&(n, _)| n)
+        .ok()
+        .map(|i| NAMED_COLORS[i].1)
+}
+
+/// The CSS named colors, sorted by name.
+#[rustfmt::skip]
+const NAMED_COLORS: &[(&str, u32)] = &[
+    ("aliceblue", 0xF0F8FF), ("antiquewhite", 0xFAEBD7), ("aqua", 0x00FFFF),
+    ("aquamarine", 0x7FFFD4), ("azure", 0xF0FFFF), ("beige", 0xF5F5DC),
+    ("bisque", 0xFFE4C4), ("black", 0x000000), ("blanchedalmond", 0xFFEBCD),
+    ("blue", 0x0000FF), ("blueviolet", 0x8A2BE2), ("brown", 0xA52A2A),
+    ("burlywood", 0xDEB887), ("cadetblue", 0x5F9EA0), ("chartreuse", 0x7FFF00),
+    ("chocolate", 0xD2691E), ("coral", 0xFF7F50), ("cornflowerblue", 0x6495ED),
+    ("cornsilk", 0xFFF8DC), ("crimson", 0xDC143C), ("cyan", 0x00FFFF),
+    ("darkblue", 0x00008B), ("darkcyan", 0x008B8B), ("darkgoldenrod", 0xB8860B),
+    ("darkgray", 0xA9A9A9), ("darkgreen", 0x006400), ("darkgrey", 0xA9A9A9),
+    ("darkkhaki", 0xBDB76B), ("darkmagenta", 0x8B008B), ("darkolivegreen", 0x556B2F),
+    ("darkorange", 0xFF8C00), ("darkorchid", 0x9932CC), ("darkred", 0x8B0000),
+    ("darksalmon", 0xE9967A), ("darkseagreen", 0x8FBC8F), ("darkslateblue", 0x483D8B),
+    ("darkslategray", 0x2F4F4F), ("darkslategrey", 0x2F4F4F), ("darkturquoise", 0x00CED1),
+    ("darkviolet", 0x9400D3), ("deeppink", 0xFF1493), ("deepskyblue", 0x00BFFF),
+    ("dimgray", 0x696969), ("dimgrey", 0x696969), ("dodgerblue", 0x1E90FF),
+    ("firebrick", 0xB22222), ("floralwhite", 0xFFFAF0), ("forestgreen", 0x228B22),
+    ("fuchsia", 0xFF00FF), ("gainsboro", 0xDCDCDC), ("ghostwhite", 0xF8F8FF),
+    ("gold", 0xFFD700), ("goldenrod", 0xDAA520), ("gray", 0x808080),
+    ("green", 0x008000), ("greenyellow", 0xADFF2F), ("grey", 0x808080),
+    ("honeydew", 0xF0FFF0), ("hotpink", 0xFF69B4), ("indianred", 0xCD5C5C),
+    ("indigo", 0x4B0082), ("ivory", 0xFFFFF0), ("khaki", 0xF0E68C),
+    ("lavender", 0xE6E6FA), ("lavenderblush", 0xFFF0F5), ("lawngreen", 0x7CFC00),
+    ("lemonchiffon", 0xFFFACD), ("lightblue", 0xADD8E6), ("lightcoral", 0xF08080),
+    ("lightcyan", 0xE0FFFF), ("lightgoldenrodyellow", 0xFAFAD2), ("lightgray", 0xD3D3D3),
+    ("lightgreen", 0x90EE90), ("lightgrey", 0xD3D3D3), ("lightpink", 0xFFB6C1),
+    ("lightsalmon", 0xFFA07A), ("lightseagreen", 0x20B2AA), ("lightskyblue", 0x87CEFA),
+    ("lightslategray", 0x778899), ("lightslategrey", 0x778899), ("lightsteelblue", 0xB0C4DE),
+    ("lightyellow", 0xFFFFE0), ("lime", 0x00FF00), ("limegreen", 0x32CD32),
+    ("linen", 0xFAF0E6), ("magenta", 0xFF00FF), ("maroon", 0x800000),
+    ("mediumaquamarine", 0x66CDAA), ("mediumblue", 0x0000CD), ("mediumorchid", 0xBA55D3),
+    ("mediumpurple", 0x9370DB), ("mediumseagreen", 0x3CB371), ("mediumslateblue", 0x7B68EE),
+    ("mediumspringgreen", 0x00FA9A), ("mediumturquoise", 0x48D1CC), ("mediumvioletred", 0xC71585),
+    ("midnightblue", 0x191970), ("mintcream", 0xF5FFFA), ("mistyrose", 0xFFE4E1),
+    ("moccasin", 0xFFE4B5), ("navajowhite", 0xFFDEAD), ("navy", 0x000080),
+    ("oldlace", 0xFDF5E6), ("olive", 0x808000), ("olivedrab", 0x6B8E23),
+    ("orange", 0xFFA500), ("orangered", 0xFF4500), ("orchid", 0xDA70D6),
+    ("palegoldenrod", 0xEEE8AA), ("palegreen", 0x98FB98), ("paleturquoise", 0xAFEEEE),
+    ("palevioletred", 0xDB7093), ("papayawhip", 0xFFEFD5), ("peachpuff", 0xFFDAB9),
+    ("peru", 0xCD853F), ("pink", 0xFFC0CB), ("plum", 0xDDA0DD),
+    ("powderblue", 0xB0E0E6), ("purple", 0x800080), ("rebeccapurple", 0x663399),
+    ("red", 0xFF0000), ("rosybrown", 0xBC8F8F), ("royalblue", 0x4169E1),
+    ("saddlebrown", 0x8B4513), ("salmon", 0xFA8072), ("sandybrown", 0xF4A460),
+    ("seagreen", 0x2E8B57), ("seashell", 0xFFF5EE), ("sienna", 0xA0522D),
+    ("silver", 0xC0C0C0), ("skyblue", 0x87CEEB), ("slateblue", 0x6A5ACD),
+    ("slategray", 0x708090), ("slategrey", 0x708090), ("snow", 0xFFFAFA),
+    ("springgreen", 0x00FF7F), ("steelblue", 0x4682B4), ("tan", 0xD2B48C),
+    ("teal", 0x008080), ("thistle", 0xD8BFD8), ("tomato", 0xFF6347),
+    ("turquoise", 0x40E0D0), ("violet", 0xEE82EE), ("wheat", 0xF5DEB3),
+    ("white", 0xFFFFFF), ("whitesmoke", 0xF5F5F5), ("yellow", 0xFFFF00),
+    ("yellowgreen", 0x9ACD32),
+];
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::{function_args, hsl_to_rgb, parse_color_literal, parse_component, parse_hsl_color},
+        proc_macro2::{Literal, TokenStream, TokenTree},
+        quote::quote,
+    };
+
+    /// Parses the provided Rust source into a single literal token.
+    fn literal(source: &str) -> Literal {
+        match source.parse::<TokenStream>().unwrap().into_iter().next() {
+            Some(TokenTree::Literal(lit)) => lit,
+            tt => panic!("Expected a literal, got {tt:?}"),
+        }
+    }
+
+    /// Parses the provided color literal and returns the generated code as a string.
+    fn parse_color(source: &str) -> String {
+        parse_color_literal(source.parse().unwrap()).to_string()
+    }
+
+    #[test]
+    fn function_args_splits_on_commas() {
+        assert_eq!(
+            function_args("rgb(1, 2,3)", "rgb"),
+            Some(vec!["1", "2", "3"])
+        );
+        assert_eq!(
+            function_args("hsl (10, 20%, 30%)", "hsl"),
+            Some(vec!["10", "20%", "30%"])
+        );
+        assert_eq!(function_args("rgba(1, 2, 3, 0.5)", "rgb"), None);
+        assert_eq!(function_args("rgb(1, 2, 3", "rgb"), None);
+        assert_eq!(function_args("red", "rgb"), None);
+    }
+
+    #[test]
+    fn component_range_errors() {
+        assert_eq!(parse_component("255", "red", 255.0), Ok(255.0));
+        assert_eq!(parse_component("0.5", "alpha", 1.0), Ok(0.5));
+        assert_eq!(
+            parse_component("256", "red", 255.0),
+            Err("The red component must be between 0 and 255, got 256".into()),
+        );
+        assert_eq!(
+            parse_component("-1", "green", 255.0),
+            Err("The green component must be between 0 and 255, got -1".into()),
+        );
+        assert_eq!(
+            parse_component("1.5", "alpha", 1.0),
+            Err("The alpha component must be between 0 and 1, got 1.5".into()),
+        );
+        assert_eq!(
+            parse_component("blue", "blue", 255.0),
+            Err("Expected a number for the blue component, got `blue`".into()),
+        );
+    }
+
+    #[test]
+    fn hsl_colors() {
+        let parse_hsl = |args: &[&str]| parse_hsl_color(args, &literal("\"\"")).to_string();
+        let rgb =
+            |r: u8, g: u8, b: u8| quote!(::kui::peniko::Color::from_rgb8(#r, #g, #b)).to_string();
+
+        assert_eq!(parse_hsl(&["120", "100%", "50%"]), rgb(0, 255, 0));
+        assert_eq!(parse_hsl(&["240deg", "100%", "25%"]), rgb(0, 0, 128));
+        // The hue wraps around.
+        assert_eq!(parse_hsl(&["-240", "100%", "50%"]), rgb(0, 255, 0));
+        assert_eq!(parse_hsl(&["480", "100%", "50%"]), rgb(0, 255, 0));
+
+        assert_eq!(hsl_to_rgb(0.0, 0.0, 1.0), [255, 255, 255]);
+        assert_eq!(hsl_to_rgb(30.0, 1.0, 0.5), [255, 128, 0]);
+    }
+
+    #[test]
+    fn color_literals() {
+        let rgb =
+            |r: u8, g: u8, b: u8| quote!(::kui::peniko::Color::from_rgb8(#r, #g, #b)).to_string();
+        let rgba = |r: u8, g: u8, b: u8, a: u8| {
+            quote!(::kui::peniko::Color::from_rgba8(#r, #g, #b, #a)).to_string()
+        };
+
+        assert_eq!(parse_color("\"#ff8000\""), rgb(255, 128, 0));
+        assert_eq!(parse_color("\"#f80\""), rgb(255, 136, 0));
+        assert_eq!(parse_color("\"#ff800080\""), rgba(255, 128, 0, 128));
+        assert_eq!(parse_color("\"rgb(255, 128, 0)\""), rgb(255, 128, 0));
+        assert_eq!(
+            parse_color("\"rgba(255, 128, 0, 0.5)\""),
+            rgba(255, 128, 0, 128)
+        );
+        assert_eq!(parse_color("\"cornflowerblue\""), rgb(100, 149, 237));
+        assert_eq!(
+            parse_color("\"transparent\""),
+            quote!(::kui::peniko::Color::TRANSPARENT).to_string(),
+        );
+    }
+}
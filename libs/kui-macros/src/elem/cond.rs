@@ -0,0 +1,222 @@
+use {
+    super::{Element, parse_single_element, single_element_to_tokens},
+    proc_macro2::{Delimiter, TokenStream, TokenTree, token_stream::IntoIter},
+    quote::quote,
+};
+
+/// A child that is only included when a condition holds.
+///
+/// ```ignore
+/// if results.is_empty() {
+///     label { text: "No results"; }
+/// } else if loading {
+///     spinner {}
+/// } else {
+///     results_list {}
+/// }
+/// ```
+///
+/// Branches are expanded to nested [`Either`] values, and a missing `else` branch expands to
+/// an [`Option`].
+///
+/// [`Either`]: kui::elements::either::Either
+pub struct CondChild {
+    /// The conditions and their associated elements, in order.
+    branches: Vec<(TokenStream, Option<Element>)>,
+    /// The element of the `else` branch, if any.
+    otherwise: Option<Option<Element>>,
+}
+
+impl CondChild {
+    /// Parses the provided token stream into a [`CondChild`].
+    ///
+    /// The stream must start with the `if` keyword.
+    ///
+    /// Returns `None` if given an empty token stream.
+    pub fn parse(tokens: &mut IntoIter) -> Option<Self> {
+        let mut branches = Vec::new();
+        let mut otherwise = None;
+
+        loop {
+            match tokens.next() {
+                Some(TokenTree::Ident(ident)) if ident == "if" => (),
+                Some(tt) => {
+                    tt.span()
+                        .unwrap()
+                        .error(format!("Expected `if`, got {tt}"))
+                        .emit();
+                    break;
+                }
+                None => return None,
+            }
+
+            let mut condition = TokenStream::new();
+            let body = loop {
+                match tokens.next() {
+                    Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => {
+                        break Some(group);
+                    }
+                    Some(tt) => condition.extend(Some(tt)),
+                    None => break None,
+                }
+            };
+
+            let Some(body) = body else {
+                proc_macro2::Span::call_site()
+                    .unwrap()
+                    .error("Expected the body of the `if` child")
+                    .emit();
+                break;
+            };
+
+            branches.push((condition, parse_single_element(&body)));
+
+            if !matches!(tokens.clone().next(), Some(TokenTree::Ident(ident)) if ident == "else") {
+                break;
+            }
+            tokens.next();
+
+            match tokens.clone().next() {
+                Some(TokenTree::Ident(ident)) if ident == "if" => continue,
+                Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => {
+                    tokens.next();
+                    otherwise = Some(parse_single_element(&group));
+                    break;
+                }
+                Some(tt) => {
+                    tt.span()
+                        .unwrap()
+                        .error(format!(
+                            "Expected `if` or the body of the `else` child, got {tt}"
+                        ))
+                        .emit();
+                    break;
+                }
+                None => {
+                    proc_macro2::Span::call_site()
+                        .unwrap()
+                        .error("Expected the body of the `else` child")
+                        .emit();
+                    break;
+                }
+            }
+        }
+
+        Some(Self {
+            branches,
+            otherwise,
+        })
+    }
+
+    /// Turns the conditional child into a token stream as a child.
+    pub fn to_tokens_as_child(&self) -> TokenStream {
+        let expr = self.to_tokens_from(0);
+
+        quote! {
+            .child(
+                #expr
+            )
+        }
+    }
+
+    /// Turns the branches starting at `index` into an expression producing the element.
+    fn to_tokens_from(&self, index: usize) -> TokenStream {
+        let Some((condition, element)) = self.branches.get(index) else {
+            return match &self.otherwise {
+                Some(element) => single_element_to_tokens(element),
+                None => quote! { () },
+            };
+        };
+
+        let element = single_element_to_tokens(element);
+
+        if index + 1 == self.branches.len() && self.otherwise.is_none() {
+            return quote! {
+                if #condition {
+                    ::core::option::Option::Some(#element)
+                } else {
+                    ::core::option::Option::None
+                }
+            };
+        }
+
+        let rest = self.to_tokens_from(index + 1);
+        quote! {
+            if #condition {
+                ::kui::elements::either::Either::Left(#element)
+            } else {
+                ::kui::elements::either::Either::Right(#rest)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {crate::elem::parse_element_tree, quote::quote};
+
+    /// Expands the provided element tree and returns the generated code as a string.
+    fn expand(source: &str) -> String {
+        parse_element_tree(source.parse().unwrap()).to_string()
+    }
+
+    #[test]
+    fn if_without_else_produces_an_option() {
+        let expected = quote! {
+            ::kui::IntoElement::into_element(
+                flex()
+                    .child(
+                        if loading {
+                            ::core::option::Option::Some(::kui::IntoElement::into_element(spinner()))
+                        } else {
+                            ::core::option::Option::None
+                        }
+                    )
+            )
+        };
+
+        assert_eq!(
+            expand("flex { if loading { spinner {} } }"),
+            expected.to_string()
+        );
+    }
+
+    #[test]
+    fn else_if_chains_produce_nested_eithers() {
+        let expected = quote! {
+            ::kui::IntoElement::into_element(
+                flex()
+                    .child(
+                        if results.is_empty() {
+                            ::kui::elements::either::Either::Left(
+                                ::kui::IntoElement::into_element(label().text("None"))
+                            )
+                        } else {
+                            ::kui::elements::either::Either::Right(
+                                if loading {
+                                    ::kui::elements::either::Either::Left(
+                                        ::kui::IntoElement::into_element(spinner())
+                                    )
+                                } else {
+                                    ::kui::elements::either::Either::Right(())
+                                }
+                            )
+                        }
+                    )
+            )
+        };
+
+        assert_eq!(
+            expand(
+                r#"flex {
+                    if results.is_empty() {
+                        label { text: "None"; }
+                    } else if loading {
+                        spinner {}
+                    } else {}
+                }"#
+            ),
+            expected.to_string(),
+        );
+    }
+}
@@ -1,12 +1,15 @@
 use {
-    super::{Element, prop::PropDecl},
+    super::{Element, cond::CondChild, for_child::ForChild, prop::PropDecl},
     proc_macro2::{Delimiter, TokenStream, TokenTree, token_stream::IntoIter},
+    quote::quote,
 };
 
 /// A declaration in an element's body.
 pub enum Decl {
     Prop(PropDecl),
     Child(Element),
+    CondChild(CondChild),
+    ForChild(ForChild),
 }
 
 impl Decl {
@@ -15,15 +18,31 @@ impl Decl {
         match DeclKind::predict(tokens.clone()) {
             DeclKind::Prop => PropDecl::parse(tokens).map(Self::Prop),
             DeclKind::Child => Element::parse(tokens).map(Self::Child),
+            DeclKind::CondChild => CondChild::parse(tokens).map(Self::CondChild),
+            DeclKind::ForChild => ForChild::parse(tokens).map(Self::ForChild),
         }
     }
 
-    /// Turns the declaration into a token stream as a builder method.
-    pub fn to_builder_method(&self) -> TokenStream {
+    /// If this declaration is a `style` prop, returns it.
+    pub fn as_style_prop(&self) -> Option<&PropDecl> {
         match self {
+            Self::Prop(prop) if prop.is_style() => Some(prop),
+            _ => None,
+        }
+    }
+
+    /// Applies the declaration to the provided element expression.
+    ///
+    /// Most declarations simply append a builder method to the expression.
+    pub fn apply_to(&self, element: TokenStream) -> TokenStream {
+        let method = match self {
             Self::Prop(prop) => prop.to_builder_method(),
             Self::Child(child) => child.to_tokens_as_child(),
-        }
+            Self::CondChild(child) => child.to_tokens_as_child(),
+            Self::ForChild(child) => return child.apply_to(element),
+        };
+
+        quote! { #element #method }
     }
 }
 
@@ -43,6 +62,8 @@ impl From<Element> for Decl {
 enum DeclKind {
     Prop,
     Child,
+    CondChild,
+    ForChild,
 }
 
 impl DeclKind {
@@ -50,6 +71,8 @@ impl DeclKind {
     /// a child.
     pub fn predict(mut iter: IntoIter) -> Self {
         match iter.next() {
+            Some(TokenTree::Ident(ident)) if ident == "if" => return Self::CondChild,
+            Some(TokenTree::Ident(ident)) if ident == "for" => return Self::ForChild,
             Some(TokenTree::Ident(_)) => (),
             _ => return Self::Prop,
         };
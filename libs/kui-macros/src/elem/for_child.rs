@@ -0,0 +1,141 @@
+use {
+    super::{Element, parse_single_element, single_element_to_tokens},
+    proc_macro2::{Delimiter, TokenStream, TokenTree, token_stream::IntoIter},
+    quote::quote,
+};
+
+/// A child that is repeated for every item of an iterator.
+///
+/// ```ignore
+/// for result in &results {
+///     label { text: result.name(); }
+/// }
+/// ```
+pub struct ForChild {
+    /// The pattern that the items are bound to.
+    pattern: TokenStream,
+    /// The expression producing the items.
+    iter: TokenStream,
+    /// The element created for every item.
+    element: Option<Element>,
+}
+
+impl ForChild {
+    /// Parses the provided token stream into a [`ForChild`].
+    ///
+    /// The stream must start with the `for` keyword.
+    ///
+    /// Returns `None` if given an empty token stream.
+    pub fn parse(tokens: &mut IntoIter) -> Option<Self> {
+        match tokens.next() {
+            Some(TokenTree::Ident(ident)) if ident == "for" => (),
+            Some(tt) => {
+                tt.span()
+                    .unwrap()
+                    .error(format!("Expected `for`, got {tt}"))
+                    .emit();
+            }
+            None => return None,
+        }
+
+        let mut pattern = TokenStream::new();
+        loop {
+            match tokens.next() {
+                Some(TokenTree::Ident(ident)) if ident == "in" => break,
+                Some(tt) => pattern.extend(Some(tt)),
+                None => {
+                    proc_macro2::Span::call_site()
+                        .unwrap()
+                        .error("Expected `in` after the pattern of the `for` child")
+                        .emit();
+                    break;
+                }
+            }
+        }
+
+        let mut iter = TokenStream::new();
+        let element = loop {
+            match tokens.next() {
+                Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => {
+                    break parse_single_element(&group);
+                }
+                Some(tt) => iter.extend(Some(tt)),
+                None => {
+                    proc_macro2::Span::call_site()
+                        .unwrap()
+                        .error("Expected the body of the `for` child")
+                        .emit();
+                    break None;
+                }
+            }
+        };
+
+        Some(Self {
+            pattern,
+            iter,
+            element,
+        })
+    }
+
+    /// Applies the loop to the provided element expression.
+    ///
+    /// Because a loop cannot be part of a method chain, the chain built so far is stored in a
+    /// variable that is re-assigned with the result of `.child(...)` for every item. The
+    /// `child` method of the parent element must therefore return `Self`.
+    pub fn apply_to(&self, base: TokenStream) -> TokenStream {
+        let pattern = &self.pattern;
+        let iter = &self.iter;
+        let element = single_element_to_tokens(&self.element);
+
+        quote! {
+            {
+                let mut __kui_elem = #base;
+                for #pattern in #iter {
+                    __kui_elem = __kui_elem.child(#element);
+                }
+                __kui_elem
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {crate::elem::parse_element_tree, quote::quote};
+
+    /// Expands the provided element tree and returns the generated code as a string.
+    fn expand(source: &str) -> String {
+        parse_element_tree(source.parse().unwrap()).to_string()
+    }
+
+    #[test]
+    fn loop_reassigns_the_chain() {
+        let expected = quote! {
+            ::kui::IntoElement::into_element(
+                {
+                    let mut __kui_elem = flex().vertical();
+                    for (index, result) in results.iter().enumerate() {
+                        __kui_elem = __kui_elem.child(
+                            ::kui::IntoElement::into_element(label().text(result.name()))
+                        );
+                    }
+                    __kui_elem
+                }
+                .child(::kui::IntoElement::into_element(spacer()))
+            )
+        };
+
+        assert_eq!(
+            expand(
+                "flex {
+                    vertical;
+                    for (index, result) in results.iter().enumerate() {
+                        label { text: result.name(); }
+                    }
+                    spacer {}
+                }"
+            ),
+            expected.to_string(),
+        );
+    }
+}
@@ -0,0 +1,433 @@
+//! Allows parsing a gradient from a `linear(..)` or `radial(..)` expression.
+
+use {
+    super::color::{is_color_literal, parse_color_literal},
+    crate::utility::{is_decimal_number_literal, is_string_literal},
+    proc_macro2::{Delimiter, Group, Span, TokenStream, TokenTree},
+    quote::{quote, quote_spanned},
+};
+
+/// The kind of a gradient expression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GradientKind {
+    /// `linear(..)`
+    Linear,
+    /// `radial(..)`
+    Radial,
+}
+
+impl GradientKind {
+    /// Parses the name of a gradient function.
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "linear" => Some(Self::Linear),
+            "radial" => Some(Self::Radial),
+            _ => None,
+        }
+    }
+}
+
+/// A color stop of a gradient.
+struct Stop {
+    /// The color of the stop, as a `peniko::Color` expression.
+    color: TokenStream,
+    /// The position of the stop along the gradient, between `0` and `1`.
+    ///
+    /// When `None`, the stop is evenly distributed between its neighbors.
+    offset: Option<f64>,
+}
+
+/// Returns whether the provided tokens look like a gradient expression that
+/// [`parse_gradient_literal`] understands.
+pub fn is_gradient_literal(tokens: TokenStream) -> bool {
+    let mut iter = tokens.into_iter();
+
+    let is_function = matches!(
+        (iter.next(), iter.next()),
+        (Some(TokenTree::Ident(ident)), Some(TokenTree::Group(group)))
+            if GradientKind::parse(&ident.to_string()).is_some()
+                && group.delimiter() == Delimiter::Parenthesis
+    );
+
+    is_function && iter.next().is_none()
+}
+
+/// Parses a `linear(..)` or `radial(..)` expression into a `peniko::Gradient`.
+///
+/// The geometry of the gradient is expressed in a unit square, where `(0, 0)` is the top-left
+/// corner of the element that is painted and `(1, 1)` its bottom-right corner.
+pub fn parse_gradient_literal(tokens: TokenStream) -> TokenStream {
+    let mut iter = tokens.into_iter();
+
+    let (Some(TokenTree::Ident(ident)), Some(TokenTree::Group(group))) = (iter.next(), iter.next())
+    else {
+        Span::call_site()
+            .unwrap()
+            .error("Expected a `linear(..)` or `radial(..)` gradient")
+            .emit();
+        return quote_fallback(Span::call_site());
+    };
+
+    let Some(kind) = GradientKind::parse(&ident.to_string()) else {
+        ident
+            .span()
+            .unwrap()
+            .error(format!("Unknown gradient kind: `{ident}`"))
+            .help("Available gradients are `linear(..)` and `radial(..)`")
+            .emit();
+        return quote_fallback(ident.span());
+    };
+
+    let mut args = split_args(&group);
+
+    let geometry = match kind {
+        GradientKind::Linear => parse_linear_geometry(&mut args, group.span()),
+        GradientKind::Radial => parse_radial_geometry(&mut args, group.span()),
+    };
+
+    let Some(stops) = parse_stops(&args, group.span()) else {
+        return quote_fallback(group.span());
+    };
+
+    quote_spanned! { group.span() =>
+        #geometry.with_stops([#(#stops),*])
+    }
+}
+
+/// A gradient that does not paint anything, used when the expression is invalid.
+fn quote_fallback(span: Span) -> TokenStream {
+    quote_spanned! { span =>
+        ::kui::peniko::Gradient::new_linear((0.0, 0.0), (0.0, 1.0))
+    }
+}
+
+/// Splits the content of the provided group on commas.
+fn split_args(group: &Group) -> Vec<Vec<TokenTree>> {
+    let mut args = Vec::new();
+    let mut current = Vec::new();
+
+    for tt in group.stream() {
+        match tt {
+            TokenTree::Punct(punct) if punct.as_char() == ',' => {
+                args.push(std::mem::take(&mut current));
+            }
+            tt => current.push(tt),
+        }
+    }
+
+    if !current.is_empty() {
+        args.push(current);
+    }
+
+    args
+}
+
+/// Parses a (possibly negative) number literal, returning its value and suffix.
+fn parse_number(arg: &[TokenTree]) -> Option<(f64, String)> {
+    let (negative, lit) = match arg {
+        [TokenTree::Literal(lit)] => (false, lit),
+        [TokenTree::Punct(punct), TokenTree::Literal(lit)] if punct.as_char() == '-' => (true, lit),
+        _ => return None,
+    };
+
+    let lit = lit.to_string();
+    let (num, suffix) = is_decimal_number_literal(&lit)?;
+    let value = num.parse::<f64>().ok()?;
+    Some((if negative { -value } else { value }, suffix.to_owned()))
+}
+
+/// Parses a point of the unit square, written `(x, y)`.
+fn parse_point(arg: &[TokenTree]) -> Option<(f64, f64)> {
+    let [TokenTree::Group(group)] = arg else {
+        return None;
+    };
+    if group.delimiter() != Delimiter::Parenthesis {
+        return None;
+    }
+
+    match split_args(group).as_slice() {
+        [x, y] => match (parse_number(x), parse_number(y)) {
+            (Some((x, x_suffix)), Some((y, y_suffix)))
+                if is_unitless(&x_suffix) && is_unitless(&y_suffix) =>
+            {
+                Some((x, y))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns whether the provided suffix is acceptable for a unitless number.
+fn is_unitless(suffix: &str) -> bool {
+    matches!(suffix, "" | "f32" | "f64")
+}
+
+/// Returns the span of the provided argument.
+fn arg_span(arg: &[TokenTree], fallback: Span) -> Span {
+    arg.first().map_or(fallback, TokenTree::span)
+}
+
+/// Parses the direction of a linear gradient, removing the arguments that describe it.
+///
+/// The direction is either an angle (`90deg`, `0.25turn`, `1.57rad`), following the CSS
+/// convention where `0deg` points upwards and angles increase clockwise, or a pair of points
+/// (`(0, 0), (1, 1)`). When missing, the gradient goes from top to bottom.
+fn parse_linear_geometry(args: &mut Vec<Vec<TokenTree>>, span: Span) -> TokenStream {
+    let (start, end) = if let Some(start) = args.first().and_then(|arg| parse_point(arg)) {
+        let Some(end) = args.get(1).and_then(|arg| parse_point(arg)) else {
+            args.get(1)
+                .map_or(span, |arg| arg_span(arg, span))
+                .unwrap()
+                .error("Expected the end point of the gradient, written `(x, y)`")
+                .emit();
+            return quote_fallback(span);
+        };
+        args.drain(..2);
+        (start, end)
+    } else {
+        let angle = match args.first().and_then(|arg| parse_number(arg)) {
+            Some((value, suffix)) => {
+                let radians = match suffix.as_str() {
+                    "deg" => value.to_radians(),
+                    "rad" => value,
+                    "turn" => value * std::f64::consts::TAU,
+                    _ => {
+                        arg_span(&args[0], span)
+                            .unwrap()
+                            .error(format!("Angle unit not recognized: `{suffix}`"))
+                            .help("Available units are `deg`, `rad` and `turn`")
+                            .emit();
+                        return quote_fallback(span);
+                    }
+                };
+                args.remove(0);
+                radians
+            }
+            None => std::f64::consts::PI,
+        };
+
+        // Like in CSS, the gradient line goes through the center of the box and is long
+        // enough for the corners to be reached by the first and last stops.
+        let (sin, cos) = angle.sin_cos();
+        let half_length = (sin.abs() + cos.abs()) / 2.0;
+        let (dx, dy) = (sin * half_length, -cos * half_length);
+        ((0.5 - dx, 0.5 - dy), (0.5 + dx, 0.5 + dy))
+    };
+
+    let ((x0, y0), (x1, y1)) = (start, end);
+    quote_spanned! { span =>
+        ::kui::peniko::Gradient::new_linear((#x0, #y0), (#x1, #y1))
+    }
+}
+
+/// Parses the geometry of a radial gradient, removing the arguments that describe it.
+///
+/// The geometry is an optional center (`(0.5, 0.5)` by default), optionally followed by a
+/// radius (`0.5` by default).
+fn parse_radial_geometry(args: &mut Vec<Vec<TokenTree>>, span: Span) -> TokenStream {
+    let mut center = (0.5, 0.5);
+    let mut radius = 0.5f32;
+
+    if let Some(point) = args.first().and_then(|arg| parse_point(arg)) {
+        center = point;
+        args.remove(0);
+
+        if let Some((value, suffix)) = args.first().and_then(|arg| parse_number(arg)) {
+            if !is_unitless(&suffix) {
+                arg_span(&args[0], span)
+                    .unwrap()
+                    .error("The radius of a radial gradient must be a unitless number")
+                    .emit();
+            }
+            radius = value as f32;
+            args.remove(0);
+        }
+    }
+
+    let (cx, cy) = center;
+    quote_spanned! { span =>
+        ::kui::peniko::Gradient::new_radial((#cx, #cy), #radius)
+    }
+}
+
+/// Parses the color stops of a gradient.
+///
+/// Each stop is a color literal, optionally followed by its position as a percentage
+/// (`"#fff" 25%`). Stops without a position are evenly distributed between their neighbors.
+fn parse_stops(args: &[Vec<TokenTree>], span: Span) -> Option<Vec<TokenStream>> {
+    if args.len() < 2 {
+        span.unwrap()
+            .error("Gradients need at least two color stops")
+            .emit();
+        return None;
+    }
+
+    let mut stops = Vec::with_capacity(args.len());
+    for arg in args {
+        stops.push(parse_stop(arg, span)?);
+    }
+
+    let offsets = resolve_offsets(&stops);
+
+    Some(
+        stops
+            .into_iter()
+            .zip(offsets)
+            .map(|(stop, offset)| {
+                let color = stop.color;
+                quote! { ::kui::peniko::ColorStop::from((#offset, #color)) }
+            })
+            .collect(),
+    )
+}
+
+/// Parses a single color stop.
+fn parse_stop(arg: &[TokenTree], span: Span) -> Option<Stop> {
+    let Some((TokenTree::Literal(lit), rest)) = arg.split_first() else {
+        arg_span(arg, span)
+            .unwrap()
+            .error("Expected a color stop, such as `\"#fff\"` or `\"#fff\" 50%`")
+            .emit();
+        return None;
+    };
+
+    let lit_str = lit.to_string();
+    if !is_string_literal(&lit_str).is_some_and(is_color_literal) {
+        lit.span().unwrap().error("Expected a color literal").emit();
+        return None;
+    }
+
+    let color = parse_color_literal(TokenTree::Literal(lit.clone()).into());
+
+    let offset = match rest {
+        [] => None,
+        [TokenTree::Literal(pos), TokenTree::Punct(percent)] if percent.as_char() == '%' => {
+            match is_decimal_number_literal(&pos.to_string()) {
+                Some((num, "")) => num.parse::<f64>().ok().map(|value| value / 100.0),
+                _ => None,
+            }
+            .or_else(|| {
+                pos.span()
+                    .unwrap()
+                    .error("Expected the position of the stop, such as `50%`")
+                    .emit();
+                None
+            })
+        }
+        [tt, ..] => {
+            tt.span()
+                .unwrap()
+                .error("Expected the position of the stop, such as `50%`")
+                .emit();
+            None
+        }
+    };
+
+    Some(Stop { color, offset })
+}
+
+/// Computes the offset of every stop, filling in the ones that were not specified.
+///
+/// The first and last stops default to `0%` and `100%`. Other missing offsets are linearly
+/// interpolated between the closest specified ones. Like in CSS, an offset smaller than the one
+/// of a previous stop is raised to match it.
+fn resolve_offsets(stops: &[Stop]) -> Vec<f32> {
+    let last = stops.len() - 1;
+    let mut offsets: Vec<Option<f64>> = stops.iter().map(|stop| stop.offset).collect();
+    offsets[0].get_or_insert(0.0);
+    offsets[last].get_or_insert(1.0);
+
+    let mut max = 0.0f64;
+    for offset in offsets.iter_mut().flatten() {
+        max = max.max(*offset);
+        *offset = max;
+    }
+
+    let mut result = Vec::with_capacity(stops.len());
+    let mut previous = 0;
+    for (index, offset) in offsets.iter().enumerate() {
+        if let Some(offset) = *offset {
+            // Distribute the stops between `previous` and `index` evenly.
+            let start = offsets[previous].unwrap_or(0.0);
+            let count = index - previous;
+            for i in previous + 1..index {
+                let t = (i - previous) as f64 / count as f64;
+                result.push((start + (offset - start) * t) as f32);
+            }
+            if result.len() == index {
+                result.push(offset as f32);
+            }
+            previous = index;
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::{Stop, parse_stop, resolve_offsets, split_args},
+        proc_macro2::{Span, TokenStream, TokenTree},
+    };
+
+    /// Parses the provided parenthesized, comma-separated arguments.
+    fn args(source: &str) -> Vec<Vec<TokenTree>> {
+        match source.parse::<TokenStream>().unwrap().into_iter().next() {
+            Some(TokenTree::Group(group)) => split_args(&group),
+            tt => panic!("Expected a group, got {tt:?}"),
+        }
+    }
+
+    /// Parses the provided color stops and returns their offsets.
+    fn stop_offsets(source: &str) -> Vec<Option<f64>> {
+        args(source)
+            .iter()
+            .map(|arg| parse_stop(arg, Span::call_site()).unwrap().offset)
+            .collect()
+    }
+
+    /// Creates stops with the provided offsets.
+    fn stops(offsets: &[Option<f64>]) -> Vec<Stop> {
+        offsets
+            .iter()
+            .map(|&offset| Stop {
+                color: TokenStream::new(),
+                offset,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn stop_positions() {
+        assert_eq!(
+            stop_offsets(r##"("#fff", "#888" 25%, "red" 100%, "rgb(0, 0, 0)")"##),
+            [None, Some(0.25), Some(1.0), None],
+        );
+    }
+
+    #[test]
+    fn missing_offsets_are_distributed() {
+        assert_eq!(
+            resolve_offsets(&stops(&[None, None, None])),
+            [0.0, 0.5, 1.0]
+        );
+        assert_eq!(
+            resolve_offsets(&stops(&[None, Some(0.25), None, None, None])),
+            [0.0, 0.25, 0.5, 0.75, 1.0],
+        );
+        assert_eq!(
+            resolve_offsets(&stops(&[None, None, Some(0.5), None, Some(0.75)])),
+            [0.0, 0.25, 0.5, 0.625, 0.75],
+        );
+    }
+
+    #[test]
+    fn decreasing_offsets_are_raised() {
+        assert_eq!(
+            resolve_offsets(&stops(&[Some(0.5), Some(0.25), None])),
+            [0.5, 0.5, 1.0],
+        );
+    }
+}
@@ -1,11 +1,14 @@
 use {
     self::decl::Decl,
-    proc_macro2::{Delimiter, TokenStream, TokenTree, token_stream::IntoIter},
+    proc_macro2::{Delimiter, Group, TokenStream, TokenTree, token_stream::IntoIter},
     quote::quote,
 };
 
 mod color;
+mod cond;
 mod decl;
+mod for_child;
+mod gradient;
 mod prop;
 
 /// Represents an element.
@@ -65,19 +68,60 @@ impl Element {
     }
 
     /// Turns the element into a token stream.
+    ///
+    /// Style sets referenced through `style` props are applied to the freshly created element
+    /// first, in the order in which they are listed. The other declarations are applied
+    /// afterwards, which means that they override whatever the style sets have configured.
     pub fn to_tokens(&self) -> TokenStream {
         let path = &self.path;
-        let decls = self.decls.iter().map(Decl::to_builder_method);
+
+        let mut base = quote! { #path () };
+        for style in self.decls.iter().filter_map(Decl::as_style_prop) {
+            for value in &style.values {
+                base = quote! { (#value)(#base) };
+            }
+        }
+
+        let element = self
+            .decls
+            .iter()
+            .filter(|decl| decl.as_style_prop().is_none())
+            .fold(base, |element, decl| decl.apply_to(element));
 
         quote! {
             ::kui::IntoElement::into_element(
-                #path ()
-                    #(#decls)*
+                #element
             )
         }
     }
 }
 
+/// Parses the body of a block that must contain a single element, such as the branch of an
+/// `if` child or the body of a `for` child.
+fn parse_single_element(body: &Group) -> Option<Element> {
+    let mut tokens = body.stream().into_iter();
+    let element = Element::parse(&mut tokens);
+
+    if let Some(tt) = tokens.next() {
+        tt.span()
+            .unwrap()
+            .error("Expected a single element in the block")
+            .emit();
+    }
+
+    element
+}
+
+/// Turns an element parsed by [`parse_single_element`] into a token stream.
+///
+/// Empty blocks produce the empty `()` element.
+fn single_element_to_tokens(element: &Option<Element>) -> TokenStream {
+    match element {
+        Some(element) => element.to_tokens(),
+        None => quote! { () },
+    }
+}
+
 /// Parses an element tree.
 pub fn parse_element_tree(tokens: TokenStream) -> TokenStream {
     match Element::parse(&mut tokens.into_iter()) {
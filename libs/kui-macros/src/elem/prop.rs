@@ -1,10 +1,13 @@
 use {
-    super::color::parse_color_literal,
+    super::{
+        color::{is_color_literal, parse_color_literal},
+        gradient::{is_gradient_literal, parse_gradient_literal},
+    },
     crate::{
         len::parse_length_literal,
         utility::{STANDARD_SUFFIXES, is_decimal_number_literal, is_string_literal},
     },
-    proc_macro2::{Ident, Spacing, TokenStream, TokenTree, token_stream::IntoIter},
+    proc_macro2::{Delimiter, Ident, Spacing, TokenStream, TokenTree, token_stream::IntoIter},
     quote::quote,
 };
 
@@ -93,6 +96,12 @@ impl PropDecl {
         Some(Self { ident, values })
     }
 
+    /// Returns whether this prop references style sets (`style: a, b;`) rather than a builder
+    /// method.
+    pub fn is_style(&self) -> bool {
+        self.ident == "style"
+    }
+
     /// Turns the field into a token stream.
     pub fn to_builder_method(&self) -> TokenStream {
         let ident = &self.ident;
@@ -103,6 +112,7 @@ impl PropDecl {
             .map(|value| match PropValueHint::predict(value.clone()) {
                 PropValueHint::Length => parse_length_literal(value.clone()),
                 PropValueHint::Color => parse_color_literal(value.clone()),
+                PropValueHint::Gradient => parse_gradient_literal(value.clone()),
                 PropValueHint::Unknown => value.clone(),
             });
 
@@ -116,6 +126,8 @@ enum PropValueHint {
     Length,
     /// The field value seems to be a color literal.
     Color,
+    /// The field value seems to be a gradient expression (`linear(..)` or `radial(..)`).
+    Gradient,
     /// The field value is unknown.
     Unknown,
 }
@@ -123,24 +135,44 @@ enum PropValueHint {
 impl PropValueHint {
     /// Attempts to predict the type of the provided tokens.
     pub fn predict(tokens: TokenStream) -> Self {
+        if is_gradient_literal(tokens.clone()) {
+            return Self::Gradient;
+        }
+
         let mut iter = tokens.into_iter();
 
         match iter.next() {
             Some(TokenTree::Literal(lit)) => {
                 let lit = lit.to_string();
                 if let Some((_, suffix)) = is_decimal_number_literal(&lit) {
-                    if suffix.is_empty()
-                        || STANDARD_SUFFIXES.contains(&suffix)
-                        || iter.next().is_some()
-                    {
+                    // Some units are tokenized separately from the literal (`50%`, `50w%`,
+                    // `1.5 em`).
+                    let mut next = iter.next();
+                    let has_unit = match &next {
+                        Some(TokenTree::Punct(punct)) => punct.as_char() == '%',
+                        Some(TokenTree::Ident(ident)) => suffix.is_empty() && ident == "em",
+                        _ => false,
+                    };
+                    if has_unit {
+                        next = iter.next();
+                    }
+
+                    if !has_unit && (suffix.is_empty() || STANDARD_SUFFIXES.contains(&suffix)) {
                         return Self::Unknown;
                     }
 
-                    return Self::Length;
+                    // Length literals may be followed by other terms (`8px + 2upx`).
+                    return match next {
+                        None => Self::Length,
+                        Some(TokenTree::Punct(punct)) if matches!(punct.as_char(), '+' | '-') => {
+                            Self::Length
+                        }
+                        Some(_) => Self::Unknown,
+                    };
                 }
 
                 if let Some(lit) = is_string_literal(&lit) {
-                    if lit.starts_with("#") {
+                    if is_color_literal(lit) {
                         if iter.next().is_some() {
                             return Self::Unknown;
                         }
@@ -151,6 +183,12 @@ impl PropValueHint {
 
                 Self::Unknown
             }
+            Some(TokenTree::Ident(ident)) if ident == "calc" => match iter.next() {
+                Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => {
+                    Self::Length
+                }
+                _ => Self::Unknown,
+            },
             _ => Self::Unknown,
         }
     }
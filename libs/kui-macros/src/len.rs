@@ -1,10 +1,14 @@
 use {
     crate::utility::is_decimal_number_literal,
-    proc_macro2::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree},
+    proc_macro2::{
+        Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree,
+        token_stream::IntoIter,
+    },
+    std::iter::Peekable,
 };
 
 /// A possible suffix for a length literal.
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub enum LengthSuffix {
     /// The length is specified in term of unscaled pixels.
     ///
@@ -23,6 +27,24 @@ pub enum LengthSuffix {
     ///
     /// `h%`
     ParentHeight,
+    /// The length is specified in term of the parent's size along the axis the length is
+    /// measured on.
+    ///
+    /// `%`
+    ParentAxis,
+
+    /// The length is specified in term of the width of the window.
+    ///
+    /// `vw`
+    ViewportWidth,
+    /// The length is specified in term of the height of the window.
+    ///
+    /// `vh`
+    ViewportHeight,
+    /// The length is specified in term of the current font size.
+    ///
+    /// `em`
+    Em,
 }
 
 impl LengthSuffix {
@@ -33,17 +55,14 @@ impl LengthSuffix {
             "px" => Ok(Self::Pixels),
             "w%" => Ok(Self::ParentWidth),
             "h%" => Ok(Self::ParentHeight),
-            "%" => {
-                span
-                    .unwrap()
-                    .warning("`%` unit is an alias for `w%`, it won't automatically handle length direction",)
-                    .emit();
-                Ok(Self::ParentWidth)
-            }
+            "%" => Ok(Self::ParentAxis),
+            "vw" => Ok(Self::ViewportWidth),
+            "vh" => Ok(Self::ViewportHeight),
+            "em" => Ok(Self::Em),
             _ => {
                 span.unwrap()
                     .error(format!("Length unit not recognized: `{s}`"))
-                    .help("Available units are `upx`, `px`, `w%`, `h%`")
+                    .help("Available units are `upx`, `px`, `%`, `w%`, `h%`, `vw`, `vh`, `em`")
                     .emit();
                 Err(())
             }
@@ -57,6 +76,10 @@ impl LengthSuffix {
             Self::Pixels => "Pixels",
             Self::ParentWidth => "ParentWidth",
             Self::ParentHeight => "ParentHeight",
+            Self::ParentAxis => "ParentAxis",
+            Self::ViewportWidth => "ViewportWidth",
+            Self::ViewportHeight => "ViewportHeight",
+            Self::Em => "Em",
         }
     }
 
@@ -67,6 +90,10 @@ impl LengthSuffix {
             Self::Pixels => Literal::f64_suffixed(val),
             Self::ParentWidth => Literal::f64_suffixed(val / 100.0),
             Self::ParentHeight => Literal::f64_suffixed(val / 100.0),
+            Self::ParentAxis => Literal::f64_suffixed(val / 100.0),
+            Self::ViewportWidth => Literal::f64_suffixed(val / 100.0),
+            Self::ViewportHeight => Literal::f64_suffixed(val / 100.0),
+            Self::Em => Literal::f64_suffixed(val),
         }
     }
 }
@@ -85,7 +112,7 @@ fn parse_f64(s: &str, span: Span) -> Result<f64, ()> {
 }
 
 /// A parsed length literal.
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub enum Length {
     /// The `0` literal.
     Zero,
@@ -96,11 +123,23 @@ pub enum Length {
         /// The suffix associated with the value.
         suffix: LengthSuffix,
     },
+    /// The sum of two lengths.
+    ///
+    /// `a + b`
+    Sum(Box<Length>, Box<Length>),
+    /// The difference of two lengths.
+    ///
+    /// `a - b`
+    Difference(Box<Length>, Box<Length>),
 }
 
 impl Length {
     /// Parses the provided literal into a length literal.
-    pub fn parse_literal(lit: &Literal) -> Result<Self, ()> {
+    ///
+    /// Some units cannot be part of the literal itself and are tokenized separately: `%` is a
+    /// punctuation, and `em` must be written `1 em` because `1em` would be lexed as an
+    /// incomplete exponent. When `unit` is set, it is appended to the suffix of the literal.
+    pub fn parse_literal(lit: &Literal, unit: Option<&TokenTree>) -> Result<Self, ()> {
         let s = lit.to_string();
         let (number_str, suffix_str) = match is_decimal_number_literal(&s) {
             Some((number, suffix)) => (number, suffix),
@@ -116,14 +155,24 @@ impl Length {
         let value_span = lit.subspan(0..number_str.len()).unwrap();
         let value = parse_f64(number_str, value_span)?;
 
-        if suffix_str.is_empty() {
+        if let Some(unit) = unit {
+            let suffix_span = if suffix_str.is_empty() {
+                unit.span()
+            } else {
+                lit.subspan(number_str.len()..).unwrap()
+            };
+            let suffix = LengthSuffix::parse(&format!("{suffix_str}{unit}"), suffix_span)?;
+            Ok(Self::Literal { value, suffix })
+        } else if suffix_str.is_empty() {
             if value == 0.0 {
                 Ok(Self::Zero)
             } else {
                 value_span
                     .unwrap()
                     .warning("Length literal without a suffix is treated as `px`")
-                    .help("Available length units are `upx`, `px`, `w%`, `h%`")
+                    .help(
+                        "Available length units are `upx`, `px`, `%`, `w%`, `h%`, `vw`, `vh`, `em`",
+                    )
                     .emit();
                 Ok(Self::Literal {
                     value,
@@ -137,12 +186,38 @@ impl Length {
         }
     }
 
-    /// Parses a length literal from the provided token stream.
-    pub fn parse(stream: TokenStream) -> Result<Self, ()> {
-        let mut tokens = stream.into_iter();
-
+    /// Parses a single length term from the provided token iterator.
+    ///
+    /// A term is either a length literal, a parenthesized length expression, or a
+    /// `calc(...)` expression.
+    fn parse_term(tokens: &mut Peekable<IntoIter>) -> Result<Self, ()> {
         match tokens.next() {
-            Some(TokenTree::Literal(lit)) => Self::parse_literal(&lit),
+            Some(TokenTree::Literal(lit)) => {
+                let has_unit = match tokens.peek() {
+                    Some(TokenTree::Punct(punct)) => punct.as_char() == '%',
+                    Some(TokenTree::Ident(_)) => is_decimal_number_literal(&lit.to_string())
+                        .is_some_and(|(_, suffix)| suffix.is_empty()),
+                    _ => false,
+                };
+                let unit = if has_unit { tokens.next() } else { None };
+                Self::parse_literal(&lit, unit.as_ref())
+            }
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => {
+                Self::parse(group.stream())
+            }
+            Some(TokenTree::Ident(ident)) if ident == "calc" => match tokens.next() {
+                Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => {
+                    Self::parse(group.stream())
+                }
+                _ => {
+                    ident
+                        .span()
+                        .unwrap()
+                        .error("Expected a parenthesized expression after `calc`")
+                        .emit();
+                    Err(())
+                }
+            },
             Some(tt) => {
                 tt.span()
                     .unwrap()
@@ -161,6 +236,39 @@ impl Length {
         }
     }
 
+    /// Parses a length expression from the provided token stream.
+    ///
+    /// A length expression is a list of terms separated by `+` or `-` operators. Terms are
+    /// either length literals or parenthesized length expressions, optionally prefixed by
+    /// `calc` for readability.
+    pub fn parse(stream: TokenStream) -> Result<Self, ()> {
+        let mut tokens = stream.into_iter().peekable();
+
+        let mut result = Self::parse_term(&mut tokens)?;
+
+        while let Some(tt) = tokens.next() {
+            match tt {
+                TokenTree::Punct(punct) if punct.as_char() == '+' => {
+                    let rhs = Self::parse_term(&mut tokens)?;
+                    result = Self::Sum(Box::new(result), Box::new(rhs));
+                }
+                TokenTree::Punct(punct) if punct.as_char() == '-' => {
+                    let rhs = Self::parse_term(&mut tokens)?;
+                    result = Self::Difference(Box::new(result), Box::new(rhs));
+                }
+                tt => {
+                    tt.span()
+                        .unwrap()
+                        .error(format!("Expected `+` or `-`, got `{tt}`"))
+                        .emit();
+                    return Err(());
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
     pub fn to_tokens(&self) -> TokenStream {
         let span = Span::call_site();
 
@@ -195,11 +303,90 @@ impl Length {
                     )),
                 ])
                 .collect(),
+            Self::Sum(lhs, rhs) => Self::binary_op_to_tokens(lhs, '+', rhs),
+            Self::Difference(lhs, rhs) => Self::binary_op_to_tokens(lhs, '-', rhs),
         }
     }
+
+    /// Turns a binary operation into a parenthesized token stream.
+    fn binary_op_to_tokens(lhs: &Self, op: char, rhs: &Self) -> TokenStream {
+        let mut inner = lhs.to_tokens();
+        inner.extend([TokenTree::Punct(Punct::new(op, Spacing::Alone))]);
+        inner.extend(rhs.to_tokens());
+        Some(TokenTree::Group(Group::new(Delimiter::Parenthesis, inner)))
+            .into_iter()
+            .collect()
+    }
 }
 
 /// Parses the provided token stream into a length literal.
 pub fn parse_length_literal(tokens: TokenStream) -> TokenStream {
     Length::parse(tokens).unwrap_or(Length::Zero).to_tokens()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Length, LengthSuffix};
+
+    /// Parses the provided length expression.
+    fn parse(s: &str) -> Length {
+        Length::parse(s.parse().unwrap()).unwrap()
+    }
+
+    /// Creates a length literal.
+    fn lit(value: f64, suffix: LengthSuffix) -> Box<Length> {
+        Box::new(Length::Literal { value, suffix })
+    }
+
+    #[test]
+    fn literals() {
+        assert_eq!(parse("0"), Length::Zero);
+        assert_eq!(parse("12px"), *lit(12.0, LengthSuffix::Pixels));
+        assert_eq!(parse("1.5upx"), *lit(1.5, LengthSuffix::UnscaledPixels));
+        assert_eq!(parse("30vh"), *lit(30.0, LengthSuffix::ViewportHeight));
+    }
+
+    #[test]
+    fn percentages() {
+        assert_eq!(parse("50%"), *lit(50.0, LengthSuffix::ParentAxis));
+        assert_eq!(parse("25w%"), *lit(25.0, LengthSuffix::ParentWidth));
+        assert_eq!(parse("75h%"), *lit(75.0, LengthSuffix::ParentHeight));
+    }
+
+    #[test]
+    fn em_is_separated_from_the_number() {
+        assert_eq!(parse("1.5 em"), *lit(1.5, LengthSuffix::Em));
+        assert_eq!(
+            parse("2 em + 4px"),
+            Length::Sum(lit(2.0, LengthSuffix::Em), lit(4.0, LengthSuffix::Pixels),)
+        );
+    }
+
+    #[test]
+    fn operators_are_left_associative() {
+        assert_eq!(
+            parse("50% - 8px + 2upx"),
+            Length::Sum(
+                Box::new(Length::Difference(
+                    lit(50.0, LengthSuffix::ParentAxis),
+                    lit(8.0, LengthSuffix::Pixels),
+                )),
+                lit(2.0, LengthSuffix::UnscaledPixels),
+            )
+        );
+    }
+
+    #[test]
+    fn calc_and_parentheses() {
+        let expected = Length::Difference(
+            lit(100.0, LengthSuffix::ParentWidth),
+            Box::new(Length::Sum(
+                lit(16.0, LengthSuffix::Pixels),
+                lit(2.0, LengthSuffix::UnscaledPixels),
+            )),
+        );
+
+        assert_eq!(parse("calc(100w% - (16px + 2upx))"), expected);
+        assert_eq!(parse("100w% - calc(16px + 2upx)"), expected);
+    }
+}
@@ -7,12 +7,160 @@ mod len;
 mod utility;
 
 /// Creates a [`kui::elements::Length`] from the given value.
+///
+/// # Units
+///
+/// - `upx`: unscaled pixels, ignoring the scale factor of the window.
+/// - `px`: pixels, scaled by the scale factor of the window.
+/// - `w%`: a percentage of the parent's width.
+/// - `h%`: a percentage of the parent's height.
+/// - `%`: a percentage of the parent's size along the axis the length is measured on.
+/// - `vw`: a percentage of the window's width.
+/// - `vh`: a percentage of the window's height.
+/// - `em`: a multiple of the current font size. Because `1em` is lexed as an incomplete
+///   exponent, the unit must be separated from the number: `1.5 em`.
+///
+/// Plain `%` resolves against the parent's width for widths, horizontal paddings and column
+/// gaps, and against its height for heights, vertical paddings and row gaps. The gap of a flex
+/// container uses its main axis. Lengths that have no natural axis (radii, border thicknesses,
+/// font sizes) resolve it against the parent's width.
+///
+/// # Expressions
+///
+/// Terms can be combined with `+` and `-`, and grouped with parentheses. The expression may
+/// optionally be wrapped in `calc(...)`:
+///
+/// ```ignore
+/// len!(50% - 8px)
+/// len!(calc(100w% - (16px + 2upx)))
+/// ```
+///
+/// Expressions are resolved lazily, when the layout of the element is computed.
 #[proc_macro]
 pub fn len(tokens: TokenStream) -> TokenStream {
     self::len::parse_length_literal(tokens.into()).into()
 }
 
 /// Creates a tree of elements.
+///
+/// # Colors
+///
+/// String literals that describe a color are turned into a `peniko::Color` when used as the
+/// value of a prop:
+///
+/// - Hexadecimal codes: `"#rgb"`, `"#rgba"`, `"#rrggbb"` and `"#rrggbbaa"`.
+///
+/// - `"rgb(r, g, b)"` and `"rgba(r, g, b, a)"`, where the channels range from `0` to `255` and
+///   the alpha component from `0.0` to `1.0`.
+///
+/// - `"hsl(h, s%, l%)"`, where the hue is in degrees.
+///
+/// - CSS named colors, such as `"red"` or `"cornflowerblue"`, and `"transparent"`.
+///
+/// # Gradients
+///
+/// `linear(..)` and `radial(..)` expressions are turned into a `peniko::Gradient`:
+///
+/// ```ignore
+/// elem! {
+///     div {
+///         brush: linear(90deg, "#111", "#333" 75%, "#444");
+///     }
+/// }
+/// ```
+///
+/// The geometry of a gradient is expressed in a unit square that is stretched over the bounds
+/// of the painted element, `(0, 0)` being its top-left corner and `(1, 1)` its bottom-right
+/// corner.
+///
+/// - `linear(direction, stops..)`: the direction is either an angle in `deg`, `rad` or `turn`
+///   (`0deg` points upwards and angles increase clockwise, like in CSS) or a start and an end
+///   point (`(0, 0), (1, 1)`). It defaults to `180deg` (top to bottom).
+///
+/// - `radial(center, radius, stops..)`: the center defaults to `(0.5, 0.5)` and the radius to
+///   `0.5`. The radius may only be specified after the center.
+///
+/// Every stop is a color literal, optionally followed by its position (`"#fff" 25%`). The first
+/// and last stops default to `0%` and `100%`, and the others are evenly distributed between
+/// their neighbors.
+///
+/// # Style sets
+///
+/// The special `style` prop applies reusable style sets to an element. A style set is any
+/// function (or closure) that takes an element and returns it after having called some of its
+/// builder methods:
+///
+/// ```ignore
+/// fn card<E>(div: Div<E>) -> Div<E> {
+///     div.radius(len!(8px)).padding(len!(16px)).brush(palette.surface)
+/// }
+///
+/// elem! {
+///     div {
+///         style: card;
+///         padding: 4px;
+///     }
+/// }
+/// ```
+///
+/// Multiple style sets can be listed (`style: card, bordered;`), and the prop can be repeated.
+/// The style sets are applied in the order in which they appear, before any other prop of the
+/// element regardless of where the `style` prop is located. This means that:
+///
+/// - A style set overrides the style sets listed before it.
+/// - The element's own props always override its style sets.
+///
+/// # Conditional children
+///
+/// A child can be included conditionally with an `if` declaration. Each branch must contain
+/// a single element, and `else if` / `else` branches are supported:
+///
+/// ```ignore
+/// elem! {
+///     flex {
+///         vertical;
+///
+///         if has_results {
+///             results_list {}
+///         } else {
+///             label { text: "Nothing found"; }
+///         }
+///     }
+/// }
+/// ```
+///
+/// The condition is evaluated once, when the tree is built. Branches are wrapped in
+/// [`kui::elements::either::Either`] so that they have the same type, and a missing `else`
+/// branch produces an empty `Option`.
+///
+/// # Repeated children
+///
+/// A child can be repeated for every item of an iterator with a `for` declaration. The body
+/// must contain a single element, in which the pattern of the loop is in scope:
+///
+/// ```ignore
+/// elem! {
+///     flex {
+///         vertical;
+///
+///         for result in &results {
+///             label { text: result.name(); }
+///         }
+///     }
+/// }
+/// ```
+///
+/// The declarations of an element normally expand to a single builder chain
+/// (`flex().vertical().child(..)`). A loop cannot be part of that chain, so the chain built so
+/// far is stored in a variable which is re-assigned with `.child(..)` for every item, and the
+/// chain continues with the declarations that follow the loop. This means that `for` children
+/// are only supported by elements whose `child` method returns `Self`, such as [`Flex`] and
+/// [`Grid`]. Single-child elements like [`Div`] change their type when given a child, and
+/// cannot be used with `for`.
+///
+/// [`Flex`]: kui::elements::flex::Flex
+/// [`Grid`]: kui::elements::grid::Grid
+/// [`Div`]: kui::elements::div::Div
 #[proc_macro]
 pub fn elem(tokens: TokenStream) -> TokenStream {
     self::elem::parse_element_tree(tokens.into()).into()
@@ -0,0 +1,39 @@
+//! Displays a label that is rebuilt from a counter every time the window is clicked.
+
+use kui::{
+    elements::{hook_events, label, scheme_host},
+    event::{EventResult, PointerButton},
+    winit::{
+        event::{ButtonSource, MouseButton},
+        window::WindowAttributes,
+    },
+};
+
+fn main() {
+    kui::run(|ctx| {
+        let window = ctx.create_window(WindowAttributes::default().with_title("Counter"));
+
+        let counter = scheme_host(
+            |count: &u32| label().text(format!("Clicked {count} times")),
+            0u32,
+        );
+
+        window.set_root_element(hook_events().child(counter).on_event(
+            |counter, elem_context, event| {
+                let Some(ev) = event.downcast_ref::<PointerButton>() else {
+                    return EventResult::Continue;
+                };
+
+                if ev.primary
+                    && !ev.state.is_pressed()
+                    && matches!(ev.button, ButtonSource::Mouse(MouseButton::Left))
+                {
+                    counter.set_state(elem_context, |count| *count += 1);
+                    return EventResult::Handled;
+                }
+
+                EventResult::Continue
+            },
+        ));
+    });
+}
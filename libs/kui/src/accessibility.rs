@@ -0,0 +1,227 @@
+//! Exporting the UI tree to assistive technologies through [`accesskit`].
+//!
+//! # Overview
+//!
+//! Elements describe themselves by implementing [`Element::accessibility`]. Most elements are
+//! only used for layout or decoration and simply forward the call to their children, which
+//! means that they do not appear in the accessibility tree at all. Elements that are meaningful
+//! to the user push a node with [`AccessibilityContext::push`] (or
+//! [`AccessibilityContext::push_with_children`] when they have meaningful children).
+//!
+//! The built-in elements are mapped to the following roles:
+//!
+//! | Element                                        | Role                   | Name / value        |
+//! |------------------------------------------------|------------------------|---------------------|
+//! | [`Text`](crate::elements::text::Text)          | [`Role::Label`]        | The text.           |
+//! | [`Button`](crate::elements::button::Button)    | [`Role::Button`]       | Its descendants.    |
+//! | [`TextInput`](crate::elements::text_input::TextInput) | [`Role::TextInput`] | The current value. |
+//! | [`ColorPicker`](crate::elements::color_picker::ColorPicker) | [`Role::ColorWell`] | The selected color. |
+//!
+//! # Accessible names
+//!
+//! Assistive technologies compute the name of a button from the text of its descendants, so a
+//! button containing a label does not need anything special. Elements that have no textual
+//! content should call [`Node::set_label`] on the node they push.
+//!
+//! Users of the library can name any element with
+//! [`ElementExt::with_label`](crate::ElementExt::with_label) (and describe it with
+//! [`ElementExt::with_description`](crate::ElementExt::with_description)). The label is applied
+//! to the first node pushed by the element, unless that node already has one. When the element
+//! does not push any node (it is purely decorative), the label is ignored.
+//!
+//! # Node identifiers
+//!
+//! Node identifiers are assigned in tree order every time the tree is built. They are stable
+//! as long as the structure of the UI tree does not change.
+//!
+//! # Platform adapters
+//!
+//! Every window owns the `accesskit` adapter of the current platform. The tree is sent to it
+//! every time the window is drawn, as long as an assistive technology is listening.
+//!
+//! The actions requested by assistive technologies (such as focusing or clicking an element)
+//! are dispatched to the UI tree as [`AccessibilityAction`] events. Elements that support
+//! actions remember the identifier of the node they pushed in a [`NodeIdCell`] and use
+//! [`AccessibilityAction::action_for`] to find out whether they are the target of the request.
+
+use {
+    crate::Element,
+    accesskit::{Action, ActionRequest, Node, NodeId, Role, Tree, TreeUpdate},
+    std::cell::Cell,
+    vello::kurbo::Rect,
+};
+
+/// A function that receives the accessibility tree of a window.
+///
+/// See [`Window::set_accessibility_handler`](crate::Window::set_accessibility_handler).
+pub type AccessibilityHandler = Box<dyn FnMut(TreeUpdate)>;
+
+/// The identifier of the root node of every window (the window itself).
+pub const ROOT_NODE_ID: NodeId = NodeId(0);
+
+/// An event sent to the UI tree when an assistive technology requests an action.
+#[derive(Clone, Debug)]
+pub struct AccessibilityAction {
+    /// The request made by the assistive technology.
+    pub request: ActionRequest,
+}
+
+impl AccessibilityAction {
+    /// Returns the requested action if it targets the node whose identifier is stored in
+    /// `node_id`.
+    #[inline]
+    pub fn action_for(&self, node_id: &NodeIdCell) -> Option<Action> {
+        (node_id.get() == Some(self.request.target)).then_some(self.request.action)
+    }
+}
+
+/// The identifier of the node pushed by an element the last time the accessibility tree was
+/// built.
+#[derive(Clone, Debug, Default)]
+pub struct NodeIdCell(Cell<Option<NodeId>>);
+
+impl NodeIdCell {
+    /// Remembers the identifier of the node pushed by the element.
+    #[inline]
+    pub fn set(&self, id: NodeId) {
+        self.0.set(Some(id));
+    }
+
+    /// Returns the identifier of the node pushed by the element, if the tree has been built
+    /// already.
+    #[inline]
+    pub fn get(&self) -> Option<NodeId> {
+        self.0.get()
+    }
+}
+
+/// The context passed to [`Element::accessibility`] while building the accessibility tree.
+pub struct AccessibilityContext {
+    /// The nodes that have been pushed so far.
+    nodes: Vec<(NodeId, Node)>,
+    /// The children of the node currently being built.
+    children: Vec<NodeId>,
+    /// The node that has the keyboard focus, if any.
+    focus: Option<NodeId>,
+    /// The identifier that will be given to the next node.
+    next_id: u64,
+}
+
+impl AccessibilityContext {
+    /// Creates a new [`AccessibilityContext`].
+    fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            children: Vec::new(),
+            focus: None,
+            next_id: ROOT_NODE_ID.0 + 1,
+        }
+    }
+
+    /// Allocates a new node identifier.
+    fn allocate_id(&mut self) -> NodeId {
+        let id = NodeId(self.next_id);
+        self.next_id += 1;
+        id
+    }
+
+    /// Adds a leaf node to the tree, as a child of the node currently being built.
+    pub fn push(&mut self, node: Node) -> NodeId {
+        let id = self.allocate_id();
+        self.children.push(id);
+        self.nodes.push((id, node));
+        id
+    }
+
+    /// Adds a node to the tree, as a child of the node currently being built.
+    ///
+    /// The nodes pushed by the provided function become the children of the new node.
+    pub fn push_with_children(
+        &mut self,
+        mut node: Node,
+        f: impl FnOnce(&mut AccessibilityContext),
+    ) -> NodeId {
+        let id = self.allocate_id();
+        self.children.push(id);
+
+        let parent_children = std::mem::take(&mut self.children);
+        f(self);
+        let children = std::mem::replace(&mut self.children, parent_children);
+
+        node.set_children(children);
+        self.nodes.push((id, node));
+        id
+    }
+
+    /// Calls the provided function, then applies the provided label and description to the
+    /// first node it pushed.
+    ///
+    /// Labels and descriptions that the node already has are kept. This means that when
+    /// multiple calls are nested, the innermost one wins.
+    pub fn with_metadata(
+        &mut self,
+        label: Option<&str>,
+        description: Option<&str>,
+        f: impl FnOnce(&mut AccessibilityContext),
+    ) {
+        let first = self.children.len();
+        f(self);
+
+        let Some(&id) = self.children.get(first) else {
+            return;
+        };
+        let Some((_, node)) = self.nodes.iter_mut().rev().find(|(i, _)| *i == id) else {
+            return;
+        };
+
+        if let Some(label) = label.filter(|_| node.label().is_none()) {
+            node.set_label(label);
+        }
+        if let Some(description) = description.filter(|_| node.description().is_none()) {
+            node.set_description(description);
+        }
+    }
+
+    /// Marks the provided node as having the keyboard focus.
+    #[inline]
+    pub fn set_focus(&mut self, id: NodeId) {
+        self.focus = Some(id);
+    }
+}
+
+/// Converts a [`Rect`] to the rectangle type used by [`accesskit`].
+#[inline]
+pub fn to_accesskit_rect(rect: Rect) -> accesskit::Rect {
+    accesskit::Rect {
+        x0: rect.x0,
+        y0: rect.y0,
+        x1: rect.x1,
+        y1: rect.y1,
+    }
+}
+
+/// Builds a complete accessibility tree for the provided root element.
+///
+/// # Parameters
+///
+/// - `root`: The root element of the window. It must have been placed already.
+///
+/// - `title`: The name of the window, used as the label of the root node.
+///
+/// - `bounds`: The bounds of the window's content, in physical pixels.
+pub fn build_accessibility_tree(root: &dyn Element, title: &str, bounds: Rect) -> TreeUpdate {
+    let mut cx = AccessibilityContext::new();
+    root.accessibility(&mut cx);
+
+    let mut window = Node::new(Role::Window);
+    window.set_label(title);
+    window.set_bounds(to_accesskit_rect(bounds));
+    window.set_children(cx.children);
+    cx.nodes.push((ROOT_NODE_ID, window));
+
+    TreeUpdate {
+        nodes: cx.nodes,
+        tree: Some(Tree::new(ROOT_NODE_ID)),
+        focus: cx.focus.unwrap_or(ROOT_NODE_ID),
+    }
+}
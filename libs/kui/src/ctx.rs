@@ -1,11 +1,15 @@
 use {
-    crate::{Window, private::CtxInner},
+    crate::{
+        Shortcut, ShortcutConflict, ShortcutHandle, Window,
+        private::{ClickSettings, CtxInner},
+    },
     slotmap::new_key_type,
     std::{
         fmt::Debug,
         rc::{Rc, Weak},
         time::{Duration, Instant},
     },
+    vello::wgpu,
     winit::window::WindowAttributes,
 };
 
@@ -103,6 +107,97 @@ impl Ctx {
         self.inner().cancel_callback(id)
     }
 
+    /// Calls the provided function once the current frame has been laid out and rendered.
+    ///
+    /// This is useful when an element needs to run logic that depends on the final layout of
+    /// the UI tree (for example, scrolling a newly added item into view), or needs to modify the
+    /// tree without re-entering it while it is being laid out or drawn.
+    ///
+    /// # Ordering
+    ///
+    /// - Deferred functions run right after a window has been rendered, before the event loop
+    ///   processes the next event.
+    ///
+    /// - They are called in the order in which they were registered. Functions registered by a
+    ///   deferred function run as part of the same batch.
+    ///
+    /// - Timed callbacks (see [`call_at`](Self::call_at)) are run separately, when the event loop
+    ///   wakes up. A deferred function never runs in the middle of a batch of timed callbacks.
+    ///
+    /// If no frame is being rendered, the function will be called after the next frame. Use
+    /// [`Window::request_redraw`] to make sure that one is rendered.
+    #[track_caller]
+    pub fn defer(&self, callback: impl FnOnce() + 'static) {
+        self.defer_boxed(Box::new(callback));
+    }
+
+    /// Calls the provided function once the current frame has been laid out and rendered.
+    ///
+    /// See [`defer`](Self::defer) for more information.
+    #[track_caller]
+    pub fn defer_boxed(&self, callback: Box<dyn FnOnce()>) {
+        self.inner().defer(callback);
+    }
+
+    /// Registers a keyboard shortcut that calls the provided function when pressed.
+    ///
+    /// Shortcuts are global to the application: they trigger regardless of the window that has
+    /// the keyboard focus.
+    ///
+    /// # Ordering
+    ///
+    /// When a key is pressed, the focused element (such as a text input) gets the first chance
+    /// to handle it. Shortcuts are only triggered if it did not, before the key press is
+    /// dispatched to the rest of the UI tree. Repeated presses generated while the key is held
+    /// down never trigger shortcuts.
+    ///
+    /// # Returns
+    ///
+    /// A handle that unregisters the shortcut when dropped.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the same shortcut is already registered. The existing shortcut is
+    /// left untouched.
+    #[track_caller]
+    pub fn register_shortcut(
+        &self,
+        shortcut: Shortcut,
+        callback: impl FnMut() + 'static,
+    ) -> Result<ShortcutHandle, ShortcutConflict> {
+        self.register_boxed_shortcut(shortcut, Box::new(callback))
+    }
+
+    /// Registers a keyboard shortcut that calls the provided function when pressed.
+    ///
+    /// See [`register_shortcut`](Self::register_shortcut) for more information.
+    #[track_caller]
+    pub fn register_boxed_shortcut(
+        &self,
+        shortcut: Shortcut,
+        callback: Box<dyn FnMut()>,
+    ) -> Result<ShortcutHandle, ShortcutConflict> {
+        let id = self.inner().register_shortcut(shortcut, callback)?;
+        Ok(ShortcutHandle {
+            ctx: self.0.clone(),
+            id,
+        })
+    }
+
+    /// Calls the provided function with the GPU device and queue used to render the UI.
+    ///
+    /// This can be used to create custom `wgpu` resources (pipelines, textures, buffers) that are
+    /// later used in a window's render hook (see [`Window::set_render_hook`]).
+    ///
+    /// # Returns
+    ///
+    /// The renderer is created along with the first window of the application. If no window
+    /// has been created yet, this function returns `None` without calling the function.
+    #[track_caller]
+    pub fn with_gpu<R>(&self, f: impl FnOnce(&wgpu::Device, &wgpu::Queue) -> R) -> Option<R> {
+        self.inner().with_gpu(f)
+    }
+
     /// Calls the provided function with a reference to the requested resource.
     ///
     /// # Panics
@@ -185,6 +280,71 @@ impl Ctx {
     pub fn ensure_resource_present<T: 'static + Default>(&self) {
         self.with_resource_or_default(|_: &mut T| ());
     }
+
+    /// Sets the maximum duration between two presses for them to count as a double-click (or
+    /// more).
+    ///
+    /// This defaults to 500 milliseconds. See [`PointerButton::click_count`].
+    ///
+    /// [`PointerButton::click_count`]: crate::event::PointerButton::click_count
+    #[track_caller]
+    pub fn set_double_click_interval(&self, interval: Duration) {
+        let inner = self.inner();
+        inner.set_click_settings(ClickSettings {
+            interval,
+            ..inner.click_settings()
+        });
+    }
+
+    /// Returns the maximum duration between two presses for them to count as a double-click.
+    #[track_caller]
+    pub fn double_click_interval(&self) -> Duration {
+        self.inner().click_settings().interval
+    }
+
+    /// Sets the maximum distance between two presses for them to count as a double-click (or
+    /// more), in scaled pixels.
+    ///
+    /// This defaults to 4 pixels. See [`PointerButton::click_count`].
+    ///
+    /// [`PointerButton::click_count`]: crate::event::PointerButton::click_count
+    #[track_caller]
+    pub fn set_double_click_distance(&self, distance: f64) {
+        let inner = self.inner();
+        inner.set_click_settings(ClickSettings {
+            distance,
+            ..inner.click_settings()
+        });
+    }
+
+    /// Returns the maximum distance between two presses for them to count as a double-click,
+    /// in scaled pixels.
+    #[track_caller]
+    pub fn double_click_distance(&self) -> f64 {
+        self.inner().click_settings().distance
+    }
+
+    /// Replaces the content of the system clipboard with the provided text.
+    ///
+    /// This is what elements should use to implement the copy shortcut.
+    #[track_caller]
+    pub fn set_clipboard_text(&self, text: String) -> Result<(), arboard::Error> {
+        self.inner().set_clipboard_text(text)
+    }
+
+    /// Returns the text currently stored in the system clipboard.
+    ///
+    /// If the clipboard is not available, or if it contains something else than UTF-8 text,
+    /// this function returns `None`.
+    ///
+    /// Elements usually do not need to call this function directly. When the paste shortcut is
+    /// pressed, the content of the clipboard is dispatched as a [`ClipboardPaste`] event.
+    ///
+    /// [`ClipboardPaste`]: crate::event::ClipboardPaste
+    #[track_caller]
+    pub fn clipboard_text(&self) -> Option<String> {
+        self.inner().clipboard_text()
+    }
 }
 
 impl Debug for Ctx {
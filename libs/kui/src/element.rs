@@ -1,12 +1,25 @@
 use {
     crate::{
         Ctx, Window,
+        accessibility::AccessibilityContext,
         event::{Event, EventResult},
     },
-    vello::kurbo::{Point, Size},
+    vello::kurbo::{Point, Rect, Size, Vec2},
 };
 
 /// Contains information about the layout of an element.
+///
+/// # Coordinate space
+///
+/// Elements are laid out in *physical pixels*, relative to the top-left corner of the window's
+/// client area. The positions and sizes passed to [`Element::place`] and the positions carried
+/// by pointer events all use that same space, which means that hit-testing never requires any
+/// conversion.
+///
+/// *Logical pixels* (the unit of [`Length::Pixels`]) only exist while resolving lengths: they
+/// are multiplied by [`scale_factor`](Self::scale_factor) to obtain physical pixels.
+///
+/// [`Length::Pixels`]: crate::elements::Length::Pixels
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct LayoutContext {
     /// The size of the parent element.
@@ -15,6 +28,108 @@ pub struct LayoutContext {
     pub parent: Size,
     /// The scale factor of the element.
     pub scale_factor: f64,
+    /// The size of the window's client area.
+    ///
+    /// Used to resolve viewport-relative lengths.
+    pub viewport: Size,
+    /// The current font size, in physical pixels.
+    ///
+    /// Used to resolve font-relative lengths.
+    pub font_size: f64,
+}
+
+impl LayoutContext {
+    /// The font size of the root element, in logical pixels.
+    pub const DEFAULT_FONT_SIZE: f64 = 16.0;
+
+    /// Creates the layout context of a root element.
+    ///
+    /// # Parameters
+    ///
+    /// - `viewport`: The size of the window's client area, in physical pixels. This is also
+    ///   the size of the parent.
+    ///
+    /// - `scale_factor`: The scale factor of the window.
+    pub fn root(viewport: Size, scale_factor: f64) -> Self {
+        Self {
+            parent: viewport,
+            scale_factor,
+            viewport,
+            font_size: Self::DEFAULT_FONT_SIZE * scale_factor,
+        }
+    }
+
+    /// Returns a copy of this layout context with a different parent size.
+    #[inline]
+    pub fn with_parent(self, parent: Size) -> Self {
+        Self { parent, ..self }
+    }
+
+    /// Returns a copy of this layout context with a different font size.
+    ///
+    /// The font size is expressed in physical pixels.
+    #[inline]
+    pub fn with_font_size(self, font_size: f64) -> Self {
+        Self { font_size, ..self }
+    }
+
+    /// Converts a value expressed in logical pixels to physical pixels.
+    #[inline]
+    pub fn logical_to_physical<T: Scalable>(&self, value: T) -> T {
+        value.scaled(self.scale_factor)
+    }
+
+    /// Converts a value expressed in physical pixels to logical pixels.
+    #[inline]
+    pub fn physical_to_logical<T: Scalable>(&self, value: T) -> T {
+        value.scaled(self.scale_factor.recip())
+    }
+}
+
+/// A geometric value that can be converted between logical and physical pixels.
+pub trait Scalable {
+    /// Multiplies the value by the provided factor.
+    fn scaled(self, factor: f64) -> Self;
+}
+
+impl Scalable for f64 {
+    #[inline]
+    fn scaled(self, factor: f64) -> Self {
+        self * factor
+    }
+}
+
+impl Scalable for Point {
+    #[inline]
+    fn scaled(self, factor: f64) -> Self {
+        Point::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl Scalable for Vec2 {
+    #[inline]
+    fn scaled(self, factor: f64) -> Self {
+        self * factor
+    }
+}
+
+impl Scalable for Size {
+    #[inline]
+    fn scaled(self, factor: f64) -> Self {
+        self * factor
+    }
+}
+
+impl Scalable for Rect {
+    #[inline]
+    fn scaled(self, factor: f64) -> Self {
+        Rect::new(
+            self.x0 * factor,
+            self.y0 * factor,
+            self.x1 * factor,
+            self.y1 * factor,
+        )
+    }
 }
 
 /// Represents the size that an element may be.
@@ -58,6 +173,31 @@ pub struct ElemContext {
     pub window: Window,
 }
 
+impl ElemContext {
+    /// Requests the keyboard focus for the element currently handling an event.
+    ///
+    /// The focus is given to the closest [`Focusable`] ancestor of the element once the event
+    /// has been dispatched, and removed from the element that previously had it. The request is
+    /// ignored if the element has no [`Focusable`] ancestor.
+    ///
+    /// [`Focusable`]: crate::elements::focusable::Focusable
+    #[inline]
+    pub fn request_focus(&self) {
+        self.window.request_focus();
+    }
+
+    /// Returns whether the closest [`Focusable`] ancestor of the element currently being
+    /// visited has the keyboard focus.
+    ///
+    /// This is always `false` for elements that have no [`Focusable`] ancestor.
+    ///
+    /// [`Focusable`]: crate::elements::focusable::Focusable
+    #[inline]
+    pub fn has_focus(&self) -> bool {
+        self.window.in_focus_scope()
+    }
+}
+
 /// Represents a single element in the UI.
 ///
 /// UI elements are the building blocks of the UI tree. They can be laid out, drawn, and respond to
@@ -182,6 +322,19 @@ pub trait Element {
     /// Called when the element is added to the UI tree.
     fn begin(&mut self, elem_context: &ElemContext) {}
 
+    /// Describes the element to assistive technologies.
+    ///
+    /// Elements that are meaningful to the user (buttons, text, inputs, etc.) should push a node
+    /// to the provided context. Other elements should forward the call to their children. See
+    /// the [`accessibility`](crate::accessibility) module for more information.
+    ///
+    /// # Requirements
+    ///
+    /// This function must be called after the element has been laid out and placed through
+    /// [`place`](Element::place).
+    #[inline]
+    fn accessibility(&self, cx: &mut AccessibilityContext) {}
+
     #[doc(hidden)]
     #[inline]
     fn __private_implementation_detail_do_not_use(&self) -> bool {
@@ -191,6 +344,171 @@ pub trait Element {
 
 impl Element for () {}
 
+/// Extension methods for [`Element`] implementations.
+pub trait ElementExt: Sized + Element {
+    /// Overrides the tab index of the focusable elements within this element.
+    ///
+    /// See [`TabIndex`] for more information.
+    ///
+    /// [`TabIndex`]: crate::elements::tab_index::TabIndex
+    #[inline]
+    fn with_tab_index(self, tab_index: i32) -> crate::elements::tab_index::TabIndex<Self> {
+        crate::elements::tab_index::TabIndex::new(tab_index, self)
+    }
+
+    /// Makes this element able to receive the keyboard focus.
+    ///
+    /// See [`Focusable`] for more information.
+    ///
+    /// [`Focusable`]: crate::elements::focusable::Focusable
+    #[inline]
+    fn focusable(self) -> crate::elements::focusable::Focusable<Self> {
+        crate::elements::focusable::Focusable::new(self)
+    }
+
+    /// Gives an accessible name to this element.
+    ///
+    /// See [`AccessibleLabel`] for more information.
+    ///
+    /// [`AccessibleLabel`]: crate::elements::accessible_label::AccessibleLabel
+    #[inline]
+    fn with_label(
+        self,
+        label: impl Into<String>,
+    ) -> crate::elements::accessible_label::AccessibleLabel<Self> {
+        crate::elements::accessible_label::AccessibleLabel::new(self).label(label)
+    }
+
+    /// Gives an accessible description to this element.
+    ///
+    /// See [`AccessibleLabel`] for more information.
+    ///
+    /// [`AccessibleLabel`]: crate::elements::accessible_label::AccessibleLabel
+    #[inline]
+    fn with_description(
+        self,
+        description: impl Into<String>,
+    ) -> crate::elements::accessible_label::AccessibleLabel<Self> {
+        crate::elements::accessible_label::AccessibleLabel::new(self).description(description)
+    }
+
+    /// Constrains the minimum width of this element.
+    ///
+    /// See [`WithSizeConstraints`] for more information.
+    ///
+    /// [`WithSizeConstraints`]: crate::elements::size_constraints::WithSizeConstraints
+    #[inline]
+    fn with_min_width(
+        self,
+        min_width: crate::elements::Length,
+    ) -> crate::elements::size_constraints::WithSizeConstraints<Self> {
+        crate::elements::size_constraints::WithSizeConstraints::new(self).with_min_width(min_width)
+    }
+
+    /// Constrains the minimum height of this element.
+    ///
+    /// See [`WithSizeConstraints`] for more information.
+    ///
+    /// [`WithSizeConstraints`]: crate::elements::size_constraints::WithSizeConstraints
+    #[inline]
+    fn with_min_height(
+        self,
+        min_height: crate::elements::Length,
+    ) -> crate::elements::size_constraints::WithSizeConstraints<Self> {
+        crate::elements::size_constraints::WithSizeConstraints::new(self)
+            .with_min_height(min_height)
+    }
+
+    /// Constrains the maximum width of this element.
+    ///
+    /// See [`WithSizeConstraints`] for more information.
+    ///
+    /// [`WithSizeConstraints`]: crate::elements::size_constraints::WithSizeConstraints
+    #[inline]
+    fn with_max_width(
+        self,
+        max_width: crate::elements::Length,
+    ) -> crate::elements::size_constraints::WithSizeConstraints<Self> {
+        crate::elements::size_constraints::WithSizeConstraints::new(self).with_max_width(max_width)
+    }
+
+    /// Constrains the maximum height of this element.
+    ///
+    /// See [`WithSizeConstraints`] for more information.
+    ///
+    /// [`WithSizeConstraints`]: crate::elements::size_constraints::WithSizeConstraints
+    #[inline]
+    fn with_max_height(
+        self,
+        max_height: crate::elements::Length,
+    ) -> crate::elements::size_constraints::WithSizeConstraints<Self> {
+        crate::elements::size_constraints::WithSizeConstraints::new(self)
+            .with_max_height(max_height)
+    }
+
+    /// Constrains the minimum size of this element.
+    ///
+    /// See [`WithSizeConstraints`] for more information.
+    ///
+    /// [`WithSizeConstraints`]: crate::elements::size_constraints::WithSizeConstraints
+    #[inline]
+    fn with_min_size(
+        self,
+        width: crate::elements::Length,
+        height: crate::elements::Length,
+    ) -> crate::elements::size_constraints::WithSizeConstraints<Self> {
+        crate::elements::size_constraints::WithSizeConstraints::new(self)
+            .with_min_size(width, height)
+    }
+
+    /// Constrains the maximum size of this element.
+    ///
+    /// See [`WithSizeConstraints`] for more information.
+    ///
+    /// [`WithSizeConstraints`]: crate::elements::size_constraints::WithSizeConstraints
+    #[inline]
+    fn with_max_size(
+        self,
+        width: crate::elements::Length,
+        height: crate::elements::Length,
+    ) -> crate::elements::size_constraints::WithSizeConstraints<Self> {
+        crate::elements::size_constraints::WithSizeConstraints::new(self)
+            .with_max_size(width, height)
+    }
+
+    /// Calls the provided function whenever the size of this element changes.
+    ///
+    /// The function is called during the first layout pass, and then only when the size given
+    /// to the element differs from the previous pass. See [`HookResize`] for more information.
+    ///
+    /// [`HookResize`]: crate::elements::hooks::HookResize
+    #[inline]
+    fn on_resize<F>(self, on_resize: F) -> crate::elements::hooks::HookResize<F, Self>
+    where
+        F: FnMut(&ElemContext, Size),
+    {
+        crate::elements::hooks::HookResize::new(on_resize, self)
+    }
+
+    /// Calls the provided function whenever the pointer enters or leaves this element.
+    ///
+    /// The element also receives [`PointerEnter`] and [`PointerLeave`] events. See
+    /// [`HookHover`] for more information.
+    ///
+    /// [`HookHover`]: crate::elements::hooks::HookHover
+    /// [`PointerEnter`]: crate::event::PointerEnter
+    /// [`PointerLeave`]: crate::event::PointerLeave
+    #[inline]
+    fn on_hover<F>(self, on_hover: F) -> crate::elements::hooks::HookHover<F, Self>
+    where
+        F: FnMut(&ElemContext, bool),
+    {
+        crate::elements::hooks::HookHover::new(on_hover, self)
+    }
+}
+
+impl<E: Element> ElementExt for E {}
+
 /// Types that can be turned into an [`Element`].
 pub trait IntoElement {
     /// The output element type.
@@ -208,3 +526,44 @@ impl<E: Element> IntoElement for E {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::Element,
+        crate::{
+            HeadlessRenderer,
+            elements::{Length, div, flex},
+            test_utils::Probe,
+        },
+        vello::kurbo::{Point, Rect, Size},
+    };
+
+    #[test]
+    fn click_hits_element_at_fractional_scale_factor() {
+        let (left, left_rect) = Probe::new(0.0, 0.0);
+        let (right, right_rect) = Probe::new(0.0, 0.0);
+
+        let cell = |probe| {
+            div()
+                .width(Length::Pixels(40.0))
+                .height(Length::Pixels(20.0))
+                .child(probe)
+        };
+        let mut root = flex().horizontal().child(cell(left)).child(cell(right));
+
+        let renderer = HeadlessRenderer::new();
+        let (_, layout_context) = renderer.place_element(&mut root, Size::new(300.0, 300.0), 1.5);
+
+        assert_eq!(left_rect.get(), Rect::new(0.0, 0.0, 60.0, 30.0));
+        assert_eq!(right_rect.get(), Rect::new(60.0, 0.0, 120.0, 30.0));
+
+        // A click 50 logical pixels from the left edge lands in the second cell, although the
+        // first cell is 40 logical pixels wide.
+        let click = layout_context.logical_to_physical(Point::new(50.0, 10.0));
+        assert_eq!(click, Point::new(75.0, 15.0));
+        assert!(root.hit_test(click));
+        assert!(!left_rect.get().contains(click));
+        assert!(right_rect.get().contains(click));
+    }
+}
@@ -0,0 +1,124 @@
+use {
+    crate::{
+        ElemContext, Element, LayoutContext, SizeHint,
+        accessibility::AccessibilityContext,
+        event::{Event, EventResult},
+    },
+    vello::kurbo::{Point, Size},
+};
+
+/// An element that gives an accessible name and description to its child.
+///
+/// This is mostly useful for interactive elements that have no textual content, such as
+/// buttons that only contain an icon. The label and description are applied to the first
+/// accessibility node pushed by the child (see the [`accessibility`](crate::accessibility)
+/// module).
+///
+/// Instances of this type are usually created through
+/// [`ElementExt::with_label`](crate::ElementExt::with_label) and
+/// [`ElementExt::with_description`](crate::ElementExt::with_description).
+#[derive(Clone, Debug, Default)]
+pub struct AccessibleLabel<E: ?Sized> {
+    /// The accessible name of the child element.
+    label: Option<String>,
+    /// The accessible description of the child element.
+    description: Option<String>,
+    /// The child element.
+    pub child: E,
+}
+
+impl<E> AccessibleLabel<E> {
+    /// Creates a new [`AccessibleLabel`] element with no label and no description.
+    #[inline]
+    pub fn new(child: E) -> Self {
+        Self {
+            label: None,
+            description: None,
+            child,
+        }
+    }
+
+    /// Sets the accessible name of the child element.
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Sets the accessible description of the child element.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+}
+
+impl<E: ?Sized> AccessibleLabel<E> {
+    /// Returns the accessible name of the child element.
+    #[inline]
+    pub fn get_label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    /// Returns the accessible description of the child element.
+    #[inline]
+    pub fn get_description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Returns the text that a tooltip for the child element should display when no tooltip
+    /// text was explicitly provided.
+    ///
+    /// This is the description if there is one, and the label otherwise.
+    #[inline]
+    pub fn tooltip_text(&self) -> Option<&str> {
+        self.get_description().or(self.get_label())
+    }
+}
+
+impl<E: ?Sized + Element> Element for AccessibleLabel<E> {
+    #[inline]
+    fn size_hint(
+        &mut self,
+        elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        space: Size,
+    ) -> SizeHint {
+        self.child.size_hint(elem_context, layout_context, space)
+    }
+
+    #[inline]
+    fn place(
+        &mut self,
+        elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        pos: Point,
+        size: Size,
+    ) {
+        self.child.place(elem_context, layout_context, pos, size);
+    }
+
+    #[inline]
+    fn hit_test(&self, point: Point) -> bool {
+        self.child.hit_test(point)
+    }
+
+    #[inline]
+    fn draw(&mut self, elem_context: &ElemContext, scene: &mut vello::Scene) {
+        self.child.draw(elem_context, scene);
+    }
+
+    #[inline]
+    fn event(&mut self, elem_context: &ElemContext, event: &dyn Event) -> EventResult {
+        self.child.event(elem_context, event)
+    }
+
+    #[inline]
+    fn begin(&mut self, elem_context: &ElemContext) {
+        self.child.begin(elem_context);
+    }
+
+    fn accessibility(&self, cx: &mut AccessibilityContext) {
+        cx.with_metadata(self.label.as_deref(), self.description.as_deref(), |cx| {
+            self.child.accessibility(cx)
+        });
+    }
+}
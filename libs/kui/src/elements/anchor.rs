@@ -1,7 +1,8 @@
 use {
-    super::Length,
+    super::{Length, flex::Direction},
     crate::{
         ElemContext, Element, LayoutContext, SizeHint,
+        accessibility::AccessibilityContext,
         event::{Event, EventResult},
     },
     core::f64,
@@ -79,14 +80,9 @@ impl<E: ?Sized + Element> Element for Anchor<E> {
         layout_context: LayoutContext,
         space: Size,
     ) -> SizeHint {
-        let child_size_hint = self.child.size_hint(
-            elem_context,
-            LayoutContext {
-                parent: space,
-                scale_factor: layout_context.scale_factor,
-            },
-            space,
-        );
+        let child_size_hint =
+            self.child
+                .size_hint(elem_context, layout_context.with_parent(space), space);
 
         SizeHint {
             preferred: child_size_hint.preferred,
@@ -102,16 +98,19 @@ impl<E: ?Sized + Element> Element for Anchor<E> {
         pos: Point,
         size: Size,
     ) {
-        let child_layout_context = LayoutContext {
-            parent: size,
-            scale_factor: layout_context.scale_factor,
-        };
+        let child_layout_context = layout_context.with_parent(size);
         let child_size_hint = self
             .child
             .size_hint(elem_context, child_layout_context, size);
 
-        let offset_x = self.style.offset_x.resolve(&layout_context);
-        let offset_y = self.style.offset_y.resolve(&layout_context);
+        let offset_x = self
+            .style
+            .offset_x
+            .resolve_along(&layout_context, Direction::Horizontal);
+        let offset_y = self
+            .style
+            .offset_y
+            .resolve_along(&layout_context, Direction::Vertical);
 
         let child_size = child_size_hint.preferred;
 
@@ -147,4 +146,9 @@ impl<E: ?Sized + Element> Element for Anchor<E> {
     fn begin(&mut self, elem_context: &ElemContext) {
         self.child.begin(elem_context);
     }
+
+    #[inline]
+    fn accessibility(&self, cx: &mut AccessibilityContext) {
+        self.child.accessibility(cx);
+    }
 }
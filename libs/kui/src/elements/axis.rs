@@ -0,0 +1,427 @@
+use {
+    super::{
+        Length,
+        flex::Direction,
+        text::{Text, UniformStyle},
+    },
+    crate::{ElemContext, Element, LayoutContext, SizeHint},
+    vello::{
+        Scene,
+        kurbo::{Affine, Line, Point, Rect, Size, Stroke},
+        peniko::{Brush, Color},
+    },
+};
+
+/// Maps values to positions along an [`Axis`].
+pub trait AxisMapping {
+    /// Returns the position of `value` along the axis.
+    ///
+    /// The returned position is a fraction of the length of the axis. `0.0` is the start of the
+    /// axis (the left edge of horizontal axes, the bottom edge of vertical axes) and `1.0` is its
+    /// end. Values outside of this range are not drawn.
+    fn map(&self, value: f64) -> f64;
+}
+
+impl<F: Fn(f64) -> f64> AxisMapping for F {
+    #[inline]
+    fn map(&self, value: f64) -> f64 {
+        self(value)
+    }
+}
+
+/// An [`AxisMapping`] that maps a range of values linearly.
+///
+/// This is usually used for decibels.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LinearScale {
+    /// The value at the start of the axis.
+    pub min: f64,
+    /// The value at the end of the axis.
+    pub max: f64,
+}
+
+impl LinearScale {
+    /// Creates a new [`LinearScale`].
+    #[inline]
+    pub const fn new(min: f64, max: f64) -> Self {
+        Self { min, max }
+    }
+}
+
+impl AxisMapping for LinearScale {
+    #[inline]
+    fn map(&self, value: f64) -> f64 {
+        (value - self.min) / (self.max - self.min)
+    }
+}
+
+/// An [`AxisMapping`] that maps a range of values logarithmically.
+///
+/// This is usually used for frequencies. Both ends of the range must be strictly positive.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LogScale {
+    /// The value at the start of the axis.
+    pub min: f64,
+    /// The value at the end of the axis.
+    pub max: f64,
+}
+
+impl LogScale {
+    /// Creates a new [`LogScale`].
+    #[inline]
+    pub const fn new(min: f64, max: f64) -> Self {
+        Self { min, max }
+    }
+}
+
+impl AxisMapping for LogScale {
+    #[inline]
+    fn map(&self, value: f64) -> f64 {
+        (value / self.min).ln() / (self.max / self.min).ln()
+    }
+}
+
+/// Returns evenly spaced decibel ticks between `min` and `max` (inclusive).
+///
+/// Labels are formatted as `"-12 dB"`.
+pub fn decibel_ticks(min: f64, max: f64, step: f64) -> impl Iterator<Item = (f64, String)> {
+    let first = (min / step).ceil() as i64;
+    let last = (max / step).floor() as i64;
+    (first..=last).map(move |i| {
+        let value = i as f64 * step;
+        (value, format!("{value} dB"))
+    })
+}
+
+/// Returns the usual frequency ticks (1, 2 and 5 times every power of ten) between `min` and
+/// `max` (inclusive).
+///
+/// Labels are formatted as `"50"`, `"200"`, `"1k"` or `"20k"`.
+pub fn frequency_ticks(min: f64, max: f64) -> impl Iterator<Item = (f64, String)> {
+    let first_decade = min.max(f64::MIN_POSITIVE).log10().floor() as i32;
+    let last_decade = max.log10().ceil() as i32;
+
+    (first_decade..=last_decade)
+        .flat_map(|decade| [1.0, 2.0, 5.0].map(|m| m * 10f64.powi(decade)))
+        .filter(move |&value| value >= min && value <= max)
+        .map(|value| {
+            let label = if value >= 1000.0 {
+                format!("{}k", value / 1000.0)
+            } else {
+                format!("{value}")
+            };
+            (value, label)
+        })
+}
+
+/// A gridline drawn by an [`Axis`] element.
+struct Tick {
+    /// The value at which the line is drawn.
+    value: f64,
+    /// The label of the line.
+    label: Option<Text<UniformStyle>>,
+
+    /// The position of the line along the axis, in pixels, computed when the element is placed.
+    ///
+    /// This is `None` if the value is outside of the axis.
+    position: Option<f64>,
+    /// Whether the label is drawn.
+    ///
+    /// Labels that would overlap with the label of a previous line are skipped.
+    label_visible: bool,
+}
+
+/// An element that draws labeled gridlines, such as the decibel scale of a level meter or the
+/// frequency scale of an equalizer.
+///
+/// Horizontal axes draw vertical lines, with values increasing from left to right and labels at
+/// the bottom. Vertical axes draw horizontal lines, with values increasing from bottom to top
+/// and labels on the left.
+///
+/// The element takes all the space it is given. It is usually stacked below the content it
+/// describes.
+pub struct Axis<M> {
+    /// The direction along which values increase.
+    pub direction: Direction,
+    /// The function that maps values to positions along the axis.
+    pub mapping: M,
+
+    /// The brush used to draw the gridlines.
+    pub line_brush: Brush,
+    /// The thickness of the gridlines.
+    pub line_thickness: Length,
+    /// The brush used to draw the labels.
+    pub label_brush: Brush,
+    /// The font size of the labels.
+    pub font_size: Length,
+    /// The minimum distance between two labels.
+    ///
+    /// A label that would be closer than this to the previous one is not drawn.
+    pub label_spacing: Length,
+
+    /// The gridlines of the axis.
+    ticks: Vec<Tick>,
+    /// The bounds of the element, computed when it is placed.
+    bounds: Rect,
+    /// The resolved thickness of the gridlines.
+    resolved_line_thickness: f64,
+}
+
+impl<M> Axis<M> {
+    /// Creates a new [`Axis`] element with the provided mapping.
+    pub fn new(mapping: M) -> Self {
+        Self {
+            direction: Direction::Horizontal,
+            mapping,
+            line_brush: Color::from_rgba8(0xff, 0xff, 0xff, 0x22).into(),
+            line_thickness: Length::Pixels(1.0),
+            label_brush: Color::from_rgb8(0x88, 0x88, 0x88).into(),
+            font_size: Length::Pixels(10.0),
+            label_spacing: Length::Pixels(4.0),
+            ticks: Vec::new(),
+            bounds: Rect::ZERO,
+            resolved_line_thickness: 0.0,
+        }
+    }
+
+    /// Sets the direction along which values increase.
+    pub fn direction(mut self, direction: Direction) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Makes the values increase from left to right.
+    pub fn horizontal(self) -> Self {
+        self.direction(Direction::Horizontal)
+    }
+
+    /// Makes the values increase from bottom to top.
+    pub fn vertical(self) -> Self {
+        self.direction(Direction::Vertical)
+    }
+
+    /// Sets the function that maps values to positions along the axis.
+    pub fn mapping<M2>(self, mapping: M2) -> Axis<M2> {
+        Axis {
+            direction: self.direction,
+            mapping,
+            line_brush: self.line_brush,
+            line_thickness: self.line_thickness,
+            label_brush: self.label_brush,
+            font_size: self.font_size,
+            label_spacing: self.label_spacing,
+            ticks: self.ticks,
+            bounds: self.bounds,
+            resolved_line_thickness: self.resolved_line_thickness,
+        }
+    }
+
+    /// Sets the brush used to draw the gridlines.
+    pub fn line_brush(mut self, brush: impl Into<Brush>) -> Self {
+        self.line_brush = brush.into();
+        self
+    }
+
+    /// Sets the thickness of the gridlines.
+    pub fn line_thickness(mut self, thickness: Length) -> Self {
+        self.line_thickness = thickness;
+        self
+    }
+
+    /// Sets the brush used to draw the labels.
+    ///
+    /// This only affects labels added after this call.
+    pub fn label_brush(mut self, brush: impl Into<Brush>) -> Self {
+        self.label_brush = brush.into();
+        self
+    }
+
+    /// Sets the font size of the labels.
+    ///
+    /// This only affects labels added after this call.
+    pub fn font_size(mut self, size: Length) -> Self {
+        self.font_size = size;
+        self
+    }
+
+    /// Sets the minimum distance between two labels.
+    pub fn label_spacing(mut self, spacing: Length) -> Self {
+        self.label_spacing = spacing;
+        self
+    }
+
+    /// Adds a gridline without a label.
+    pub fn line(mut self, value: f64) -> Self {
+        self.push_tick(value, None);
+        self
+    }
+
+    /// Adds a labeled gridline.
+    pub fn tick(mut self, value: f64, label: impl Into<String>) -> Self {
+        self.push_tick(value, Some(label.into()));
+        self
+    }
+
+    /// Adds multiple labeled gridlines.
+    ///
+    /// See [`decibel_ticks`] and [`frequency_ticks`] for common sets of ticks.
+    pub fn ticks<S: Into<String>>(mut self, ticks: impl IntoIterator<Item = (f64, S)>) -> Self {
+        for (value, label) in ticks {
+            self.push_tick(value, Some(label.into()));
+        }
+        self
+    }
+
+    /// Removes all the gridlines of the axis.
+    pub fn clear_ticks(&mut self) {
+        self.ticks.clear();
+    }
+
+    /// Adds a gridline to the axis.
+    fn push_tick(&mut self, value: f64, label: Option<String>) {
+        let label = label.map(|text| {
+            Text::default()
+                .text(text)
+                .inline(true)
+                .brush(self.label_brush.clone())
+                .font_size(self.font_size.clone())
+        });
+
+        self.ticks.push(Tick {
+            value,
+            label,
+            position: None,
+            label_visible: false,
+        });
+    }
+
+    /// Returns the point at which the line at `position` meets the edge where labels are drawn.
+    fn line_origin(&self, position: f64) -> Point {
+        match self.direction {
+            Direction::Horizontal => Point::new(self.bounds.x0 + position, self.bounds.y1),
+            Direction::Vertical => Point::new(self.bounds.x0, self.bounds.y1 - position),
+        }
+    }
+}
+
+impl<M: AxisMapping> Element for Axis<M> {
+    fn size_hint(
+        &mut self,
+        _elem_context: &ElemContext,
+        _layout_context: LayoutContext,
+        space: Size,
+    ) -> SizeHint {
+        let finite_or_zero = |f: f64| if f.is_finite() { f } else { 0.0 };
+
+        SizeHint {
+            preferred: Size::new(finite_or_zero(space.width), finite_or_zero(space.height)),
+            min: Size::ZERO,
+            max: Size::new(f64::INFINITY, f64::INFINITY),
+        }
+    }
+
+    fn place(
+        &mut self,
+        elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        pos: Point,
+        size: Size,
+    ) {
+        self.bounds = Rect::from_origin_size(pos, size);
+        self.resolved_line_thickness = self.line_thickness.resolve(&layout_context);
+        let label_spacing = self.label_spacing.resolve(&layout_context);
+
+        let length = match self.direction {
+            Direction::Horizontal => size.width,
+            Direction::Vertical => size.height,
+        };
+
+        // Place the ticks in order of increasing position, so that overlapping labels can be
+        // detected by only looking at the previous label.
+        for tick in &mut self.ticks {
+            let t = self.mapping.map(tick.value);
+            tick.position = (0.0..=1.0).contains(&t).then_some(t * length);
+        }
+        self.ticks.sort_by(|a, b| {
+            f64::total_cmp(&a.position.unwrap_or(-1.0), &b.position.unwrap_or(-1.0))
+        });
+
+        let mut previous_label_end = f64::NEG_INFINITY;
+        for i in 0..self.ticks.len() {
+            let Some(position) = self.ticks[i].position else {
+                self.ticks[i].label_visible = false;
+                continue;
+            };
+
+            let origin = self.line_origin(position);
+            let bounds = self.bounds;
+            let direction = self.direction;
+            let tick = &mut self.ticks[i];
+            let Some(label) = &mut tick.label else {
+                continue;
+            };
+
+            let label_size = label
+                .size_hint(
+                    elem_context,
+                    layout_context,
+                    Size::new(f64::INFINITY, f64::INFINITY),
+                )
+                .preferred;
+
+            // Center the label on its line, but keep it within the bounds of the element.
+            let (label_pos, start, end) = match direction {
+                Direction::Horizontal => {
+                    let x = (origin.x - label_size.width * 0.5)
+                        .min(bounds.x1 - label_size.width)
+                        .max(bounds.x0);
+                    let y = bounds.y1 - label_size.height;
+                    (Point::new(x, y), x, x + label_size.width)
+                }
+                Direction::Vertical => {
+                    let y = (origin.y - label_size.height * 0.5)
+                        .min(bounds.y1 - label_size.height)
+                        .max(bounds.y0);
+                    // Values increase upwards, so the "start" of the label along the axis is
+                    // its bottom edge.
+                    let start = bounds.y1 - (y + label_size.height);
+                    (Point::new(bounds.x0, y), start, start + label_size.height)
+                }
+            };
+
+            tick.label_visible = start >= previous_label_end + label_spacing;
+            if tick.label_visible {
+                previous_label_end = end;
+                label.place(elem_context, layout_context, label_pos, label_size);
+            }
+        }
+    }
+
+    fn draw(&mut self, elem_context: &ElemContext, scene: &mut Scene) {
+        let stroke = Stroke::new(self.resolved_line_thickness);
+
+        for tick in &self.ticks {
+            let Some(position) = tick.position else {
+                continue;
+            };
+
+            let origin = self.line_origin(position);
+            let line = match self.direction {
+                Direction::Horizontal => Line::new(origin, (origin.x, self.bounds.y0)),
+                Direction::Vertical => Line::new(origin, (self.bounds.x1, origin.y)),
+            };
+
+            scene.stroke(&stroke, Affine::IDENTITY, &self.line_brush, None, &line);
+        }
+
+        for tick in &mut self.ticks {
+            if !tick.label_visible {
+                continue;
+            }
+
+            if let Some(label) = &mut tick.label {
+                label.draw(elem_context, scene);
+            }
+        }
+    }
+}
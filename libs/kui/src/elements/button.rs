@@ -2,9 +2,11 @@ use {
     super::interactive::InteractiveState,
     crate::{
         ElemContext, Element, LayoutContext, SizeHint,
+        accessibility::{AccessibilityContext, NodeIdCell},
         elements::interactive::Appearance,
         event::{Event, EventResult},
     },
+    accesskit::{Action, Node, Role},
     vello::{
         Scene,
         kurbo::{Point, Size},
@@ -15,6 +17,8 @@ use {
 #[derive(Clone, Debug, Default)]
 pub struct Button<A: ?Sized> {
     state: InteractiveState,
+    /// The identifier of the node of the button in the accessibility tree.
+    node_id: NodeIdCell,
 
     /// Whether to act on press.
     ///
@@ -30,6 +34,7 @@ impl<A> Button<A> {
         Self {
             act_on_press: false,
             state: InteractiveState::empty(),
+            node_id: NodeIdCell::default(),
             appearance,
         }
     }
@@ -45,6 +50,7 @@ impl<A> Button<A> {
         Button {
             act_on_press: self.act_on_press,
             state: self.state,
+            node_id: self.node_id,
             appearance,
         }
     }
@@ -98,11 +104,16 @@ where
         self.state.remove_transient_states();
 
         let og_state = self.state;
+        self.state.handle_focus_traversal(event);
+        let action = self
+            .state
+            .handle_accessibility_action(elem_context, &self.node_id, event);
         let event_result = self
             .state
             .handle_pointer_interactions(&mut |pt| self.appearance.hit_test(pt), event);
         if (self.act_on_press && self.state.just_pressed())
             || (!self.act_on_press && self.state.just_clicked())
+            || action == Some(Action::Click)
         {
             self.state.insert(InteractiveState::VALUE_CHANGED);
         }
@@ -120,4 +131,19 @@ where
         self.appearance.begin(elem_context);
         self.appearance.state_changed(elem_context, self.state, &());
     }
+
+    fn accessibility(&self, cx: &mut AccessibilityContext) {
+        let mut node = Node::new(Role::Button);
+        node.add_action(Action::Focus);
+        node.add_action(Action::Click);
+        if self.state.disabled() {
+            node.set_disabled();
+        }
+
+        let id = cx.push_with_children(node, |cx| self.appearance.accessibility(cx));
+        self.node_id.set(id);
+        if self.state.focused() {
+            cx.set_focus(id);
+        }
+    }
 }
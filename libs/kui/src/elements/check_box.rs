@@ -0,0 +1,449 @@
+use {
+    crate::{
+        ElemContext, Element, LayoutContext, SizeHint,
+        accessibility::{AccessibilityContext, NodeIdCell, to_accesskit_rect},
+        elements::{Length, interactive::InteractiveState},
+        event::{Event, EventResult, KeyEvent},
+    },
+    accesskit::{Action, Node, Role, Toggled},
+    std::time::Instant,
+    vello::{
+        kurbo::{Affine, BezPath, Cap, Join, Point, Rect, RoundedRect, Size, Stroke},
+        peniko::{Brush, Color, Fill, Mix},
+    },
+    winit::keyboard::{Key, NamedKey},
+};
+
+/// The rate at which the check mark animates towards its target state, in units per second.
+///
+/// With this rate, the animation is visually complete after roughly 150 milliseconds.
+const CHECK_ANIMATION_RATE: f64 = 30.0;
+
+/// The points of the check mark, relative to the bounds of the box.
+const CHECK_MARK: [(f64, f64); 3] = [(0.24, 0.52), (0.42, 0.70), (0.76, 0.32)];
+
+/// The function called by a [`CheckBox`] when it is toggled.
+pub trait OnToggle {
+    /// The check box has been toggled.
+    fn on_toggle(&mut self, elem_context: &ElemContext, checked: bool);
+}
+
+impl OnToggle for () {
+    #[inline]
+    fn on_toggle(&mut self, _elem_context: &ElemContext, _checked: bool) {}
+}
+
+impl<F> OnToggle for F
+where
+    F: FnMut(&ElemContext, bool),
+{
+    #[inline]
+    fn on_toggle(&mut self, elem_context: &ElemContext, checked: bool) {
+        self(elem_context, checked)
+    }
+}
+
+/// Moves `current` towards `target`, independently of the frame rate.
+///
+/// `rate` is expressed in units per second and `dt` in seconds.
+fn exp_decay(current: f64, target: f64, rate: f64, dt: f64) -> f64 {
+    target + (current - target) * (-rate * dt).exp()
+}
+
+/// An element that lets the user toggle a boolean value.
+///
+/// The check box is toggled when clicked, or when Space or Enter is pressed while it is
+/// focused.
+#[derive(Clone, Debug)]
+pub struct CheckBox<F: ?Sized> {
+    /// The state of the interactive element.
+    pub state: InteractiveState,
+    /// The identifier of the node of the check box in the accessibility tree.
+    node_id: NodeIdCell,
+
+    /// Whether the check box is checked.
+    checked: bool,
+
+    /// The size of the box.
+    pub size: Length,
+    /// The radius of the corners of the box.
+    pub radius: Length,
+    /// The thickness of the border of the box.
+    pub border_thickness: Length,
+    /// The brush used to fill the box when it is unchecked.
+    pub box_brush: Brush,
+    /// The brush used to draw the border of the box.
+    pub border_brush: Brush,
+    /// The brush used to fill the box when it is checked.
+    pub checked_brush: Brush,
+    /// The brush used to draw the check mark.
+    pub mark_brush: Brush,
+
+    /// The bounds of the check box.
+    rect: Rect,
+    /// The resolved radius of the corners of the box.
+    computed_radius: f64,
+    /// The resolved thickness of the border of the box.
+    computed_border_thickness: f64,
+    /// The progress of the check animation, from `0` (unchecked) to `1` (checked).
+    progress: f64,
+    /// The last time the check box was drawn while animating.
+    last_frame: Option<Instant>,
+
+    /// The function called when the check box is toggled.
+    pub on_toggle: F,
+}
+
+impl Default for CheckBox<()> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(())
+    }
+}
+
+impl<F> CheckBox<F> {
+    /// Creates a new [`CheckBox`] element.
+    ///
+    /// The check box is unchecked by default.
+    pub fn new(on_toggle: F) -> Self {
+        Self {
+            state: InteractiveState::empty(),
+            node_id: NodeIdCell::default(),
+            checked: false,
+            size: Length::Pixels(16.0),
+            radius: Length::Pixels(3.0),
+            border_thickness: Length::Pixels(1.0),
+            box_brush: Color::from_rgb8(0x2a, 0x2a, 0x2a).into(),
+            border_brush: Color::from_rgb8(0x55, 0x55, 0x55).into(),
+            checked_brush: Color::from_rgb8(0x5a, 0x8d, 0xee).into(),
+            mark_brush: Color::WHITE.into(),
+            rect: Rect::ZERO,
+            computed_radius: 0.0,
+            computed_border_thickness: 0.0,
+            progress: 0.0,
+            last_frame: None,
+            on_toggle,
+        }
+    }
+
+    /// Sets the function called when the check box is toggled.
+    ///
+    /// The function receives the new state of the check box.
+    pub fn on_toggle<F2>(self, on_toggle: F2) -> CheckBox<F2>
+    where
+        F2: FnMut(&ElemContext, bool),
+    {
+        CheckBox {
+            state: self.state,
+            node_id: self.node_id,
+            checked: self.checked,
+            size: self.size,
+            radius: self.radius,
+            border_thickness: self.border_thickness,
+            box_brush: self.box_brush,
+            border_brush: self.border_brush,
+            checked_brush: self.checked_brush,
+            mark_brush: self.mark_brush,
+            rect: self.rect,
+            computed_radius: self.computed_radius,
+            computed_border_thickness: self.computed_border_thickness,
+            progress: self.progress,
+            last_frame: self.last_frame,
+            on_toggle,
+        }
+    }
+
+    /// Sets whether the check box is initially checked.
+    ///
+    /// The check box does not animate to its initial state.
+    pub fn checked(mut self, yes: bool) -> Self {
+        self.checked = yes;
+        self.progress = if yes { 1.0 } else { 0.0 };
+        self
+    }
+
+    /// Sets the size of the box.
+    pub fn size(mut self, size: Length) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Sets the radius of the corners of the box.
+    pub fn radius(mut self, radius: Length) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Sets the thickness of the border of the box.
+    pub fn border_thickness(mut self, thickness: Length) -> Self {
+        self.border_thickness = thickness;
+        self
+    }
+
+    /// Sets the brush used to fill the box when it is unchecked.
+    pub fn box_brush(mut self, brush: impl Into<Brush>) -> Self {
+        self.box_brush = brush.into();
+        self
+    }
+
+    /// Sets the brush used to draw the border of the box.
+    pub fn border_brush(mut self, brush: impl Into<Brush>) -> Self {
+        self.border_brush = brush.into();
+        self
+    }
+
+    /// Sets the brush used to fill the box when it is checked.
+    pub fn checked_brush(mut self, brush: impl Into<Brush>) -> Self {
+        self.checked_brush = brush.into();
+        self
+    }
+
+    /// Sets the brush used to draw the check mark.
+    pub fn mark_brush(mut self, brush: impl Into<Brush>) -> Self {
+        self.mark_brush = brush.into();
+        self
+    }
+
+    /// Sets whether the check box is disabled or not.
+    pub fn disabled(mut self, yes: bool) -> Self {
+        self.state.set(InteractiveState::DISABLED, yes);
+        self
+    }
+}
+
+impl<F: ?Sized> CheckBox<F> {
+    /// Returns whether the check box is checked.
+    #[inline]
+    pub fn is_checked(&self) -> bool {
+        self.checked
+    }
+
+    /// Sets whether the check box is checked.
+    ///
+    /// The check mark animates to its new state the next time the check box is drawn. This
+    /// does not call the [`OnToggle`] function.
+    pub fn set_checked(&mut self, yes: bool) {
+        self.checked = yes;
+    }
+
+    /// Advances the check animation, returning whether it is still running.
+    fn animate(&mut self) -> bool {
+        let target = if self.checked { 1.0 } else { 0.0 };
+        if self.progress == target {
+            self.last_frame = None;
+            return false;
+        }
+
+        let now = Instant::now();
+        let dt = self
+            .last_frame
+            .map_or(0.0, |last| (now - last).as_secs_f64());
+        self.last_frame = Some(now);
+
+        self.progress = exp_decay(self.progress, target, CHECK_ANIMATION_RATE, dt);
+        if (self.progress - target).abs() < 1e-3 {
+            self.progress = target;
+        }
+
+        true
+    }
+
+    /// Returns the part of the check mark that is visible for the current animation progress.
+    fn check_mark_path(&self) -> BezPath {
+        let r = self.rect;
+        let points =
+            CHECK_MARK.map(|(x, y)| Point::new(r.x0 + x * r.width(), r.y0 + y * r.height()));
+
+        let total: f64 = points.windows(2).map(|w| w[0].distance(w[1])).sum();
+        let mut remaining = total * self.progress;
+
+        let mut path = BezPath::new();
+        path.move_to(points[0]);
+        for w in points.windows(2) {
+            let length = w[0].distance(w[1]);
+            if remaining >= length {
+                path.line_to(w[1]);
+                remaining -= length;
+            } else {
+                path.line_to(w[0].lerp(w[1], remaining / length));
+                break;
+            }
+        }
+        path
+    }
+}
+
+impl<F: ?Sized + OnToggle> CheckBox<F> {
+    /// Toggles the check box, calling the [`OnToggle`] function.
+    fn toggle(&mut self, elem_context: &ElemContext) {
+        self.checked = !self.checked;
+        self.state.insert(InteractiveState::VALUE_CHANGED);
+        self.on_toggle.on_toggle(elem_context, self.checked);
+    }
+}
+
+impl<F> Element for CheckBox<F>
+where
+    F: ?Sized + OnToggle,
+{
+    fn size_hint(
+        &mut self,
+        _elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        _space: Size,
+    ) -> SizeHint {
+        let size = self.size.resolve(&layout_context);
+        let size = Size::new(size, size);
+
+        SizeHint {
+            preferred: size,
+            min: size,
+            max: size,
+        }
+    }
+
+    fn place(
+        &mut self,
+        _elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        pos: Point,
+        size: Size,
+    ) {
+        // The box stays square, even if the parent stretches the element.
+        let side = size.width.min(size.height);
+        self.rect = Rect::from_center_size(
+            Rect::from_origin_size(pos, size).center(),
+            Size::new(side, side),
+        );
+        self.computed_radius = self.radius.resolve(&layout_context);
+        self.computed_border_thickness = self.border_thickness.resolve(&layout_context);
+    }
+
+    #[inline]
+    fn hit_test(&self, point: Point) -> bool {
+        self.rect.contains(point)
+    }
+
+    fn draw(&mut self, elem_context: &ElemContext, scene: &mut vello::Scene) {
+        if self.animate() {
+            elem_context.window.request_redraw();
+        }
+
+        let shape = RoundedRect::from_rect(self.rect, self.computed_radius);
+        scene.fill(
+            Fill::NonZero,
+            Affine::IDENTITY,
+            &self.box_brush,
+            None,
+            &shape,
+        );
+
+        if self.progress > 0.0 {
+            scene.push_layer(Mix::Normal, self.progress as f32, Affine::IDENTITY, &shape);
+            scene.fill(
+                Fill::NonZero,
+                Affine::IDENTITY,
+                &self.checked_brush,
+                None,
+                &shape,
+            );
+            scene.stroke(
+                &Stroke::new(self.rect.width() * 0.12)
+                    .with_caps(Cap::Round)
+                    .with_join(Join::Round),
+                Affine::IDENTITY,
+                &self.mark_brush,
+                None,
+                &self.check_mark_path(),
+            );
+            scene.pop_layer();
+        }
+
+        if self.computed_border_thickness > 0.0 {
+            let half = self.computed_border_thickness / 2.0;
+            scene.stroke(
+                &Stroke::new(self.computed_border_thickness),
+                Affine::IDENTITY,
+                &self.border_brush,
+                None,
+                &RoundedRect::from_rect(
+                    self.rect.inset(-half),
+                    (self.computed_radius - half).max(0.0),
+                ),
+            );
+        }
+
+        if self.state.focus_visible() {
+            scene.stroke(
+                &Stroke::new(2.0),
+                Affine::IDENTITY,
+                &self.checked_brush,
+                None,
+                &RoundedRect::from_rect(self.rect.inflate(3.0, 3.0), self.computed_radius + 3.0),
+            );
+        }
+    }
+
+    fn event(&mut self, elem_context: &ElemContext, event: &dyn Event) -> EventResult {
+        self.state.remove_transient_states();
+
+        if self.state.disabled() {
+            return EventResult::Continue;
+        }
+
+        let og_state = self.state;
+
+        self.state.handle_focus_traversal(event);
+        let action = self
+            .state
+            .handle_accessibility_action(elem_context, &self.node_id, event);
+        let mut event_result = self
+            .state
+            .handle_pointer_interactions(&mut |pt| self.rect.contains(pt), event);
+
+        if self.state.just_clicked() {
+            self.toggle(elem_context);
+        } else if action == Some(Action::Click) {
+            self.toggle(elem_context);
+            event_result = EventResult::Handled;
+        } else if let Some(ev) = event.downcast_ref::<KeyEvent>() {
+            if self.state.focused()
+                && ev.is_initial_press()
+                && matches!(
+                    ev.logical_key,
+                    Key::Named(NamedKey::Space | NamedKey::Enter)
+                )
+            {
+                self.toggle(elem_context);
+                event_result = EventResult::Handled;
+            }
+        }
+
+        if og_state != self.state {
+            elem_context.window.request_redraw();
+        }
+
+        event_result
+    }
+
+    fn accessibility(&self, cx: &mut AccessibilityContext) {
+        let mut node = Node::new(Role::CheckBox);
+        node.add_action(Action::Focus);
+        node.add_action(Action::Click);
+        node.set_bounds(to_accesskit_rect(self.rect));
+        node.set_toggled(if self.checked {
+            Toggled::True
+        } else {
+            Toggled::False
+        });
+        if self.state.disabled() {
+            node.set_disabled();
+        }
+
+        let id = cx.push(node);
+        self.node_id.set(id);
+        if self.state.focused() {
+            cx.set_focus(id);
+        }
+    }
+}
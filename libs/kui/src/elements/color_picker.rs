@@ -0,0 +1,576 @@
+use {
+    crate::{
+        ElemContext, Element, LayoutContext, SizeHint,
+        accessibility::{AccessibilityContext, to_accesskit_rect},
+        elements::Length,
+        event::{Event, EventResult, PointerButton, PointerMoved},
+    },
+    accesskit::{Node, Role},
+    vello::{
+        kurbo::{Affine, Circle, Point, Rect, Size, Stroke},
+        peniko::{Color, Fill, Gradient},
+    },
+    winit::event::{ButtonSource, MouseButton},
+};
+
+/// A color expressed in the HSV (hue, saturation, value) color model.
+///
+/// The components are relative to the sRGB color space used by [`Color`].
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub struct Hsv {
+    /// The hue, in degrees, in the range `[0, 360)`.
+    pub hue: f32,
+    /// The saturation, in the range `[0, 1]`.
+    pub saturation: f32,
+    /// The value (brightness), in the range `[0, 1]`.
+    pub value: f32,
+}
+
+impl Hsv {
+    /// Creates a new [`Hsv`] color.
+    #[inline]
+    pub const fn new(hue: f32, saturation: f32, value: f32) -> Self {
+        Self {
+            hue,
+            saturation,
+            value,
+        }
+    }
+
+    /// Converts the provided sRGB components (in the range `[0, 1]`) to HSV.
+    ///
+    /// The hue of achromatic colors (grays) is zero.
+    pub fn from_rgb([r, g, b]: [f32; 3]) -> Self {
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let delta = max - min;
+
+        let hue = if delta == 0.0 {
+            0.0
+        } else if max == r {
+            60.0 * ((g - b) / delta).rem_euclid(6.0)
+        } else if max == g {
+            60.0 * ((b - r) / delta + 2.0)
+        } else {
+            60.0 * ((r - g) / delta + 4.0)
+        };
+
+        let saturation = if max == 0.0 { 0.0 } else { delta / max };
+
+        Self {
+            hue: if hue >= 360.0 { 0.0 } else { hue },
+            saturation,
+            value: max,
+        }
+    }
+
+    /// Converts this color to sRGB components in the range `[0, 1]`.
+    pub fn to_rgb(self) -> [f32; 3] {
+        let hue = self.hue.rem_euclid(360.0) / 60.0;
+        let chroma = self.value * self.saturation;
+        let x = chroma * (1.0 - (hue % 2.0 - 1.0).abs());
+        let m = self.value - chroma;
+
+        let (r, g, b) = match hue as u32 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+
+        [r + m, g + m, b + m]
+    }
+
+    /// Converts the provided [`Color`] to HSV, ignoring its alpha component.
+    #[inline]
+    pub fn from_color(color: Color) -> Self {
+        let [r, g, b, _] = color.components;
+        Self::from_rgb([r, g, b])
+    }
+
+    /// Converts this color to a [`Color`] with the provided alpha component.
+    #[inline]
+    pub fn to_color(self, alpha: f32) -> Color {
+        let [r, g, b] = self.to_rgb();
+        Color::new([r, g, b, alpha])
+    }
+}
+
+/// The function called by a [`ColorPicker`] when the user picks a new color.
+pub trait OnColorChange {
+    /// The color has changed.
+    fn on_change(&mut self, elem_context: &ElemContext, color: Color);
+}
+
+impl OnColorChange for () {
+    fn on_change(&mut self, _elem_context: &ElemContext, _color: Color) {}
+}
+
+impl<F> OnColorChange for F
+where
+    F: FnMut(&ElemContext, Color),
+{
+    fn on_change(&mut self, elem_context: &ElemContext, color: Color) {
+        self(elem_context, color)
+    }
+}
+
+/// A part of a [`ColorPicker`] that the user can drag.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ColorPickerPart {
+    /// The saturation/value square.
+    Square,
+    /// The hue strip.
+    Hue,
+    /// The alpha strip.
+    Alpha,
+}
+
+/// An element that lets the user pick a color.
+///
+/// The picker is made of a saturation/value square, followed by a vertical hue strip and an
+/// optional alpha strip. The [`OnColorChange`] function is called continuously while the user
+/// drags any of those parts.
+#[derive(Clone, Debug)]
+pub struct ColorPicker<F: ?Sized> {
+    /// The currently selected color, in HSV.
+    ///
+    /// The color is stored in HSV rather than RGB so that the hue and saturation are preserved
+    /// when the color becomes achromatic.
+    hsv: Hsv,
+    /// The alpha component of the currently selected color.
+    alpha: f32,
+
+    /// Whether the alpha strip is displayed.
+    pub show_alpha: bool,
+    /// The size of the saturation/value square.
+    pub square_size: Length,
+    /// The width of the hue and alpha strips.
+    pub strip_width: Length,
+    /// The spacing between the parts of the picker.
+    pub spacing: Length,
+
+    /// The part of the picker that the user is currently dragging.
+    dragging: Option<ColorPickerPart>,
+
+    /// The bounds of the saturation/value square.
+    square_rect: Rect,
+    /// The bounds of the hue strip.
+    hue_rect: Rect,
+    /// The bounds of the alpha strip.
+    alpha_rect: Rect,
+
+    /// The function called when the color changes.
+    pub on_change: F,
+}
+
+impl Default for ColorPicker<()> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(())
+    }
+}
+
+impl<F> ColorPicker<F> {
+    /// Creates a new [`ColorPicker`] element.
+    pub fn new(on_change: F) -> Self {
+        Self {
+            hsv: Hsv::new(0.0, 0.0, 1.0),
+            alpha: 1.0,
+            show_alpha: false,
+            square_size: Length::Pixels(200.0),
+            strip_width: Length::Pixels(20.0),
+            spacing: Length::Pixels(8.0),
+            dragging: None,
+            square_rect: Rect::ZERO,
+            hue_rect: Rect::ZERO,
+            alpha_rect: Rect::ZERO,
+            on_change,
+        }
+    }
+
+    /// Sets the function called when the color changes.
+    pub fn on_change<F2>(self, on_change: F2) -> ColorPicker<F2>
+    where
+        F2: FnMut(&ElemContext, Color),
+    {
+        ColorPicker {
+            hsv: self.hsv,
+            alpha: self.alpha,
+            show_alpha: self.show_alpha,
+            square_size: self.square_size,
+            strip_width: self.strip_width,
+            spacing: self.spacing,
+            dragging: None,
+            square_rect: self.square_rect,
+            hue_rect: self.hue_rect,
+            alpha_rect: self.alpha_rect,
+            on_change,
+        }
+    }
+
+    /// Sets the initial color of the picker.
+    pub fn color(mut self, color: impl Into<Color>) -> Self {
+        self.set_color(color.into());
+        self
+    }
+
+    /// Sets whether the alpha strip is displayed.
+    pub fn show_alpha(mut self, yes: bool) -> Self {
+        self.show_alpha = yes;
+        self
+    }
+
+    /// Sets the size of the saturation/value square.
+    pub fn square_size(mut self, size: Length) -> Self {
+        self.square_size = size;
+        self
+    }
+
+    /// Sets the width of the hue and alpha strips.
+    pub fn strip_width(mut self, width: Length) -> Self {
+        self.strip_width = width;
+        self
+    }
+
+    /// Sets the spacing between the parts of the picker.
+    pub fn spacing(mut self, spacing: Length) -> Self {
+        self.spacing = spacing;
+        self
+    }
+}
+
+impl<F: ?Sized> ColorPicker<F> {
+    /// Returns the currently selected color.
+    #[inline]
+    pub fn get_color(&self) -> Color {
+        self.hsv.to_color(self.alpha)
+    }
+
+    /// Returns the currently selected color, in HSV.
+    #[inline]
+    pub fn get_hsv(&self) -> Hsv {
+        self.hsv
+    }
+
+    /// Sets the currently selected color.
+    ///
+    /// This does not call the [`OnColorChange`] function.
+    pub fn set_color(&mut self, color: Color) {
+        let hsv = Hsv::from_color(color);
+
+        // Preserve the hue (and saturation) when they are undefined for the new color, so that
+        // the cursors do not jump around.
+        self.hsv.value = hsv.value;
+        if hsv.value != 0.0 {
+            self.hsv.saturation = hsv.saturation;
+        }
+        if hsv.saturation != 0.0 && hsv.value != 0.0 {
+            self.hsv.hue = hsv.hue;
+        }
+        self.alpha = color.components[3];
+    }
+
+    /// Returns the part of the picker at the provided position.
+    fn part_at(&self, point: Point) -> Option<ColorPickerPart> {
+        if self.square_rect.contains(point) {
+            Some(ColorPickerPart::Square)
+        } else if self.hue_rect.contains(point) {
+            Some(ColorPickerPart::Hue)
+        } else if self.show_alpha && self.alpha_rect.contains(point) {
+            Some(ColorPickerPart::Alpha)
+        } else {
+            None
+        }
+    }
+
+    /// Updates the color from a pointer position over the provided part.
+    fn update_from_point(&mut self, part: ColorPickerPart, point: Point) {
+        /// Returns the position of `x` within `[start, end]`, clamped to `[0, 1]`.
+        fn fraction(x: f64, start: f64, end: f64) -> f32 {
+            if end <= start {
+                return 0.0;
+            }
+            ((x - start) / (end - start)).clamp(0.0, 1.0) as f32
+        }
+
+        match part {
+            ColorPickerPart::Square => {
+                let r = self.square_rect;
+                self.hsv.saturation = fraction(point.x, r.x0, r.x1);
+                self.hsv.value = 1.0 - fraction(point.y, r.y0, r.y1);
+            }
+            ColorPickerPart::Hue => {
+                let r = self.hue_rect;
+                self.hsv.hue = (fraction(point.y, r.y0, r.y1) * 360.0).min(359.999);
+            }
+            ColorPickerPart::Alpha => {
+                let r = self.alpha_rect;
+                self.alpha = 1.0 - fraction(point.y, r.y0, r.y1);
+            }
+        }
+    }
+
+    /// Draws a circular cursor at the provided position.
+    fn draw_cursor(scene: &mut vello::Scene, center: Point) {
+        let circle = Circle::new(center, 5.0);
+        scene.stroke(
+            &Stroke::new(3.0),
+            Affine::IDENTITY,
+            Color::BLACK,
+            None,
+            &circle,
+        );
+        scene.stroke(
+            &Stroke::new(1.5),
+            Affine::IDENTITY,
+            Color::WHITE,
+            None,
+            &circle,
+        );
+    }
+}
+
+impl<F> Element for ColorPicker<F>
+where
+    F: ?Sized + OnColorChange,
+{
+    fn size_hint(
+        &mut self,
+        _elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        _space: Size,
+    ) -> SizeHint {
+        let square = self.square_size.resolve(&layout_context);
+        let strip = self.strip_width.resolve(&layout_context);
+        let spacing = self.spacing.resolve(&layout_context);
+        let strip_count = if self.show_alpha { 2.0 } else { 1.0 };
+
+        let size = Size::new(square + (spacing + strip) * strip_count, square);
+
+        SizeHint {
+            preferred: size,
+            min: size,
+            max: size,
+        }
+    }
+
+    fn place(
+        &mut self,
+        _elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        pos: Point,
+        size: Size,
+    ) {
+        let strip = self.strip_width.resolve(&layout_context);
+        let spacing = self.spacing.resolve(&layout_context);
+        let strip_count = if self.show_alpha { 2.0 } else { 1.0 };
+
+        let square = (size.width - (spacing + strip) * strip_count)
+            .min(size.height)
+            .max(0.0);
+
+        self.square_rect = Rect::from_origin_size(pos, Size::new(square, square));
+        self.hue_rect = Rect::from_origin_size(
+            Point::new(self.square_rect.x1 + spacing, pos.y),
+            Size::new(strip, square),
+        );
+        self.alpha_rect = Rect::from_origin_size(
+            Point::new(self.hue_rect.x1 + spacing, pos.y),
+            Size::new(strip, square),
+        );
+    }
+
+    fn hit_test(&self, point: Point) -> bool {
+        self.part_at(point).is_some()
+    }
+
+    fn draw(&mut self, _elem_context: &ElemContext, scene: &mut vello::Scene) {
+        //
+        // SATURATION/VALUE SQUARE
+        //
+        let r = self.square_rect;
+        let pure_hue = Hsv::new(self.hsv.hue, 1.0, 1.0).to_color(1.0);
+        scene.fill(Fill::NonZero, Affine::IDENTITY, pure_hue, None, &r);
+        scene.fill(
+            Fill::NonZero,
+            Affine::IDENTITY,
+            &Gradient::new_linear((r.x0, r.y0), (r.x1, r.y0))
+                .with_stops([Color::WHITE, Color::WHITE.with_alpha(0.0)]),
+            None,
+            &r,
+        );
+        scene.fill(
+            Fill::NonZero,
+            Affine::IDENTITY,
+            &Gradient::new_linear((r.x0, r.y0), (r.x0, r.y1))
+                .with_stops([Color::BLACK.with_alpha(0.0), Color::BLACK]),
+            None,
+            &r,
+        );
+        Self::draw_cursor(
+            scene,
+            Point::new(
+                r.x0 + r.width() * self.hsv.saturation as f64,
+                r.y0 + r.height() * (1.0 - self.hsv.value as f64),
+            ),
+        );
+
+        //
+        // HUE STRIP
+        //
+        let r = self.hue_rect;
+        let hue_stops: [Color; 7] =
+            std::array::from_fn(|i| Hsv::new(i as f32 * 60.0, 1.0, 1.0).to_color(1.0));
+        scene.fill(
+            Fill::NonZero,
+            Affine::IDENTITY,
+            &Gradient::new_linear((r.x0, r.y0), (r.x0, r.y1)).with_stops(hue_stops),
+            None,
+            &r,
+        );
+        Self::draw_cursor(
+            scene,
+            Point::new(
+                r.center().x,
+                r.y0 + r.height() * self.hsv.hue as f64 / 360.0,
+            ),
+        );
+
+        //
+        // ALPHA STRIP
+        //
+        if self.show_alpha {
+            let r = self.alpha_rect;
+            let opaque = self.hsv.to_color(1.0);
+
+            // A simple checkerboard makes transparency visible.
+            let cell = r.width() / 2.0;
+            let mut y = r.y0;
+            let mut row = 0;
+            while y < r.y1 {
+                let x = if row % 2 == 0 { r.x0 } else { r.x0 + cell };
+                let cell_rect = Rect::new(x, y, x + cell, (y + cell).min(r.y1));
+                scene.fill(
+                    Fill::NonZero,
+                    Affine::IDENTITY,
+                    Color::from_rgb8(0xcc, 0xcc, 0xcc),
+                    None,
+                    &cell_rect,
+                );
+                y += cell;
+                row += 1;
+            }
+
+            scene.fill(
+                Fill::NonZero,
+                Affine::IDENTITY,
+                &Gradient::new_linear((r.x0, r.y0), (r.x0, r.y1))
+                    .with_stops([opaque, opaque.with_alpha(0.0)]),
+                None,
+                &r,
+            );
+            Self::draw_cursor(
+                scene,
+                Point::new(r.center().x, r.y0 + r.height() * (1.0 - self.alpha as f64)),
+            );
+        }
+    }
+
+    fn event(&mut self, elem_context: &ElemContext, event: &dyn Event) -> EventResult {
+        if let Some(ev) = event.downcast_ref::<PointerButton>() {
+            if !ev.primary || !matches!(ev.button, ButtonSource::Mouse(MouseButton::Left)) {
+                return EventResult::Continue;
+            }
+
+            if ev.state.is_pressed() {
+                let Some(part) = self.part_at(ev.position) else {
+                    return EventResult::Continue;
+                };
+
+                self.dragging = Some(part);
+                self.update_from_point(part, ev.position);
+                self.on_change.on_change(elem_context, self.get_color());
+                elem_context.window.request_redraw();
+                return EventResult::Handled;
+            } else if self.dragging.take().is_some() {
+                return EventResult::Handled;
+            }
+        } else if let Some(ev) = event.downcast_ref::<PointerMoved>() {
+            if !ev.primary {
+                return EventResult::Continue;
+            }
+
+            if let Some(part) = self.dragging {
+                self.update_from_point(part, ev.position);
+                self.on_change.on_change(elem_context, self.get_color());
+                elem_context.window.request_redraw();
+                return EventResult::Handled;
+            }
+        }
+
+        EventResult::Continue
+    }
+
+    fn accessibility(&self, cx: &mut AccessibilityContext) {
+        let rgba = self.get_color().to_rgba8();
+
+        let mut node = Node::new(Role::ColorWell);
+        node.set_bounds(to_accesskit_rect(self.square_rect.union(
+            if self.show_alpha {
+                self.alpha_rect
+            } else {
+                self.hue_rect
+            },
+        )));
+        node.set_color_value(u32::from_be_bytes([rgba.r, rgba.g, rgba.b, rgba.a]));
+        cx.push(node);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Hsv;
+
+    /// The corners of the RGB cube, along with their HSV representation.
+    const CORNERS: [([f32; 3], Hsv); 8] = [
+        ([0.0, 0.0, 0.0], Hsv::new(0.0, 0.0, 0.0)),
+        ([1.0, 1.0, 1.0], Hsv::new(0.0, 0.0, 1.0)),
+        ([1.0, 0.0, 0.0], Hsv::new(0.0, 1.0, 1.0)),
+        ([1.0, 1.0, 0.0], Hsv::new(60.0, 1.0, 1.0)),
+        ([0.0, 1.0, 0.0], Hsv::new(120.0, 1.0, 1.0)),
+        ([0.0, 1.0, 1.0], Hsv::new(180.0, 1.0, 1.0)),
+        ([0.0, 0.0, 1.0], Hsv::new(240.0, 1.0, 1.0)),
+        ([1.0, 0.0, 1.0], Hsv::new(300.0, 1.0, 1.0)),
+    ];
+
+    #[test]
+    fn rgb_corners_convert_to_hsv() {
+        for (rgb, hsv) in CORNERS {
+            assert_eq!(Hsv::from_rgb(rgb), hsv, "{rgb:?}");
+        }
+    }
+
+    #[test]
+    fn hsv_corners_convert_to_rgb() {
+        for (rgb, hsv) in CORNERS {
+            assert_eq!(hsv.to_rgb(), rgb, "{hsv:?}");
+        }
+    }
+
+    #[test]
+    fn full_hue_wraps_to_red() {
+        assert_eq!(Hsv::new(360.0, 1.0, 1.0).to_rgb(), [1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn color_round_trip_keeps_alpha() {
+        for (rgb, hsv) in CORNERS {
+            let color = hsv.to_color(0.5);
+            assert_eq!(color.components, [rgb[0], rgb[1], rgb[2], 0.5]);
+            assert_eq!(Hsv::from_color(color), hsv);
+        }
+    }
+}
@@ -1,15 +1,16 @@
 use {
     crate::{
         ElemContext, Element, LayoutContext, SizeHint,
-        elements::Length,
-        event::{Event, EventResult},
+        accessibility::AccessibilityContext,
+        elements::{Length, flex::Direction, interactive::InteractiveState},
+        event::{Event, EventResult, PointerLeft},
     },
     smallvec::smallvec,
     vello::{
         kurbo::{
             Affine, Insets, Point, Rect, RoundedRect, RoundedRectRadii, Shape, Size, Stroke, Vec2,
         },
-        peniko::{Brush, Fill, Mix},
+        peniko::{Brush, Color, Fill, Mix},
     },
 };
 
@@ -20,6 +21,8 @@ use {
 #[derive(Clone, Debug)]
 pub struct DivStyle {
     pub brush: Option<Brush>,
+    pub hover_brush: Option<Brush>,
+    pub active_brush: Option<Brush>,
     pub top_left_radius: Length,
     pub top_right_radius: Length,
     pub bottom_left_radius: Length,
@@ -40,16 +43,44 @@ pub struct DivStyle {
     pub max_height: Option<Length>,
     pub clip_content: bool,
     pub opacity: f32,
+    pub shadow_color: Option<Color>,
+    pub shadow_blur: Length,
+    pub shadow_offset_x: Length,
+    pub shadow_offset_y: Length,
 }
 
 impl DivStyle {
+    /// Returns whether the style of the [`Div`] depends on its interaction state.
+    #[inline]
+    pub fn is_interactive(&self) -> bool {
+        self.hover_brush.is_some() || self.active_brush.is_some()
+    }
+
+    /// Returns the background brush to use for the provided interaction state.
+    ///
+    /// The active brush is only used while the pointer is both pressing and hovering the
+    /// element. Otherwise, the hover brush is used while the pointer hovers the element. Missing
+    /// brushes fall back to the next one in that order, down to the regular brush.
+    pub fn brush_for_state(&self, state: InteractiveState) -> Option<&Brush> {
+        if !state.hover() {
+            return self.brush.as_ref();
+        }
+
+        let active = self.active_brush.as_ref().filter(|_| state.active());
+        active.or(self.hover_brush.as_ref()).or(self.brush.as_ref())
+    }
+
     /// Resolves the horizontal padding.
     ///
     /// # Remarks
     ///
     /// This function does not take border thickness into account.
     pub fn resolve_horizontal_padding(&self, layout_context: &LayoutContext) -> f64 {
-        self.padding_left.resolve(layout_context) + self.padding_right.resolve(layout_context)
+        self.padding_left
+            .resolve_along(layout_context, Direction::Horizontal)
+            + self
+                .padding_right
+                .resolve_along(layout_context, Direction::Horizontal)
     }
 
     /// Resolves the vertical padding.
@@ -58,7 +89,11 @@ impl DivStyle {
     ///
     /// This function does not take border thickness into account.
     pub fn resolve_vertical_padding(&self, layout_context: &LayoutContext) -> f64 {
-        self.padding_top.resolve(layout_context) + self.padding_bottom.resolve(layout_context)
+        self.padding_top
+            .resolve_along(layout_context, Direction::Vertical)
+            + self
+                .padding_bottom
+                .resolve_along(layout_context, Direction::Vertical)
     }
 
     /// Resolves the vertical and horizontal padding into a [`Size`].
@@ -77,25 +112,25 @@ impl DivStyle {
     /// Resolves the minimum size of the [`Div`] element.
     pub fn resolve_min_size(&self, layout_context: &LayoutContext) -> Size {
         Size::new(
-            self.min_width
-                .as_ref()
-                .map_or(0.0, |min_width| min_width.resolve(layout_context)),
-            self.min_height
-                .as_ref()
-                .map_or(0.0, |min_height| min_height.resolve(layout_context)),
+            self.min_width.as_ref().map_or(0.0, |min_width| {
+                min_width.resolve_along(layout_context, Direction::Horizontal)
+            }),
+            self.min_height.as_ref().map_or(0.0, |min_height| {
+                min_height.resolve_along(layout_context, Direction::Vertical)
+            }),
         )
     }
 
     /// Resolves the maximum size of the [`Div`] element.
     pub fn resolve_max_size(&self, layout_context: &LayoutContext) -> Size {
         Size::new(
-            self.max_width
-                .as_ref()
-                .map_or(f64::INFINITY, |max_width| max_width.resolve(layout_context)),
+            self.max_width.as_ref().map_or(f64::INFINITY, |max_width| {
+                max_width.resolve_along(layout_context, Direction::Horizontal)
+            }),
             self.max_height
                 .as_ref()
                 .map_or(f64::INFINITY, |max_height| {
-                    max_height.resolve(layout_context)
+                    max_height.resolve_along(layout_context, Direction::Vertical)
                 }),
         )
     }
@@ -103,12 +138,12 @@ impl DivStyle {
     /// Resolves the size of the [`Div`] element.
     pub fn resolve_size(&self, fallback: Size, layout_context: &LayoutContext) -> Size {
         Size::new(
-            self.width
-                .as_ref()
-                .map_or(fallback.width, |width| width.resolve(layout_context)),
-            self.height
-                .as_ref()
-                .map_or(fallback.height, |height| height.resolve(layout_context)),
+            self.width.as_ref().map_or(fallback.width, |width| {
+                width.resolve_along(layout_context, Direction::Horizontal)
+            }),
+            self.height.as_ref().map_or(fallback.height, |height| {
+                height.resolve_along(layout_context, Direction::Vertical)
+            }),
         )
     }
 }
@@ -117,6 +152,8 @@ impl Default for DivStyle {
     fn default() -> Self {
         Self {
             brush: None,
+            hover_brush: None,
+            active_brush: None,
             top_left_radius: Length::ZERO,
             top_right_radius: Length::ZERO,
             bottom_left_radius: Length::ZERO,
@@ -137,6 +174,10 @@ impl Default for DivStyle {
             max_height: None,
             clip_content: false,
             opacity: 1.0,
+            shadow_color: None,
+            shadow_blur: Length::ZERO,
+            shadow_offset_x: Length::ZERO,
+            shadow_offset_y: Length::ZERO,
         }
     }
 }
@@ -150,6 +191,8 @@ pub struct DivComputedStyle {
     pub border_thickness: f64,
     pub border_dash: f64,
     pub border_dash_offset: f64,
+    pub shadow_blur: f64,
+    pub shadow_offset: Vec2,
 }
 
 /// Works a bit like an HTML `<div>` element, except it does not provide any layout capabilities.
@@ -159,17 +202,42 @@ pub struct Div<E: ?Sized> {
     pub style: DivStyle,
     /// The computed style of the [`Div`] element.
     pub computed_style: DivComputedStyle,
+    /// The interaction state of the [`Div`] element.
+    ///
+    /// This is only tracked when a hover or active brush is set.
+    pub state: InteractiveState,
     /// The child element of the [`Div`].
     pub child: E,
 }
 
 impl<E> Div<E> {
     /// Sets the background brush of the [`Div`] element.
+    ///
+    /// The geometry of gradient brushes is expressed in a unit square that is stretched over
+    /// the bounds of the element.
     pub fn brush(mut self, brush: impl Into<Brush>) -> Self {
         self.style.brush = Some(brush.into());
         self
     }
 
+    /// Sets the background brush of the [`Div`] element while the pointer hovers it.
+    ///
+    /// When unset, the regular brush is used.
+    pub fn hover_brush(mut self, brush: impl Into<Brush>) -> Self {
+        self.style.hover_brush = Some(brush.into());
+        self
+    }
+
+    /// Sets the background brush of the [`Div`] element while it is being pressed.
+    ///
+    /// The brush is only used while the pointer is over the element. When the pointer leaves
+    /// the element while the button is still held down, the regular brush is used again. When
+    /// unset, the hover brush is used.
+    pub fn active_brush(mut self, brush: impl Into<Brush>) -> Self {
+        self.style.active_brush = Some(brush.into());
+        self
+    }
+
     /// Sets the top-left radius of the [`Div`] element.
     pub fn top_left_radius(mut self, radius: Length) -> Self {
         self.style.top_left_radius = radius;
@@ -204,6 +272,8 @@ impl<E> Div<E> {
     }
 
     /// Sets the border brush of the [`Div`] element.
+    ///
+    /// Like for the background brush, gradients are stretched over the bounds of the element.
     pub fn border_brush(mut self, brush: impl Into<Brush>) -> Self {
         self.style.border_brush = Some(brush.into());
         self
@@ -277,11 +347,41 @@ impl<E> Div<E> {
         self
     }
 
+    /// Sets the color of the drop shadow drawn behind the [`Div`] element.
+    ///
+    /// The shadow follows the corner radius of the element and is multiplied by its opacity. It
+    /// does not take part in hit testing.
+    pub fn shadow_color(mut self, color: impl Into<Color>) -> Self {
+        self.style.shadow_color = Some(color.into());
+        self
+    }
+
+    /// Sets the blur radius of the drop shadow of the [`Div`] element.
+    pub fn shadow_blur(mut self, blur: Length) -> Self {
+        self.style.shadow_blur = blur;
+        self
+    }
+
+    /// Sets the offset of the drop shadow of the [`Div`] element.
+    pub fn shadow_offset(mut self, x: Length, y: Length) -> Self {
+        self.style.shadow_offset_x = x;
+        self.style.shadow_offset_y = y;
+        self
+    }
+
+    /// Sets the color, blur radius and offset of the drop shadow of the [`Div`] element.
+    pub fn shadow(self, color: impl Into<Color>, blur: Length, x: Length, y: Length) -> Self {
+        self.shadow_color(color)
+            .shadow_blur(blur)
+            .shadow_offset(x, y)
+    }
+
     /// Sets the child of the [`Div`] element.
     pub fn child<E2>(self, child: E2) -> Div<E2> {
         Div {
             style: self.style,
             computed_style: DivComputedStyle::default(),
+            state: InteractiveState::default(),
             child,
         }
     }
@@ -328,6 +428,18 @@ impl<E: ?Sized + Element> Div<E> {
     }
 }
 
+/// Returns the transform applied to the provided brush when painting an element with the
+/// provided bounds.
+///
+/// Gradients are expressed in a unit square that is stretched over the bounds of the element.
+/// Other brushes are not transformed.
+fn brush_transform(brush: &Brush, bounds: Rect) -> Option<Affine> {
+    matches!(brush, Brush::Gradient(_)).then(|| {
+        Affine::translate(bounds.origin().to_vec2())
+            * Affine::scale_non_uniform(bounds.width(), bounds.height())
+    })
+}
+
 fn size_min(a: Size, b: Size) -> Size {
     Size::new(a.width.min(b.width), a.height.min(b.height))
 }
@@ -356,10 +468,7 @@ impl<E: ?Sized + Element> Element for Div<E> {
 
         let child_size_hint = self.child.size_hint(
             elem_context,
-            LayoutContext {
-                parent: child_space,
-                scale_factor: layout_context.scale_factor,
-            },
+            layout_context.with_parent(child_space),
             child_space,
         );
 
@@ -381,10 +490,26 @@ impl<E: ?Sized + Element> Element for Div<E> {
     ) {
         let border_thickness = self.style.border_thickness.resolve(&layout_context);
 
-        let padding_left = self.style.padding_left.resolve(&layout_context) + border_thickness;
-        let padding_right = self.style.padding_right.resolve(&layout_context) + border_thickness;
-        let padding_top = self.style.padding_top.resolve(&layout_context) + border_thickness;
-        let padding_bottom = self.style.padding_bottom.resolve(&layout_context) + border_thickness;
+        let padding_left = self
+            .style
+            .padding_left
+            .resolve_along(&layout_context, Direction::Horizontal)
+            + border_thickness;
+        let padding_right = self
+            .style
+            .padding_right
+            .resolve_along(&layout_context, Direction::Horizontal)
+            + border_thickness;
+        let padding_top = self
+            .style
+            .padding_top
+            .resolve_along(&layout_context, Direction::Vertical)
+            + border_thickness;
+        let padding_bottom = self
+            .style
+            .padding_bottom
+            .resolve_along(&layout_context, Direction::Vertical)
+            + border_thickness;
 
         let horizontal_padding = padding_left + padding_right;
         let vertical_padding = padding_top + padding_bottom;
@@ -393,10 +518,7 @@ impl<E: ?Sized + Element> Element for Div<E> {
 
         self.child.place(
             elem_context,
-            LayoutContext {
-                parent: content_size,
-                scale_factor: layout_context.scale_factor,
-            },
+            layout_context.with_parent(content_size),
             position + Vec2::new(padding_left, padding_top),
             content_size,
         );
@@ -413,6 +535,15 @@ impl<E: ?Sized + Element> Element for Div<E> {
             border_thickness,
             border_dash: self.style.border_dash.resolve(&layout_context),
             border_dash_offset: self.style.border_dash_offset.resolve(&layout_context),
+            shadow_blur: self.style.shadow_blur.resolve(&layout_context),
+            shadow_offset: Vec2::new(
+                self.style
+                    .shadow_offset_x
+                    .resolve_along(&layout_context, Direction::Horizontal),
+                self.style
+                    .shadow_offset_y
+                    .resolve_along(&layout_context, Direction::Vertical),
+            ),
         };
     }
 
@@ -421,7 +552,10 @@ impl<E: ?Sized + Element> Element for Div<E> {
             return true;
         }
 
-        if self.style.brush.is_some() || self.style.border_brush.is_some() {
+        if self.style.brush.is_some()
+            || self.style.border_brush.is_some()
+            || self.style.is_interactive()
+        {
             self.computed_shape().contains(point)
         } else {
             false
@@ -431,8 +565,34 @@ impl<E: ?Sized + Element> Element for Div<E> {
     fn draw(&mut self, elem_context: &ElemContext, scene: &mut vello::Scene) {
         let outer_shape = self.computed_shape();
 
-        if let Some(brush) = self.style.brush.as_ref() {
-            scene.fill(Fill::NonZero, Affine::IDENTITY, brush, None, &outer_shape);
+        if let Some(shadow_color) = self.style.shadow_color {
+            // Vello only supports blurring rectangles with a uniform corner radius.
+            let radii = self.computed_style.corner_radiuses;
+            let radius = radii
+                .top_left
+                .max(radii.top_right)
+                .max(radii.bottom_left)
+                .max(radii.bottom_right);
+
+            scene.draw_blurred_rounded_rect(
+                Affine::IDENTITY,
+                outer_shape.rect() + self.computed_style.shadow_offset,
+                shadow_color.multiply_alpha(self.style.opacity),
+                radius,
+                // The blur radius is interpreted like in CSS, where it is twice the standard
+                // deviation of the gaussian.
+                self.computed_style.shadow_blur / 2.0,
+            );
+        }
+
+        if let Some(brush) = self.style.brush_for_state(self.state) {
+            scene.fill(
+                Fill::NonZero,
+                Affine::IDENTITY,
+                brush,
+                brush_transform(brush, outer_shape.rect()),
+                &outer_shape,
+            );
         }
 
         if let Some(border_brush) = self.style.border_brush.as_ref() {
@@ -449,7 +609,7 @@ impl<E: ?Sized + Element> Element for Div<E> {
                 },
                 Affine::IDENTITY,
                 border_brush,
-                None,
+                brush_transform(border_brush, outer_shape.rect()),
                 &(outer_shape.rect() - Insets::uniform(self.computed_style.border_thickness / 2.0))
                     .to_rounded_rect(self.computed_style.corner_radiuses),
             );
@@ -475,13 +635,40 @@ impl<E: ?Sized + Element> Element for Div<E> {
         }
     }
 
-    #[inline]
     fn event(&mut self, elem_context: &ElemContext, event: &dyn Event) -> EventResult {
-        self.child.event(elem_context, event)
+        let result = self.child.event(elem_context, event);
+
+        if self.style.is_interactive() {
+            let old_brush = self.style.brush_for_state(self.state).cloned();
+
+            let shape = self.computed_shape();
+            self.state
+                .handle_pointer_interactions(&mut |pt| shape.contains(pt), event);
+
+            // The release of the button might happen outside of the window, in which case
+            // the element would never know about it.
+            if event.is::<PointerLeft>() {
+                self.state.remove(InteractiveState::ACTIVE);
+            }
+
+            self.state.remove_transient_states();
+
+            if self.style.brush_for_state(self.state) != old_brush.as_ref() {
+                elem_context.window.request_redraw();
+            }
+        }
+
+        // Styling the element does not consume any event.
+        result
     }
 
     #[inline]
     fn begin(&mut self, elem_context: &ElemContext) {
         self.child.begin(elem_context);
     }
+
+    #[inline]
+    fn accessibility(&self, cx: &mut AccessibilityContext) {
+        self.child.accessibility(cx);
+    }
 }
@@ -0,0 +1,272 @@
+use {
+    crate::{
+        ElemContext, Element, LayoutContext, SizeHint,
+        accessibility::AccessibilityContext,
+        event::{Event, EventResult, FileDropped, FileHoverCancelled, FileHovered},
+    },
+    std::{
+        path::{Path, PathBuf},
+        rc::Rc,
+    },
+    vello::{
+        kurbo::{Affine, Point, Rect, Size},
+        peniko::{Brush, Color, Fill},
+    },
+};
+
+/// The function called when files are dropped on a [`DropTarget`].
+pub trait OnDrop {
+    /// Files have been dropped on the target.
+    fn on_drop(&mut self, elem_context: &ElemContext, event: &FileDropped);
+}
+
+impl OnDrop for () {
+    #[inline]
+    fn on_drop(&mut self, _elem_context: &ElemContext, _event: &FileDropped) {}
+}
+
+impl<F> OnDrop for F
+where
+    F: FnMut(&ElemContext, &FileDropped),
+{
+    #[inline]
+    fn on_drop(&mut self, elem_context: &ElemContext, event: &FileDropped) {
+        self(elem_context, event)
+    }
+}
+
+/// An element that accepts files dragged from the operating system.
+///
+/// The area covered by the element is highlighted while files are hovering it. When drop
+/// targets are nested, only the innermost one under the pointer is highlighted and receives
+/// the dropped files.
+///
+/// The target can be restricted to some file types with
+/// [`accept_extensions`](Self::accept_extensions). Files it does not accept are neither
+/// highlighted nor reported to its [`OnDrop`] function, and are left to the targets below it.
+#[derive(Clone, Debug)]
+pub struct DropTarget<F, E: ?Sized> {
+    /// The function called when files are dropped on the element.
+    on_drop: F,
+    /// The brush used to highlight the element while files are hovering it.
+    highlight: Option<Brush>,
+    /// The extensions of the files accepted by the element, in lowercase.
+    ///
+    /// When `None`, all files are accepted.
+    extensions: Option<Vec<String>>,
+    /// Whether files are currently hovering the element.
+    hovered: bool,
+    /// The area covered by the element.
+    bounds: Rect,
+    /// The child element.
+    pub child: E,
+}
+
+impl<F: Default, E: Default> Default for DropTarget<F, E> {
+    fn default() -> Self {
+        Self {
+            on_drop: F::default(),
+            highlight: Some(Color::from_rgba8(255, 255, 255, 24).into()),
+            extensions: None,
+            hovered: false,
+            bounds: Rect::ZERO,
+            child: E::default(),
+        }
+    }
+}
+
+impl<F, E> DropTarget<F, E> {
+    /// Sets the function called when files are dropped on the element.
+    ///
+    /// All the files dropped at once are provided in a single call. The function is responsible
+    /// for ignoring the files it does not support.
+    pub fn on_drop<F2>(self, on_drop: F2) -> DropTarget<F2, E>
+    where
+        F2: FnMut(&ElemContext, &FileDropped),
+    {
+        DropTarget {
+            on_drop,
+            highlight: self.highlight,
+            extensions: self.extensions,
+            hovered: self.hovered,
+            bounds: self.bounds,
+            child: self.child,
+        }
+    }
+
+    /// Sets the brush used to highlight the element while files are hovering it.
+    ///
+    /// The highlight is drawn on top of the child element, which means that the brush should
+    /// usually be translucent.
+    pub fn highlight(mut self, brush: impl Into<Brush>) -> Self {
+        self.highlight = Some(brush.into());
+        self
+    }
+
+    /// Disables the highlight drawn while files are hovering the element.
+    pub fn no_highlight(mut self) -> Self {
+        self.highlight = None;
+        self
+    }
+
+    /// Restricts the files accepted by the element to the provided extensions (such as
+    /// `"wav"`), compared without regard to their case.
+    pub fn accept_extensions<I>(mut self, extensions: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        self.extensions = Some(
+            extensions
+                .into_iter()
+                .map(|ext| ext.as_ref().to_lowercase())
+                .collect(),
+        );
+        self
+    }
+
+    /// Sets the child element of this [`DropTarget`].
+    pub fn child<E2>(self, child: E2) -> DropTarget<F, E2> {
+        DropTarget {
+            on_drop: self.on_drop,
+            highlight: self.highlight,
+            extensions: self.extensions,
+            hovered: self.hovered,
+            bounds: self.bounds,
+            child,
+        }
+    }
+}
+
+impl<F, E: ?Sized> DropTarget<F, E> {
+    /// Returns whether files are currently hovering the element.
+    #[inline]
+    pub fn is_hovered(&self) -> bool {
+        self.hovered
+    }
+
+    /// Returns whether the element accepts the file at the provided path.
+    pub fn accepts(&self, path: &Path) -> bool {
+        let Some(extensions) = &self.extensions else {
+            return true;
+        };
+
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| extensions.iter().any(|e| e.eq_ignore_ascii_case(ext)))
+    }
+
+    /// Updates the hover state of the element, requesting a redraw if it changed.
+    fn set_hovered(&mut self, elem_context: &ElemContext, hovered: bool) {
+        if self.hovered != hovered {
+            self.hovered = hovered;
+            elem_context.window.request_redraw();
+        }
+    }
+}
+
+impl<F, E> Element for DropTarget<F, E>
+where
+    F: OnDrop,
+    E: ?Sized + Element,
+{
+    #[inline]
+    fn size_hint(
+        &mut self,
+        elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        space: Size,
+    ) -> SizeHint {
+        self.child.size_hint(elem_context, layout_context, space)
+    }
+
+    fn place(
+        &mut self,
+        elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        pos: Point,
+        size: Size,
+    ) {
+        self.bounds = Rect::from_origin_size(pos, size);
+        self.child.place(elem_context, layout_context, pos, size);
+    }
+
+    fn hit_test(&self, point: Point) -> bool {
+        self.child.hit_test(point)
+    }
+
+    fn draw(&mut self, elem_context: &ElemContext, scene: &mut vello::Scene) {
+        self.child.draw(elem_context, scene);
+
+        if let Some(brush) = self.highlight.as_ref().filter(|_| self.hovered) {
+            scene.fill(Fill::NonZero, Affine::IDENTITY, brush, None, &self.bounds);
+        }
+    }
+
+    fn event(&mut self, elem_context: &ElemContext, event: &dyn Event) -> EventResult {
+        // The child is given a chance to handle the event first so that nested drop targets
+        // take precedence over this one.
+        let child_result = self.child.event(elem_context, event);
+
+        if let Some(ev) = event.downcast_ref::<FileHovered>() {
+            let hovered = !child_result.is_handled()
+                && self.bounds.contains(ev.position)
+                && ev.paths.iter().any(|path| self.accepts(path));
+            self.set_hovered(elem_context, hovered);
+            return if hovered {
+                ev.accept();
+                EventResult::Handled
+            } else {
+                child_result
+            };
+        }
+
+        if let Some(ev) = event.downcast_ref::<FileDropped>() {
+            self.set_hovered(elem_context, false);
+
+            if child_result.is_handled() || !self.bounds.contains(ev.position) {
+                return child_result;
+            }
+
+            if self.extensions.is_none() {
+                self.on_drop.on_drop(elem_context, ev);
+                return EventResult::Handled;
+            }
+
+            let paths: Rc<[PathBuf]> = ev
+                .paths
+                .iter()
+                .filter(|path| self.accepts(path))
+                .cloned()
+                .collect();
+            if paths.is_empty() {
+                return child_result;
+            }
+
+            self.on_drop.on_drop(
+                elem_context,
+                &FileDropped {
+                    paths,
+                    position: ev.position,
+                },
+            );
+            return EventResult::Handled;
+        }
+
+        if event.is::<FileHoverCancelled>() {
+            self.set_hovered(elem_context, false);
+        }
+
+        child_result
+    }
+
+    #[inline]
+    fn begin(&mut self, elem_context: &ElemContext) {
+        self.child.begin(elem_context);
+    }
+
+    #[inline]
+    fn accessibility(&self, cx: &mut AccessibilityContext) {
+        self.child.accessibility(cx);
+    }
+}
@@ -0,0 +1,169 @@
+use {
+    crate::{
+        ElemContext, Element, LayoutContext, SizeHint,
+        accessibility::AccessibilityContext,
+        event::{Event, EventResult},
+    },
+    vello::kurbo::{Point, Size},
+};
+
+/// An element that is one of two possible elements.
+///
+/// This is mostly useful when a child is chosen at runtime, since both alternatives must have
+/// the same type. The `elem!` macro expands `if`/`else` children to this type.
+#[derive(Clone, Debug)]
+pub enum Either<A, B> {
+    /// The first alternative.
+    Left(A),
+    /// The second alternative.
+    Right(B),
+}
+
+impl<A, B> Either<A, B> {
+    /// Returns whether this is the [`Left`](Either::Left) alternative.
+    #[inline]
+    pub fn is_left(&self) -> bool {
+        matches!(self, Self::Left(_))
+    }
+
+    /// Returns whether this is the [`Right`](Either::Right) alternative.
+    #[inline]
+    pub fn is_right(&self) -> bool {
+        matches!(self, Self::Right(_))
+    }
+}
+
+impl<A: Element, B: Element> Element for Either<A, B> {
+    #[inline]
+    fn size_hint(
+        &mut self,
+        elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        space: Size,
+    ) -> SizeHint {
+        match self {
+            Self::Left(e) => e.size_hint(elem_context, layout_context, space),
+            Self::Right(e) => e.size_hint(elem_context, layout_context, space),
+        }
+    }
+
+    #[inline]
+    fn place(
+        &mut self,
+        elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        pos: Point,
+        size: Size,
+    ) {
+        match self {
+            Self::Left(e) => e.place(elem_context, layout_context, pos, size),
+            Self::Right(e) => e.place(elem_context, layout_context, pos, size),
+        }
+    }
+
+    #[inline]
+    fn hit_test(&self, point: Point) -> bool {
+        match self {
+            Self::Left(e) => e.hit_test(point),
+            Self::Right(e) => e.hit_test(point),
+        }
+    }
+
+    #[inline]
+    fn draw(&mut self, elem_context: &ElemContext, scene: &mut vello::Scene) {
+        match self {
+            Self::Left(e) => e.draw(elem_context, scene),
+            Self::Right(e) => e.draw(elem_context, scene),
+        }
+    }
+
+    #[inline]
+    fn event(&mut self, elem_context: &ElemContext, event: &dyn Event) -> EventResult {
+        match self {
+            Self::Left(e) => e.event(elem_context, event),
+            Self::Right(e) => e.event(elem_context, event),
+        }
+    }
+
+    #[inline]
+    fn begin(&mut self, elem_context: &ElemContext) {
+        match self {
+            Self::Left(e) => e.begin(elem_context),
+            Self::Right(e) => e.begin(elem_context),
+        }
+    }
+
+    #[inline]
+    fn accessibility(&self, cx: &mut AccessibilityContext) {
+        match self {
+            Self::Left(e) => e.accessibility(cx),
+            Self::Right(e) => e.accessibility(cx),
+        }
+    }
+}
+
+/// An element that might be absent.
+///
+/// `None` behaves like the empty `()` element. The `elem!` macro expands `if` children without
+/// an `else` branch to this type.
+impl<E: Element> Element for Option<E> {
+    #[inline]
+    fn size_hint(
+        &mut self,
+        elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        space: Size,
+    ) -> SizeHint {
+        match self {
+            Some(e) => e.size_hint(elem_context, layout_context, space),
+            None => SizeHint::default(),
+        }
+    }
+
+    #[inline]
+    fn place(
+        &mut self,
+        elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        pos: Point,
+        size: Size,
+    ) {
+        if let Some(e) = self {
+            e.place(elem_context, layout_context, pos, size);
+        }
+    }
+
+    #[inline]
+    fn hit_test(&self, point: Point) -> bool {
+        self.as_ref().is_some_and(|e| e.hit_test(point))
+    }
+
+    #[inline]
+    fn draw(&mut self, elem_context: &ElemContext, scene: &mut vello::Scene) {
+        if let Some(e) = self {
+            e.draw(elem_context, scene);
+        }
+    }
+
+    #[inline]
+    fn event(&mut self, elem_context: &ElemContext, event: &dyn Event) -> EventResult {
+        match self {
+            Some(e) => e.event(elem_context, event),
+            None => EventResult::Continue,
+        }
+    }
+
+    #[inline]
+    fn begin(&mut self, elem_context: &ElemContext) {
+        if let Some(e) = self {
+            e.begin(elem_context);
+        }
+    }
+
+    #[inline]
+    fn accessibility(&self, cx: &mut AccessibilityContext) {
+        if let Some(e) = self {
+            e.accessibility(cx);
+        }
+    }
+}
@@ -2,6 +2,7 @@ use {
     super::Length,
     crate::{
         ElemContext, Element, LayoutContext, SizeHint,
+        accessibility::AccessibilityContext,
         event::{Event, EventResult},
     },
     core::f64,
@@ -281,14 +282,11 @@ impl Element for Flex<'_> {
             max_cross_length,
             ..
         } = ChildrenMetrics::compute(
-            self.gap.resolve(&layout_context),
+            self.gap.resolve_along(&layout_context, self.direction),
             self.direction,
             &mut self.children,
             elem_context,
-            LayoutContext {
-                parent: space,
-                scale_factor: layout_context.scale_factor,
-            },
+            layout_context.with_parent(space),
         );
 
         SizeHint {
@@ -313,7 +311,7 @@ impl Element for Flex<'_> {
         // minimum size.
         //
 
-        let gap = self.gap.resolve(&layout_context);
+        let gap = self.gap.resolve_along(&layout_context, self.direction);
 
         let max_length = match self.direction {
             Direction::Horizontal => size.width,
@@ -329,10 +327,7 @@ impl Element for Flex<'_> {
             self.direction,
             &mut self.children,
             elem_context,
-            LayoutContext {
-                parent: size,
-                scale_factor: layout_context.scale_factor,
-            },
+            layout_context.with_parent(size),
         );
 
         let grow_factor = if total_growth > 0.0 && max_length > total_length {
@@ -399,10 +394,7 @@ impl Element for Flex<'_> {
 
             child.child.place(
                 elem_context,
-                LayoutContext {
-                    parent: size,
-                    scale_factor: layout_context.scale_factor,
-                },
+                layout_context.with_parent(size),
                 pos + child_offset,
                 child_size,
             );
@@ -442,4 +434,10 @@ impl Element for Flex<'_> {
             .iter_mut()
             .for_each(|child| child.child.begin(elem_context));
     }
+
+    fn accessibility(&self, cx: &mut AccessibilityContext) {
+        self.children
+            .iter()
+            .for_each(|child| child.child.accessibility(cx));
+    }
 }
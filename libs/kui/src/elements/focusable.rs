@@ -0,0 +1,244 @@
+use {
+    crate::{
+        ElemContext, Element, LayoutContext, SizeHint,
+        accessibility::AccessibilityContext,
+        elements::interactive::InteractiveState,
+        event::{ClipboardPaste, CollectFocusTargets, Event, EventResult, KeyEvent, MoveFocus},
+    },
+    vello::kurbo::{Point, Size},
+};
+
+/// An event used internally to give the focused element a chance to handle keyboard events
+/// before the rest of the tree.
+///
+/// When the window dispatches a keyboard event, it first dispatches a [`FocusPass`]. The
+/// focused [`Focusable`] element forwards the wrapped event to its child during that pass. If
+/// the event is not handled, the window then dispatches the event itself to the whole tree.
+pub(crate) struct FocusPass {
+    /// The event being dispatched.
+    ///
+    /// This pointer is only valid for the duration of the dispatch.
+    event: *const dyn Event,
+}
+
+impl FocusPass {
+    /// Creates a new [`FocusPass`] for the provided event.
+    #[inline]
+    pub fn new(event: &dyn Event) -> Self {
+        Self {
+            event: event as *const dyn Event,
+        }
+    }
+
+    /// Returns the wrapped event.
+    #[inline]
+    pub fn event(&self) -> &dyn Event {
+        // SAFETY: `FocusPass` instances are only created by the window while it dispatches the
+        // wrapped event, and elements only receive a reference to it during that time.
+        unsafe { &*self.event }
+    }
+}
+
+/// Returns whether the provided event is a keyboard event, routed through a [`FocusPass`].
+fn is_keyboard_event(event: &dyn Event) -> bool {
+    event.is::<KeyEvent>() || event.is::<ClipboardPaste>()
+}
+
+/// An element that can receive the keyboard focus.
+///
+/// The element takes the focus when it is clicked, when the user reaches it with Tab or
+/// Shift+Tab, or when one of its descendants calls [`ElemContext::request_focus`]. Its
+/// descendants can check whether it is focused with [`ElemContext::has_focus`].
+///
+/// While the element is focused, keyboard events are dispatched to its child before any other
+/// element of the window. They only reach the rest of the tree if the child does not handle
+/// them.
+///
+/// Instances of this type are usually created through
+/// [`ElementExt::focusable`](crate::ElementExt::focusable).
+#[derive(Clone, Debug, Default)]
+pub struct Focusable<E: ?Sized> {
+    /// The state of the interactive element.
+    pub state: InteractiveState,
+    /// Whether a descendant of the element requested the focus during the current event.
+    focus_requested: bool,
+    /// The child element.
+    pub child: E,
+}
+
+impl<E> Focusable<E> {
+    /// Creates a new [`Focusable`] element.
+    #[inline]
+    pub fn new(child: E) -> Self {
+        Self {
+            state: InteractiveState::empty(),
+            focus_requested: false,
+            child,
+        }
+    }
+
+    /// Sets the child element of this [`Focusable`] element.
+    pub fn child<E2>(self, child: E2) -> Focusable<E2> {
+        Focusable {
+            state: self.state,
+            focus_requested: self.focus_requested,
+            child,
+        }
+    }
+
+    /// Sets whether the element is disabled or not.
+    ///
+    /// Disabled elements cannot receive the focus.
+    pub fn disabled(mut self, yes: bool) -> Self {
+        self.state.set(InteractiveState::DISABLED, yes);
+        self
+    }
+}
+
+impl<E: ?Sized> Focusable<E> {
+    /// Returns whether the element currently has the keyboard focus.
+    #[inline]
+    pub fn is_focused(&self) -> bool {
+        self.state.focused()
+    }
+}
+
+impl<E: ?Sized + Element> Focusable<E> {
+    /// Forwards an event to the child element, within the focus scope of this element.
+    fn forward_event(&mut self, elem_context: &ElemContext, event: &dyn Event) -> EventResult {
+        let result = elem_context
+            .window
+            .with_focus_scope(self.state.focused(), || {
+                self.child.event(elem_context, event)
+            });
+
+        if !self.state.disabled() && elem_context.window.claim_focus_request() {
+            self.focus_requested = true;
+        }
+
+        result
+    }
+
+    /// Handles the focus traversal events.
+    ///
+    /// Unlike [`InteractiveState::handle_focus_traversal`], the focus is only made visible when
+    /// it was moved with the keyboard.
+    fn handle_focus_traversal(&mut self, event: &dyn Event) {
+        if self.state.disabled() {
+            return;
+        }
+
+        if let Some(ev) = event.downcast_ref::<CollectFocusTargets>() {
+            if self.focus_requested {
+                ev.register_requested();
+            } else {
+                ev.register(self.state.focused());
+            }
+        } else if let Some(ev) = event.downcast_ref::<MoveFocus>() {
+            let requested = std::mem::take(&mut self.focus_requested);
+
+            if ev.next() {
+                if !self.state.focused() {
+                    self.state.insert(InteractiveState::JUST_FOCUSED);
+                }
+                self.state.insert(InteractiveState::FOCUS);
+                self.state.set(InteractiveState::FOCUS_VISIBLE, !requested);
+            } else if self.state.focused() {
+                self.state
+                    .remove(InteractiveState::FOCUS | InteractiveState::FOCUS_VISIBLE);
+                self.state.insert(InteractiveState::JUST_UNFOCUSED);
+            }
+        }
+    }
+}
+
+impl<E: ?Sized + Element> Element for Focusable<E> {
+    #[inline]
+    fn size_hint(
+        &mut self,
+        elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        space: Size,
+    ) -> SizeHint {
+        self.child.size_hint(elem_context, layout_context, space)
+    }
+
+    #[inline]
+    fn place(
+        &mut self,
+        elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        pos: Point,
+        size: Size,
+    ) {
+        self.child.place(elem_context, layout_context, pos, size);
+    }
+
+    #[inline]
+    fn hit_test(&self, point: Point) -> bool {
+        self.child.hit_test(point)
+    }
+
+    fn draw(&mut self, elem_context: &ElemContext, scene: &mut vello::Scene) {
+        elem_context
+            .window
+            .with_focus_scope(self.state.focused(), || {
+                self.child.draw(elem_context, scene)
+            });
+    }
+
+    fn event(&mut self, elem_context: &ElemContext, event: &dyn Event) -> EventResult {
+        if let Some(pass) = event.downcast_ref::<FocusPass>() {
+            // Nested focusable elements must get a chance to see the pass first, since they
+            // are closer to the focused element.
+            if self.forward_event(elem_context, event).is_handled() {
+                return EventResult::Handled;
+            }
+
+            if !self.state.focused() {
+                return EventResult::Continue;
+            }
+
+            return self.forward_event(elem_context, pass.event());
+        }
+
+        if is_keyboard_event(event) {
+            // The child has already seen this event during the focus pass.
+            return EventResult::Continue;
+        }
+
+        let was_focused = self.state.focused();
+        self.state.remove_transient_states();
+        self.handle_focus_traversal(event);
+
+        let result = self.forward_event(elem_context, event);
+
+        let child = &self.child;
+        let pointer_result = self
+            .state
+            .handle_pointer_interactions(&mut |pt| child.hit_test(pt), event);
+
+        if was_focused != self.state.focused() {
+            if !self.state.focused() {
+                self.state.remove(InteractiveState::FOCUS_VISIBLE);
+            }
+            elem_context.window.request_redraw();
+        }
+
+        if result.is_handled() {
+            EventResult::Handled
+        } else {
+            pointer_result
+        }
+    }
+
+    #[inline]
+    fn begin(&mut self, elem_context: &ElemContext) {
+        self.child.begin(elem_context);
+    }
+
+    #[inline]
+    fn accessibility(&self, cx: &mut AccessibilityContext) {
+        self.child.accessibility(cx);
+    }
+}
@@ -0,0 +1,527 @@
+use {
+    super::{Length, flex::Direction},
+    crate::{
+        ElemContext, Element, LayoutContext, SizeHint,
+        accessibility::AccessibilityContext,
+        event::{Event, EventResult},
+    },
+    vello::{
+        Scene,
+        kurbo::{Point, Size, Vec2},
+    },
+};
+
+/// The size of a row or a column of a [`Grid`] element.
+#[derive(Clone, Debug, Default)]
+pub enum GridTrack {
+    /// The track has a fixed size.
+    Fixed(Length),
+    /// The track is as large as the largest child it contains.
+    ///
+    /// Only children that span a single track are taken into account.
+    #[default]
+    Auto,
+    /// The track takes a share of the space left once the other tracks have been sized.
+    ///
+    /// The associated value is the growth factor of the track. The remaining space is
+    /// distributed among fractional tracks in proportion to their factors. When the grid is not
+    /// constrained, fractional tracks behave like [`GridTrack::Auto`] tracks.
+    Fraction(f64),
+}
+
+/// A child of a [`Grid`] element.
+#[derive(Debug, Clone)]
+pub struct GridChild<E: ?Sized> {
+    /// The index of the first row covered by the child.
+    pub row: usize,
+    /// The index of the first column covered by the child.
+    pub column: usize,
+    /// The number of rows covered by the child.
+    pub row_span: usize,
+    /// The number of columns covered by the child.
+    pub column_span: usize,
+
+    /// Cached size hint of the child element.
+    size_hint: SizeHint,
+
+    pub child: E,
+}
+
+impl Default for GridChild<()> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(())
+    }
+}
+
+impl<E> GridChild<E> {
+    /// Creates a new [`GridChild`] covering the first cell of the grid.
+    pub fn new(child: E) -> Self {
+        Self {
+            row: 0,
+            column: 0,
+            row_span: 1,
+            column_span: 1,
+            size_hint: SizeHint::default(),
+            child,
+        }
+    }
+
+    /// Sets the cell in which this [`GridChild`] is placed.
+    pub fn at(mut self, row: usize, column: usize) -> Self {
+        self.row = row;
+        self.column = column;
+        self
+    }
+
+    /// Sets the number of rows and columns covered by this [`GridChild`].
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `rows` or `columns` is zero.
+    pub fn span(mut self, rows: usize, columns: usize) -> Self {
+        assert!(
+            rows > 0 && columns > 0,
+            "A grid child must cover at least one cell",
+        );
+        self.row_span = rows;
+        self.column_span = columns;
+        self
+    }
+
+    /// Sets the child of this [`GridChild`].
+    pub fn child<E2>(self, child: E2) -> GridChild<E2> {
+        GridChild {
+            row: self.row,
+            column: self.column,
+            row_span: self.row_span,
+            column_span: self.column_span,
+            size_hint: SizeHint::default(),
+            child,
+        }
+    }
+}
+
+impl<E: Element> From<E> for GridChild<E> {
+    #[inline]
+    fn from(child: E) -> Self {
+        Self::new(child)
+    }
+}
+
+/// An element that lays out its children in rows and columns.
+///
+/// Children are placed in a cell identified by its `(row, column)` coordinates, and may span
+/// multiple rows and columns. Each child is stretched to cover its cells (within the limits of
+/// its size hint).
+///
+/// Rows that are not explicitly configured through [`Grid::rows`] are [`GridTrack::Auto`]
+/// rows, and the grid has as many rows as needed to contain its children.
+#[derive(Default)]
+pub struct Grid<'a> {
+    /// The columns of the grid.
+    pub columns: Vec<GridTrack>,
+    /// The explicitly configured rows of the grid.
+    pub rows: Vec<GridTrack>,
+    /// The gap between two consecutive rows.
+    pub row_gap: Length,
+    /// The gap between two consecutive columns.
+    pub column_gap: Length,
+    pub children: Vec<Box<GridChild<dyn 'a + Element>>>,
+}
+
+impl<'a> Grid<'a> {
+    /// Sets the columns of this [`Grid`] element.
+    pub fn columns(mut self, columns: impl IntoIterator<Item = GridTrack>) -> Self {
+        self.columns = columns.into_iter().collect();
+        self
+    }
+
+    /// Adds a column to this [`Grid`] element.
+    pub fn column(mut self, column: GridTrack) -> Self {
+        self.columns.push(column);
+        self
+    }
+
+    /// Sets the rows of this [`Grid`] element.
+    pub fn rows(mut self, rows: impl IntoIterator<Item = GridTrack>) -> Self {
+        self.rows = rows.into_iter().collect();
+        self
+    }
+
+    /// Adds a row to this [`Grid`] element.
+    pub fn row(mut self, row: GridTrack) -> Self {
+        self.rows.push(row);
+        self
+    }
+
+    /// Sets the gap between two consecutive rows of this [`Grid`] element.
+    pub fn row_gap(mut self, gap: Length) -> Self {
+        self.row_gap = gap;
+        self
+    }
+
+    /// Sets the gap between two consecutive columns of this [`Grid`] element.
+    pub fn column_gap(mut self, gap: Length) -> Self {
+        self.column_gap = gap;
+        self
+    }
+
+    /// Sets both the row gap and the column gap of this [`Grid`] element.
+    pub fn gap(self, gap: Length) -> Self {
+        self.row_gap(gap.clone()).column_gap(gap)
+    }
+
+    /// Adds a child to this [`Grid`] element.
+    pub fn child<E: Element + 'a>(mut self, child: impl Into<GridChild<E>>) -> Self {
+        self.children.push(Box::new(child.into()));
+        self
+    }
+
+    /// Returns the number of columns of the grid.
+    ///
+    /// This includes the columns implicitly created by children placed past the last
+    /// configured column.
+    fn column_count(&self) -> usize {
+        self.children
+            .iter()
+            .map(|child| child.column + child.column_span)
+            .fold(self.columns.len(), usize::max)
+    }
+
+    /// Returns the number of rows of the grid.
+    ///
+    /// This includes the rows implicitly created by children placed past the last configured
+    /// row.
+    fn row_count(&self) -> usize {
+        self.children
+            .iter()
+            .map(|child| child.row + child.row_span)
+            .fold(self.rows.len(), usize::max)
+    }
+
+    /// Computes and caches the size hints of the children.
+    fn compute_children_size_hints(&mut self, elem_context: &ElemContext, parent: LayoutContext) {
+        let space = Size::new(f64::INFINITY, f64::INFINITY);
+        for child in &mut self.children {
+            child.size_hint = child.child.size_hint(elem_context, parent, space);
+        }
+    }
+
+    /// Computes the sizes of the columns and the rows of the grid.
+    ///
+    /// The size hints of the children must have been computed beforehand.
+    ///
+    /// # Parameters
+    ///
+    /// - `layout_context`: The layout context used to resolve the fixed tracks.
+    ///
+    /// - `available`: The size available to the grid. Infinite components make fractional
+    ///   tracks behave like auto tracks, and zero components make them collapse.
+    fn compute_tracks(
+        &self,
+        layout_context: &LayoutContext,
+        available: Size,
+    ) -> (TrackLayout, TrackLayout) {
+        let columns = TrackLayout::compute(
+            &self.columns,
+            self.column_count(),
+            self.column_gap
+                .resolve_along(layout_context, Direction::Horizontal),
+            layout_context,
+            Direction::Horizontal,
+            available.width,
+            self.children
+                .iter()
+                .filter(|c| c.column_span == 1)
+                .map(|c| {
+                    (
+                        c.column,
+                        preferred_or_min(c.size_hint.preferred.width, c.size_hint.min.width),
+                    )
+                }),
+        );
+
+        let rows = TrackLayout::compute(
+            &self.rows,
+            self.row_count(),
+            self.row_gap
+                .resolve_along(layout_context, Direction::Vertical),
+            layout_context,
+            Direction::Vertical,
+            available.height,
+            self.children.iter().filter(|c| c.row_span == 1).map(|c| {
+                (
+                    c.row,
+                    preferred_or_min(c.size_hint.preferred.height, c.size_hint.min.height),
+                )
+            }),
+        );
+
+        (columns, rows)
+    }
+}
+
+impl std::fmt::Debug for Grid<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Grid")
+            .field("columns", &self.columns)
+            .field("rows", &self.rows)
+            .field("row_gap", &self.row_gap)
+            .field("column_gap", &self.column_gap)
+            .field("children", &self.children.len())
+            .finish()
+    }
+}
+
+/// Returns the preferred length of a child, or its minimum length if the preferred length is
+/// not finite.
+fn preferred_or_min(preferred: f64, min: f64) -> f64 {
+    if preferred.is_finite() {
+        preferred
+    } else {
+        min
+    }
+}
+
+/// The computed positions of the rows or the columns of a [`Grid`] element.
+struct TrackLayout {
+    /// The offset of each track from the start of the grid.
+    offsets: Vec<f64>,
+    /// The size of each track.
+    sizes: Vec<f64>,
+}
+
+impl TrackLayout {
+    /// Computes the layout of the tracks along one axis.
+    ///
+    /// # Parameters
+    ///
+    /// - `tracks`: The explicitly configured tracks. Tracks past the end of this list are
+    ///   [`GridTrack::Auto`] tracks.
+    ///
+    /// - `count`: The total number of tracks.
+    ///
+    /// - `gap`: The gap between two consecutive tracks.
+    ///
+    /// - `layout_context`: The layout context used to resolve fixed tracks.
+    ///
+    /// - `axis`: The axis along which the tracks are laid out.
+    ///
+    /// - `available`: The length available to the tracks, including the gaps.
+    ///
+    /// - `contents`: The `(track, length)` pairs of the children that span a single track.
+    fn compute(
+        tracks: &[GridTrack],
+        count: usize,
+        gap: f64,
+        layout_context: &LayoutContext,
+        axis: Direction,
+        available: f64,
+        contents: impl Iterator<Item = (usize, f64)>,
+    ) -> Self {
+        let mut content_sizes = vec![0.0f64; count];
+        for (track, length) in contents {
+            content_sizes[track] = content_sizes[track].max(length);
+        }
+
+        let mut sizes = vec![0.0; count];
+        let mut used = gap * count.saturating_sub(1) as f64;
+        let mut total_fraction = 0.0;
+
+        for (i, size) in sizes.iter_mut().enumerate() {
+            match tracks.get(i).unwrap_or(&GridTrack::Auto) {
+                GridTrack::Fixed(length) => {
+                    *size = length.resolve_along(layout_context, axis).max(0.0)
+                }
+                GridTrack::Auto => *size = content_sizes[i],
+                GridTrack::Fraction(fraction) => {
+                    total_fraction += fraction.max(0.0);
+                    continue;
+                }
+            }
+            used += *size;
+        }
+
+        if available.is_finite() {
+            let per_fraction = if total_fraction > 0.0 {
+                (available - used).max(0.0) / total_fraction
+            } else {
+                0.0
+            };
+
+            for (track, size) in tracks.iter().zip(&mut sizes) {
+                if let GridTrack::Fraction(fraction) = track {
+                    *size = fraction.max(0.0) * per_fraction;
+                }
+            }
+        } else {
+            for ((track, size), content) in tracks.iter().zip(&mut sizes).zip(&content_sizes) {
+                if let GridTrack::Fraction(_) = track {
+                    *size = *content;
+                }
+            }
+        }
+
+        let mut offsets = Vec::with_capacity(count);
+        let mut advance = 0.0;
+        for size in &sizes {
+            offsets.push(advance);
+            advance += size + gap;
+        }
+
+        Self { offsets, sizes }
+    }
+
+    /// Returns the total length of the tracks, including the gaps.
+    fn total_length(&self) -> f64 {
+        match (self.offsets.last(), self.sizes.last()) {
+            (Some(offset), Some(size)) => offset + size,
+            _ => 0.0,
+        }
+    }
+
+    /// Returns the offset and the length of the area covered by `span` tracks starting at
+    /// `start`.
+    fn span(&self, start: usize, span: usize) -> (f64, f64) {
+        let end = start + span - 1;
+        let length = self.offsets[end] + self.sizes[end] - self.offsets[start];
+        (self.offsets[start], length)
+    }
+}
+
+impl Element for Grid<'_> {
+    fn size_hint(
+        &mut self,
+        elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        space: Size,
+    ) -> SizeHint {
+        let child_layout_context = layout_context.with_parent(space);
+
+        self.compute_children_size_hints(elem_context, child_layout_context);
+
+        let infinite = Size::new(f64::INFINITY, f64::INFINITY);
+        let (columns, rows) = self.compute_tracks(&child_layout_context, infinite);
+        let preferred = Size::new(columns.total_length(), rows.total_length());
+
+        let (columns, rows) = self.compute_tracks(&child_layout_context, Size::ZERO);
+        let min = Size::new(columns.total_length(), rows.total_length());
+
+        SizeHint {
+            preferred,
+            min,
+            max: Size::new(f64::INFINITY, f64::INFINITY),
+        }
+    }
+
+    fn place(
+        &mut self,
+        elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        pos: Point,
+        size: Size,
+    ) {
+        let child_layout_context = layout_context.with_parent(size);
+
+        self.compute_children_size_hints(elem_context, child_layout_context);
+        let (columns, rows) = self.compute_tracks(&child_layout_context, size);
+
+        for child in &mut self.children {
+            let (x, width) = columns.span(child.column, child.column_span);
+            let (y, height) = rows.span(child.row, child.row_span);
+
+            let child_size =
+                Size::new(width, height).clamp(child.size_hint.min, child.size_hint.max);
+
+            child.child.place(
+                elem_context,
+                child_layout_context,
+                pos + Vec2::new(x, y),
+                child_size,
+            );
+        }
+    }
+
+    fn hit_test(&self, point: Point) -> bool {
+        self.children
+            .iter()
+            .any(|child| child.child.hit_test(point))
+    }
+
+    fn draw(&mut self, elem_context: &ElemContext, scene: &mut Scene) {
+        self.children
+            .iter_mut()
+            .for_each(|child| child.child.draw(elem_context, scene))
+    }
+
+    fn event(&mut self, elem_context: &ElemContext, event: &dyn Event) -> EventResult {
+        for child in &mut self.children {
+            if child.child.event(elem_context, event).is_handled() {
+                return EventResult::Handled;
+            }
+        }
+        EventResult::Continue
+    }
+
+    #[inline]
+    fn begin(&mut self, elem_context: &ElemContext) {
+        self.children
+            .iter_mut()
+            .for_each(|child| child.child.begin(elem_context));
+    }
+
+    fn accessibility(&self, cx: &mut AccessibilityContext) {
+        self.children
+            .iter()
+            .for_each(|child| child.child.accessibility(cx));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::GridTrack,
+        crate::{
+            HeadlessRenderer,
+            elements::{Length, grid, grid_child},
+            test_utils::Probe,
+        },
+        vello::kurbo::{Rect, Size},
+    };
+
+    #[test]
+    fn fractional_column_takes_leftover_width() {
+        let cells = [(0, 0), (0, 1), (1, 0), (1, 1)].map(|(row, column)| {
+            let (probe, rect) = Probe::new(30.0, 20.0);
+            (grid_child().at(row, column).child(probe), rect)
+        });
+
+        let mut root = grid()
+            .column(GridTrack::Fixed(Length::Pixels(100.0)))
+            .column(GridTrack::Fraction(1.0))
+            .gap(Length::Pixels(5.0));
+        let mut rects = Vec::new();
+        for (child, rect) in cells {
+            root = root.child(child);
+            rects.push(rect);
+        }
+
+        let renderer = HeadlessRenderer::new();
+        renderer.place_element(&mut root, Size::new(800.0, 400.0), 2.0);
+
+        // At a scale factor of 2, the fixed column is 200 pixels wide and the gaps are 10
+        // pixels. The fractional column gets the remaining 800 - 200 - 10 = 590 pixels, and the
+        // auto rows are as tall as their content.
+        let rects: Vec<Rect> = rects.iter().map(|rect| rect.get()).collect();
+        assert_eq!(
+            rects,
+            [
+                Rect::new(0.0, 0.0, 200.0, 20.0),
+                Rect::new(210.0, 0.0, 800.0, 20.0),
+                Rect::new(0.0, 30.0, 200.0, 50.0),
+                Rect::new(210.0, 30.0, 800.0, 50.0),
+            ]
+        );
+    }
+}
@@ -1,7 +1,11 @@
 use {
     crate::{
         ElemContext, Element, LayoutContext,
-        event::{Event, EventResult},
+        accessibility::AccessibilityContext,
+        event::{
+            Event, EventResult, PointerButton, PointerEnter, PointerLeave, PointerLeft,
+            PointerMoved,
+        },
     },
     vello::kurbo::{Point, Size},
 };
@@ -98,6 +102,11 @@ where
         self.child.begin(elem_context);
     }
 
+    #[inline]
+    fn accessibility(&self, cx: &mut AccessibilityContext) {
+        self.child.accessibility(cx);
+    }
+
     #[inline]
     fn hit_test(&self, point: Point) -> bool {
         self.child.hit_test(point)
@@ -136,3 +145,366 @@ where
         self.child.event(elem_context, event)
     }
 }
+
+/// The function called by [`HookResize`] when the size of its child changes.
+pub trait OnResize {
+    /// The size of the element has changed.
+    fn on_resize(&mut self, elem_context: &ElemContext, size: Size);
+}
+
+impl OnResize for () {
+    #[inline]
+    fn on_resize(&mut self, _elem_context: &ElemContext, _size: Size) {}
+}
+
+impl<F> OnResize for F
+where
+    F: FnMut(&ElemContext, Size),
+{
+    #[inline]
+    fn on_resize(&mut self, elem_context: &ElemContext, size: Size) {
+        self(elem_context, size)
+    }
+}
+
+/// An element that calls a function when the size of its child changes.
+///
+/// Elements are placed on every layout pass, even when their size is unchanged. This element
+/// remembers the size of the last pass so that expensive work (such as re-computing caches)
+/// only happens when the element is actually resized. Moving the element does not call the
+/// function.
+///
+/// Instances of this type are usually created through
+/// [`ElementExt::on_resize`](crate::ElementExt::on_resize).
+#[derive(Default, Clone, Debug)]
+pub struct HookResize<F, E: ?Sized> {
+    /// The hook function.
+    pub on_resize: F,
+    /// The size of the child during the last layout pass, if it has been placed already.
+    last_size: Option<Size>,
+    /// The child element.
+    pub child: E,
+}
+
+impl<F, E> HookResize<F, E> {
+    /// Creates a new `HookResize` element.
+    #[inline]
+    pub fn new(on_resize: F, child: E) -> Self
+    where
+        F: OnResize,
+    {
+        Self {
+            on_resize,
+            last_size: None,
+            child,
+        }
+    }
+
+    /// The hook function of this [`HookResize`].
+    #[inline]
+    pub fn on_resize<F2>(self, on_resize: F2) -> HookResize<F2, E>
+    where
+        F2: FnMut(&ElemContext, Size),
+    {
+        HookResize {
+            on_resize,
+            last_size: self.last_size,
+            child: self.child,
+        }
+    }
+
+    /// The child element of this [`HookResize`].
+    #[inline]
+    pub fn child<E2>(self, child: E2) -> HookResize<F, E2> {
+        HookResize {
+            on_resize: self.on_resize,
+            last_size: None,
+            child,
+        }
+    }
+}
+
+impl<F, E: ?Sized> HookResize<F, E> {
+    /// Returns the size of the child during the last layout pass.
+    ///
+    /// This is `None` until the element has been placed once.
+    #[inline]
+    pub fn last_size(&self) -> Option<Size> {
+        self.last_size
+    }
+}
+
+impl<F, E> Element for HookResize<F, E>
+where
+    F: OnResize,
+    E: Element + ?Sized,
+{
+    #[inline]
+    fn draw(&mut self, elem_context: &ElemContext, scene: &mut vello::Scene) {
+        self.child.draw(elem_context, scene);
+    }
+
+    #[inline]
+    fn begin(&mut self, elem_context: &ElemContext) {
+        self.child.begin(elem_context);
+    }
+
+    #[inline]
+    fn accessibility(&self, cx: &mut AccessibilityContext) {
+        self.child.accessibility(cx);
+    }
+
+    #[inline]
+    fn hit_test(&self, point: Point) -> bool {
+        self.child.hit_test(point)
+    }
+
+    fn place(
+        &mut self,
+        elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        pos: Point,
+        size: Size,
+    ) {
+        self.child.place(elem_context, layout_context, pos, size);
+
+        if self.last_size != Some(size) {
+            self.last_size = Some(size);
+            self.on_resize.on_resize(elem_context, size);
+        }
+    }
+
+    #[inline]
+    fn size_hint(
+        &mut self,
+        elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        space: Size,
+    ) -> crate::SizeHint {
+        self.child.size_hint(elem_context, layout_context, space)
+    }
+
+    #[inline]
+    fn event(&mut self, elem_context: &ElemContext, event: &dyn Event) -> EventResult {
+        self.child.event(elem_context, event)
+    }
+}
+
+/// The function called by [`HookHover`] when the pointer enters or leaves its child.
+pub trait OnHover {
+    /// The pointer entered (`hovered` is `true`) or left (`hovered` is `false`) the element.
+    fn on_hover(&mut self, elem_context: &ElemContext, hovered: bool);
+}
+
+impl OnHover for () {
+    #[inline]
+    fn on_hover(&mut self, _elem_context: &ElemContext, _hovered: bool) {}
+}
+
+impl<F> OnHover for F
+where
+    F: FnMut(&ElemContext, bool),
+{
+    #[inline]
+    fn on_hover(&mut self, elem_context: &ElemContext, hovered: bool) {
+        self(elem_context, hovered)
+    }
+}
+
+/// An element that tracks whether the pointer is over its child.
+///
+/// The element hit-tests its child every time the pointer moves, and compares the result with
+/// the previous move. When the pointer enters the child, a [`PointerEnter`] event is dispatched
+/// to the child and the hook function is called. When it leaves (either because it moved
+/// elsewhere or because it left the window), a [`PointerLeave`] event is dispatched instead.
+///
+/// This spares the child from comparing its hit-test with the position of the pointer on every
+/// [`PointerMoved`] event.
+///
+/// Instances of this type are usually created through
+/// [`ElementExt::on_hover`](crate::ElementExt::on_hover).
+#[derive(Default, Clone, Debug)]
+pub struct HookHover<F, E: ?Sized> {
+    /// The hook function.
+    pub on_hover: F,
+    /// Whether the pointer was over the child during the last pointer event.
+    hovered: bool,
+    /// The child element.
+    pub child: E,
+}
+
+impl<F, E> HookHover<F, E> {
+    /// Creates a new `HookHover` element.
+    #[inline]
+    pub fn new(on_hover: F, child: E) -> Self
+    where
+        F: OnHover,
+    {
+        Self {
+            on_hover,
+            hovered: false,
+            child,
+        }
+    }
+
+    /// The hook function of this [`HookHover`].
+    #[inline]
+    pub fn on_hover<F2>(self, on_hover: F2) -> HookHover<F2, E>
+    where
+        F2: FnMut(&ElemContext, bool),
+    {
+        HookHover {
+            on_hover,
+            hovered: self.hovered,
+            child: self.child,
+        }
+    }
+
+    /// The child element of this [`HookHover`].
+    #[inline]
+    pub fn child<E2>(self, child: E2) -> HookHover<F, E2> {
+        HookHover {
+            on_hover: self.on_hover,
+            hovered: false,
+            child,
+        }
+    }
+}
+
+impl<F, E: ?Sized> HookHover<F, E> {
+    /// Returns whether the pointer is currently over the child.
+    #[inline]
+    pub fn is_hovered(&self) -> bool {
+        self.hovered
+    }
+}
+
+impl<F, E> HookHover<F, E>
+where
+    F: OnHover,
+    E: Element + ?Sized,
+{
+    /// Updates the hover state of the element, notifying the child and the hook function if
+    /// it changed.
+    fn set_hovered(&mut self, elem_context: &ElemContext, hovered: bool, position: Point) {
+        if self.hovered == hovered {
+            return;
+        }
+
+        self.hovered = hovered;
+
+        if hovered {
+            self.child.event(elem_context, &PointerEnter { position });
+        } else {
+            self.child.event(elem_context, &PointerLeave);
+        }
+
+        self.on_hover.on_hover(elem_context, hovered);
+    }
+}
+
+impl<F, E> Element for HookHover<F, E>
+where
+    F: OnHover,
+    E: Element + ?Sized,
+{
+    #[inline]
+    fn draw(&mut self, elem_context: &ElemContext, scene: &mut vello::Scene) {
+        self.child.draw(elem_context, scene);
+    }
+
+    #[inline]
+    fn begin(&mut self, elem_context: &ElemContext) {
+        self.child.begin(elem_context);
+    }
+
+    #[inline]
+    fn accessibility(&self, cx: &mut AccessibilityContext) {
+        self.child.accessibility(cx);
+    }
+
+    #[inline]
+    fn hit_test(&self, point: Point) -> bool {
+        self.child.hit_test(point)
+    }
+
+    #[inline]
+    fn place(
+        &mut self,
+        elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        pos: Point,
+        size: Size,
+    ) {
+        self.child.place(elem_context, layout_context, pos, size);
+    }
+
+    #[inline]
+    fn size_hint(
+        &mut self,
+        elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        space: Size,
+    ) -> crate::SizeHint {
+        self.child.size_hint(elem_context, layout_context, space)
+    }
+
+    fn event(&mut self, elem_context: &ElemContext, event: &dyn Event) -> EventResult {
+        // The hover state is updated before the child sees the pointer event, so that it
+        // receives the enter (or leave) event first.
+        if let Some(ev) = event.downcast_ref::<PointerMoved>() {
+            if ev.primary {
+                let hovered = self.child.hit_test(ev.position);
+                self.set_hovered(elem_context, hovered, ev.position);
+            }
+        } else if let Some(ev) = event.downcast_ref::<PointerButton>() {
+            // Touch screens may press without moving the pointer first.
+            if ev.primary {
+                let hovered = self.child.hit_test(ev.position);
+                self.set_hovered(elem_context, hovered, ev.position);
+            }
+        } else if let Some(ev) = event.downcast_ref::<PointerLeft>() {
+            if ev.primary {
+                self.set_hovered(elem_context, false, Point::ORIGIN);
+            }
+        }
+
+        self.child.event(elem_context, event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        crate::{Element, ElementExt, HeadlessRenderer, LayoutContext},
+        std::{cell::RefCell, rc::Rc},
+        vello::kurbo::{Point, Size},
+    };
+
+    #[test]
+    fn moving_does_not_trigger_resize() {
+        let sizes = Rc::new(RefCell::new(Vec::new()));
+        let mut elem = ().on_resize({
+            let sizes = sizes.clone();
+            move |_, size| sizes.borrow_mut().push(size)
+        });
+
+        let renderer = HeadlessRenderer::new();
+        let elem_context = renderer.elem_context();
+        let layout_context = LayoutContext::root(Size::new(800.0, 600.0), 1.0);
+        let mut place = |pos: Point, size: Size| {
+            elem.place(&elem_context, layout_context, pos, size);
+        };
+
+        place(Point::new(0.0, 0.0), Size::new(100.0, 50.0));
+        place(Point::new(30.0, 20.0), Size::new(100.0, 50.0));
+        place(Point::new(60.0, 40.0), Size::new(100.0, 50.0));
+        assert_eq!(*sizes.borrow(), [Size::new(100.0, 50.0)]);
+
+        place(Point::new(60.0, 40.0), Size::new(120.0, 50.0));
+        assert_eq!(
+            *sizes.borrow(),
+            [Size::new(100.0, 50.0), Size::new(120.0, 50.0)]
+        );
+    }
+}
@@ -0,0 +1,301 @@
+use {
+    super::{Length, flex::Direction},
+    crate::{
+        ElemContext, Element, ImageBuffer, LayoutContext, SizeHint,
+        accessibility::{AccessibilityContext, to_accesskit_rect},
+    },
+    accesskit::{Node, Role},
+    std::path::Path,
+    vello::{
+        kurbo::{Affine, Point, Rect, Size},
+        peniko::{self, Blob, ImageFormat, Mix},
+    },
+};
+
+/// An error that might occur when loading an image from a file.
+#[derive(Debug)]
+pub enum LoadImageError {
+    /// The file could not be read.
+    Io(std::io::Error),
+    /// The file is not an image in a supported format, or is corrupted.
+    Decode(image::ImageError),
+}
+
+impl std::fmt::Display for LoadImageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "Failed to read the image: {err}"),
+            Self::Decode(err) => write!(f, "Failed to decode the image: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for LoadImageError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Decode(err) => Some(err),
+        }
+    }
+}
+
+impl From<image::ImageError> for LoadImageError {
+    fn from(err: image::ImageError) -> Self {
+        match err {
+            image::ImageError::IoError(err) => Self::Io(err),
+            err => Self::Decode(err),
+        }
+    }
+}
+
+/// Loads and decodes the image at the provided path.
+///
+/// PNG and JPEG images are supported. The format is guessed from the content of the file.
+pub fn load_image(path: &Path) -> Result<ImageBuffer, LoadImageError> {
+    let image = image::ImageReader::open(path)
+        .map_err(LoadImageError::Io)?
+        .with_guessed_format()
+        .map_err(LoadImageError::Io)?
+        .decode()?
+        .into_rgba8();
+
+    Ok(ImageBuffer {
+        width: image.width(),
+        height: image.height(),
+        data: image.into_raw(),
+    })
+}
+
+/// How an [`Image`] is scaled to fit the space given to the element.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ImageFit {
+    /// The image is scaled to fit entirely within the element, preserving its aspect ratio.
+    ///
+    /// The image is centered, leaving empty space on two of its sides when the aspect ratios
+    /// differ.
+    #[default]
+    Contain,
+    /// The image is scaled to cover the whole element, preserving its aspect ratio.
+    ///
+    /// The image is centered, and the parts that overflow the element are clipped.
+    Cover,
+    /// The image is stretched to cover the element exactly, ignoring its aspect ratio.
+    Stretch,
+}
+
+/// An element that displays a bitmap image.
+///
+/// When no explicit size is provided, the element prefers the intrinsic size of the image,
+/// where one pixel of the image covers one *scaled* pixel of the window. When only one
+/// dimension is provided, the other one is derived from the aspect ratio of the image.
+#[derive(Clone, Debug, Default)]
+pub struct Image {
+    /// The image being displayed.
+    image: Option<peniko::Image>,
+    /// How the image is scaled to fit the element.
+    pub fit: ImageFit,
+    /// The explicit width of the element.
+    pub width: Option<Length>,
+    /// The explicit height of the element.
+    pub height: Option<Length>,
+    /// The accessible description of the image.
+    pub alt: Option<String>,
+
+    /// The bounds of the element.
+    rect: Rect,
+}
+
+impl Image {
+    /// Creates a new [`Image`] element displaying the provided image.
+    pub fn new(buffer: ImageBuffer) -> Self {
+        Self::default().image(buffer)
+    }
+
+    /// Creates a new [`Image`] element displaying the image at the provided path.
+    ///
+    /// See [`load_image`] for the supported formats.
+    pub fn open(path: &Path) -> Result<Self, LoadImageError> {
+        load_image(path).map(Self::new)
+    }
+
+    /// Sets the image displayed by the element.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the size of the buffer does not match its dimensions.
+    pub fn image(mut self, buffer: ImageBuffer) -> Self {
+        self.set_image(Some(buffer));
+        self
+    }
+
+    /// Sets how the image is scaled to fit the element.
+    pub fn fit(mut self, fit: ImageFit) -> Self {
+        self.fit = fit;
+        self
+    }
+
+    /// Sets the explicit width of the element.
+    pub fn width(mut self, width: impl Into<Option<Length>>) -> Self {
+        self.width = width.into();
+        self
+    }
+
+    /// Sets the explicit height of the element.
+    pub fn height(mut self, height: impl Into<Option<Length>>) -> Self {
+        self.height = height.into();
+        self
+    }
+
+    /// Sets the accessible description of the image.
+    pub fn alt(mut self, alt: impl Into<String>) -> Self {
+        self.alt = Some(alt.into());
+        self
+    }
+
+    /// Replaces the image displayed by the element.
+    ///
+    /// The caller is responsible for requesting a relayout if the intrinsic size of the image
+    /// changed.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the size of the buffer does not match its dimensions.
+    pub fn set_image(&mut self, buffer: Option<ImageBuffer>) {
+        self.image = buffer.map(|buffer| {
+            assert_eq!(
+                buffer.data.len(),
+                buffer.width as usize * buffer.height as usize * 4,
+                "The image buffer does not match its dimensions",
+            );
+
+            peniko::Image::new(
+                Blob::from(buffer.data),
+                ImageFormat::Rgba8,
+                buffer.width,
+                buffer.height,
+            )
+        });
+    }
+
+    /// Returns the intrinsic size of the image, in pixels.
+    fn intrinsic_size(&self) -> Size {
+        match &self.image {
+            Some(image) => Size::new(image.width as f64, image.height as f64),
+            None => Size::ZERO,
+        }
+    }
+
+    /// Resolves the preferred size of the element.
+    fn resolve_size(&self, layout_context: &LayoutContext) -> Size {
+        let intrinsic = self.intrinsic_size();
+        let width = self
+            .width
+            .as_ref()
+            .map(|w| w.resolve_along(layout_context, Direction::Horizontal));
+        let height = self
+            .height
+            .as_ref()
+            .map(|h| h.resolve_along(layout_context, Direction::Vertical));
+
+        match (width, height) {
+            (Some(width), Some(height)) => Size::new(width, height),
+            (Some(width), None) if intrinsic.width > 0.0 => {
+                Size::new(width, width * intrinsic.height / intrinsic.width)
+            }
+            (None, Some(height)) if intrinsic.height > 0.0 => {
+                Size::new(height * intrinsic.width / intrinsic.height, height)
+            }
+            (Some(width), None) => Size::new(width, 0.0),
+            (None, Some(height)) => Size::new(0.0, height),
+            (None, None) => intrinsic * layout_context.scale_factor,
+        }
+    }
+
+    /// Computes the rectangle in which the image is drawn, based on the bounds of the element.
+    fn image_rect(&self) -> Rect {
+        let intrinsic = self.intrinsic_size();
+        if self.fit == ImageFit::Stretch || intrinsic.is_zero_area() {
+            return self.rect;
+        }
+
+        let scale_x = self.rect.width() / intrinsic.width;
+        let scale_y = self.rect.height() / intrinsic.height;
+        let scale = match self.fit {
+            ImageFit::Contain => scale_x.min(scale_y),
+            ImageFit::Cover => scale_x.max(scale_y),
+            ImageFit::Stretch => unreachable!(),
+        };
+
+        Rect::from_center_size(self.rect.center(), intrinsic * scale)
+    }
+}
+
+impl Element for Image {
+    fn size_hint(
+        &mut self,
+        _elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        _space: Size,
+    ) -> SizeHint {
+        let preferred = self.resolve_size(&layout_context);
+
+        SizeHint {
+            preferred,
+            min: Size::ZERO,
+            max: Size::new(f64::INFINITY, f64::INFINITY),
+        }
+    }
+
+    fn place(
+        &mut self,
+        _elem_context: &ElemContext,
+        _layout_context: LayoutContext,
+        pos: Point,
+        size: Size,
+    ) {
+        self.rect = Rect::from_origin_size(pos, size);
+    }
+
+    fn hit_test(&self, point: Point) -> bool {
+        self.rect.contains(point)
+    }
+
+    fn draw(&mut self, _elem_context: &ElemContext, scene: &mut vello::Scene) {
+        let Some(image) = self.image.as_ref() else {
+            return;
+        };
+
+        let intrinsic = self.intrinsic_size();
+        if intrinsic.is_zero_area() || self.rect.is_zero_area() {
+            return;
+        }
+
+        let dest = self.image_rect();
+        let transform = Affine::translate(dest.origin().to_vec2())
+            * Affine::scale_non_uniform(
+                dest.width() / intrinsic.width,
+                dest.height() / intrinsic.height,
+            );
+
+        // Covering images overflow the element.
+        let clip = self.fit == ImageFit::Cover;
+        if clip {
+            scene.push_layer(Mix::Clip, 1.0, Affine::IDENTITY, &self.rect);
+        }
+
+        scene.draw_image(image, transform);
+
+        if clip {
+            scene.pop_layer();
+        }
+    }
+
+    fn accessibility(&self, cx: &mut AccessibilityContext) {
+        let mut node = Node::new(Role::Image);
+        node.set_bounds(to_accesskit_rect(self.rect));
+        if let Some(alt) = self.alt.as_deref() {
+            node.set_description(alt);
+        }
+        cx.push(node);
+    }
+}
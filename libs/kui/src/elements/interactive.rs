@@ -1,8 +1,13 @@
 use {
     crate::{
         ElemContext, Element, LayoutContext, SizeHint,
-        event::{Event, EventResult, PointerButton, PointerLeft, PointerMoved},
+        accessibility::{AccessibilityAction, AccessibilityContext, NodeIdCell},
+        event::{
+            CollectFocusTargets, Event, EventResult, MoveFocus, PointerButton, PointerLeft,
+            PointerMoved,
+        },
     },
+    accesskit::Action,
     bitflags::bitflags,
     vello::kurbo::{Point, Size},
     winit::event::{ButtonSource, MouseButton},
@@ -46,6 +51,10 @@ bitflags! {
 
         /// The value of the element changed.
         const VALUE_CHANGED = 1 << 12;
+
+        /// The element requested the focus. It will receive it once the current event has been
+        /// dispatched.
+        const FOCUS_REQUESTED = 1 << 13;
     }
 }
 
@@ -142,6 +151,70 @@ impl InteractiveState {
         self.contains(InteractiveState::JUST_UNFOCUSED)
     }
 
+    /// Handles the focus traversal events ([`CollectFocusTargets`] and [`MoveFocus`]),
+    /// updating the state of the element accordingly.
+    ///
+    /// Disabled elements cannot receive the focus.
+    pub fn handle_focus_traversal(&mut self, event: &dyn Event) {
+        if self.disabled() {
+            return;
+        }
+
+        if let Some(ev) = event.downcast_ref::<CollectFocusTargets>() {
+            if self.contains(InteractiveState::FOCUS_REQUESTED) {
+                ev.register_requested();
+            } else {
+                ev.register(self.focused());
+            }
+        } else if let Some(ev) = event.downcast_ref::<MoveFocus>() {
+            self.remove(InteractiveState::FOCUS_REQUESTED);
+
+            if ev.next() {
+                if !self.focused() {
+                    self.insert(InteractiveState::JUST_FOCUSED);
+                }
+                // The focus was moved with the keyboard, so it should be visible.
+                self.insert(InteractiveState::FOCUS | InteractiveState::FOCUS_VISIBLE);
+            } else if self.focused() {
+                self.remove(InteractiveState::FOCUS | InteractiveState::FOCUS_VISIBLE);
+                self.insert(InteractiveState::JUST_UNFOCUSED);
+            }
+        }
+    }
+
+    /// Handles the [`AccessibilityAction`] events targeting the node identified by `node_id`.
+    ///
+    /// Focus requests are handled here, the focus being moved to the element once the current
+    /// event has been dispatched. Disabled elements ignore every action.
+    ///
+    /// # Returns
+    ///
+    /// The other actions requested for the element, which must be handled by the caller.
+    pub fn handle_accessibility_action(
+        &mut self,
+        elem_context: &ElemContext,
+        node_id: &NodeIdCell,
+        event: &dyn Event,
+    ) -> Option<Action> {
+        if self.disabled() {
+            return None;
+        }
+
+        let action = event
+            .downcast_ref::<AccessibilityAction>()?
+            .action_for(node_id)?;
+
+        if action != Action::Focus {
+            return Some(action);
+        }
+
+        elem_context.request_focus();
+        if elem_context.window.claim_focus_request() {
+            self.insert(InteractiveState::FOCUS_REQUESTED);
+        }
+        None
+    }
+
     /// Handles the provided event, updating the state of the element accordingly.
     pub fn handle_pointer_interactions(
         &mut self,
@@ -289,6 +362,11 @@ where
     fn begin(&mut self, elem_context: &ElemContext) {
         self.child.begin(elem_context);
     }
+
+    #[inline]
+    fn accessibility(&self, cx: &mut AccessibilityContext) {
+        self.child.accessibility(cx);
+    }
 }
 
 impl<F, E, T> Appearance<T> for AppearanceFn<F, E>
@@ -0,0 +1,197 @@
+use {
+    crate::{
+        ElemContext, Element, LayoutContext, SizeHint,
+        accessibility::AccessibilityContext,
+        event::{Event, EventResult},
+    },
+    vello::kurbo::{Point, Size},
+};
+
+/// A rendering layer.
+///
+/// Elements in higher layers are drawn after (on top of) every element of lower layers,
+/// regardless of their position in the UI tree. They also receive events before them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Layer {
+    /// The regular layer, in which elements are drawn in tree order.
+    #[default]
+    Base,
+    /// A layer for things that float over the regular content, such as tooltips or drag ghosts.
+    Overlay,
+    /// A layer for popups and menus, drawn over everything else.
+    Popup,
+}
+
+impl Layer {
+    /// The layers that are drawn after the base layer, from the lowest to the highest.
+    pub(crate) const ELEVATED: [Layer; 2] = [Layer::Overlay, Layer::Popup];
+
+    /// Returns the index of an elevated layer within [`Layer::ELEVATED`].
+    ///
+    /// Returns `None` for [`Layer::Base`].
+    #[inline]
+    pub(crate) fn elevated_index(self) -> Option<usize> {
+        match self {
+            Layer::Base => None,
+            Layer::Overlay => Some(0),
+            Layer::Popup => Some(1),
+        }
+    }
+}
+
+/// An event used internally to give elevated layers a chance to handle events before the base
+/// layer.
+///
+/// When the window dispatches an event, it first dispatches a [`LayerPass`] for every elevated
+/// layer (highest first), and only then the event itself. [`Layered`] elements forward the
+/// wrapped event to their child during the pass of their own layer, and ignore the event
+/// during the base pass.
+pub(crate) struct LayerPass {
+    /// The layer being dispatched.
+    pub layer: Layer,
+    /// The event being dispatched.
+    ///
+    /// This pointer is only valid for the duration of the dispatch.
+    event: *const dyn Event,
+}
+
+impl LayerPass {
+    /// Creates a new [`LayerPass`] for the provided event.
+    #[inline]
+    pub fn new(layer: Layer, event: &dyn Event) -> Self {
+        Self {
+            layer,
+            event: event as *const dyn Event,
+        }
+    }
+
+    /// Returns the wrapped event.
+    #[inline]
+    pub fn event(&self) -> &dyn Event {
+        // SAFETY: `LayerPass` instances are only created by the window while it dispatches the
+        // wrapped event, and elements only receive a reference to it during that time.
+        unsafe { &*self.event }
+    }
+}
+
+/// An element that draws its child in a specific [`Layer`].
+///
+/// This is used for overlays (tooltips, drag ghosts, popups) that must appear on top of the rest
+/// of the UI even though they are located in the middle of the tree.
+///
+/// The child is laid out normally by its parent. Only its paint order and its priority when
+/// handling events change.
+#[derive(Clone, Debug, Default)]
+pub struct Layered<E: ?Sized> {
+    /// The layer in which the child is drawn.
+    pub layer: Layer,
+    /// The child element.
+    pub child: E,
+}
+
+impl<E> Layered<E> {
+    /// Creates a new [`Layered`] element.
+    #[inline]
+    pub fn new(layer: Layer, child: E) -> Self {
+        Self { layer, child }
+    }
+
+    /// Sets the layer in which the child is drawn.
+    pub fn layer(mut self, layer: Layer) -> Self {
+        self.layer = layer;
+        self
+    }
+
+    /// Draws the child in the [`Layer::Overlay`] layer.
+    pub fn overlay(self) -> Self {
+        self.layer(Layer::Overlay)
+    }
+
+    /// Draws the child in the [`Layer::Popup`] layer.
+    pub fn popup(self) -> Self {
+        self.layer(Layer::Popup)
+    }
+
+    /// Sets the child element of this [`Layered`] element.
+    pub fn child<E2>(self, child: E2) -> Layered<E2> {
+        Layered {
+            layer: self.layer,
+            child,
+        }
+    }
+}
+
+impl<E: ?Sized + Element> Element for Layered<E> {
+    #[inline]
+    fn size_hint(
+        &mut self,
+        elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        space: Size,
+    ) -> SizeHint {
+        self.child.size_hint(elem_context, layout_context, space)
+    }
+
+    #[inline]
+    fn place(
+        &mut self,
+        elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        pos: Point,
+        size: Size,
+    ) {
+        self.child.place(elem_context, layout_context, pos, size);
+    }
+
+    #[inline]
+    fn hit_test(&self, point: Point) -> bool {
+        self.child.hit_test(point)
+    }
+
+    fn draw(&mut self, elem_context: &ElemContext, scene: &mut vello::Scene) {
+        if self.layer == Layer::Base {
+            self.child.draw(elem_context, scene);
+            return;
+        }
+
+        let drawn = elem_context
+            .window
+            .with_layer_scene(self.layer, &mut |layer_scene| {
+                self.child.draw(elem_context, layer_scene)
+            });
+
+        if !drawn {
+            // The window is not available (or the layer is already being drawn to), draw
+            // directly in the current scene.
+            self.child.draw(elem_context, scene);
+        }
+    }
+
+    fn event(&mut self, elem_context: &ElemContext, event: &dyn Event) -> EventResult {
+        if let Some(pass) = event.downcast_ref::<LayerPass>() {
+            if pass.layer == self.layer {
+                return self.child.event(elem_context, pass.event());
+            }
+
+            // Nested layered elements must get a chance to see the pass.
+            return self.child.event(elem_context, event);
+        }
+
+        if self.layer != Layer::Base && elem_context.window.is_layer_active(self.layer) {
+            // The event has already been dispatched to this element during its layer pass.
+            return EventResult::Continue;
+        }
+
+        self.child.event(elem_context, event)
+    }
+
+    #[inline]
+    fn begin(&mut self, elem_context: &ElemContext) {
+        self.child.begin(elem_context);
+    }
+
+    #[inline]
+    fn accessibility(&self, cx: &mut AccessibilityContext) {
+        self.child.accessibility(cx);
+    }
+}
@@ -1,13 +1,33 @@
 mod types;
 pub use self::types::*;
 
+pub mod accessible_label;
 pub mod anchor;
+pub mod axis;
 pub mod button;
+pub mod check_box;
+pub mod color_picker;
 pub mod div;
+pub mod drop_target;
+pub mod either;
 pub mod flex;
+pub mod focusable;
+pub mod grid;
 pub mod hooks;
+pub mod image;
+pub mod layer;
+pub mod number_field;
+pub mod popup;
+pub mod scheme;
+pub mod scroll;
+pub mod selection;
+pub mod separator;
+pub mod size_constraints;
+pub mod slider;
+pub mod tab_index;
 pub mod text;
 pub mod text_input;
+pub mod visibility;
 
 pub mod interactive;
 
@@ -18,6 +38,20 @@ pub fn div() -> self::div::Div<()> {
     self::div::Div::default()
 }
 
+/// Creates a new [`DropTarget`] element.
+///
+/// [`DropTarget`]: self::drop_target::DropTarget
+pub fn drop_target() -> self::drop_target::DropTarget<(), ()> {
+    self::drop_target::DropTarget::default()
+}
+
+/// Creates a new [`Focusable`] element.
+///
+/// [`Focusable`]: self::focusable::Focusable
+pub fn focusable() -> self::focusable::Focusable<()> {
+    self::focusable::Focusable::default()
+}
+
 /// Creates a new [`Anchor`] element.
 ///
 /// [`Anchor`]: self::anchor::Anchor
@@ -25,6 +59,13 @@ pub fn anchor() -> self::anchor::Anchor<()> {
     self::anchor::Anchor::default()
 }
 
+/// Creates a new [`Axis`] element with the provided mapping.
+///
+/// [`Axis`]: self::axis::Axis
+pub fn axis<M: self::axis::AxisMapping>(mapping: M) -> self::axis::Axis<M> {
+    self::axis::Axis::new(mapping)
+}
+
 /// Creates a new [`Text`] element.
 ///
 /// [`Text`]: self::text::Text
@@ -46,6 +87,20 @@ pub fn flex_child() -> self::flex::FlexChild<()> {
     self::flex::FlexChild::default()
 }
 
+/// Creates a new [`Grid`] element.
+///
+/// [`Grid`]: self::grid::Grid
+pub fn grid<'a>() -> self::grid::Grid<'a> {
+    self::grid::Grid::default()
+}
+
+/// Creates a new [`GridChild`] element.
+///
+/// [`GridChild`]: self::grid::GridChild
+pub fn grid_child() -> self::grid::GridChild<()> {
+    self::grid::GridChild::default()
+}
+
 /// Creates a new [`Button`] element.
 ///
 /// [`Button`]: self::button::Button
@@ -53,6 +108,20 @@ pub fn button() -> self::button::Button<()> {
     self::button::Button::new(())
 }
 
+/// Creates a new [`CheckBox`] element.
+///
+/// [`CheckBox`]: self::check_box::CheckBox
+pub fn check_box() -> self::check_box::CheckBox<()> {
+    self::check_box::CheckBox::default()
+}
+
+/// Creates a new [`ColorPicker`] element.
+///
+/// [`ColorPicker`]: self::color_picker::ColorPicker
+pub fn color_picker() -> self::color_picker::ColorPicker<()> {
+    self::color_picker::ColorPicker::default()
+}
+
 /// Creates a new [`HookEvents`] element.
 ///
 /// [`HookEvents`]: self::hooks::HookEvent
@@ -60,9 +129,103 @@ pub fn hook_events() -> self::hooks::HookEvent<(), ()> {
     self::hooks::HookEvent::new((), ())
 }
 
+/// Creates a new [`HookResize`] element.
+///
+/// [`HookResize`]: self::hooks::HookResize
+pub fn hook_resize() -> self::hooks::HookResize<(), ()> {
+    self::hooks::HookResize::new((), ())
+}
+
+/// Creates a new [`Image`] element displaying the provided image.
+///
+/// [`Image`]: self::image::Image
+pub fn image(buffer: crate::ImageBuffer) -> self::image::Image {
+    self::image::Image::new(buffer)
+}
+
+/// Creates a new [`HookHover`] element.
+///
+/// [`HookHover`]: self::hooks::HookHover
+pub fn hook_hover() -> self::hooks::HookHover<(), ()> {
+    self::hooks::HookHover::new((), ())
+}
+
+/// Creates a new [`Layered`] element.
+///
+/// [`Layered`]: self::layer::Layered
+pub fn layered() -> self::layer::Layered<()> {
+    self::layer::Layered::default()
+}
+
+/// Creates a new [`NumberField`] element.
+///
+/// [`NumberField`]: self::number_field::NumberField
+pub fn number_field() -> self::number_field::NumberField<(), ()> {
+    self::number_field::NumberField::default()
+}
+
+/// Creates a new [`SchemeHost`] element that builds its child from `state` using `scheme`.
+///
+/// [`SchemeHost`]: self::scheme::SchemeHost
+pub fn scheme_host<S, T>(scheme: S, state: T) -> self::scheme::SchemeHost<S, T>
+where
+    S: self::scheme::Scheme<T>,
+{
+    self::scheme::SchemeHost::new(scheme, state)
+}
+
+/// Creates a new [`Scroll`] element.
+///
+/// [`Scroll`]: self::scroll::Scroll
+pub fn scroll() -> self::scroll::Scroll<()> {
+    self::scroll::Scroll::default()
+}
+
+/// Creates a new [`Popup`] element.
+///
+/// [`Popup`]: self::popup::Popup
+pub fn popup() -> self::popup::Popup<(), ()> {
+    self::popup::Popup::default()
+}
+
+/// Creates a new [`SelectionArea`] element.
+///
+/// [`SelectionArea`]: self::selection::SelectionArea
+pub fn selection_area() -> self::selection::SelectionArea<(), ()> {
+    self::selection::SelectionArea::new((), ())
+}
+
+/// Creates a new [`Separator`] element.
+///
+/// [`Separator`]: self::separator::Separator
+pub fn separator() -> self::separator::Separator {
+    self::separator::Separator::default()
+}
+
+/// Creates a new [`Slider`] element.
+///
+/// [`Slider`]: self::slider::Slider
+pub fn slider() -> self::slider::Slider<()> {
+    self::slider::Slider::default()
+}
+
 /// Creates a new [`TextInput`] element.
 ///
 /// [`TextInput`]: self::text_input::TextInput
 pub fn text_input() -> self::text_input::TextInput<()> {
     self::text_input::TextInput::default()
 }
+
+/// Creates a new [`WithSizeConstraints`] element.
+///
+/// [`WithSizeConstraints`]: self::size_constraints::WithSizeConstraints
+pub fn with_size_constraints() -> self::size_constraints::WithSizeConstraints<()> {
+    self::size_constraints::WithSizeConstraints::default()
+}
+
+/// Creates a new [`WithVisibility`] element.
+///
+/// [`WithVisibility`]: self::visibility::WithVisibility
+pub fn with_visibility() -> self::visibility::WithVisibility<()> {
+    self::visibility::WithVisibility::default()
+}
@@ -0,0 +1,642 @@
+use {
+    crate::{
+        ElemContext, Element, LayoutContext, SizeHint,
+        accessibility::{AccessibilityContext, NodeIdCell},
+        elements::interactive::{Appearance, InteractiveState},
+        event::{Event, EventResult, KeyEvent, PointerButton, PointerMoved},
+    },
+    accesskit::{Action, Node, Role},
+    std::time::{Duration, Instant},
+    vello::kurbo::{Point, Size},
+    winit::keyboard::NamedKey,
+};
+
+/// The maximum delay between two clicks for them to be considered a double-click.
+const DOUBLE_CLICK_DELAY: Duration = Duration::from_millis(400);
+
+/// The function called by a [`NumberField`] when its value changes.
+pub trait OnNumberChange {
+    /// The value has changed.
+    fn on_change(&mut self, elem_context: &ElemContext, value: f64);
+}
+
+impl OnNumberChange for () {
+    #[inline]
+    fn on_change(&mut self, _elem_context: &ElemContext, _value: f64) {}
+}
+
+impl<F> OnNumberChange for F
+where
+    F: FnMut(&ElemContext, f64),
+{
+    #[inline]
+    fn on_change(&mut self, elem_context: &ElemContext, value: f64) {
+        self(elem_context, value)
+    }
+}
+
+/// Formats a number using the provided format string.
+///
+/// The first `{}` placeholder of the format string is replaced with the number. The placeholder
+/// may specify a precision, such as `{:.2}`. When the format string has no placeholder, the
+/// number is appended to it.
+pub fn format_number(format: &str, value: f64) -> String {
+    let Some((prefix, spec, suffix)) = split_format(format) else {
+        return format!("{format}{value}");
+    };
+
+    let precision = spec
+        .strip_prefix(":.")
+        .and_then(|p| p.parse::<usize>().ok());
+    let number = match precision {
+        Some(precision) => format!("{value:.precision$}"),
+        None => format!("{value}"),
+    };
+
+    format!("{prefix}{number}{suffix}")
+}
+
+/// Parses a number typed by the user.
+///
+/// The text may include the literal parts of the format string (such as a unit), which are
+/// ignored.
+///
+/// # Returns
+///
+/// The parsed number, or `None` if the text is not a valid number.
+pub fn parse_number(format: &str, text: &str) -> Option<f64> {
+    let mut text = text.trim();
+
+    if let Some((prefix, _, suffix)) = split_format(format) {
+        text = text.strip_prefix(prefix.trim()).unwrap_or(text).trim();
+        text = text.strip_suffix(suffix.trim()).unwrap_or(text).trim();
+    }
+
+    text.parse::<f64>().ok().filter(|x| x.is_finite())
+}
+
+/// Splits a format string into the text before its placeholder, the content of the placeholder
+/// and the text after it.
+fn split_format(format: &str) -> Option<(&str, &str, &str)> {
+    let start = format.find('{')?;
+    let len = format[start..].find('}')?;
+    Some((
+        &format[..start],
+        &format[start + 1..start + len],
+        &format[start + len + 1..],
+    ))
+}
+
+/// The information that a [`NumberField`] provides to its appearance.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NumberFieldDisplay {
+    /// The current value of the field.
+    pub value: f64,
+    /// The text that should be displayed.
+    ///
+    /// This is the formatted value, or the text typed by the user while editing.
+    pub text: String,
+    /// Whether the user is currently typing a value.
+    pub editing: bool,
+}
+
+/// The state of an ongoing drag gesture.
+#[derive(Clone, Copy, Debug)]
+struct Drag {
+    /// The position of the pointer when the drag started.
+    start: Point,
+    /// The value of the field when the drag started.
+    start_value: f64,
+}
+
+/// A numeric input that the user can drag to change its value.
+///
+/// Dragging the field up or to the right increases its value, and dragging it down or to the
+/// left decreases it. Holding Shift makes the drag ten times finer.
+///
+/// Double-clicking the field (or pressing Enter while it is focused) lets the user type a value.
+/// The typed value is committed when Enter is pressed or when the field loses the focus, and
+/// discarded when Escape is pressed.
+///
+/// # Remarks
+///
+/// Like [`TextInput`], this does not include any text rendering. The appearance of the field
+/// receives a [`NumberFieldDisplay`] describing what should be displayed.
+///
+/// [`TextInput`]: super::text_input::TextInput
+#[derive(Clone, Debug)]
+pub struct NumberField<F, A: ?Sized> {
+    /// The state of the interactive element.
+    pub state: InteractiveState,
+    /// The identifier of the node of the field in the accessibility tree.
+    node_id: NodeIdCell,
+    /// What the appearance of the field should display.
+    display: NumberFieldDisplay,
+    /// Whether the text being edited should be replaced by the next typed character.
+    ///
+    /// This mimics the whole text being selected when the user starts editing.
+    replace_on_type: bool,
+
+    /// The minimum value of the field.
+    min: f64,
+    /// The maximum value of the field.
+    max: f64,
+    /// The step to which the value snaps, or zero for continuous values.
+    step: f64,
+    /// The amount by which the value changes per pixel dragged.
+    ///
+    /// When `None`, it is derived from the range of the field.
+    drag_speed: Option<f64>,
+    /// The format string used to display the value.
+    format: String,
+
+    /// The ongoing drag gesture, if any.
+    drag: Option<Drag>,
+    /// The last time the field was pressed, used to detect double-clicks.
+    last_press: Option<Instant>,
+    /// The scale factor of the last layout.
+    scale_factor: f64,
+
+    /// The function called when the value changes.
+    on_change: F,
+    /// The appearance of the field.
+    pub appearance: A,
+}
+
+impl<F: Default, A: Default> Default for NumberField<F, A> {
+    fn default() -> Self {
+        Self {
+            state: InteractiveState::empty(),
+            node_id: NodeIdCell::default(),
+            display: NumberFieldDisplay {
+                value: 0.0,
+                text: format_number("{}", 0.0),
+                editing: false,
+            },
+            replace_on_type: false,
+            min: f64::NEG_INFINITY,
+            max: f64::INFINITY,
+            step: 0.0,
+            drag_speed: None,
+            format: "{}".into(),
+            drag: None,
+            last_press: None,
+            scale_factor: 1.0,
+            on_change: F::default(),
+            appearance: A::default(),
+        }
+    }
+}
+
+impl<F, A> NumberField<F, A> {
+    /// Sets the function called when the value changes.
+    ///
+    /// The function is called continuously while the user drags the field, and once when a
+    /// typed value is committed.
+    pub fn on_change<F2>(self, on_change: F2) -> NumberField<F2, A>
+    where
+        F2: FnMut(&ElemContext, f64),
+    {
+        NumberField {
+            state: self.state,
+            node_id: self.node_id,
+            display: self.display,
+            replace_on_type: self.replace_on_type,
+            min: self.min,
+            max: self.max,
+            step: self.step,
+            drag_speed: self.drag_speed,
+            format: self.format,
+            drag: self.drag,
+            last_press: self.last_press,
+            scale_factor: self.scale_factor,
+            on_change,
+            appearance: self.appearance,
+        }
+    }
+
+    /// Sets the appearance of the field.
+    pub fn appearance<A2>(self, appearance: A2) -> NumberField<F, A2> {
+        NumberField {
+            state: self.state,
+            node_id: self.node_id,
+            display: self.display,
+            replace_on_type: self.replace_on_type,
+            min: self.min,
+            max: self.max,
+            step: self.step,
+            drag_speed: self.drag_speed,
+            format: self.format,
+            drag: self.drag,
+            last_press: self.last_press,
+            scale_factor: self.scale_factor,
+            on_change: self.on_change,
+            appearance,
+        }
+    }
+
+    /// Sets the initial value of the field.
+    pub fn value(mut self, value: f64) -> Self {
+        self.set_value(value);
+        self
+    }
+
+    /// Sets the minimum value of the field.
+    pub fn min(mut self, min: f64) -> Self {
+        self.min = min;
+        self.set_value(self.display.value);
+        self
+    }
+
+    /// Sets the maximum value of the field.
+    pub fn max(mut self, max: f64) -> Self {
+        self.max = max;
+        self.set_value(self.display.value);
+        self
+    }
+
+    /// Sets the step to which the value snaps.
+    ///
+    /// Steps are counted from the minimum value when it is finite, and from zero otherwise. A
+    /// step of zero allows any value.
+    pub fn step(mut self, step: f64) -> Self {
+        self.step = step.max(0.0);
+        self.set_value(self.display.value);
+        self
+    }
+
+    /// Sets the amount by which the value changes per logical pixel dragged.
+    ///
+    /// By default, dragging the field by 200 pixels covers its whole range. When the range is
+    /// unbounded, the value changes by one step (or by one) per pixel.
+    pub fn drag_speed(mut self, speed: f64) -> Self {
+        self.drag_speed = Some(speed);
+        self
+    }
+
+    /// Sets the format string used to display the value.
+    ///
+    /// See [`format_number`] for the supported syntax.
+    pub fn format(mut self, format: impl Into<String>) -> Self {
+        self.format = format.into();
+        self.display.text = format_number(&self.format, self.display.value);
+        self
+    }
+
+    /// Sets whether the field is disabled or not.
+    pub fn disabled(mut self, yes: bool) -> Self {
+        self.state.set(InteractiveState::DISABLED, yes);
+        self
+    }
+}
+
+impl<F, A: ?Sized> NumberField<F, A> {
+    /// Returns the current value of the field.
+    #[inline]
+    pub fn get_value(&self) -> f64 {
+        self.display.value
+    }
+
+    /// Returns whether the user is currently typing a value.
+    #[inline]
+    pub fn is_editing(&self) -> bool {
+        self.display.editing
+    }
+
+    /// Sets the value of the field.
+    ///
+    /// The value is snapped to the step of the field and clamped to its range. This does not call
+    /// the [`OnNumberChange`] function.
+    pub fn set_value(&mut self, value: f64) {
+        self.display.value = self.constrain(value);
+        if !self.display.editing {
+            self.display.text = format_number(&self.format, self.display.value);
+        }
+    }
+
+    /// Snaps the provided value to the step of the field and clamps it to its range.
+    fn constrain(&self, mut value: f64) -> f64 {
+        if self.step > 0.0 {
+            let base = if self.min.is_finite() { self.min } else { 0.0 };
+            value = base + ((value - base) / self.step).round() * self.step;
+        }
+
+        value.clamp(self.min, self.max.max(self.min))
+    }
+
+    /// Returns the amount by which the value changes per logical pixel dragged.
+    fn resolved_drag_speed(&self) -> f64 {
+        if let Some(speed) = self.drag_speed {
+            speed
+        } else if (self.max - self.min).is_finite() {
+            (self.max - self.min) / 200.0
+        } else if self.step > 0.0 {
+            self.step
+        } else {
+            1.0
+        }
+    }
+
+    /// Computes the value of the field after the pointer was dragged to the provided position.
+    fn value_for_drag(&self, drag: Drag, position: Point, fine: bool) -> f64 {
+        let delta = (position.x - drag.start.x) - (position.y - drag.start.y);
+        let mut speed = self.resolved_drag_speed() / self.scale_factor;
+        if fine {
+            speed /= 10.0;
+        }
+        self.constrain(drag.start_value + delta * speed)
+    }
+
+    /// Starts editing the value as text.
+    fn begin_editing(&mut self) {
+        self.display.editing = true;
+        self.display.text = format_number("{}", self.display.value);
+        self.replace_on_type = true;
+        self.drag = None;
+    }
+
+    /// Stops editing the value, discarding the typed text.
+    fn cancel_editing(&mut self) {
+        self.display.editing = false;
+        self.display.text = format_number(&self.format, self.display.value);
+    }
+}
+
+impl<F: OnNumberChange, A: ?Sized> NumberField<F, A> {
+    /// Changes the value of the field, calling the [`OnNumberChange`] function if it actually
+    /// changed.
+    fn change_value(&mut self, elem_context: &ElemContext, value: f64) {
+        if value == self.display.value {
+            return;
+        }
+
+        self.set_value(value);
+        self.state.insert(InteractiveState::VALUE_CHANGED);
+        self.on_change.on_change(elem_context, self.display.value);
+    }
+
+    /// Stops editing the value, committing the typed text if it is a valid number.
+    fn commit_editing(&mut self, elem_context: &ElemContext) {
+        let parsed = parse_number(&self.format, &self.display.text);
+        self.cancel_editing();
+        if let Some(value) = parsed {
+            self.change_value(elem_context, self.constrain(value));
+        }
+    }
+
+    /// Handles a key event while the value is being edited.
+    fn handle_edit_key(&mut self, elem_context: &ElemContext, event: &KeyEvent) -> bool {
+        if !event.is_pressed() {
+            return false;
+        }
+
+        if event.logical_key == NamedKey::Enter {
+            self.commit_editing(elem_context);
+            return true;
+        }
+
+        if event.logical_key == NamedKey::Escape {
+            self.cancel_editing();
+            return true;
+        }
+
+        if event.logical_key == NamedKey::Backspace {
+            if std::mem::take(&mut self.replace_on_type) {
+                self.display.text.clear();
+            } else {
+                self.display.text.pop();
+            }
+            return true;
+        }
+
+        if let Some(text) = event.text.as_ref() {
+            if std::mem::take(&mut self.replace_on_type) {
+                self.display.text.clear();
+            }
+            self.display.text.push_str(text);
+            return true;
+        }
+
+        false
+    }
+}
+
+impl<F, A> Element for NumberField<F, A>
+where
+    F: OnNumberChange,
+    A: ?Sized + Appearance<NumberFieldDisplay>,
+{
+    #[inline]
+    fn size_hint(
+        &mut self,
+        elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        space: Size,
+    ) -> SizeHint {
+        self.appearance
+            .size_hint(elem_context, layout_context, space)
+    }
+
+    #[inline]
+    fn place(
+        &mut self,
+        elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        pos: Point,
+        size: Size,
+    ) {
+        self.scale_factor = layout_context.scale_factor;
+        self.appearance
+            .place(elem_context, layout_context, pos, size);
+    }
+
+    #[inline]
+    fn hit_test(&self, point: Point) -> bool {
+        self.appearance.hit_test(point)
+    }
+
+    #[inline]
+    fn draw(&mut self, elem_context: &ElemContext, scene: &mut vello::Scene) {
+        self.appearance.draw(elem_context, scene);
+    }
+
+    fn event(&mut self, elem_context: &ElemContext, event: &dyn Event) -> EventResult {
+        self.state.remove_transient_states();
+
+        let og_state = self.state;
+        let og_display = self.display.clone();
+
+        self.state.handle_focus_traversal(event);
+        self.state
+            .handle_accessibility_action(elem_context, &self.node_id, event);
+        let mut event_result = self
+            .state
+            .handle_pointer_interactions(&mut |pt| self.appearance.hit_test(pt), event);
+
+        if self.display.editing {
+            if self.state.just_unfocused() {
+                // Losing the focus commits the typed value.
+                self.commit_editing(elem_context);
+            } else if let Some(ev) = event
+                .downcast_ref::<KeyEvent>()
+                .filter(|ev| ev.logical_key != NamedKey::Tab)
+            {
+                self.handle_edit_key(elem_context, ev);
+                event_result = EventResult::Handled;
+            }
+        } else if self.state.just_pressed() {
+            let now = Instant::now();
+            let double_click = self
+                .last_press
+                .is_some_and(|last| now.duration_since(last) < DOUBLE_CLICK_DELAY);
+
+            if double_click {
+                self.last_press = None;
+                self.begin_editing();
+            } else if let Some(ev) = event.downcast_ref::<PointerButton>() {
+                self.last_press = Some(now);
+                self.drag = Some(Drag {
+                    start: ev.position,
+                    start_value: self.display.value,
+                });
+            }
+        } else if self.state.just_released() {
+            self.drag = None;
+        } else if let Some((drag, ev)) = self.drag.zip(event.downcast_ref::<PointerMoved>()) {
+            if ev.primary {
+                let value = self.value_for_drag(drag, ev.position, ev.modifiers.shift_key());
+                self.change_value(elem_context, value);
+                event_result = EventResult::Handled;
+            }
+        } else if self.state.focused() {
+            let enter = event
+                .downcast_ref::<KeyEvent>()
+                .is_some_and(|ev| ev.is_initial_press() && ev.logical_key == NamedKey::Enter);
+            if enter {
+                self.begin_editing();
+                event_result = EventResult::Handled;
+            }
+        }
+
+        if og_state != self.state || og_display != self.display {
+            self.appearance
+                .state_changed(elem_context, self.state, &self.display);
+        }
+        if event_result.is_handled() {
+            return EventResult::Handled;
+        }
+        self.appearance.event(elem_context, event)
+    }
+
+    fn begin(&mut self, elem_context: &ElemContext) {
+        self.appearance.begin(elem_context);
+        self.appearance
+            .state_changed(elem_context, self.state, &self.display);
+    }
+
+    fn accessibility(&self, cx: &mut AccessibilityContext) {
+        let mut node = Node::new(Role::SpinButton);
+        node.add_action(Action::Focus);
+        node.set_numeric_value(self.display.value);
+        if self.min.is_finite() {
+            node.set_min_numeric_value(self.min);
+        }
+        if self.max.is_finite() {
+            node.set_max_numeric_value(self.max);
+        }
+        if self.step > 0.0 {
+            node.set_numeric_value_step(self.step);
+        }
+        node.set_value(self.display.text.as_str());
+        if self.state.disabled() {
+            node.set_disabled();
+        }
+
+        let id = cx.push(node);
+        self.node_id.set(id);
+        if self.state.focused() {
+            cx.set_focus(id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::{Drag, NumberField, format_number, parse_number},
+        crate::HeadlessRenderer,
+        std::{cell::RefCell, rc::Rc},
+        vello::kurbo::Point,
+    };
+
+    /// Creates a field going from 0 to 100 by steps of 1, laid out at a scale factor of 2.
+    fn percent_field() -> NumberField<(), ()> {
+        let mut field = NumberField::default()
+            .min(0.0)
+            .max(100.0)
+            .step(1.0)
+            .value(50.0);
+        field.scale_factor = 2.0;
+        field
+    }
+
+    #[test]
+    fn scrubbing_changes_value_by_drag_distance() {
+        let field = percent_field();
+        let drag = Drag {
+            start: Point::new(10.0, 10.0),
+            start_value: field.get_value(),
+        };
+
+        // Moving 30 pixels right and 10 pixels up is a 40 physical pixels drag. The whole range
+        // is covered by 200 logical (400 physical) pixels.
+        let position = Point::new(40.0, 0.0);
+        assert_eq!(field.value_for_drag(drag, position, false), 60.0);
+        assert_eq!(field.value_for_drag(drag, position, true), 51.0);
+
+        assert_eq!(
+            field.value_for_drag(drag, Point::new(10.0, 500.0), false),
+            0.0
+        );
+        assert_eq!(
+            field.value_for_drag(drag, Point::new(1000.0, 10.0), false),
+            100.0
+        );
+    }
+
+    #[test]
+    fn typed_value_is_parsed_on_commit() {
+        let changes = Rc::new(RefCell::new(Vec::new()));
+        let mut field = percent_field().format("{:.0} %").on_change({
+            let changes = changes.clone();
+            move |_, value| changes.borrow_mut().push(value)
+        });
+
+        let renderer = HeadlessRenderer::new();
+        let elem_context = renderer.elem_context();
+
+        field.begin_editing();
+        field.display.text = "72.4 %".into();
+        assert_eq!(field.get_value(), 50.0);
+
+        field.commit_editing(&elem_context);
+        assert!(!field.is_editing());
+        assert_eq!(field.get_value(), 72.0);
+        assert_eq!(field.display.text, "72 %");
+
+        field.begin_editing();
+        field.display.text = "lots".into();
+        field.commit_editing(&elem_context);
+        assert_eq!(field.get_value(), 72.0);
+
+        assert_eq!(*changes.borrow(), [72.0]);
+    }
+
+    #[test]
+    fn format_and_parse_round_trip() {
+        assert_eq!(format_number("{:.2} dB", -3.5), "-3.50 dB");
+        assert_eq!(parse_number("{:.2} dB", " -3.50 dB "), Some(-3.5));
+        assert_eq!(parse_number("{:.2} dB", "-3.5"), Some(-3.5));
+        assert_eq!(parse_number("{:.2} dB", "inf"), None);
+    }
+}
@@ -0,0 +1,478 @@
+use {
+    crate::{
+        ElemContext, Element, LayoutContext, SizeHint,
+        accessibility::AccessibilityContext,
+        elements::{
+            Length,
+            layer::{Layer, LayerPass},
+        },
+        event::{Event, EventResult, KeyEvent, PointerButton, PointerMoved},
+    },
+    vello::kurbo::{Point, Rect, Size},
+    winit::keyboard::NamedKey,
+};
+
+/// Where a [`Popup`] is placed relative to its anchor.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum PopupPlacement {
+    /// The popup is placed below the anchor, aligned with its left edge.
+    #[default]
+    Below,
+    /// The popup is placed above the anchor, aligned with its left edge.
+    Above,
+    /// The popup is placed to the left of the anchor, aligned with its top edge.
+    Left,
+    /// The popup is placed to the right of the anchor, aligned with its top edge.
+    Right,
+}
+
+impl PopupPlacement {
+    /// Returns the placement on the other side of the anchor.
+    pub fn flipped(self) -> Self {
+        match self {
+            Self::Below => Self::Above,
+            Self::Above => Self::Below,
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+        }
+    }
+}
+
+/// Computes the area covered by a popup.
+///
+/// # Parameters
+///
+/// - `anchor`: the area that the popup is attached to.
+///
+/// - `size`: the size of the popup.
+///
+/// - `placement`: the preferred placement of the popup.
+///
+/// - `gap`: the distance between the anchor and the popup.
+///
+/// - `bounds`: the area that the popup must stay within (usually the window).
+///
+/// # Returns
+///
+/// The area covered by the popup, and the placement that was actually used.
+///
+/// When the popup would overflow `bounds` with the preferred placement, it is flipped to the
+/// other side of the anchor, but only if there is more room there. The popup is then shifted
+/// to fit within `bounds` as much as possible.
+pub fn compute_popup_rect(
+    anchor: Rect,
+    size: Size,
+    placement: PopupPlacement,
+    gap: f64,
+    bounds: Rect,
+) -> (Rect, PopupPlacement) {
+    // The space available on each side of the anchor.
+    let room = |placement: PopupPlacement| match placement {
+        PopupPlacement::Below => bounds.y1 - anchor.y1 - gap,
+        PopupPlacement::Above => anchor.y0 - gap - bounds.y0,
+        PopupPlacement::Left => anchor.x0 - gap - bounds.x0,
+        PopupPlacement::Right => bounds.x1 - anchor.x1 - gap,
+    };
+    let needed = match placement {
+        PopupPlacement::Below | PopupPlacement::Above => size.height,
+        PopupPlacement::Left | PopupPlacement::Right => size.width,
+    };
+
+    let placement = if room(placement) < needed && room(placement.flipped()) > room(placement) {
+        placement.flipped()
+    } else {
+        placement
+    };
+
+    let origin = match placement {
+        PopupPlacement::Below => Point::new(anchor.x0, anchor.y1 + gap),
+        PopupPlacement::Above => Point::new(anchor.x0, anchor.y0 - gap - size.height),
+        PopupPlacement::Left => Point::new(anchor.x0 - gap - size.width, anchor.y0),
+        PopupPlacement::Right => Point::new(anchor.x1 + gap, anchor.y0),
+    };
+
+    // When the popup is larger than the bounds, its top-left corner is kept visible.
+    let origin = Point::new(
+        origin.x.min(bounds.x1 - size.width).max(bounds.x0),
+        origin.y.min(bounds.y1 - size.height).max(bounds.y0),
+    );
+
+    (Rect::from_origin_size(origin, size), placement)
+}
+
+/// The function called when a [`Popup`] is dismissed by the user.
+pub trait OnClose {
+    /// The popup has been closed.
+    fn on_close(&mut self, elem_context: &ElemContext);
+}
+
+impl OnClose for () {
+    #[inline]
+    fn on_close(&mut self, _elem_context: &ElemContext) {}
+}
+
+impl<F> OnClose for F
+where
+    F: FnMut(&ElemContext),
+{
+    #[inline]
+    fn on_close(&mut self, elem_context: &ElemContext) {
+        self(elem_context)
+    }
+}
+
+/// An element that floats above the rest of the UI, next to an anchor.
+///
+/// This is the building block of dropdowns, context menus and other floating panels. The
+/// popup does not take any space in the layout of its parent. Instead, its content is laid out
+/// at its preferred size next to the anchor, and kept within the bounds of the window (see
+/// [`compute_popup_rect`]).
+///
+/// The content is drawn in the [`Layer::Popup`] layer. While open, the popup closes itself when
+/// the user clicks outside of it or presses Escape, and notifies its [`OnClose`] function.
+#[derive(Clone, Debug, Default)]
+pub struct Popup<F, E: ?Sized> {
+    /// The area that the popup is attached to, in physical pixels.
+    ///
+    /// When `None`, the area given to the popup by its parent is used.
+    anchor: Option<Rect>,
+    /// The preferred placement of the popup.
+    placement: PopupPlacement,
+    /// The distance between the anchor and the popup.
+    gap: Length,
+    /// Whether the popup is currently displayed.
+    open: bool,
+    /// The function called when the popup is dismissed.
+    on_close: F,
+    /// The area covered by the popup during the last layout.
+    rect: Rect,
+    /// The content of the popup.
+    pub child: E,
+}
+
+impl<F, E> Popup<F, E> {
+    /// Sets the area that the popup is attached to, in physical pixels.
+    pub fn anchor(mut self, anchor: Rect) -> Self {
+        self.anchor = Some(anchor);
+        self
+    }
+
+    /// Sets the preferred placement of the popup.
+    pub fn placement(mut self, placement: PopupPlacement) -> Self {
+        self.placement = placement;
+        self
+    }
+
+    /// Places the popup below its anchor.
+    #[inline]
+    pub fn below(self) -> Self {
+        self.placement(PopupPlacement::Below)
+    }
+
+    /// Places the popup above its anchor.
+    #[inline]
+    pub fn above(self) -> Self {
+        self.placement(PopupPlacement::Above)
+    }
+
+    /// Places the popup to the left of its anchor.
+    #[inline]
+    pub fn left(self) -> Self {
+        self.placement(PopupPlacement::Left)
+    }
+
+    /// Places the popup to the right of its anchor.
+    #[inline]
+    pub fn right(self) -> Self {
+        self.placement(PopupPlacement::Right)
+    }
+
+    /// Sets the distance between the anchor and the popup.
+    pub fn gap(mut self, gap: Length) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// Sets whether the popup is initially open.
+    pub fn open(mut self, yes: bool) -> Self {
+        self.open = yes;
+        self
+    }
+
+    /// Sets the function called when the popup is dismissed by the user.
+    pub fn on_close<F2>(self, on_close: F2) -> Popup<F2, E>
+    where
+        F2: FnMut(&ElemContext),
+    {
+        Popup {
+            anchor: self.anchor,
+            placement: self.placement,
+            gap: self.gap,
+            open: self.open,
+            on_close,
+            rect: self.rect,
+            child: self.child,
+        }
+    }
+
+    /// Sets the content of the popup.
+    pub fn child<E2>(self, child: E2) -> Popup<F, E2> {
+        Popup {
+            anchor: self.anchor,
+            placement: self.placement,
+            gap: self.gap,
+            open: self.open,
+            on_close: self.on_close,
+            rect: self.rect,
+            child,
+        }
+    }
+}
+
+impl<F, E: ?Sized> Popup<F, E> {
+    /// Returns whether the popup is currently displayed.
+    #[inline]
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Opens or closes the popup.
+    ///
+    /// The caller is responsible for requesting a relayout of the window when opening the
+    /// popup.
+    #[inline]
+    pub fn set_open(&mut self, yes: bool) {
+        self.open = yes;
+    }
+
+    /// Sets the area that the popup is attached to.
+    ///
+    /// The caller is responsible for requesting a relayout of the window.
+    #[inline]
+    pub fn set_anchor(&mut self, anchor: Option<Rect>) {
+        self.anchor = anchor;
+    }
+
+    /// Returns the area covered by the popup during the last layout.
+    #[inline]
+    pub fn rect(&self) -> Rect {
+        self.rect
+    }
+}
+
+impl<F: OnClose, E: ?Sized + Element> Popup<F, E> {
+    /// Closes the popup because the user dismissed it.
+    fn dismiss(&mut self, elem_context: &ElemContext) {
+        self.open = false;
+        self.on_close.on_close(elem_context);
+        elem_context.window.request_redraw();
+    }
+
+    /// Handles an event dispatched to the popup's layer.
+    fn handle_event(&mut self, elem_context: &ElemContext, event: &dyn Event) -> EventResult {
+        if !self.open {
+            return EventResult::Continue;
+        }
+
+        if self.child.event(elem_context, event).is_handled() {
+            return EventResult::Handled;
+        }
+
+        if let Some(ev) = event.downcast_ref::<PointerButton>() {
+            if self.rect.contains(ev.position) {
+                return EventResult::Handled;
+            }
+
+            if ev.state.is_pressed() {
+                // The click is not consumed so that it can reach the element under the pointer.
+                self.dismiss(elem_context);
+            }
+
+            return EventResult::Continue;
+        }
+
+        if let Some(ev) = event.downcast_ref::<PointerMoved>() {
+            return if self.rect.contains(ev.position) {
+                EventResult::Handled
+            } else {
+                EventResult::Continue
+            };
+        }
+
+        let escape = event
+            .downcast_ref::<KeyEvent>()
+            .is_some_and(|ev| ev.is_initial_press() && ev.logical_key == NamedKey::Escape);
+        if escape {
+            self.dismiss(elem_context);
+            return EventResult::Handled;
+        }
+
+        EventResult::Continue
+    }
+}
+
+impl<F, E> Element for Popup<F, E>
+where
+    F: OnClose,
+    E: ?Sized + Element,
+{
+    fn size_hint(
+        &mut self,
+        _elem_context: &ElemContext,
+        _layout_context: LayoutContext,
+        _space: Size,
+    ) -> SizeHint {
+        SizeHint {
+            preferred: Size::ZERO,
+            min: Size::ZERO,
+            max: Size::ZERO,
+        }
+    }
+
+    fn place(
+        &mut self,
+        elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        pos: Point,
+        size: Size,
+    ) {
+        if !self.open {
+            return;
+        }
+
+        let bounds = Rect::from_origin_size(Point::ORIGIN, elem_context.window.size());
+        let anchor = self
+            .anchor
+            .unwrap_or_else(|| Rect::from_origin_size(pos, size));
+
+        let child_layout_context = layout_context.with_parent(bounds.size());
+        let content_size = self
+            .child
+            .size_hint(elem_context, child_layout_context, bounds.size())
+            .preferred;
+
+        let (rect, _) = compute_popup_rect(
+            anchor,
+            content_size,
+            self.placement,
+            self.gap.resolve(&layout_context),
+            bounds,
+        );
+
+        self.rect = rect;
+        self.child.place(
+            elem_context,
+            child_layout_context,
+            rect.origin(),
+            rect.size(),
+        );
+    }
+
+    #[inline]
+    fn hit_test(&self, point: Point) -> bool {
+        self.open && self.rect.contains(point)
+    }
+
+    fn draw(&mut self, elem_context: &ElemContext, scene: &mut vello::Scene) {
+        if !self.open {
+            return;
+        }
+
+        let drawn = elem_context
+            .window
+            .with_layer_scene(Layer::Popup, &mut |layer_scene| {
+                self.child.draw(elem_context, layer_scene)
+            });
+
+        if !drawn {
+            self.child.draw(elem_context, scene);
+        }
+    }
+
+    fn event(&mut self, elem_context: &ElemContext, event: &dyn Event) -> EventResult {
+        // See the implementation of `Layered` for the reasoning behind this.
+        if let Some(pass) = event.downcast_ref::<LayerPass>() {
+            if pass.layer == Layer::Popup {
+                return self.handle_event(elem_context, pass.event());
+            }
+
+            return self.child.event(elem_context, event);
+        }
+
+        if elem_context.window.is_layer_active(Layer::Popup) {
+            return EventResult::Continue;
+        }
+
+        self.handle_event(elem_context, event)
+    }
+
+    #[inline]
+    fn begin(&mut self, elem_context: &ElemContext) {
+        self.child.begin(elem_context);
+    }
+
+    fn accessibility(&self, cx: &mut AccessibilityContext) {
+        if self.open {
+            self.child.accessibility(cx);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::{PopupPlacement, compute_popup_rect},
+        vello::kurbo::{Rect, Size},
+    };
+
+    const WINDOW: Rect = Rect::new(0.0, 0.0, 800.0, 600.0);
+
+    #[test]
+    fn popup_flips_above_near_bottom_edge() {
+        let anchor = Rect::new(100.0, 550.0, 200.0, 580.0);
+        let size = Size::new(150.0, 100.0);
+
+        let (rect, placement) =
+            compute_popup_rect(anchor, size, PopupPlacement::Below, 4.0, WINDOW);
+
+        assert_eq!(placement, PopupPlacement::Above);
+        assert_eq!(rect, Rect::new(100.0, 446.0, 250.0, 546.0));
+    }
+
+    #[test]
+    fn popup_flips_left_near_right_edge() {
+        let anchor = Rect::new(700.0, 100.0, 780.0, 120.0);
+        let size = Size::new(120.0, 50.0);
+
+        let (rect, placement) =
+            compute_popup_rect(anchor, size, PopupPlacement::Right, 0.0, WINDOW);
+
+        assert_eq!(placement, PopupPlacement::Left);
+        assert_eq!(rect, Rect::new(580.0, 100.0, 700.0, 150.0));
+    }
+
+    #[test]
+    fn popup_is_shifted_when_it_fits_below() {
+        let anchor = Rect::new(750.0, 100.0, 790.0, 120.0);
+        let size = Size::new(100.0, 50.0);
+
+        let (rect, placement) =
+            compute_popup_rect(anchor, size, PopupPlacement::Below, 0.0, WINDOW);
+
+        assert_eq!(placement, PopupPlacement::Below);
+        assert_eq!(rect, Rect::new(700.0, 120.0, 800.0, 170.0));
+    }
+
+    #[test]
+    fn popup_does_not_flip_to_a_smaller_side() {
+        let bounds = Rect::new(0.0, 0.0, 200.0, 60.0);
+        let anchor = Rect::new(0.0, 10.0, 50.0, 20.0);
+        let size = Size::new(40.0, 45.0);
+
+        let (rect, placement) =
+            compute_popup_rect(anchor, size, PopupPlacement::Below, 0.0, bounds);
+
+        assert_eq!(placement, PopupPlacement::Below);
+        assert_eq!(rect, Rect::new(0.0, 15.0, 40.0, 60.0));
+    }
+}
@@ -0,0 +1,241 @@
+use {
+    crate::{
+        ElemContext, Element, LayoutContext, SizeHint,
+        accessibility::AccessibilityContext,
+        event::{Event, EventResult},
+    },
+    vello::kurbo::{Point, Rect, Size},
+};
+
+/// Describes how to build an element tree from a state value.
+///
+/// Schemes are used by [`SchemeHost`] to keep an element tree in sync with a piece of state.
+pub trait Scheme<T: ?Sized> {
+    /// The element produced by the scheme.
+    type Element: Element;
+
+    /// Builds the element tree for the provided state.
+    fn build(&mut self, elem_context: &ElemContext, state: &T) -> Self::Element;
+
+    /// Updates a previously built element tree to reflect the provided state.
+    ///
+    /// The default implementation replaces the element with a freshly built one. Implementations
+    /// may instead update the existing element in place, preserving its internal state (focus,
+    /// scroll position, animations, etc).
+    ///
+    /// # Remarks
+    ///
+    /// Implementations that create new elements are responsible for calling [`Element::begin`]
+    /// on them.
+    fn rebuild(&mut self, elem_context: &ElemContext, state: &T, element: &mut Self::Element) {
+        *element = self.build(elem_context, state);
+        element.begin(elem_context);
+    }
+}
+
+impl<T: ?Sized, E, F> Scheme<T> for F
+where
+    F: FnMut(&T) -> E,
+    E: Element,
+{
+    type Element = E;
+
+    #[inline]
+    fn build(&mut self, _elem_context: &ElemContext, state: &T) -> Self::Element {
+        self(state)
+    }
+}
+
+/// The last layout pass of the element of a [`SchemeHost`].
+#[derive(Clone, Copy, Debug)]
+struct LastLayout {
+    /// The layout context of the pass.
+    layout_context: LayoutContext,
+    /// The space that was available to the element.
+    space: Size,
+    /// The preferred size that the element reported.
+    preferred: Size,
+    /// The bounds in which the element was placed.
+    bounds: Rect,
+}
+
+/// An element that owns a piece of state and rebuilds its child from it using a [`Scheme`].
+///
+/// The child is built when the host is added to the UI tree. It is then rebuilt every time the
+/// state is modified through [`set_state`](Self::set_state).
+///
+/// After a rebuild, only the child is laid out again (within the bounds it was previously given)
+/// as long as its preferred size did not change. Otherwise, a relayout of the whole window is
+/// requested.
+pub struct SchemeHost<S, T>
+where
+    S: Scheme<T>,
+{
+    /// The scheme used to build the child element.
+    scheme: S,
+    /// The state from which the child element is built.
+    state: T,
+    /// The child element, built when the host is added to the UI tree.
+    element: Option<S::Element>,
+    /// The last layout pass of the child element.
+    last_layout: Option<LastLayout>,
+}
+
+impl<S, T> SchemeHost<S, T>
+where
+    S: Scheme<T>,
+{
+    /// Creates a new [`SchemeHost`] element.
+    pub fn new(scheme: S, state: T) -> Self {
+        Self {
+            scheme,
+            state,
+            element: None,
+            last_layout: None,
+        }
+    }
+
+    /// Returns the current state of the host.
+    #[inline]
+    pub fn state(&self) -> &T {
+        &self.state
+    }
+
+    /// Returns the child element, if it has been built already.
+    #[inline]
+    pub fn element(&self) -> Option<&S::Element> {
+        self.element.as_ref()
+    }
+
+    /// Modifies the state of the host, then rebuilds its child element.
+    pub fn set_state(&mut self, elem_context: &ElemContext, f: impl FnOnce(&mut T)) {
+        f(&mut self.state);
+        self.rebuild(elem_context);
+    }
+
+    /// Rebuilds the child element from the current state.
+    ///
+    /// This does nothing if the child has not been built yet.
+    pub fn rebuild(&mut self, elem_context: &ElemContext) {
+        let Some(element) = &mut self.element else {
+            return;
+        };
+
+        self.scheme.rebuild(elem_context, &self.state, element);
+
+        let Some(last) = self.last_layout else {
+            elem_context.window.request_relayout();
+            return;
+        };
+
+        let hint = element.size_hint(elem_context, last.layout_context, last.space);
+        if hint.preferred != last.preferred {
+            // The parent may want to give the element a different size.
+            elem_context.window.request_relayout();
+            return;
+        }
+
+        element.place(
+            elem_context,
+            last.layout_context,
+            last.bounds.origin(),
+            last.bounds.size(),
+        );
+        elem_context.window.request_redraw();
+    }
+}
+
+impl<S, T> Element for SchemeHost<S, T>
+where
+    S: Scheme<T>,
+{
+    fn size_hint(
+        &mut self,
+        elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        space: Size,
+    ) -> SizeHint {
+        let Some(element) = &mut self.element else {
+            return SizeHint::default();
+        };
+
+        let hint = element.size_hint(elem_context, layout_context, space);
+        self.last_layout = Some(LastLayout {
+            layout_context,
+            space,
+            preferred: hint.preferred,
+            bounds: self.last_layout.map_or(Rect::ZERO, |last| last.bounds),
+        });
+        hint
+    }
+
+    fn place(
+        &mut self,
+        elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        pos: Point,
+        size: Size,
+    ) {
+        let Some(element) = &mut self.element else {
+            return;
+        };
+
+        element.place(elem_context, layout_context, pos, size);
+
+        let bounds = Rect::from_origin_size(pos, size);
+        match &mut self.last_layout {
+            Some(last) => {
+                last.layout_context = layout_context;
+                last.bounds = bounds;
+            }
+            None => {
+                self.last_layout = Some(LastLayout {
+                    layout_context,
+                    space: size,
+                    preferred: size,
+                    bounds,
+                });
+            }
+        }
+    }
+
+    #[inline]
+    fn hit_test(&self, point: Point) -> bool {
+        self.element
+            .as_ref()
+            .is_some_and(|element| element.hit_test(point))
+    }
+
+    #[inline]
+    fn draw(&mut self, elem_context: &ElemContext, scene: &mut vello::Scene) {
+        if let Some(element) = &mut self.element {
+            element.draw(elem_context, scene);
+        }
+    }
+
+    #[inline]
+    fn event(&mut self, elem_context: &ElemContext, event: &dyn Event) -> EventResult {
+        match &mut self.element {
+            Some(element) => element.event(elem_context, event),
+            None => EventResult::Continue,
+        }
+    }
+
+    fn begin(&mut self, elem_context: &ElemContext) {
+        match &mut self.element {
+            Some(element) => element.begin(elem_context),
+            None => {
+                let mut element = self.scheme.build(elem_context, &self.state);
+                element.begin(elem_context);
+                self.element = Some(element);
+            }
+        }
+    }
+
+    #[inline]
+    fn accessibility(&self, cx: &mut AccessibilityContext) {
+        if let Some(element) = &self.element {
+            element.accessibility(cx);
+        }
+    }
+}
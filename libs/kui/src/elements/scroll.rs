@@ -0,0 +1,793 @@
+use {
+    crate::{
+        ElemContext, Element, LayoutContext, SizeHint,
+        accessibility::AccessibilityContext,
+        elements::{Length, flex::Direction},
+        event::{Event, EventResult, PointerButton, PointerLeft, PointerMoved, PointerScrolled},
+    },
+    std::time::{Duration, Instant},
+    vello::{
+        kurbo::{Affine, Point, Rect, Size, Vec2},
+        peniko::{Brush, Color, Fill, Mix},
+    },
+    winit::event::{ButtonSource, MouseButton},
+};
+
+/// The minimum length of a scrollbar thumb, relative to the thickness of the scrollbar.
+const MIN_THUMB_LENGTH: f64 = 2.0;
+
+/// The time without scroll events after which an inertial scroll gesture is considered released.
+const INERTIA_RELEASE_DELAY: Duration = Duration::from_millis(60);
+
+/// The smallest interval between two scroll events used to estimate the scroll velocity, in
+/// seconds.
+///
+/// Some platforms deliver several scroll events in the same frame, which would otherwise result
+/// in absurd velocities.
+const MIN_SCROLL_INTERVAL: f64 = 1.0 / 240.0;
+
+/// The rate at which the velocity of an inertial scroll decays, in units per second.
+const INERTIA_FRICTION_RATE: f64 = 4.0;
+
+/// The rate at which the velocity decays while the content is pulled past its bounds.
+const INERTIA_OVERSHOOT_RATE: f64 = 24.0;
+
+/// The rate at which the content springs back within its bounds after an overshoot.
+const INERTIA_SPRING_RATE: f64 = 18.0;
+
+/// The velocity below which an inertial scroll stops, in logical pixels per second.
+const INERTIA_STOP_VELOCITY: f64 = 12.0;
+
+/// The maximum distance by which the content can be pulled past its bounds, in logical pixels.
+const MAX_OVERSHOOT: f64 = 32.0;
+
+/// Moves `current` towards `target`, independently of the frame rate.
+///
+/// `rate` is expressed in units per second and `dt` in seconds.
+fn exp_decay(current: f64, target: f64, rate: f64, dt: f64) -> f64 {
+    target + (current - target) * (-rate * dt).exp()
+}
+
+/// The scrollbar thumb being dragged by the user.
+#[derive(Clone, Copy, Debug)]
+struct ThumbDrag {
+    /// The scrollbar being dragged.
+    direction: Direction,
+    /// The position of the pointer along the scrollbar when the drag started.
+    start_pointer: f64,
+    /// The scroll offset along the scrollbar when the drag started.
+    start_offset: f64,
+}
+
+/// The geometry of a visible scrollbar.
+#[derive(Clone, Copy, Debug)]
+struct ScrollbarGeometry {
+    /// The track of the scrollbar.
+    track: Rect,
+    /// The thumb of the scrollbar.
+    thumb: Rect,
+}
+
+/// An element that shows a portion of its child, and lets the user scroll through the rest.
+///
+/// Scrolling can be enabled independently on each axis. Along a scrolling axis, the child is
+/// given its preferred size rather than the size of the [`Scroll`] element, and the part that
+/// does not fit is clipped.
+///
+/// The user can scroll with the mouse wheel (holding Shift scrolls horizontally) or by dragging
+/// the thumbs of the scrollbars. Scrollbars are only shown when the content does not fit.
+///
+/// When [inertia](Self::with_inertia) is enabled, the content keeps scrolling for a moment after
+/// the user stops scrolling, and bounces back slightly when it reaches its bounds.
+#[derive(Clone, Debug)]
+pub struct Scroll<E: ?Sized> {
+    /// Whether the content can be scrolled horizontally.
+    pub scroll_x: bool,
+    /// Whether the content can be scrolled vertically.
+    pub scroll_y: bool,
+    /// Whether to show scrollbars when the content does not fit.
+    pub scrollbars: bool,
+    /// Whether the content keeps scrolling after the user stops scrolling.
+    pub inertia: bool,
+
+    /// The thickness of the scrollbars.
+    pub scrollbar_thickness: Length,
+    /// The radius of the corners of the element.
+    ///
+    /// The content and the scrollbars are clipped to the resulting shape.
+    pub radius: Length,
+    /// The brush used to draw the tracks of the scrollbars.
+    pub track_brush: Brush,
+    /// The brush used to draw the thumbs of the scrollbars.
+    pub thumb_brush: Brush,
+
+    /// The current scroll offset.
+    offset: Vec2,
+    /// The thumb being dragged, if any.
+    drag: Option<ThumbDrag>,
+    /// The current velocity of the content, in physical pixels per second.
+    ///
+    /// Only used when inertia is enabled.
+    velocity: Vec2,
+    /// The time at which the last scroll event was received.
+    last_scroll: Option<Instant>,
+    /// The time at which the last frame of the inertial scroll was drawn.
+    last_frame: Option<Instant>,
+
+    /// The layout context with which the element was last placed.
+    layout_context: LayoutContext,
+    /// The bounds of the element.
+    rect: Rect,
+    /// The size of the child element.
+    content_size: Size,
+    /// The resolved thickness of the scrollbars.
+    computed_thickness: f64,
+    /// The resolved radius of the corners of the element.
+    computed_radius: f64,
+
+    /// The child element.
+    pub child: E,
+}
+
+impl Default for Scroll<()> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(())
+    }
+}
+
+impl<E> Scroll<E> {
+    /// Creates a new [`Scroll`] element.
+    ///
+    /// The element scrolls vertically by default.
+    pub fn new(child: E) -> Self {
+        Self {
+            scroll_x: false,
+            scroll_y: true,
+            scrollbars: true,
+            inertia: false,
+            scrollbar_thickness: Length::Pixels(6.0),
+            radius: Length::ZERO,
+            track_brush: Color::from_rgba8(0xff, 0xff, 0xff, 0x10).into(),
+            thumb_brush: Color::from_rgba8(0xff, 0xff, 0xff, 0x60).into(),
+            offset: Vec2::ZERO,
+            drag: None,
+            velocity: Vec2::ZERO,
+            last_scroll: None,
+            last_frame: None,
+            layout_context: LayoutContext::default(),
+            rect: Rect::ZERO,
+            content_size: Size::ZERO,
+            computed_thickness: 0.0,
+            computed_radius: 0.0,
+            child,
+        }
+    }
+
+    /// Sets whether the content can be scrolled horizontally.
+    pub fn with_scroll_x(mut self, yes: bool) -> Self {
+        self.scroll_x = yes;
+        self
+    }
+
+    /// Sets whether the content can be scrolled vertically.
+    pub fn with_scroll_y(mut self, yes: bool) -> Self {
+        self.scroll_y = yes;
+        self
+    }
+
+    /// Sets whether to show scrollbars when the content does not fit.
+    pub fn with_scrollbars(mut self, yes: bool) -> Self {
+        self.scrollbars = yes;
+        self
+    }
+
+    /// Sets whether the content keeps scrolling after the user stops scrolling.
+    ///
+    /// This is disabled by default.
+    pub fn with_inertia(mut self, yes: bool) -> Self {
+        self.inertia = yes;
+        self
+    }
+
+    /// Sets the thickness of the scrollbars.
+    pub fn scrollbar_thickness(mut self, thickness: Length) -> Self {
+        self.scrollbar_thickness = thickness;
+        self
+    }
+
+    /// Sets the radius of the corners of the element.
+    pub fn radius(mut self, radius: Length) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Sets the brush used to draw the tracks of the scrollbars.
+    pub fn track_brush(mut self, brush: impl Into<Brush>) -> Self {
+        self.track_brush = brush.into();
+        self
+    }
+
+    /// Sets the brush used to draw the thumbs of the scrollbars.
+    pub fn thumb_brush(mut self, brush: impl Into<Brush>) -> Self {
+        self.thumb_brush = brush.into();
+        self
+    }
+
+    /// Sets the child element of this [`Scroll`] element.
+    pub fn child<E2>(self, child: E2) -> Scroll<E2> {
+        Scroll {
+            scroll_x: self.scroll_x,
+            scroll_y: self.scroll_y,
+            scrollbars: self.scrollbars,
+            inertia: self.inertia,
+            scrollbar_thickness: self.scrollbar_thickness,
+            radius: self.radius,
+            track_brush: self.track_brush,
+            thumb_brush: self.thumb_brush,
+            offset: Vec2::ZERO,
+            drag: None,
+            velocity: Vec2::ZERO,
+            last_scroll: None,
+            last_frame: None,
+            layout_context: LayoutContext::default(),
+            rect: Rect::ZERO,
+            content_size: Size::ZERO,
+            computed_thickness: 0.0,
+            computed_radius: 0.0,
+            child,
+        }
+    }
+}
+
+impl<E: ?Sized> Scroll<E> {
+    /// Returns the current scroll offset.
+    #[inline]
+    pub fn scroll_offset(&self) -> Vec2 {
+        self.offset
+    }
+
+    /// Returns the maximum scroll offset along each axis.
+    pub fn max_scroll_offset(&self) -> Vec2 {
+        Vec2::new(
+            (self.content_size.width - self.rect.width()).max(0.0),
+            (self.content_size.height - self.rect.height()).max(0.0),
+        )
+    }
+
+    /// Returns whether the scrollbar along the provided direction is visible.
+    fn scrollbar_visible(&self, direction: Direction) -> bool {
+        if !self.scrollbars {
+            return false;
+        }
+
+        match direction {
+            Direction::Horizontal => self.scroll_x && self.max_scroll_offset().x > 0.0,
+            Direction::Vertical => self.scroll_y && self.max_scroll_offset().y > 0.0,
+        }
+    }
+
+    /// Computes the geometry of the scrollbar along the provided direction.
+    ///
+    /// Returns `None` if the scrollbar is not visible.
+    fn scrollbar(&self, direction: Direction) -> Option<ScrollbarGeometry> {
+        if !self.scrollbar_visible(direction) {
+            return None;
+        }
+
+        let thickness = self.computed_thickness;
+        let max_offset = self.max_scroll_offset();
+
+        // When both scrollbars are visible, they must not overlap in the bottom-right corner.
+        let corner = match direction {
+            Direction::Horizontal => self.scrollbar_visible(Direction::Vertical),
+            Direction::Vertical => self.scrollbar_visible(Direction::Horizontal),
+        };
+        let corner = if corner { thickness } else { 0.0 };
+
+        let (viewport, content, offset, max) = match direction {
+            Direction::Horizontal => (
+                self.rect.width(),
+                self.content_size.width,
+                self.offset.x,
+                max_offset.x,
+            ),
+            Direction::Vertical => (
+                self.rect.height(),
+                self.content_size.height,
+                self.offset.y,
+                max_offset.y,
+            ),
+        };
+
+        let track_length = (viewport - corner).max(0.0);
+        let thumb_length = (track_length * viewport / content)
+            .max(thickness * MIN_THUMB_LENGTH)
+            .min(track_length);
+        // The offset may briefly exceed its bounds during an inertial scroll.
+        let thumb_start = (track_length - thumb_length) * (offset / max).clamp(0.0, 1.0);
+
+        let r = self.rect;
+        Some(match direction {
+            Direction::Horizontal => ScrollbarGeometry {
+                track: Rect::new(r.x0, r.y1 - thickness, r.x0 + track_length, r.y1),
+                thumb: Rect::new(
+                    r.x0 + thumb_start,
+                    r.y1 - thickness,
+                    r.x0 + thumb_start + thumb_length,
+                    r.y1,
+                ),
+            },
+            Direction::Vertical => ScrollbarGeometry {
+                track: Rect::new(r.x1 - thickness, r.y0, r.x1, r.y0 + track_length),
+                thumb: Rect::new(
+                    r.x1 - thickness,
+                    r.y0 + thumb_start,
+                    r.x1,
+                    r.y0 + thumb_start + thumb_length,
+                ),
+            },
+        })
+    }
+}
+
+impl<E: ?Sized + Element> Scroll<E> {
+    /// Sets the scroll offset.
+    ///
+    /// The offset is clamped to the range allowed by the size of the content. Axes along which
+    /// scrolling is disabled are ignored.
+    ///
+    /// # Returns
+    ///
+    /// Whether the scroll offset changed.
+    ///
+    /// # Remarks
+    ///
+    /// This stops any inertial scroll in progress.
+    pub fn set_scroll_offset(&mut self, elem_context: &ElemContext, offset: Vec2) -> bool {
+        self.stop_inertia();
+
+        let max = self.max_scroll_offset();
+        let offset = Vec2::new(
+            if self.scroll_x {
+                offset.x.clamp(0.0, max.x)
+            } else {
+                0.0
+            },
+            if self.scroll_y {
+                offset.y.clamp(0.0, max.y)
+            } else {
+                0.0
+            },
+        );
+
+        if offset == self.offset {
+            return false;
+        }
+
+        self.offset = offset;
+        self.place_child(elem_context);
+        elem_context.window.request_redraw();
+        true
+    }
+
+    /// Stops the inertial scroll in progress, if any.
+    fn stop_inertia(&mut self) {
+        self.velocity = Vec2::ZERO;
+        self.last_scroll = None;
+        self.last_frame = None;
+    }
+
+    /// Records a scroll event to estimate the velocity of the content.
+    ///
+    /// `delta` is the change of the scroll offset caused by the event.
+    fn record_scroll(&mut self, delta: Vec2) {
+        let now = Instant::now();
+        let interval = self
+            .last_scroll
+            .map(|last| now - last)
+            .filter(|&interval| interval < INERTIA_RELEASE_DELAY);
+        self.last_scroll = Some(now);
+        self.last_frame = None;
+
+        // The first event of a gesture says nothing about its speed. This also prevents a
+        // single notch of a mouse wheel from flinging the content.
+        let Some(interval) = interval else {
+            self.velocity = Vec2::ZERO;
+            return;
+        };
+
+        let instant_velocity = delta / interval.as_secs_f64().max(MIN_SCROLL_INTERVAL);
+        self.velocity = (self.velocity + instant_velocity) / 2.0;
+    }
+
+    /// Advances the inertial scroll, returning whether it is still running.
+    fn animate_inertia(&mut self, elem_context: &ElemContext) -> bool {
+        if !self.inertia {
+            return false;
+        }
+
+        let max = self.max_scroll_offset();
+        let clamped = Vec2::new(
+            self.offset.x.clamp(0.0, max.x),
+            self.offset.y.clamp(0.0, max.y),
+        );
+        if self.velocity == Vec2::ZERO && self.offset == clamped {
+            self.last_frame = None;
+            return false;
+        }
+
+        let now = Instant::now();
+        if self
+            .last_scroll
+            .is_some_and(|last| now - last < INERTIA_RELEASE_DELAY)
+        {
+            // The user is still scrolling. Keep drawing to notice when they stop.
+            return true;
+        }
+
+        let dt = self
+            .last_frame
+            .map_or(0.0, |last| (now - last).as_secs_f64());
+        self.last_frame = Some(now);
+
+        let scale_factor = self.layout_context.scale_factor;
+        let max_overshoot = MAX_OVERSHOOT * scale_factor;
+        let stop_velocity = INERTIA_STOP_VELOCITY * scale_factor;
+
+        let axis = |offset: f64, velocity: f64, max: f64| {
+            let clamped = offset.clamp(0.0, max);
+            let overshooting = offset != clamped;
+
+            let rate = if overshooting {
+                INERTIA_OVERSHOOT_RATE
+            } else {
+                INERTIA_FRICTION_RATE
+            };
+            let mut velocity = exp_decay(velocity, 0.0, rate, dt);
+            let mut offset = (offset + velocity * dt).clamp(-max_overshoot, max + max_overshoot);
+
+            if velocity.abs() < stop_velocity {
+                velocity = 0.0;
+
+                // Spring back within the bounds of the content.
+                let clamped = offset.clamp(0.0, max);
+                offset = exp_decay(offset, clamped, INERTIA_SPRING_RATE, dt);
+                if (offset - clamped).abs() < 0.5 {
+                    offset = clamped;
+                }
+            }
+
+            (offset, velocity)
+        };
+
+        let (x, vx) = axis(self.offset.x, self.velocity.x, max.x);
+        let (y, vy) = axis(self.offset.y, self.velocity.y, max.y);
+        self.offset = Vec2::new(x, y);
+        self.velocity = Vec2::new(vx, vy);
+        self.place_child(elem_context);
+
+        true
+    }
+
+    /// Places the child element according to the current scroll offset.
+    fn place_child(&mut self, elem_context: &ElemContext) {
+        self.child.place(
+            elem_context,
+            self.layout_context,
+            self.rect.origin() - self.offset,
+            self.content_size,
+        );
+    }
+
+    /// Returns the space given to the child element to compute its size hint.
+    fn child_space(&self, space: Size) -> Size {
+        Size::new(
+            if self.scroll_x {
+                f64::INFINITY
+            } else {
+                space.width
+            },
+            if self.scroll_y {
+                f64::INFINITY
+            } else {
+                space.height
+            },
+        )
+    }
+
+    /// Handles the pointer events used to drag the thumbs of the scrollbars.
+    fn handle_thumb_drag(&mut self, elem_context: &ElemContext, event: &dyn Event) -> EventResult {
+        if let Some(ev) = event.downcast_ref::<PointerButton>() {
+            if !ev.primary || !matches!(ev.button, ButtonSource::Mouse(MouseButton::Left)) {
+                return EventResult::Continue;
+            }
+
+            if !ev.state.is_pressed() {
+                return match self.drag.take() {
+                    Some(_) => EventResult::Handled,
+                    None => EventResult::Continue,
+                };
+            }
+
+            for direction in [Direction::Horizontal, Direction::Vertical] {
+                let Some(geometry) = self.scrollbar(direction) else {
+                    continue;
+                };
+
+                if !geometry.track.contains(ev.position) {
+                    continue;
+                }
+
+                let (pointer, thumb_start, thumb_end, offset) = match direction {
+                    Direction::Horizontal => (
+                        ev.position.x,
+                        geometry.thumb.x0,
+                        geometry.thumb.x1,
+                        self.offset.x,
+                    ),
+                    Direction::Vertical => (
+                        ev.position.y,
+                        geometry.thumb.y0,
+                        geometry.thumb.y1,
+                        self.offset.y,
+                    ),
+                };
+
+                let mut start_offset = offset;
+                if pointer < thumb_start || pointer > thumb_end {
+                    // Clicking the track outside of the thumb centers the thumb on the pointer.
+                    let delta = pointer - (thumb_start + thumb_end) / 2.0;
+                    start_offset += delta * self.content_per_track_pixel(direction);
+                    let mut new_offset = self.offset;
+                    match direction {
+                        Direction::Horizontal => new_offset.x = start_offset,
+                        Direction::Vertical => new_offset.y = start_offset,
+                    }
+                    self.set_scroll_offset(elem_context, new_offset);
+                    start_offset = match direction {
+                        Direction::Horizontal => self.offset.x,
+                        Direction::Vertical => self.offset.y,
+                    };
+                }
+
+                self.stop_inertia();
+                self.drag = Some(ThumbDrag {
+                    direction,
+                    start_pointer: pointer,
+                    start_offset,
+                });
+                return EventResult::Handled;
+            }
+        } else if let Some(ev) = event.downcast_ref::<PointerMoved>() {
+            let Some(drag) = self.drag else {
+                return EventResult::Continue;
+            };
+
+            if !ev.primary {
+                return EventResult::Continue;
+            }
+
+            let pointer = match drag.direction {
+                Direction::Horizontal => ev.position.x,
+                Direction::Vertical => ev.position.y,
+            };
+            let target = drag.start_offset
+                + (pointer - drag.start_pointer) * self.content_per_track_pixel(drag.direction);
+
+            let mut new_offset = self.offset;
+            match drag.direction {
+                Direction::Horizontal => new_offset.x = target,
+                Direction::Vertical => new_offset.y = target,
+            }
+            self.set_scroll_offset(elem_context, new_offset);
+            return EventResult::Handled;
+        } else if event.is::<PointerLeft>() {
+            // The release of the button might happen outside of the window, in which case the
+            // element would never know about it.
+            self.drag = None;
+        }
+
+        EventResult::Continue
+    }
+
+    /// Returns the distance by which the content scrolls when the thumb of the provided
+    /// scrollbar moves by one pixel.
+    fn content_per_track_pixel(&self, direction: Direction) -> f64 {
+        let Some(geometry) = self.scrollbar(direction) else {
+            return 0.0;
+        };
+
+        let (free_track, max) = match direction {
+            Direction::Horizontal => (
+                geometry.track.width() - geometry.thumb.width(),
+                self.max_scroll_offset().x,
+            ),
+            Direction::Vertical => (
+                geometry.track.height() - geometry.thumb.height(),
+                self.max_scroll_offset().y,
+            ),
+        };
+
+        if free_track > 0.0 {
+            max / free_track
+        } else {
+            0.0
+        }
+    }
+}
+
+impl<E: ?Sized + Element> Element for Scroll<E> {
+    fn size_hint(
+        &mut self,
+        elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        space: Size,
+    ) -> SizeHint {
+        let child_hint = self.child.size_hint(
+            elem_context,
+            layout_context.with_parent(space),
+            self.child_space(space),
+        );
+
+        let finite_or = |f: f64, default: f64| if f.is_finite() { f } else { default };
+
+        // Along scrolling axes, the element can shrink down to nothing.
+        SizeHint {
+            preferred: Size::new(
+                if self.scroll_x {
+                    finite_or(space.width, child_hint.preferred.width)
+                } else {
+                    child_hint.preferred.width
+                },
+                if self.scroll_y {
+                    finite_or(space.height, child_hint.preferred.height)
+                } else {
+                    child_hint.preferred.height
+                },
+            ),
+            min: Size::new(
+                if self.scroll_x {
+                    0.0
+                } else {
+                    child_hint.min.width
+                },
+                if self.scroll_y {
+                    0.0
+                } else {
+                    child_hint.min.height
+                },
+            ),
+            max: Size::new(f64::INFINITY, f64::INFINITY),
+        }
+    }
+
+    fn place(
+        &mut self,
+        elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        pos: Point,
+        size: Size,
+    ) {
+        self.layout_context = layout_context.with_parent(size);
+        self.rect = Rect::from_origin_size(pos, size);
+        self.computed_thickness = self.scrollbar_thickness.resolve(&layout_context);
+        self.computed_radius = self.radius.resolve(&layout_context);
+
+        let child_hint =
+            self.child
+                .size_hint(elem_context, self.layout_context, self.child_space(size));
+
+        // The content always covers the whole element.
+        let content_along = |scroll: bool, preferred: f64, viewport: f64| {
+            if scroll && preferred.is_finite() {
+                preferred.max(viewport)
+            } else {
+                viewport
+            }
+        };
+        self.content_size = Size::new(
+            content_along(self.scroll_x, child_hint.preferred.width, size.width),
+            content_along(self.scroll_y, child_hint.preferred.height, size.height),
+        )
+        .clamp(child_hint.min, child_hint.max);
+
+        // The content might have shrunk since the last layout.
+        let max = self.max_scroll_offset();
+        self.offset = Vec2::new(self.offset.x.min(max.x), self.offset.y.min(max.y));
+
+        self.place_child(elem_context);
+    }
+
+    fn hit_test(&self, point: Point) -> bool {
+        self.rect.contains(point)
+            && (self.child.hit_test(point)
+                || [Direction::Horizontal, Direction::Vertical]
+                    .into_iter()
+                    .filter_map(|direction| self.scrollbar(direction))
+                    .any(|geometry| geometry.track.contains(point)))
+    }
+
+    fn draw(&mut self, elem_context: &ElemContext, scene: &mut vello::Scene) {
+        if self.animate_inertia(elem_context) {
+            elem_context.window.request_redraw();
+        }
+
+        let clip = self.rect.to_rounded_rect(self.computed_radius);
+        scene.push_layer(Mix::Clip, 1.0, Affine::IDENTITY, &clip);
+
+        self.child.draw(elem_context, scene);
+
+        // The scrollbars are drawn within the clip so that they follow the rounded corners.
+        for direction in [Direction::Horizontal, Direction::Vertical] {
+            if let Some(geometry) = self.scrollbar(direction) {
+                let radius = self.computed_thickness / 2.0;
+                scene.fill(
+                    Fill::NonZero,
+                    Affine::IDENTITY,
+                    &self.track_brush,
+                    None,
+                    &geometry.track.to_rounded_rect(radius),
+                );
+                scene.fill(
+                    Fill::NonZero,
+                    Affine::IDENTITY,
+                    &self.thumb_brush,
+                    None,
+                    &geometry.thumb.to_rounded_rect(radius),
+                );
+            }
+        }
+
+        scene.pop_layer();
+    }
+
+    fn event(&mut self, elem_context: &ElemContext, event: &dyn Event) -> EventResult {
+        if self.handle_thumb_drag(elem_context, event).is_handled() {
+            return EventResult::Handled;
+        }
+
+        if self.child.event(elem_context, event).is_handled() {
+            return EventResult::Handled;
+        }
+
+        if let Some(ev) = event.downcast_ref::<PointerScrolled>() {
+            if !self.rect.contains(ev.position) {
+                return EventResult::Continue;
+            }
+
+            let delta = if ev.modifiers.shift_key() && ev.delta.x == 0.0 {
+                Vec2::new(ev.delta.y, 0.0)
+            } else {
+                ev.delta
+            };
+
+            let last_scroll = self.last_scroll;
+            let velocity = self.velocity;
+            let previous = self.offset;
+            if self.set_scroll_offset(elem_context, self.offset - delta) {
+                if self.inertia {
+                    // Setting the offset stopped the inertial scroll, but this event belongs
+                    // to the same gesture.
+                    self.last_scroll = last_scroll;
+                    self.velocity = velocity;
+                    self.record_scroll(self.offset - previous);
+                    elem_context.window.request_redraw();
+                }
+                return EventResult::Handled;
+            }
+        }
+
+        EventResult::Continue
+    }
+
+    #[inline]
+    fn begin(&mut self, elem_context: &ElemContext) {
+        self.child.begin(elem_context);
+    }
+
+    #[inline]
+    fn accessibility(&self, cx: &mut AccessibilityContext) {
+        self.child.accessibility(cx);
+    }
+}
@@ -0,0 +1,671 @@
+use {
+    crate::{
+        ElemContext, Element, LayoutContext, SizeHint,
+        accessibility::AccessibilityContext,
+        elements::Length,
+        event::{Event, EventResult, PointerButton, PointerMoved},
+    },
+    vello::{
+        kurbo::{Affine, Insets, Point, Rect, Size, Stroke},
+        peniko::{Brush, Color, Fill},
+    },
+    winit::{
+        event::{ButtonSource, MouseButton},
+        keyboard::ModifiersState,
+    },
+};
+
+/// Describes how a user interaction should modify a [`Selection`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum SelectionMode {
+    /// The selection is replaced.
+    #[default]
+    Replace,
+    /// The interacted items are toggled in/out of the selection.
+    Toggle,
+    /// The selection is extended up to the interacted items.
+    Extend,
+}
+
+impl SelectionMode {
+    /// Determines the selection mode from the state of the keyboard modifiers.
+    ///
+    /// - Shift extends the selection.
+    ///
+    /// - Ctrl (Cmd on macOS) toggles items.
+    ///
+    /// - Otherwise, the selection is replaced.
+    pub fn from_modifiers(modifiers: ModifiersState) -> Self {
+        let toggle = if cfg!(target_os = "macos") {
+            modifiers.super_key()
+        } else {
+            modifiers.control_key()
+        };
+
+        if modifiers.shift_key() {
+            Self::Extend
+        } else if toggle {
+            Self::Toggle
+        } else {
+            Self::Replace
+        }
+    }
+}
+
+/// A set of selected items.
+///
+/// Items are identified by an `Id` value that is cheap to clone and compare (an index, a
+/// slotmap key, etc).
+///
+/// Every method that modifies the selection returns whether the selection actually changed,
+/// which can be used to notify the rest of the application.
+#[derive(Clone, Debug)]
+pub struct Selection<Id> {
+    /// The items that are currently selected.
+    selected: Vec<Id>,
+    /// The item from which range selections start.
+    ///
+    /// This is the last item that was clicked without extending the selection.
+    anchor: Option<Id>,
+    /// The selection as it was when the current marquee selection started.
+    ///
+    /// This is `None` when no marquee selection is in progress.
+    marquee_base: Option<Vec<Id>>,
+}
+
+impl<Id> Default for Selection<Id> {
+    fn default() -> Self {
+        Self {
+            selected: Vec::new(),
+            anchor: None,
+            marquee_base: None,
+        }
+    }
+}
+
+impl<Id: Clone + PartialEq> Selection<Id> {
+    /// Creates a new empty [`Selection`].
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns whether the provided item is selected.
+    #[inline]
+    pub fn is_selected(&self, id: &Id) -> bool {
+        self.selected.contains(id)
+    }
+
+    /// Returns the selected items, in the order they were selected.
+    #[inline]
+    pub fn selected(&self) -> &[Id] {
+        &self.selected
+    }
+
+    /// Returns the number of selected items.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.selected.len()
+    }
+
+    /// Returns whether no item is selected.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.selected.is_empty()
+    }
+
+    /// Returns the item from which range selections start.
+    #[inline]
+    pub fn anchor(&self) -> Option<&Id> {
+        self.anchor.as_ref()
+    }
+
+    /// Removes all items from the selection.
+    pub fn clear(&mut self) -> bool {
+        self.anchor = None;
+        let changed = !self.selected.is_empty();
+        self.selected.clear();
+        changed
+    }
+
+    /// Replaces the selection with a single item.
+    pub fn select(&mut self, id: Id) -> bool {
+        let changed = self.selected.len() != 1 || self.selected[0] != id;
+        self.selected.clear();
+        self.selected.push(id.clone());
+        self.anchor = Some(id);
+        changed
+    }
+
+    /// Adds an item to the selection, or removes it if it was already selected.
+    pub fn toggle(&mut self, id: Id) -> bool {
+        match self.selected.iter().position(|x| *x == id) {
+            Some(index) => {
+                self.selected.remove(index);
+            }
+            None => self.selected.push(id.clone()),
+        }
+        self.anchor = Some(id);
+        true
+    }
+
+    /// Selects every item between the anchor and `id` (inclusive), replacing the previous
+    /// selection.
+    ///
+    /// `order` is the list of all items, in the order in which they are displayed. If no anchor
+    /// is set, or if the anchor is not part of `order`, this behaves like [`select`].
+    ///
+    /// The anchor is not modified, allowing the range to be adjusted by subsequent calls.
+    ///
+    /// [`select`]: Self::select
+    pub fn select_range(&mut self, order: &[Id], id: Id) -> bool {
+        let anchor_index = self
+            .anchor
+            .as_ref()
+            .and_then(|anchor| order.iter().position(|x| x == anchor));
+        let target_index = order.iter().position(|x| *x == id);
+
+        let (Some(a), Some(b)) = (anchor_index, target_index) else {
+            return self.select(id);
+        };
+
+        let range = &order[a.min(b)..=a.max(b)];
+
+        let changed =
+            self.selected.len() != range.len() || range.iter().any(|x| !self.selected.contains(x));
+        self.selected.clear();
+        self.selected.extend_from_slice(range);
+        changed
+    }
+
+    /// Handles a click on the provided item.
+    ///
+    /// # Parameters
+    ///
+    /// - `id`: The item that was clicked.
+    ///
+    /// - `mode`: How the selection should be modified. This is usually computed from the keyboard
+    ///   modifiers with [`SelectionMode::from_modifiers`].
+    ///
+    /// - `order`: The list of all items in display order, used to resolve range selections.
+    pub fn click(&mut self, id: Id, mode: SelectionMode, order: &[Id]) -> bool {
+        match mode {
+            SelectionMode::Replace => self.select(id),
+            SelectionMode::Toggle => self.toggle(id),
+            SelectionMode::Extend => self.select_range(order, id),
+        }
+    }
+
+    /// Starts a marquee (rubber-band) selection.
+    ///
+    /// The current selection is remembered so that it can be combined with the items covered by
+    /// the marquee in [`update_marquee`](Self::update_marquee).
+    pub fn begin_marquee(&mut self) {
+        self.marquee_base = Some(self.selected.clone());
+    }
+
+    /// Updates the selection with the items intersecting the marquee rectangle.
+    ///
+    /// # Parameters
+    ///
+    /// - `items`: The items that may be selected, along with their bounds.
+    ///
+    /// - `rect`: The bounds of the marquee.
+    ///
+    /// - `mode`: How the items intersecting the marquee are combined with the selection as it
+    ///   was when the marquee started. [`Replace`](SelectionMode::Replace) ignores the previous
+    ///   selection, [`Extend`](SelectionMode::Extend) adds to it and
+    ///   [`Toggle`](SelectionMode::Toggle) inverts the covered items.
+    ///
+    /// If no marquee selection is in progress, the current selection is used as the base.
+    pub fn update_marquee(
+        &mut self,
+        items: impl IntoIterator<Item = (Id, Rect)>,
+        rect: Rect,
+        mode: SelectionMode,
+    ) -> bool {
+        let base = self
+            .marquee_base
+            .get_or_insert_with(|| self.selected.clone());
+
+        let mut new_selection = match mode {
+            SelectionMode::Replace => Vec::new(),
+            SelectionMode::Extend | SelectionMode::Toggle => base.clone(),
+        };
+
+        for (id, bounds) in items {
+            if bounds.intersect(rect).area() <= 0.0 && !rect.contains(bounds.origin()) {
+                continue;
+            }
+
+            match new_selection.iter().position(|x| *x == id) {
+                Some(index) if mode == SelectionMode::Toggle => {
+                    new_selection.remove(index);
+                }
+                Some(_) => (),
+                None => new_selection.push(id),
+            }
+        }
+
+        let changed = new_selection.len() != self.selected.len()
+            || new_selection.iter().any(|x| !self.selected.contains(x));
+        self.selected = new_selection;
+        changed
+    }
+
+    /// Ends the current marquee selection, keeping the items that were selected.
+    pub fn end_marquee(&mut self) {
+        self.marquee_base = None;
+    }
+
+    /// Returns whether a marquee selection is currently in progress.
+    #[inline]
+    pub fn is_marquee_active(&self) -> bool {
+        self.marquee_base.is_some()
+    }
+}
+
+/// The phase of a marquee drag.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MarqueePhase {
+    /// The user just pressed the pointer on an empty part of the area.
+    Started,
+    /// The pointer moved while dragging.
+    Moved,
+    /// The user released the pointer.
+    Ended,
+}
+
+/// Information about a marquee drag, passed to the [`OnMarquee`] function of a
+/// [`SelectionArea`].
+#[derive(Clone, Copy, Debug)]
+pub struct Marquee {
+    /// The phase of the drag.
+    pub phase: MarqueePhase,
+    /// The rectangle covered by the marquee, in window coordinates.
+    pub rect: Rect,
+    /// The selection mode, computed from the keyboard modifiers held when the drag started.
+    pub mode: SelectionMode,
+}
+
+/// The function called by a [`SelectionArea`] when the marquee changes.
+pub trait OnMarquee<E: ?Sized> {
+    /// The marquee has changed.
+    fn on_marquee(&mut self, child: &mut E, elem_context: &ElemContext, marquee: &Marquee);
+}
+
+impl<E: ?Sized> OnMarquee<E> for () {
+    fn on_marquee(&mut self, _child: &mut E, _elem_context: &ElemContext, _marquee: &Marquee) {}
+}
+
+impl<E: ?Sized, F> OnMarquee<E> for F
+where
+    F: FnMut(&mut E, &ElemContext, &Marquee),
+{
+    fn on_marquee(&mut self, child: &mut E, elem_context: &ElemContext, marquee: &Marquee) {
+        self(child, elem_context, marquee)
+    }
+}
+
+/// An in-progress marquee drag.
+#[derive(Clone, Copy, Debug)]
+struct MarqueeDrag {
+    /// The position at which the drag started.
+    origin: Point,
+    /// The current position of the pointer.
+    current: Point,
+    /// The selection mode for the drag.
+    mode: SelectionMode,
+}
+
+impl MarqueeDrag {
+    /// Returns the rectangle covered by the drag.
+    #[inline]
+    fn rect(&self) -> Rect {
+        Rect::from_points(self.origin, self.current)
+    }
+}
+
+/// An element that lets the user drag a marquee (rubber-band) rectangle over its child in order
+/// to select multiple items.
+///
+/// A drag starts when the primary pointer button is pressed over the area and the child element
+/// did not handle the press (for example, the press was on an empty part of a canvas rather than
+/// on an item). The pointer keeps being tracked until it is released, even if it leaves the area.
+///
+/// The marquee is reported to the [`OnMarquee`] function, which is usually responsible for
+/// updating a [`Selection`] with [`Selection::update_marquee`].
+///
+/// While a drag is in progress, the marquee is drawn as a translucent rectangle on top of the
+/// child element. It disappears as soon as the pointer is released.
+#[derive(Clone, Debug)]
+pub struct SelectionArea<F, E: ?Sized> {
+    /// The function called when the marquee changes.
+    pub on_marquee: F,
+    /// The brush used to fill the marquee.
+    pub marquee_brush: Option<Brush>,
+    /// The brush used to draw the outline of the marquee.
+    pub marquee_border_brush: Option<Brush>,
+    /// The thickness of the outline of the marquee.
+    pub marquee_border_thickness: Length,
+    /// The resolved value of `marquee_border_thickness`.
+    computed_border_thickness: f64,
+    /// The marquee drag currently in progress.
+    drag: Option<MarqueeDrag>,
+    /// The bounds of the element, in window coordinates.
+    bounds: Rect,
+    /// The child element.
+    pub child: E,
+}
+
+impl<F, E> SelectionArea<F, E> {
+    /// Creates a new [`SelectionArea`] element.
+    #[inline]
+    pub fn new(on_marquee: F, child: E) -> Self
+    where
+        F: OnMarquee<E>,
+    {
+        Self {
+            on_marquee,
+            marquee_brush: Some(Color::from_rgba8(0x4a, 0x90, 0xe2, 0x40).into()),
+            marquee_border_brush: Some(Color::from_rgba8(0x4a, 0x90, 0xe2, 0xc0).into()),
+            marquee_border_thickness: Length::Pixels(1.0),
+            computed_border_thickness: 0.0,
+            drag: None,
+            bounds: Rect::ZERO,
+            child,
+        }
+    }
+
+    /// Sets the brush used to fill the marquee.
+    pub fn marquee_brush(mut self, brush: impl Into<Brush>) -> Self {
+        self.marquee_brush = Some(brush.into());
+        self
+    }
+
+    /// Sets the brush used to draw the outline of the marquee.
+    pub fn marquee_border_brush(mut self, brush: impl Into<Brush>) -> Self {
+        self.marquee_border_brush = Some(brush.into());
+        self
+    }
+
+    /// Sets the thickness of the outline of the marquee.
+    pub fn marquee_border_thickness(mut self, thickness: Length) -> Self {
+        self.marquee_border_thickness = thickness;
+        self
+    }
+
+    /// Sets the function called when the marquee changes.
+    #[inline]
+    pub fn on_marquee<F2>(self, on_marquee: F2) -> SelectionArea<F2, E>
+    where
+        F2: FnMut(&mut E, &ElemContext, &Marquee),
+    {
+        SelectionArea {
+            on_marquee,
+            marquee_brush: self.marquee_brush,
+            marquee_border_brush: self.marquee_border_brush,
+            marquee_border_thickness: self.marquee_border_thickness,
+            computed_border_thickness: self.computed_border_thickness,
+            drag: self.drag,
+            bounds: self.bounds,
+            child: self.child,
+        }
+    }
+
+    /// Sets the child element of this [`SelectionArea`].
+    #[inline]
+    pub fn child<E2>(self, child: E2) -> SelectionArea<F, E2> {
+        SelectionArea {
+            on_marquee: self.on_marquee,
+            marquee_brush: self.marquee_brush,
+            marquee_border_brush: self.marquee_border_brush,
+            marquee_border_thickness: self.marquee_border_thickness,
+            computed_border_thickness: self.computed_border_thickness,
+            drag: None,
+            bounds: self.bounds,
+            child,
+        }
+    }
+}
+
+impl<F, E: ?Sized> SelectionArea<F, E> {
+    /// Returns the rectangle covered by the marquee, if a drag is in progress.
+    ///
+    /// The rectangle is expressed in window coordinates, just like the bounds of the child
+    /// elements, which means that it can be passed directly to [`Selection::update_marquee`].
+    #[inline]
+    pub fn marquee_rect(&self) -> Option<Rect> {
+        self.drag.as_ref().map(MarqueeDrag::rect)
+    }
+
+    /// Draws the marquee, if a drag is in progress.
+    fn draw_marquee(&self, scene: &mut vello::Scene) {
+        let Some(rect) = self.marquee_rect() else {
+            return;
+        };
+
+        if let Some(brush) = self.marquee_brush.as_ref() {
+            scene.fill(Fill::NonZero, Affine::IDENTITY, brush, None, &rect);
+        }
+
+        if let Some(border_brush) = self.marquee_border_brush.as_ref() {
+            if self.computed_border_thickness > 0.0 {
+                scene.stroke(
+                    &Stroke::new(self.computed_border_thickness),
+                    Affine::IDENTITY,
+                    border_brush,
+                    None,
+                    &(rect - Insets::uniform(self.computed_border_thickness / 2.0)),
+                );
+            }
+        }
+    }
+}
+
+impl<E: Default> Default for SelectionArea<(), E> {
+    #[inline]
+    fn default() -> Self {
+        Self::new((), E::default())
+    }
+}
+
+impl<F, E> Element for SelectionArea<F, E>
+where
+    F: OnMarquee<E>,
+    E: ?Sized + Element,
+{
+    #[inline]
+    fn size_hint(
+        &mut self,
+        elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        space: Size,
+    ) -> SizeHint {
+        self.child.size_hint(elem_context, layout_context, space)
+    }
+
+    #[inline]
+    fn place(
+        &mut self,
+        elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        pos: Point,
+        size: Size,
+    ) {
+        self.bounds = Rect::from_origin_size(pos, size);
+        self.computed_border_thickness = self.marquee_border_thickness.resolve(&layout_context);
+        self.child.place(elem_context, layout_context, pos, size);
+    }
+
+    #[inline]
+    fn hit_test(&self, point: Point) -> bool {
+        self.bounds.contains(point)
+    }
+
+    #[inline]
+    fn draw(&mut self, elem_context: &ElemContext, scene: &mut vello::Scene) {
+        self.child.draw(elem_context, scene);
+        self.draw_marquee(scene);
+    }
+
+    fn event(&mut self, elem_context: &ElemContext, event: &dyn Event) -> EventResult {
+        if let Some(drag) = self.drag.as_mut() {
+            if let Some(ev) = event.downcast_ref::<PointerMoved>() {
+                if !ev.primary {
+                    return EventResult::Continue;
+                }
+
+                drag.current = ev.position;
+                let marquee = Marquee {
+                    phase: MarqueePhase::Moved,
+                    rect: drag.rect(),
+                    mode: drag.mode,
+                };
+                self.on_marquee
+                    .on_marquee(&mut self.child, elem_context, &marquee);
+                elem_context.window.request_redraw();
+                return EventResult::Handled;
+            }
+
+            if let Some(ev) = event.downcast_ref::<PointerButton>() {
+                if ev.primary
+                    && !ev.state.is_pressed()
+                    && matches!(ev.button, ButtonSource::Mouse(MouseButton::Left))
+                {
+                    drag.current = ev.position;
+                    let marquee = Marquee {
+                        phase: MarqueePhase::Ended,
+                        rect: drag.rect(),
+                        mode: drag.mode,
+                    };
+                    self.drag = None;
+                    self.on_marquee
+                        .on_marquee(&mut self.child, elem_context, &marquee);
+                    elem_context.window.request_redraw();
+                    return EventResult::Handled;
+                }
+            }
+        }
+
+        if self.child.event(elem_context, event).is_handled() {
+            return EventResult::Handled;
+        }
+
+        if let Some(ev) = event.downcast_ref::<PointerButton>() {
+            if ev.primary
+                && ev.state.is_pressed()
+                && matches!(ev.button, ButtonSource::Mouse(MouseButton::Left))
+                && self.bounds.contains(ev.position)
+            {
+                let drag = MarqueeDrag {
+                    origin: ev.position,
+                    current: ev.position,
+                    mode: SelectionMode::from_modifiers(ev.modifiers),
+                };
+                self.drag = Some(drag);
+                let marquee = Marquee {
+                    phase: MarqueePhase::Started,
+                    rect: drag.rect(),
+                    mode: drag.mode,
+                };
+                self.on_marquee
+                    .on_marquee(&mut self.child, elem_context, &marquee);
+                return EventResult::Handled;
+            }
+        }
+
+        EventResult::Continue
+    }
+
+    #[inline]
+    fn begin(&mut self, elem_context: &ElemContext) {
+        self.child.begin(elem_context);
+    }
+
+    #[inline]
+    fn accessibility(&self, cx: &mut AccessibilityContext) {
+        self.child.accessibility(cx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::{Selection, SelectionMode},
+        vello::kurbo::Rect,
+    };
+
+    const ORDER: [u32; 5] = [0, 1, 2, 3, 4];
+
+    #[test]
+    fn toggle_adds_and_removes_items() {
+        let mut selection = Selection::new();
+
+        assert!(selection.toggle(1));
+        assert!(selection.toggle(3));
+        assert_eq!(selection.selected(), [1, 3]);
+
+        assert!(selection.toggle(1));
+        assert_eq!(selection.selected(), [3]);
+        assert_eq!(selection.anchor(), Some(&1));
+    }
+
+    #[test]
+    fn range_spans_from_anchor_in_both_directions() {
+        let mut selection = Selection::new();
+        selection.select(2);
+
+        assert!(selection.select_range(&ORDER, 4));
+        assert_eq!(selection.selected(), [2, 3, 4]);
+
+        // The anchor is kept, so the range can be adjusted on the other side.
+        assert!(selection.select_range(&ORDER, 0));
+        assert_eq!(selection.selected(), [0, 1, 2]);
+        assert_eq!(selection.anchor(), Some(&2));
+
+        assert!(!selection.select_range(&ORDER, 0));
+    }
+
+    #[test]
+    fn range_without_anchor_selects_single_item() {
+        let mut selection = Selection::new();
+
+        assert!(selection.select_range(&ORDER, 3));
+        assert_eq!(selection.selected(), [3]);
+        assert_eq!(selection.anchor(), Some(&3));
+    }
+
+    #[test]
+    fn replace_discards_previous_selection() {
+        let mut selection = Selection::new();
+        selection.click(0, SelectionMode::Replace, &ORDER);
+        selection.click(2, SelectionMode::Toggle, &ORDER);
+        assert_eq!(selection.selected(), [0, 2]);
+
+        assert!(selection.click(4, SelectionMode::Replace, &ORDER));
+        assert_eq!(selection.selected(), [4]);
+
+        assert!(!selection.click(4, SelectionMode::Replace, &ORDER));
+    }
+
+    #[test]
+    fn marquee_toggles_covered_items() {
+        let items = ORDER.map(|id| {
+            let x = id as f64 * 10.0;
+            (id, Rect::new(x, 0.0, x + 5.0, 5.0))
+        });
+
+        let mut selection = Selection::new();
+        selection.select(1);
+        selection.begin_marquee();
+
+        // Covers the items 1 and 2.
+        let rect = Rect::new(8.0, 0.0, 22.0, 5.0);
+        assert!(selection.update_marquee(items, rect, SelectionMode::Toggle));
+        assert_eq!(selection.selected(), [2]);
+
+        selection.end_marquee();
+        assert!(!selection.is_marquee_active());
+    }
+}
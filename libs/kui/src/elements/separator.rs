@@ -0,0 +1,173 @@
+use {
+    super::{Length, flex::Direction},
+    crate::{ElemContext, Element, LayoutContext, SizeHint},
+    vello::{
+        kurbo::{Affine, Point, Rect, Size},
+        peniko::{Brush, Color, Fill},
+    },
+};
+
+/// A thin line used to visually separate groups of elements, such as the buttons of a toolbar.
+///
+/// The line runs along its [direction](Self::direction). Along that axis, the element takes all
+/// the space it is offered, which makes it stretch across the cross axis of a
+/// [`Flex`](super::flex::Flex) container laid out in the other direction. Across it, the element
+/// is only as large as the line itself.
+#[derive(Clone, Debug)]
+pub struct Separator {
+    /// The direction along which the line runs.
+    pub direction: Direction,
+    /// The thickness of the line.
+    pub thickness: Length,
+    /// The distance by which the line is shortened at both of its ends.
+    pub inset: Length,
+    /// The brush used to draw the line.
+    pub brush: Brush,
+
+    /// The rectangle in which the line is drawn.
+    line: Rect,
+}
+
+impl Default for Separator {
+    fn default() -> Self {
+        Self {
+            direction: Direction::Horizontal,
+            thickness: Length::Pixels(1.0),
+            inset: Length::ZERO,
+            brush: Color::from_rgba8(0xff, 0xff, 0xff, 0x20).into(),
+            line: Rect::ZERO,
+        }
+    }
+}
+
+impl Separator {
+    /// Creates a new [`Separator`] element.
+    ///
+    /// The line is horizontal by default.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the direction along which the line runs.
+    pub fn direction(mut self, direction: Direction) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Makes the line run horizontally.
+    pub fn horizontal(self) -> Self {
+        self.direction(Direction::Horizontal)
+    }
+
+    /// Makes the line run vertically.
+    ///
+    /// This is usually what is needed between the elements of a horizontal toolbar.
+    pub fn vertical(self) -> Self {
+        self.direction(Direction::Vertical)
+    }
+
+    /// Sets the thickness of the line.
+    pub fn thickness(mut self, thickness: Length) -> Self {
+        self.thickness = thickness;
+        self
+    }
+
+    /// Sets the distance by which the line is shortened at both of its ends.
+    pub fn inset(mut self, inset: Length) -> Self {
+        self.inset = inset;
+        self
+    }
+
+    /// Sets the brush used to draw the line.
+    pub fn brush(mut self, brush: impl Into<Brush>) -> Self {
+        self.brush = brush.into();
+        self
+    }
+
+    /// Returns the axis across which the thickness of the line is measured.
+    fn cross_direction(&self) -> Direction {
+        match self.direction {
+            Direction::Horizontal => Direction::Vertical,
+            Direction::Vertical => Direction::Horizontal,
+        }
+    }
+}
+
+impl Element for Separator {
+    fn size_hint(
+        &mut self,
+        _elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        space: Size,
+    ) -> SizeHint {
+        let thickness = self
+            .thickness
+            .resolve_along(&layout_context, self.cross_direction());
+        let finite_or_zero = |f: f64| if f.is_finite() { f } else { 0.0 };
+
+        let (preferred, min) = match self.direction {
+            Direction::Horizontal => (
+                Size::new(finite_or_zero(space.width), thickness),
+                Size::new(0.0, thickness),
+            ),
+            Direction::Vertical => (
+                Size::new(thickness, finite_or_zero(space.height)),
+                Size::new(thickness, 0.0),
+            ),
+        };
+
+        SizeHint {
+            preferred,
+            min,
+            max: Size::new(f64::INFINITY, f64::INFINITY),
+        }
+    }
+
+    fn place(
+        &mut self,
+        _elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        pos: Point,
+        size: Size,
+    ) {
+        let layout_context = layout_context.with_parent(size);
+        let thickness = self
+            .thickness
+            .resolve_along(&layout_context, self.cross_direction());
+        let inset = self.inset.resolve_along(&layout_context, self.direction);
+
+        // The line is centered within the bounds of the element, which might be larger than the
+        // line itself when the parent stretches the element.
+        let rect = Rect::from_origin_size(pos, size);
+        let center = rect.center();
+        self.line = match self.direction {
+            Direction::Horizontal => Rect::new(
+                rect.x0 + inset,
+                center.y - thickness / 2.0,
+                (rect.x1 - inset).max(rect.x0 + inset),
+                center.y + thickness / 2.0,
+            ),
+            Direction::Vertical => Rect::new(
+                center.x - thickness / 2.0,
+                rect.y0 + inset,
+                center.x + thickness / 2.0,
+                (rect.y1 - inset).max(rect.y0 + inset),
+            ),
+        };
+    }
+
+    fn draw(&mut self, _elem_context: &ElemContext, scene: &mut vello::Scene) {
+        if self.line.is_zero_area() {
+            return;
+        }
+
+        scene.fill(
+            Fill::NonZero,
+            Affine::IDENTITY,
+            &self.brush,
+            None,
+            &self.line,
+        );
+    }
+}
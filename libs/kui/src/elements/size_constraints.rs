@@ -0,0 +1,182 @@
+use {
+    super::{Length, flex::Direction},
+    crate::{
+        ElemContext, Element, LayoutContext, SizeHint,
+        accessibility::AccessibilityContext,
+        event::{Event, EventResult},
+    },
+    vello::kurbo::{Point, Size},
+};
+
+/// An element that constrains the size of its child.
+///
+/// The size hint reported by the child is clamped to the constraints, and so is the size given
+/// to the child when it is placed. When a minimum is larger than the corresponding maximum, the
+/// minimum wins.
+///
+/// Instances of this type are usually created through the methods of
+/// [`ElementExt`](crate::ElementExt), such as
+/// [`with_min_width`](crate::ElementExt::with_min_width). Calling those methods again on the
+/// resulting element updates its constraints rather than wrapping it a second time.
+#[derive(Clone, Debug, Default)]
+pub struct WithSizeConstraints<E: ?Sized> {
+    /// The minimum width of the child.
+    pub min_width: Option<Length>,
+    /// The minimum height of the child.
+    pub min_height: Option<Length>,
+    /// The maximum width of the child.
+    pub max_width: Option<Length>,
+    /// The maximum height of the child.
+    pub max_height: Option<Length>,
+    /// The child element.
+    pub child: E,
+}
+
+impl<E> WithSizeConstraints<E> {
+    /// Creates a new [`WithSizeConstraints`] element without any constraint.
+    #[inline]
+    pub fn new(child: E) -> Self {
+        Self {
+            min_width: None,
+            min_height: None,
+            max_width: None,
+            max_height: None,
+            child,
+        }
+    }
+
+    /// Sets the minimum width of the child.
+    pub fn with_min_width(mut self, min_width: impl Into<Option<Length>>) -> Self {
+        self.min_width = min_width.into();
+        self
+    }
+
+    /// Sets the minimum height of the child.
+    pub fn with_min_height(mut self, min_height: impl Into<Option<Length>>) -> Self {
+        self.min_height = min_height.into();
+        self
+    }
+
+    /// Sets the maximum width of the child.
+    pub fn with_max_width(mut self, max_width: impl Into<Option<Length>>) -> Self {
+        self.max_width = max_width.into();
+        self
+    }
+
+    /// Sets the maximum height of the child.
+    pub fn with_max_height(mut self, max_height: impl Into<Option<Length>>) -> Self {
+        self.max_height = max_height.into();
+        self
+    }
+
+    /// Sets the minimum size of the child.
+    pub fn with_min_size(self, width: Length, height: Length) -> Self {
+        self.with_min_width(width).with_min_height(height)
+    }
+
+    /// Sets the maximum size of the child.
+    pub fn with_max_size(self, width: Length, height: Length) -> Self {
+        self.with_max_width(width).with_max_height(height)
+    }
+
+    /// Sets the child element of this [`WithSizeConstraints`] element.
+    pub fn child<E2>(self, child: E2) -> WithSizeConstraints<E2> {
+        WithSizeConstraints {
+            min_width: self.min_width,
+            min_height: self.min_height,
+            max_width: self.max_width,
+            max_height: self.max_height,
+            child,
+        }
+    }
+}
+
+impl<E: ?Sized> WithSizeConstraints<E> {
+    /// Resolves the constraints to concrete minimum and maximum sizes.
+    ///
+    /// The returned maximum is never smaller than the returned minimum.
+    fn resolve(&self, layout_context: &LayoutContext) -> (Size, Size) {
+        let resolve = |length: &Option<Length>, direction: Direction, default: f64| {
+            length
+                .as_ref()
+                .map_or(default, |l| l.resolve_along(layout_context, direction))
+        };
+
+        let min = Size::new(
+            resolve(&self.min_width, Direction::Horizontal, 0.0),
+            resolve(&self.min_height, Direction::Vertical, 0.0),
+        );
+        let max = Size::new(
+            resolve(&self.max_width, Direction::Horizontal, f64::INFINITY).max(min.width),
+            resolve(&self.max_height, Direction::Vertical, f64::INFINITY).max(min.height),
+        );
+
+        (min, max)
+    }
+}
+
+/// Clamps each dimension of `size` between the ones of `min` and `max`.
+fn clamp_size(size: Size, min: Size, max: Size) -> Size {
+    Size::new(
+        size.width.clamp(min.width, max.width),
+        size.height.clamp(min.height, max.height),
+    )
+}
+
+impl<E: ?Sized + Element> Element for WithSizeConstraints<E> {
+    fn size_hint(
+        &mut self,
+        elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        space: Size,
+    ) -> SizeHint {
+        let (min, max) = self.resolve(&layout_context);
+
+        // The child can never be given more space than the maximum size.
+        let space = Size::new(space.width.min(max.width), space.height.min(max.height));
+        let hint = self.child.size_hint(elem_context, layout_context, space);
+
+        SizeHint {
+            preferred: clamp_size(hint.preferred, min, max),
+            min: clamp_size(hint.min, min, max),
+            max: clamp_size(hint.max, min, max),
+        }
+    }
+
+    fn place(
+        &mut self,
+        elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        pos: Point,
+        size: Size,
+    ) {
+        let (min, max) = self.resolve(&layout_context);
+        let size = clamp_size(size, min, max);
+        self.child.place(elem_context, layout_context, pos, size);
+    }
+
+    #[inline]
+    fn hit_test(&self, point: Point) -> bool {
+        self.child.hit_test(point)
+    }
+
+    #[inline]
+    fn draw(&mut self, elem_context: &ElemContext, scene: &mut vello::Scene) {
+        self.child.draw(elem_context, scene);
+    }
+
+    #[inline]
+    fn event(&mut self, elem_context: &ElemContext, event: &dyn Event) -> EventResult {
+        self.child.event(elem_context, event)
+    }
+
+    #[inline]
+    fn begin(&mut self, elem_context: &ElemContext) {
+        self.child.begin(elem_context);
+    }
+
+    #[inline]
+    fn accessibility(&self, cx: &mut AccessibilityContext) {
+        self.child.accessibility(cx);
+    }
+}
@@ -0,0 +1,522 @@
+use {
+    crate::{
+        ElemContext, Element, LayoutContext, SizeHint,
+        accessibility::{AccessibilityContext, NodeIdCell, to_accesskit_rect},
+        elements::{
+            Length, flex::Direction, interactive::InteractiveState, number_field::OnNumberChange,
+        },
+        event::{Event, EventResult, KeyEvent, PointerButton, PointerMoved},
+    },
+    accesskit::{Action, Node, Orientation, Role},
+    vello::{
+        kurbo::{Affine, Circle, Point, Rect, RoundedRect, Size, Stroke},
+        peniko::{Brush, Color, Fill},
+    },
+    winit::keyboard::{Key, NamedKey},
+};
+
+/// An element that lets the user pick a value in a range by dragging a thumb along a track.
+///
+/// Horizontal sliders increase from left to right, and vertical sliders increase from bottom to
+/// top (like the faders of a mixing console).
+///
+/// When focused, the arrow keys move the value by one step, Page Up and Page Down by ten steps,
+/// and Home and End jump to the bounds of the range.
+#[derive(Clone, Debug)]
+pub struct Slider<F: ?Sized> {
+    /// The state of the interactive element.
+    pub state: InteractiveState,
+    /// The identifier of the node of the slider in the accessibility tree.
+    node_id: NodeIdCell,
+
+    /// The current value of the slider.
+    value: f64,
+    /// The minimum value of the slider.
+    min: f64,
+    /// The maximum value of the slider.
+    max: f64,
+    /// The step to which the value snaps, or zero for continuous values.
+    step: f64,
+
+    /// The orientation of the slider.
+    pub direction: Direction,
+    /// The preferred length of the slider, along its direction.
+    pub length: Length,
+    /// The thickness of the track.
+    pub thickness: Length,
+    /// The diameter of the thumb.
+    pub thumb_size: Length,
+    /// The brush used to draw the track.
+    pub track_brush: Brush,
+    /// The brush used to draw the part of the track between the minimum and the thumb.
+    pub fill_brush: Brush,
+    /// The brush used to draw the thumb.
+    pub thumb_brush: Brush,
+
+    /// Whether the user is currently dragging the thumb.
+    dragging: bool,
+    /// The bounds of the slider.
+    rect: Rect,
+    /// The resolved thickness of the track.
+    computed_thickness: f64,
+    /// The resolved diameter of the thumb.
+    computed_thumb_size: f64,
+
+    /// The function called when the value changes.
+    pub on_change: F,
+}
+
+impl Default for Slider<()> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(())
+    }
+}
+
+impl<F> Slider<F> {
+    /// Creates a new [`Slider`] element.
+    ///
+    /// The slider is horizontal and covers the range `[0, 1]` by default.
+    pub fn new(on_change: F) -> Self {
+        Self {
+            state: InteractiveState::empty(),
+            node_id: NodeIdCell::default(),
+            value: 0.0,
+            min: 0.0,
+            max: 1.0,
+            step: 0.0,
+            direction: Direction::Horizontal,
+            length: Length::Pixels(150.0),
+            thickness: Length::Pixels(4.0),
+            thumb_size: Length::Pixels(14.0),
+            track_brush: Color::from_rgb8(0x44, 0x44, 0x44).into(),
+            fill_brush: Color::from_rgb8(0x5a, 0x8d, 0xee).into(),
+            thumb_brush: Color::WHITE.into(),
+            dragging: false,
+            rect: Rect::ZERO,
+            computed_thickness: 0.0,
+            computed_thumb_size: 0.0,
+            on_change,
+        }
+    }
+
+    /// Sets the function called when the value changes.
+    ///
+    /// The function is called continuously while the user drags the thumb, and once per key
+    /// press when the value is adjusted with the keyboard.
+    pub fn on_change<F2>(self, on_change: F2) -> Slider<F2>
+    where
+        F2: FnMut(&ElemContext, f64),
+    {
+        Slider {
+            state: self.state,
+            node_id: self.node_id,
+            value: self.value,
+            min: self.min,
+            max: self.max,
+            step: self.step,
+            direction: self.direction,
+            length: self.length,
+            thickness: self.thickness,
+            thumb_size: self.thumb_size,
+            track_brush: self.track_brush,
+            fill_brush: self.fill_brush,
+            thumb_brush: self.thumb_brush,
+            dragging: false,
+            rect: self.rect,
+            computed_thickness: self.computed_thickness,
+            computed_thumb_size: self.computed_thumb_size,
+            on_change,
+        }
+    }
+
+    /// Sets the initial value of the slider.
+    pub fn value(mut self, value: f64) -> Self {
+        self.set_value(value);
+        self
+    }
+
+    /// Sets the minimum value of the slider.
+    pub fn min(mut self, min: f64) -> Self {
+        self.min = min;
+        self.set_value(self.value);
+        self
+    }
+
+    /// Sets the maximum value of the slider.
+    pub fn max(mut self, max: f64) -> Self {
+        self.max = max;
+        self.set_value(self.value);
+        self
+    }
+
+    /// Sets the step to which the value snaps.
+    ///
+    /// Steps are counted from the minimum value. A step of zero allows any value, in which case
+    /// the keyboard moves the value by a hundredth of the range.
+    pub fn step(mut self, step: f64) -> Self {
+        self.step = step.max(0.0);
+        self.set_value(self.value);
+        self
+    }
+
+    /// Sets the orientation of the slider.
+    pub fn direction(mut self, direction: Direction) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Makes the slider horizontal.
+    #[inline]
+    pub fn horizontal(self) -> Self {
+        self.direction(Direction::Horizontal)
+    }
+
+    /// Makes the slider vertical.
+    #[inline]
+    pub fn vertical(self) -> Self {
+        self.direction(Direction::Vertical)
+    }
+
+    /// Sets the preferred length of the slider, along its direction.
+    ///
+    /// The slider may still be stretched by its parent.
+    pub fn length(mut self, length: Length) -> Self {
+        self.length = length;
+        self
+    }
+
+    /// Sets the thickness of the track.
+    pub fn thickness(mut self, thickness: Length) -> Self {
+        self.thickness = thickness;
+        self
+    }
+
+    /// Sets the diameter of the thumb.
+    pub fn thumb_size(mut self, size: Length) -> Self {
+        self.thumb_size = size;
+        self
+    }
+
+    /// Sets the brush used to draw the track.
+    pub fn track_brush(mut self, brush: impl Into<Brush>) -> Self {
+        self.track_brush = brush.into();
+        self
+    }
+
+    /// Sets the brush used to draw the part of the track between the minimum and the thumb.
+    pub fn fill_brush(mut self, brush: impl Into<Brush>) -> Self {
+        self.fill_brush = brush.into();
+        self
+    }
+
+    /// Sets the brush used to draw the thumb.
+    pub fn thumb_brush(mut self, brush: impl Into<Brush>) -> Self {
+        self.thumb_brush = brush.into();
+        self
+    }
+
+    /// Sets whether the slider is disabled or not.
+    pub fn disabled(mut self, yes: bool) -> Self {
+        self.state.set(InteractiveState::DISABLED, yes);
+        self
+    }
+}
+
+impl<F: ?Sized> Slider<F> {
+    /// Returns the current value of the slider.
+    #[inline]
+    pub fn get_value(&self) -> f64 {
+        self.value
+    }
+
+    /// Sets the value of the slider.
+    ///
+    /// The value is snapped to the step of the slider and clamped to its range. This does not
+    /// call the [`OnNumberChange`] function.
+    pub fn set_value(&mut self, value: f64) {
+        self.value = self.constrain(value);
+    }
+
+    /// Snaps the provided value to the step of the slider and clamps it to its range.
+    fn constrain(&self, mut value: f64) -> f64 {
+        if self.step > 0.0 {
+            value = self.min + ((value - self.min) / self.step).round() * self.step;
+        }
+
+        value.clamp(self.min, self.max.max(self.min))
+    }
+
+    /// Returns the position of the value within the range of the slider, in `[0, 1]`.
+    fn fraction(&self) -> f64 {
+        if self.max <= self.min {
+            return 0.0;
+        }
+        ((self.value - self.min) / (self.max - self.min)).clamp(0.0, 1.0)
+    }
+
+    /// Returns the amount by which the arrow keys change the value.
+    fn key_step(&self) -> f64 {
+        if self.step > 0.0 {
+            self.step
+        } else {
+            (self.max - self.min) / 100.0
+        }
+    }
+
+    /// Returns the segment along which the center of the thumb moves, from the minimum to the
+    /// maximum.
+    fn thumb_course(&self) -> (Point, Point) {
+        let r = self.rect;
+        let half = self.computed_thumb_size / 2.0;
+        match self.direction {
+            Direction::Horizontal => {
+                let y = r.center().y;
+                (
+                    Point::new(r.x0 + half, y),
+                    Point::new((r.x1 - half).max(r.x0 + half), y),
+                )
+            }
+            Direction::Vertical => {
+                let x = r.center().x;
+                (
+                    Point::new(x, (r.y1 - half).max(r.y0 + half)),
+                    Point::new(x, r.y0 + half),
+                )
+            }
+        }
+    }
+
+    /// Returns the center of the thumb.
+    fn thumb_center(&self) -> Point {
+        let (start, end) = self.thumb_course();
+        start.lerp(end, self.fraction())
+    }
+
+    /// Computes the value that corresponds to the provided pointer position.
+    fn value_at(&self, point: Point) -> f64 {
+        let (start, end) = self.thumb_course();
+        let (pos, start, end) = match self.direction {
+            Direction::Horizontal => (point.x, start.x, end.x),
+            Direction::Vertical => (point.y, start.y, end.y),
+        };
+
+        let fraction = if start == end {
+            0.0
+        } else {
+            ((pos - start) / (end - start)).clamp(0.0, 1.0)
+        };
+
+        self.constrain(self.min + fraction * (self.max - self.min))
+    }
+}
+
+impl<F: ?Sized + OnNumberChange> Slider<F> {
+    /// Changes the value of the slider, calling the [`OnNumberChange`] function if it actually
+    /// changed.
+    fn change_value(&mut self, elem_context: &ElemContext, value: f64) {
+        let value = self.constrain(value);
+        if value == self.value {
+            return;
+        }
+
+        self.value = value;
+        self.state.insert(InteractiveState::VALUE_CHANGED);
+        self.on_change.on_change(elem_context, value);
+    }
+
+    /// Handles a key press while the slider is focused.
+    fn handle_key(&mut self, elem_context: &ElemContext, event: &KeyEvent) -> bool {
+        if !event.is_pressed() {
+            return false;
+        }
+
+        let step = self.key_step();
+        let value = match event.logical_key {
+            Key::Named(NamedKey::ArrowRight | NamedKey::ArrowUp) => self.value + step,
+            Key::Named(NamedKey::ArrowLeft | NamedKey::ArrowDown) => self.value - step,
+            Key::Named(NamedKey::PageUp) => self.value + step * 10.0,
+            Key::Named(NamedKey::PageDown) => self.value - step * 10.0,
+            Key::Named(NamedKey::Home) => self.min,
+            Key::Named(NamedKey::End) => self.max,
+            _ => return false,
+        };
+
+        self.change_value(elem_context, value);
+        true
+    }
+}
+
+impl<F> Element for Slider<F>
+where
+    F: ?Sized + OnNumberChange,
+{
+    fn size_hint(
+        &mut self,
+        _elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        _space: Size,
+    ) -> SizeHint {
+        let length = self.length.resolve(&layout_context);
+        let thumb = self.thumb_size.resolve(&layout_context);
+        let cross = thumb.max(self.thickness.resolve(&layout_context));
+
+        let (preferred, min, max) = match self.direction {
+            Direction::Horizontal => (
+                Size::new(length, cross),
+                Size::new(thumb, cross),
+                Size::new(f64::INFINITY, cross),
+            ),
+            Direction::Vertical => (
+                Size::new(cross, length),
+                Size::new(cross, thumb),
+                Size::new(cross, f64::INFINITY),
+            ),
+        };
+
+        SizeHint {
+            preferred,
+            min,
+            max,
+        }
+    }
+
+    fn place(
+        &mut self,
+        _elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        pos: Point,
+        size: Size,
+    ) {
+        self.rect = Rect::from_origin_size(pos, size);
+        self.computed_thickness = self.thickness.resolve(&layout_context);
+        self.computed_thumb_size = self.thumb_size.resolve(&layout_context);
+    }
+
+    #[inline]
+    fn hit_test(&self, point: Point) -> bool {
+        self.rect.contains(point)
+    }
+
+    fn draw(&mut self, _elem_context: &ElemContext, scene: &mut vello::Scene) {
+        let (start, end) = self.thumb_course();
+        let thumb = self.thumb_center();
+        let radius = self.computed_thickness / 2.0;
+
+        let track = Rect::from_points(start, end).inflate(radius, radius);
+        scene.fill(
+            Fill::NonZero,
+            Affine::IDENTITY,
+            &self.track_brush,
+            None,
+            &RoundedRect::from_rect(track, radius),
+        );
+
+        let fill = Rect::from_points(start, thumb).inflate(radius, radius);
+        scene.fill(
+            Fill::NonZero,
+            Affine::IDENTITY,
+            &self.fill_brush,
+            None,
+            &RoundedRect::from_rect(fill, radius),
+        );
+
+        let circle = Circle::new(thumb, self.computed_thumb_size / 2.0);
+        scene.fill(
+            Fill::NonZero,
+            Affine::IDENTITY,
+            &self.thumb_brush,
+            None,
+            &circle,
+        );
+        if self.state.focus_visible() {
+            scene.stroke(
+                &Stroke::new(2.0),
+                Affine::IDENTITY,
+                &self.fill_brush,
+                None,
+                &circle,
+            );
+        }
+    }
+
+    fn event(&mut self, elem_context: &ElemContext, event: &dyn Event) -> EventResult {
+        self.state.remove_transient_states();
+
+        if self.state.disabled() {
+            return EventResult::Continue;
+        }
+
+        let og_state = self.state;
+        let og_value = self.value;
+
+        self.state.handle_focus_traversal(event);
+        let action = self
+            .state
+            .handle_accessibility_action(elem_context, &self.node_id, event);
+        let mut event_result = self
+            .state
+            .handle_pointer_interactions(&mut |pt| self.rect.contains(pt), event);
+
+        let requested_value = match action {
+            Some(Action::Increment) => Some(self.value + self.key_step()),
+            Some(Action::Decrement) => Some(self.value - self.key_step()),
+            _ => None,
+        };
+
+        if let Some(value) = requested_value {
+            self.change_value(elem_context, value);
+            event_result = EventResult::Handled;
+        } else if self.state.just_pressed() {
+            if let Some(ev) = event.downcast_ref::<PointerButton>() {
+                // Pressing the track moves the thumb right under the pointer.
+                self.dragging = true;
+                self.change_value(elem_context, self.value_at(ev.position));
+            }
+        } else if self.state.just_released() {
+            self.dragging = false;
+        } else if let Some(ev) = event.downcast_ref::<PointerMoved>() {
+            if self.dragging && ev.primary {
+                self.change_value(elem_context, self.value_at(ev.position));
+                event_result = EventResult::Handled;
+            }
+        } else if let Some(ev) = event.downcast_ref::<KeyEvent>() {
+            if self.state.focused() && self.handle_key(elem_context, ev) {
+                event_result = EventResult::Handled;
+            }
+        }
+
+        if og_state != self.state || og_value != self.value {
+            elem_context.window.request_redraw();
+        }
+
+        event_result
+    }
+
+    fn accessibility(&self, cx: &mut AccessibilityContext) {
+        let mut node = Node::new(Role::Slider);
+        node.add_action(Action::Focus);
+        node.add_action(Action::Increment);
+        node.add_action(Action::Decrement);
+        node.set_bounds(to_accesskit_rect(self.rect));
+        node.set_orientation(match self.direction {
+            Direction::Horizontal => Orientation::Horizontal,
+            Direction::Vertical => Orientation::Vertical,
+        });
+        node.set_numeric_value(self.value);
+        node.set_min_numeric_value(self.min);
+        node.set_max_numeric_value(self.max);
+        if self.step > 0.0 {
+            node.set_numeric_value_step(self.step);
+        }
+        if self.state.disabled() {
+            node.set_disabled();
+        }
+
+        let id = cx.push(node);
+        self.node_id.set(id);
+        if self.state.focused() {
+            cx.set_focus(id);
+        }
+    }
+}
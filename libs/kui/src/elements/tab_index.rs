@@ -0,0 +1,146 @@
+use {
+    crate::{
+        ElemContext, Element, LayoutContext, SizeHint,
+        accessibility::AccessibilityContext,
+        event::{CollectFocusTargets, Event, EventResult},
+    },
+    vello::kurbo::{Point, Size},
+};
+
+/// An element that overrides the tab index of the focusable elements it contains.
+///
+/// When the user presses Tab, the focus moves through focusable elements by increasing tab
+/// index. Elements with the same tab index are visited in tree order. Elements that are not
+/// wrapped in a [`TabIndex`] have a tab index of zero.
+///
+/// A negative tab index makes the elements focusable by clicking on them, but skips them
+/// during keyboard traversal.
+///
+/// Instances of this type are usually created through
+/// [`ElementExt::with_tab_index`](crate::ElementExt::with_tab_index).
+#[derive(Clone, Debug, Default)]
+pub struct TabIndex<E: ?Sized> {
+    /// The tab index to apply.
+    pub tab_index: i32,
+    /// The child element.
+    pub child: E,
+}
+
+impl<E> TabIndex<E> {
+    /// Creates a new [`TabIndex`] element.
+    #[inline]
+    pub fn new(tab_index: i32, child: E) -> Self {
+        Self { tab_index, child }
+    }
+}
+
+impl<E: ?Sized + Element> Element for TabIndex<E> {
+    #[inline]
+    fn size_hint(
+        &mut self,
+        elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        space: Size,
+    ) -> SizeHint {
+        self.child.size_hint(elem_context, layout_context, space)
+    }
+
+    #[inline]
+    fn place(
+        &mut self,
+        elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        pos: Point,
+        size: Size,
+    ) {
+        self.child.place(elem_context, layout_context, pos, size);
+    }
+
+    #[inline]
+    fn hit_test(&self, point: Point) -> bool {
+        self.child.hit_test(point)
+    }
+
+    #[inline]
+    fn draw(&mut self, elem_context: &ElemContext, scene: &mut vello::Scene) {
+        self.child.draw(elem_context, scene);
+    }
+
+    fn event(&mut self, elem_context: &ElemContext, event: &dyn Event) -> EventResult {
+        match event.downcast_ref::<CollectFocusTargets>() {
+            Some(ev) => ev.with_tab_index(self.tab_index, || self.child.event(elem_context, event)),
+            None => self.child.event(elem_context, event),
+        }
+    }
+
+    #[inline]
+    fn begin(&mut self, elem_context: &ElemContext) {
+        self.child.begin(elem_context);
+    }
+
+    #[inline]
+    fn accessibility(&self, cx: &mut AccessibilityContext) {
+        self.child.accessibility(cx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        crate::{
+            ElemContext, Element, ElementExt, HeadlessRenderer,
+            elements::flex,
+            event::{CollectFocusTargets, Event, EventResult, MoveFocus},
+        },
+        std::{cell::Cell, rc::Rc},
+    };
+
+    /// A focusable element that records whether it has the focus.
+    struct Target(Rc<Cell<bool>>);
+
+    impl Element for Target {
+        fn event(&mut self, _elem_context: &ElemContext, event: &dyn Event) -> EventResult {
+            if let Some(ev) = event.downcast_ref::<CollectFocusTargets>() {
+                ev.register(self.0.get());
+            } else if let Some(ev) = event.downcast_ref::<MoveFocus>() {
+                self.0.set(ev.next());
+            }
+            EventResult::Continue
+        }
+    }
+
+    /// Moves the focus to the next element, like the window does when Tab is pressed.
+    fn press_tab(root: &mut dyn Element, elem_context: &ElemContext, reverse: bool) {
+        let targets = CollectFocusTargets::default();
+        root.event(elem_context, &targets);
+
+        if let Some(target) = targets.next_target(reverse) {
+            root.event(elem_context, &MoveFocus::new(target));
+        }
+    }
+
+    #[test]
+    fn tab_visits_elements_by_tab_index() {
+        let focus = [(); 3].map(|_| Rc::new(Cell::new(false)));
+        let mut root = flex()
+            .child(Target(focus[0].clone()))
+            .child(Target(focus[1].clone()).with_tab_index(2))
+            .child(Target(focus[2].clone()).with_tab_index(1));
+
+        let renderer = HeadlessRenderer::new();
+        let elem_context = renderer.elem_context();
+        let focused = || focus.iter().position(|f| f.get());
+
+        // The first element keeps its tree order, while the two others are swapped by their
+        // tab index. The focus wraps around after the last element.
+        let mut order = Vec::new();
+        for _ in 0..4 {
+            press_tab(&mut root, &elem_context, false);
+            order.push(focused());
+        }
+        assert_eq!(order, [Some(0), Some(2), Some(1), Some(0)]);
+
+        press_tab(&mut root, &elem_context, true);
+        assert_eq!(focused(), Some(1));
+    }
+}
@@ -1,14 +1,28 @@
 use {
     super::Length,
-    crate::{ElemContext, Element, LayoutContext, SizeHint},
+    crate::{
+        CallbackId, ElemContext, Element, LayoutContext, SizeHint,
+        accessibility::{AccessibilityContext, to_accesskit_rect},
+        event::{Event, EventResult, KeyEvent, PointerButton, PointerMoved},
+    },
+    accesskit::{Node, Role},
     parley::{
-        Alignment, FontSettings, FontStack, FontStyle, FontVariation, FontWeight, FontWidth,
-        GenericFamily, Layout, PositionedLayoutItem, StyleProperty,
+        Affinity, Alignment, Cursor, FontSettings, FontStack, FontStyle, FontVariation, FontWeight,
+        FontWidth, GenericFamily, Layout, PositionedLayoutItem, RangedBuilder, Selection,
+        StyleProperty,
+    },
+    std::{
+        ops::Range,
+        time::{Duration, Instant},
     },
     vello::{
         Glyph, Scene,
-        kurbo::{Affine, Point, Size},
-        peniko::{self, Brush, Color, Fill},
+        kurbo::{Affine, Point, Rect, Size, Vec2},
+        peniko::{self, Brush, Color, Fill, Mix},
+    },
+    winit::{
+        event::{ButtonSource, MouseButton},
+        keyboard::Key,
     },
 };
 
@@ -34,10 +48,22 @@ impl TextResource {
 /// Allows running a function that will be used to style a [`Text`] element.
 pub trait TextStyle {
     /// Styles the provided text.
+    ///
+    /// # Parameters
+    ///
+    /// - `offset`: The byte index of `text` within the whole text of the [`Text`] element.
+    ///   This allows styles to apply ranges expressed relative to the whole text.
+    ///
+    /// # Remarks
+    ///
+    /// Text is laid out one paragraph at a time, which means that `text` never contains line
+    /// breaks. This allows [`Text`] elements to only re-compute the layout of the paragraphs
+    /// that were modified when their text changes.
     fn style(
         &self,
         layout_context: &LayoutContext,
         res: &mut TextResource,
+        offset: usize,
         text: &str,
         output: &mut Layout<Brush>,
     );
@@ -48,6 +74,7 @@ impl TextStyle for () {
         &self,
         _layout_context: &LayoutContext,
         _res: &mut TextResource,
+        _offset: usize,
         _text: &str,
         _output: &mut Layout<Brush>,
     ) {
@@ -99,17 +126,15 @@ impl Default for UniformStyle {
     }
 }
 
-impl TextStyle for UniformStyle {
+impl UniformStyle {
+    /// Pushes the properties of this style as the defaults of the provided builder.
     #[rustfmt::skip]
-    fn style(
-        &self,
-        layout_context: &LayoutContext,
-        res: &mut TextResource,
-        text: &str,
-        output: &mut Layout<Brush>,
-    ) {
+    fn push_defaults(&self, layout_context: &LayoutContext, builder: &mut RangedBuilder<Brush>) {
         let font_size = self.font_size.resolve(layout_context) ;
 
+        // Other font-relative lengths are relative to the font size of this element.
+        let layout_context = &layout_context.with_font_size(font_size);
+
         let variations = vec![
             FontVariation {
                 tag: u32::from_be_bytes(*b"wght"),
@@ -117,7 +142,6 @@ impl TextStyle for UniformStyle {
             }
         ];
 
-        let mut builder = res.layout_ctx.ranged_builder(&mut res.font_ctx, text, 1.0);
         builder.push_default(StyleProperty::Brush(self.brush.clone()));
         builder.push_default(StyleProperty::FontSize(font_size as f32));
         builder.push_default(StyleProperty::FontStack(self.font_stack.clone()));
@@ -136,6 +160,88 @@ impl TextStyle for UniformStyle {
         builder.push_default(StyleProperty::LineHeight(self.line_height.as_ref().map_or(1.0, |l| l.resolve(layout_context) / font_size) as f32));
         builder.push_default(StyleProperty::WordSpacing(self.word_spacing.resolve(layout_context) as f32));
         builder.push_default(StyleProperty::LetterSpacing(self.letter_spacing.resolve(layout_context) as f32));
+    }
+}
+
+impl TextStyle for UniformStyle {
+    fn style(
+        &self,
+        layout_context: &LayoutContext,
+        res: &mut TextResource,
+        _offset: usize,
+        text: &str,
+        output: &mut Layout<Brush>,
+    ) {
+        let mut builder = res.layout_ctx.ranged_builder(&mut res.font_ctx, text, 1.0);
+        self.push_defaults(layout_context, &mut builder);
+        builder.build_into(output, text);
+    }
+}
+
+/// A style property applied to a range of the text of a [`StyledText`].
+pub type StyleSpan = (Range<usize>, StyleProperty<'static, Brush>);
+
+/// A [`TextStyle`] that applies a base style to the whole text, and additional properties to
+/// specific ranges of it.
+///
+/// This allows highlighting a single word, or mixing multiple font weights in the same
+/// element.
+///
+/// # Remarks
+///
+/// Ranges are byte indices into the whole text of the [`Text`] element, and must be located on
+/// character boundaries. When multiple spans set the same property on overlapping ranges, the
+/// span that was added last wins.
+///
+/// Spans are not updated when the text changes. When modifying the text, the spans should be
+/// replaced as well (through [`Text::style_mut`]), which re-computes the layout of the whole
+/// text.
+#[derive(Clone, Debug, Default)]
+pub struct StyledText {
+    /// The style applied to the whole text.
+    pub base: UniformStyle,
+    /// The properties applied to specific ranges of the text, in the order they were added.
+    pub spans: Vec<StyleSpan>,
+}
+
+impl StyledText {
+    /// Creates a new [`StyledText`] with the provided base style.
+    #[inline]
+    pub fn new(base: UniformStyle) -> Self {
+        Self {
+            base,
+            spans: Vec::new(),
+        }
+    }
+
+    /// Applies a style property to the provided range of the text.
+    pub fn push(&mut self, range: Range<usize>, property: StyleProperty<'static, Brush>) {
+        self.spans.push((range, property));
+    }
+}
+
+impl TextStyle for StyledText {
+    fn style(
+        &self,
+        layout_context: &LayoutContext,
+        res: &mut TextResource,
+        offset: usize,
+        text: &str,
+        output: &mut Layout<Brush>,
+    ) {
+        let mut builder = res.layout_ctx.ranged_builder(&mut res.font_ctx, text, 1.0);
+        self.base.push_defaults(layout_context, &mut builder);
+
+        let end = offset + text.len();
+        for (range, property) in &self.spans {
+            // Only keep the part of the span that covers this paragraph.
+            let start = range.start.clamp(offset, end) - offset;
+            let stop = range.end.clamp(offset, end) - offset;
+            if start < stop {
+                builder.push(property.clone(), start..stop);
+            }
+        }
+
         builder.build_into(output, text);
     }
 }
@@ -147,6 +253,8 @@ enum TextDirtAmount {
     /// for rendering.
     #[default]
     Clean,
+    /// Some paragraphs are dirty, but the text as a whole is not.
+    Paragraphs,
     /// The alignment of the text has changed.
     Align,
     /// The lines must be recomputed, but the text itself is still the same.
@@ -155,6 +263,53 @@ enum TextDirtAmount {
     Text,
 }
 
+/// A paragraph of an [`UnstyledText`].
+///
+/// Each paragraph has its own layout so that modifying one of them does not require
+/// re-computing the layout of the others.
+#[derive(Clone)]
+struct Paragraph {
+    /// The length of the paragraph, in bytes, excluding the line break that ends it.
+    len: usize,
+    /// The amount of dirt that is specific to this paragraph.
+    ///
+    /// The dirt of the whole text applies to every paragraph in addition to this.
+    dirt: TextDirtAmount,
+    /// The laid out paragraph.
+    layout: parley::Layout<peniko::Brush>,
+    /// The width with which the paragraph was last aligned.
+    ///
+    /// This is NaN when the paragraph needs to be aligned again.
+    aligned_width: f32,
+
+    /// The glyph runs of the paragraph, recorded relative to its top-left corner.
+    ///
+    /// This is re-used between frames until the layout of the paragraph changes.
+    scene: Scene,
+    /// Whether `scene` must be recorded again before being used.
+    scene_dirty: bool,
+    /// The horizontal position past which glyphs are replaced by an ellipsis, relative to the
+    /// left edge of the paragraph.
+    ///
+    /// This is `None` when the paragraph is not truncated.
+    truncated_at: Option<f32>,
+}
+
+impl Paragraph {
+    /// Creates a new [`Paragraph`] that has not been laid out yet.
+    fn new(text: &str) -> Self {
+        Self {
+            len: text.len(),
+            dirt: TextDirtAmount::Text,
+            layout: parley::Layout::default(),
+            aligned_width: f32::NAN,
+            scene: Scene::new(),
+            scene_dirty: true,
+            truncated_at: None,
+        }
+    }
+}
+
 /// Text that does not include any styling whatsoever.
 #[derive(Clone, Default)]
 struct UnstyledText {
@@ -166,6 +321,12 @@ struct UnstyledText {
     pub align: Alignment,
     /// Whether the text should take the least amount of space possible vertically.
     pub inline: bool,
+    /// Whether lines that do not fit in the container are cut and end with an ellipsis.
+    ///
+    /// This only applies when wrapping is disabled.
+    pub truncate: bool,
+    /// The laid out ellipsis drawn at the end of truncated lines.
+    pub ellipsis: Layout<Brush>,
 
     /// The position of the text.
     pub position: Point,
@@ -174,10 +335,52 @@ struct UnstyledText {
     /// The width for which the text is expected to be laid out.
     pub container_width: f32,
 
-    /// The amount of dirt the text has.
+    /// The amount of dirt the whole text has.
     pub dirt: TextDirtAmount,
-    /// The laid out text (if built).
-    pub layout: parley::Layout<peniko::Brush>,
+    /// The paragraphs of the text, separated by line breaks.
+    ///
+    /// When the text is clean, there is exactly one paragraph per line of `text`.
+    pub paragraphs: Vec<Paragraph>,
+    /// The width of the widest paragraph.
+    pub width: f32,
+    /// The total height of the paragraphs.
+    pub height: f32,
+
+    /// Whether the user can select and copy the text.
+    pub selectable: bool,
+    /// The brush used to highlight the selected text.
+    ///
+    /// When `None`, a default translucent blue is used.
+    pub selection_brush: Option<Brush>,
+    /// The selected range of the text, as an `(anchor, focus)` pair of byte indices.
+    ///
+    /// The anchor is where the selection started, and the focus is where it currently ends. The
+    /// focus may be located before the anchor.
+    pub selection: Option<(usize, usize)>,
+    /// Whether the user is currently dragging the pointer to select text.
+    pub selecting: bool,
+    /// Whether the text has been clicked and should respond to the copy shortcut.
+    pub focused: bool,
+
+    /// The byte index of the caret that must be kept visible, if any.
+    ///
+    /// When set, the text is scrolled horizontally to keep the caret within the container, and
+    /// clipped to the area of the element.
+    pub caret: Option<usize>,
+    /// The horizontal distance by which the text is scrolled.
+    pub scroll_x: f64,
+    /// The brush used to draw the caret.
+    ///
+    /// When `None`, the caret is not drawn, but the text is still scrolled to keep it visible.
+    pub caret_brush: Option<Brush>,
+    /// The time at which the caret last moved.
+    ///
+    /// The caret blinks relative to this instant, so that it is always visible right after
+    /// moving. This is `None` until the caret is drawn for the first time after moving.
+    pub caret_moved_at: Option<Instant>,
+    /// The callback that redraws the window when the caret blinks, along with the time at which
+    /// it is scheduled.
+    pub blink_callback: Option<(CallbackId, Instant)>,
 }
 
 impl UnstyledText {
@@ -186,6 +389,49 @@ impl UnstyledText {
         self.dirt = self.dirt.max(amount);
     }
 
+    /// Replaces the text with the provided string.
+    ///
+    /// Only the paragraphs that differ from the previous text are marked as dirty. The common
+    /// paragraphs at the start and at the end of the text keep their layout.
+    fn set_text(&mut self, text: String) {
+        if text == self.text {
+            return;
+        }
+
+        if self.paragraphs.len() != self.text.split('\n').count() {
+            // The paragraphs are out of sync with the text (it has never been laid out). They
+            // will all be created during the next flush.
+            self.text = text;
+            self.selection = None;
+            self.paragraphs.clear();
+            self.add_dirt(TextDirtAmount::Text);
+            return;
+        }
+
+        let old: Vec<&str> = self.text.split('\n').collect();
+        let new: Vec<&str> = text.split('\n').collect();
+
+        let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+        let max_suffix = old.len().min(new.len()) - prefix;
+        let suffix = old
+            .iter()
+            .rev()
+            .zip(new.iter().rev())
+            .take(max_suffix)
+            .take_while(|(a, b)| a == b)
+            .count();
+
+        let changed = &new[prefix..new.len() - suffix];
+        self.paragraphs.splice(
+            prefix..old.len() - suffix,
+            changed.iter().map(|&p| Paragraph::new(p)),
+        );
+
+        self.text = text;
+        self.selection = None;
+        self.add_dirt(TextDirtAmount::Paragraphs);
+    }
+
     /// Sets the maximum width of the text.
     fn set_container_width(&mut self, width: f32) {
         if self.container_width != width {
@@ -208,35 +454,112 @@ impl UnstyledText {
             return;
         }
 
+        if self.paragraphs.len() != self.text.split('\n').count() {
+            self.paragraphs = self.text.split('\n').map(Paragraph::new).collect();
+        }
+
         elem_context
             .ctx
             .with_resource_or_default(|text_res: &mut TextResource| {
-                if self.dirt >= TextDirtAmount::Text {
-                    style.style(&self.layout_context, text_res, &self.text, &mut self.layout);
+                let max_advance = if self.wrap {
+                    self.container_width
+                } else {
+                    f32::INFINITY
+                };
+
+                let mut start = 0;
+                for paragraph in &mut self.paragraphs {
+                    let offset = start;
+                    let text = &self.text[offset..offset + paragraph.len];
+                    start += paragraph.len + 1;
+
+                    let dirt = paragraph.dirt.max(self.dirt);
+
+                    if dirt >= TextDirtAmount::Text {
+                        style.style(
+                            &self.layout_context,
+                            text_res,
+                            offset,
+                            text,
+                            &mut paragraph.layout,
+                        );
+                    }
+
+                    if dirt >= TextDirtAmount::Lines {
+                        paragraph.layout.break_lines().break_remaining(max_advance);
+                    }
+
+                    if dirt >= TextDirtAmount::Align {
+                        paragraph.aligned_width = f32::NAN;
+                    }
+
+                    paragraph.dirt = TextDirtAmount::Clean;
                 }
 
-                if self.dirt >= TextDirtAmount::Lines {
-                    let max_advance = if self.wrap {
-                        self.container_width
-                    } else {
-                        f32::INFINITY
-                    };
-                    self.layout.break_lines().break_remaining(max_advance);
+                if self.truncate && self.dirt >= TextDirtAmount::Text {
+                    style.style(
+                        &self.layout_context,
+                        text_res,
+                        self.text.len(),
+                        ELLIPSIS,
+                        &mut self.ellipsis,
+                    );
+                    self.ellipsis.break_lines().break_remaining(f32::INFINITY);
                 }
 
-                if self.dirt >= TextDirtAmount::Align {
-                    let container_width = if self.inline {
-                        None
+                // Paragraphs must agree on the width of the text, which might have changed
+                // even if only one of them was modified.
+                self.width = self
+                    .paragraphs
+                    .iter()
+                    .map(|p| p.layout.width())
+                    .fold(0.0, f32::max);
+                self.height = self.paragraphs.iter().map(|p| p.layout.height()).sum();
+
+                let container_width = if self.inline {
+                    self.clamped_width()
+                } else {
+                    self.container_width
+                };
+                let truncate = self.truncate && !self.wrap;
+                for paragraph in &mut self.paragraphs {
+                    let overflows = truncate && paragraph.layout.width() > container_width;
+
+                    // Truncated paragraphs are cut on their right, which only makes sense when
+                    // they start at the left edge of the container.
+                    let align_width = if overflows {
+                        paragraph.layout.width()
                     } else {
-                        Some(self.container_width)
+                        container_width
                     };
-                    self.layout.align(container_width, self.align, false);
+                    if paragraph.aligned_width != align_width {
+                        paragraph.layout.align(Some(align_width), self.align, false);
+                        paragraph.aligned_width = align_width;
+                        paragraph.scene_dirty = true;
+                    }
+
+                    let truncated_at =
+                        overflows.then(|| (container_width - self.ellipsis.width()).max(0.0));
+                    if paragraph.truncated_at != truncated_at {
+                        paragraph.truncated_at = truncated_at;
+                        paragraph.scene_dirty = true;
+                    }
                 }
 
                 self.dirt = TextDirtAmount::Clean;
             });
     }
 
+    /// Returns the width of the widest paragraph, limited to the width of the container when
+    /// the text is truncated.
+    fn clamped_width(&self) -> f32 {
+        if self.truncate && !self.wrap {
+            self.width.min(self.container_width)
+        } else {
+            self.width
+        }
+    }
+
     /// Computes the dimensions of the text for the provided space.
     fn size_hint(
         &mut self,
@@ -250,12 +573,18 @@ impl UnstyledText {
         self.flush(elem_context, style);
 
         let preferred = if self.inline {
-            Size::new(self.layout.width() as f64, self.layout.height() as f64)
+            Size::new(self.clamped_width() as f64, self.height as f64)
         } else {
-            Size::new(space.width, self.layout.height() as f64)
+            Size::new(space.width, self.height as f64)
         };
 
-        let min = if self.wrap { Size::ZERO } else { preferred };
+        let min = if self.wrap {
+            Size::ZERO
+        } else if self.truncate {
+            Size::new(0.0, preferred.height)
+        } else {
+            preferred
+        };
 
         SizeHint {
             preferred,
@@ -271,35 +600,435 @@ impl UnstyledText {
         self.set_layout_context(layout_context);
     }
 
+    /// Returns the area covered by the text.
+    fn bounds(&self) -> Rect {
+        Rect::from_origin_size(
+            self.position,
+            Size::new(self.clamped_width() as f64, self.height as f64),
+        )
+    }
+
+    /// Returns the area covered by the caret when placed before the provided byte index.
+    ///
+    /// The returned rectangle is relative to the top-left corner of the text, ignoring the
+    /// current scroll offset.
+    fn local_caret_rect(&self, index: usize) -> Rect {
+        let index = index.min(self.text.len());
+
+        let mut start = 0;
+        let mut y = 0.0;
+        for (i, paragraph) in self.paragraphs.iter().enumerate() {
+            if index <= start + paragraph.len || i + 1 == self.paragraphs.len() {
+                let local = (index - start).min(paragraph.len);
+                let bbox = Cursor::from_byte_index(&paragraph.layout, local, Affinity::Upstream)
+                    .geometry(&paragraph.layout, CARET_WIDTH);
+                return Rect::new(bbox.x0, bbox.y0 + y, bbox.x1, bbox.y1 + y);
+            }
+
+            start += paragraph.len + 1;
+            y += paragraph.layout.height() as f64;
+        }
+
+        Rect::ZERO
+    }
+
+    /// Updates the scroll offset of the text so that the caret is visible.
+    ///
+    /// This must be called once the layout of the text is up to date, which is why it is done
+    /// right before drawing rather than when the caret moves.
+    fn scroll_to_caret(&mut self) {
+        let Some(caret) = self.caret else {
+            self.scroll_x = 0.0;
+            return;
+        };
+
+        self.scroll_x = scroll_to_show(
+            self.scroll_x,
+            self.local_caret_rect(caret),
+            self.container_width as f64,
+            self.width as f64,
+        );
+    }
+
+    /// Returns the byte index of the character boundary closest to the provided point.
+    fn index_at(&self, point: Point) -> usize {
+        let local = point - self.position + Vec2::new(self.scroll_x, 0.0);
+
+        let mut start = 0;
+        let mut y = 0.0;
+        for (i, paragraph) in self.paragraphs.iter().enumerate() {
+            let height = paragraph.layout.height() as f64;
+
+            if local.y < y + height || i + 1 == self.paragraphs.len() {
+                let cursor =
+                    Cursor::from_point(&paragraph.layout, local.x as f32, (local.y - y) as f32);
+                return start + cursor.index().min(paragraph.len);
+            }
+
+            start += paragraph.len + 1;
+            y += height;
+        }
+
+        0
+    }
+
+    /// Returns the selected text, if any.
+    fn selected_text(&self) -> Option<&str> {
+        let (anchor, focus) = self.selection?;
+        let range = anchor.min(focus)..anchor.max(focus);
+        (!range.is_empty()).then(|| &self.text[range])
+    }
+
+    /// Handles an event, updating the selection of the text.
+    ///
+    /// This does nothing if the text is not selectable.
+    fn event(&mut self, elem_context: &ElemContext, event: &dyn Event) -> EventResult {
+        if !self.selectable {
+            return EventResult::Continue;
+        }
+
+        if let Some(ev) = event.downcast_ref::<PointerButton>() {
+            if !ev.primary || !matches!(ev.button, ButtonSource::Mouse(MouseButton::Left)) {
+                return EventResult::Continue;
+            }
+
+            if !ev.state.is_pressed() {
+                self.selecting = false;
+                return EventResult::Continue;
+            }
+
+            if !self.bounds().contains(ev.position) {
+                self.focused = false;
+                if self.selection.take().is_some() {
+                    elem_context.window.request_redraw();
+                }
+                return EventResult::Continue;
+            }
+
+            let index = self.index_at(ev.position);
+            let anchor = match self.selection {
+                Some((anchor, _)) if ev.modifiers.shift_key() => anchor,
+                _ => index,
+            };
+
+            self.focused = true;
+            self.selecting = true;
+            self.selection = Some((anchor, index));
+            elem_context.window.request_redraw();
+            return EventResult::Handled;
+        }
+
+        if let Some(ev) = event.downcast_ref::<PointerMoved>() {
+            if !self.selecting || !ev.primary {
+                return EventResult::Continue;
+            }
+
+            let index = self.index_at(ev.position);
+            if let Some((_, focus)) = self.selection.as_mut().filter(|(_, f)| *f != index) {
+                *focus = index;
+                elem_context.window.request_redraw();
+            }
+
+            return EventResult::Handled;
+        }
+
+        if let Some(ev) = event.downcast_ref::<KeyEvent>() {
+            if !self.focused || !ev.is_initial_press() {
+                return EventResult::Continue;
+            }
+
+            let modifiers = ev.modifiers;
+            let shortcut = if cfg!(target_os = "macos") {
+                modifiers.super_key()
+            } else {
+                modifiers.control_key()
+            };
+
+            if !shortcut {
+                return EventResult::Continue;
+            }
+
+            match &ev.logical_key {
+                Key::Character(c) if c.eq_ignore_ascii_case("c") => {
+                    if let Some(text) = self.selected_text() {
+                        // There is nothing much to do if the clipboard is not available.
+                        let _ = elem_context.ctx.set_clipboard_text(text.to_owned());
+                    }
+                    return EventResult::Handled;
+                }
+                Key::Character(c) if c.eq_ignore_ascii_case("a") => {
+                    self.selection = Some((0, self.text.len()));
+                    elem_context.window.request_redraw();
+                    return EventResult::Handled;
+                }
+                _ => (),
+            }
+        }
+
+        EventResult::Continue
+    }
+
+    /// Draws the highlight behind the selected text.
+    fn draw_selection(&self, scene: &mut Scene) {
+        let Some((anchor, focus)) = self.selection else {
+            return;
+        };
+
+        let (sel_start, sel_end) = (anchor.min(focus), anchor.max(focus));
+        if sel_start == sel_end {
+            return;
+        }
+
+        const DEFAULT_SELECTION_COLOR: Color = Color::from_rgba8(0x33, 0x99, 0xff, 0x66);
+        let default_brush = Brush::Solid(DEFAULT_SELECTION_COLOR);
+        let brush = self.selection_brush.as_ref().unwrap_or(&default_brush);
+
+        let mut start = 0;
+        let mut y = 0.0;
+        for paragraph in &self.paragraphs {
+            let end = start + paragraph.len;
+
+            if sel_start <= end && sel_end > start {
+                let local_start = sel_start.saturating_sub(start);
+                let local_end = (sel_end - start).min(paragraph.len);
+
+                let selection = Selection::new(
+                    Cursor::from_byte_index(&paragraph.layout, local_start, Affinity::Downstream),
+                    Cursor::from_byte_index(&paragraph.layout, local_end, Affinity::Upstream),
+                );
+
+                let offset = self.position.to_vec2() + Vec2::new(-self.scroll_x, y);
+                selection.geometry_with(&paragraph.layout, |rect| {
+                    let rect = Rect::new(rect.x0, rect.y0, rect.x1, rect.y1) + offset;
+                    scene.fill(Fill::NonZero, Affine::IDENTITY, brush, None, &rect);
+                });
+            }
+
+            start = end + 1;
+            y += paragraph.layout.height() as f64;
+        }
+    }
+
+    /// Returns whether the caret is currently visible, and schedules a redraw for the next time
+    /// it blinks.
+    fn caret_blink(&mut self, elem_context: &ElemContext) -> bool {
+        let now = Instant::now();
+        let moved_at = *self.caret_moved_at.get_or_insert(now);
+
+        let phase = (now - moved_at).as_nanos() / CARET_BLINK_INTERVAL.as_nanos();
+        let next_blink = moved_at + CARET_BLINK_INTERVAL * (phase as u32 + 1);
+
+        if self.blink_callback.is_none_or(|(_, at)| at != next_blink) {
+            self.cancel_caret_blink(elem_context);
+
+            let window = elem_context.window.clone();
+            let id = elem_context.ctx.call_at(next_blink, move || {
+                if window.is_open() {
+                    window.request_redraw();
+                }
+            });
+            self.blink_callback = Some((id, next_blink));
+        }
+
+        phase % 2 == 0
+    }
+
+    /// Cancels the redraw scheduled for the next blink of the caret, if any.
+    fn cancel_caret_blink(&mut self, elem_context: &ElemContext) {
+        if let Some((id, _)) = self.blink_callback.take() {
+            elem_context.ctx.cancel_callback(id);
+        }
+    }
+
+    /// Draws the caret, if it is currently visible.
+    fn draw_caret(&mut self, elem_context: &ElemContext, scene: &mut Scene) {
+        let Some(caret) = self.caret.filter(|_| self.caret_brush.is_some()) else {
+            self.cancel_caret_blink(elem_context);
+            return;
+        };
+
+        if !self.caret_blink(elem_context) {
+            return;
+        }
+
+        let local = self.local_caret_rect(caret);
+        let x = self.position.x + local.x0 - self.scroll_x;
+        let rect = Rect::new(
+            x,
+            self.position.y + local.y0,
+            x + self.layout_context.scale_factor.max(1.0),
+            self.position.y + local.y1,
+        );
+        if let Some(brush) = self.caret_brush.as_ref() {
+            scene.fill(Fill::NonZero, Affine::IDENTITY, brush, None, &rect);
+        }
+    }
+
+    /// Describes the text to assistive technologies.
+    fn accessibility(&self, cx: &mut AccessibilityContext) {
+        let mut node = Node::new(Role::Label);
+        node.set_value(self.text.as_str());
+        node.set_bounds(to_accesskit_rect(self.bounds()));
+        cx.push(node);
+    }
+
     /// Draws the text to the provided scene.
+    ///
+    /// The glyph runs of each paragraph are recorded once and re-used until the layout of the
+    /// paragraph changes.
     fn draw(&mut self, elem_context: &ElemContext, scene: &mut Scene, style: &mut dyn TextStyle) {
         self.flush(elem_context, style);
+        self.scroll_to_caret();
+
+        let clip = self.caret.is_some();
+        if clip {
+            let area = Rect::from_origin_size(
+                self.position,
+                Size::new(self.container_width as f64, self.height as f64),
+            );
+            scene.push_layer(Mix::Clip, 1.0, Affine::IDENTITY, &area);
+        }
+
+        self.draw_selection(scene);
+
+        let mut y = 0.0;
+        for paragraph in &mut self.paragraphs {
+            if paragraph.scene_dirty {
+                paragraph.scene.reset();
+                match paragraph.truncated_at {
+                    Some(limit) => {
+                        let end = record_glyph_runs(
+                            &paragraph.layout,
+                            &mut paragraph.scene,
+                            Vec2::ZERO,
+                            limit,
+                        );
 
-        for line in self.layout.lines() {
-            for item in line.items() {
-                match item {
-                    PositionedLayoutItem::GlyphRun(run) => {
-                        scene
-                            .draw_glyphs(run.run().font())
-                            .brush(&run.style().brush)
-                            .font_size(run.run().font_size())
-                            .transform(Affine::translate(self.position.to_vec2()))
-                            .draw(
-                                Fill::NonZero,
-                                run.positioned_glyphs().map(|g| Glyph {
+                        // Align the baseline of the ellipsis with the one of the paragraph.
+                        let baseline = |layout: &Layout<Brush>| {
+                            layout
+                                .lines()
+                                .next()
+                                .map_or(0.0, |line| line.metrics().baseline)
+                        };
+                        let dy = baseline(&paragraph.layout) - baseline(&self.ellipsis);
+                        record_glyph_runs(
+                            &self.ellipsis,
+                            &mut paragraph.scene,
+                            Vec2::new(end as f64, dy as f64),
+                            f32::INFINITY,
+                        );
+                    }
+                    None => {
+                        record_glyph_runs(
+                            &paragraph.layout,
+                            &mut paragraph.scene,
+                            Vec2::ZERO,
+                            f32::INFINITY,
+                        );
+                    }
+                }
+                paragraph.scene_dirty = false;
+            }
+
+            let transform =
+                Affine::translate(self.position.to_vec2() + Vec2::new(-self.scroll_x, y));
+            y += paragraph.layout.height() as f64;
+
+            scene.append(&paragraph.scene, Some(transform));
+        }
+
+        self.draw_caret(elem_context, scene);
+
+        if clip {
+            scene.pop_layer();
+        }
+    }
+}
+
+/// The text drawn at the end of truncated lines.
+const ELLIPSIS: &str = "\u{2026}";
+
+/// The width of the caret used to compute the area it covers.
+const CARET_WIDTH: f32 = 1.0;
+
+/// Computes the horizontal scroll offset of a text so that its caret is visible.
+///
+/// # Parameters
+///
+/// - `scroll_x`: the current scroll offset. The text is only scrolled when the caret is outside
+///   of the visible area.
+///
+/// - `caret`: the area covered by the caret, relative to the start of the text.
+///
+/// - `visible`: the width of the visible area.
+///
+/// - `text_width`: the width of the whole text.
+fn scroll_to_show(scroll_x: f64, caret: Rect, visible: f64, text_width: f64) -> f64 {
+    let mut scroll_x = scroll_x;
+
+    if caret.x1 > scroll_x + visible {
+        scroll_x = caret.x1 - visible;
+    }
+    if caret.x0 < scroll_x {
+        scroll_x = caret.x0;
+    }
+
+    // Removing text must not leave blank space after the end of the text.
+    let max_scroll = (text_width + CARET_WIDTH as f64 - visible).max(0.0);
+    scroll_x.clamp(0.0, max_scroll)
+}
+
+/// The duration for which the caret stays visible (or hidden) when blinking.
+const CARET_BLINK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Draws the glyph runs of the provided layout to a scene.
+///
+/// # Parameters
+///
+/// - `offset`: The offset applied to every glyph.
+///
+/// - `limit`: Glyphs that end past this horizontal position (before `offset` is applied) are
+///   not drawn.
+///
+/// # Returns
+///
+/// The horizontal position at which the last drawn glyph ends, before `offset` is applied.
+fn record_glyph_runs(
+    layout: &parley::Layout<peniko::Brush>,
+    scene: &mut Scene,
+    offset: Vec2,
+    limit: f32,
+) -> f32 {
+    let mut end = 0.0f32;
+    for line in layout.lines() {
+        for item in line.items() {
+            match item {
+                PositionedLayoutItem::GlyphRun(run) => {
+                    scene
+                        .draw_glyphs(run.run().font())
+                        .brush(&run.style().brush)
+                        .font_size(run.run().font_size())
+                        .transform(Affine::translate(offset))
+                        .draw(
+                            Fill::NonZero,
+                            run.positioned_glyphs()
+                                .filter(|g| g.x + g.advance <= limit)
+                                .inspect(|g| end = end.max(g.x + g.advance))
+                                .map(|g| Glyph {
                                     id: g.id as u32,
                                     x: g.x,
                                     y: g.y,
                                 }),
-                            );
-                    }
-                    PositionedLayoutItem::InlineBox(_box) => {
-                        panic!("Inline boxes are not yet supported");
-                    }
+                        );
+                }
+                PositionedLayoutItem::InlineBox(_box) => {
+                    panic!("Inline boxes are not yet supported");
                 }
             }
         }
     }
+    end
 }
 
 impl std::fmt::Debug for UnstyledText {
@@ -321,14 +1050,67 @@ pub struct Text<S: ?Sized> {
 
 impl<S> Text<S> {
     /// Sets the text of this [`Text`] element.
+    ///
+    /// When the modification is localized (for example when text is appended, or when a single
+    /// line is edited), only the layout of the modified paragraphs is re-computed.
     pub fn set_text(&mut self, text: impl Into<String>) {
-        let text = text.into();
-        if text != self.unstyled.text {
-            self.unstyled.text = text;
-            self.unstyled.add_dirt(TextDirtAmount::Text);
+        self.unstyled.set_text(text.into());
+    }
+
+    /// Returns the text currently selected by the user, if any.
+    ///
+    /// This is always `None` unless the element is [`selectable`](Self::selectable).
+    #[inline]
+    pub fn selected_text(&self) -> Option<&str> {
+        self.unstyled.selected_text()
+    }
+
+    /// Sets the position of the caret, as a byte index into the text.
+    ///
+    /// When a caret is set, the text is scrolled horizontally so that the caret remains visible,
+    /// and the text is clipped to the area of the element. This is mostly useful for
+    /// single-line text inputs whose content may be wider than the element. Removing the caret
+    /// scrolls the text back to its start.
+    pub fn set_caret(&mut self, index: Option<usize>) {
+        if self.unstyled.caret != index {
+            self.unstyled.caret = index;
+            self.unstyled.caret_moved_at = None;
         }
     }
 
+    /// Sets the brush used to draw the caret.
+    ///
+    /// When a brush is set, the caret passed to [`set_caret`](Self::set_caret) is drawn and
+    /// blinks while it stays in place. Otherwise, the caret is only used to scroll the text.
+    pub fn set_caret_brush(&mut self, brush: Option<Brush>) {
+        self.unstyled.caret_brush = brush;
+    }
+
+    /// Sets the selected range of the text, as an `(anchor, focus)` pair of byte indices.
+    ///
+    /// The selection is highlighted behind the glyphs, whether the element is
+    /// [`selectable`](Self::selectable) or not. It is removed whenever the text changes.
+    pub fn set_selection(&mut self, selection: Option<(usize, usize)>) {
+        self.unstyled.selection = selection;
+    }
+
+    /// Returns the area covered by the caret when placed before the provided byte index.
+    ///
+    /// The returned rectangle is in window coordinates and takes the current scroll offset of
+    /// the text into account. It is only accurate once the text has been laid out.
+    pub fn caret_rect(&self, index: usize) -> Rect {
+        self.unstyled.local_caret_rect(index) + self.unstyled.position.to_vec2()
+            - Vec2::new(self.unstyled.scroll_x, 0.0)
+    }
+
+    /// Returns the horizontal distance by which the text is currently scrolled.
+    ///
+    /// See [`set_caret`](Self::set_caret).
+    #[inline]
+    pub fn scroll_offset(&self) -> f64 {
+        self.unstyled.scroll_x
+    }
+
     /// Access the style of this [`Text`] element.
     ///
     /// This invalidates the text's layout.
@@ -339,8 +1121,7 @@ impl<S> Text<S> {
 
     /// The string that this [`Text`] element will render.
     pub fn text(mut self, text: impl Into<String>) -> Self {
-        self.unstyled.text = text.into();
-        self.unstyled.add_dirt(TextDirtAmount::Text);
+        self.unstyled.set_text(text.into());
         self
     }
 
@@ -388,6 +1169,40 @@ impl<S> Text<S> {
         self.unstyled.add_dirt(TextDirtAmount::Lines);
         self
     }
+
+    /// Whether lines that are wider than the [`Text`] element are cut and end with an
+    /// ellipsis.
+    ///
+    /// This only applies when wrapping is disabled. Truncated text can shrink down to a width
+    /// of zero, and its preferred width never exceeds the available space.
+    pub fn truncate(mut self, yes: bool) -> Self {
+        self.unstyled.truncate = yes;
+        self.unstyled.add_dirt(TextDirtAmount::Text);
+        self
+    }
+
+    /// Whether the user can select the text of the [`Text`] element with the pointer.
+    ///
+    /// Once clicked, the element responds to the copy (Ctrl+C, or Cmd+C on macOS) and select
+    /// all (Ctrl+A) shortcuts. The text cannot be edited.
+    pub fn selectable(mut self, yes: bool) -> Self {
+        self.unstyled.selectable = yes;
+        self
+    }
+
+    /// Sets the brush used to highlight the selected text of the [`Text`] element.
+    pub fn selection_brush(mut self, brush: impl Into<Brush>) -> Self {
+        self.unstyled.selection_brush = Some(brush.into());
+        self
+    }
+
+    /// Sets the brush used to draw the caret of the [`Text`] element.
+    ///
+    /// See [`set_caret_brush`](Self::set_caret_brush).
+    pub fn caret_brush(mut self, brush: impl Into<Brush>) -> Self {
+        self.unstyled.caret_brush = Some(brush.into());
+        self
+    }
 }
 
 impl Text<UniformStyle> {
@@ -509,6 +1324,34 @@ impl Text<UniformStyle> {
         self.unstyled.add_dirt(TextDirtAmount::Text);
         self
     }
+
+    /// Applies a style property to a range of the text of this [`Text`] element.
+    ///
+    /// The current style becomes the base style of a [`StyledText`]. See [`StyledText`] for
+    /// more information.
+    pub fn span(
+        mut self,
+        range: Range<usize>,
+        property: StyleProperty<'static, Brush>,
+    ) -> Text<StyledText> {
+        self.unstyled.add_dirt(TextDirtAmount::Text);
+        Text {
+            unstyled: self.unstyled,
+            style: StyledText::new(self.style),
+        }
+        .span(range, property)
+    }
+}
+
+impl Text<StyledText> {
+    /// Applies a style property to a range of the text of this [`Text`] element.
+    ///
+    /// See [`StyledText`] for more information.
+    pub fn span(mut self, range: Range<usize>, property: StyleProperty<'static, Brush>) -> Self {
+        self.style.push(range, property);
+        self.unstyled.add_dirt(TextDirtAmount::Text);
+        self
+    }
 }
 
 impl<S: TextStyle> Element for Text<S> {
@@ -532,9 +1375,23 @@ impl<S: TextStyle> Element for Text<S> {
         self.unstyled.place(layout_context, pos, size);
     }
 
+    fn hit_test(&self, point: Point) -> bool {
+        self.unstyled.selectable && self.unstyled.bounds().contains(point)
+    }
+
     fn draw(&mut self, elem_context: &ElemContext, scene: &mut Scene) {
         self.unstyled.draw(elem_context, scene, &mut self.style);
     }
+
+    #[inline]
+    fn event(&mut self, elem_context: &ElemContext, event: &dyn Event) -> EventResult {
+        self.unstyled.event(elem_context, event)
+    }
+
+    #[inline]
+    fn accessibility(&self, cx: &mut AccessibilityContext) {
+        self.unstyled.accessibility(cx);
+    }
 }
 
 impl Element for Text<dyn TextStyle> {
@@ -558,7 +1415,116 @@ impl Element for Text<dyn TextStyle> {
         self.unstyled.place(layout_context, pos, size);
     }
 
+    fn hit_test(&self, point: Point) -> bool {
+        self.unstyled.selectable && self.unstyled.bounds().contains(point)
+    }
+
     fn draw(&mut self, elem_context: &ElemContext, scene: &mut Scene) {
         self.unstyled.draw(elem_context, scene, &mut self.style);
     }
+
+    #[inline]
+    fn event(&mut self, elem_context: &ElemContext, event: &dyn Event) -> EventResult {
+        self.unstyled.event(elem_context, event)
+    }
+
+    #[inline]
+    fn accessibility(&self, cx: &mut AccessibilityContext) {
+        self.unstyled.accessibility(cx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::{CARET_WIDTH, scroll_to_show},
+        crate::elements::label,
+        vello::kurbo::Rect,
+    };
+
+    #[test]
+    fn copied_text_is_the_selected_range() {
+        let mut text = label().text("first line\nsecond line").selectable(true);
+
+        // The focus is before the anchor, and the selection spans both paragraphs.
+        text.unstyled.selection = Some((13, 6));
+        assert_eq!(text.selected_text(), Some("line\nse"));
+
+        text.unstyled.selection = Some((11, 17));
+        assert_eq!(text.selected_text(), Some("second"));
+    }
+
+    #[test]
+    fn copied_text_respects_multibyte_characters() {
+        let mut text = label().text("naïve café").selectable(true);
+
+        // "ï" and "é" are two bytes long.
+        text.unstyled.selection = Some((2, 6));
+        assert_eq!(text.selected_text(), Some("ïve"));
+
+        text.unstyled.selection = Some((0, "naïve café".len()));
+        assert_eq!(text.selected_text(), Some("naïve café"));
+    }
+
+    #[test]
+    fn empty_or_reset_selection_copies_nothing() {
+        let mut text = label().text("hello").selectable(true);
+
+        text.unstyled.selection = Some((3, 3));
+        assert_eq!(text.selected_text(), None);
+
+        text.unstyled.selection = Some((0, 5));
+        text.set_text("world");
+        assert_eq!(text.selected_text(), None);
+    }
+
+    /// Returns the area covered by a caret located `x` pixels from the start of the text.
+    fn caret_at(x: f64) -> Rect {
+        Rect::new(x, 0.0, x + CARET_WIDTH as f64, 20.0)
+    }
+
+    /// Returns whether the caret is within the visible area.
+    fn is_on_screen(caret: Rect, scroll_x: f64, visible: f64) -> bool {
+        caret.x0 >= scroll_x && caret.x1 <= scroll_x + visible
+    }
+
+    #[test]
+    fn caret_stays_on_screen_while_typing() {
+        let visible = 100.0;
+        let mut scroll_x = 0.0;
+
+        // The text grows by one 10 pixels wide character at a time, with the caret at its end.
+        for len in 0..30 {
+            let width = len as f64 * 10.0;
+            let caret = caret_at(width);
+            scroll_x = scroll_to_show(scroll_x, caret, visible, width);
+            assert!(is_on_screen(caret, scroll_x, visible), "{len}: {scroll_x}");
+        }
+
+        assert_eq!(scroll_x, 290.0 + CARET_WIDTH as f64 - visible);
+    }
+
+    #[test]
+    fn caret_moving_left_scrolls_back() {
+        let visible = 100.0;
+        let caret = caret_at(50.0);
+
+        let scroll_x = scroll_to_show(200.0, caret, visible, 300.0);
+
+        assert_eq!(scroll_x, 50.0);
+        assert!(is_on_screen(caret, scroll_x, visible));
+    }
+
+    #[test]
+    fn caret_within_visible_area_does_not_scroll() {
+        assert_eq!(scroll_to_show(40.0, caret_at(80.0), 100.0, 300.0), 40.0);
+    }
+
+    #[test]
+    fn removing_text_does_not_leave_blank_space() {
+        // The text shrank from 300 to 120 pixels while scrolled to its end.
+        let scroll_x = scroll_to_show(201.0, caret_at(120.0), 100.0, 120.0);
+
+        assert_eq!(scroll_x, 120.0 + CARET_WIDTH as f64 - 100.0);
+    }
 }
@@ -1,34 +1,192 @@
 use {
     crate::{
         ElemContext, Element, LayoutContext, SizeHint,
+        accessibility::{AccessibilityContext, NodeIdCell},
         elements::interactive::{Appearance, InteractiveState},
-        event::{Event, EventResult, KeyEvent},
+        event::{ClipboardPaste, Event, EventResult, KeyEvent},
     },
+    accesskit::{Action, Node, Role},
+    std::ops::Range,
     vello::kurbo::{Point, Size},
-    winit::keyboard::{ModifiersState, NamedKey},
+    winit::keyboard::{Key, NamedKey},
 };
 
-/// Removes the last word of the provided string.
-fn remove_last_word(s: &mut String) {
-    let idx = s
+/// Returns the byte index of the character boundary before `index`.
+fn prev_char_boundary(s: &str, index: usize) -> usize {
+    s[..index].char_indices().next_back().map_or(0, |(i, _)| i)
+}
+
+/// Returns the byte index of the character boundary after `index`.
+fn next_char_boundary(s: &str, index: usize) -> usize {
+    s[index..]
+        .chars()
+        .next()
+        .map_or(s.len(), |c| index + c.len_utf8())
+}
+
+/// Returns the byte index of the start of the word before `index`.
+///
+/// The whitespace that precedes the word is included, so that removing the range leaves no
+/// trailing whitespace.
+fn prev_word_boundary(s: &str, index: usize) -> usize {
+    s[..index]
         .trim_end_matches(|c: char| c.is_whitespace())
         .trim_end_matches(|c: char| !c.is_whitespace())
         .trim_end_matches(|c: char| c.is_whitespace())
-        .len();
-    s.truncate(idx);
+        .len()
+}
+
+/// Returns the byte index of the end of the word after `index`.
+fn next_word_boundary(s: &str, index: usize) -> usize {
+    let rest = s[index..].trim_start_matches(|c: char| c.is_whitespace());
+    let rest = rest.trim_start_matches(|c: char| !c.is_whitespace());
+    s.len() - rest.len()
+}
+
+/// A string being edited, along with a caret and a selection.
+///
+/// The caret and the anchor of the selection are byte indices into the text, always located on
+/// character boundaries. When they are equal, nothing is selected.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EditableText {
+    /// The text being edited.
+    text: String,
+    /// The position of the caret.
+    ///
+    /// This is also the end of the selection that moves when the selection is extended.
+    caret: usize,
+    /// The position where the selection started.
+    anchor: usize,
+}
+
+impl EditableText {
+    /// Creates a new [`EditableText`] with the caret at the end of the provided text.
+    pub fn new(text: impl Into<String>) -> Self {
+        let text = text.into();
+        let len = text.len();
+        Self {
+            text,
+            caret: len,
+            anchor: len,
+        }
+    }
+
+    /// Returns the text being edited.
+    #[inline]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns whether the text is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// Returns the position of the caret, as a byte index into the text.
+    #[inline]
+    pub fn caret(&self) -> usize {
+        self.caret
+    }
+
+    /// Returns the selection, as an `(anchor, caret)` pair of byte indices.
+    ///
+    /// This is `None` when nothing is selected.
+    pub fn selection(&self) -> Option<(usize, usize)> {
+        (self.anchor != self.caret).then_some((self.anchor, self.caret))
+    }
+
+    /// Returns the selected range of the text.
+    ///
+    /// The range is empty (and located at the caret) when nothing is selected.
+    pub fn selected_range(&self) -> Range<usize> {
+        self.anchor.min(self.caret)..self.anchor.max(self.caret)
+    }
+
+    /// Replaces the whole text, moving the caret to its end.
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        *self = Self::new(text);
+    }
+
+    /// Moves the caret to the provided byte index.
+    ///
+    /// When `extend` is set, the anchor of the selection stays in place. Otherwise, the
+    /// selection is removed.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `index` is not a character boundary of the text.
+    pub fn move_caret(&mut self, index: usize, extend: bool) {
+        assert!(
+            self.text.is_char_boundary(index),
+            "The caret must be on a character boundary",
+        );
+
+        self.caret = index;
+        if !extend {
+            self.anchor = index;
+        }
+    }
+
+    /// Selects the whole text.
+    pub fn select_all(&mut self) {
+        self.anchor = 0;
+        self.caret = self.text.len();
+    }
+
+    /// Replaces the selection with the provided text, or inserts it at the caret when nothing
+    /// is selected.
+    pub fn insert(&mut self, text: &str) {
+        let range = self.selected_range();
+        self.text.replace_range(range.clone(), text);
+        self.move_caret(range.start + text.len(), false);
+    }
+
+    /// Removes the provided range of the text, moving the caret to its start.
+    fn remove(&mut self, range: Range<usize>) {
+        self.text.replace_range(range.clone(), "");
+        self.move_caret(range.start, false);
+    }
+
+    /// Removes the selected text.
+    ///
+    /// # Returns
+    ///
+    /// Whether something was selected (and thus removed).
+    pub fn delete_selection(&mut self) -> bool {
+        let range = self.selected_range();
+        if range.is_empty() {
+            return false;
+        }
+        self.remove(range);
+        true
+    }
 }
 
 /// An element that allows the user to input text.
 ///
+/// The element keeps track of a caret and a selection. The arrow keys move the caret (by
+/// words when Ctrl, or Option on macOS, is held), Home and End move it to the bounds of the
+/// text, and holding Shift extends the selection instead of removing it. The selection can be
+/// copied or cut to the system clipboard, and [`ClipboardPaste`] events are inserted at the
+/// caret.
+///
 /// # Remarks
 ///
-/// This does not include any text rendering.
+/// This does not include any text rendering. The appearance of the element receives the
+/// [`EditableText`] and is expected to render the caret and the selection itself (see
+/// [`Text::set_caret`] and [`Text::set_selection`]).
+///
+/// [`Text::set_caret`]: super::text::Text::set_caret
+/// [`Text::set_selection`]: super::text::Text::set_selection
 #[derive(Clone, Debug, Default)]
 pub struct TextInput<A: ?Sized> {
     /// The value of the text input element.
-    pub value: String,
+    pub value: EditableText,
     /// The state of the interactive element.
     pub state: InteractiveState,
+    /// The identifier of the node of the text input element in the accessibility tree.
+    node_id: NodeIdCell,
     /// The appearance of the text input element.
     pub appearance: A,
 }
@@ -39,51 +197,141 @@ impl<A> TextInput<A> {
         TextInput {
             value: self.value,
             state: self.state,
+            node_id: self.node_id,
             appearance,
         }
     }
 }
 
-impl<A: ?Sized + Appearance<str>> TextInput<A> {
+impl<A: ?Sized + Appearance<EditableText>> TextInput<A> {
     /// Handles a key event.
-    fn handle_key_event(&mut self, modifiers: ModifiersState, event: &KeyEvent) -> bool {
-        if !event.state.is_pressed() {
+    ///
+    /// Repeated key presses are handled just like fresh ones, so that holding a key down keeps
+    /// inserting (or removing) characters.
+    ///
+    /// # Returns
+    ///
+    /// Whether the text, the caret or the selection changed.
+    fn handle_key_event(&mut self, elem_context: &ElemContext, event: &KeyEvent) -> bool {
+        if !event.is_pressed() {
             return false;
         }
 
-        if event.logical_key == NamedKey::Backspace {
-            if cfg!(target_os = "macos") {
-                if modifiers.control_key() {
+        let modifiers = event.modifiers;
+        let (word, line, shortcut) = if cfg!(target_os = "macos") {
+            (
+                modifiers.alt_key(),
+                modifiers.super_key(),
+                modifiers.super_key(),
+            )
+        } else {
+            (modifiers.control_key(), false, modifiers.control_key())
+        };
+        let extend = modifiers.shift_key();
+
+        let text = self.value.text();
+        let caret = self.value.caret();
+
+        match &event.logical_key {
+            Key::Named(NamedKey::ArrowLeft) => {
+                let index = if line {
+                    0
+                } else if word {
+                    prev_word_boundary(text, caret)
+                } else if !extend && self.value.selection().is_some() {
+                    self.value.selected_range().start
+                } else {
+                    prev_char_boundary(text, caret)
+                };
+                self.value.move_caret(index, extend);
+                return true;
+            }
+            Key::Named(NamedKey::ArrowRight) => {
+                let index = if line {
+                    text.len()
+                } else if word {
+                    next_word_boundary(text, caret)
+                } else if !extend && self.value.selection().is_some() {
+                    self.value.selected_range().end
+                } else {
+                    next_char_boundary(text, caret)
+                };
+                self.value.move_caret(index, extend);
+                return true;
+            }
+            Key::Named(NamedKey::Home | NamedKey::ArrowUp) => {
+                self.value.move_caret(0, extend);
+                return true;
+            }
+            Key::Named(NamedKey::End | NamedKey::ArrowDown) => {
+                self.value.move_caret(text.len(), extend);
+                return true;
+            }
+            Key::Named(NamedKey::Backspace) => {
+                if cfg!(target_os = "macos") && modifiers.control_key() {
                     // Ignored.
                     return false;
                 }
 
-                if modifiers.super_key() {
-                    self.value.clear();
-                } else if modifiers.alt_key() {
-                    remove_last_word(&mut self.value);
+                let range = if self.value.selection().is_some() {
+                    self.value.selected_range()
+                } else if line {
+                    0..caret
+                } else if word {
+                    prev_word_boundary(text, caret)..caret
                 } else {
-                    self.value.pop();
-                }
-            } else {
-                #[allow(clippy::collapsible_if)]
-                if modifiers.control_key() {
-                    remove_last_word(&mut self.value);
+                    prev_char_boundary(text, caret)..caret
+                };
+                self.value.remove(range);
+
+                self.state.insert(InteractiveState::VALUE_CHANGED);
+                return true;
+            }
+            Key::Named(NamedKey::Delete) => {
+                let range = if self.value.selection().is_some() {
+                    self.value.selected_range()
+                } else if word {
+                    caret..next_word_boundary(text, caret)
                 } else {
-                    self.value.pop();
-                }
+                    caret..next_char_boundary(text, caret)
+                };
+                self.value.remove(range);
+
+                self.state.insert(InteractiveState::VALUE_CHANGED);
+                return true;
             }
+            Key::Named(NamedKey::Enter) => return true,
+            Key::Character(c) if shortcut && c.eq_ignore_ascii_case("a") => {
+                self.value.select_all();
+                return true;
+            }
+            Key::Character(c) if shortcut && c.eq_ignore_ascii_case("c") => {
+                self.copy_selection(elem_context);
+                return false;
+            }
+            Key::Character(c) if shortcut && c.eq_ignore_ascii_case("x") => {
+                if !self.copy_selection(elem_context) {
+                    return false;
+                }
 
-            self.state.insert(InteractiveState::VALUE_CHANGED);
-            return true;
+                self.value.delete_selection();
+                self.state.insert(InteractiveState::VALUE_CHANGED);
+                return true;
+            }
+            _ => (),
         }
 
-        if event.logical_key == NamedKey::Enter || event.logical_key == NamedKey::Tab {
-            return true;
+        if shortcut {
+            // Shortcuts are left to the rest of the application.
+            return false;
         }
 
-        if let Some(text) = event.text.as_ref() {
-            self.value.push_str(text);
+        if let Some(text) = event
+            .text
+            .as_ref()
+            .filter(|t| !t.chars().any(char::is_control))
+        {
+            self.value.insert(text);
             self.state.insert(InteractiveState::VALUE_CHANGED);
             return true;
         }
@@ -92,9 +340,41 @@ impl<A: ?Sized + Appearance<str>> TextInput<A> {
     }
 }
 
+impl<A: ?Sized> TextInput<A> {
+    /// Copies the selected text to the system clipboard.
+    ///
+    /// # Returns
+    ///
+    /// Whether some text was selected and successfully copied.
+    fn copy_selection(&self, elem_context: &ElemContext) -> bool {
+        let range = self.value.selected_range();
+        if range.is_empty() {
+            return false;
+        }
+
+        let text = self.value.text()[range].to_owned();
+        elem_context.ctx.set_clipboard_text(text).is_ok()
+    }
+
+    /// Inserts pasted text at the caret, replacing the selection.
+    ///
+    /// Line breaks are replaced with spaces because the input only holds a single line.
+    fn paste(&mut self, text: &str) {
+        let text: String = text
+            .chars()
+            .filter(|&c| c != '\r')
+            .map(|c| if c == '\n' { ' ' } else { c })
+            .filter(|c| !c.is_control())
+            .collect();
+
+        self.value.insert(&text);
+        self.state.insert(InteractiveState::VALUE_CHANGED);
+    }
+}
+
 impl<A> Element for TextInput<A>
 where
-    A: ?Sized + Appearance<str>,
+    A: ?Sized + Appearance<EditableText>,
 {
     #[inline]
     fn size_hint(
@@ -134,16 +414,28 @@ where
         self.state.remove_transient_states();
 
         let og_state = self.state;
+        let mut edited = false;
+        self.state.handle_focus_traversal(event);
+        self.state
+            .handle_accessibility_action(elem_context, &self.node_id, event);
         let mut event_result = self
             .state
             .handle_pointer_interactions(&mut |pt| self.appearance.hit_test(pt), event);
         if self.state.focused() {
-            if let Some(ev) = event.downcast_ref::<KeyEvent>() {
-                self.handle_key_event(elem_context.window.keyboard_modifiers(), ev);
+            // Tab is left to the window so that it can move the focus to the next element.
+            if let Some(ev) = event
+                .downcast_ref::<KeyEvent>()
+                .filter(|ev| ev.logical_key != NamedKey::Tab)
+            {
+                edited = self.handle_key_event(elem_context, ev);
+                event_result = EventResult::Handled;
+            } else if let Some(ClipboardPaste(text)) = event.downcast_ref::<ClipboardPaste>() {
+                self.paste(text);
+                edited = true;
                 event_result = EventResult::Handled;
             }
         }
-        if og_state != self.state {
+        if og_state != self.state || edited {
             self.appearance
                 .state_changed(elem_context, self.state, &self.value);
         }
@@ -159,4 +451,19 @@ where
         self.appearance
             .state_changed(elem_context, self.state, &self.value);
     }
+
+    fn accessibility(&self, cx: &mut AccessibilityContext) {
+        let mut node = Node::new(Role::TextInput);
+        node.add_action(Action::Focus);
+        node.set_value(self.value.text());
+        if self.state.disabled() {
+            node.set_disabled();
+        }
+
+        let id = cx.push(node);
+        self.node_id.set(id);
+        if self.state.focused() {
+            cx.set_focus(id);
+        }
+    }
 }
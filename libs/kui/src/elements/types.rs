@@ -1,4 +1,10 @@
-use {crate::LayoutContext, std::fmt::Debug};
+use {
+    crate::{LayoutContext, elements::flex::Direction},
+    std::{
+        fmt::Debug,
+        ops::{Add, Mul, Neg, Sub},
+    },
+};
 
 /// Represents a length.
 #[derive(Clone)]
@@ -17,6 +23,33 @@ pub enum Length {
     ParentWidth(f64),
     /// A fraction of the parent element's height.
     ParentHeight(f64),
+    /// A fraction of the parent element's size along the axis the length is measured on.
+    ///
+    /// Widths, horizontal paddings and horizontal gaps resolve this against the parent's width,
+    /// while heights, vertical paddings and vertical gaps resolve it against the parent's
+    /// height. The gap of a flex container is measured along its main axis. Lengths that are not
+    /// tied to an axis (such as corner radii or border thicknesses) use the parent's width.
+    ParentAxis(f64),
+
+    /// A fraction of the width of the window's client area.
+    ViewportWidth(f64),
+    /// A fraction of the height of the window's client area.
+    ViewportHeight(f64),
+    /// A multiple of the current font size.
+    ///
+    /// The style properties of a text element (such as its letter spacing) are relative to the
+    /// font size of that element. Other lengths are relative to the font size of the root
+    /// element, [`LayoutContext::DEFAULT_FONT_SIZE`].
+    Em(f64),
+
+    /// The sum of two lengths.
+    ///
+    /// This is usually created through the `+` and `-` operators.
+    Sum(Box<Length>, Box<Length>),
+    /// A length multiplied by a constant factor.
+    ///
+    /// This is usually created through the `*` and unary `-` operators.
+    Scaled(f64, Box<Length>),
 
     /// Computes the length using a runtime function.
     Compute(Box<dyn LengthCalculation>),
@@ -29,7 +62,19 @@ impl Length {
     pub const INFINITY: Self = Self::UnscaledPixels(f64::INFINITY);
 
     /// Resolves the length to a concrete value in unscaled pixels.
+    ///
+    /// [`Length::ParentAxis`] is resolved against the parent's width. Use
+    /// [`resolve_along`](Self::resolve_along) for lengths measured along a specific axis.
+    #[inline]
     pub fn resolve(&self, context: &LayoutContext) -> f64 {
+        self.resolve_along(context, Direction::Horizontal)
+    }
+
+    /// Resolves the length to a concrete value in unscaled pixels, assuming it is measured
+    /// along the provided axis.
+    ///
+    /// This only affects how [`Length::ParentAxis`] is resolved.
+    pub fn resolve_along(&self, context: &LayoutContext, axis: Direction) -> f64 {
         /// If the input is finite, returns the input. Otherwise, returns zero.
         #[inline]
         fn finite_or_zero(f: f64) -> f64 {
@@ -41,9 +86,93 @@ impl Length {
             Length::Pixels(pixels) => pixels * context.scale_factor,
             Length::ParentWidth(fraction) => finite_or_zero(context.parent.width) * fraction,
             Length::ParentHeight(fraction) => finite_or_zero(context.parent.height) * fraction,
+            Length::ParentAxis(fraction) => match axis {
+                Direction::Horizontal => finite_or_zero(context.parent.width) * fraction,
+                Direction::Vertical => finite_or_zero(context.parent.height) * fraction,
+            },
+            Length::ViewportWidth(fraction) => finite_or_zero(context.viewport.width) * fraction,
+            Length::ViewportHeight(fraction) => finite_or_zero(context.viewport.height) * fraction,
+            Length::Em(factor) => context.font_size * factor,
+            Length::Sum(a, b) => a.resolve_along(context, axis) + b.resolve_along(context, axis),
+            Length::Scaled(factor, length) => factor * length.resolve_along(context, axis),
             Length::Compute(f) => f.resolve(context),
         }
     }
+
+    /// Multiplies the length by the provided factor.
+    ///
+    /// Simple lengths are scaled directly, without introducing a [`Length::Scaled`] node.
+    pub fn scale(self, factor: f64) -> Self {
+        match self {
+            Length::UnscaledPixels(pixels) => Length::UnscaledPixels(pixels * factor),
+            Length::Pixels(pixels) => Length::Pixels(pixels * factor),
+            Length::ParentWidth(fraction) => Length::ParentWidth(fraction * factor),
+            Length::ParentHeight(fraction) => Length::ParentHeight(fraction * factor),
+            Length::ParentAxis(fraction) => Length::ParentAxis(fraction * factor),
+            Length::ViewportWidth(fraction) => Length::ViewportWidth(fraction * factor),
+            Length::ViewportHeight(fraction) => Length::ViewportHeight(fraction * factor),
+            Length::Em(em) => Length::Em(em * factor),
+            Length::Scaled(inner, length) => Length::Scaled(inner * factor, length),
+            other => Length::Scaled(factor, Box::new(other)),
+        }
+    }
+}
+
+impl Add for Length {
+    type Output = Length;
+
+    /// Adds two lengths together.
+    ///
+    /// Lengths of the same unit are added directly, without introducing a [`Length::Sum`] node.
+    fn add(self, rhs: Length) -> Length {
+        match (self, rhs) {
+            (Length::UnscaledPixels(a), Length::UnscaledPixels(b)) => Length::UnscaledPixels(a + b),
+            (Length::Pixels(a), Length::Pixels(b)) => Length::Pixels(a + b),
+            (Length::ParentWidth(a), Length::ParentWidth(b)) => Length::ParentWidth(a + b),
+            (Length::ParentHeight(a), Length::ParentHeight(b)) => Length::ParentHeight(a + b),
+            (Length::ParentAxis(a), Length::ParentAxis(b)) => Length::ParentAxis(a + b),
+            (Length::ViewportWidth(a), Length::ViewportWidth(b)) => Length::ViewportWidth(a + b),
+            (Length::ViewportHeight(a), Length::ViewportHeight(b)) => Length::ViewportHeight(a + b),
+            (Length::Em(a), Length::Em(b)) => Length::Em(a + b),
+            (a, b) => Length::Sum(Box::new(a), Box::new(b)),
+        }
+    }
+}
+
+impl Sub for Length {
+    type Output = Length;
+
+    #[inline]
+    fn sub(self, rhs: Length) -> Length {
+        self + -rhs
+    }
+}
+
+impl Neg for Length {
+    type Output = Length;
+
+    #[inline]
+    fn neg(self) -> Length {
+        self.scale(-1.0)
+    }
+}
+
+impl Mul<f64> for Length {
+    type Output = Length;
+
+    #[inline]
+    fn mul(self, rhs: f64) -> Length {
+        self.scale(rhs)
+    }
+}
+
+impl Mul<Length> for f64 {
+    type Output = Length;
+
+    #[inline]
+    fn mul(self, rhs: Length) -> Length {
+        rhs.scale(self)
+    }
 }
 
 impl Default for Length {
@@ -57,8 +186,14 @@ impl Debug for Length {
         match self {
             Length::UnscaledPixels(pixels) => write!(f, "{}upx", pixels),
             Length::Pixels(pixels) => write!(f, "{}px", pixels),
-            Length::ParentWidth(fraction) => write!(f, "{}%", fraction * 100.0),
-            Length::ParentHeight(fraction) => write!(f, "{}%", fraction * 100.0),
+            Length::ParentWidth(fraction) => write!(f, "{}w%", fraction * 100.0),
+            Length::ParentHeight(fraction) => write!(f, "{}h%", fraction * 100.0),
+            Length::ParentAxis(fraction) => write!(f, "{}%", fraction * 100.0),
+            Length::ViewportWidth(fraction) => write!(f, "{}vw", fraction * 100.0),
+            Length::ViewportHeight(fraction) => write!(f, "{}vh", fraction * 100.0),
+            Length::Em(em) => write!(f, "{em}em"),
+            Length::Sum(a, b) => write!(f, "({a:?} + {b:?})"),
+            Length::Scaled(factor, length) => write!(f, "{factor} * {length:?}"),
             Length::Compute(calc) => calc.fmt_debug(f),
         }
     }
@@ -97,3 +232,67 @@ impl<F: 'static + Clone + Fn(&LayoutContext) -> f64> LengthCalculation for F {
         Box::new(self.clone())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::Length,
+        crate::{LayoutContext, elements::flex::Direction},
+        vello::kurbo::Size,
+    };
+
+    /// Returns a layout context with a scale factor of 2, a 1000x500 viewport, and a 200x100
+    /// parent.
+    fn layout_context() -> LayoutContext {
+        LayoutContext::root(Size::new(1000.0, 500.0), 2.0).with_parent(Size::new(200.0, 100.0))
+    }
+
+    #[test]
+    fn same_units_are_folded() {
+        let length = Length::Pixels(3.0) + Length::Pixels(4.0);
+        assert!(matches!(length, Length::Pixels(7.0)));
+
+        let length = -Length::Em(2.0) * 0.5;
+        assert!(matches!(length, Length::Em(-1.0)));
+    }
+
+    #[test]
+    fn difference_of_units_is_resolved() {
+        let length = Length::ParentWidth(0.5) - Length::Pixels(10.0);
+        assert!(matches!(length, Length::Sum(..)));
+
+        // 50% of 200 minus 10 scaled pixels.
+        assert_eq!(length.resolve(&layout_context()), 80.0);
+    }
+
+    #[test]
+    fn scaled_sum_is_resolved() {
+        let length = (Length::ViewportWidth(0.1) + Length::Em(1.0)) * 2.0;
+        assert!(matches!(length, Length::Scaled(..)));
+
+        // 10% of 1000, plus the default font size at a scale factor of 2, doubled.
+        assert_eq!(length.resolve(&layout_context()), 264.0);
+    }
+
+    #[test]
+    fn nested_sum_is_resolved_along_axis() {
+        let length = 2.0 * (Length::ParentAxis(0.25) + Length::UnscaledPixels(5.0))
+            - Length::ViewportHeight(0.1);
+        let context = layout_context();
+
+        assert_eq!(length.resolve_along(&context, Direction::Horizontal), 60.0);
+        assert_eq!(length.resolve_along(&context, Direction::Vertical), 10.0);
+    }
+
+    #[test]
+    fn viewport_and_font_units_are_resolved() {
+        // A 1280x720 window, in which the element's parent is much smaller.
+        let context =
+            LayoutContext::root(Size::new(1280.0, 720.0), 1.0).with_parent(Size::new(100.0, 100.0));
+
+        // `50vw`, `25vh` and `2 em`.
+        assert_eq!(Length::ViewportWidth(0.5).resolve(&context), 640.0);
+        assert_eq!(Length::ViewportHeight(0.25).resolve(&context), 180.0);
+        assert_eq!(Length::Em(2.0).resolve(&context), 32.0);
+    }
+}
@@ -0,0 +1,199 @@
+use {
+    crate::{
+        ElemContext, Element, LayoutContext, SizeHint,
+        accessibility::AccessibilityContext,
+        event::{Event, EventResult},
+    },
+    vello::kurbo::{Point, Size},
+};
+
+/// Whether an element is displayed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Visibility {
+    /// The element is displayed normally.
+    #[default]
+    Visible,
+    /// The element is not drawn and does not receive events, but it still takes up space in the
+    /// layout.
+    ///
+    /// This is the equivalent of CSS's `visibility: hidden`.
+    Hidden,
+    /// The element is not drawn, does not receive events, and does not take up any space.
+    ///
+    /// This is the equivalent of CSS's `display: none`. Note that containers might still add
+    /// spacing (such as the gap of a flex container) around the collapsed element.
+    Collapsed,
+}
+
+/// An element that can hide its child without removing it from the UI tree.
+///
+/// This is cheaper than re-building the tree for things that are toggled often, such as the
+/// content of an expandable section. The child keeps its state while hidden.
+#[derive(Clone, Debug, Default)]
+pub struct WithVisibility<E: ?Sized> {
+    /// The visibility of the child element.
+    visibility: Visibility,
+    /// The child element.
+    pub child: E,
+}
+
+impl<E> WithVisibility<E> {
+    /// Creates a new [`WithVisibility`] element.
+    #[inline]
+    pub fn new(visibility: Visibility, child: E) -> Self {
+        Self { visibility, child }
+    }
+
+    /// Sets the visibility of the child element.
+    pub fn visibility(mut self, visibility: Visibility) -> Self {
+        self.visibility = visibility;
+        self
+    }
+
+    /// Sets the child element of this [`WithVisibility`] element.
+    pub fn child<E2>(self, child: E2) -> WithVisibility<E2> {
+        WithVisibility {
+            visibility: self.visibility,
+            child,
+        }
+    }
+}
+
+impl<E: ?Sized> WithVisibility<E> {
+    /// Returns the visibility of the child element.
+    #[inline]
+    pub fn get_visibility(&self) -> Visibility {
+        self.visibility
+    }
+
+    /// Sets the visibility of the child element.
+    ///
+    /// The caller is responsible for requesting a relayout of the window when switching from or
+    /// to [`Visibility::Collapsed`], and a redraw otherwise.
+    #[inline]
+    pub fn set_visibility(&mut self, visibility: Visibility) {
+        self.visibility = visibility;
+    }
+
+    /// Returns whether the child element is visible.
+    #[inline]
+    pub fn is_visible(&self) -> bool {
+        self.visibility == Visibility::Visible
+    }
+}
+
+impl<E: ?Sized + Element> Element for WithVisibility<E> {
+    fn size_hint(
+        &mut self,
+        elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        space: Size,
+    ) -> SizeHint {
+        if self.visibility == Visibility::Collapsed {
+            return SizeHint {
+                preferred: Size::ZERO,
+                min: Size::ZERO,
+                max: Size::ZERO,
+            };
+        }
+
+        self.child.size_hint(elem_context, layout_context, space)
+    }
+
+    fn place(
+        &mut self,
+        elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        pos: Point,
+        size: Size,
+    ) {
+        if self.visibility == Visibility::Collapsed {
+            return;
+        }
+
+        self.child.place(elem_context, layout_context, pos, size);
+    }
+
+    fn hit_test(&self, point: Point) -> bool {
+        self.is_visible() && self.child.hit_test(point)
+    }
+
+    fn draw(&mut self, elem_context: &ElemContext, scene: &mut vello::Scene) {
+        if self.is_visible() {
+            self.child.draw(elem_context, scene);
+        }
+    }
+
+    fn event(&mut self, elem_context: &ElemContext, event: &dyn Event) -> EventResult {
+        if !self.is_visible() {
+            return EventResult::Continue;
+        }
+
+        self.child.event(elem_context, event)
+    }
+
+    #[inline]
+    fn begin(&mut self, elem_context: &ElemContext) {
+        self.child.begin(elem_context);
+    }
+
+    fn accessibility(&self, cx: &mut AccessibilityContext) {
+        if self.is_visible() {
+            self.child.accessibility(cx);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::{Visibility, WithVisibility},
+        crate::{Element, HeadlessRenderer, LayoutContext, elements::flex, test_utils::Probe},
+        vello::kurbo::{Rect, Size},
+    };
+
+    /// Lays out three 30x10 elements in a row, the middle one having the provided visibility.
+    ///
+    /// # Returns
+    ///
+    /// The minimum width of the row, and the rectangle in which the last element was placed.
+    fn layout_row(visibility: Visibility) -> (f64, Rect) {
+        let (last, last_rect) = Probe::new(30.0, 10.0);
+        let mut root = flex()
+            .horizontal()
+            .child(Probe::new(30.0, 10.0).0)
+            .child(WithVisibility::new(visibility, Probe::new(30.0, 10.0).0))
+            .child(last);
+
+        let renderer = HeadlessRenderer::new();
+        let size = Size::new(200.0, 100.0);
+        let min_width = root
+            .size_hint(
+                &renderer.elem_context(),
+                LayoutContext::root(size, 1.0),
+                size,
+            )
+            .min
+            .width;
+        renderer.place_element(&mut root, size, 1.0);
+
+        (min_width, last_rect.get())
+    }
+
+    #[test]
+    fn hidden_element_keeps_its_space() {
+        let (min_width, last_rect) = layout_row(Visibility::Hidden);
+
+        assert_eq!(min_width, 90.0);
+        assert_eq!(last_rect, Rect::new(60.0, 0.0, 90.0, 10.0));
+        assert_eq!(layout_row(Visibility::Visible), (min_width, last_rect));
+    }
+
+    #[test]
+    fn collapsed_element_takes_no_space() {
+        let (min_width, last_rect) = layout_row(Visibility::Collapsed);
+
+        assert_eq!(min_width, 60.0);
+        assert_eq!(last_rect, Rect::new(30.0, 0.0, 60.0, 10.0));
+    }
+}
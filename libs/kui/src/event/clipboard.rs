@@ -0,0 +1,25 @@
+use winit::keyboard::{Key, ModifiersState};
+
+/// The user pressed the paste shortcut (Ctrl+V, or Cmd+V on macOS) while the clipboard
+/// contained some text.
+///
+/// The event carries the text of the clipboard. Like keyboard events, it is dispatched to the
+/// whole UI tree, and only the focused element is expected to handle it.
+///
+/// When no element handles this event, the key press that triggered it is dispatched as a
+/// regular [`KeyEvent`](super::KeyEvent). Clipboards that contain no text (or data that is not
+/// valid UTF-8) do not produce a paste event at all.
+#[derive(Clone, Debug)]
+pub struct ClipboardPaste(pub String);
+
+/// Returns whether the provided key, pressed with the provided modifiers, is the platform's paste
+/// shortcut.
+pub fn is_paste_shortcut(key: &Key, modifiers: ModifiersState) -> bool {
+    let shortcut = if cfg!(target_os = "macos") {
+        modifiers.super_key()
+    } else {
+        modifiers.control_key()
+    };
+
+    matches!(key, Key::Character(c) if shortcut && c.eq_ignore_ascii_case("v"))
+}
@@ -0,0 +1,65 @@
+use {
+    std::{cell::Cell, path::PathBuf, rc::Rc},
+    vello::kurbo::Point,
+};
+
+/// Files from the operating system are being dragged over the window.
+///
+/// This event is dispatched once when the files enter the window, and then every time they move
+/// over it.
+///
+/// When multiple files are dragged at once, a single event carrying all of their paths is
+/// dispatched rather than one event per file.
+///
+/// Elements that would accept the files if they were dropped at the current position should
+/// call [`accept`](Self::accept). The window uses this to tell the user whether the files can be
+/// dropped (for example by changing the cursor).
+#[derive(Clone, Debug)]
+pub struct FileHovered {
+    /// The paths of the files being dragged.
+    pub paths: Rc<[PathBuf]>,
+    /// The position of the pointer, in physical pixels relative to the window's client area.
+    pub position: Point,
+    /// Whether an element accepted the files.
+    accepted: Cell<bool>,
+}
+
+impl FileHovered {
+    /// Creates a new [`FileHovered`] event that no element accepted yet.
+    pub fn new(paths: Rc<[PathBuf]>, position: Point) -> Self {
+        Self {
+            paths,
+            position,
+            accepted: Cell::new(false),
+        }
+    }
+
+    /// Signals that the files would be accepted if they were dropped at the current position.
+    #[inline]
+    pub fn accept(&self) {
+        self.accepted.set(true);
+    }
+
+    /// Returns whether an element accepted the files.
+    #[inline]
+    pub fn is_accepted(&self) -> bool {
+        self.accepted.get()
+    }
+}
+
+/// The files that were being dragged over the window left it without being dropped.
+#[derive(Clone, Debug)]
+pub struct FileHoverCancelled;
+
+/// Files from the operating system have been dropped on the window.
+///
+/// When multiple files are dropped at once, a single event carrying all of their paths is
+/// dispatched rather than one event per file. The paths are in the order reported by the
+/// operating system.
+#[derive(Clone, Debug)]
+pub struct FileDropped {
+    /// The paths of the dropped files.
+    pub paths: Rc<[PathBuf]>,
+    /// The position of the pointer, in physical pixels relative to the window's client area.
+    pub position: Point,
+}
@@ -0,0 +1,137 @@
+use std::cell::{Cell, RefCell};
+
+/// A focusable element registered through [`CollectFocusTargets`].
+#[derive(Clone, Copy, Debug)]
+struct FocusTarget {
+    /// The tab index of the element.
+    tab_index: i32,
+    /// Whether the element currently has the focus.
+    focused: bool,
+    /// Whether the element requested the focus.
+    requested: bool,
+}
+
+/// An event dispatched by the window to find the elements that can receive the keyboard focus.
+///
+/// Focusable elements must call [`register`](Self::register) when they receive this event,
+/// and must then respond to [`MoveFocus`]. Both events are dispatched to the same tree, which
+/// means that elements are visited in the same order.
+#[derive(Debug, Default)]
+pub struct CollectFocusTargets {
+    /// The elements that have been registered so far, in tree order.
+    targets: RefCell<Vec<FocusTarget>>,
+    /// The tab index to assign to the elements being registered.
+    tab_index: Cell<i32>,
+}
+
+impl CollectFocusTargets {
+    /// Registers a focusable element.
+    pub fn register(&self, focused: bool) {
+        self.targets.borrow_mut().push(FocusTarget {
+            tab_index: self.tab_index.get(),
+            focused,
+            requested: false,
+        });
+    }
+
+    /// Registers a focusable element that requested the focus.
+    ///
+    /// See [`ElemContext::request_focus`](crate::ElemContext::request_focus).
+    pub fn register_requested(&self) {
+        self.targets.borrow_mut().push(FocusTarget {
+            tab_index: self.tab_index.get(),
+            focused: false,
+            requested: true,
+        });
+    }
+
+    /// Returns the element that requested the focus, if any.
+    ///
+    /// When multiple elements requested the focus, the last one in tree order wins.
+    ///
+    /// # Returns
+    ///
+    /// The index of the element in registration order.
+    pub fn requested_target(&self) -> Option<usize> {
+        self.targets.borrow().iter().rposition(|t| t.requested)
+    }
+
+    /// Calls the provided function with a tab index override.
+    ///
+    /// Elements registered within the function are given the provided tab index.
+    pub fn with_tab_index<R>(&self, tab_index: i32, f: impl FnOnce() -> R) -> R {
+        let prev = self.tab_index.replace(tab_index);
+        let ret = f();
+        self.tab_index.set(prev);
+        ret
+    }
+
+    /// Returns the element that should receive the focus after the currently focused one.
+    ///
+    /// Elements are ordered by tab index, and elements with the same tab index are ordered
+    /// according to their position in the tree. Elements with a negative tab index are never
+    /// selected.
+    ///
+    /// # Parameters
+    ///
+    /// - `reverse`: whether to move the focus backwards (Shift+Tab) rather than forwards.
+    ///
+    /// # Returns
+    ///
+    /// The index of the element in registration order, if any focusable element was found.
+    pub fn next_target(&self, reverse: bool) -> Option<usize> {
+        let targets = self.targets.borrow();
+
+        let mut order: Vec<usize> = (0..targets.len())
+            .filter(|&i| targets[i].tab_index >= 0)
+            .collect();
+        // The sort is stable: elements with the same tab index stay in tree order.
+        order.sort_by_key(|&i| targets[i].tab_index);
+
+        if order.is_empty() {
+            return None;
+        }
+
+        let current = order.iter().position(|&i| targets[i].focused);
+        let next = match (current, reverse) {
+            (Some(pos), false) => (pos + 1) % order.len(),
+            (Some(pos), true) => (pos + order.len() - 1) % order.len(),
+            (None, false) => 0,
+            (None, true) => order.len() - 1,
+        };
+
+        Some(order[next])
+    }
+}
+
+/// An event dispatched by the window to move the keyboard focus to a specific element.
+///
+/// The target is identified by its registration order during the last [`CollectFocusTargets`]
+/// pass. Focusable elements must call [`next`](Self::next) exactly once when they receive this
+/// event (under the same conditions they registered), and take the focus if it returns `true`.
+/// Other focused elements must drop their focus.
+#[derive(Debug)]
+pub struct MoveFocus {
+    /// The index of the element that should receive the focus.
+    target: usize,
+    /// The number of focusable elements visited so far.
+    visited: Cell<usize>,
+}
+
+impl MoveFocus {
+    /// Creates a new [`MoveFocus`] event.
+    #[inline]
+    pub fn new(target: usize) -> Self {
+        Self {
+            target,
+            visited: Cell::new(0),
+        }
+    }
+
+    /// Visits the next focusable element, returning whether it should receive the focus.
+    pub fn next(&self) -> bool {
+        let index = self.visited.get();
+        self.visited.set(index + 1);
+        index == self.target
+    }
+}
@@ -1,4 +1,7 @@
-use {std::ops::Deref, winit::event::DeviceId};
+use winit::{
+    event::{DeviceId, ElementState},
+    keyboard::{Key, KeyLocation, ModifiersState, PhysicalKey, SmolStr},
+};
 
 /// An event that reports that the state of a keyboard key has changed.
 #[derive(Clone, Debug)]
@@ -9,15 +12,115 @@ pub struct KeyEvent {
     /// The ID of the device that generated the event.
     pub device_id: Option<DeviceId>,
 
-    /// The inner key event.
-    pub inner: winit::event::KeyEvent,
+    /// The physical location of the key on the keyboard, regardless of the layout.
+    pub physical_key: PhysicalKey,
+
+    /// The meaning of the key, taking the keyboard layout and the modifiers into account.
+    pub logical_key: Key,
+
+    /// The text produced by the key, if any.
+    pub text: Option<SmolStr>,
+
+    /// The location of the key on the keyboard, for keys that appear more than once.
+    pub location: KeyLocation,
+
+    /// Whether the key was pressed or released.
+    pub state: ElementState,
+
+    /// Whether the event was generated by the operating system's key repeat mechanism
+    /// (the key is being held down) rather than by a fresh key press.
+    ///
+    /// Text input should honor repeated key presses (holding a key inserts the character
+    /// multiple times), while shortcuts should ignore them and only trigger once per physical
+    /// key press. See [`is_initial_press`](Self::is_initial_press).
+    pub repeat: bool,
+
+    /// The keyboard modifiers that were pressed when the event was generated.
+    pub modifiers: ModifiersState,
 }
 
-impl Deref for KeyEvent {
-    type Target = winit::event::KeyEvent;
+impl KeyEvent {
+    /// Returns whether the event represents a key being pressed, including repeated presses
+    /// generated while the key is held down.
+    #[inline]
+    pub fn is_pressed(&self) -> bool {
+        self.state.is_pressed()
+    }
 
+    /// Returns whether the event represents a fresh key press, ignoring repeated presses
+    /// generated while the key is held down.
+    ///
+    /// This is what shortcuts should use to avoid firing multiple times.
     #[inline]
-    fn deref(&self) -> &Self::Target {
-        &self.inner
+    pub fn is_initial_press(&self) -> bool {
+        self.is_pressed() && !self.repeat
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::KeyEvent,
+        crate::{
+            Element, HeadlessRenderer, Shortcut,
+            elements::{interactive::InteractiveState, text_input},
+        },
+        std::{cell::Cell, rc::Rc},
+        winit::{
+            event::ElementState,
+            keyboard::{Key, KeyCode, KeyLocation, ModifiersState, PhysicalKey},
+        },
+    };
+
+    /// Creates an event for the A key, pressed without any modifier.
+    fn key_a(state: ElementState, repeat: bool) -> KeyEvent {
+        KeyEvent {
+            synthetic: false,
+            device_id: None,
+            physical_key: PhysicalKey::Code(KeyCode::KeyA),
+            logical_key: Key::Character("a".into()),
+            text: state.is_pressed().then(|| "a".into()),
+            location: KeyLocation::Standard,
+            state,
+            repeat,
+            modifiers: ModifiersState::empty(),
+        }
+    }
+
+    #[test]
+    fn repeated_press_inserts_twice_but_triggers_shortcut_once() {
+        let renderer = HeadlessRenderer::new();
+        let elem_context = renderer.elem_context();
+        let ctx = renderer.ctx();
+
+        let trigger_count = Rc::new(Cell::new(0));
+        let _shortcut = ctx
+            .register_shortcut(
+                Shortcut::new(ModifiersState::empty(), Key::Character("a".into())),
+                {
+                    let trigger_count = trigger_count.clone();
+                    move || trigger_count.set(trigger_count.get() + 1)
+                },
+            )
+            .unwrap();
+
+        let mut input = text_input();
+        input.state.insert(InteractiveState::FOCUS);
+
+        let events = [
+            key_a(ElementState::Pressed, false),
+            key_a(ElementState::Pressed, true),
+            key_a(ElementState::Released, false),
+        ];
+
+        // This is the order in which the event loop gives key events to the focused element
+        // and to the shortcuts of the application.
+        for event in &events {
+            input.event(&elem_context, event);
+            ctx.0.upgrade().unwrap().trigger_shortcut(event);
+        }
+
+        assert_eq!(input.value.text(), "aa");
+        assert_eq!(trigger_count.get(), 1);
     }
 }
@@ -6,6 +6,15 @@ pub use self::pointer::*;
 mod keyboard;
 pub use self::keyboard::*;
 
+mod file_drop;
+pub use self::file_drop::*;
+
+mod focus;
+pub use self::focus::*;
+
+mod clipboard;
+pub use self::clipboard::*;
+
 /// The result of an event.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum EventResult {
@@ -1,6 +1,9 @@
 use {
-    vello::kurbo::Point,
-    winit::event::{ButtonSource, DeviceId, ElementState, PointerKind, PointerSource},
+    vello::kurbo::{Point, Vec2},
+    winit::{
+        event::{ButtonSource, DeviceId, ElementState, PointerKind, PointerSource},
+        keyboard::ModifiersState,
+    },
 };
 
 /// Indicates that the pointer has moved over the window.
@@ -8,7 +11,7 @@ use {
 pub struct PointerMoved {
     /// The ID of the device that generated the event.
     pub device_id: Option<DeviceId>,
-    /// The new position of the pointer.
+    /// The new position of the pointer, in physical pixels relative to the window's client area.
     pub position: Point,
     /// Whether the pointer is the primary pointer.
     ///
@@ -19,6 +22,8 @@ pub struct PointerMoved {
     /// This can be used to differentiate between different kinds of pointers, like touchpads,
     /// mouses, or touchscreens.
     pub source: PointerSource,
+    /// The state of the keyboard modifiers when the event was generated.
+    pub modifiers: ModifiersState,
 }
 
 /// A pointer button has been pressed or released.
@@ -26,7 +31,8 @@ pub struct PointerMoved {
 pub struct PointerButton {
     /// The ID of the device that generated the event.
     pub device_id: Option<DeviceId>,
-    /// The position of the pointer at the time of the event.
+    /// The position of the pointer at the time of the event, in physical pixels relative to the
+    /// window's client area.
     pub position: Point,
     /// Whether the button was pressed or released.
     pub state: ElementState,
@@ -36,6 +42,17 @@ pub struct PointerButton {
     pub primary: bool,
     /// The button that was pressed or released.
     pub button: ButtonSource,
+    /// The number of consecutive clicks this event is part of.
+    ///
+    /// This is `1` for a simple click, `2` for a double-click, and so on. Presses of the same
+    /// button are counted together when they happen quickly enough and close enough to each
+    /// other (see [`Ctx::set_double_click_interval`]). Releases carry the count of the press
+    /// that preceded them.
+    ///
+    /// [`Ctx::set_double_click_interval`]: crate::Ctx::set_double_click_interval
+    pub click_count: u32,
+    /// The state of the keyboard modifiers when the event was generated.
+    pub modifiers: ModifiersState,
 }
 
 /// An event that indicates that the pointer has left or entered the window.
@@ -43,7 +60,8 @@ pub struct PointerButton {
 pub struct PointerEnetered {
     /// The ID of the device that generated the event.
     pub device_id: Option<DeviceId>,
-    /// The position of the pointer at the time of the event.
+    /// The position of the pointer at the time of the event, in physical pixels relative to the
+    /// window's client area.
     pub position: Point,
     /// Whether the pointer is the primary pointer.
     ///
@@ -65,3 +83,41 @@ pub struct PointerLeft {
     /// The kind of the pointer.
     pub kind: PointerKind,
 }
+
+/// The user scrolled with a mouse wheel or a touchpad.
+#[derive(Clone, Debug)]
+pub struct PointerScrolled {
+    /// The ID of the device that generated the event.
+    pub device_id: Option<DeviceId>,
+    /// The last known position of the pointer, in physical pixels relative to the window's
+    /// client area.
+    pub position: Point,
+    /// The distance scrolled, in physical pixels.
+    ///
+    /// Positive values mean that the user wants to see the content located to the left (for
+    /// `x`) or above (for `y`) of what is currently visible. Line-based scrolling (notably
+    /// mouse wheels) is converted to pixels.
+    pub delta: Vec2,
+    /// The state of the keyboard modifiers when the event was generated.
+    pub modifiers: ModifiersState,
+}
+
+/// The pointer entered the area of a [`HookHover`] element.
+///
+/// This event is dispatched to the child of the [`HookHover`] element only.
+///
+/// [`HookHover`]: crate::elements::hooks::HookHover
+#[derive(Clone, Debug)]
+pub struct PointerEnter {
+    /// The position of the pointer, in physical pixels relative to the window's client area.
+    pub position: Point,
+}
+
+/// The pointer left the area of a [`HookHover`] element.
+///
+/// This event is dispatched to the child of the [`HookHover`] element only. It is also
+/// dispatched when the pointer leaves the window while hovering the element.
+///
+/// [`HookHover`]: crate::elements::hooks::HookHover
+#[derive(Clone, Debug)]
+pub struct PointerLeave;
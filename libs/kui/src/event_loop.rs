@@ -4,18 +4,25 @@
 use {
     crate::{
         Ctx,
-        event::{KeyEvent, PointerButton, PointerEnetered, PointerLeft, PointerMoved},
+        event::{
+            ClipboardPaste, EventResult, FileDropped, FileHoverCancelled, KeyEvent, PointerButton,
+            PointerEnetered, PointerLeft, PointerMoved, PointerScrolled, is_paste_shortcut,
+        },
         private::CtxInner,
     },
     std::rc::Rc,
     winit::{
         application::ApplicationHandler,
-        event::{StartCause, WindowEvent},
+        event::{MouseScrollDelta, StartCause, WindowEvent},
         event_loop::{ActiveEventLoop, ControlFlow, EventLoop},
+        keyboard::NamedKey,
         window::WindowId,
     },
 };
 
+/// The distance scrolled by one line of a mouse wheel, in scaled pixels.
+const SCROLL_LINE_HEIGHT: f64 = 40.0;
+
 /// The signature of the initialization function that is called when the application initially
 /// starts.
 pub type InitFn<'a> = Box<dyn FnOnce(Ctx) + 'a>;
@@ -111,8 +118,16 @@ impl AppState {
         window_id: WindowId,
         event: WindowEvent,
     ) {
+        self.ctx.try_with_window(window_id, |window| {
+            window.process_accessibility_event(&event);
+        });
+
         self.ctx.set_active_event_loop(el, || match event {
             WindowEvent::CloseRequested => {
+                // The window is taken out of the context so that the handler is free to create
+                // or close windows.
+                let window = self.ctx.with_window(window_id, Rc::clone);
+                window.notify_close_requested();
                 el.exit();
             }
             WindowEvent::SurfaceResized(new_size) => {
@@ -139,6 +154,7 @@ impl AppState {
                     primary,
                     source,
                     position: physical_position_to_point(position),
+                    modifiers: window.keyboard_modifiers(),
                 });
             }),
             WindowEvent::PointerButton {
@@ -149,15 +165,39 @@ impl AppState {
                 button,
             } => {
                 self.ctx.with_window(window_id, |window| {
+                    let position = physical_position_to_point(position);
+                    let click_count = window.count_click(button, position, state.is_pressed());
                     window.dispatch_event(&PointerButton {
                         device_id,
                         state,
                         primary,
                         button,
-                        position: physical_position_to_point(position),
+                        position,
+                        click_count,
+                        modifiers: window.keyboard_modifiers(),
                     });
                 });
             }
+            WindowEvent::MouseWheel {
+                device_id, delta, ..
+            } => self.ctx.with_window(window_id, |window| {
+                let delta = match delta {
+                    MouseScrollDelta::LineDelta(x, y) => {
+                        vello::kurbo::Vec2::new(x as f64, y as f64)
+                            * (SCROLL_LINE_HEIGHT * window.scale_factor())
+                    }
+                    MouseScrollDelta::PixelDelta(delta) => {
+                        vello::kurbo::Vec2::new(delta.x, delta.y)
+                    }
+                };
+
+                window.dispatch_event(&PointerScrolled {
+                    device_id,
+                    position: physical_position_to_point(window.last_pointer_position()),
+                    delta,
+                    modifiers: window.keyboard_modifiers(),
+                });
+            }),
             WindowEvent::PointerLeft {
                 device_id,
                 position,
@@ -195,13 +235,85 @@ impl AppState {
                 device_id,
                 event,
                 is_synthetic,
-            } => self.ctx.with_window(window_id, |window| {
-                window.dispatch_event(&KeyEvent {
+            } => {
+                // The window is taken out of the context so that shortcuts are free to create
+                // or close windows.
+                let window = self.ctx.with_window(window_id, Rc::clone);
+                let is_tab = event.state.is_pressed() && event.logical_key == NamedKey::Tab;
+
+                // The paste shortcut is turned into a `ClipboardPaste` event. The key event is
+                // only dispatched if no element handled the paste (or if the clipboard contains
+                // no text).
+                if event.state.is_pressed()
+                    && is_paste_shortcut(&event.logical_key, window.keyboard_modifiers())
+                {
+                    let pasted = self.ctx.clipboard_text().is_some_and(|text| {
+                        window
+                            .dispatch_keyboard_event(&ClipboardPaste(text))
+                            .is_handled()
+                    });
+
+                    if pasted {
+                        return;
+                    }
+                }
+
+                let event = KeyEvent {
                     synthetic: is_synthetic,
                     device_id,
-                    inner: event,
+                    physical_key: event.physical_key,
+                    logical_key: event.logical_key,
+                    text: event.text,
+                    location: event.location,
+                    state: event.state,
+                    repeat: event.repeat,
+                    modifiers: window.keyboard_modifiers(),
+                };
+
+                // The focused element sees the key press first, then the shortcuts of the
+                // application, and finally the rest of the tree.
+                let mut result = window.dispatch_focus_pass(&event);
+                if !result.is_handled() && self.ctx.trigger_shortcut(&event) {
+                    result = EventResult::Handled;
+                }
+                if !result.is_handled() {
+                    result = window.dispatch_event(&event);
+                }
+
+                // Tab presses that are not handled by an element move the focus.
+                if is_tab && !result.is_handled() {
+                    window.move_focus(window.keyboard_modifiers().shift_key());
+                }
+            }
+            WindowEvent::DragEntered { paths, position } => {
+                self.ctx.with_window(window_id, |window| {
+                    window.set_hovered_files(Some(paths.into()));
+                    window.set_last_pointer_position(position);
+                    window.dispatch_file_hover(physical_position_to_point(position));
                 });
-            }),
+            }
+            WindowEvent::DragMoved { position } => {
+                self.ctx.with_window(window_id, |window| {
+                    window.set_last_pointer_position(position);
+                    window.dispatch_file_hover(physical_position_to_point(position));
+                });
+            }
+            WindowEvent::DragDropped { paths, position } => {
+                self.ctx.with_window(window_id, |window| {
+                    window.end_file_hover();
+                    window.set_last_pointer_position(position);
+                    window.dispatch_event(&FileDropped {
+                        paths: paths.into(),
+                        position: physical_position_to_point(position),
+                    });
+                });
+            }
+            WindowEvent::DragLeft { .. } => {
+                self.ctx.with_window(window_id, |window| {
+                    window.end_file_hover();
+                    window.dispatch_event(&FileHoverCancelled);
+                });
+            }
             WindowEvent::ModifiersChanged(modifiers) => {
                 self.ctx.with_window(window_id, |window| {
                     window.notify_keyboard_modifiers_changed(modifiers.state());
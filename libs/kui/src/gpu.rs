@@ -0,0 +1,97 @@
+use {vello::wgpu, winit::dpi::PhysicalSize};
+
+/// The information passed to a window's render hook.
+///
+/// See [`Window::set_render_hook`](crate::Window::set_render_hook) for more information.
+pub struct RenderHookContext<'a> {
+    /// The GPU device used to render the UI.
+    pub device: &'a wgpu::Device,
+    /// The queue used to submit commands to the GPU.
+    pub queue: &'a wgpu::Queue,
+    /// A view into the surface texture that is about to be presented.
+    ///
+    /// When the hook is called, this texture already contains the rendered UI.
+    pub target: &'a wgpu::TextureView,
+    /// The format of the target texture.
+    pub format: wgpu::TextureFormat,
+    /// The size of the target texture, in physical pixels.
+    pub size: PhysicalSize<u32>,
+}
+
+/// A function that is called after the UI of a window has been rendered, but before the frame
+/// is presented.
+pub type RenderHook = Box<dyn FnMut(&RenderHookContext)>;
+
+/// An image stored in CPU memory.
+///
+/// Pixels are stored row by row, in the RGBA8 format (non-premultiplied), without any padding
+/// between rows.
+#[derive(Clone)]
+pub struct ImageBuffer {
+    /// The width of the image, in pixels.
+    pub width: u32,
+    /// The height of the image, in pixels.
+    pub height: u32,
+    /// The pixels of the image.
+    ///
+    /// This contains exactly `width * height * 4` bytes.
+    pub data: Vec<u8>,
+}
+
+impl ImageBuffer {
+    /// Returns the RGBA value of the pixel at the provided coordinates.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the coordinates are out of bounds.
+    #[track_caller]
+    pub fn pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        assert!(x < self.width && y < self.height, "Pixel out of bounds");
+        let index = (y as usize * self.width as usize + x as usize) * 4;
+        self.data[index..index + 4].try_into().unwrap()
+    }
+}
+
+impl std::fmt::Debug for ImageBuffer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ImageBuffer")
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .finish_non_exhaustive()
+    }
+}
+
+/// An error that might occur when capturing the content of a window.
+#[derive(Debug)]
+pub enum CaptureError {
+    /// The target has a width or a height of zero.
+    EmptyTarget,
+    /// The renderer has not been initialized yet.
+    NoRenderer,
+    /// The scene could not be rendered.
+    Render(vello::Error),
+    /// The rendered image could not be read back from the GPU.
+    ReadBack(wgpu::BufferAsyncError),
+}
+
+impl std::fmt::Display for CaptureError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::EmptyTarget => f.write_str("The capture target is empty"),
+            Self::NoRenderer => f.write_str("The renderer has not been initialized"),
+            Self::Render(err) => write!(f, "Failed to render the scene: {err}"),
+            Self::ReadBack(err) => write!(f, "Failed to read the image back: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for CaptureError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::EmptyTarget => None,
+            Self::NoRenderer => None,
+            Self::Render(err) => Some(err),
+            Self::ReadBack(err) => Some(err),
+        }
+    }
+}
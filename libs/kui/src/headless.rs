@@ -0,0 +1,220 @@
+//! Rendering elements without a window or an event loop.
+//!
+//! This is mainly useful to test the layout and drawing code of elements.
+
+use {
+    crate::{
+        CaptureError, Ctx, ElemContext, Element, ImageBuffer, IntoElement, LayoutContext, Window,
+        private::{CtxInner, Renderer},
+    },
+    std::rc::{Rc, Weak},
+    vello::{
+        kurbo::{Point, Size},
+        peniko,
+    },
+    winit::dpi::PhysicalSize,
+};
+
+/// A renderer that draws elements to offscreen images.
+///
+/// # Remarks
+///
+/// Elements rendered through a [`HeadlessRenderer`] are given a real [`Ctx`] (which can be used
+/// to access resources and schedule callbacks), but no window. Elements that attempt to access
+/// their window will panic.
+///
+/// Timed callbacks scheduled through the context are never called automatically. Deferred
+/// functions (see [`Ctx::defer`]) are called after every call to
+/// [`render_element`](Self::render_element).
+pub struct HeadlessRenderer {
+    /// The context given to the rendered elements.
+    ctx: Rc<CtxInner>,
+    /// The renderer responsible for drawing the scenes.
+    ///
+    /// This is created lazily, so that elements can be laid out on machines that have no GPU
+    /// adapter.
+    renderer: Option<Renderer>,
+    /// The scene used to draw elements.
+    scene: vello::Scene,
+    /// The color used to clear the image before drawing.
+    base_color: peniko::Color,
+}
+
+impl HeadlessRenderer {
+    /// Creates a new [`HeadlessRenderer`].
+    ///
+    /// The GPU renderer is only created the first time an element is drawn.
+    pub fn new() -> Self {
+        Self {
+            ctx: Rc::new(CtxInner::default()),
+            renderer: None,
+            scene: vello::Scene::new(),
+            base_color: peniko::Color::TRANSPARENT,
+        }
+    }
+
+    /// Returns the context given to the rendered elements.
+    ///
+    /// This can be used to register resources (such as fonts) before rendering elements.
+    #[inline]
+    pub fn ctx(&self) -> Ctx {
+        Ctx(Rc::downgrade(&self.ctx))
+    }
+
+    /// Creates the [`ElemContext`] given to the rendered elements.
+    ///
+    /// This can be used to call the methods of an element manually (for example to check the
+    /// result of [`size_hint`](Element::size_hint)).
+    #[inline]
+    pub fn elem_context(&self) -> ElemContext {
+        ElemContext {
+            ctx: self.ctx(),
+            window: Window(Weak::new()),
+        }
+    }
+
+    /// Sets the color used to clear the image before drawing.
+    ///
+    /// By default, the image is cleared with a fully transparent color.
+    #[inline]
+    pub fn set_clear_color(&mut self, color: impl Into<peniko::Color>) {
+        self.base_color = color.into();
+    }
+
+    /// Lays out the provided element to fill an area of the provided size, and returns the
+    /// [`ElemContext`] and [`LayoutContext`] that were used.
+    ///
+    /// The size is expressed in physical pixels.
+    pub fn place_element(
+        &self,
+        elem: &mut dyn Element,
+        size: Size,
+        scale_factor: f64,
+    ) -> (ElemContext, LayoutContext) {
+        let elem_context = self.elem_context();
+        let layout_context = LayoutContext::root(size, scale_factor);
+
+        elem.place(&elem_context, layout_context, Point::ORIGIN, size);
+
+        (elem_context, layout_context)
+    }
+
+    /// Lays out and draws the provided element to an image.
+    ///
+    /// # Parameters
+    ///
+    /// - `elem`: The element to render. It is placed at the origin of the image and fills it
+    ///   entirely, just like the root element of a window.
+    ///
+    /// - `size`: The size of the image, in physical pixels.
+    ///
+    /// - `scale_factor`: The scale factor used to resolve the lengths of the element.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if no GPU adapter (hardware or software) is available.
+    pub fn render_element(
+        &mut self,
+        elem: &mut dyn Element,
+        size: Size,
+        scale_factor: f64,
+    ) -> Result<ImageBuffer, CaptureError> {
+        let (elem_context, _) = self.place_element(elem, size, scale_factor);
+
+        self.scene.reset();
+        elem.draw(&elem_context, &mut self.scene);
+
+        let renderer = self.renderer.get_or_insert_with(Renderer::new_headless);
+        let result = renderer.render_to_image(
+            &self.scene,
+            PhysicalSize::new(size.width.ceil() as u32, size.height.ceil() as u32),
+            self.base_color,
+        );
+
+        self.ctx.run_deferred();
+
+        result
+    }
+}
+
+impl Default for HeadlessRenderer {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Renders the provided element to an image, without requiring a window or an event loop.
+///
+/// This is a shorthand for creating a [`HeadlessRenderer`] and calling
+/// [`render_element`](HeadlessRenderer::render_element) on it. When rendering multiple elements,
+/// re-using the same [`HeadlessRenderer`] is much cheaper.
+///
+/// # Panics
+///
+/// This function panics if no GPU adapter (hardware or software) is available.
+pub fn render_element_to_image(
+    elem: impl IntoElement,
+    size: Size,
+    scale_factor: f64,
+) -> Result<ImageBuffer, CaptureError> {
+    let mut elem = elem.into_element();
+    let mut renderer = HeadlessRenderer::new();
+    elem.begin(&renderer.elem_context());
+    renderer.render_element(&mut elem, size, scale_factor)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::{HeadlessRenderer, render_element_to_image},
+        crate::{
+            elements::{Length, div, flex, flex_child},
+            test_utils::Probe,
+        },
+        vello::{
+            kurbo::{Rect, Size},
+            peniko::Color,
+        },
+    };
+
+    #[test]
+    fn flex_children_are_laid_out_headlessly() {
+        let (first, first_rect) = Probe::new(50.0, 20.0);
+        let (middle, middle_rect) = Probe::new(0.0, 20.0);
+        let (last, last_rect) = Probe::new(50.0, 20.0);
+
+        let mut root = flex()
+            .horizontal()
+            .gap(Length::Pixels(5.0))
+            .child(first)
+            .child(flex_child().grow(1.0).child(middle))
+            .child(last);
+
+        let renderer = HeadlessRenderer::new();
+        renderer.place_element(&mut root, Size::new(300.0, 100.0), 2.0);
+
+        // The gap is 10 physical pixels at a scale factor of 2, and the middle child takes up
+        // the remaining 300 - 50 - 50 - 2 * 10 = 180 pixels.
+        assert_eq!(first_rect.get(), Rect::new(0.0, 0.0, 50.0, 20.0));
+        assert_eq!(middle_rect.get(), Rect::new(60.0, 0.0, 240.0, 20.0));
+        assert_eq!(last_rect.get(), Rect::new(250.0, 0.0, 300.0, 20.0));
+    }
+
+    #[test]
+    #[ignore = "requires a GPU adapter"]
+    fn div_is_drawn_headlessly() {
+        let root = flex().child(
+            div()
+                .width(Length::Pixels(4.0))
+                .height(Length::Pixels(4.0))
+                .brush(Color::from_rgb8(0xff, 0x00, 0x00)),
+        );
+
+        let image = render_element_to_image(root, Size::new(8.0, 8.0), 1.0).unwrap();
+
+        assert_eq!((image.width, image.height), (8, 8));
+        assert_eq!(image.pixel(1, 1), [0xff, 0x00, 0x00, 0xff]);
+        assert_eq!(image.pixel(6, 6), [0x00, 0x00, 0x00, 0x00]);
+    }
+}
@@ -7,7 +7,7 @@
 #![feature(downcast_unchecked)]
 
 pub use {
-    parley,
+    accesskit, parley,
     vello::{
         self, kurbo,
         peniko::{self, color},
@@ -20,6 +20,7 @@ pub use kui_macros::*;
 
 mod private;
 
+pub mod accessibility;
 pub mod elements;
 pub mod event;
 pub mod event_loop;
@@ -30,9 +31,21 @@ pub use self::ctx::*;
 mod window;
 pub use self::window::*;
 
+mod shortcut;
+pub use self::shortcut::*;
+
 mod element;
 pub use self::element::*;
 
+mod gpu;
+pub use self::gpu::*;
+
+mod headless;
+pub use self::headless::*;
+
+#[cfg(test)]
+mod test_utils;
+
 /// Runs the Kui application.
 ///
 /// # Parameters
@@ -0,0 +1,289 @@
+//! The bridge between the accessibility tree of a window and the accessibility API of the
+//! platform (UI Automation on Windows, `NSAccessibility` on macOS and AT-SPI on Linux).
+
+use {
+    crate::{accessibility::AccessibilityAction, private::WindowProxyInner},
+    accesskit::{ActionHandler, ActionRequest, ActivationHandler, DeactivationHandler, TreeUpdate},
+    std::sync::Weak,
+    winit::{event::WindowEvent, event_loop::EventLoopProxy, window::Window as WinitWindow},
+};
+
+/// Answers assistive technologies connecting to a window.
+struct ActivationRequest {
+    /// The window being activated.
+    proxy: Weak<WindowProxyInner>,
+}
+
+impl ActivationHandler for ActivationRequest {
+    fn request_initial_tree(&mut self) -> Option<TreeUpdate> {
+        // The tree is only built while the window is drawn, because that's the only time where
+        // the layout of the elements is known to be up to date. The platform adapter provides a
+        // placeholder until then.
+        if let Some(proxy) = self.proxy.upgrade() {
+            proxy.winit_window().request_redraw();
+        }
+        None
+    }
+}
+
+/// Forwards the actions requested by assistive technologies to the UI tree of a window.
+///
+/// Depending on the platform, actions may be requested from another thread. They are sent to
+/// the window like the events of a [`WindowProxy`](crate::WindowProxy).
+struct ActionForwarder {
+    /// The window that the actions target.
+    proxy: Weak<WindowProxyInner>,
+    /// Used to wake the event loop up once an action has been queued.
+    event_loop_proxy: EventLoopProxy,
+}
+
+impl ActionHandler for ActionForwarder {
+    fn do_action(&mut self, request: ActionRequest) {
+        if let Some(proxy) = self.proxy.upgrade() {
+            proxy.send_event(Box::new(AccessibilityAction { request }));
+            self.event_loop_proxy.wake_up();
+        }
+    }
+}
+
+/// Ignores assistive technologies disconnecting from a window.
+///
+/// The tree is rebuilt every time the window is drawn anyway.
+struct Deactivation;
+
+impl DeactivationHandler for Deactivation {
+    fn deactivate_accessibility(&mut self) {}
+}
+
+/// The platform accessibility adapter of a window.
+pub struct AccessibilityAdapter {
+    /// The adapter provided by `accesskit` for the current platform.
+    inner: platform::Adapter,
+}
+
+impl AccessibilityAdapter {
+    /// Creates the accessibility adapter of the provided window.
+    ///
+    /// # Remarks
+    ///
+    /// On Windows and macOS, the adapter must be created before the window is shown for the
+    /// first time.
+    pub fn new(
+        window: &dyn WinitWindow,
+        proxy: Weak<WindowProxyInner>,
+        event_loop_proxy: EventLoopProxy,
+    ) -> Self {
+        let activation_handler = ActivationRequest {
+            proxy: proxy.clone(),
+        };
+        let action_handler = ActionForwarder {
+            proxy,
+            event_loop_proxy,
+        };
+
+        Self {
+            inner: platform::Adapter::new(window, activation_handler, action_handler, Deactivation),
+        }
+    }
+
+    /// Sends a new accessibility tree to the platform, if an assistive technology is listening.
+    ///
+    /// The provided function is only called in that case.
+    #[inline]
+    pub fn update_if_active(&mut self, update: impl FnOnce() -> TreeUpdate) {
+        self.inner.update_if_active(update);
+    }
+
+    /// Notifies the adapter of an event received by the window.
+    ///
+    /// Some platforms need to know about the position and the focus state of the window.
+    #[inline]
+    pub fn process_event(&mut self, window: &dyn WinitWindow, event: &WindowEvent) {
+        self.inner.process_event(window, event);
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use {
+        accesskit::{ActionHandler, ActivationHandler, DeactivationHandler, TreeUpdate},
+        accesskit_windows::{HWND, SubclassingAdapter},
+        winit::{
+            event::WindowEvent,
+            raw_window_handle::{HasWindowHandle, RawWindowHandle},
+            window::Window as WinitWindow,
+        },
+    };
+
+    pub struct Adapter(SubclassingAdapter);
+
+    impl Adapter {
+        pub fn new(
+            window: &dyn WinitWindow,
+            activation_handler: impl 'static + ActivationHandler,
+            action_handler: impl 'static + ActionHandler + Send,
+            _deactivation_handler: impl 'static + DeactivationHandler,
+        ) -> Self {
+            let hwnd = match window.window_handle().map(|handle| handle.as_raw()) {
+                Ok(RawWindowHandle::Win32(handle)) => handle.hwnd.get() as *mut _,
+                _ => unreachable!("Windows windows always have a Win32 handle"),
+            };
+
+            Self(SubclassingAdapter::new(
+                HWND(hwnd),
+                activation_handler,
+                action_handler,
+            ))
+        }
+
+        pub fn update_if_active(&mut self, update: impl FnOnce() -> TreeUpdate) {
+            if let Some(events) = self.0.update_if_active(update) {
+                events.raise();
+            }
+        }
+
+        pub fn process_event(&mut self, _window: &dyn WinitWindow, _event: &WindowEvent) {}
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use {
+        accesskit::{ActionHandler, ActivationHandler, DeactivationHandler, TreeUpdate},
+        accesskit_macos::SubclassingAdapter,
+        winit::{
+            event::WindowEvent,
+            raw_window_handle::{HasWindowHandle, RawWindowHandle},
+            window::Window as WinitWindow,
+        },
+    };
+
+    pub struct Adapter(SubclassingAdapter);
+
+    impl Adapter {
+        pub fn new(
+            window: &dyn WinitWindow,
+            activation_handler: impl 'static + ActivationHandler,
+            action_handler: impl 'static + ActionHandler,
+            _deactivation_handler: impl 'static + DeactivationHandler,
+        ) -> Self {
+            let view = match window.window_handle().map(|handle| handle.as_raw()) {
+                Ok(RawWindowHandle::AppKit(handle)) => handle.ns_view.as_ptr(),
+                _ => unreachable!("macOS windows always have an AppKit handle"),
+            };
+
+            // SAFETY: The view belongs to the window, which outlives the adapter.
+            Self(unsafe { SubclassingAdapter::new(view, activation_handler, action_handler) })
+        }
+
+        pub fn update_if_active(&mut self, update: impl FnOnce() -> TreeUpdate) {
+            if let Some(events) = self.0.update_if_active(update) {
+                events.raise();
+            }
+        }
+
+        pub fn process_event(&mut self, _window: &dyn WinitWindow, event: &WindowEvent) {
+            if let WindowEvent::Focused(focused) = event {
+                if let Some(events) = self.0.update_view_focus_state(*focused) {
+                    events.raise();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+mod platform {
+    use {
+        accesskit::{ActionHandler, ActivationHandler, DeactivationHandler, Rect, TreeUpdate},
+        winit::{event::WindowEvent, window::Window as WinitWindow},
+    };
+
+    pub struct Adapter(accesskit_unix::Adapter);
+
+    impl Adapter {
+        pub fn new(
+            _window: &dyn WinitWindow,
+            activation_handler: impl 'static + ActivationHandler + Send,
+            action_handler: impl 'static + ActionHandler + Send,
+            deactivation_handler: impl 'static + DeactivationHandler + Send,
+        ) -> Self {
+            Self(accesskit_unix::Adapter::new(
+                activation_handler,
+                action_handler,
+                deactivation_handler,
+            ))
+        }
+
+        pub fn update_if_active(&mut self, update: impl FnOnce() -> TreeUpdate) {
+            self.0.update_if_active(update);
+        }
+
+        pub fn process_event(&mut self, window: &dyn WinitWindow, event: &WindowEvent) {
+            match event {
+                WindowEvent::Moved(_) | WindowEvent::SurfaceResized(_) => {
+                    // AT-SPI expects the bounds of the window in screen coordinates.
+                    let outer_position = window.outer_position().unwrap_or_default();
+                    let outer_size = window.outer_size();
+                    let surface_position = window.surface_position();
+                    let surface_size = window.surface_size();
+
+                    let outer = Rect::from_origin_size(
+                        (outer_position.x as f64, outer_position.y as f64),
+                        (outer_size.width as f64, outer_size.height as f64),
+                    );
+                    let inner = Rect::from_origin_size(
+                        (
+                            (outer_position.x + surface_position.x) as f64,
+                            (outer_position.y + surface_position.y) as f64,
+                        ),
+                        (surface_size.width as f64, surface_size.height as f64),
+                    );
+                    self.0.set_root_window_bounds(outer, inner);
+                }
+                WindowEvent::Focused(focused) => self.0.update_window_focus_state(*focused),
+                _ => (),
+            }
+        }
+    }
+}
+
+#[cfg(not(any(
+    target_os = "windows",
+    target_os = "macos",
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+)))]
+mod platform {
+    use {
+        accesskit::{ActionHandler, ActivationHandler, DeactivationHandler, TreeUpdate},
+        winit::{event::WindowEvent, window::Window as WinitWindow},
+    };
+
+    /// Assistive technologies are not supported on this platform.
+    pub struct Adapter;
+
+    impl Adapter {
+        pub fn new(
+            _window: &dyn WinitWindow,
+            _activation_handler: impl 'static + ActivationHandler,
+            _action_handler: impl 'static + ActionHandler,
+            _deactivation_handler: impl 'static + DeactivationHandler,
+        ) -> Self {
+            Self
+        }
+
+        pub fn update_if_active(&mut self, _update: impl FnOnce() -> TreeUpdate) {}
+
+        pub fn process_event(&mut self, _window: &dyn WinitWindow, _event: &WindowEvent) {}
+    }
+}
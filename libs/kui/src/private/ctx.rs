@@ -1,6 +1,7 @@
 use {
     crate::{
-        CallbackId,
+        CallbackId, CaptureError, ImageBuffer, Shortcut, ShortcutConflict, ShortcutId,
+        event::KeyEvent,
         private::{ManagedSurface, Renderer, WindowInner},
     },
     rustc_hash::FxHashMap,
@@ -11,8 +12,9 @@ use {
         cell::{Cell, RefCell},
         ptr::NonNull,
         rc::Rc,
-        time::Instant,
+        time::{Duration, Instant},
     },
+    vello::wgpu,
     winit::{
         event_loop::{ActiveEventLoop, EventLoopProxy},
         window::{WindowAttributes, WindowId},
@@ -74,6 +76,17 @@ struct Callback {
     time: Instant,
 }
 
+/// A keyboard shortcut registered in the context.
+struct RegisteredShortcut {
+    /// The shortcut that triggers the callback.
+    shortcut: Shortcut,
+    /// The function called when the shortcut is triggered.
+    ///
+    /// It is reference-counted so that it can be called without holding a reference to the
+    /// `shortcuts` field, which the callback might want to modify.
+    callback: Rc<RefCell<dyn FnMut()>>,
+}
+
 /// Just a simple structure that holds the windows and the renderer.
 ///
 /// This avoids having multiple `RefCell` objects for stuff that will always be used together.
@@ -110,9 +123,41 @@ pub struct CtxInner {
     callbacks: RefCell<SlotMap<CallbackId, Callback>>,
     /// The time at which the next callback is scheduled to be called.
     next_callback_time: Cell<Option<Instant>>,
+    /// Functions to be called once the current frame has been rendered.
+    deferred: RefCell<Vec<Box<dyn FnOnce()>>>,
+
+    /// The keyboard shortcuts registered by the application.
+    shortcuts: RefCell<SlotMap<ShortcutId, RegisteredShortcut>>,
 
     /// Some global resources which may be used by the user.
     resources: RefCell<TypeMap>,
+
+    /// The system clipboard, created the first time it is accessed.
+    ///
+    /// It is kept alive because on some platforms, the content of the clipboard is lost when
+    /// the object that wrote to it is dropped.
+    clipboard: RefCell<Option<arboard::Clipboard>>,
+
+    /// The thresholds used to detect multiple clicks.
+    click_settings: Cell<ClickSettings>,
+}
+
+/// The thresholds used to group consecutive presses into multiple clicks.
+#[derive(Clone, Copy, Debug)]
+pub struct ClickSettings {
+    /// The maximum duration between two presses of the same click sequence.
+    pub interval: Duration,
+    /// The maximum distance between two presses of the same click sequence, in scaled pixels.
+    pub distance: f64,
+}
+
+impl Default for ClickSettings {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_millis(500),
+            distance: 4.0,
+        }
+    }
 }
 
 impl CtxInner {
@@ -183,8 +228,11 @@ impl CtxInner {
 
     /// Creates a new window and returns its ID.
     pub fn create_window(self: &Rc<Self>, window: WindowAttributes) -> Rc<WindowInner> {
+        // The accessibility adapter must be created before the window is shown for the first
+        // time.
+        let visible = window.visible;
         let window = self.with_active_event_loop(|el| {
-            el.create_window(window)
+            el.create_window(window.with_visible(false))
                 .unwrap_or_else(|err| panic!("Failed to create new window: {err}"))
         });
         let id = window.id();
@@ -203,6 +251,9 @@ impl CtxInner {
         };
 
         let window_inner = unsafe { Rc::new(WindowInner::new(self.clone(), surface, window)) };
+        if visible {
+            window_inner.proxy().winit_window().set_visible(true);
+        }
 
         windows.insert(id, window_inner.clone());
         window_inner
@@ -231,6 +282,36 @@ impl CtxInner {
             .get(&window_id)
             .unwrap()
             .render_scene(renderer.as_mut().unwrap(), scratch_scene);
+        drop(renderer_and_windows);
+
+        self.run_deferred();
+    }
+
+    /// Draws the content of a window and renders it to an image rather than to the window's
+    /// surface.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the window with the provided ID does not exist.
+    #[track_caller]
+    pub fn capture_window(&self, window_id: WindowId) -> Result<ImageBuffer, CaptureError> {
+        let window = self
+            .renderer_and_windows
+            .borrow()
+            .windows
+            .get(&window_id)
+            .expect("Window ID not found")
+            .clone();
+
+        let mut scene = vello::Scene::new();
+        window.draw_to_scene(&mut scene);
+
+        let mut renderer_and_windows = self.renderer_and_windows.borrow_mut();
+        let renderer = renderer_and_windows
+            .renderer
+            .as_mut()
+            .ok_or(CaptureError::NoRenderer)?;
+        window.render_scene_to_image(renderer, &scene)
     }
 
     /// Calls the provided function with a reference to the window with the provided ID.
@@ -248,6 +329,16 @@ impl CtxInner {
             .expect("Window ID not found"))
     }
 
+    /// Calls the provided function with a reference to the window with the provided ID, if it
+    /// exists.
+    pub fn try_with_window<R>(
+        &self,
+        id: WindowId,
+        f: impl FnOnce(&Rc<WindowInner>) -> R,
+    ) -> Option<R> {
+        self.renderer_and_windows.borrow().windows.get(&id).map(f)
+    }
+
     /// Removes a window from the context.
     ///
     /// # Returns
@@ -261,6 +352,20 @@ impl CtxInner {
             .is_some()
     }
 
+    /// Calls the provided function with the GPU device and queue used by the renderer.
+    ///
+    /// # Returns
+    ///
+    /// If the renderer has not been created yet (because no window has been created), this
+    /// function returns `None` without calling the function.
+    pub fn with_gpu<R>(&self, f: impl FnOnce(&wgpu::Device, &wgpu::Queue) -> R) -> Option<R> {
+        self.renderer_and_windows
+            .borrow()
+            .renderer
+            .as_ref()
+            .map(|renderer| f(renderer.device(), renderer.queue()))
+    }
+
     /// Dispatches pending events for all windows.
     pub fn dispatch_pending_events(&self) {
         self.renderer_and_windows
@@ -355,6 +460,97 @@ impl CtxInner {
         }
     }
 
+    /// Registers a function to be called once the current frame has been rendered.
+    pub fn defer(&self, callback: Box<dyn FnOnce()>) {
+        self.deferred.borrow_mut().push(callback);
+    }
+
+    /// Runs the functions registered through [`defer`](Self::defer).
+    ///
+    /// Functions registered while running the deferred functions are run as part of the same
+    /// call, in order.
+    pub fn run_deferred(&self) {
+        loop {
+            // Just like for regular callbacks, the lock must not be held while the functions
+            // are running because they might register new deferred functions.
+            let deferred = std::mem::take(&mut *self.deferred.borrow_mut());
+            if deferred.is_empty() {
+                break;
+            }
+            deferred.into_iter().for_each(|cb| cb());
+        }
+    }
+
+    //
+    // SHORTCUTS
+    //
+
+    /// Registers a keyboard shortcut.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the shortcut is already registered.
+    pub fn register_shortcut(
+        &self,
+        shortcut: Shortcut,
+        callback: Box<dyn FnMut()>,
+    ) -> Result<ShortcutId, ShortcutConflict> {
+        let mut shortcuts = self.shortcuts.borrow_mut();
+
+        if shortcuts.values().any(|s| s.shortcut == shortcut) {
+            return Err(ShortcutConflict(shortcut));
+        }
+
+        Ok(shortcuts.insert(RegisteredShortcut {
+            shortcut,
+            callback: Rc::new(RefCell::new(callback)),
+        }))
+    }
+
+    /// Unregisters a keyboard shortcut.
+    ///
+    /// # Returns
+    ///
+    /// This function returns whether the shortcut was successfully removed.
+    pub fn unregister_shortcut(&self, id: ShortcutId) -> bool {
+        self.shortcuts.borrow_mut().remove(id).is_some()
+    }
+
+    /// Calls the function of the shortcut triggered by the provided key event, if any.
+    ///
+    /// Shortcuts are only triggered by fresh key presses. Repeated presses generated while the
+    /// key is held down are ignored.
+    ///
+    /// # Returns
+    ///
+    /// This function returns whether a shortcut was triggered.
+    pub fn trigger_shortcut(&self, event: &KeyEvent) -> bool {
+        if !event.is_initial_press() {
+            return false;
+        }
+
+        let callback = self
+            .shortcuts
+            .borrow()
+            .values()
+            .find(|s| s.shortcut.matches(&event.logical_key, event.modifiers))
+            .map(|s| s.callback.clone());
+
+        // The lock is released before calling the function, which might register or
+        // unregister shortcuts.
+        match callback {
+            Some(callback) => match callback.try_borrow_mut() {
+                Ok(mut callback) => {
+                    (&mut *callback)();
+                    true
+                }
+                // The shortcut was triggered from within its own callback.
+                Err(_) => false,
+            },
+            None => false,
+        }
+    }
+
     /// Calls the provided function with the resources map.
     #[track_caller]
     pub fn with_resources_mut<R>(&self, f: impl FnOnce(&mut TypeMap) -> R) -> R {
@@ -366,4 +562,46 @@ impl CtxInner {
     pub fn with_resources<R>(&self, f: impl FnOnce(&TypeMap) -> R) -> R {
         f(&self.resources.borrow())
     }
+
+    /// Returns the thresholds used to detect multiple clicks.
+    #[inline]
+    pub fn click_settings(&self) -> ClickSettings {
+        self.click_settings.get()
+    }
+
+    /// Sets the thresholds used to detect multiple clicks.
+    #[inline]
+    pub fn set_click_settings(&self, settings: ClickSettings) {
+        self.click_settings.set(settings);
+    }
+
+    //
+    // CLIPBOARD
+    //
+
+    /// Calls the provided function with the system clipboard, creating it if needed.
+    fn with_clipboard<R>(
+        &self,
+        f: impl FnOnce(&mut arboard::Clipboard) -> Result<R, arboard::Error>,
+    ) -> Result<R, arboard::Error> {
+        let mut clipboard = self.clipboard.borrow_mut();
+        let clipboard = match clipboard.as_mut() {
+            Some(clipboard) => clipboard,
+            None => clipboard.insert(arboard::Clipboard::new()?),
+        };
+        f(clipboard)
+    }
+
+    /// Replaces the content of the system clipboard with the provided text.
+    pub fn set_clipboard_text(&self, text: String) -> Result<(), arboard::Error> {
+        self.with_clipboard(|clipboard| clipboard.set_text(text))
+    }
+
+    /// Returns the text currently stored in the system clipboard.
+    ///
+    /// If the clipboard is not available, or if it does not contain valid UTF-8 text, this
+    /// function returns `None`.
+    pub fn clipboard_text(&self) -> Option<String> {
+        self.with_clipboard(|clipboard| clipboard.get_text()).ok()
+    }
 }
@@ -1,4 +1,5 @@
 use {
+    crate::{CaptureError, ImageBuffer, RenderHookContext},
     pollster::FutureExt,
     std::cell::Cell,
     vello::{peniko, wgpu},
@@ -56,6 +57,60 @@ impl Renderer {
             .block_on()
             .unwrap_or_else(|| panic!("Failed to find a suitable GPU adapter"));
 
+        let output_format = *surface
+            .surface
+            .get_capabilities(&adapter)
+            .formats
+            .iter()
+            .find(|&&f| is_format_supported_by_vello(f))
+            .unwrap();
+
+        (
+            Self::from_adapter(instance, adapter, output_format, true),
+            surface,
+        )
+    }
+
+    /// Creates a new [`Renderer`] that is not associated with any window.
+    ///
+    /// Such a renderer can only render to offscreen textures (see
+    /// [`render_to_image`](Self::render_to_image)).
+    ///
+    /// If no hardware adapter is available, a software fallback adapter is used if the platform
+    /// provides one.
+    pub fn new_headless() -> Self {
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
+            backends: wgpu::Backends::all(),
+            ..Default::default()
+        });
+
+        let request_adapter = |force_fallback_adapter| {
+            instance
+                .request_adapter(&wgpu::RequestAdapterOptions {
+                    power_preference: wgpu::PowerPreference::None,
+                    compatible_surface: None,
+                    force_fallback_adapter,
+                })
+                .block_on()
+        };
+
+        let adapter = request_adapter(false)
+            .or_else(|| request_adapter(true))
+            .unwrap_or_else(|| panic!("Failed to find a suitable GPU adapter"));
+
+        Self::from_adapter(instance, adapter, wgpu::TextureFormat::Rgba8Unorm, false)
+    }
+
+    /// Creates a new [`Renderer`] from an existing adapter.
+    ///
+    /// When `with_surface` is set, the renderer is able to render to surfaces of format
+    /// `output_format`.
+    fn from_adapter(
+        instance: wgpu::Instance,
+        adapter: wgpu::Adapter,
+        output_format: wgpu::TextureFormat,
+        with_surface: bool,
+    ) -> Self {
         let (device, queue) = adapter
             .request_device(
                 &wgpu::DeviceDescriptor {
@@ -67,33 +122,150 @@ impl Renderer {
             .block_on()
             .unwrap_or_else(|err| panic!("Failed to create device: {err}"));
 
-        let output_format = *surface
-            .surface
-            .get_capabilities(&adapter)
-            .formats
-            .iter()
-            .find(|&&f| is_format_supported_by_vello(f))
-            .unwrap();
-
         let vello_renderer = vello::Renderer::new(&device, vello::RendererOptions {
-            surface_format: Some(output_format),
+            surface_format: with_surface.then_some(output_format),
             use_cpu: false,
             antialiasing_support: vello::AaSupport::area_only(),
             num_init_threads: None,
         })
         .unwrap_or_else(|err| panic!("Failed to create the 2D renderer: {err}"));
 
-        (
-            Self {
-                instance,
-                adapter,
-                device,
-                queue,
-                output_format,
-                vello_renderer,
+        Self {
+            instance,
+            adapter,
+            device,
+            queue,
+            output_format,
+            vello_renderer,
+        }
+    }
+
+    /// Returns the GPU device used by the renderer.
+    #[inline]
+    pub fn device(&self) -> &wgpu::Device {
+        &self.device
+    }
+
+    /// Returns the queue used to submit commands to the GPU.
+    #[inline]
+    pub fn queue(&self) -> &wgpu::Queue {
+        &self.queue
+    }
+
+    /// Renders the provided scene to an offscreen texture and reads it back into CPU memory.
+    ///
+    /// This function blocks until the GPU has finished rendering the scene.
+    pub fn render_to_image(
+        &mut self,
+        scene: &vello::Scene,
+        size: PhysicalSize<u32>,
+        base_color: peniko::Color,
+    ) -> Result<ImageBuffer, CaptureError> {
+        if size.width == 0 || size.height == 0 {
+            return Err(CaptureError::EmptyTarget);
+        }
+
+        let extent = wgpu::Extent3d {
+            width: size.width,
+            height: size.height,
+            depth_or_array_layers: 1,
+        };
+
+        //
+        // Render the scene to an offscreen texture.
+        //
+
+        let texture = self.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Capture Texture"),
+            size: extent,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::STORAGE_BINDING | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        self.vello_renderer
+            .render_to_texture(&self.device, &self.queue, scene, &view, &vello::RenderParams {
+                base_color,
+                width: size.width,
+                height: size.height,
+                antialiasing_method: vello::AaConfig::Area,
+            })
+            .map_err(CaptureError::Render)?;
+
+        //
+        // Copy the texture to a buffer that can be read from the CPU.
+        //
+        // Rows of the buffer must be aligned to `COPY_BYTES_PER_ROW_ALIGNMENT`, meaning that
+        // the buffer might be a bit larger than the actual image.
+        //
+
+        let unpadded_row_size = size.width * 4;
+        let padded_row_size = unpadded_row_size.next_multiple_of(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT);
+
+        let buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Capture Buffer"),
+            size: padded_row_size as u64 * size.height as u64,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Capture Encoder"),
+            });
+        encoder.copy_texture_to_buffer(
+            wgpu::TexelCopyTextureInfo {
+                texture: &texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
             },
-            surface,
-        )
+            wgpu::TexelCopyBufferInfo {
+                buffer: &buffer,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_row_size),
+                    rows_per_image: None,
+                },
+            },
+            extent,
+        );
+        self.queue.submit([encoder.finish()]);
+
+        //
+        // Read the buffer back, removing the padding.
+        //
+
+        let slice = buffer.slice(..);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+        self.device.poll(wgpu::Maintain::Wait);
+        receiver
+            .recv()
+            .unwrap_or(Err(wgpu::BufferAsyncError))
+            .map_err(CaptureError::ReadBack)?;
+
+        let mut data = Vec::with_capacity(unpadded_row_size as usize * size.height as usize);
+        {
+            let mapped = slice.get_mapped_range();
+            for row in mapped.chunks_exact(padded_row_size as usize) {
+                data.extend_from_slice(&row[..unpadded_row_size as usize]);
+            }
+        }
+        buffer.unmap();
+
+        Ok(ImageBuffer {
+            width: size.width,
+            height: size.height,
+            data,
+        })
     }
 }
 
@@ -108,8 +280,12 @@ pub struct ManagedSurface {
 
     /// The cached size of the window/surface.
     size: Cell<PhysicalSize<u32>>,
-    /// The present mode to be used when presenting the surface.
+    /// The present mode requested by the user.
     present_mode: Cell<wgpu::PresentMode>,
+    /// The present mode that was actually used the last time the surface was configured.
+    ///
+    /// This might differ from `present_mode` when the requested mode is not supported.
+    effective_present_mode: Cell<wgpu::PresentMode>,
     /// Whether the surface is dirty and needs to be redrawn.
     surface_dirty: Cell<bool>,
     /// The color to use when clearing the surface.
@@ -140,6 +316,7 @@ impl ManagedSurface {
             surface,
             size: Cell::new(size),
             present_mode: Cell::new(wgpu::PresentMode::AutoVsync),
+            effective_present_mode: Cell::new(wgpu::PresentMode::AutoVsync),
             surface_dirty: Cell::new(true),
             base_color: Cell::new(peniko::Color::BLACK),
         }
@@ -189,14 +366,76 @@ impl ManagedSurface {
         self.surface_dirty.set(true);
     }
 
+    /// Returns the present mode that was used the last time the surface was configured.
+    #[inline]
+    pub fn effective_present_mode(&self) -> wgpu::PresentMode {
+        self.effective_present_mode.get()
+    }
+
+    /// Picks the present mode to use for the surface.
+    ///
+    /// If the requested present mode is not supported by the adapter, this function falls back
+    /// to the closest supported mode.
+    fn select_present_mode(&self, renderer: &Renderer) -> wgpu::PresentMode {
+        use wgpu::PresentMode::*;
+
+        let requested = self.present_mode.get();
+
+        // Those are always supported because `wgpu` resolves them to a supported mode itself.
+        if matches!(requested, AutoVsync | AutoNoVsync | Fifo) {
+            return requested;
+        }
+
+        let supported = self
+            .surface
+            .get_capabilities(&renderer.adapter)
+            .present_modes;
+
+        if supported.contains(&requested) {
+            return requested;
+        }
+
+        // `Mailbox` and `Immediate` both avoid waiting for the vertical blank. When one is not
+        // available, the other is the next best thing.
+        let fallback = match requested {
+            Mailbox => Immediate,
+            Immediate => Mailbox,
+            FifoRelaxed => Fifo,
+            _ => AutoVsync,
+        };
+
+        if supported.contains(&fallback) {
+            fallback
+        } else if matches!(requested, Mailbox | Immediate) {
+            AutoNoVsync
+        } else {
+            AutoVsync
+        }
+    }
+
     /// Sets the base color to use when clearing the surface.
     #[inline]
     pub fn set_base_color(&self, color: peniko::Color) {
         self.base_color.set(color);
     }
 
+    /// Returns the base color used when clearing the surface.
+    #[inline]
+    pub fn base_color(&self) -> peniko::Color {
+        self.base_color.get()
+    }
+
     /// Renders the provided scene to the surface.
-    pub fn render(&self, window: &dyn Window, renderer: &mut Renderer, scene: &vello::Scene) {
+    ///
+    /// If a render hook is provided, it is called after the scene has been rendered to the
+    /// surface texture, right before the frame is presented.
+    pub fn render(
+        &self,
+        window: &dyn Window,
+        renderer: &mut Renderer,
+        scene: &vello::Scene,
+        render_hook: Option<&mut dyn FnMut(&RenderHookContext)>,
+    ) {
         let size = self.size.get();
 
         if size.width == 0 || size.height == 0 {
@@ -204,13 +443,16 @@ impl ManagedSurface {
         }
 
         if self.surface_dirty.replace(false) {
+            let present_mode = self.select_present_mode(renderer);
+            self.effective_present_mode.set(present_mode);
+
             self.surface
                 .configure(&renderer.device, &wgpu::SurfaceConfiguration {
                     usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
                     format: renderer.output_format,
                     width: size.width,
                     height: size.height,
-                    present_mode: self.present_mode.get(),
+                    present_mode,
                     desired_maximum_frame_latency: 1,
                     alpha_mode: wgpu::CompositeAlphaMode::Auto,
                     view_formats: vec![],
@@ -239,6 +481,19 @@ impl ManagedSurface {
             )
             .unwrap_or_else(|err| panic!("Failed to render to surface: {err}"));
 
+        if let Some(render_hook) = render_hook {
+            let target = frame
+                .texture
+                .create_view(&wgpu::TextureViewDescriptor::default());
+            render_hook(&RenderHookContext {
+                device: &renderer.device,
+                queue: &renderer.queue,
+                target: &target,
+                format: renderer.output_format,
+                size,
+            });
+        }
+
         window.pre_present_notify();
         frame.present();
     }
@@ -6,3 +6,6 @@ pub use self::window::*;
 
 mod gfx;
 pub use self::gfx::*;
+
+mod accessibility;
+pub use self::accessibility::*;
@@ -1,19 +1,29 @@
 use {
     crate::{
-        Ctx, ElemContext, LayoutContext, Window,
+        CaptureError, Ctx, ElemContext, ImageBuffer, LayoutContext, PopupId, RenderHook, Window,
+        accessibility::{AccessibilityHandler, build_accessibility_tree},
         element::Element,
-        event::{Event, EventResult},
-        private::{CtxInner, ManagedSurface, Renderer},
+        elements::{
+            focusable::FocusPass,
+            layer::{Layer, LayerPass},
+        },
+        event::{
+            CollectFocusTargets, Event, EventResult, FileHovered, KeyEvent, MoveFocus,
+            PointerButton, PointerMoved, PointerScrolled,
+        },
+        private::{AccessibilityAdapter, CtxInner, ManagedSurface, Renderer},
     },
     core::f64,
     parking_lot::Mutex,
     std::{
-        cell::Cell,
+        cell::{Cell, RefCell},
+        path::PathBuf,
         rc::Rc,
         sync::{
             Arc,
             atomic::{AtomicBool, Ordering},
         },
+        time::Instant,
     },
     vello::{
         kurbo::{self, Point},
@@ -21,8 +31,9 @@ use {
     },
     winit::{
         dpi::{PhysicalPosition, PhysicalSize},
-        keyboard::ModifiersState,
-        window::Window as WinitWindow,
+        event::{ButtonSource, WindowEvent},
+        keyboard::{ModifiersState, NamedKey},
+        window::{CursorIcon, Window as WinitWindow},
     },
 };
 
@@ -58,6 +69,143 @@ impl WindowProxyInner {
     }
 }
 
+/// The state of a focus request made through [`ElemContext::request_focus`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FocusRequest {
+    /// No element requested the focus.
+    #[default]
+    None,
+    /// An element requested the focus, but no [`Focusable`] ancestor claimed the request yet.
+    ///
+    /// [`Focusable`]: crate::elements::focusable::Focusable
+    Pending,
+    /// A [`Focusable`] element claimed the request. The focus will be moved to it once the
+    /// current event has been dispatched.
+    ///
+    /// [`Focusable`]: crate::elements::focusable::Focusable
+    Claimed,
+}
+
+/// The last press of a pointer button, used to detect multiple clicks.
+#[derive(Debug)]
+struct LastClick {
+    /// The button that was pressed.
+    button: ButtonSource,
+    /// The time at which the button was pressed.
+    time: Instant,
+    /// The position of the pointer when the button was pressed.
+    position: Point,
+    /// The number of consecutive clicks that the press was part of.
+    count: u32,
+}
+
+/// A popup opened with [`Window::open_popup`].
+struct OpenPopup {
+    /// The identifier of the popup.
+    id: PopupId,
+    /// The requested position of the top-left corner of the popup.
+    position: Point,
+    /// The element displayed in the popup.
+    element: Box<dyn Element>,
+    /// The area covered by the popup, computed when it is placed.
+    rect: kurbo::Rect,
+    /// Whether the popup must be placed before it is drawn.
+    needs_layout: bool,
+}
+
+impl OpenPopup {
+    /// Computes the layout of the popup.
+    ///
+    /// The popup takes its preferred size, and is shifted so that it stays within the window
+    /// as much as possible.
+    fn place(
+        &mut self,
+        elem_context: &ElemContext,
+        layout_context: LayoutContext,
+        window_size: kurbo::Size,
+    ) {
+        let hint = self
+            .element
+            .size_hint(elem_context, layout_context, window_size);
+        let size = hint.preferred.clamp(hint.min, hint.max);
+        let size = kurbo::Size::new(
+            size.width.min(window_size.width),
+            size.height.min(window_size.height),
+        );
+
+        let position = Point::new(
+            self.position.x.min(window_size.width - size.width).max(0.0),
+            self.position
+                .y
+                .min(window_size.height - size.height)
+                .max(0.0),
+        );
+
+        self.element
+            .place(elem_context, layout_context, position, size);
+        self.rect = kurbo::Rect::from_origin_size(position, size);
+        self.needs_layout = false;
+    }
+}
+
+/// A slot holding a user-defined hook that may replace or clear itself while it runs.
+struct HookSlot<T> {
+    /// The hook, if any.
+    ///
+    /// This is `None` while the hook is running.
+    hook: Cell<Option<T>>,
+    /// Incremented every time the hook is replaced or cleared.
+    ///
+    /// This is used to know whether the hook must be put back in the slot once it has run.
+    generation: Cell<u64>,
+}
+
+impl<T> HookSlot<T> {
+    /// Creates a new empty [`HookSlot`].
+    const fn new() -> Self {
+        Self {
+            hook: Cell::new(None),
+            generation: Cell::new(0),
+        }
+    }
+
+    /// Replaces or clears the hook.
+    fn set(&self, hook: Option<T>) {
+        self.hook.set(hook);
+        self.generation.set(self.generation.get().wrapping_add(1));
+    }
+
+    /// Takes the hook out of the slot and calls `f` with it.
+    ///
+    /// Once `f` returns, the hook is put back in the slot, unless it was replaced or cleared
+    /// in the meantime.
+    fn call<R>(&self, f: impl FnOnce(&mut Option<T>) -> R) -> R {
+        let generation = self.generation.get();
+        let mut hook = self.hook.take();
+
+        let ret = f(&mut hook);
+
+        if self.generation.get() == generation {
+            self.hook.set(hook);
+        }
+
+        ret
+    }
+}
+
+/// Returns the position of the pointer carried by the provided event, if it is a pointer event.
+fn pointer_event_position(event: &dyn Event) -> Option<Point> {
+    if let Some(ev) = event.downcast_ref::<PointerButton>() {
+        Some(ev.position)
+    } else if let Some(ev) = event.downcast_ref::<PointerMoved>() {
+        Some(ev.position)
+    } else {
+        event
+            .downcast_ref::<PointerScrolled>()
+            .map(|ev| ev.position)
+    }
+}
+
 /// The inner state associated with a window.
 pub struct WindowInner {
     /// The context that owns the window.
@@ -75,6 +223,54 @@ pub struct WindowInner {
     last_pointer_position: Cell<PhysicalPosition<f64>>,
     /// The state of the keyboard modifiers for the window.
     keyboard_modifiers: Cell<ModifiersState>,
+    /// The paths of the files currently being dragged over the window, if any.
+    hovered_files: RefCell<Option<Rc<[PathBuf]>>>,
+    /// The last press of a pointer button.
+    last_click: RefCell<Option<LastClick>>,
+
+    /// Whether the closest [`Focusable`] ancestor of the element currently being visited has
+    /// the focus.
+    ///
+    /// [`Focusable`]: crate::elements::focusable::Focusable
+    focus_scope: Cell<bool>,
+    /// The focus request made during the current event dispatch, if any.
+    focus_request: Cell<FocusRequest>,
+
+    /// A user-defined function called after the UI has been rendered to the window's surface.
+    render_hook: HookSlot<RenderHook>,
+    /// The scenes in which the elements of elevated layers are drawn.
+    ///
+    /// They are appended to the main scene once the whole tree has been drawn.
+    layer_scenes: [RefCell<vello::Scene>; Layer::ELEVATED.len()],
+    /// Whether each elevated layer contained at least one element during the last frame.
+    active_layers: Cell<[bool; Layer::ELEVATED.len()]>,
+
+    /// The popups displayed on top of the root element, from bottom to top.
+    ///
+    /// This list is taken out of the window while the popups are drawn or handle an event.
+    /// Popups opened in the meantime are pushed to the (temporarily empty) list and moved on top
+    /// of the others once the popups are put back.
+    popups: RefCell<Vec<OpenPopup>>,
+    /// Whether the popups are currently taken out of the window.
+    popups_in_use: Cell<bool>,
+    /// The popups that must be closed once the popups are put back.
+    closed_popups: RefCell<Vec<PopupId>>,
+    /// Whether all popups must be closed once the popups are put back.
+    close_all_popups: Cell<bool>,
+    /// The identifier of the next popup to be opened.
+    next_popup_id: Cell<u64>,
+
+    /// A user-defined function receiving the accessibility tree of the window every time it is
+    /// drawn.
+    accessibility_handler: HookSlot<AccessibilityHandler>,
+    /// Exposes the accessibility tree of the window to the assistive technologies of the
+    /// platform.
+    ///
+    /// This must be dropped before the window itself.
+    accessibility_adapter: RefCell<AccessibilityAdapter>,
+
+    /// A user-defined function called when the user requests the window to be closed.
+    close_handler: HookSlot<Box<dyn FnMut()>>,
 
     /// The pending events that need to be dispatched to the window.
     proxy: Arc<WindowProxyInner>,
@@ -93,6 +289,17 @@ impl WindowInner {
     ) -> Self {
         let scale_factor = window.scale_factor();
 
+        let proxy = Arc::new(WindowProxyInner {
+            pending_events: Mutex::new(Vec::new()),
+            recompute_layout: AtomicBool::new(false),
+            window,
+        });
+        let accessibility_adapter = AccessibilityAdapter::new(
+            proxy.winit_window(),
+            Arc::downgrade(&proxy),
+            ctx.event_loop_proxy(),
+        );
+
         Self {
             ctx,
             surface: managed_surface,
@@ -100,11 +307,22 @@ impl WindowInner {
             scale_factor: Cell::new(scale_factor),
             last_pointer_position: Cell::new(PhysicalPosition::new(f64::INFINITY, f64::INFINITY)),
             keyboard_modifiers: Cell::new(ModifiersState::empty()),
-            proxy: Arc::new(WindowProxyInner {
-                pending_events: Mutex::new(Vec::new()),
-                recompute_layout: AtomicBool::new(false),
-                window,
-            }),
+            hovered_files: RefCell::new(None),
+            last_click: RefCell::new(None),
+            focus_scope: Cell::new(false),
+            focus_request: Cell::new(FocusRequest::None),
+            render_hook: HookSlot::new(),
+            accessibility_handler: HookSlot::new(),
+            accessibility_adapter: RefCell::new(accessibility_adapter),
+            close_handler: HookSlot::new(),
+            layer_scenes: Default::default(),
+            active_layers: Cell::new([false; Layer::ELEVATED.len()]),
+            popups: RefCell::new(Vec::new()),
+            popups_in_use: Cell::new(false),
+            closed_popups: RefCell::new(Vec::new()),
+            close_all_popups: Cell::new(false),
+            next_popup_id: Cell::new(0),
+            proxy,
         }
     }
 
@@ -170,30 +388,389 @@ impl WindowInner {
     pub fn draw_to_scene(self: &Rc<Self>, scene: &mut vello::Scene) {
         let elem_context = self.make_elem_context();
 
+        let size = self.surface.cached_size();
+        let size = kurbo::Size::new(size.width as f64, size.height as f64);
+        let layout_context = LayoutContext::root(size, self.scale_factor.get());
+        let relayout = self.proxy.recompute_layout.swap(false, Ordering::Acquire);
+
         self.with_root_element(|elem| {
-            if self.proxy.recompute_layout.swap(false, Ordering::Acquire) {
-                let size = self.surface.cached_size();
-                let size = kurbo::Size::new(size.width as f64, size.height as f64);
-                elem.place(
-                    &elem_context,
-                    LayoutContext {
-                        parent: size,
-                        scale_factor: self.scale_factor.get(),
-                    },
-                    Point::ORIGIN,
-                    size,
-                );
+            if relayout {
+                elem.place(&elem_context, layout_context, Point::ORIGIN, size);
             }
 
             scene.reset();
+            self.layer_scenes
+                .iter()
+                .for_each(|layer_scene| layer_scene.borrow_mut().reset());
+            self.active_layers.set([false; Layer::ELEVATED.len()]);
+
             elem.draw(&elem_context, scene);
+
+            for (layer_scene, active) in self.layer_scenes.iter().zip(self.active_layers.get()) {
+                if active {
+                    scene.append(&layer_scene.borrow(), None);
+                }
+            }
+
+            self.with_popups(|popups| {
+                for popup in popups {
+                    if relayout || popup.needs_layout {
+                        popup.place(&elem_context, layout_context, size);
+                    }
+                    popup.element.draw(&elem_context, scene);
+                }
+            });
+
+            // The tree is only built if someone is listening, and at most once.
+            let mut tree = None;
+            self.accessibility_handler.call(|handler| {
+                if let Some(handler) = handler {
+                    handler(
+                        tree.get_or_insert_with(|| self.build_accessibility_tree(elem))
+                            .clone(),
+                    );
+                }
+            });
+            self.accessibility_adapter
+                .borrow_mut()
+                .update_if_active(|| tree.unwrap_or_else(|| self.build_accessibility_tree(elem)));
         });
     }
 
+    /// Builds the accessibility tree of the provided root element.
+    fn build_accessibility_tree(&self, root: &dyn Element) -> accesskit::TreeUpdate {
+        let size = self.surface.cached_size();
+        build_accessibility_tree(
+            root,
+            &self.proxy.window.title(),
+            kurbo::Rect::new(0.0, 0.0, size.width as f64, size.height as f64),
+        )
+    }
+
+    /// Builds the accessibility tree of the window.
+    pub fn accessibility_tree(&self) -> accesskit::TreeUpdate {
+        self.with_root_element(|elem| self.build_accessibility_tree(elem))
+    }
+
+    /// Sets the function receiving the accessibility tree of the window.
+    pub fn set_accessibility_handler(&self, handler: Option<AccessibilityHandler>) {
+        self.accessibility_handler.set(handler);
+    }
+
+    /// Notifies the accessibility adapter of the window of an event received by the window.
+    #[inline]
+    pub fn process_accessibility_event(&self, event: &WindowEvent) {
+        self.accessibility_adapter
+            .borrow_mut()
+            .process_event(self.proxy.window.as_ref(), event);
+    }
+
+    /// Sets the function called when the user requests the window to be closed.
+    pub fn set_close_handler(&self, handler: Option<Box<dyn FnMut()>>) {
+        self.close_handler.set(handler);
+    }
+
+    /// Notifies the window that the user requested it to be closed.
+    pub fn notify_close_requested(&self) {
+        // The handler may replace or clear itself while it runs.
+        self.close_handler.call(|handler| {
+            if let Some(handler) = handler {
+                handler();
+            }
+        });
+    }
+
+    /// Calls the provided function with the scene associated with an elevated layer.
+    ///
+    /// # Returns
+    ///
+    /// Returns `false` without calling the function if `layer` is [`Layer::Base`] or if the
+    /// scene of the layer is already in use.
+    pub fn with_layer_scene(&self, layer: Layer, f: &mut dyn FnMut(&mut vello::Scene)) -> bool {
+        let Some(index) = layer.elevated_index() else {
+            return false;
+        };
+        let Ok(mut layer_scene) = self.layer_scenes[index].try_borrow_mut() else {
+            return false;
+        };
+
+        let mut active_layers = self.active_layers.get();
+        active_layers[index] = true;
+        self.active_layers.set(active_layers);
+
+        f(&mut layer_scene);
+        true
+    }
+
+    /// Returns whether the provided layer contained at least one element during the last frame.
+    ///
+    /// [`Layer::Base`] is always active.
+    pub fn is_layer_active(&self, layer: Layer) -> bool {
+        match layer.elevated_index() {
+            Some(index) => self.active_layers.get()[index],
+            None => true,
+        }
+    }
+
+    /// Calls the provided function with the popups of the window.
+    ///
+    /// Popups opened while the function is running are placed on top of the others, and popups
+    /// closed while it is running are removed, once it returns.
+    fn with_popups<R>(&self, f: impl FnOnce(&mut Vec<OpenPopup>) -> R) -> R {
+        let mut popups = self.popups.take();
+        let was_in_use = self.popups_in_use.replace(true);
+        let ret = f(&mut popups);
+        self.popups_in_use.set(was_in_use);
+        popups.append(&mut self.popups.borrow_mut());
+
+        let len = popups.len();
+        if self.close_all_popups.take() {
+            popups.clear();
+        }
+        for id in std::mem::take(&mut *self.closed_popups.borrow_mut()) {
+            // Closing a popup also closes the ones that were stacked on top of it.
+            if let Some(index) = popups.iter().position(|popup| popup.id == id) {
+                popups.truncate(index);
+            }
+        }
+        if popups.len() != len {
+            self.proxy.window.request_redraw();
+        }
+
+        *self.popups.borrow_mut() = popups;
+        ret
+    }
+
+    /// Opens a popup displaying the provided element on top of the root element.
+    pub fn open_popup(self: &Rc<Self>, position: Point, mut element: Box<dyn Element>) -> PopupId {
+        let id = PopupId(self.next_popup_id.get());
+        self.next_popup_id.set(id.0 + 1);
+
+        element.begin(&self.make_elem_context());
+        self.popups.borrow_mut().push(OpenPopup {
+            id,
+            position,
+            element,
+            rect: kurbo::Rect::ZERO,
+            needs_layout: true,
+        });
+        self.proxy.window.request_redraw();
+
+        id
+    }
+
+    /// Closes the provided popup, along with the popups stacked on top of it.
+    ///
+    /// When the popups are in use, they are closed once they are put back.
+    pub fn close_popup(&self, id: PopupId) {
+        self.closed_popups.borrow_mut().push(id);
+        if !self.popups_in_use.get() {
+            self.with_popups(|_| ());
+        }
+    }
+
+    /// Closes all the popups of the window.
+    ///
+    /// When the popups are in use, they are closed once they are put back.
+    pub fn close_all_popups(&self) {
+        self.close_all_popups.set(true);
+        if !self.popups_in_use.get() {
+            self.with_popups(|_| ());
+        }
+    }
+
+    /// Dispatches an event to the popups of the window, from top to bottom.
+    ///
+    /// Pressing a pointer button outside of a popup closes it, and Escape closes the topmost
+    /// popup. Pointer events that happen over a popup never reach the root element.
+    fn dispatch_event_to_popups(
+        &self,
+        elem_context: &ElemContext,
+        event: &dyn Event,
+    ) -> EventResult {
+        self.with_popups(|popups| {
+            let Some(top) = popups.last() else {
+                return EventResult::Continue;
+            };
+            let top = top.id;
+
+            for popup in popups.iter_mut().rev() {
+                if popup.element.event(elem_context, event).is_handled() {
+                    return EventResult::Handled;
+                }
+            }
+
+            if let Some(position) = pointer_event_position(event) {
+                let hovered = popups
+                    .iter()
+                    .rposition(|popup| popup.rect.contains(position));
+
+                let pressed = event
+                    .downcast_ref::<PointerButton>()
+                    .is_some_and(|ev| ev.state.is_pressed());
+                if pressed {
+                    let first_closed = hovered.map_or(0, |index| index + 1);
+                    if let Some(popup) = popups.get(first_closed) {
+                        self.closed_popups.borrow_mut().push(popup.id);
+                    }
+                }
+
+                // Clicks outside of the popups are not consumed so that they can reach the
+                // element under the pointer.
+                return if hovered.is_some() {
+                    EventResult::Handled
+                } else {
+                    EventResult::Continue
+                };
+            }
+
+            let escape = event
+                .downcast_ref::<KeyEvent>()
+                .is_some_and(|ev| ev.is_initial_press() && ev.logical_key == NamedKey::Escape);
+            if escape {
+                self.closed_popups.borrow_mut().push(top);
+                return EventResult::Handled;
+            }
+
+            EventResult::Continue
+        })
+    }
+
+    /// Dispatches an event to the provided root element, giving popups and elevated layers a
+    /// chance to handle it first.
+    fn dispatch_event_to(
+        &self,
+        elem: &mut dyn Element,
+        elem_context: &ElemContext,
+        event: &dyn Event,
+    ) -> EventResult {
+        if self
+            .dispatch_event_to_popups(elem_context, event)
+            .is_handled()
+        {
+            return EventResult::Handled;
+        }
+
+        for layer in Layer::ELEVATED.into_iter().rev() {
+            if !self.is_layer_active(layer) {
+                continue;
+            }
+
+            if elem
+                .event(elem_context, &LayerPass::new(layer, event))
+                .is_handled()
+            {
+                return EventResult::Handled;
+            }
+        }
+
+        elem.event(elem_context, event)
+    }
+
     /// Dispatches an event to the window.
     pub fn dispatch_event(self: &Rc<Self>, event: &dyn Event) -> EventResult {
         let elem_context = self.make_elem_context();
-        self.with_root_element(|elem| elem.event(&elem_context, event))
+        let result =
+            self.with_root_element(|elem| self.dispatch_event_to(elem, &elem_context, event));
+        self.apply_focus_request();
+        result
+    }
+
+    /// Dispatches a keyboard event to the window.
+    ///
+    /// The event is first given to the focused [`Focusable`] element (and its descendants).
+    /// It is only dispatched to the rest of the tree if they did not handle it.
+    ///
+    /// [`Focusable`]: crate::elements::focusable::Focusable
+    pub fn dispatch_keyboard_event(self: &Rc<Self>, event: &dyn Event) -> EventResult {
+        if self.dispatch_focus_pass(event).is_handled() {
+            return EventResult::Handled;
+        }
+
+        self.dispatch_event(event)
+    }
+
+    /// Gives the focused element a chance to handle a keyboard event, without dispatching it
+    /// to the rest of the tree.
+    #[inline]
+    pub fn dispatch_focus_pass(self: &Rc<Self>, event: &dyn Event) -> EventResult {
+        self.dispatch_event(&FocusPass::new(event))
+    }
+
+    /// Calls the provided function within the scope of a [`Focusable`] element.
+    ///
+    /// # Parameters
+    ///
+    /// - `focused`: Whether the [`Focusable`] element has the focus.
+    ///
+    /// [`Focusable`]: crate::elements::focusable::Focusable
+    pub fn with_focus_scope<R>(&self, focused: bool, f: impl FnOnce() -> R) -> R {
+        let prev = self.focus_scope.replace(focused);
+        let ret = f();
+        self.focus_scope.set(prev);
+        ret
+    }
+
+    /// Returns whether the closest [`Focusable`] ancestor of the element currently being
+    /// visited has the focus.
+    ///
+    /// [`Focusable`]: crate::elements::focusable::Focusable
+    #[inline]
+    pub fn in_focus_scope(&self) -> bool {
+        self.focus_scope.get()
+    }
+
+    /// Requests the focus on behalf of the element currently being visited.
+    ///
+    /// The request must be claimed by a [`Focusable`] ancestor through
+    /// [`claim_focus_request`](Self::claim_focus_request), otherwise it is ignored.
+    ///
+    /// [`Focusable`]: crate::elements::focusable::Focusable
+    pub fn request_focus(&self) {
+        if self.focus_request.get() == FocusRequest::None {
+            self.focus_request.set(FocusRequest::Pending);
+        }
+    }
+
+    /// Claims the pending focus request, if any.
+    ///
+    /// # Returns
+    ///
+    /// Whether a request was pending. In that case, the caller must register itself with
+    /// [`CollectFocusTargets::register_requested`] during the next focus traversal.
+    pub fn claim_focus_request(&self) -> bool {
+        if self.focus_request.get() == FocusRequest::Pending {
+            self.focus_request.set(FocusRequest::Claimed);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Moves the focus to the element that claimed the last focus request, if any.
+    fn apply_focus_request(self: &Rc<Self>) {
+        if self.focus_request.take() != FocusRequest::Claimed {
+            return;
+        }
+
+        let targets = CollectFocusTargets::default();
+        self.dispatch_event(&targets);
+
+        if let Some(target) = targets.requested_target() {
+            self.dispatch_event(&MoveFocus::new(target));
+        }
+    }
+
+    /// Moves the keyboard focus to the next focusable element of the window.
+    ///
+    /// See [`CollectFocusTargets::next_target`] for the order in which elements are visited.
+    pub fn move_focus(self: &Rc<Self>, reverse: bool) {
+        let targets = CollectFocusTargets::default();
+        self.dispatch_event(&targets);
+
+        if let Some(target) = targets.next_target(reverse) {
+            self.dispatch_event(&MoveFocus::new(target));
+        }
     }
 
     pub fn dispatch_pending_events(self: &Rc<Self>) {
@@ -201,9 +778,10 @@ impl WindowInner {
         let mut pending_events = std::mem::take(&mut *self.proxy.pending_events.lock());
         self.with_root_element(|elem| {
             for event in pending_events.drain(..) {
-                elem.event(&elem_context, event.as_ref());
+                self.dispatch_event_to(elem, &elem_context, event.as_ref());
             }
         });
+        self.apply_focus_request();
 
         // If no new events have been added to the pending list, just re-use the previous
         // allocation.
@@ -213,11 +791,33 @@ impl WindowInner {
         }
     }
 
-    /// Renders the provided scene to this window.
+    /// Renders the provided scene to an image with the size and clear color of this window.
     #[inline]
+    pub fn render_scene_to_image(
+        &self,
+        renderer: &mut Renderer,
+        scene: &vello::Scene,
+    ) -> Result<ImageBuffer, CaptureError> {
+        renderer.render_to_image(scene, self.surface.cached_size(), self.surface.base_color())
+    }
+
+    /// Renders the provided scene to this window.
     pub fn render_scene(&self, renderer: &mut Renderer, scene: &vello::Scene) {
-        self.surface
-            .render(self.proxy.window.as_ref(), renderer, scene);
+        // The hook may replace or clear itself while it runs.
+        self.render_hook.call(|render_hook| {
+            self.surface.render(
+                self.proxy.window.as_ref(),
+                renderer,
+                scene,
+                render_hook.as_deref_mut(),
+            );
+        });
+    }
+
+    /// Sets the function called after the UI has been rendered to the window's surface.
+    #[inline]
+    pub fn set_render_hook(&self, render_hook: Option<RenderHook>) {
+        self.render_hook.set(render_hook);
     }
 
     /// Notifies the window that it has been resized.
@@ -257,6 +857,12 @@ impl WindowInner {
         self.surface.set_present_mode(present_mode);
     }
 
+    /// Returns the present mode that is actually used by the window.
+    #[inline]
+    pub fn effective_present_mode(&self) -> wgpu::PresentMode {
+        self.surface.effective_present_mode()
+    }
+
     /// Sets the base (clear) color of the window.
     #[inline]
     pub fn set_base_color(&self, base_color: peniko::Color) {
@@ -291,6 +897,89 @@ impl WindowInner {
         self.last_pointer_position.get()
     }
 
+    /// Sets the paths of the files currently being dragged over the window.
+    #[inline]
+    pub fn set_hovered_files(&self, paths: Option<Rc<[PathBuf]>>) {
+        *self.hovered_files.borrow_mut() = paths;
+    }
+
+    /// Returns the paths of the files currently being dragged over the window, if any.
+    #[inline]
+    pub fn hovered_files(&self) -> Option<Rc<[PathBuf]>> {
+        self.hovered_files.borrow().clone()
+    }
+
+    /// Dispatches a [`FileHovered`] event for the files currently being dragged over the
+    /// window, and updates the cursor to reflect whether an element accepted them.
+    ///
+    /// This does nothing if no files are being dragged over the window.
+    pub fn dispatch_file_hover(self: &Rc<Self>, position: Point) {
+        let Some(paths) = self.hovered_files() else {
+            return;
+        };
+
+        let event = FileHovered::new(paths, position);
+        self.dispatch_event(&event);
+
+        let cursor = if event.is_accepted() {
+            CursorIcon::Copy
+        } else {
+            CursorIcon::NotAllowed
+        };
+        self.proxy.winit_window().set_cursor(cursor.into());
+    }
+
+    /// Forgets about the files being dragged over the window, and restores the cursor.
+    ///
+    /// This is called when the files are dropped, or when they leave the window.
+    pub fn end_file_hover(&self) {
+        self.set_hovered_files(None);
+        self.proxy
+            .winit_window()
+            .set_cursor(CursorIcon::Default.into());
+    }
+
+    /// Records a press or a release of a pointer button, and returns the number of consecutive
+    /// clicks it is part of.
+    ///
+    /// Presses of the same button that happen within the double-click interval of the previous
+    /// press, and close enough to it, increment the count. Releases report the count of the
+    /// press that preceded them.
+    pub fn count_click(&self, button: ButtonSource, position: Point, pressed: bool) -> u32 {
+        let mut last_click = self.last_click.borrow_mut();
+
+        if !pressed {
+            return match last_click.as_ref() {
+                Some(last) if last.button == button => last.count,
+                _ => 1,
+            };
+        }
+
+        let settings = self.ctx.click_settings();
+        let now = Instant::now();
+        let max_distance = settings.distance * self.scale_factor.get();
+
+        let count = match last_click.as_ref() {
+            Some(last)
+                if last.button == button
+                    && now.duration_since(last.time) <= settings.interval
+                    && last.position.distance(position) <= max_distance =>
+            {
+                last.count.saturating_add(1)
+            }
+            _ => 1,
+        };
+
+        *last_click = Some(LastClick {
+            button,
+            time: now,
+            position,
+            count,
+        });
+
+        count
+    }
+
     /// Returns the window's size.
     #[inline]
     pub fn cached_size(&self) -> PhysicalSize<u32> {
@@ -303,3 +992,66 @@ impl WindowInner {
         &self.proxy
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use {super::HookSlot, std::rc::Rc};
+
+    type TestHook = Box<dyn FnMut()>;
+
+    /// Runs the hook stored in the provided slot, if any.
+    fn run(slot: &HookSlot<TestHook>) {
+        slot.call(|hook| {
+            if let Some(hook) = hook {
+                hook();
+            }
+        });
+    }
+
+    /// Returns whether the provided slot contains a hook.
+    fn is_set(slot: &HookSlot<TestHook>) -> bool {
+        slot.call(|hook| hook.is_some())
+    }
+
+    #[test]
+    fn hook_is_restored_after_running() {
+        let slot = HookSlot::<TestHook>::new();
+        slot.set(Some(Box::new(|| ())));
+
+        run(&slot);
+
+        assert!(is_set(&slot));
+    }
+
+    #[test]
+    fn hook_can_clear_itself() {
+        let slot = Rc::new(HookSlot::<TestHook>::new());
+        let weak = Rc::downgrade(&slot);
+        slot.set(Some(Box::new(move || weak.upgrade().unwrap().set(None))));
+
+        run(&slot);
+
+        assert!(!is_set(&slot));
+    }
+
+    #[test]
+    fn hook_can_replace_itself() {
+        let slot = Rc::new(HookSlot::<TestHook>::new());
+        let replaced = Rc::new(std::cell::Cell::new(false));
+
+        let weak = Rc::downgrade(&slot);
+        let flag = replaced.clone();
+        slot.set(Some(Box::new(move || {
+            let flag = flag.clone();
+            weak.upgrade()
+                .unwrap()
+                .set(Some(Box::new(move || flag.set(true))));
+        })));
+
+        run(&slot);
+        assert!(!replaced.get());
+
+        run(&slot);
+        assert!(replaced.get());
+    }
+}
@@ -0,0 +1,192 @@
+use {
+    crate::private::CtxInner,
+    slotmap::new_key_type,
+    std::{fmt::Display, mem::ManuallyDrop, rc::Weak},
+    winit::keyboard::{Key, ModifiersState},
+};
+
+new_key_type! {
+    /// A key that is used to identify a keyboard shortcut within the application.
+    pub(crate) struct ShortcutId;
+}
+
+/// A combination of keyboard modifiers and a key that triggers an application-level action.
+///
+/// Shortcuts are registered with [`Ctx::register_shortcut`](crate::Ctx::register_shortcut).
+///
+/// # Matching
+///
+/// A shortcut matches a key press when the pressed modifiers are exactly the ones of the
+/// shortcut, and when the logical key is the same. Character keys are compared without regard
+/// to their case, which means that `Ctrl+Shift+S` can be written with `"s"` even though the key
+/// produces an uppercase letter.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Shortcut {
+    /// The modifiers that must be held for the shortcut to trigger.
+    modifiers: ModifiersState,
+    /// The key that triggers the shortcut.
+    ///
+    /// Character keys are stored in lowercase.
+    key: Key,
+}
+
+impl Shortcut {
+    /// Creates a new [`Shortcut`].
+    ///
+    /// # Parameters
+    ///
+    /// - `modifiers`: The modifiers that must be held, such as
+    ///   `ModifiersState::CONTROL | ModifiersState::SHIFT`.
+    ///
+    /// - `key`: The key that triggers the shortcut, such as `Key::Character("s".into())` or
+    ///   `NamedKey::Escape`.
+    pub fn new(modifiers: ModifiersState, key: impl Into<Key>) -> Self {
+        Self {
+            modifiers,
+            key: normalize_key(key.into()),
+        }
+    }
+
+    /// Creates a new [`Shortcut`] that uses the platform's primary modifier.
+    ///
+    /// This is Cmd on macOS, and Ctrl everywhere else.
+    pub fn primary(key: impl Into<Key>) -> Self {
+        Self::new(primary_modifier(), key)
+    }
+
+    /// Adds Shift to the modifiers of the shortcut.
+    pub fn with_shift(mut self) -> Self {
+        self.modifiers |= ModifiersState::SHIFT;
+        self
+    }
+
+    /// Adds Alt (Option on macOS) to the modifiers of the shortcut.
+    pub fn with_alt(mut self) -> Self {
+        self.modifiers |= ModifiersState::ALT;
+        self
+    }
+
+    /// Returns the modifiers that must be held for the shortcut to trigger.
+    #[inline]
+    pub fn modifiers(&self) -> ModifiersState {
+        self.modifiers
+    }
+
+    /// Returns the key that triggers the shortcut.
+    #[inline]
+    pub fn key(&self) -> &Key {
+        &self.key
+    }
+
+    /// Returns whether the provided key, pressed with the provided modifiers, triggers this
+    /// shortcut.
+    pub fn matches(&self, key: &Key, modifiers: ModifiersState) -> bool {
+        if modifiers != self.modifiers {
+            return false;
+        }
+
+        match (key, &self.key) {
+            (Key::Character(pressed), Key::Character(expected)) => {
+                pressed.to_lowercase() == expected.as_str()
+            }
+            (pressed, expected) => pressed == expected,
+        }
+    }
+}
+
+impl Display for Shortcut {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names = [
+            (ModifiersState::CONTROL, "Ctrl"),
+            (ModifiersState::ALT, "Alt"),
+            (ModifiersState::SHIFT, "Shift"),
+            (
+                ModifiersState::SUPER,
+                if cfg!(target_os = "macos") {
+                    "Cmd"
+                } else {
+                    "Super"
+                },
+            ),
+        ];
+
+        for (modifier, name) in names {
+            if self.modifiers.contains(modifier) {
+                write!(f, "{name}+")?;
+            }
+        }
+
+        match &self.key {
+            Key::Character(c) => f.write_str(&c.to_uppercase()),
+            Key::Named(named) => write!(f, "{named:?}"),
+            key => write!(f, "{key:?}"),
+        }
+    }
+}
+
+/// Returns the platform's primary modifier, used by most shortcuts.
+///
+/// This is Cmd on macOS, and Ctrl everywhere else.
+pub fn primary_modifier() -> ModifiersState {
+    if cfg!(target_os = "macos") {
+        ModifiersState::SUPER
+    } else {
+        ModifiersState::CONTROL
+    }
+}
+
+/// Turns character keys into lowercase so that they can be compared regardless of Shift.
+fn normalize_key(key: Key) -> Key {
+    match key {
+        Key::Character(c) if c.chars().any(char::is_uppercase) => {
+            Key::Character(c.to_lowercase().into())
+        }
+        key => key,
+    }
+}
+
+/// The error returned when registering a [`Shortcut`] that is already registered.
+#[derive(Clone, Debug)]
+pub struct ShortcutConflict(pub Shortcut);
+
+impl Display for ShortcutConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "The shortcut `{}` is already registered", self.0)
+    }
+}
+
+impl std::error::Error for ShortcutConflict {}
+
+/// A registered keyboard shortcut.
+///
+/// The shortcut is unregistered when this value is dropped. Use [`detach`](Self::detach) to keep
+/// it registered for the rest of the application's lifetime.
+#[must_use = "the shortcut is unregistered when the handle is dropped"]
+pub struct ShortcutHandle {
+    /// The context in which the shortcut is registered.
+    pub(crate) ctx: Weak<CtxInner>,
+    /// The ID of the shortcut within the context.
+    pub(crate) id: ShortcutId,
+}
+
+impl ShortcutHandle {
+    /// Keeps the shortcut registered for the rest of the application's lifetime.
+    #[inline]
+    pub fn detach(self) {
+        let _ = ManuallyDrop::new(self);
+    }
+}
+
+impl Drop for ShortcutHandle {
+    fn drop(&mut self) {
+        if let Some(ctx) = self.ctx.upgrade() {
+            ctx.unregister_shortcut(self.id);
+        }
+    }
+}
+
+impl std::fmt::Debug for ShortcutHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ShortcutHandle { ... }")
+    }
+}
@@ -0,0 +1,59 @@
+//! Helpers shared by the unit tests of the crate.
+
+use {
+    crate::{ElemContext, Element, LayoutContext, SizeHint},
+    std::{cell::Cell, rc::Rc},
+    vello::kurbo::{Point, Rect, Size},
+};
+
+/// An element with a fixed preferred size that records where it was placed.
+pub struct Probe {
+    /// The preferred size of the element.
+    size: Size,
+    /// The rectangle the element was last placed in.
+    rect: Rc<Cell<Rect>>,
+}
+
+impl Probe {
+    /// Creates a new [`Probe`] element with the provided preferred size.
+    ///
+    /// # Returns
+    ///
+    /// The element, and a handle to the rectangle it was last placed in.
+    pub fn new(width: f64, height: f64) -> (Self, Rc<Cell<Rect>>) {
+        let rect = Rc::new(Cell::new(Rect::ZERO));
+        let probe = Self {
+            size: Size::new(width, height),
+            rect: rect.clone(),
+        };
+        (probe, rect)
+    }
+}
+
+impl Element for Probe {
+    fn size_hint(
+        &mut self,
+        _elem_context: &ElemContext,
+        _layout_context: LayoutContext,
+        _space: Size,
+    ) -> SizeHint {
+        SizeHint {
+            preferred: self.size,
+            ..SizeHint::default()
+        }
+    }
+
+    fn place(
+        &mut self,
+        _elem_context: &ElemContext,
+        _layout_context: LayoutContext,
+        pos: Point,
+        size: Size,
+    ) {
+        self.rect.set(Rect::from_origin_size(pos, size));
+    }
+
+    fn hit_test(&self, point: Point) -> bool {
+        self.rect.get().contains(point)
+    }
+}
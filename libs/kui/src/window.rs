@@ -1,6 +1,8 @@
 use {
     crate::{
-        element::Element,
+        CaptureError, ImageBuffer, RenderHookContext,
+        element::{Element, Scalable},
+        elements::layer::Layer,
         event::Event,
         private::{WindowInner, WindowProxyInner},
     },
@@ -65,6 +67,10 @@ impl WindowProxy {
     }
 }
 
+/// Identifies a popup opened with [`Window::open_popup`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PopupId(pub(crate) u64);
+
 /// A window that is managed by the application.
 ///
 /// # Remarks
@@ -94,6 +100,63 @@ impl Window {
             .expect("Attempted to use a `Window` after it has been closed")
     }
 
+    /// Calls the provided function with the scene associated with an elevated layer.
+    ///
+    /// Returns `false` without calling the function if the window is not available, if `layer`
+    /// is the base layer, or if the layer is already being drawn to.
+    pub(crate) fn with_layer_scene(
+        &self,
+        layer: Layer,
+        f: &mut dyn FnMut(&mut vello::Scene),
+    ) -> bool {
+        match self.0.upgrade() {
+            Some(inner) => inner.with_layer_scene(layer, f),
+            None => false,
+        }
+    }
+
+    /// Returns whether the provided layer contained at least one element during the last frame.
+    pub(crate) fn is_layer_active(&self, layer: Layer) -> bool {
+        match self.0.upgrade() {
+            Some(inner) => inner.is_layer_active(layer),
+            None => false,
+        }
+    }
+
+    /// Calls the provided function within the scope of a [`Focusable`] element.
+    ///
+    /// The function is still called if the window is not available.
+    ///
+    /// [`Focusable`]: crate::elements::focusable::Focusable
+    pub(crate) fn with_focus_scope<R>(&self, focused: bool, f: impl FnOnce() -> R) -> R {
+        match self.0.upgrade() {
+            Some(inner) => inner.with_focus_scope(focused, f),
+            None => f(),
+        }
+    }
+
+    /// Returns whether the closest [`Focusable`] ancestor of the element currently being
+    /// visited has the focus.
+    ///
+    /// [`Focusable`]: crate::elements::focusable::Focusable
+    pub(crate) fn in_focus_scope(&self) -> bool {
+        self.0.upgrade().is_some_and(|inner| inner.in_focus_scope())
+    }
+
+    /// Requests the focus on behalf of the element currently being visited.
+    pub(crate) fn request_focus(&self) {
+        if let Some(inner) = self.0.upgrade() {
+            inner.request_focus();
+        }
+    }
+
+    /// Claims the pending focus request, if any.
+    pub(crate) fn claim_focus_request(&self) -> bool {
+        self.0
+            .upgrade()
+            .is_some_and(|inner| inner.claim_focus_request())
+    }
+
     /// Closes the window.
     #[track_caller]
     pub fn close(&self) {
@@ -116,15 +179,150 @@ impl Window {
     }
 
     /// Sets whether the window should use V-Sync or not.
+    ///
+    /// This is a shorthand for [`set_present_mode`](Self::set_present_mode) with
+    /// [`AutoVsync`](wgpu::PresentMode::AutoVsync) or
+    /// [`AutoNoVsync`](wgpu::PresentMode::AutoNoVsync).
     #[track_caller]
     pub fn set_vsync(&self, vsync: bool) {
-        self.inner().set_present_mode(if vsync {
+        self.set_present_mode(if vsync {
             wgpu::PresentMode::AutoVsync
         } else {
             wgpu::PresentMode::AutoNoVsync
         });
     }
 
+    /// Sets the present mode used when presenting frames to the window.
+    ///
+    /// The surface is reconfigured the next time the window is rendered.
+    ///
+    /// # Present modes
+    ///
+    /// - [`Fifo`](wgpu::PresentMode::Fifo): Frames are synchronized with the display's refresh
+    ///   rate (V-Sync). No tearing, lowest power usage, but the highest latency. This is the only
+    ///   mode that is guaranteed to be supported everywhere.
+    ///
+    /// - [`Mailbox`](wgpu::PresentMode::Mailbox): Frames are presented on the vertical blank, but
+    ///   new frames replace the pending one instead of waiting. No tearing and low latency, at the
+    ///   cost of rendering frames that might never be displayed.
+    ///
+    /// - [`Immediate`](wgpu::PresentMode::Immediate): Frames are presented as soon as they are
+    ///   ready. Lowest latency, but tearing may occur and power usage is higher.
+    ///
+    /// # Remarks
+    ///
+    /// If the requested mode is not supported by the GPU, the window falls back to the closest
+    /// supported mode (`Mailbox` and `Immediate` fall back to one another, and eventually to
+    /// [`AutoNoVsync`](wgpu::PresentMode::AutoNoVsync)). The mode actually in use can be queried
+    /// with [`present_mode`](Self::present_mode).
+    #[track_caller]
+    pub fn set_present_mode(&self, present_mode: wgpu::PresentMode) {
+        self.inner().set_present_mode(present_mode);
+        self.request_redraw();
+    }
+
+    /// Returns the present mode that is currently used by the window.
+    ///
+    /// This might differ from the mode requested with [`set_present_mode`](Self::set_present_mode)
+    /// if it was not supported, or if the window has not been rendered since the mode changed.
+    #[track_caller]
+    pub fn present_mode(&self) -> wgpu::PresentMode {
+        self.inner().effective_present_mode()
+    }
+
+    /// Sets a function that is called every time the window is rendered, after the UI has been
+    /// drawn to the window's surface but before the frame is presented.
+    ///
+    /// This can be used to run custom `wgpu` render passes on top of the UI, using the device and
+    /// queue provided in the [`RenderHookContext`].
+    ///
+    /// # Remarks
+    ///
+    /// The commands submitted by the UI renderer are always submitted before the hook is called,
+    /// meaning that custom passes submitted by the hook are executed after them. The target
+    /// texture view is only valid for the duration of the call.
+    ///
+    /// The hook must not attempt to access the GPU through [`Ctx::with_gpu`] or the function
+    /// will panic.
+    ///
+    /// [`Ctx::with_gpu`]: crate::Ctx::with_gpu
+    #[track_caller]
+    pub fn set_render_hook(&self, hook: impl 'static + FnMut(&RenderHookContext)) {
+        self.inner().set_render_hook(Some(Box::new(hook)));
+    }
+
+    /// Removes the render hook previously set with [`set_render_hook`](Self::set_render_hook).
+    #[track_caller]
+    pub fn clear_render_hook(&self) {
+        self.inner().set_render_hook(None);
+    }
+
+    /// Sets the function that receives the accessibility tree of the window.
+    ///
+    /// The tree is rebuilt and passed to the handler every time the window is drawn. The window
+    /// already exposes it to the platform's accessibility API, so this is only needed to
+    /// inspect the tree (for example in tests).
+    ///
+    /// See the [`accessibility`](crate::accessibility) module for more information.
+    #[track_caller]
+    pub fn set_accessibility_handler(&self, handler: impl 'static + FnMut(accesskit::TreeUpdate)) {
+        self.inner()
+            .set_accessibility_handler(Some(Box::new(handler)));
+    }
+
+    /// Removes the handler previously set with
+    /// [`set_accessibility_handler`](Self::set_accessibility_handler).
+    #[track_caller]
+    pub fn clear_accessibility_handler(&self) {
+        self.inner().set_accessibility_handler(None);
+    }
+
+    /// Sets a function that is called when the user requests the window to be closed (for
+    /// example by clicking the close button of its title bar).
+    ///
+    /// The window is still open when the function is called, which makes it a good place to save
+    /// state associated with the window, such as its position and size. The application exits
+    /// right after the function returns.
+    #[track_caller]
+    pub fn set_close_handler(&self, handler: impl 'static + FnMut()) {
+        self.inner().set_close_handler(Some(Box::new(handler)));
+    }
+
+    /// Removes the handler previously set with [`set_close_handler`](Self::set_close_handler).
+    #[track_caller]
+    pub fn clear_close_handler(&self) {
+        self.inner().set_close_handler(None);
+    }
+
+    /// Builds the accessibility tree of the window.
+    ///
+    /// # Remarks
+    ///
+    /// Calling this function from within one of the window's elements returns an empty tree
+    /// because the element tree is in use at that point.
+    #[track_caller]
+    pub fn accessibility_tree(&self) -> accesskit::TreeUpdate {
+        self.inner().accessibility_tree()
+    }
+
+    /// Captures the current content of the window.
+    ///
+    /// The UI tree of the window is drawn and rendered to an offscreen texture, which is then
+    /// copied back to CPU memory. The returned image has the same size as the window's surface.
+    ///
+    /// # Remarks
+    ///
+    /// This function blocks until the GPU has finished rendering the frame.
+    ///
+    /// Calling this function from within one of the window's elements (for example while
+    /// handling an event) won't draw that element tree because it is in use at that point.
+    #[track_caller]
+    pub fn capture_frame(&self) -> Result<ImageBuffer, CaptureError> {
+        let inner = self.inner();
+        let id = inner.proxy().winit_window().id();
+        inner.ctx().capture_window(id)
+    }
+
     /// Requests a redraw of the window.
     #[track_caller]
     pub fn request_redraw(&self) {
@@ -150,20 +348,79 @@ impl Window {
         self.set_root_element_boxed(Box::new(elem));
     }
 
+    /// Opens a popup displaying the provided element on top of everything else in the window.
+    ///
+    /// The element takes its preferred size, and its top-left corner is placed at `position`
+    /// (in physical pixels). It is shifted when needed so that it stays within the window.
+    ///
+    /// Popups receive events before the root element. Pointer events that happen over a popup
+    /// do not reach the elements below it. Popups are closed when a pointer button is pressed
+    /// outside of them, and the topmost popup is closed when Escape is pressed.
+    ///
+    /// Opening a popup while another one is open stacks it on top (for example, to display a
+    /// submenu). Closing a popup also closes the popups stacked on top of it.
+    #[track_caller]
+    pub fn open_popup(&self, position: Point, elem: impl 'static + Element) -> PopupId {
+        self.open_popup_boxed(position, Box::new(elem))
+    }
+
+    /// Opens a popup displaying the provided boxed element.
+    ///
+    /// See [`open_popup`](Self::open_popup).
+    #[track_caller]
+    pub fn open_popup_boxed(&self, position: Point, elem: Box<dyn Element>) -> PopupId {
+        self.inner().open_popup(position, elem)
+    }
+
+    /// Closes the provided popup, along with the popups stacked on top of it.
+    ///
+    /// Nothing happens if the popup is already closed.
+    #[track_caller]
+    pub fn close_popup(&self, id: PopupId) {
+        self.inner().close_popup(id);
+    }
+
+    /// Closes all the popups of the window.
+    #[track_caller]
+    pub fn close_all_popups(&self) {
+        self.inner().close_all_popups();
+    }
+
     /// Returns the scale factor of the window.
     #[track_caller]
     pub fn scale_factor(&self) -> f64 {
         self.inner().scale_factor()
     }
 
-    /// Returns the size of the window.
+    /// Converts a value expressed in logical pixels to physical pixels, using the current scale
+    /// factor of the window.
+    ///
+    /// See [`LayoutContext`](crate::LayoutContext) for more information about the coordinate
+    /// spaces used by the library.
+    #[track_caller]
+    pub fn logical_to_physical<T: Scalable>(&self, value: T) -> T {
+        value.scaled(self.scale_factor())
+    }
+
+    /// Converts a value expressed in physical pixels to logical pixels, using the current scale
+    /// factor of the window.
+    ///
+    /// See [`LayoutContext`](crate::LayoutContext) for more information about the coordinate
+    /// spaces used by the library.
+    #[track_caller]
+    pub fn physical_to_logical<T: Scalable>(&self, value: T) -> T {
+        value.scaled(self.scale_factor().recip())
+    }
+
+    /// Returns the size of the window, in physical pixels.
     #[track_caller]
     pub fn size(&self) -> Size {
         let cached_size = self.inner().cached_size();
         Size::new(cached_size.width as f64, cached_size.height as f64)
     }
 
-    /// Returns the last known position of the pointer over the window's client area.
+    /// Returns the last known position of the pointer over the window's client area, in
+    /// physical pixels.
     #[track_caller]
     pub fn pointer_position(&self) -> Point {
         let pos = self.inner().last_pointer_position();